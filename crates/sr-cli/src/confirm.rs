@@ -0,0 +1,328 @@
+//! Interactive confirmation before `sr release` pushes anything, and of what
+//! `sr init` detected.
+//!
+//! Only used when both stdin and stdout are terminals and `--yes` is not
+//! passed; the input and output streams are injected so the prompt can be
+//! driven from tests.
+
+use std::io::{self, BufRead, Write};
+
+use sr_core::commit::CommitClassifier;
+use sr_core::config::{DetectedDefaults, ReleaseConfig};
+use sr_core::dry_run::DryRunReport;
+use sr_core::release::ReleasePlan;
+use sr_core::version::BumpLevel;
+
+use crate::style::Styles;
+
+/// Why the plan bumps the way it does, e.g. `2 breaking change(s): feat(api)!: drop v1`.
+pub fn bump_reason(plan: &ReleasePlan, classifier: &dyn CommitClassifier) -> String {
+    if plan.forced {
+        return "forced re-release of the current tag".into();
+    }
+    let breaking: Vec<_> = plan.commits.iter().filter(|c| c.breaking).collect();
+    let (label, drivers) = if plan.bump == BumpLevel::Major || !breaking.is_empty() {
+        ("breaking change(s)", breaking)
+    } else {
+        let drivers = plan
+            .commits
+            .iter()
+            .filter(|c| classifier.commit_bump(c) == Some(plan.bump))
+            .collect();
+        ("commit(s)", drivers)
+    };
+    let Some(first) = drivers.first() else {
+        return format!("{} bump", plan.bump);
+    };
+    let scope = first
+        .scope
+        .as_deref()
+        .map(|s| format!("({s})"))
+        .unwrap_or_default();
+    let bang = if first.breaking { "!" } else { "" };
+    let mut reason = format!(
+        "{} {label}: {}{scope}{bang}: {}",
+        drivers.len(),
+        first.r#type,
+        first.description
+    );
+    if drivers.len() > 1 {
+        reason.push_str(", ...");
+    }
+    if plan.bump != BumpLevel::Major && label.starts_with("breaking") {
+        reason.push_str(" (v0 protection: minor instead of major)");
+    }
+    reason
+}
+
+/// Summary shown before the prompt: what would be released and where.
+pub fn release_summary(
+    report: &DryRunReport,
+    config: &ReleaseConfig,
+    reason: &str,
+    s: &Styles,
+) -> String {
+    let plan = &report.plan;
+    let current = plan
+        .current_version
+        .as_ref()
+        .map(|v| config.format_version(v))
+        .unwrap_or_else(|| "none".into());
+    let mut lines = vec![
+        format!(
+            "Release {} ({current} -> {})",
+            s.bold(&plan.tag_name),
+            s.added(config.format_version(&plan.next_version))
+        ),
+        format!("  bump:      {} ({reason})", s.bump(plan.bump)),
+        format!("  commits:   {}", plan.commits.len()),
+    ];
+    if !report.version_files.is_empty() {
+        let files: Vec<_> = report
+            .version_files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        lines.push(format!("  bumps:     {}", files.join(", ")));
+    }
+    if !report.artifacts.is_empty()
+        || !report.built_artifacts.is_empty()
+        || !report.attestations.is_empty()
+    {
+        let uploads: Vec<_> = report
+            .artifacts
+            .iter()
+            .map(|a| a.path.as_str())
+            .chain(report.built_artifacts.iter().map(|a| a.name()))
+            .chain(report.attestations.iter().map(|a| a.file.as_str()))
+            .collect();
+        lines.push(format!("  uploads:   {}", uploads.join(", ")));
+    }
+    let release = match (&report.release, report.no_push) {
+        (_, true) => "no (--no-push)".to_string(),
+        (Some(release), false) => {
+            let providers: Vec<_> = release
+                .providers
+                .iter()
+                .map(|p| p.provider.as_str())
+                .collect();
+            format!("yes ({})", providers.join(", "))
+        }
+        (None, false) => "no".to_string(),
+    };
+    lines.push(format!("  release:   {release}"));
+    for error in &report.errors {
+        lines.push(format!("  {}     {error}", s.danger("error:")));
+    }
+    for warning in &report.warnings {
+        lines.push(format!("  {}   {warning}", s.warning("warning:")));
+    }
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// Print `summary` and ask to proceed. Major releases must also be confirmed by
+/// typing the new version, as `config` formats it. Anything but an explicit
+/// yes declines.
+pub fn confirm(
+    summary: &str,
+    plan: &ReleasePlan,
+    config: &ReleaseConfig,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> io::Result<bool> {
+    write!(output, "{summary}\nProceed with the release? [y/N] ")?;
+    output.flush()?;
+    if !matches!(read_answer(input)?.to_lowercase().as_str(), "y" | "yes") {
+        return Ok(false);
+    }
+    if plan.bump != BumpLevel::Major {
+        return Ok(true);
+    }
+    let version = config.format_version(&plan.next_version);
+    write!(
+        output,
+        "This is a major release with breaking changes. Type {version} to confirm: "
+    )?;
+    output.flush()?;
+    let answer = read_answer(input)?;
+    Ok(answer.strip_prefix('v').unwrap_or(&answer) == version)
+}
+
+/// What `sr init` detected, one line per kind.
+pub fn detected_summary(detected: &DetectedDefaults) -> String {
+    let mut lines = vec!["Detected:".to_string()];
+    if !detected.version_files.is_empty() {
+        lines.push(format!(
+            "  version files: {}",
+            detected.version_files.join(", ")
+        ));
+    }
+    if let Some(file) = &detected.changelog_file {
+        lines.push(format!("  changelog:     {file}"));
+    }
+    if !detected.artifacts.is_empty() {
+        lines.push(format!(
+            "  artifacts:     {} (commented out)",
+            detected.artifacts.join(", ")
+        ));
+    }
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// Print `summary` and ask whether `sr init` should use what it detected.
+/// Anything but an explicit no accepts.
+pub fn confirm_detected(
+    summary: &str,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> io::Result<bool> {
+    write!(output, "{summary}Use these in the config? [Y/n] ")?;
+    output.flush()?;
+    Ok(!matches!(
+        read_answer(input)?.to_lowercase().as_str(),
+        "n" | "no"
+    ))
+}
+
+fn read_answer(input: &mut dyn BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::Version;
+    use sr_core::commit::{ConventionalCommit, DefaultCommitClassifier};
+
+    fn commit(r#type: &str, description: &str, breaking: bool) -> ConventionalCommit {
+        ConventionalCommit {
+            sha: "abc".into(),
+            r#type: r#type.into(),
+            scope: None,
+            description: description.into(),
+            body: None,
+            breaking,
+        }
+    }
+
+    fn plan(bump: BumpLevel, next: &str, commits: Vec<ConventionalCommit>) -> ReleasePlan {
+        ReleasePlan {
+            schema_version: sr_core::release::PLAN_SCHEMA_VERSION,
+            current_version: Some(Version::new(1, 2, 3)),
+            next_version: Version::parse(next).unwrap(),
+            bump,
+            commits,
+            tag_name: format!("v{next}"),
+            floating_tag_name: None,
+            prerelease: false,
+            head_sha: "abc".into(),
+            forced: false,
+            touched_paths: Vec::new(),
+            contributors: 0,
+        }
+    }
+
+    fn run(plan: &ReleasePlan, answers: &str) -> (bool, String) {
+        run_with(plan, &ReleaseConfig::default(), answers)
+    }
+
+    fn run_with(plan: &ReleasePlan, config: &ReleaseConfig, answers: &str) -> (bool, String) {
+        let mut input = io::Cursor::new(answers.as_bytes().to_vec());
+        let mut output = Vec::new();
+        let ok = confirm("summary\n", plan, config, &mut input, &mut output).unwrap();
+        (ok, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn confirm_accepts_yes() {
+        let plan = plan(BumpLevel::Minor, "1.3.0", vec![]);
+        assert!(run(&plan, "y\n").0);
+        assert!(run(&plan, "YES\n").0);
+    }
+
+    #[test]
+    fn confirm_declines_by_default() {
+        let plan = plan(BumpLevel::Minor, "1.3.0", vec![]);
+        assert!(!run(&plan, "\n").0);
+        assert!(!run(&plan, "n\n").0);
+        assert!(!run(&plan, "").0);
+    }
+
+    #[test]
+    fn major_release_requires_typing_the_version() {
+        let plan = plan(BumpLevel::Major, "2.0.0", vec![]);
+        let (ok, output) = run(&plan, "y\n2.0.0\n");
+        assert!(ok);
+        assert!(output.contains("Type 2.0.0 to confirm"));
+        assert!(run(&plan, "y\nv2.0.0\n").0);
+        assert!(!run(&plan, "y\ny\n").0);
+        assert!(!run(&plan, "y\n").0);
+    }
+
+    #[test]
+    fn major_calver_release_is_confirmed_with_the_padded_version() {
+        let config = ReleaseConfig::parse("versioning: calver\n").unwrap();
+        let plan = plan(BumpLevel::Major, "2026.1.0", vec![]);
+        let (ok, output) = run_with(&plan, &config, "y\n2026.01.0\n");
+        assert!(ok);
+        assert!(output.contains("Type 2026.01.0 to confirm"), "{output}");
+        assert!(!run_with(&plan, &config, "y\n2026.1.0\n").0);
+    }
+
+    #[test]
+    fn detected_defaults_are_accepted_unless_declined() {
+        let detected = DetectedDefaults {
+            version_files: vec!["Cargo.toml".into(), "package.json".into()],
+            artifacts: vec!["dist/*".into()],
+            changelog_file: Some("CHANGELOG.md".into()),
+        };
+        let summary = detected_summary(&detected);
+        assert_eq!(
+            summary,
+            "Detected:\n  version files: Cargo.toml, package.json\n  \
+             changelog:     CHANGELOG.md\n  artifacts:     dist/* (commented out)\n"
+        );
+
+        let answer = |answers: &str| {
+            let mut input = io::Cursor::new(answers.as_bytes().to_vec());
+            let mut output = Vec::new();
+            let ok = confirm_detected(&summary, &mut input, &mut output).unwrap();
+            assert!(String::from_utf8(output).unwrap().ends_with("[Y/n] "));
+            ok
+        };
+        assert!(answer("\n"));
+        assert!(answer("y\n"));
+        assert!(!answer("n\n"));
+        assert!(!answer("No\n"));
+    }
+
+    #[test]
+    fn bump_reason_names_the_driving_commits() {
+        let classifier = DefaultCommitClassifier::default();
+
+        let minor = plan(
+            BumpLevel::Minor,
+            "1.3.0",
+            vec![
+                commit("fix", "a bug", false),
+                commit("feat", "login", false),
+            ],
+        );
+        assert_eq!(bump_reason(&minor, &classifier), "1 commit(s): feat: login");
+
+        let major = plan(
+            BumpLevel::Major,
+            "2.0.0",
+            vec![
+                commit("feat", "drop v1", true),
+                commit("fix", "remove flag", true),
+            ],
+        );
+        assert_eq!(
+            bump_reason(&major, &classifier),
+            "2 breaking change(s): feat!: drop v1, ..."
+        );
+    }
+}
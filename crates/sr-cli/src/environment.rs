@@ -0,0 +1,114 @@
+//! What sr can tell about where it runs: a CI service, or a person at a terminal.
+//!
+//! `require_ci` and anything else that behaves differently under CI read the
+//! same variables through [`detect_ci`].
+
+use std::io::IsTerminal;
+
+/// Variables CI services set, with the service each names. `CI` comes first:
+/// most services set it, including GitHub Actions and GitLab CI.
+pub const CI_VARIABLES: &[(&str, &str)] = &[
+    ("CI", "CI"),
+    ("GITHUB_ACTIONS", "GitHub Actions"),
+    ("GITLAB_CI", "GitLab CI"),
+    ("BUILDKITE", "Buildkite"),
+    ("CIRCLECI", "CircleCI"),
+    ("TF_BUILD", "Azure Pipelines"),
+    ("JENKINS_URL", "Jenkins"),
+    ("TEAMCITY_VERSION", "TeamCity"),
+    ("BITBUCKET_BUILD_NUMBER", "Bitbucket Pipelines"),
+];
+
+/// A detected CI service and the variable that gave it away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ci {
+    pub variable: &'static str,
+    pub name: &'static str,
+}
+
+/// The CI service sr runs under, if any.
+pub fn detect_ci() -> Option<Ci> {
+    detect_ci_from(|name| std::env::var(name).ok())
+}
+
+/// [`CI_VARIABLES`] names, comma-separated, for messages.
+pub fn ci_variable_names() -> String {
+    CI_VARIABLES
+        .iter()
+        .map(|(variable, _)| *variable)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether a person is at the terminal to answer prompts.
+pub fn interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// The first of [`CI_VARIABLES`] that `var` reports as set. Empty, `false`,
+/// and `0` count as unset, so `CI=false` turns detection off.
+fn detect_ci_from(var: impl Fn(&str) -> Option<String>) -> Option<Ci> {
+    CI_VARIABLES
+        .iter()
+        .find(|(variable, _)| {
+            var(variable).is_some_and(|value| {
+                let value = value.trim();
+                !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false")
+            })
+        })
+        .map(|&(variable, name)| Ci { variable, name })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(vars: &[(&str, &str)]) -> Option<Ci> {
+        detect_ci_from(|name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn detects_ci_services() {
+        assert_eq!(detect(&[]), None);
+        assert_eq!(
+            detect(&[("GITLAB_CI", "true")]),
+            Some(Ci {
+                variable: "GITLAB_CI",
+                name: "GitLab CI"
+            })
+        );
+        assert_eq!(
+            detect(&[("GITHUB_ACTIONS", "true"), ("CI", "true")]).map(|ci| ci.variable),
+            Some("CI"),
+            "CI is checked first"
+        );
+        assert_eq!(
+            detect(&[("JENKINS_URL", "https://ci.example.com/")]).map(|ci| ci.name),
+            Some("Jenkins")
+        );
+    }
+
+    #[test]
+    fn false_and_empty_values_are_not_ci() {
+        assert_eq!(detect(&[("CI", "false")]), None);
+        assert_eq!(detect(&[("CI", "0"), ("GITHUB_ACTIONS", "")]), None);
+        assert_eq!(
+            detect(&[("CI", "FALSE"), ("BUILDKITE", "true")]).map(|ci| ci.variable),
+            Some("BUILDKITE")
+        );
+    }
+
+    #[test]
+    fn variable_names_list_every_variable() {
+        let names = ci_variable_names();
+        assert!(
+            names.starts_with("CI, GITHUB_ACTIONS, GITLAB_CI"),
+            "{names}"
+        );
+        assert_eq!(names.split(", ").count(), CI_VARIABLES.len());
+    }
+}
@@ -0,0 +1,70 @@
+//! Log output for the CLI: every `tracing` event from sr-core, sr-git, and
+//! sr-github goes to stderr, filtered by `-v`/`-q` and shaped by `--log-format`.
+
+use std::fmt;
+
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::style::Styles;
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Plain messages, prefixed with `warning:`/`error:` where relevant
+    Human,
+    /// One JSON object per event, for CI log ingestion
+    Json,
+}
+
+/// Install the global subscriber. `-q` keeps warnings and errors, `-qq` only
+/// errors; `-v` adds debug events (git commands, HTTP requests, release steps)
+/// and `-vv` adds trace events (command output).
+pub fn init(verbose: u8, quiet: u8, format: LogFormat, styles: Styles) {
+    let level = match (quiet, verbose) {
+        (0, 0) => LevelFilter::INFO,
+        (0, 1) => LevelFilter::DEBUG,
+        (0, _) => LevelFilter::TRACE,
+        (1, _) => LevelFilter::WARN,
+        _ => LevelFilter::ERROR,
+    };
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr);
+    match format {
+        LogFormat::Human => builder.event_format(HumanFormat { styles }).init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+/// Renders events the way sr has always printed them: info events as bare
+/// messages, warnings and errors with a (colored) `warning:`/`error:` prefix.
+struct HumanFormat {
+    styles: Styles,
+}
+
+impl<S, N> FormatEvent<S, N> for HumanFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let s = &self.styles;
+        match *event.metadata().level() {
+            Level::ERROR => write!(writer, "{} ", s.danger("error:"))?,
+            Level::WARN => write!(writer, "{} ", s.warning("warning:"))?,
+            Level::INFO => {}
+            Level::DEBUG => write!(writer, "{} ", s.dim("debug:"))?,
+            Level::TRACE => write!(writer, "{} ", s.dim("trace:"))?,
+        }
+        ctx.field_format().format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
@@ -1,15 +1,29 @@
-use std::path::Path;
+mod confirm;
+mod environment;
+mod logging;
+mod report;
+mod style;
+mod workflow;
+
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use clap::{CommandFactory, Parser, Subcommand};
+use logging::LogFormat;
 use sr_ai::ai::{Backend, BackendConfig};
-use sr_core::changelog::DefaultChangelogFormatter;
-use sr_core::commit::DefaultCommitParser;
-use sr_core::config::{DEFAULT_CONFIG_FILE, LEGACY_CONFIG_FILE, ReleaseConfig};
+use sr_core::changelog::Destination;
+use sr_core::config::{
+    DEFAULT_CONFIG_FILE, DetectedDefaults, LEGACY_CONFIG_FILE, ProviderMode, ReleaseConfig,
+    TagPrefix,
+};
 use sr_core::error::ReleaseError;
-use sr_core::release::{ReleaseStrategy, TrunkReleaseStrategy, VcsProvider};
-use sr_git::NativeGitRepository;
-use sr_github::GitHubProvider;
+use sr_core::release::{ExecuteOptions, ReleasePlan, ReleaseStrategy};
+use sr_git::{AnyGitRepository, GitBackend};
+use sr_runner::{
+    Release, ReleaseBuilder, Strategy, build_providers, default_token, resolve_origin,
+};
+use style::{ColorChoice, Styles};
+use tracing::{error, info, warn};
 
 #[derive(Parser)]
 #[command(name = "sr", about = "AI-powered release engineering CLI", version)]
@@ -30,6 +44,41 @@ struct Cli {
     #[arg(long, global = true, env = "SR_DEBUG")]
     debug: bool,
 
+    /// Git backend: native (git CLI) or gix (pure Rust). Defaults to native when git is installed
+    #[arg(long, global = true, env = "SR_GIT_BACKEND")]
+    git_backend: Option<GitBackend>,
+
+    /// Run as if sr was started in PATH (like `git -C`). Relative paths in other
+    /// flags, such as --config, are taken from there
+    #[arg(long, global = true, value_name = "PATH")]
+    cwd: Option<PathBuf>,
+
+    /// Config file to use. Defaults to the nearest sr.yaml, sr.yml, .sr.yaml, .sr.yml,
+    /// sr.config.yml or .urmzd.sr.yml (then a Cargo.toml or package.json with an `sr`
+    /// section), searching upward from the current directory to the git root
+    #[arg(long, global = true, env = "SR_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Never touch the network: no provider API calls, remote `extends` configs, or tag fetches
+    #[arg(long, global = true, env = "SR_OFFLINE")]
+    offline: bool,
+
+    /// Log more: -v adds git commands, HTTP requests, and release steps; -vv adds their output
+    #[arg(long, short, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Log less: -q prints only warnings and errors, -qq only errors
+    #[arg(long, short, global = true, action = clap::ArgAction::Count)]
+    quiet: u8,
+
+    /// When to color human-readable output (NO_COLOR and CLICOLOR_FORCE apply to auto)
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    color: ColorChoice,
+
+    /// Log format on stderr
+    #[arg(long, global = true, env = "SR_LOG_FORMAT", default_value = "human")]
+    log_format: LogFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -46,11 +95,20 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
 
+        /// Dry-run output format: human-readable log on stderr, or a JSON report on stdout
+        #[arg(long, default_value = "human", requires = "dry_run")]
+        format: PlanFormat,
+
         /// Glob patterns for artifact files to upload to the release (repeatable)
         #[arg(long = "artifacts")]
         artifacts: Vec<String>,
 
         /// Re-release the current tag (use when a previous release partially failed)
+        ///
+        /// Only applies when HEAD is exactly at the latest tag: the tag is reused, the
+        /// existing remote release is updated in place rather than duplicated, and
+        /// artifacts are re-uploaded (replacing assets of the same name). Combine with
+        /// --dry-run to preview the re-release without touching the tag or the release.
         #[arg(long)]
         force: bool,
 
@@ -81,6 +139,72 @@ enum Commands {
         /// Create GitHub release as a draft (requires manual publishing)
         #[arg(long)]
         draft: bool,
+
+        /// Skip remote release creation and asset upload (same as `provider: none`)
+        #[arg(long)]
+        no_vcs: bool,
+
+        /// Release even if the current branch is not listed in `branches`
+        #[arg(long)]
+        allow_any_branch: bool,
+
+        /// Warn instead of failing when tracked files outside the release have uncommitted changes
+        #[arg(long)]
+        allow_dirty: bool,
+
+        /// Release even if HEAD moved between planning and execution
+        #[arg(long)]
+        ignore_head_drift: bool,
+
+        /// Release outside CI even when `require_ci` is set
+        #[arg(long)]
+        allow_local: bool,
+
+        /// Branch to push the release commit to when HEAD is detached (default: GITHUB_REF_NAME unless a tag triggered the run)
+        #[arg(long, value_name = "BRANCH")]
+        push_branch: Option<String>,
+
+        /// Release even when the `policy` thresholds (min_commits, min_interval) are not met
+        #[arg(long)]
+        override_policy: bool,
+
+        /// Prepare the release locally (files, commit, tag) without pushing or calling the provider
+        #[arg(long)]
+        no_push: bool,
+
+        /// Only create and push the tag and remote release: no version files, changelog, or commit
+        #[arg(long)]
+        tag_only: bool,
+
+        /// Exit with code 2 when there is nothing to release (default: exit 0)
+        #[arg(long)]
+        fail_on_no_release: bool,
+
+        /// Skip the confirmation prompt shown when running in a terminal
+        #[arg(long, short)]
+        yes: bool,
+
+        /// Execute the plan saved by `sr plan --save` instead of recomputing it (HEAD must still match)
+        #[arg(long = "plan", value_name = "FILE", conflicts_with_all = ["force", "prerelease", "ignore_head_drift"])]
+        plan_file: Option<PathBuf>,
+    },
+
+    /// Undo a release: delete its GitHub release and tag, and restore the floating tag
+    Rollback {
+        /// Release tag to roll back (e.g. v1.4.0)
+        tag: String,
+
+        /// Target a specific package in a monorepo
+        #[arg(long, short)]
+        package: Option<String>,
+
+        /// Also revert the release commit the tag points at (and push the revert)
+        #[arg(long)]
+        revert_commit: bool,
+
+        /// Preview what would happen without making changes
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Show what the next release would look like
@@ -92,6 +216,28 @@ enum Commands {
         /// Output format
         #[arg(long, default_value = "human")]
         format: PlanFormat,
+
+        /// Plan a forced re-release of the current tag when HEAD is exactly at it
+        #[arg(long)]
+        force: bool,
+
+        /// Plan as if HEAD were at this revision (tag, branch, or SHA) without checking it out
+        #[arg(long = "ref", value_name = "REV")]
+        git_ref: Option<String>,
+
+        /// Also write the plan to this file for `sr release --plan`
+        #[arg(long, value_name = "FILE")]
+        save: Option<PathBuf>,
+
+        /// Post the plan as a sticky comment on this pull request (default: the
+        /// pull request in GITHUB_REF on pull_request events)
+        #[arg(long, value_name = "NUMBER")]
+        comment_pr: Option<Option<u64>>,
+
+        /// Compare the release with the previous one: commits, contributors,
+        /// breaking changes and commits per section
+        #[arg(long)]
+        compare: bool,
     },
 
     /// Generate or preview the changelog
@@ -107,6 +253,88 @@ enum Commands {
         /// Regenerate the entire changelog from all tags
         #[arg(long)]
         regenerate: bool,
+
+        /// Only regenerate releases at or above this version (spliced into the existing file)
+        #[arg(long, requires = "regenerate", value_parser = parse_version)]
+        from_version: Option<semver::Version>,
+
+        /// Only regenerate releases at or below this version (spliced into the existing file)
+        #[arg(long, requires = "regenerate", value_parser = parse_version)]
+        to_version: Option<semver::Version>,
+
+        /// Only regenerate the N most recent releases (spliced into the existing file)
+        #[arg(long, value_name = "N", requires = "regenerate", conflicts_with_all = ["from_version", "to_version"])]
+        last: Option<usize>,
+    },
+
+    /// Print the release notes for an existing tag
+    Notes {
+        /// Tag to print notes for (default: the latest tag)
+        tag: Option<String>,
+
+        /// Target a specific package in a monorepo
+        #[arg(long, short)]
+        package: Option<String>,
+
+        /// Also create or update the remote release for the tag with these notes
+        #[arg(long)]
+        push: bool,
+    },
+
+    /// Upload files to the release of an existing tag, replacing same-name assets
+    Upload {
+        /// Globs of files to upload (default: the configured artifacts)
+        globs: Vec<String>,
+
+        /// Tag whose release to upload to (default: the latest tag)
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Target a specific package in a monorepo
+        #[arg(long, short)]
+        package: Option<String>,
+
+        /// Create the release from the annotated tag's message if it does not exist
+        #[arg(long)]
+        create_missing: bool,
+
+        /// Also upload a SHA256 .sha256 checksum for each file
+        #[arg(long)]
+        checksums: bool,
+
+        /// Show what would be uploaded without uploading
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Check the environment for problems that would break a release
+    Doctor {
+        /// Target a specific package in a monorepo
+        #[arg(long, short)]
+        package: Option<String>,
+
+        /// Output format: a pass/warn/fail table, or JSON for automation
+        #[arg(long, default_value = "human")]
+        format: PlanFormat,
+    },
+
+    /// Validate commit messages against commit_pattern and types
+    Check {
+        /// Commits to check as <from>..<to> (default: commits since the latest tag)
+        #[arg(conflicts_with_all = ["message_file", "stdin"])]
+        range: Option<String>,
+
+        /// Check the single message in this file (what git passes to a commit-msg hook)
+        #[arg(long, conflicts_with = "stdin")]
+        message_file: Option<PathBuf>,
+
+        /// Check a single message read from stdin
+        #[arg(long)]
+        stdin: bool,
+
+        /// Target a specific package in a monorepo
+        #[arg(long, short)]
+        package: Option<String>,
     },
 
     /// Show the next version
@@ -118,6 +346,31 @@ enum Commands {
         /// Print only the version number
         #[arg(long)]
         short: bool,
+
+        /// Output format. `json` prints {current, next, bump, release_needed,
+        /// tag, floating_tag} and exits 0 when there is nothing to release.
+        #[arg(long, default_value = "human", conflicts_with = "short")]
+        format: PlanFormat,
+    },
+
+    /// Show the latest released version
+    Latest {
+        /// Target a specific package in a monorepo
+        #[arg(long, short)]
+        package: Option<String>,
+
+        /// Look for tags with this prefix instead of `tag_prefix`
+        #[arg(long)]
+        prefix: Option<String>,
+
+        /// Exit non-zero unless this version is newer than the latest release
+        #[arg(long, value_parser = parse_version)]
+        check: Option<semver::Version>,
+
+        /// Output format. `json` prints {version, tag, sha, date}, all null
+        /// when no tag exists.
+        #[arg(long, default_value = "human")]
+        format: PlanFormat,
     },
 
     /// Validate and display resolved configuration
@@ -125,17 +378,45 @@ enum Commands {
         /// Show the fully resolved config with defaults applied
         #[arg(long)]
         resolved: bool,
+
+        /// Check the config file and exit non-zero listing every problem found
+        #[arg(long, conflicts_with = "resolved")]
+        validate: bool,
+
+        /// Print the JSON Schema for the config file (for editor validation and completion)
+        #[arg(long, conflicts_with_all = ["resolved", "validate"])]
+        schema: bool,
     },
 
     /// Create a default configuration file and sync git hooks
     Init {
-        /// Overwrite the config file if it already exists
+        /// Overwrite the config file if it already exists (with --hooks: replace an existing commit-msg hook)
         #[arg(long)]
         force: bool,
 
         /// Merge new default fields into existing config without overwriting customizations
         #[arg(long, conflicts_with = "force")]
         merge: bool,
+
+        /// Only install a commit-msg git hook that runs `sr check --message-file`
+        #[arg(long, conflicts_with = "merge")]
+        hooks: bool,
+
+        /// With --hooks: print the hook script instead of installing it (e.g. for husky)
+        #[arg(long, requires = "hooks")]
+        print: bool,
+
+        /// Use the detected version files, changelog and artifacts without asking
+        #[arg(long, conflicts_with_all = ["merge", "hooks"])]
+        yes: bool,
+
+        /// Write the generic default config, without inspecting the project
+        #[arg(long, conflicts_with_all = ["merge", "hooks", "yes"])]
+        minimal: bool,
+
+        /// Only write a GitHub Actions release workflow to .github/workflows/release.yml
+        #[arg(long, conflicts_with_all = ["merge", "hooks", "yes", "minimal"])]
+        github_workflow: bool,
     },
 
     /// Generate shell completions
@@ -201,170 +482,418 @@ enum PlanFormat {
     Json,
 }
 
-/// A no-op VcsProvider used when GITHUB_TOKEN is not available.
-struct NoopVcsProvider;
-
-impl VcsProvider for NoopVcsProvider {
-    fn create_release(
-        &self,
-        _tag: &str,
-        _name: &str,
-        _body: &str,
-        _prerelease: bool,
-        _draft: bool,
-    ) -> Result<String, sr_core::error::ReleaseError> {
-        Ok(String::new())
-    }
+/// Parse a `--from-version`/`--to-version` value, accepting a leading `v`.
+fn parse_version(s: &str) -> Result<semver::Version, String> {
+    semver::Version::parse(s.strip_prefix('v').unwrap_or(s)).map_err(|e| e.to_string())
+}
 
-    fn compare_url(
-        &self,
-        _base: &str,
-        _head: &str,
-    ) -> Result<String, sr_core::error::ReleaseError> {
-        Ok(String::new())
+/// `Release::builder()` for the repository in the current directory.
+fn release_builder(config: ReleaseConfig, git_backend: Option<GitBackend>) -> ReleaseBuilder {
+    let builder = Release::builder().config(config);
+    match git_backend {
+        Some(backend) => builder.git_backend(backend),
+        None => builder,
     }
+}
 
-    fn release_exists(&self, _tag: &str) -> Result<bool, sr_core::error::ReleaseError> {
-        Ok(false)
-    }
+/// `sr version --format json` output.
+#[derive(serde::Serialize)]
+struct VersionStatus {
+    current: Option<String>,
+    next: Option<String>,
+    bump: Option<String>,
+    release_needed: bool,
+    tag: Option<String>,
+    floating_tag: Option<String>,
+}
 
-    fn delete_release(&self, _tag: &str) -> Result<(), sr_core::error::ReleaseError> {
-        Ok(())
-    }
+/// The next version, or the current one restated when there is nothing to
+/// release: `NoCommits` and `NoBump` are answers here, not errors.
+fn version_status(strategy: &sr_runner::Strategy) -> Result<VersionStatus, ReleaseError> {
+    use sr_core::git::GitRepository;
+
+    let config = &strategy.config;
+    let tag = match strategy.plan() {
+        Ok(plan) => {
+            return Ok(VersionStatus {
+                current: plan
+                    .current_version
+                    .as_ref()
+                    .map(|v| config.format_version(v)),
+                next: Some(config.format_version(&plan.next_version)),
+                bump: Some(plan.bump.to_string()),
+                release_needed: true,
+                tag: Some(plan.tag_name),
+                floating_tag: plan.floating_tag_name,
+            });
+        }
+        Err(ReleaseError::NoCommits { tag, .. } | ReleaseError::NoBump { tag, .. }) => tag,
+        Err(e) => return Err(e),
+    };
+    let current = strategy
+        .git
+        .all_tags(&config.tag_prefix.all())?
+        .into_iter()
+        .find(|t| t.name == tag);
+    let current_version = current.as_ref().map(|t| config.format_version(&t.version));
+    let floating_tag = current
+        .as_ref()
+        .filter(|t| config.floating_tags && t.version.pre.is_empty())
+        .map(|t| format!("{}{}", config.tag_prefix.primary(), t.version.major));
+    Ok(VersionStatus {
+        next: current_version.clone(),
+        current: current_version,
+        bump: None,
+        release_needed: false,
+        tag: current.map(|t| t.name),
+        floating_tag,
+    })
 }
 
+/// `sr latest --format json` output. Every field is null before the first
+/// release.
+#[derive(Default, serde::Serialize)]
+struct LatestStatus {
+    version: Option<String>,
+    tag: Option<String>,
+    sha: Option<String>,
+    date: Option<String>,
+}
+
+/// A strategy without remote providers, for commands that never publish.
 fn build_local_strategy(
     config: ReleaseConfig,
     force: bool,
-) -> anyhow::Result<
-    TrunkReleaseStrategy<
-        NativeGitRepository,
-        NoopVcsProvider,
-        DefaultCommitParser,
-        DefaultChangelogFormatter,
-    >,
-> {
-    let git = NativeGitRepository::open(Path::new("."))?;
-    let types = config.types.clone();
-    let breaking_section = config.breaking_section.clone();
-    let misc_section = config.misc_section.clone();
-    let formatter = DefaultChangelogFormatter::new(
-        config.changelog.template.clone(),
-        types,
-        breaking_section,
-        misc_section,
-    );
-    Ok(TrunkReleaseStrategy {
-        git,
-        vcs: None,
-        parser: DefaultCommitParser,
-        formatter,
-        config,
-        force,
-    })
+    git_backend: Option<GitBackend>,
+) -> anyhow::Result<Strategy> {
+    Ok(release_builder(config, git_backend)
+        .force(force)
+        .remote(false)
+        .build()?
+        .into_strategy())
 }
 
-fn build_full_strategy(
-    config: ReleaseConfig,
-    force: bool,
-) -> anyhow::Result<
-    TrunkReleaseStrategy<
-        NativeGitRepository,
-        GitHubProvider,
-        DefaultCommitParser,
-        DefaultChangelogFormatter,
-    >,
-> {
-    let git = NativeGitRepository::open(Path::new("."))?;
-    let (hostname, owner, repo) = git.parse_remote_full()?;
-
-    let token = std::env::var("GH_TOKEN")
-        .or_else(|_| std::env::var("GITHUB_TOKEN"))
-        .map_err(|_| anyhow::anyhow!("neither GH_TOKEN nor GITHUB_TOKEN is set"))?;
-
-    let git = git.with_http_auth(hostname.clone(), token.clone());
-    let vcs = GitHubProvider::new(owner, repo, hostname, token);
-    let types = config.types.clone();
-    let breaking_section = config.breaking_section.clone();
-    let misc_section = config.misc_section.clone();
-    let formatter = DefaultChangelogFormatter::new(
-        config.changelog.template.clone(),
-        types,
-        breaking_section,
-        misc_section,
-    );
-
-    Ok(TrunkReleaseStrategy {
-        git,
-        vcs: Some(vcs),
-        parser: DefaultCommitParser,
-        formatter,
-        config,
-        force,
-    })
+/// The pull request number in `GITHUB_REF` (`refs/pull/<n>/merge`), for
+/// `sr plan --comment-pr` without a number.
+fn pull_request_from_env() -> anyhow::Result<u64> {
+    let git_ref = std::env::var("GITHUB_REF").unwrap_or_default();
+    git_ref
+        .strip_prefix("refs/pull/")
+        .and_then(|rest| rest.split('/').next())
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "--comment-pr needs a pull request number outside pull_request events \
+                 (GITHUB_REF is '{git_ref}')"
+            )
+        })
+}
+
+/// Create or update the release preview comment on pull request `number`.
+fn comment_on_pull_request(strategy: &Strategy, number: u64, body: &str) -> anyhow::Result<()> {
+    let client = sr_runner::github_client(&strategy.config, &strategy.git, None)?;
+    let url = client.upsert_issue_comment(number, report::PLAN_COMMENT_MARKER, body)?;
+    info!("updated the release preview on pull request #{number}: {url}");
+    Ok(())
+}
+
+/// Read a plan saved by `sr plan --save`, checking it belongs to this package.
+fn load_saved_plan(path: &Path, tag_prefix: &TagPrefix) -> Result<ReleasePlan, ReleaseError> {
+    let json = std::fs::read_to_string(path).map_err(|e| {
+        ReleaseError::Config(format!("cannot read plan file {}: {e}", path.display()))
+    })?;
+    let plan = ReleasePlan::from_json(&json)?;
+    if !plan.tag_name.starts_with(tag_prefix.primary()) {
+        return Err(ReleaseError::Config(format!(
+            "plan file {} is for tag {}, which does not match tag_prefix '{}'",
+            path.display(),
+            plan.tag_name,
+            tag_prefix.primary()
+        )));
+    }
+    Ok(plan)
+}
+
+/// Web URL of the repository, for changelog links. `None` when it cannot be resolved.
+fn repo_url(config: &ReleaseConfig, git_backend: Option<GitBackend>) -> Option<String> {
+    let git = AnyGitRepository::open(Path::new("."), git_backend).ok()?;
+    let (hostname, owner, repo) = resolve_origin(config, &git).ok()?;
+    Some(format!("https://{hostname}/{owner}/{repo}"))
+}
+
+/// `sr changelog --regenerate` in a monorepo without `--package`: rebuild every
+/// package's changelog from its own tags and path-filtered commits, plus the
+/// aggregated root changelog when `changelog.aggregate` is set.
+fn regenerate_package_changelogs(
+    config: &ReleaseConfig,
+    write: bool,
+    git_backend: Option<GitBackend>,
+) -> anyhow::Result<()> {
+    use sr_core::git::GitRepository;
+
+    let repo_url = repo_url(config, git_backend);
+    // (tag timestamp, date, subsection) for the aggregated root changelog
+    let mut aggregated = Vec::new();
+    for pkg in &config.packages {
+        let resolved = config.resolve_package(pkg);
+        let strategy = build_local_strategy(resolved.clone(), false, git_backend)?;
+        let tags = strategy.git.all_tags(&resolved.tag_prefix.all())?;
+        if tags.is_empty() {
+            warn!(
+                "package '{}': no tags found with prefix {}",
+                pkg.name,
+                resolved.tag_prefix.describe()
+            );
+            continue;
+        }
+
+        let mut rendered = Vec::with_capacity(tags.len());
+        strategy.visit_tag_entries(
+            &tags,
+            repo_url.as_deref(),
+            |_| true,
+            |i, entry| {
+                let section = strategy.render_entry(entry.clone(), Destination::Changelog)?;
+                if resolved.aggregate_changelog.is_some() {
+                    aggregated.push((
+                        strategy.git.tag_timestamp(&tags[i].name)?,
+                        entry.date.clone(),
+                        sr_core::changelog::package_subsection(&pkg.name, &entry.version, &section),
+                    ));
+                }
+                rendered.push(section);
+                Ok(())
+            },
+        )?;
+        rendered.reverse();
+        let changelog = rendered.join("\n\n");
+
+        if write {
+            let file = resolved.changelog_path();
+            std::fs::write(&file, format!("# Changelog\n\n{changelog}\n"))?;
+            info!("wrote {file}");
+        } else {
+            println!("# {}\n\n{changelog}\n", pkg.name);
+        }
+    }
+
+    if write
+        && let Some(root) = config
+            .changelog
+            .file
+            .as_deref()
+            .filter(|_| config.changelog.aggregate)
+    {
+        aggregated.sort_by_key(|(timestamp, _, _)| std::cmp::Reverse(*timestamp));
+        let releases: Vec<(String, String)> = aggregated
+            .into_iter()
+            .map(|(_, date, subsection)| (date, subsection))
+            .collect();
+        let body = sr_core::changelog::aggregate_sections(&releases);
+        std::fs::write(root, format!("# Changelog\n\n{body}\n"))?;
+        info!("wrote {root}");
+    }
+    Ok(())
 }
 
 /// Returns true if the error represents "nothing to release" (as opposed to a real failure).
 fn is_no_release_error(err: &anyhow::Error) -> bool {
-    if let Some(re) = err.downcast_ref::<ReleaseError>() {
-        matches!(
-            re,
-            ReleaseError::NoCommits { .. } | ReleaseError::NoBump { .. }
+    matches!(
+        release_error(err),
+        Some(
+            ReleaseError::NoCommits { .. }
+                | ReleaseError::NoBump { .. }
+                | ReleaseError::Deferred { .. }
         )
-    } else {
-        false
+    )
+}
+
+/// The `ReleaseError` behind `err`, looking through any added context.
+fn release_error(err: &anyhow::Error) -> Option<&ReleaseError> {
+    err.chain().find_map(|e| e.downcast_ref::<ReleaseError>())
+}
+
+/// `err` and its causes joined with `: `, like `{err:#}`, but skipping causes
+/// whose text the message already includes (`ReleaseError` variants that keep
+/// a typed source also show it in their own message).
+fn error_message(err: &anyhow::Error) -> String {
+    let mut message = String::new();
+    for cause in err.chain() {
+        let text = cause.to_string();
+        if message.contains(&text) {
+            continue;
+        }
+        if !message.is_empty() {
+            message.push_str(": ");
+        }
+        message.push_str(&text);
+    }
+    message
+}
+
+/// Process exit code for a failed command. Documented in the README's
+/// "Exit codes" table; keep the two in sync.
+fn exit_code(err: &anyhow::Error) -> u8 {
+    match release_error(err) {
+        Some(
+            ReleaseError::NoCommits { .. }
+            | ReleaseError::NoBump { .. }
+            | ReleaseError::Deferred { .. },
+        ) => 2,
+        Some(ReleaseError::Config(_)) => 3,
+        Some(
+            ReleaseError::Git(_)
+            | ReleaseError::GitCommand { .. }
+            | ReleaseError::GitLibrary { .. },
+        ) => 4,
+        Some(ReleaseError::Vcs(_) | ReleaseError::Http { .. }) => 5,
+        Some(
+            ReleaseError::Branch(_)
+            | ReleaseError::HeadMoved { .. }
+            | ReleaseError::Conflict(_)
+            | ReleaseError::NotCi { .. },
+        ) => 6,
+        Some(ReleaseError::VersionBump(_)) => 7,
+        Some(
+            ReleaseError::BuildCommand(_) | ReleaseError::Hook(_) | ReleaseError::Artifacts(_),
+        ) => 8,
+        Some(ReleaseError::Publish(_)) => 9,
+        Some(ReleaseError::NoTag { .. }) => 10,
+        Some(ReleaseError::NotNewer { .. }) => 11,
+        _ => 1,
     }
 }
 
+/// Global flags that control where config comes from.
+#[derive(Clone, Copy)]
+struct ConfigArgs<'a> {
+    /// Explicit `--config` path.
+    file: Option<&'a Path>,
+    /// Whether remote `extends` bases may be fetched.
+    offline: bool,
+}
+
 /// Load config and optionally resolve a package, returning the effective config.
-fn load_config_for_package(package: Option<&str>) -> anyhow::Result<ReleaseConfig> {
-    let config_path = resolve_config_path();
-    let mut config = ReleaseConfig::load(&config_path)?;
+fn load_config_for_package(
+    args: ConfigArgs<'_>,
+    package: Option<&str>,
+) -> anyhow::Result<ReleaseConfig> {
+    let config_path = resolve_config_path(args.file)?;
+    let mut config = load_config(&config_path, args)?;
     match package {
         Some(name) => {
             let pkg = config.find_package(name)?;
             Ok(config.resolve_package(pkg))
         }
         None => {
-            // Auto-detect version files if none configured
+            // Auto-detect version files next to the config if none configured.
+            // `load_config` already rebased the configured paths.
             if config.version_files.is_empty() {
-                config.version_files = sr_core::version_files::detect_version_files(Path::new("."));
+                let base = config_dir(&config_path);
+                config.version_files = sr_core::version_files::detect_version_files(base)
+                    .into_iter()
+                    .map(|file| base.join(file).to_string_lossy().into_owned())
+                    .collect();
             }
             Ok(config)
         }
     }
 }
 
-/// Find the config file, printing a deprecation warning if the legacy name is used.
-fn resolve_config_path() -> std::path::PathBuf {
-    match ReleaseConfig::find_config(Path::new(".")) {
+/// Warn about configured types that `commits` never use and commit types that
+/// are not configured.
+fn warn_type_usage(
+    config: &ReleaseConfig,
+    commits: &[sr_core::commit::Commit],
+) -> anyhow::Result<()> {
+    for warning in config.type_usage(commits)?.warnings() {
+        warn!("{warning}");
+    }
+    Ok(())
+}
+
+/// Load the config at `path` (fetching remote `extends` bases unless offline),
+/// resolving relative file paths against its directory.
+fn load_config(path: &Path, args: ConfigArgs<'_>) -> anyhow::Result<ReleaseConfig> {
+    let fetch: Option<&sr_core::config::ConfigFetcher> = if args.offline {
+        None
+    } else {
+        Some(&sr_github::fetch_text)
+    };
+    let mut config = ReleaseConfig::load_with(path, fetch)?;
+    config.resolve_paths_relative_to(config_dir(path));
+    Ok(config)
+}
+
+/// Directory holding the config file (`.` for a bare file name).
+fn config_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// Find the config file: the `--config` path when given, otherwise the nearest
+/// candidate between the current directory and the git root. Prints a deprecation
+/// warning if the legacy name is used.
+fn resolve_config_path(config_file: Option<&Path>) -> anyhow::Result<PathBuf> {
+    if let Some(path) = config_file {
+        if !path.is_file() {
+            anyhow::bail!("config file not found: {}", path.display());
+        }
+        return Ok(path.to_path_buf());
+    }
+    match ReleaseConfig::discover_config(Path::new(".")) {
         Some((path, is_legacy)) => {
             if is_legacy {
-                eprintln!(
-                    "warning: {} is deprecated, rename to {} (legacy support will be removed in a future release)",
+                warn!(
+                    "{} is deprecated, rename to {} (legacy support will be removed in a future release)",
                     LEGACY_CONFIG_FILE, DEFAULT_CONFIG_FILE,
                 );
             }
-            path
+            Ok(path)
         }
-        None => std::path::PathBuf::from(DEFAULT_CONFIG_FILE),
+        // Without a config, version files are auto-detected at the repository
+        // root, not in whatever subdirectory sr runs from
+        None => Ok(repo_root_from_cwd()
+            .unwrap_or_default()
+            .join(DEFAULT_CONFIG_FILE)),
     }
 }
 
+/// The repository root relative to the current directory (e.g. `../..`), or
+/// `None` at the root itself or outside a repository.
+fn repo_root_from_cwd() -> Option<PathBuf> {
+    use sr_core::git::GitRepository;
+
+    let root = AnyGitRepository::open(Path::new("."), None)
+        .ok()?
+        .workdir()
+        .ok()?
+        .canonicalize()
+        .ok()?;
+    let cwd = std::env::current_dir().ok()?.canonicalize().ok()?;
+    let depth = cwd.strip_prefix(root).ok()?.components().count();
+    (depth > 0).then(|| std::iter::repeat_n("..", depth).collect())
+}
+
 /// Best-effort hook sync — keeps `.githooks/` in sync with `sr.yaml`.
 /// Failures print a warning but never abort the calling command.
-fn ensure_hooks_synced() {
-    let config_path = resolve_config_path();
+fn ensure_hooks_synced(args: ConfigArgs<'_>) {
+    let Ok(config_path) = resolve_config_path(args.file) else {
+        return;
+    };
     if !config_path.exists() {
         return;
     }
-    if let Ok(config) = ReleaseConfig::load(&config_path)
-        && sr_core::hooks::needs_sync(Path::new("."), &config.hooks)
+    let root = repo_root_from_cwd().unwrap_or_else(|| PathBuf::from("."));
+    if let Ok(config) = load_config(&config_path, args)
+        && sr_core::hooks::needs_sync(&root, &config.hooks)
     {
-        match sr_core::hooks::sync_hooks(Path::new("."), &config.hooks) {
-            Ok(true) => eprintln!("hooks synced with {}", config_path.display()),
+        match sr_core::hooks::sync_hooks(&root, &config.hooks) {
+            Ok(true) => info!("hooks synced with {}", config_path.display()),
             Ok(false) => {}
-            Err(e) => eprintln!("warning: failed to sync hooks: {e}"),
+            Err(e) => warn!("failed to sync hooks: {e}"),
         }
     }
 }
@@ -373,7 +902,8 @@ const INSTALL_SCRIPT_URL: &str = "https://raw.githubusercontent.com/urmzd/sr/mai
 
 /// Self-update sr by running the install script.
 fn self_update() -> anyhow::Result<()> {
-    eprintln!("current version: {}", env!("CARGO_PKG_VERSION"));
+    sr_core::offline::ensure_online("sr update")?;
+    info!("current version: {}", env!("CARGO_PKG_VERSION"));
 
     // Resolve install dir to wherever the current binary lives
     let current_exe = std::env::current_exe()?;
@@ -395,6 +925,19 @@ fn self_update() -> anyhow::Result<()> {
 
 async fn run() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    let (stdout_styles, stderr_styles) = {
+        use std::io::IsTerminal;
+        (
+            Styles::for_stream(cli.color, std::io::stdout().is_terminal()),
+            Styles::for_stream(cli.color, std::io::stderr().is_terminal()),
+        )
+    };
+    logging::init(cli.verbose, cli.quiet, cli.log_format, stderr_styles);
+    sr_core::offline::set_offline(cli.offline);
+    if let Some(ref dir) = cli.cwd {
+        std::env::set_current_dir(dir)
+            .map_err(|e| anyhow::anyhow!("cannot change to --cwd {}: {e}", dir.display()))?;
+    }
 
     let backend_config = BackendConfig {
         backend: cli.backend,
@@ -402,145 +945,417 @@ async fn run() -> anyhow::Result<()> {
         budget: cli.budget,
         debug: cli.debug,
     };
+    let git_backend = cli.git_backend;
+    let config_args = ConfigArgs {
+        file: cli.config.as_deref(),
+        offline: cli.offline,
+    };
 
     match cli.command {
-        Commands::Init { force, merge } => {
-            let path = Path::new(DEFAULT_CONFIG_FILE);
+        Commands::Init {
+            hooks: true,
+            force,
+            print,
+            ..
+        } => {
+            let script = sr_core::hooks::commit_msg_hook_script();
+            if print {
+                print!("{script}");
+                return Ok(());
+            }
+            let output = std::process::Command::new("git")
+                .args(["rev-parse", "--git-path", "hooks"])
+                .output()?;
+            if !output.status.success() {
+                anyhow::bail!(
+                    "not in a git repository; run `sr init --hooks --print` to get the hook script"
+                );
+            }
+            let hooks_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+            let path = sr_core::hooks::install_commit_msg_hook(&hooks_dir, force)?;
+            info!("installed {}", path.display());
+            Ok(())
+        }
 
-            if path.exists() && !force && !merge {
+        Commands::Init {
+            github_workflow: true,
+            force,
+            ..
+        } => {
+            let root = repo_root_from_cwd().unwrap_or_else(|| PathBuf::from("."));
+            let path = root.join(workflow::WORKFLOW_PATH);
+            if path.exists() && !force {
                 anyhow::bail!(
-                    "{DEFAULT_CONFIG_FILE} already exists (use --force to overwrite, or --merge to add new fields)"
+                    "{} already exists (use --force to overwrite)",
+                    path.display()
                 );
             }
+            let config = load_config(&resolve_config_path(config_args.file)?, config_args)?;
+            let build = workflow::detect_build_step(&root);
+            let text = workflow::release_workflow(&config.branches, build.as_ref());
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, text)?;
+            info!("wrote {}", path.display());
+            Ok(())
+        }
 
-            // Auto-detect version files in the current directory
-            let detected = sr_core::version_files::detect_version_files(Path::new("."));
-            if !detected.is_empty() {
-                for f in &detected {
-                    eprintln!("detected version file: {f}");
-                }
+        Commands::Init {
+            force,
+            merge,
+            yes,
+            minimal,
+            ..
+        } => {
+            let path = config_args.file.unwrap_or(Path::new(DEFAULT_CONFIG_FILE));
+
+            if path.exists() && !force && !merge {
+                anyhow::bail!(
+                    "{} already exists (use --force to overwrite, or --merge to add new fields)",
+                    path.display()
+                );
             }
 
             if merge && path.exists() {
                 let existing = std::fs::read_to_string(path)?;
                 let merged = sr_core::config::merge_config_yaml(&existing)?;
                 std::fs::write(path, merged)?;
-                eprintln!("merged new defaults into {DEFAULT_CONFIG_FILE}");
+                info!("merged new defaults into {}", path.display());
             } else {
+                // Pre-fill from the project next to the config file
+                let mut detected = if minimal {
+                    DetectedDefaults::default()
+                } else {
+                    sr_core::config::detect_project(config_dir(path))
+                };
+                if !detected.is_empty() {
+                    let summary = confirm::detected_summary(&detected);
+                    if !yes && environment::interactive() {
+                        let accepted = confirm::confirm_detected(
+                            &summary,
+                            &mut std::io::stdin().lock(),
+                            &mut std::io::stderr(),
+                        )?;
+                        if !accepted {
+                            detected = DetectedDefaults::default();
+                        }
+                    } else {
+                        for line in summary.lines().skip(1) {
+                            info!("detected {}", line.trim());
+                        }
+                    }
+                }
                 let template = sr_core::config::default_config_template(&detected);
                 std::fs::write(path, template)?;
-                eprintln!("wrote {DEFAULT_CONFIG_FILE}");
+                info!("wrote {}", path.display());
             }
 
-            let config = ReleaseConfig::load(path)?;
-            sr_core::hooks::sync_hooks(Path::new("."), &config.hooks)?;
+            let config = load_config(path, config_args)?;
+            let root = repo_root_from_cwd().unwrap_or_else(|| PathBuf::from("."));
+            sr_core::hooks::sync_hooks(&root, &config.hooks)?;
 
             Ok(())
         }
 
-        Commands::Config { resolved } => {
-            let config_path = resolve_config_path();
-            let config = ReleaseConfig::load(&config_path)?;
+        Commands::Config {
+            resolved,
+            validate,
+            schema,
+        } => {
+            if schema {
+                let schema = sr_core::config::config_schema();
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+                return Ok(());
+            }
+            let config_path = resolve_config_path(config_args.file)?;
+            let config = load_config(&config_path, config_args)?;
+            let embedded = sr_core::config::manifest_config(&config_path)?;
+            let source = match &embedded {
+                Some((_, key)) => format!("{} [{key}]", config_path.display()),
+                None => config_path.display().to_string(),
+            };
+            if validate {
+                if config_path.exists() {
+                    info!("{source} is valid");
+                } else {
+                    info!("no config file found; defaults are valid");
+                }
+                // Outside a repository there is no history to check the types against
+                if let Ok(repo) = AnyGitRepository::open(Path::new("."), git_backend) {
+                    use sr_core::git::GitRepository;
+                    let repo = repo.with_lenient_tags(config.lenient_tags);
+                    let since = repo.all_tags(&config.tag_prefix.all())?.pop();
+                    let commits = repo.commits_since(since.as_ref().map(|t| t.name.as_str()))?;
+                    warn_type_usage(&config, &commits)?;
+                }
+                return Ok(());
+            }
+            if config_path.exists() {
+                info!("loaded config from {source}");
+            }
             if resolved {
                 let yaml = serde_yaml_ng::to_string(&config)?;
                 print!("{yaml}");
+            } else if let Some((section, _)) = embedded {
+                print!("{}", serde_yaml_ng::to_string(&section)?);
             } else if config_path.exists() {
                 let raw = std::fs::read_to_string(&config_path)?;
                 print!("{raw}");
             } else {
-                eprintln!("no config file found; showing defaults");
+                info!("no config file found; showing defaults");
                 let yaml = serde_yaml_ng::to_string(&config)?;
                 print!("{yaml}");
             }
             Ok(())
         }
 
-        Commands::Version { short, package } => {
-            let config = load_config_for_package(package.as_deref())?;
-            let strategy = build_local_strategy(config, false)?;
+        Commands::Check {
+            range,
+            message_file,
+            stdin,
+            package,
+        } => {
+            use sr_core::git::GitRepository;
+
+            let config = load_config_for_package(config_args, package.as_deref())?;
+            let message = match (message_file, stdin) {
+                (Some(path), _) => Some(
+                    std::fs::read_to_string(&path)
+                        .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", path.display()))?,
+                ),
+                (None, true) => {
+                    let mut message = String::new();
+                    std::io::Read::read_to_string(&mut std::io::stdin(), &mut message)?;
+                    Some(message)
+                }
+                (None, false) => None,
+            };
+            if let Some(message) = message {
+                sr_core::hooks::check_commit_message(&config, &message)?;
+                return Ok(());
+            }
+
+            let repo = AnyGitRepository::open(Path::new("."), git_backend)?;
+            let (from, to) = match range.as_deref().map(|r| r.split_once("..")) {
+                Some(Some((from, to))) => (
+                    Some(from.to_string()).filter(|f| !f.is_empty()),
+                    if to.is_empty() { "HEAD" } else { to }.to_string(),
+                ),
+                Some(None) => anyhow::bail!("range must look like <from>..<to>"),
+                None => (
+                    repo.all_tags(&config.tag_prefix.all())?
+                        .last()
+                        .map(|t| t.name.clone()),
+                    "HEAD".to_string(),
+                ),
+            };
+            let commits = repo.commits_between(from.as_deref(), &to)?;
+            warn_type_usage(&config, &commits)?;
+            let mut failed = 0;
+            for commit in &commits {
+                if let Err(e) = sr_core::hooks::check_commit_message(&config, &commit.message) {
+                    failed += 1;
+                    error!("{}: {e}", &commit.sha[..7.min(commit.sha.len())]);
+                }
+            }
+            if failed > 0 {
+                anyhow::bail!("{failed} of {} commit(s) failed the check", commits.len());
+            }
+            info!("{} commit(s) ok", commits.len());
+            Ok(())
+        }
+
+        Commands::Version {
+            short,
+            package,
+            format,
+        } => {
+            let config = load_config_for_package(config_args, package.as_deref())?;
+            let strategy = build_local_strategy(config, false, git_backend)?;
+            if matches!(format, PlanFormat::Json) {
+                println!("{}", serde_json::to_string(&version_status(&strategy)?)?);
+                return Ok(());
+            }
             let plan = strategy.plan()?;
+            let config = &strategy.config;
             if short {
-                println!("{}", plan.next_version);
+                println!("{}", config.format_version(&plan.next_version));
             } else {
                 println!(
                     "{} -> {} ({})",
                     plan.current_version
-                        .map(|v| v.to_string())
+                        .map(|v| config.format_version(&v))
                         .unwrap_or_else(|| "none".to_string()),
-                    plan.next_version,
+                    config.format_version(&plan.next_version),
                     plan.bump
                 );
             }
             Ok(())
         }
 
-        Commands::Plan { format, package } => {
-            let config = load_config_for_package(package.as_deref())?;
-            let formatter = DefaultChangelogFormatter::new(
-                config.changelog.template.clone(),
-                config.types.clone(),
-                config.breaking_section.clone(),
-                config.misc_section.clone(),
-            );
-            let strategy = build_local_strategy(config, false)?;
-            let plan = strategy.plan()?;
+        Commands::Latest {
+            package,
+            prefix,
+            check,
+            format,
+        } => {
+            let config = load_config_for_package(config_args, package.as_deref())?;
+            let strategy = build_local_strategy(config, false, git_backend)?;
+            let config = &strategy.config;
+            let latest = strategy.latest(prefix.as_deref())?;
+            let status = latest
+                .as_ref()
+                .map(|(tag, date)| LatestStatus {
+                    version: Some(config.format_version(&tag.version)),
+                    tag: Some(tag.name.clone()),
+                    sha: Some(tag.sha.clone()),
+                    date: Some(date.clone()),
+                })
+                .unwrap_or_default();
+            match format {
+                PlanFormat::Json => println!("{}", serde_json::to_string(&status)?),
+                PlanFormat::Human => {
+                    if let Some((tag, date)) = &latest {
+                        println!(
+                            "{} ({}, {}, {date})",
+                            config.format_version(&tag.version),
+                            tag.name,
+                            &tag.sha[..tag.sha.len().min(7)]
+                        );
+                    }
+                }
+            }
+            match (latest, check) {
+                (Some((tag, _)), Some(version)) if version <= tag.version => {
+                    Err(ReleaseError::NotNewer {
+                        version: config.format_version(&version),
+                        latest: tag.name,
+                    }
+                    .into())
+                }
+                (None, None) => Err(ReleaseError::NoTag {
+                    prefixes: match prefix {
+                        Some(prefix) => format!("'{prefix}'"),
+                        None => config.tag_prefix.describe(),
+                    },
+                }
+                .into()),
+                // Before the first release, any version passes --check
+                _ => Ok(()),
+            }
+        }
 
-            let repo_url = NativeGitRepository::open(Path::new("."))
-                .ok()
-                .and_then(|git| git.parse_remote_full().ok())
-                .map(|(hostname, owner, repo)| format!("https://{hostname}/{owner}/{repo}"));
+        Commands::Plan {
+            format,
+            package,
+            force,
+            git_ref,
+            save,
+            comment_pr,
+            compare,
+        } => {
+            let config = load_config_for_package(config_args, package.as_deref())?;
+            let formatter = config.changelog_formatter()?;
+            let repo_url = repo_url(&config, git_backend);
+            let mut strategy = release_builder(config, git_backend)
+                .force(force)
+                .remote(false)
+                .changed_paths(true)
+                .build()?
+                .into_strategy();
+            strategy.target_ref = git_ref.clone();
+            let pull_request = comment_pr
+                .map(|number| number.map_or_else(pull_request_from_env, Ok))
+                .transpose()?;
+            let plan = match strategy.plan() {
+                Ok(plan) => plan,
+                Err(e) => {
+                    if let Some(number) = pull_request
+                        && matches!(
+                            e,
+                            ReleaseError::NoCommits { .. }
+                                | ReleaseError::NoBump { .. }
+                                | ReleaseError::Deferred { .. }
+                        )
+                    {
+                        let body = report::no_release_comment(&e.to_string());
+                        comment_on_pull_request(&strategy, number, &body)?;
+                    }
+                    return Err(e.into());
+                }
+            };
+            if let Some(path) = &save {
+                std::fs::write(path, serde_json::to_string_pretty(&plan)? + "\n")
+                    .map_err(|e| anyhow::anyhow!("cannot write {}: {e}", path.display()))?;
+                info!("saved plan for {} to {}", plan.tag_name, path.display());
+            }
 
-            let today = sr_core::release::today_string();
             let entry = sr_core::changelog::ChangelogEntry {
-                version: plan.next_version.to_string(),
-                date: today,
+                version: strategy.config.format_version(&plan.next_version),
+                tag_name: Some(plan.tag_name.clone()),
+                date: strategy.release_date(&plan)?,
                 commits: plan.commits.clone(),
                 compare_url: None,
                 repo_url,
             };
+            let entry = strategy.config.entry_for(entry, Destination::Changelog);
             let changelog = sr_core::changelog::ChangelogFormatter::format(&formatter, &[entry])?;
 
+            let comparison = if compare {
+                Some(strategy.compare_with_previous(&plan)?)
+            } else {
+                None
+            };
+
+            if let Some(number) = pull_request {
+                let reason = confirm::bump_reason(&plan, &strategy.config);
+                let body = report::plan_comment(&plan, &strategy.config, &reason, &changelog);
+                comment_on_pull_request(&strategy, number, &body)?;
+            }
+
             match format {
                 PlanFormat::Json => {
                     #[derive(serde::Serialize)]
                     struct PlanOutput<'a> {
                         #[serde(flatten)]
                         plan: &'a sr_core::release::ReleasePlan,
+                        /// The revision the plan was computed at.
+                        r#ref: &'a str,
                         changelog: String,
+                        /// `stats` and `previous_release` with `--compare`.
+                        #[serde(flatten)]
+                        comparison: Option<sr_core::release::ReleaseComparison>,
                     }
                     let output = PlanOutput {
                         plan: &plan,
+                        r#ref: git_ref.as_deref().unwrap_or("HEAD"),
                         changelog,
+                        comparison,
                     };
                     println!("{}", serde_json::to_string_pretty(&output)?);
                 }
                 PlanFormat::Human => {
-                    println!("Next release: {}", plan.tag_name);
-                    println!(
-                        "Current version: {}",
-                        plan.current_version
-                            .as_ref()
-                            .map(|v| v.to_string())
-                            .unwrap_or_else(|| "none".to_string())
+                    print!(
+                        "{}",
+                        report::plan(
+                            &plan,
+                            &strategy.config,
+                            git_ref.as_deref(),
+                            &changelog,
+                            &stdout_styles
+                        )
                     );
-                    println!("Next version: {}", plan.next_version);
-                    println!("Bump: {}", plan.bump);
-                    println!("Commits ({})", plan.commits.len());
-                    for commit in &plan.commits {
-                        let scope = commit
-                            .scope
-                            .as_deref()
-                            .map(|s| format!("({s})"))
-                            .unwrap_or_default();
-                        let breaking = if commit.breaking { " BREAKING" } else { "" };
-                        println!(
-                            "  - {}{scope}: {}{breaking} ({})",
-                            commit.r#type,
-                            commit.description,
-                            &commit.sha[..7.min(commit.sha.len())]
+                    if let Some(comparison) = &comparison {
+                        print!(
+                            "\n{}",
+                            report::comparison(
+                                comparison,
+                                &strategy.config.section_names(),
+                                &stdout_styles
+                            )
                         );
                     }
-                    println!("\nChangelog preview:\n{changelog}");
                 }
             }
             Ok(())
@@ -550,100 +1365,128 @@ async fn run() -> anyhow::Result<()> {
             write,
             regenerate,
             package,
+            from_version,
+            to_version,
+            last,
         } => {
-            let config = load_config_for_package(package.as_deref())?;
+            let config = load_config_for_package(config_args, package.as_deref())?;
 
-            let formatter = DefaultChangelogFormatter::new(
-                config.changelog.template.clone(),
-                config.types.clone(),
-                config.breaking_section.clone(),
-                config.misc_section.clone(),
-            );
+            let ranged = from_version.is_some() || to_version.is_some() || last.is_some();
+            if regenerate && package.is_none() && !config.packages.is_empty() {
+                if ranged {
+                    anyhow::bail!(
+                        "--from-version, --to-version and --last need --package in a monorepo"
+                    );
+                }
+                return regenerate_package_changelogs(&config, write, git_backend);
+            }
 
+            let formatter = config.changelog_formatter()?;
+
+            let mut sections = Vec::new();
             let changelog = if regenerate {
-                use sr_core::commit::CommitParser;
                 use sr_core::git::GitRepository;
 
-                let git = NativeGitRepository::open(Path::new("."))?;
-                let repo_url = git
-                    .parse_remote_full()
-                    .ok()
-                    .map(|(hostname, owner, repo)| format!("https://{hostname}/{owner}/{repo}"));
-
-                let tags = git.all_tags(&config.tag_prefix)?;
+                let repo_url = repo_url(&config, git_backend);
+                let strategy = build_local_strategy(config.clone(), false, git_backend)?;
+                let tags = strategy.git.all_tags(&config.tag_prefix.all())?;
                 if tags.is_empty() {
-                    anyhow::bail!("no tags found with prefix '{}'", config.tag_prefix);
+                    anyhow::bail!("no tags found with prefix {}", config.tag_prefix.describe());
                 }
 
-                let parser = DefaultCommitParser;
-                let mut entries = Vec::new();
-
-                for (i, tag) in tags.iter().enumerate() {
-                    let from = if i == 0 {
-                        None
-                    } else {
-                        Some(tags[i - 1].sha.as_str())
-                    };
-                    let raw_commits = if let Some(ref path) = config.path_filter {
-                        git.commits_between_in_path(from, &tag.name, path)?
-                    } else {
-                        git.commits_between(from, &tag.name)?
-                    };
-                    let conventional: Vec<_> = raw_commits
-                        .iter()
-                        .filter(|c| !c.message.starts_with("chore(release):"))
-                        .filter_map(|c| parser.parse(c).ok())
+                if ranged {
+                    let mut selected: Vec<usize> = (0..tags.len())
+                        .filter(|&i| {
+                            let version = &tags[i].version;
+                            from_version.as_ref().is_none_or(|from| version >= from)
+                                && to_version.as_ref().is_none_or(|to| version <= to)
+                        })
                         .collect();
+                    if let Some(n) = last {
+                        selected.drain(..selected.len().saturating_sub(n));
+                    }
+                    if selected.is_empty() {
+                        anyhow::bail!("no tags in the requested version range");
+                    }
 
-                    let date = git.tag_date(&tag.name)?;
-                    let compare_url = if i > 0 {
-                        repo_url
-                            .as_ref()
-                            .map(|url| format!("{url}/compare/{}...{}", tags[i - 1].name, tag.name))
-                    } else {
-                        None
-                    };
-
-                    entries.push(sr_core::changelog::ChangelogEntry {
-                        version: tag.version.to_string(),
-                        date,
-                        commits: conventional,
-                        compare_url,
-                        repo_url: repo_url.clone(),
-                    });
+                    // Each release is rendered on its own so it can be spliced in place.
+                    strategy.visit_tag_entries(
+                        &tags,
+                        repo_url.as_deref(),
+                        |i| selected.contains(&i),
+                        |i, entry| {
+                            let entry = config.entry_for(entry, Destination::Changelog);
+                            let section = sr_core::changelog::ChangelogFormatter::format(
+                                &formatter,
+                                std::slice::from_ref(&entry),
+                            )?;
+                            sections.push((tags[i].version.clone(), section));
+                            Ok(())
+                        },
+                    )?;
+                    sections.reverse();
+                    sections
+                        .iter()
+                        .map(|(_, section)| section.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n\n")
+                } else {
+                    let mut rendered = Vec::with_capacity(tags.len());
+                    strategy.visit_tag_entries(
+                        &tags,
+                        repo_url.as_deref(),
+                        |_| true,
+                        |_, entry| {
+                            let entry = config.entry_for(entry, Destination::Changelog);
+                            rendered.push(sr_core::changelog::ChangelogFormatter::format(
+                                &formatter,
+                                std::slice::from_ref(&entry),
+                            )?);
+                            Ok(())
+                        },
+                    )?;
+                    // Newest first.
+                    rendered.reverse();
+                    rendered.join("\n\n")
                 }
-
-                // Newest first
-                entries.reverse();
-
-                sr_core::changelog::ChangelogFormatter::format(&formatter, &entries)?
             } else {
-                let strategy = build_local_strategy(config.clone(), false)?;
+                let strategy = build_local_strategy(config.clone(), false, git_backend)?;
                 let plan = strategy.plan()?;
 
-                let repo_url = NativeGitRepository::open(Path::new("."))
-                    .ok()
-                    .and_then(|git| git.parse_remote_full().ok())
-                    .map(|(hostname, owner, repo)| format!("https://{hostname}/{owner}/{repo}"));
+                let repo_url = repo_url(&config, git_backend);
 
-                let today = sr_core::release::today_string();
                 let entry = sr_core::changelog::ChangelogEntry {
-                    version: plan.next_version.to_string(),
-                    date: today,
+                    version: config.format_version(&plan.next_version),
+                    tag_name: Some(plan.tag_name.clone()),
+                    date: strategy.release_date(&plan)?,
                     commits: plan.commits,
                     compare_url: None,
                     repo_url,
                 };
+                let entry = config.entry_for(entry, Destination::Changelog);
 
                 sr_core::changelog::ChangelogFormatter::format(&formatter, &[entry])?
             };
 
             if write {
-                let file = config.changelog.file.as_deref().unwrap_or("CHANGELOG.md");
+                let file = config.changelog_path();
+                let file = file.as_str();
                 let path = Path::new(file);
-                if regenerate {
+                if regenerate && !ranged {
                     let content = format!("# Changelog\n\n{changelog}\n");
                     std::fs::write(path, content)?;
+                } else if ranged {
+                    let existing = if path.exists() {
+                        std::fs::read_to_string(path)?
+                    } else {
+                        String::new()
+                    };
+                    let content = if existing.is_empty() {
+                        format!("# Changelog\n\n{changelog}\n")
+                    } else {
+                        sr_core::changelog::splice_sections(&existing, &sections)
+                    };
+                    std::fs::write(path, content)?;
                 } else {
                     let existing = if path.exists() {
                         std::fs::read_to_string(path)?
@@ -663,13 +1506,152 @@ async fn run() -> anyhow::Result<()> {
                     };
                     std::fs::write(path, content)?;
                 }
-                eprintln!("wrote {file}");
+                info!("wrote {file}");
             } else {
                 println!("{changelog}");
             }
             Ok(())
         }
 
+        Commands::Notes { tag, package, push } => {
+            let config = load_config_for_package(config_args, package.as_deref())?;
+            let repo_url = repo_url(&config, git_backend);
+            let strategy = release_builder(config, git_backend)
+                .remote(push)
+                .build()?
+                .into_strategy();
+            let (tag, notes) = strategy.notes(tag.as_deref(), repo_url.as_deref())?;
+            if push {
+                strategy.push_notes(&tag, &notes)?;
+            }
+            println!("{notes}");
+            Ok(())
+        }
+
+        Commands::Upload {
+            globs,
+            tag,
+            package,
+            create_missing,
+            checksums,
+            dry_run,
+        } => {
+            let config = load_config_for_package(config_args, package.as_deref())?;
+            let strategy = release_builder(config, git_backend)
+                .dry_run(dry_run)
+                .build()?
+                .into_strategy();
+            let uploaded = strategy.upload(
+                tag.as_deref(),
+                &globs,
+                sr_core::release::UploadOptions {
+                    create_missing,
+                    checksums,
+                    dry_run,
+                },
+            )?;
+            for url in uploaded.iter().flat_map(|u| &u.urls) {
+                println!("{url}");
+            }
+            Ok(())
+        }
+
+        Commands::Doctor { package, format } => {
+            use sr_core::doctor::{self, CheckResult, CheckStatus};
+            use sr_core::git::GitRepository;
+            use sr_core::release::{detect_default_branch, release_branches};
+
+            let mut checks = vec![doctor::git_binary(sr_git::git_version().as_deref())];
+            let config = match load_config_for_package(config_args, package.as_deref()) {
+                Ok(config) => {
+                    checks.push(CheckResult::pass("config", "loaded"));
+                    config
+                }
+                Err(e) => {
+                    checks.push(CheckResult::fail("config", error_message(&e)));
+                    ReleaseConfig::default()
+                }
+            };
+            let remote_release = config.provider != ProviderMode::None;
+            match AnyGitRepository::open(Path::new("."), git_backend) {
+                Ok(repo) => {
+                    let repo = repo.with_lenient_tags(config.lenient_tags);
+                    checks.push(doctor::repository(Ok(())));
+                    checks.push(doctor::remote(&repo.parse_remote_full()));
+                    checks.push(doctor::history(repo.is_shallow()));
+                    checks.push(doctor::tags(
+                        repo.all_tags(&config.tag_prefix.all()),
+                        &config.tag_prefix.describe(),
+                    ));
+                    let offline = remote_release && config_args.offline;
+                    let providers = match resolve_origin(&config, &repo) {
+                        Ok(origin) if remote_release && !offline => Some(
+                            build_providers(&config, &origin, None).map(|(providers, _)| providers),
+                        ),
+                        _ => None,
+                    };
+                    let vcs = match &providers {
+                        Some(Ok(providers)) => providers.first().map(|vcs| vcs.as_ref()),
+                        _ => None,
+                    };
+                    let default_branch = detect_default_branch(&repo, vcs);
+                    checks.push(doctor::default_branch(&default_branch));
+                    let branches = release_branches(&config.branches, &repo, || {
+                        Ok(default_branch.as_ref().ok().cloned().flatten())
+                    })?;
+                    checks.push(doctor::branch(
+                        repo.current_branch(),
+                        std::env::var("GITHUB_REF_NAME").ok().as_deref(),
+                        &branches,
+                    ));
+                    checks.push(doctor::working_tree(repo.dirty_paths()));
+                    match providers {
+                        None if offline => {
+                            checks.push(CheckResult::warn("token", "not checked (offline)"))
+                        }
+                        Some(Ok(providers)) => {
+                            let label = providers.len() > 1;
+                            for vcs in providers {
+                                let mut check = doctor::token(true, true, Some(vcs.can_push()));
+                                if label {
+                                    check.detail = format!("{}: {}", vcs.name(), check.detail);
+                                }
+                                checks.push(check);
+                            }
+                        }
+                        Some(Err(e)) => checks.push(CheckResult::fail("token", e.to_string())),
+                        None => checks.push(doctor::token(
+                            default_token().is_some(),
+                            remote_release,
+                            None,
+                        )),
+                    }
+                }
+                Err(e) => {
+                    checks.push(doctor::repository(Err(&e)));
+                    checks.push(doctor::token(
+                        default_token().is_some(),
+                        remote_release,
+                        None,
+                    ));
+                }
+            }
+            checks.extend(doctor::files(&config));
+
+            match format {
+                PlanFormat::Json => println!("{}", serde_json::to_string_pretty(&checks)?),
+                PlanFormat::Human => print!("{}", report::doctor(&checks, &stdout_styles)),
+            }
+            let failed = checks
+                .iter()
+                .filter(|c| c.status == CheckStatus::Fail)
+                .count();
+            if failed > 0 {
+                anyhow::bail!("{failed} check(s) failed");
+            }
+            Ok(())
+        }
+
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
             clap_complete::generate(shell, &mut cmd, "sr", &mut std::io::stdout());
@@ -679,6 +1661,7 @@ async fn run() -> anyhow::Result<()> {
         Commands::Release {
             package,
             dry_run,
+            format,
             artifacts,
             force,
             build_command,
@@ -688,20 +1671,48 @@ async fn run() -> anyhow::Result<()> {
             prerelease,
             sign_tags,
             draft,
+            no_vcs,
+            allow_any_branch,
+            allow_dirty,
+            ignore_head_drift,
+            allow_local,
+            push_branch,
+            override_policy,
+            no_push,
+            tag_only,
+            fail_on_no_release,
+            yes,
+            plan_file,
         } => {
-            ensure_hooks_synced();
-
-            let mut config = load_config_for_package(package.as_deref())?;
-            config.artifacts.extend(artifacts);
+            ensure_hooks_synced(config_args);
+
+            let mut config = load_config_for_package(config_args, package.as_deref())?;
+            if config.require_ci && !dry_run {
+                match environment::detect_ci() {
+                    Some(ci) => info!("require_ci: running under {} ({})", ci.name, ci.variable),
+                    None if allow_local => {
+                        warn!("require_ci: no CI detected; releasing locally (--allow-local)")
+                    }
+                    None => {
+                        return Err(ReleaseError::NotCi {
+                            checked: environment::ci_variable_names(),
+                        }
+                        .into());
+                    }
+                }
+            }
+            config
+                .artifacts
+                .extend(artifacts.into_iter().map(Into::into));
             config.stage_files.extend(stage_files);
             if build_command.is_some() {
-                config.build_command = build_command;
+                config.build_command = build_command.map(Into::into);
             }
             if pre_release_command.is_some() {
-                config.pre_release_command = pre_release_command;
+                config.pre_release_command = pre_release_command.map(Into::into);
             }
             if post_release_command.is_some() {
-                config.post_release_command = post_release_command;
+                config.post_release_command = post_release_command.map(Into::into);
             }
             if prerelease.is_some() {
                 config.prerelease = prerelease;
@@ -712,26 +1723,83 @@ async fn run() -> anyhow::Result<()> {
             if draft {
                 config.draft = true;
             }
+            if no_vcs {
+                config.provider = ProviderMode::None;
+            }
+            let remote_release = config.provider != ProviderMode::None;
+            let offline = config_args.offline;
+            if offline && !dry_run && !no_push {
+                anyhow::bail!(
+                    "--offline needs --dry-run or --no-push: a release pushes tags and calls the provider"
+                );
+            }
 
-            // Try to build with GitHub; fall back to local-only if no token
-            let plan = match build_full_strategy(config.clone(), force) {
-                Ok(strategy) => {
-                    let plan = strategy.plan()?;
-                    strategy.execute(&plan, dry_run)?;
-                    plan
+            // `provider: none` never constructs a provider. Otherwise try to build
+            // with GitHub; a dry-run falls back to local-only if there is no token.
+            if remote_release && offline {
+                info!("offline: skipping the remote release provider");
+            }
+            let builder = release_builder(config, git_backend)
+                .force(force)
+                .dry_run(dry_run)
+                .remote(remote_release && !offline);
+            let mut strategy = match builder.clone().build() {
+                Ok(release) => release.into_strategy(),
+                // --no-push never calls the provider
+                Err(_) if no_push => builder.remote(false).build()?.into_strategy(),
+                Err(e) => return Err(e.into()),
+            };
+            strategy.allow_any_branch = allow_any_branch;
+            strategy.allow_dirty = allow_dirty;
+            strategy.ignore_head_drift = ignore_head_drift;
+            strategy.push_branch = push_branch;
+            strategy.override_policy = override_policy;
+            strategy.options = ExecuteOptions { no_push, tag_only };
+            let planned = match &plan_file {
+                Some(path) => load_saved_plan(path, &strategy.config.tag_prefix),
+                None => strategy.plan(),
+            };
+            let plan = match planned {
+                Ok(plan) => plan,
+                Err(e @ (ReleaseError::NoCommits { .. } | ReleaseError::NoBump { .. }))
+                    if !fail_on_no_release =>
+                {
+                    info!("no release necessary: {e}");
+                    return Ok(());
                 }
-                Err(e) => {
-                    if dry_run {
-                        eprintln!("warning: {e} (continuing dry-run without GitHub)");
-                        let strategy = build_local_strategy(config, force)?;
-                        let plan = strategy.plan()?;
-                        strategy.execute(&plan, dry_run)?;
-                        plan
-                    } else {
-                        return Err(e);
-                    }
+                Err(e @ ReleaseError::Deferred { .. }) if !fail_on_no_release => {
+                    info!("{e} (pass --override-policy to release now)");
+                    return Ok(());
                 }
+                Err(e) => return Err(e.into()),
             };
+            if dry_run && matches!(format, PlanFormat::Json) {
+                let report = strategy.dry_run_report(&plan)?;
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            if !dry_run && !yes && environment::interactive() {
+                let report = strategy.dry_run_report(&plan)?;
+                let reason = confirm::bump_reason(&plan, &strategy.config);
+                let summary =
+                    confirm::release_summary(&report, &strategy.config, &reason, &stderr_styles);
+                let confirmed = confirm::confirm(
+                    &summary,
+                    &plan,
+                    &strategy.config,
+                    &mut std::io::stdin().lock(),
+                    &mut std::io::stderr(),
+                )?;
+                if !confirmed {
+                    anyhow::bail!("release cancelled");
+                }
+            }
+            let result = strategy.execute(&plan, dry_run);
+            if !dry_run && !no_push {
+                strategy.notify(&plan, result.as_ref().err(), &sr_github::send_webhook);
+            }
+            result?;
+
             // Print structured JSON to stdout (machine-readable; all logs go to stderr)
             #[derive(serde::Serialize)]
             struct ReleaseOutput {
@@ -741,26 +1809,43 @@ async fn run() -> anyhow::Result<()> {
                 bump: String,
                 floating_tag: String,
                 commit_count: usize,
+                remote_release: bool,
             }
             let output = ReleaseOutput {
-                version: plan.next_version.to_string(),
+                version: strategy.config.format_version(&plan.next_version),
                 previous_version: plan
                     .current_version
                     .as_ref()
-                    .map(|v| v.to_string())
+                    .map(|v| strategy.config.format_version(v))
                     .unwrap_or_default(),
                 tag: plan.tag_name.clone(),
                 bump: plan.bump.to_string(),
                 floating_tag: plan.floating_tag_name.as_deref().unwrap_or("").to_string(),
                 commit_count: plan.commits.len(),
+                remote_release: remote_release && !no_push,
             };
             println!("{}", serde_json::to_string(&output)?);
             Ok(())
         }
 
+        Commands::Rollback {
+            tag,
+            package,
+            revert_commit,
+            dry_run,
+        } => {
+            let config = load_config_for_package(config_args, package.as_deref())?;
+            let strategy = release_builder(config, git_backend)
+                .dry_run(dry_run)
+                .build()?
+                .into_strategy();
+            strategy.rollback(&tag, revert_commit, dry_run)?;
+            Ok(())
+        }
+
         // --- AI-powered commands ---
         Commands::Commit(args) => {
-            ensure_hooks_synced();
+            ensure_hooks_synced(config_args);
             sr_ai::commands::commit::run(&args, &backend_config).await
         }
         Commands::Rebase(args) => sr_ai::commands::rebase::run(&args, &backend_config).await,
@@ -772,8 +1857,7 @@ async fn run() -> anyhow::Result<()> {
         Commands::Cache(args) => sr_ai::commands::cache::run(&args),
 
         Commands::Hook { command } => {
-            let config_path = resolve_config_path();
-            let config = ReleaseConfig::load(&config_path)?;
+            let config = load_config(&resolve_config_path(config_args.file)?, config_args)?;
             match command {
                 HookCommands::CommitMsg => {
                     sr_core::hooks::validate_commit_msg(&config)?;
@@ -795,12 +1879,11 @@ async fn main() -> ExitCode {
         Ok(()) => ExitCode::from(0),
         Err(e) => {
             if is_no_release_error(&e) {
-                eprintln!("{e:#}");
-                ExitCode::from(2)
+                info!("{}", error_message(&e));
             } else {
-                eprintln!("error: {e:#}");
-                ExitCode::from(1)
+                error!("{}", error_message(&e));
             }
+            ExitCode::from(exit_code(&e))
         }
     }
 }
@@ -0,0 +1,429 @@
+//! Human-readable reports printed to stdout. Each renderer returns a string so
+//! tests can check the output, with and without colors.
+
+use sr_core::config::ReleaseConfig;
+use sr_core::doctor::{CheckResult, CheckStatus};
+use sr_core::release::{ReleaseComparison, ReleasePlan};
+
+use crate::style::Styles;
+
+/// Hidden marker identifying the `sr plan --comment-pr` comment, so later runs
+/// edit it instead of adding another.
+pub const PLAN_COMMENT_MARKER: &str = "<!-- sr:release-preview -->";
+
+/// Markdown for the `sr plan --comment-pr` pull request comment. `reason`
+/// names the commits driving the bump (see `confirm::bump_reason`).
+pub fn plan_comment(
+    plan: &ReleasePlan,
+    config: &ReleaseConfig,
+    reason: &str,
+    changelog: &str,
+) -> String {
+    let current = plan
+        .current_version
+        .as_ref()
+        .map(|v| format!("`{}`", config.format_version(v)))
+        .unwrap_or_else(|| "none".to_string());
+    format!(
+        "{PLAN_COMMENT_MARKER}\n\
+         ### Release preview: `{}`\n\n\
+         | | |\n|---|---|\n\
+         | Current version | {current} |\n\
+         | Next version | **`{}`** |\n\
+         | Bump | `{}` ({}) |\n\
+         | Commits | {} |\n\n\
+         <details>\n<summary>Changelog preview</summary>\n\n{}\n\n</details>\n",
+        plan.tag_name,
+        config.format_version(&plan.next_version),
+        plan.bump,
+        reason.replace('|', "\\|"),
+        plan.commits.len(),
+        changelog.trim_end()
+    )
+}
+
+/// The `sr plan --comment-pr` comment when merging would not release.
+pub fn no_release_comment(reason: &str) -> String {
+    format!("{PLAN_COMMENT_MARKER}\n### Release preview\n\nNo release: {reason}\n")
+}
+
+/// `sr plan` output: versions, bump, commits, and the changelog preview.
+/// `git_ref` is the `--ref` revision the plan was computed at, if not HEAD.
+pub fn plan(
+    plan: &ReleasePlan,
+    config: &ReleaseConfig,
+    git_ref: Option<&str>,
+    changelog: &str,
+    s: &Styles,
+) -> String {
+    let mut out = String::new();
+    if let Some(git_ref) = git_ref {
+        let sha = &plan.head_sha[..7.min(plan.head_sha.len())];
+        out.push_str(&format!(
+            "Planned at: {git_ref} {}\n",
+            s.dim(format!("({sha})"))
+        ));
+    }
+    if plan.forced {
+        out.push_str(&format!(
+            "Re-releasing existing tag {} (forced)\n",
+            plan.tag_name
+        ));
+    }
+    let current = plan
+        .current_version
+        .as_ref()
+        .map(|v| config.format_version(v))
+        .unwrap_or_else(|| "none".to_string());
+    out.push_str(&format!("Next release: {}\n", s.bold(&plan.tag_name)));
+    out.push_str(&format!("Current version: {current}\n"));
+    out.push_str(&format!(
+        "Next version: {}\n",
+        s.added(config.format_version(&plan.next_version))
+    ));
+    out.push_str(&format!("Bump: {}\n", s.bump(plan.bump)));
+    out.push_str(&format!("Commits ({})\n", plan.commits.len()));
+    for commit in &plan.commits {
+        let scope = commit
+            .scope
+            .as_deref()
+            .map(|s| format!("({s})"))
+            .unwrap_or_default();
+        let kind = format!("{}{scope}", commit.r#type);
+        let kind = if commit.r#type == "feat" {
+            s.added(kind)
+        } else {
+            kind
+        };
+        let breaking = if commit.breaking {
+            format!(" {}", s.danger("BREAKING"))
+        } else {
+            String::new()
+        };
+        let sha = &commit.sha[..7.min(commit.sha.len())];
+        out.push_str(&format!(
+            "  - {kind}: {}{breaking} {}\n",
+            commit.description,
+            s.dim(format!("({sha})"))
+        ));
+    }
+    if !plan.touched_paths.is_empty() {
+        out.push_str("Changes by area\n");
+        let areas: Vec<(String, usize)> = plan
+            .touched_paths
+            .iter()
+            .map(|t| match t.path.as_str() {
+                "." => ("(root)".to_string(), t.commits),
+                dir => (format!("{dir}/"), t.commits),
+            })
+            .collect();
+        let width = areas.iter().map(|(area, _)| area.len()).max().unwrap_or(0);
+        for (area, commits) in areas {
+            out.push_str(&format!("  {area:<width$}  {commits} commit(s)\n"));
+        }
+    }
+    out.push_str(&format!("\nChangelog preview:\n{changelog}\n"));
+    out
+}
+
+/// `sr plan --compare` table: the planned release next to the one before it,
+/// with the change in percent. `sections` are the changelog headings in
+/// order; those empty in both releases are left out.
+pub fn comparison(comparison: &ReleaseComparison, sections: &[&str], s: &Styles) -> String {
+    let Some(previous) = &comparison.previous_release else {
+        return "No previous release to compare with\n".to_string();
+    };
+    let current = &comparison.stats;
+    let mut rows = vec![
+        ("Commits", current.commits, previous.stats.commits),
+        (
+            "Contributors",
+            current.contributors,
+            previous.stats.contributors,
+        ),
+        (
+            "Breaking changes",
+            current.breaking,
+            previous.stats.breaking,
+        ),
+    ];
+    rows.extend(
+        sections
+            .iter()
+            .map(|section| {
+                (
+                    *section,
+                    current.section(section),
+                    previous.stats.section(section),
+                )
+            })
+            .filter(|&(_, now, before)| now > 0 || before > 0),
+    );
+
+    let header = ("", "This release", previous.tag.as_str(), "Change");
+    let label = rows.iter().map(|(l, ..)| l.len()).max().unwrap_or(0);
+    let now = header.1.len();
+    let before = header.2.len().max(
+        rows.iter()
+            .map(|(_, _, b)| b.to_string().len())
+            .max()
+            .unwrap_or(0),
+    );
+    let change = header.3.len();
+    let mut out = format!("Compared with {}\n", s.bold(&previous.tag));
+    out.push_str(&format!(
+        "  {:<label$}  {:>now$}  {:>before$}  {:>change$}\n",
+        header.0, header.1, header.2, header.3
+    ));
+    for (name, current, previous) in rows {
+        out.push_str(&format!(
+            "  {name:<label$}  {current:>now$}  {previous:>before$}  {:>change$}\n",
+            percent_change(current, previous)
+        ));
+    }
+    out
+}
+
+/// `current` against `previous` as a signed whole percentage; `new` when
+/// there was nothing before.
+fn percent_change(current: usize, previous: usize) -> String {
+    if previous == 0 {
+        return if current == 0 { "0%" } else { "new" }.to_string();
+    }
+    let percent = ((current as f64 - previous as f64) * 100.0 / previous as f64).round() as i64;
+    if percent > 0 {
+        format!("+{percent}%")
+    } else {
+        format!("{percent}%")
+    }
+}
+
+/// `sr doctor` table with colored statuses.
+pub fn doctor(checks: &[CheckResult], s: &Styles) -> String {
+    sr_core::doctor::render_table(checks, &|status, text| match status {
+        CheckStatus::Pass => s.added(text),
+        CheckStatus::Warn => s.warning(text),
+        CheckStatus::Fail => s.danger(text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::Version;
+    use sr_core::commit::ConventionalCommit;
+    use sr_core::release::{PreviousRelease, ReleaseStats, SectionStats, TouchedPath};
+    use sr_core::version::BumpLevel;
+
+    use crate::style::ColorChoice;
+
+    fn styles(choice: ColorChoice) -> Styles {
+        Styles::for_stream(choice, false)
+    }
+
+    fn sample_plan() -> ReleasePlan {
+        ReleasePlan {
+            schema_version: sr_core::release::PLAN_SCHEMA_VERSION,
+            current_version: Some(Version::new(1, 2, 3)),
+            next_version: Version::new(2, 0, 0),
+            bump: BumpLevel::Major,
+            commits: vec![ConventionalCommit {
+                sha: "abcdef1234567890".into(),
+                r#type: "feat".into(),
+                scope: Some("api".into()),
+                description: "drop v1".into(),
+                body: None,
+                breaking: true,
+            }],
+            tag_name: "v2.0.0".into(),
+            floating_tag_name: None,
+            prerelease: false,
+            head_sha: "abcdef1234567890".into(),
+            forced: false,
+            touched_paths: Vec::new(),
+            contributors: 0,
+        }
+    }
+
+    #[test]
+    fn plan_renders_plain_text() {
+        assert_eq!(
+            plan(
+                &sample_plan(),
+                &ReleaseConfig::default(),
+                None,
+                "## 2.0.0",
+                &styles(ColorChoice::Never)
+            ),
+            "Next release: v2.0.0\n\
+             Current version: 1.2.3\n\
+             Next version: 2.0.0\n\
+             Bump: major\n\
+             Commits (1)\n  \
+             - feat(api): drop v1 BREAKING (abcdef1)\n\
+             \nChangelog preview:\n## 2.0.0\n"
+        );
+    }
+
+    #[test]
+    fn plan_lists_changes_by_area() {
+        let mut p = sample_plan();
+        p.touched_paths = vec![
+            TouchedPath {
+                path: "crates".into(),
+                commits: 3,
+            },
+            TouchedPath {
+                path: ".".into(),
+                commits: 1,
+            },
+        ];
+        let out = plan(
+            &p,
+            &ReleaseConfig::default(),
+            None,
+            "",
+            &styles(ColorChoice::Never),
+        );
+        assert!(
+            out.contains("Changes by area\n  crates/  3 commit(s)\n  (root)   1 commit(s)\n"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn plan_comment_renders_markdown() {
+        let comment = plan_comment(
+            &sample_plan(),
+            &ReleaseConfig::default(),
+            "1 breaking change(s): feat(api)!: drop v1 | v2",
+            "## 2.0.0\n\n- drop v1\n",
+        );
+        assert_eq!(
+            comment,
+            "<!-- sr:release-preview -->\n\
+             ### Release preview: `v2.0.0`\n\n\
+             | | |\n|---|---|\n\
+             | Current version | `1.2.3` |\n\
+             | Next version | **`2.0.0`** |\n\
+             | Bump | `major` (1 breaking change(s): feat(api)!: drop v1 \\| v2) |\n\
+             | Commits | 1 |\n\n\
+             <details>\n<summary>Changelog preview</summary>\n\n\
+             ## 2.0.0\n\n- drop v1\n\n</details>\n"
+        );
+        assert!(no_release_comment("no commits").starts_with(PLAN_COMMENT_MARKER));
+    }
+
+    #[test]
+    fn plan_shows_calver_versions_as_tagged() {
+        let config = ReleaseConfig::parse("versioning: calver\n").unwrap();
+        let mut p = sample_plan();
+        p.current_version = Some(Version::new(2026, 1, 0));
+        p.next_version = Version::new(2026, 2, 0);
+        p.tag_name = "v2026.02.0".into();
+
+        let comment = plan_comment(&p, &config, "calver", "");
+        assert!(
+            comment.contains("| Current version | `2026.01.0` |"),
+            "{comment}"
+        );
+        assert!(
+            comment.contains("| Next version | **`2026.02.0`** |"),
+            "{comment}"
+        );
+
+        let out = plan(&p, &config, None, "", &styles(ColorChoice::Never));
+        assert!(
+            out.contains("Current version: 2026.01.0\nNext version: 2026.02.0\n"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn plan_colors_breaking_changes() {
+        let styles = styles(ColorChoice::Always);
+        let out = plan(&sample_plan(), &ReleaseConfig::default(), None, "", &styles);
+        assert!(out.contains("\x1b[1;31mBREAKING\x1b[0m"), "{out}");
+        assert!(out.contains("Bump: \x1b[1;31mmajor\x1b[0m"), "{out}");
+        assert!(out.contains("\x1b[2m(abcdef1)\x1b[0m"), "{out}");
+    }
+
+    #[test]
+    fn plan_states_the_analysed_ref() {
+        let out = plan(
+            &sample_plan(),
+            &ReleaseConfig::default(),
+            Some("release/1.x"),
+            "",
+            &styles(ColorChoice::Never),
+        );
+        assert!(
+            out.starts_with("Planned at: release/1.x (abcdef1)\nNext release: v2.0.0\n"),
+            "{out}"
+        );
+    }
+
+    fn stats(commits: usize, contributors: usize, sections: &[(&str, usize)]) -> ReleaseStats {
+        ReleaseStats {
+            commits,
+            contributors,
+            breaking: 0,
+            sections: sections
+                .iter()
+                .map(|&(section, commits)| SectionStats {
+                    section: section.into(),
+                    commits,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn comparison_shows_percentage_changes() {
+        let comparison = ReleaseComparison {
+            stats: stats(5, 2, &[("Features", 3), ("Bug Fixes", 2)]),
+            previous_release: Some(PreviousRelease {
+                tag: "v1.1.0".into(),
+                stats: stats(4, 2, &[("Bug Fixes", 3), ("Documentation", 1)]),
+            }),
+        };
+        let sections = ["Breaking Changes", "Features", "Bug Fixes", "Documentation"];
+        assert_eq!(
+            super::comparison(&comparison, &sections, &styles(ColorChoice::Never)),
+            "Compared with v1.1.0\n\
+             \x20                   This release  v1.1.0  Change\n\
+             \x20 Commits                      5       4    +25%\n\
+             \x20 Contributors                 2       2      0%\n\
+             \x20 Breaking changes             0       0      0%\n\
+             \x20 Features                     3       0     new\n\
+             \x20 Bug Fixes                    2       3    -33%\n\
+             \x20 Documentation                0       1   -100%\n"
+        );
+    }
+
+    #[test]
+    fn comparison_without_a_previous_release() {
+        let comparison = ReleaseComparison {
+            stats: stats(1, 1, &[]),
+            previous_release: None,
+        };
+        assert_eq!(
+            super::comparison(&comparison, &[], &styles(ColorChoice::Never)),
+            "No previous release to compare with\n"
+        );
+    }
+
+    #[test]
+    fn doctor_colors_statuses() {
+        let checks = [CheckResult::fail("git", "missing")];
+        assert_eq!(
+            doctor(&checks, &styles(ColorChoice::Never)),
+            "FAIL  git  missing\n"
+        );
+        let styles = styles(ColorChoice::Always);
+        assert_eq!(
+            doctor(&checks, &styles),
+            "\x1b[1;31mFAIL\x1b[0m  git  missing\n"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! ANSI colors for human-readable output.
+//!
+//! Each output stream gets its own [`Styles`]: colors are used when the stream
+//! is a terminal, unless `--color`, `NO_COLOR`, or `CLICOLOR_FORCE` say
+//! otherwise. JSON output never goes through here.
+
+use std::fmt::Display;
+
+use sr_core::version::BumpLevel;
+
+#[derive(Clone, Copy, Default, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal, honoring NO_COLOR and CLICOLOR_FORCE
+    #[default]
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+/// Styling for one output stream; every method returns plain text when colors are off.
+#[derive(Clone, Copy)]
+pub struct Styles {
+    enabled: bool,
+}
+
+impl Styles {
+    /// Styles for a stream, reading `NO_COLOR` and `CLICOLOR_FORCE` from the environment.
+    pub fn for_stream(choice: ColorChoice, is_terminal: bool) -> Self {
+        Self {
+            enabled: color_enabled(
+                choice,
+                std::env::var("NO_COLOR").ok().as_deref(),
+                std::env::var("CLICOLOR_FORCE").ok().as_deref(),
+                is_terminal,
+            ),
+        }
+    }
+
+    fn paint(&self, code: &str, text: impl Display) -> String {
+        if self.enabled {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn bold(&self, text: impl Display) -> String {
+        self.paint("1", text)
+    }
+
+    pub fn dim(&self, text: impl Display) -> String {
+        self.paint("2", text)
+    }
+
+    /// New versions and added features.
+    pub fn added(&self, text: impl Display) -> String {
+        self.paint("32", text)
+    }
+
+    pub fn warning(&self, text: impl Display) -> String {
+        self.paint("33", text)
+    }
+
+    /// Breaking changes and errors.
+    pub fn danger(&self, text: impl Display) -> String {
+        self.paint("1;31", text)
+    }
+
+    /// A bump level, colored by how disruptive it is.
+    pub fn bump(&self, bump: BumpLevel) -> String {
+        match bump {
+            BumpLevel::Major => self.danger(bump),
+            BumpLevel::Minor => self.added(bump),
+            BumpLevel::Patch => self.bold(bump),
+        }
+    }
+}
+
+/// `--color never` and `--color always` win; otherwise a non-empty `NO_COLOR`
+/// disables colors, a `CLICOLOR_FORCE` other than `0` forces them, and
+/// terminals get them.
+fn color_enabled(
+    choice: ColorChoice,
+    no_color: Option<&str>,
+    clicolor_force: Option<&str>,
+    is_terminal: bool,
+) -> bool {
+    match choice {
+        ColorChoice::Never => false,
+        ColorChoice::Always => true,
+        ColorChoice::Auto => {
+            if no_color.is_some_and(|v| !v.is_empty()) {
+                false
+            } else if clicolor_force.is_some_and(|v| v != "0") {
+                true
+            } else {
+                is_terminal
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_follows_terminal() {
+        assert!(color_enabled(ColorChoice::Auto, None, None, true));
+        assert!(!color_enabled(ColorChoice::Auto, None, None, false));
+    }
+
+    #[test]
+    fn no_color_and_clicolor_force() {
+        assert!(!color_enabled(ColorChoice::Auto, Some("1"), None, true));
+        assert!(color_enabled(ColorChoice::Auto, Some(""), None, true));
+        assert!(color_enabled(ColorChoice::Auto, None, Some("1"), false));
+        assert!(!color_enabled(ColorChoice::Auto, None, Some("0"), false));
+        assert!(!color_enabled(
+            ColorChoice::Auto,
+            Some("1"),
+            Some("1"),
+            true
+        ));
+    }
+
+    #[test]
+    fn explicit_choice_wins() {
+        assert!(color_enabled(ColorChoice::Always, Some("1"), None, false));
+        assert!(!color_enabled(ColorChoice::Never, None, Some("1"), true));
+    }
+
+    #[test]
+    fn plain_styles_leave_text_untouched() {
+        let plain = Styles { enabled: false };
+        assert_eq!(plain.danger("BREAKING"), "BREAKING");
+        assert_eq!(plain.bump(BumpLevel::Major), "major");
+        let styles = Styles { enabled: true };
+        assert_eq!(styles.danger("BREAKING"), "\x1b[1;31mBREAKING\x1b[0m");
+    }
+}
@@ -0,0 +1,169 @@
+//! The GitHub Actions release workflow `sr init --github-workflow` writes.
+
+use std::path::Path;
+
+use sr_core::version_files::cargo_package_name;
+
+/// Where the workflow is written, relative to the repository root.
+pub const WORKFLOW_PATH: &str = ".github/workflows/release.yml";
+
+/// How the project in a directory builds release assets: the command the
+/// action runs after the version bump, and the glob of files it uploads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildStep {
+    pub command: &'static str,
+    pub artifacts: String,
+}
+
+/// The [`BuildStep`] for the project in `dir`, by its manifest: a Cargo
+/// package, an npm package, a Python project, or a Go module.
+pub fn detect_build_step(dir: &Path) -> Option<BuildStep> {
+    if let Some(name) = cargo_package_name(dir) {
+        return Some(BuildStep {
+            command: "cargo build --release",
+            artifacts: format!("target/release/{name}"),
+        });
+    }
+    let command = if dir.join("package.json").is_file() {
+        "npm ci && npm run build"
+    } else if dir.join("pyproject.toml").is_file() {
+        "pipx run build"
+    } else if dir.join("go.mod").is_file() {
+        "go build -o dist/ ./..."
+    } else {
+        return None;
+    };
+    Some(BuildStep {
+        command,
+        artifacts: "dist/*".into(),
+    })
+}
+
+/// A workflow releasing on every push to `branches`. Checkout fetches full
+/// history and tags, and the concurrency group keeps two releases from
+/// running at once. `build`, when known, is wired into the sr step commented
+/// out, for the user to opt into.
+pub fn release_workflow(branches: &[String], build: Option<&BuildStep>) -> String {
+    // A JSON array of strings is a YAML flow sequence, quoted as needed.
+    let branches = serde_json::to_string(branches).expect("strings serialize");
+    let build = match build {
+        Some(step) => format!(
+            "          # Build release assets after the version bump and upload them:\n          \
+             # build-command: {}\n          # artifacts: {}\n",
+            step.command, step.artifacts
+        ),
+        None => String::new(),
+    };
+    format!(
+        r#"name: Release
+
+on:
+  push:
+    branches: {branches}
+
+permissions:
+  contents: write
+
+# One release at a time; a queued run waits instead of cancelling the current one.
+concurrency:
+  group: release-${{{{ github.ref }}}}
+  cancel-in-progress: false
+
+jobs:
+  release:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+          fetch-tags: true
+      - uses: urmzd/sr@v2
+        id: sr
+        with:
+          github-token: ${{{{ secrets.GITHUB_TOKEN }}}}
+{build}"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(workflow: &str) -> serde_yaml_ng::Value {
+        serde_yaml_ng::from_str(workflow).unwrap()
+    }
+
+    #[test]
+    fn workflow_has_the_keys_a_release_needs() {
+        let workflow = parse(&release_workflow(
+            &["main".into(), "release/*".into()],
+            None,
+        ));
+        assert_eq!(
+            workflow["on"]["push"]["branches"],
+            parse("[main, release/*]")
+        );
+        assert_eq!(workflow["permissions"]["contents"], "write");
+        assert_eq!(
+            workflow["concurrency"]["group"],
+            "release-${{ github.ref }}"
+        );
+        assert_eq!(workflow["concurrency"]["cancel-in-progress"], false);
+
+        let steps = &workflow["jobs"]["release"]["steps"];
+        assert_eq!(steps[0]["uses"], "actions/checkout@v4");
+        assert_eq!(steps[0]["with"]["fetch-depth"], 0);
+        assert_eq!(steps[0]["with"]["fetch-tags"], true);
+        assert_eq!(steps[1]["uses"], "urmzd/sr@v2");
+        assert_eq!(
+            steps[1]["with"]["github-token"],
+            "${{ secrets.GITHUB_TOKEN }}"
+        );
+    }
+
+    #[test]
+    fn build_step_is_suggested_commented_out() {
+        let step = BuildStep {
+            command: "cargo build --release",
+            artifacts: "target/release/app".into(),
+        };
+        let text = release_workflow(&["main".into()], Some(&step));
+        assert!(
+            text.contains(
+                "          # build-command: cargo build --release\n          \
+                 # artifacts: target/release/app\n"
+            ),
+            "{text}"
+        );
+        let workflow = parse(&text);
+        let with = workflow["jobs"]["release"]["steps"][1]["with"]
+            .as_mapping()
+            .unwrap();
+        assert_eq!(with.len(), 1, "only github-token is set");
+    }
+
+    #[test]
+    fn build_step_follows_the_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect_build_step(dir.path()), None);
+
+        std::fs::write(dir.path().join("pyproject.toml"), "[project]\n").unwrap();
+        assert_eq!(
+            detect_build_step(dir.path()),
+            Some(BuildStep {
+                command: "pipx run build",
+                artifacts: "dist/*".into(),
+            })
+        );
+
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_build_step(dir.path()).unwrap().artifacts,
+            "target/release/app"
+        );
+    }
+}
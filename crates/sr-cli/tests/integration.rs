@@ -0,0 +1,574 @@
+//! Runs of the `sr` binary against throwaway repositories.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&out.stderr)
+    );
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+/// `sr` in `cwd` with a clean environment for config and providers. The
+/// binary is put on `PATH` for the commit-msg hook sr installs.
+fn sr_command(cwd: &Path, args: &[&str]) -> Command {
+    let bin = Path::new(env!("CARGO_BIN_EXE_sr"));
+    let path = std::env::join_paths(std::iter::once(bin.parent().unwrap().to_path_buf()).chain(
+        std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+    ))
+    .unwrap();
+    let mut command = Command::new(bin);
+    command
+        .current_dir(cwd)
+        .args(args)
+        .env("PATH", path)
+        .env_remove("SR_CONFIG")
+        .env_remove("GITHUB_REF_NAME");
+    command
+}
+
+/// Run [`sr_command`].
+fn sr_output(cwd: &Path, args: &[&str]) -> Output {
+    sr_command(cwd, args).output().unwrap()
+}
+
+/// Like [`sr_output`], asserting that `sr` succeeded.
+fn sr(cwd: &Path, args: &[&str]) -> Output {
+    let out = sr_output(cwd, args);
+    assert!(
+        out.status.success(),
+        "sr {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&out.stderr)
+    );
+    out
+}
+
+/// A repository with an `sr.yaml` and a manifest at its root, one `feat`
+/// commit, a bare `origin`, and an empty nested directory `a/b`.
+fn repo_with_nested_dir() -> (TempDir, TempDir) {
+    let origin = TempDir::new().unwrap();
+    git(origin.path(), &["init", "--bare", "-b", "main"]);
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    git(path, &["init", "-b", "main"]);
+    git(path, &["config", "user.email", "test@test.com"]);
+    git(path, &["config", "user.name", "Test"]);
+    std::fs::write(
+        path.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        path.join("sr.yaml"),
+        "provider: none\nversion_files: [Cargo.toml]\nchangelog:\n  file: CHANGELOG.md\n",
+    )
+    .unwrap();
+    git(path, &["add", "."]);
+    git(path, &["commit", "-m", "feat: initial"]);
+    git(
+        path,
+        &["remote", "add", "origin", origin.path().to_str().unwrap()],
+    );
+    git(path, &["push", "-u", "origin", "main"]);
+    std::fs::create_dir_all(path.join("a/b")).unwrap();
+    (dir, origin)
+}
+
+#[test]
+fn plan_and_changelog_from_a_nested_directory() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let nested = dir.path().join("a/b");
+
+    let out = sr(&nested, &["plan", "--format", "json"]);
+    let plan: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        plan["tag_name"], "v0.2.0",
+        "bumped from the root Cargo.toml"
+    );
+
+    sr(&nested, &["changelog", "--write"]);
+    let changelog = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+    assert!(changelog.contains("initial"), "{changelog}");
+    assert!(!nested.join("CHANGELOG.md").exists());
+}
+
+#[test]
+fn detected_version_files_from_a_subdirectory_keep_paths_in_the_repo() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+    std::fs::write(
+        path.join("sr.yaml"),
+        "provider: none\nchangelog:\n  file: CHANGELOG.md\n",
+    )
+    .unwrap();
+    git(
+        path,
+        &["commit", "--no-verify", "-am", "fix: detect version files"],
+    );
+    let nested = path.join("a/b");
+
+    let out = sr(&nested, &["plan", "--format", "json"]);
+    let plan: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        plan["tag_name"], "v0.2.0",
+        "bumped from the detected Cargo.toml"
+    );
+
+    sr(&nested, &["changelog", "--write"]);
+    let changelog = std::fs::read_to_string(path.join("CHANGELOG.md")).unwrap();
+    assert!(changelog.contains("detect version files"), "{changelog}");
+    assert!(!nested.join("CHANGELOG.md").exists());
+}
+
+#[test]
+fn plan_reports_changes_by_area() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+    std::fs::create_dir_all(path.join("src")).unwrap();
+    std::fs::write(path.join("src/lib.rs"), "").unwrap();
+    git(path, &["add", "."]);
+    git(path, &["commit", "--no-verify", "-m", "fix: library"]);
+
+    let out = sr(path, &["plan", "--format", "json"]);
+    let plan: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        plan["touched_paths"],
+        serde_json::json!([{"path": ".", "commits": 1}, {"path": "src", "commits": 1}])
+    );
+
+    let out = sr(path, &["plan"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("Changes by area\n  (root)  1 commit(s)\n  src/    1 commit(s)\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn plan_compares_with_the_previous_release() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+    git(path, &["tag", "v0.1.0"]);
+    git(
+        path,
+        &["commit", "--allow-empty", "--no-verify", "-m", "fix: one"],
+    );
+    git(
+        path,
+        &[
+            "-c",
+            "user.email=other@test.com",
+            "commit",
+            "--allow-empty",
+            "--no-verify",
+            "-m",
+            "fix: two",
+        ],
+    );
+
+    let out = sr(path, &["plan", "--compare", "--format", "json"]);
+    let plan: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(plan["stats"]["commits"], 2);
+    assert_eq!(plan["stats"]["contributors"], 2);
+    assert_eq!(
+        plan["previous_release"],
+        serde_json::json!({
+            "tag": "v0.1.0",
+            "commits": 1,
+            "contributors": 1,
+            "breaking": 0,
+            "sections": [{"section": "Features", "commits": 1}],
+        })
+    );
+
+    let out = sr(path, &["plan", "--compare"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Compared with v0.1.0\n"), "{stdout}");
+    assert!(
+        stdout.contains("  Contributors                 2       1   +100%\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("  Bug Fixes                    2       0     new\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn release_from_a_nested_directory_commits_root_files() {
+    let (dir, origin) = repo_with_nested_dir();
+
+    let nested = dir.path().join("a/b");
+
+    sr(&nested, &["release", "--yes"]);
+    assert!(dir.path().join(".githooks/commit-msg").exists());
+    assert!(!nested.join(".githooks").exists());
+    assert_eq!(git(origin.path(), &["tag", "--list"]), "v0.2.0");
+    let files = git(dir.path(), &["show", "--name-only", "--format=", "HEAD"]);
+    assert_eq!(
+        files.lines().collect::<Vec<_>>(),
+        ["CHANGELOG.md", "Cargo.toml"]
+    );
+}
+
+#[test]
+fn cwd_flag_points_sr_at_another_checkout() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let elsewhere = TempDir::new().unwrap();
+
+    let out = sr(
+        elsewhere.path(),
+        &[
+            "--cwd",
+            dir.path().join("a").to_str().unwrap(),
+            "plan",
+            "--format",
+            "json",
+        ],
+    );
+    let plan: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(plan["tag_name"], "v0.2.0");
+}
+
+#[test]
+fn tag_prefix_list_spans_a_prefix_migration() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+    git(path, &["tag", "release-2.8.0"]);
+    std::fs::write(
+        path.join("sr.yaml"),
+        "provider: none\ntag_prefix: [v, release-]\nversion_files: [Cargo.toml]\n\
+         changelog:\n  file: CHANGELOG.md\n",
+    )
+    .unwrap();
+    git(path, &["commit", "-am", "feat: after the migration"]);
+
+    let out = sr(path, &["plan", "--format", "json"]);
+    let plan: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(plan["current_version"], "2.8.0");
+    assert_eq!(plan["tag_name"], "v2.9.0");
+
+    git(path, &["tag", "v2.9.0"]);
+    sr(path, &["changelog", "--regenerate", "--write"]);
+    let changelog = std::fs::read_to_string(path.join("CHANGELOG.md")).unwrap();
+    assert!(changelog.contains("## 2.9.0"), "{changelog}");
+    assert!(changelog.contains("## 2.8.0"), "{changelog}");
+}
+
+#[test]
+fn validate_warns_about_types_no_commit_uses() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+    std::fs::write(
+        path.join("sr.yaml"),
+        "provider: none\ntypes_mode: replace\ntypes:\n  - name: feature\n    bump: minor\n    \
+         section: Features\n",
+    )
+    .unwrap();
+
+    let out = sr(path, &["config", "--validate"]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("1 commit(s) of type 'feat' match no configured type"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("configured type(s) 'feature' matched none"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn version_json_reports_whether_a_release_is_needed() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+
+    let out = sr(path, &["version", "--format", "json"]);
+    let status: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(status["current"], serde_json::Value::Null);
+    assert_eq!(status["next"], "0.2.0");
+    assert_eq!(status["bump"], "minor");
+    assert_eq!(status["release_needed"], true);
+    assert_eq!(status["tag"], "v0.2.0");
+
+    git(path, &["tag", "v0.2.0"]);
+    let out = sr(path, &["version", "--format", "json"]);
+    let status: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        status,
+        serde_json::json!({
+            "current": "0.2.0",
+            "next": "0.2.0",
+            "bump": null,
+            "release_needed": false,
+            "tag": "v0.2.0",
+            "floating_tag": null,
+        })
+    );
+}
+
+#[test]
+fn latest_reports_the_newest_tag_and_checks_versions_against_it() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+
+    let out = sr_output(path, &["latest", "--format", "json"]);
+    assert_eq!(out.status.code(), Some(10), "no tag yet");
+    let status: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        status,
+        serde_json::json!({"version": null, "tag": null, "sha": null, "date": null})
+    );
+    sr(path, &["latest", "--check", "0.1.0"]);
+
+    git(path, &["tag", "v1.2.0"]);
+    git(path, &["tag", "release-9.0.0"]);
+    let sha = git(path, &["rev-parse", "HEAD"]);
+    let out = sr(path, &["latest", "--format", "json"]);
+    let status: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(status["version"], "1.2.0");
+    assert_eq!(status["tag"], "v1.2.0");
+    assert_eq!(status["sha"], sha.as_str());
+    assert!(status["date"].is_string(), "{status}");
+
+    let out = sr(path, &["latest", "--prefix", "release-"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.starts_with(&format!("9.0.0 (release-9.0.0, {}, ", &sha[..7])),
+        "{stdout}"
+    );
+
+    sr(path, &["latest", "--check", "v1.3.0"]);
+    let out = sr_output(path, &["latest", "--check", "1.2.0"]);
+    assert_eq!(out.status.code(), Some(11));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("1.2.0 is not newer than the latest release v1.2.0"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn require_ci_refuses_local_releases() {
+    let (dir, origin) = repo_with_nested_dir();
+    let path = dir.path();
+    std::fs::write(
+        path.join("sr.yaml"),
+        "provider: none\nrequire_ci: true\nversion_files: [Cargo.toml]\n",
+    )
+    .unwrap();
+    git(path, &["commit", "-am", "chore: require ci"]);
+    let run = |args: &[&str], ci: Option<&str>| {
+        let mut command = sr_command(path, args);
+        for variable in [
+            "CI",
+            "GITHUB_ACTIONS",
+            "GITLAB_CI",
+            "BUILDKITE",
+            "CIRCLECI",
+            "TF_BUILD",
+            "JENKINS_URL",
+            "TEAMCITY_VERSION",
+            "BITBUCKET_BUILD_NUMBER",
+        ] {
+            command.env_remove(variable);
+        }
+        if let Some(value) = ci {
+            command.env("CI", value);
+        }
+        command.output().unwrap()
+    };
+
+    let out = run(&["release", "--yes"], Some("false"));
+    assert_eq!(out.status.code(), Some(6));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("no CI environment detected (checked CI, GITHUB_ACTIONS, GITLAB_CI"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("--allow-local"), "{stderr}");
+    assert!(run(&["release", "--dry-run"], None).status.success());
+    assert_eq!(git(origin.path(), &["tag", "--list"]), "");
+
+    let out = run(&["release", "--yes"], Some("true"));
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(git(origin.path(), &["tag", "--list"]), "v0.2.0");
+
+    git(
+        path,
+        &["commit", "--no-verify", "--allow-empty", "-m", "fix: local"],
+    );
+    let out = run(&["release", "--yes", "--allow-local"], None);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(git(origin.path(), &["tag", "--list"]).contains("v0.2.1"));
+}
+
+#[test]
+fn default_branch_stands_in_for_main_and_master() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+    git(path, &["branch", "-m", "main", "trunk"]);
+    git(path, &["push", "-u", "origin", "trunk"]);
+    git(path, &["update-ref", "-d", "refs/remotes/origin/main"]);
+    git(path, &["remote", "set-head", "origin", "trunk"]);
+
+    let out = sr_output(path, &["doctor", "--format", "json"]);
+    let checks: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let check = |name: &str| {
+        checks
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["name"] == name)
+            .cloned()
+            .unwrap()
+    };
+    assert_eq!(
+        check("default branch")["detail"],
+        "trunk (from origin/HEAD)"
+    );
+    assert_eq!(check("branch")["status"], "pass", "{checks}");
+
+    sr(path, &["release", "--yes"]);
+    assert!(git(path, &["tag", "--list"]).contains("v0.2.0"));
+}
+
+#[test]
+fn regenerate_skips_broken_tags_and_reads_lenient_ones() {
+    let (dir, _origin) = repo_with_nested_dir();
+    let path = dir.path();
+    let configure = |lenient: bool| {
+        std::fs::write(
+            path.join("sr.yaml"),
+            format!("provider: none\nrepository: o/r\nlenient_tags: {lenient}\n"),
+        )
+        .unwrap();
+    };
+    configure(false);
+    git(path, &["commit", "-am", "chore: configure"]);
+    git(path, &["tag", "v1.1.0"]);
+    git(path, &["commit", "--allow-empty", "-m", "feat: ancient"]);
+    git(path, &["tag", "v1.2"]);
+    git(path, &["tag", "vdemo-build"]);
+    git(path, &["commit", "--allow-empty", "-m", "fix: later"]);
+    git(path, &["tag", "v1.3.0"]);
+    // A tag whose commit was garbage-collected.
+    std::fs::write(
+        path.join(".git/refs/tags/v1.2.5"),
+        format!("{}\n", "1".repeat(40)),
+    )
+    .unwrap();
+
+    let out = sr(path, &["changelog", "--regenerate"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("skipping tag v1.2.5"), "{stderr}");
+    let headings: Vec<&str> = stdout.lines().filter(|l| l.starts_with("## ")).collect();
+    assert_eq!(headings.len(), 2, "{stdout}");
+    assert!(headings[0].starts_with("## [1.3.0]"), "{stdout}");
+    assert!(stdout.contains("/compare/v1.1.0...v1.3.0)"), "{stdout}");
+    assert!(stdout.contains("ancient"), "{stdout}");
+
+    configure(true);
+    let out = sr(path, &["changelog", "--regenerate"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let headings: Vec<&str> = stdout.lines().filter(|l| l.starts_with("## ")).collect();
+    assert_eq!(headings.len(), 3, "{stdout}");
+    assert!(
+        headings[1].starts_with("## [1.2.0](https://github.com/o/r/releases/tag/v1.2)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("/compare/v1.1.0...v1.2)"), "{stdout}");
+    assert!(stdout.contains("/compare/v1.2...v1.3.0)"), "{stdout}");
+}
+
+#[test]
+fn init_prefills_the_config_from_the_project() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    git(path, &["init", "-b", "main"]);
+    std::fs::write(
+        path.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(path.join("CHANGELOG.md"), "# Changelog\n").unwrap();
+    std::fs::create_dir(path.join("dist")).unwrap();
+
+    sr(path, &["init", "--yes"]);
+    let config = std::fs::read_to_string(path.join("sr.yaml")).unwrap();
+    assert!(
+        config.contains("version_files:\n  - Cargo.toml\n"),
+        "{config}"
+    );
+    assert!(config.contains("  file: CHANGELOG.md\n"), "{config}");
+    assert!(config.contains("artifacts: []\n"), "{config}");
+    assert!(config.contains("#   - dist/*\n"), "{config}");
+
+    sr(path, &["init", "--minimal", "--force"]);
+    let config = std::fs::read_to_string(path.join("sr.yaml")).unwrap();
+    assert!(config.contains("version_files: []\n"), "{config}");
+    assert!(config.contains("  file:\n"), "{config}");
+    assert!(!config.contains("#   - dist/*\n"), "{config}");
+}
+
+#[test]
+fn init_writes_a_github_workflow_without_clobbering() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    git(path, &["init", "-b", "main"]);
+    std::fs::write(path.join("sr.yaml"), "branches: [trunk]\n").unwrap();
+    std::fs::write(path.join("go.mod"), "module example.com/app\n").unwrap();
+
+    sr(path, &["init", "--github-workflow"]);
+    let workflow_path = path.join(".github/workflows/release.yml");
+    let workflow = std::fs::read_to_string(&workflow_path).unwrap();
+    assert!(
+        workflow.contains("    branches: [\"trunk\"]\n"),
+        "{workflow}"
+    );
+    assert!(
+        workflow.contains("          fetch-depth: 0\n"),
+        "{workflow}"
+    );
+    assert!(
+        workflow.contains("# build-command: go build -o dist/ ./...\n"),
+        "{workflow}"
+    );
+    assert!(!path.join(".git/hooks/commit-msg").exists());
+
+    std::fs::write(&workflow_path, "# mine\n").unwrap();
+    let output = sr_output(path, &["init", "--github-workflow"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("use --force to overwrite"),
+        "{output:?}"
+    );
+    assert_eq!(std::fs::read_to_string(&workflow_path).unwrap(), "# mine\n");
+
+    sr(path, &["init", "--github-workflow", "--force"]);
+    let workflow = std::fs::read_to_string(&workflow_path).unwrap();
+    assert!(workflow.starts_with("name: Release\n"), "{workflow}");
+}
@@ -0,0 +1,706 @@
+//! Release assets sr produces itself: `.tar.gz` archives assembled from
+//! repository files, files downloaded from URLs, and glob matches uploaded
+//! under a templated name. Plain glob entries are resolved by the release step.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tracing::info;
+
+use crate::config::{
+    ArchiveArtifact, ArtifactEntry, ReleaseConfig, is_file_name, render_url_template,
+};
+use crate::error::ReleaseError;
+
+/// Fetches `url`, sending `token` as `Authorization: Bearer` when given.
+/// Injected so sr-core stays free of an HTTP client.
+pub type Downloader = dyn Fn(&str, Option<&str>) -> Result<Vec<u8>, ReleaseError> + Send + Sync;
+
+/// An asset a release would build or download.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum PlannedArtifact {
+    /// A `.tar.gz` of `files`, relative to `base`.
+    Archive {
+        name: String,
+        files: Vec<String>,
+        #[serde(skip)]
+        base: PathBuf,
+    },
+    /// A download from `url`, with the token from `token_env`.
+    Download {
+        name: String,
+        url: String,
+        token_env: Option<String>,
+    },
+    /// A file matched by a glob with a `rename` template, uploaded as `name`.
+    Renamed { name: String, path: String },
+}
+
+impl PlannedArtifact {
+    /// File name of the uploaded asset.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Archive { name, .. }
+            | Self::Download { name, .. }
+            | Self::Renamed { name, .. } => name,
+        }
+    }
+
+    /// Where the asset comes from, for error messages.
+    fn source(&self) -> &str {
+        match self {
+            Self::Archive { .. } => "an archive entry",
+            Self::Download { url, .. } => url,
+            Self::Renamed { path, .. } => path,
+        }
+    }
+}
+
+/// Assets built or downloaded for one release. The directory holding them is
+/// removed on drop.
+#[derive(Debug, Default)]
+pub struct PreparedArtifacts {
+    dir: Option<PathBuf>,
+    pub files: Vec<String>,
+}
+
+impl Drop for PreparedArtifacts {
+    fn drop(&mut self) {
+        if let Some(ref dir) = self.dir {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// The glob entries of `config.artifacts` whose matches upload under their
+/// own names.
+pub fn globs(config: &ReleaseConfig) -> Vec<String> {
+    config
+        .artifacts
+        .iter()
+        .filter_map(|a| match a {
+            ArtifactEntry::Glob(pattern)
+            | ArtifactEntry::Pattern {
+                path: pattern,
+                rename: None,
+                ..
+            } => Some(pattern.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The glob entries of `config.artifacts` that must match at least one file.
+pub fn required_globs(config: &ReleaseConfig) -> Vec<String> {
+    config
+        .artifacts
+        .iter()
+        .filter_map(|a| match a {
+            ArtifactEntry::Glob(pattern) if config.artifacts_required => Some(pattern.clone()),
+            ArtifactEntry::Pattern { path, required, .. }
+                if required.unwrap_or(config.artifacts_required) =>
+            {
+                Some(path.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The archives, downloads and renamed files `config.artifacts` would
+/// produce, with globs resolved against the current directory and each
+/// archive's file list against its [`base`](ArchiveArtifact::base).
+pub fn planned(
+    config: &ReleaseConfig,
+    version: &str,
+    tag: &str,
+) -> Result<Vec<PlannedArtifact>, ReleaseError> {
+    let mut planned = Vec::new();
+    for artifact in &config.artifacts {
+        match artifact {
+            ArtifactEntry::Glob(_) | ArtifactEntry::Pattern { rename: None, .. } => {}
+            ArtifactEntry::Pattern {
+                path,
+                rename: Some(rename),
+                ..
+            } => {
+                let paths = glob::glob(path).map_err(|e| {
+                    ReleaseError::Artifacts(format!("invalid glob pattern '{path}': {e}"))
+                })?;
+                let mut files: Vec<PathBuf> = paths.flatten().filter(|p| p.is_file()).collect();
+                files.sort();
+                for file in files {
+                    planned.push(PlannedArtifact::Renamed {
+                        name: render_rename(rename, &file, version, tag),
+                        path: file.to_string_lossy().into_owned(),
+                    });
+                }
+            }
+            ArtifactEntry::Archive { archive } => planned.push(PlannedArtifact::Archive {
+                name: render_url_template(&archive.name, version, tag),
+                files: archive_files(&archive.base, archive)?,
+                base: archive.base.clone(),
+            }),
+            ArtifactEntry::Url {
+                url,
+                token_env,
+                name,
+            } => {
+                let url = render_url_template(url, version, tag);
+                let name = match name {
+                    Some(name) => render_url_template(name, version, tag),
+                    None => url_file_name(&url),
+                };
+                planned.push(PlannedArtifact::Download {
+                    name,
+                    url,
+                    token_env: token_env.clone(),
+                });
+            }
+        }
+    }
+    Ok(planned)
+}
+
+/// `rename` with `{basename}` and `{ext}` taken from `file`, then `{version}`
+/// and `{tag}` substituted.
+fn render_rename(rename: &str, file: &Path, version: &str, tag: &str) -> String {
+    let file_name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (basename, ext) = split_extension(&file_name);
+    let name = rename.replace("{basename}", basename).replace("{ext}", ext);
+    render_url_template(&name, version, tag)
+}
+
+/// `name` split before its extension, keeping the dot with the extension.
+/// Compressed tarballs (`.tar.gz` and the like) count as one extension, and a
+/// leading dot does not start one.
+fn split_extension(name: &str) -> (&str, &str) {
+    const TARBALLS: &[&str] = &[".tar.gz", ".tar.xz", ".tar.bz2", ".tar.zst"];
+    if let Some(ext) = TARBALLS
+        .iter()
+        .find(|ext| name.len() > ext.len() && name.ends_with(*ext))
+    {
+        return name.split_at(name.len() - ext.len());
+    }
+    match name.rfind('.') {
+        Some(i) if i > 0 => name.split_at(i),
+        _ => (name, ""),
+    }
+}
+
+/// Fail when two different files would be uploaded under the same asset
+/// name: `globbed` and `extra` upload under their file names, `planned` under
+/// [`PlannedArtifact::name`].
+pub fn check_unique_names(
+    globbed: &[String],
+    planned: &[PlannedArtifact],
+    extra: &[String],
+) -> Result<(), ReleaseError> {
+    let file_name = |path: &str| {
+        Path::new(path)
+            .file_name()
+            .map_or_else(|| path.to_string(), |n| n.to_string_lossy().into_owned())
+    };
+    let uploads = globbed
+        .iter()
+        .map(|path| (file_name(path), path.as_str()))
+        .chain(planned.iter().map(|a| (a.name().to_string(), a.source())))
+        .chain(extra.iter().map(|path| (file_name(path), path.as_str())));
+    let mut seen: std::collections::BTreeMap<String, &str> = std::collections::BTreeMap::new();
+    let mut clashes = Vec::new();
+    for (name, source) in uploads {
+        match seen.get(&name) {
+            Some(first) if *first != source => {
+                clashes.push(format!("{name} (from {first} and {source})"));
+            }
+            Some(_) => {}
+            None => {
+                seen.insert(name, source);
+            }
+        }
+    }
+    if clashes.is_empty() {
+        Ok(())
+    } else {
+        Err(ReleaseError::Artifacts(format!(
+            "several files would be uploaded as {}",
+            clashes.join(", ")
+        )))
+    }
+}
+
+/// Build every archive, download every URL artifact and copy every renamed
+/// file into a fresh temporary directory, named as they will be uploaded.
+pub fn prepare(
+    planned: Vec<PlannedArtifact>,
+    download: Option<&Downloader>,
+) -> Result<PreparedArtifacts, ReleaseError> {
+    if planned.is_empty() {
+        return Ok(PreparedArtifacts::default());
+    }
+    let dir = unique_temp_dir("sr-artifacts")?;
+    let mut prepared = PreparedArtifacts {
+        dir: Some(dir.clone()),
+        files: Vec::new(),
+    };
+    for artifact in planned {
+        // Placeholders such as `{tag}` may expand to a path
+        if !is_file_name(artifact.name()) {
+            return Err(ReleaseError::Artifacts(format!(
+                "asset name '{}' from {} must be a file name, not a path",
+                artifact.name(),
+                artifact.source()
+            )));
+        }
+        let output = dir.join(artifact.name());
+        match artifact {
+            PlannedArtifact::Archive {
+                ref name,
+                ref files,
+                ref base,
+            } => {
+                info!("Building archive {name} ({} file(s))", files.len());
+                write_archive(base, files, &output)?;
+            }
+            PlannedArtifact::Download {
+                ref name,
+                ref url,
+                ref token_env,
+            } => {
+                let download = download.ok_or_else(|| {
+                    ReleaseError::Vcs(format!("cannot download {url}: no HTTP client available"))
+                })?;
+                let token = match token_env {
+                    Some(var) => Some(std::env::var(var).map_err(|_| {
+                        ReleaseError::Vcs(format!("{var} is not set (token_env for {url})"))
+                    })?),
+                    None => None,
+                };
+                info!("Downloading {url} as {name}");
+                let bytes = download(url, token.as_deref())?;
+                fs::write(&output, bytes).map_err(|e| {
+                    ReleaseError::Vcs(format!("failed to write {}: {e}", output.display()))
+                })?;
+            }
+            PlannedArtifact::Renamed { ref name, ref path } => {
+                info!("Uploading {path} as {name}");
+                fs::copy(path, &output).map_err(|e| {
+                    ReleaseError::Artifacts(format!("failed to copy {path} to {name}: {e}"))
+                })?;
+            }
+        }
+        prepared.files.push(output.to_string_lossy().into_owned());
+    }
+    Ok(prepared)
+}
+
+/// Files matched by `archive.paths` under `base`, with directories expanded
+/// and `archive.exclude` applied. Paths are relative to `base`, `/`-separated,
+/// and sorted.
+fn archive_files(base: &Path, archive: &ArchiveArtifact) -> Result<Vec<String>, ReleaseError> {
+    let mut files = std::collections::BTreeSet::new();
+    for pattern in &archive.paths {
+        // glob's trailing `**` yields only subdirectories; directories are
+        // expanded below anyway
+        let pattern = pattern.strip_suffix("/**").unwrap_or(pattern);
+        let full = base.join(pattern).to_string_lossy().into_owned();
+        let paths = glob::glob(&full).map_err(|e| {
+            ReleaseError::BuildCommand(format!("invalid archive path pattern '{pattern}': {e}"))
+        })?;
+        for path in paths.flatten() {
+            collect_files(base, &path, &mut files);
+        }
+    }
+    let files: Vec<String> = files
+        .into_iter()
+        .filter(|file| !is_excluded(file, &archive.exclude))
+        .collect();
+    if files.is_empty() {
+        return Err(ReleaseError::BuildCommand(format!(
+            "archive {}: paths matched no files",
+            archive.name
+        )));
+    }
+    Ok(files)
+}
+
+fn collect_files(base: &Path, path: &Path, files: &mut std::collections::BTreeSet<String>) {
+    if path.file_name().is_some_and(|n| n == ".git") {
+        return;
+    }
+    if path.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                collect_files(base, &entry.path(), files);
+            }
+        }
+    } else if path.is_file() {
+        let relative = path.strip_prefix(base).unwrap_or(path);
+        let name: Vec<_> = relative
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect();
+        files.insert(name.join("/"));
+    }
+}
+
+/// Whether `path` or one of its parent directories matches the `.gitignore`-style
+/// `patterns`. As in git, a file inside an excluded directory cannot be
+/// re-included.
+fn is_excluded(path: &str, patterns: &[String]) -> bool {
+    let parts: Vec<&str> = path.split('/').collect();
+    (1..=parts.len()).any(|i| matches_exclude(&parts[..i].join("/"), i < parts.len(), patterns))
+}
+
+/// The last pattern matching `path` decides; `!pattern` re-includes.
+fn matches_exclude(path: &str, is_dir: bool, patterns: &[String]) -> bool {
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    let mut excluded = false;
+    for raw in patterns {
+        let (negated, pattern) = match raw.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, raw.as_str()),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+        if dir_only && !is_dir {
+            continue;
+        }
+        // Without a slash the pattern matches a name at any depth
+        let target = if pattern.contains('/') {
+            path
+        } else {
+            path.rsplit('/').next().unwrap_or(path)
+        };
+        let Ok(glob) = glob::Pattern::new(pattern.trim_start_matches('/')) else {
+            continue;
+        };
+        if glob.matches_with(target, options) {
+            excluded = !negated;
+        }
+    }
+    excluded
+}
+
+/// Write `files` (relative to `base`) into a gzip-compressed tarball. Headers
+/// are deterministic, so the same files give the same archive.
+fn write_archive(base: &Path, files: &[String], output: &Path) -> Result<(), ReleaseError> {
+    let err = |e: std::io::Error| {
+        ReleaseError::BuildCommand(format!("failed to write archive {}: {e}", output.display()))
+    };
+    let file = fs::File::create(output).map_err(err)?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder.mode(tar::HeaderMode::Deterministic);
+    for name in files {
+        builder
+            .append_path_with_name(base.join(name), name)
+            .map_err(err)?;
+    }
+    builder.into_inner().map_err(err)?.finish().map_err(err)?;
+    Ok(())
+}
+
+/// Last segment of the URL path, ignoring any query or fragment.
+fn url_file_name(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = without_scheme.split_once('/').map_or("", |(_, path)| path);
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("download")
+        .to_string()
+}
+
+/// A new, empty directory under the system temp dir.
+pub(crate) fn unique_temp_dir(prefix: &str) -> Result<PathBuf, ReleaseError> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "{prefix}-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir).map_err(|e| {
+        ReleaseError::BuildCommand(format!("failed to create {}: {e}", dir.display()))
+    })?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(paths: &[&str], exclude: &[&str]) -> ArchiveArtifact {
+        ArchiveArtifact {
+            name: "source-{version}.tar.gz".into(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            exclude: exclude.iter().map(|p| p.to_string()).collect(),
+            base: PathBuf::new(),
+        }
+    }
+
+    fn tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "LICENSE",
+            "src/main.rs",
+            "src/app.log",
+            "src/fixtures/big.bin",
+            "src/fixtures/keep.txt",
+            "src/nested/keep.log",
+            "docs/guide.md",
+        ] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, file).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn archive_files_expand_directories_and_apply_excludes() {
+        let dir = tree();
+        let files = archive_files(
+            dir.path(),
+            &archive(
+                &["src", "LICENSE"],
+                &["*.log", "!nested/keep.log", "fixtures/", "!keep.txt"],
+            ),
+        )
+        .unwrap();
+        // `nested/keep.log` is anchored to the archive root, so it does not
+        // re-include src/nested/keep.log; files in an excluded directory stay out
+        assert_eq!(files, ["LICENSE", "src/main.rs"]);
+
+        let files = archive_files(
+            dir.path(),
+            &archive(&["src/**"], &["*.log", "!src/nested/keep.log"]),
+        )
+        .unwrap();
+        assert_eq!(
+            files,
+            [
+                "src/fixtures/big.bin",
+                "src/fixtures/keep.txt",
+                "src/main.rs",
+                "src/nested/keep.log",
+            ]
+        );
+
+        let err = archive_files(dir.path(), &archive(&["missing/*"], &[])).unwrap_err();
+        assert!(matches!(err, ReleaseError::BuildCommand(_)), "{err}");
+    }
+
+    #[test]
+    fn write_archive_is_a_readable_deterministic_tarball() {
+        let dir = tree();
+        let files = vec!["LICENSE".to_string(), "src/main.rs".to_string()];
+        let first = dir.path().join("a.tar.gz");
+        let second = dir.path().join("b.tar.gz");
+        write_archive(dir.path(), &files, &first).unwrap();
+        write_archive(dir.path(), &files, &second).unwrap();
+        assert_eq!(fs::read(&first).unwrap(), fs::read(&second).unwrap());
+
+        let decoder = flate2::read::GzDecoder::new(fs::File::open(&first).unwrap());
+        let mut entries = Vec::new();
+        for entry in tar::Archive::new(decoder).entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let mut contents = String::new();
+            std::io::Read::read_to_string(&mut entry, &mut contents).unwrap();
+            entries.push((entry.path().unwrap().display().to_string(), contents));
+        }
+        assert_eq!(
+            entries,
+            [
+                ("LICENSE".to_string(), "LICENSE".to_string()),
+                ("src/main.rs".to_string(), "src/main.rs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn planned_renders_names_and_urls() {
+        let config = ReleaseConfig::parse(
+            "artifacts:\n  - dist/*.zip\n  - url: https://ci.example.com/{tag}/app-{version}.zip?sig=1\n  - url: https://ci.example.com/{version}/app.zip\n    name: app-{tag}.zip\n    token_env: CI_TOKEN\n",
+        )
+        .unwrap();
+        assert_eq!(globs(&config), ["dist/*.zip"]);
+        let planned = planned(&config, "1.2.0", "v1.2.0").unwrap();
+        let names: Vec<_> = planned.iter().map(|p| p.name()).collect();
+        assert_eq!(names, ["app-1.2.0.zip", "app-v1.2.0.zip"]);
+        match &planned[1] {
+            PlannedArtifact::Download { url, token_env, .. } => {
+                assert_eq!(url, "https://ci.example.com/1.2.0/app.zip");
+                assert_eq!(token_env.as_deref(), Some("CI_TOKEN"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn archives_are_built_from_the_config_dir() {
+        let dir = tree();
+        let mut config = ReleaseConfig::parse(
+            "artifacts:\n  - archive:\n      name: src.tar.gz\n      paths: [LICENSE, src/main.rs]\n",
+        )
+        .unwrap();
+        config.resolve_paths_relative_to(dir.path());
+        let prepared = prepare(planned(&config, "1.0.0", "v1.0.0").unwrap(), None).unwrap();
+
+        let decoder = flate2::read::GzDecoder::new(fs::File::open(&prepared.files[0]).unwrap());
+        let names: Vec<String> = tar::Archive::new(decoder)
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .collect();
+        assert_eq!(names, ["LICENSE", "src/main.rs"]);
+    }
+
+    #[test]
+    fn prepare_rejects_names_that_expand_to_paths() {
+        let config = ReleaseConfig::parse(
+            "artifacts:\n  - url: https://ci.example.com/app.zip\n    name: \"{tag}.zip\"\n",
+        )
+        .unwrap();
+        let fetch = |_: &str, _: Option<&str>| -> Result<Vec<u8>, ReleaseError> { Ok(vec![]) };
+        let err = prepare(
+            planned(&config, "1.0.0", "pkg/v1.0.0").unwrap(),
+            Some(&fetch),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("'pkg/v1.0.0.zip'"), "{err}");
+        assert!(err.contains("must be a file name"), "{err}");
+    }
+
+    #[test]
+    fn required_globs_follow_the_default_and_per_entry_overrides() {
+        let yaml = "artifacts:\n  - dist/*.tar.gz\n  - path: dist/*.sha256\n    required: true\n  - path: docs/*.pdf\n    required: false\n";
+        let config = ReleaseConfig::parse(yaml).unwrap();
+        assert_eq!(
+            globs(&config),
+            ["dist/*.tar.gz", "dist/*.sha256", "docs/*.pdf"]
+        );
+        assert_eq!(required_globs(&config), ["dist/*.sha256"]);
+
+        let config = ReleaseConfig::parse(&format!("artifacts_required: true\n{yaml}")).unwrap();
+        assert_eq!(required_globs(&config), ["dist/*.tar.gz", "dist/*.sha256"]);
+    }
+
+    #[test]
+    fn prepare_downloads_with_the_injected_client() {
+        let config = ReleaseConfig::parse(
+            "artifacts:\n  - url: https://ci.example.com/{version}/app.zip\n    token_env: SR_ARTIFACTS_TEST_TOKEN\n",
+        )
+        .unwrap();
+        let fetch = |url: &str, token: Option<&str>| -> Result<Vec<u8>, ReleaseError> {
+            Ok(format!("{url} {}", token.unwrap_or("-")).into_bytes())
+        };
+
+        let err = prepare(planned(&config, "1.0.0", "v1.0.0").unwrap(), Some(&fetch)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("SR_ARTIFACTS_TEST_TOKEN is not set"),
+            "{err}"
+        );
+
+        let config =
+            ReleaseConfig::parse("artifacts:\n  - url: https://ci.example.com/{version}/app.zip\n")
+                .unwrap();
+        let prepared = prepare(planned(&config, "1.0.0", "v1.0.0").unwrap(), Some(&fetch)).unwrap();
+        assert_eq!(prepared.files.len(), 1);
+        let path = PathBuf::from(&prepared.files[0]);
+        assert!(path.ends_with("app.zip"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "https://ci.example.com/1.0.0/app.zip -"
+        );
+        let dir = path.parent().unwrap().to_path_buf();
+        drop(prepared);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn renamed_globs_substitute_the_matched_file_name() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("app-linux.tar.gz"), "tgz").unwrap();
+        fs::write(dir.path().join("app-mac.zip"), "zip").unwrap();
+        let pattern = dir.path().join("app-*").to_str().unwrap().to_string();
+        let config = ReleaseConfig::parse(&format!(
+            "artifacts:\n  - path: '{pattern}'\n    rename: '{{basename}}-{{version}}{{ext}}'\n  - path: '{pattern}'\n    rename: '{{tag}}-{{basename}}'\n"
+        ))
+        .unwrap();
+        assert!(globs(&config).is_empty());
+
+        let renamed = planned(&config, "1.2.0", "v1.2.0").unwrap();
+        let names: Vec<_> = renamed.iter().map(|p| p.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "app-linux-1.2.0.tar.gz",
+                "app-mac-1.2.0.zip",
+                "v1.2.0-app-linux",
+                "v1.2.0-app-mac"
+            ]
+        );
+
+        let prepared = prepare(renamed, None).unwrap();
+        let copied = PathBuf::from(&prepared.files[0]);
+        assert!(copied.ends_with("app-linux-1.2.0.tar.gz"));
+        assert_eq!(fs::read_to_string(copied).unwrap(), "tgz");
+    }
+
+    #[test]
+    fn split_extension_keeps_compressed_tarballs_whole() {
+        assert_eq!(split_extension("app.tar.gz"), ("app", ".tar.gz"));
+        assert_eq!(split_extension("app-1.0.zip"), ("app-1.0", ".zip"));
+        assert_eq!(split_extension("README"), ("README", ""));
+        assert_eq!(split_extension(".env"), (".env", ""));
+    }
+
+    #[test]
+    fn check_unique_names_reports_clashing_sources() {
+        let renamed = |name: &str, path: &str| PlannedArtifact::Renamed {
+            name: name.into(),
+            path: path.into(),
+        };
+        let globbed = vec!["dist/app.zip".to_string()];
+        check_unique_names(&globbed, &[renamed("app-1.0.zip", "dist/app.zip")], &[]).unwrap();
+        // The same file matched twice is one upload
+        check_unique_names(&[globbed[0].clone(), globbed[0].clone()], &[], &[]).unwrap();
+
+        let err = check_unique_names(
+            &globbed,
+            &[renamed("app.zip", "build/app.zip")],
+            &["other/app.zip".into()],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "artifacts: several files would be uploaded as app.zip (from dist/app.zip and build/app.zip), app.zip (from dist/app.zip and other/app.zip)"
+        );
+    }
+
+    #[test]
+    fn url_file_name_uses_the_last_path_segment() {
+        assert_eq!(url_file_name("https://x.com/a/b/app.zip?sig=1"), "app.zip");
+        assert_eq!(url_file_name("https://x.com/a/app.zip#frag"), "app.zip");
+        assert_eq!(url_file_name("https://x.com/"), "download");
+    }
+}
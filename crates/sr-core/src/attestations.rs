@@ -0,0 +1,221 @@
+//! Supply-chain attestations: SBOM and provenance files produced by the
+//! configured generators and uploaded alongside the release artifacts.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::config::{HookCommand, ReleaseConfig};
+use crate::error::ReleaseError;
+
+/// An attestation file a release would generate.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedAttestation {
+    pub format: String,
+    /// File name of the upload, `<name>-<version>.<format>`.
+    pub file: String,
+    pub run: HookCommand,
+}
+
+/// Attestation files written for one release. The directory holding them is
+/// removed on drop.
+#[derive(Debug, Default)]
+pub struct GeneratedAttestations {
+    dir: Option<PathBuf>,
+    pub files: Vec<String>,
+}
+
+impl Drop for GeneratedAttestations {
+    fn drop(&mut self) {
+        if let Some(ref dir) = self.dir {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// The files `config.attestations` would generate for `version`; empty when
+/// attestations are disabled.
+pub fn planned(config: &ReleaseConfig, version: &str) -> Vec<PlannedAttestation> {
+    if !config.attestations.enabled {
+        return Vec::new();
+    }
+    let name = base_name(config);
+    config
+        .attestations
+        .generators
+        .iter()
+        .map(|g| {
+            let format = g.format.trim_start_matches('.');
+            PlannedAttestation {
+                format: format.to_string(),
+                file: format!("{name}-{version}.{format}"),
+                run: g.run.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Run every generator with `SR_VERSION` and `SR_TAG` set, saving its stdout
+/// in a fresh temporary directory.
+///
+/// A failing generator is skipped with a warning, unless
+/// `attestations.required` is set.
+pub fn generate(
+    config: &ReleaseConfig,
+    version: &str,
+    tag: &str,
+) -> Result<GeneratedAttestations, ReleaseError> {
+    let planned = planned(config, version);
+    if planned.is_empty() {
+        return Ok(GeneratedAttestations::default());
+    }
+    let dir = crate::artifacts::unique_temp_dir("sr-attestations")?;
+    let mut generated = GeneratedAttestations {
+        dir: Some(dir.clone()),
+        files: Vec::new(),
+    };
+    for attestation in planned {
+        let output = dir.join(&attestation.file);
+        let label = format!("attestation {}", attestation.format);
+        info!("Generating {}: {}", attestation.file, attestation.run);
+        let result = crate::hooks::run_hook_command_to_file(
+            &attestation.run,
+            &label,
+            &[("SR_VERSION", version), ("SR_TAG", tag)],
+            &output,
+        );
+        match result {
+            Ok(()) => generated.files.push(output.to_string_lossy().into_owned()),
+            Err(e) if config.attestations.required => {
+                return Err(ReleaseError::BuildCommand(format!(
+                    "{label}: {e} (attestations.required is set)"
+                )));
+            }
+            Err(e) => warn!("{label}: {e}; {} will not be uploaded", attestation.file),
+        }
+    }
+    Ok(generated)
+}
+
+/// Hex SHA-256 digest of the file at `path`.
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+    Ok(format!("{:x}", Sha256::digest(fs::read(path)?)))
+}
+
+/// Markdown table of the SHA-256 digest of each file, for the release body.
+pub fn digest_table(files: &[String]) -> Result<String, ReleaseError> {
+    let mut table = String::from("### Artifact digests\n\n| File | SHA-256 |\n| --- | --- |\n");
+    for file in files {
+        let digest = sha256_file(Path::new(file))
+            .map_err(|e| ReleaseError::Vcs(format!("failed to read {file} for digest: {e}")))?;
+        table.push_str(&format!("| `{}` | `{digest}` |\n", file_name(file)));
+    }
+    Ok(table)
+}
+
+/// `attestations.name`, else the package name, else the current directory.
+fn base_name(config: &ReleaseConfig) -> String {
+    config
+        .attestations
+        .name
+        .clone()
+        .or_else(|| config.package_name.clone())
+        .or_else(|| {
+            std::env::current_dir()
+                .ok()
+                .and_then(|d| d.file_name().map(|n| n.to_string_lossy().into_owned()))
+        })
+        .unwrap_or_else(|| "release".into())
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AttestationGenerator;
+
+    fn config(generators: &[(&str, &str)], required: bool) -> ReleaseConfig {
+        let mut config = ReleaseConfig::default();
+        config.attestations.enabled = true;
+        config.attestations.required = required;
+        config.attestations.name = Some("app".into());
+        config.attestations.generators = generators
+            .iter()
+            .map(|(format, run)| AttestationGenerator {
+                format: format.to_string(),
+                run: (*run).into(),
+            })
+            .collect();
+        config
+    }
+
+    #[test]
+    fn planned_names_files_after_the_release() {
+        let mut config = config(&[("spdx.json", "syft ."), (".intoto.jsonl", "slsa")], false);
+        let files: Vec<_> = planned(&config, "1.2.0")
+            .into_iter()
+            .map(|p| p.file)
+            .collect();
+        assert_eq!(files, ["app-1.2.0.spdx.json", "app-1.2.0.intoto.jsonl"]);
+
+        config.attestations.name = None;
+        config.package_name = Some("core".into());
+        assert_eq!(planned(&config, "1.2.0")[0].file, "core-1.2.0.spdx.json");
+
+        config.attestations.enabled = false;
+        assert!(planned(&config, "1.2.0").is_empty());
+    }
+
+    #[test]
+    fn generate_saves_stdout_with_release_env() {
+        let config = config(
+            &[("txt", "printf '%s %s' \"$SR_VERSION\" \"$SR_TAG\"")],
+            true,
+        );
+        let generated = generate(&config, "1.2.0", "v1.2.0").unwrap();
+        assert_eq!(generated.files.len(), 1);
+        let path = PathBuf::from(&generated.files[0]);
+        assert!(path.ends_with("app-1.2.0.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1.2.0 v1.2.0");
+
+        let dir = path.parent().unwrap().to_path_buf();
+        drop(generated);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn failing_generator_is_skipped_unless_required() {
+        let generators = [("spdx.json", "exit 3"), ("txt", "echo ok")];
+        let generated = generate(&config(&generators, false), "1.0.0", "v1.0.0").unwrap();
+        assert_eq!(generated.files.len(), 1);
+        assert!(generated.files[0].ends_with("app-1.0.0.txt"));
+
+        let err = generate(&config(&generators, true), "1.0.0", "v1.0.0").unwrap_err();
+        assert!(matches!(err, ReleaseError::BuildCommand(_)), "{err}");
+        assert!(err.to_string().contains("attestation spdx.json"), "{err}");
+    }
+
+    #[test]
+    fn digest_table_lists_file_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.tar.gz");
+        fs::write(&file, "hello").unwrap();
+        let table = digest_table(&[file.to_string_lossy().into_owned()]).unwrap();
+        assert!(table.starts_with("### Artifact digests\n"), "{table}");
+        assert!(
+            table.contains(
+                "| `app.tar.gz` | `2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824` |"
+            ),
+            "{table}"
+        );
+    }
+}
@@ -1,20 +1,155 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeSet;
 
-use serde::Serialize;
+use regex::Regex;
+use schemars::JsonSchema;
+use semver::Version;
+use serde::{Deserialize, Serialize};
 
-use crate::commit::{CommitType, ConventionalCommit};
+use crate::commit::{
+    ChangelogSection, CommitClassifier, ConventionalCommit, DefaultCommitClassifier,
+};
 use crate::error::ReleaseError;
 
 /// A single changelog entry representing a release.
 #[derive(Debug, Clone, Serialize)]
 pub struct ChangelogEntry {
     pub version: String,
+    /// Release tag, e.g. `v1.4.0`; the version heading links to its release page.
+    pub tag_name: Option<String>,
     pub date: String,
     pub commits: Vec<ConventionalCommit>,
     pub compare_url: Option<String>,
     pub repo_url: Option<String>,
 }
 
+/// Where a rendered entry goes. Each has its own `sections` and
+/// `compare_link` settings (see [`ReleaseConfig::entry_for`]).
+///
+/// [`ReleaseConfig::entry_for`]: crate::config::ReleaseConfig::entry_for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Destination {
+    /// The changelog file and the release tag message.
+    Changelog,
+    /// The body of the remote release, `sr notes`, and notifications.
+    Release,
+}
+
+/// What the built-in format does with commits whose type is not in `types`
+/// (`changelog.unknown_types`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnknownTypes {
+    /// Leave them out.
+    #[default]
+    Hide,
+    /// List them under `misc_section`.
+    Misc,
+    /// List them under their own section, `other_section`.
+    OwnSection,
+}
+
+impl UnknownTypes {
+    /// The section commits of unknown types are listed under, if any.
+    pub fn section(self) -> Option<ChangelogSection<'static>> {
+        match self {
+            UnknownTypes::Hide => None,
+            UnknownTypes::Misc => Some(ChangelogSection::Misc),
+            UnknownTypes::OwnSection => Some(ChangelogSection::Other),
+        }
+    }
+}
+
+/// Rewrites applied to every commit description before it is rendered
+/// (`changelog.transform`). Parsed commits are left as they are, so bump
+/// decisions and custom templates' other fields see the original commit.
+///
+/// ```yaml
+/// changelog:
+///   transform:
+///     capitalize_first: true
+///     strip_trailing_period: true
+///     replacements:
+///       - {pattern: "\\b(PROJ-\\d+)", replace: "[$1](https://jira.example.com/browse/$1)"}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct DescriptionTransform {
+    /// Upper-case the first letter, e.g. `add login` -> `Add login`.
+    pub capitalize_first: bool,
+    /// Drop a trailing `.` (an ellipsis is kept).
+    pub strip_trailing_period: bool,
+    /// Regex replacements, applied in order before the other transforms.
+    pub replacements: Vec<Replacement>,
+}
+
+/// One regex replacement in [`DescriptionTransform::replacements`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct Replacement {
+    /// Regex matched against the description; every match is replaced.
+    pub pattern: String,
+    /// Replacement text; `$1` or `${name}` insert capture groups.
+    #[serde(default)]
+    pub replace: String,
+}
+
+impl DescriptionTransform {
+    /// Whether the transform leaves every description unchanged.
+    pub fn is_identity(&self) -> bool {
+        !self.capitalize_first && !self.strip_trailing_period && self.replacements.is_empty()
+    }
+
+    /// `description` with the replacements, then the other transforms, applied.
+    /// Invalid patterns are skipped; config validation reports them.
+    pub fn apply(&self, description: &str) -> String {
+        let mut out = description.to_string();
+        for replacement in &self.replacements {
+            if let Ok(re) = Regex::new(&replacement.pattern) {
+                out = re
+                    .replace_all(&out, replacement.replace.as_str())
+                    .into_owned();
+            }
+        }
+        if self.strip_trailing_period {
+            let trimmed = out.trim_end();
+            if trimmed.ends_with('.') && !trimmed.ends_with("..") {
+                out = trimmed[..trimmed.len() - 1].to_string();
+            }
+        }
+        if self.capitalize_first {
+            let mut chars = out.chars();
+            if let Some(first) = chars.next() {
+                out = first.to_uppercase().chain(chars).collect();
+            }
+        }
+        out
+    }
+
+    /// Record replacements whose pattern is not a valid regex.
+    pub fn check(&self, field: &str, problems: &mut Vec<String>) {
+        for (i, replacement) in self.replacements.iter().enumerate() {
+            if let Err(e) = Regex::new(&replacement.pattern) {
+                problems.push(format!(
+                    "{field}.replacements[{i}].pattern is not a valid regex: {e}"
+                ));
+            }
+        }
+    }
+
+    fn entry(&self, entry: &ChangelogEntry) -> ChangelogEntry {
+        let mut entry = entry.clone();
+        for commit in &mut entry.commits {
+            commit.description = self.apply(&commit.description);
+        }
+        entry
+    }
+}
+
+/// Heading of the section gathering breaking-change footers, with
+/// `changelog.migration_notes`.
+pub const MIGRATION_NOTES_HEADING: &str = "Migration notes";
+
 /// Formats changelog entries into a string representation.
 pub trait ChangelogFormatter: Send + Sync {
     fn format(&self, entries: &[ChangelogEntry]) -> Result<String, ReleaseError>;
@@ -24,97 +159,153 @@ pub trait ChangelogFormatter: Send + Sync {
 /// When a custom template is provided, renders using minijinja.
 pub struct DefaultChangelogFormatter {
     template: Option<String>,
-    types: Vec<CommitType>,
+    classifier: DefaultCommitClassifier,
     breaking_section: String,
     misc_section: String,
+    transform: DescriptionTransform,
+    link_versions: bool,
+    unknown_types: UnknownTypes,
+    other_section: String,
+    migration_notes: bool,
 }
 
 impl DefaultChangelogFormatter {
+    /// Commits are placed in sections by `classifier` (see
+    /// [`CommitClassifier::commit_section`]).
     pub fn new(
         template: Option<String>,
-        types: Vec<CommitType>,
+        classifier: DefaultCommitClassifier,
         breaking_section: String,
         misc_section: String,
     ) -> Self {
         Self {
             template,
-            types,
+            classifier,
             breaking_section,
             misc_section,
+            transform: DescriptionTransform::default(),
+            link_versions: true,
+            unknown_types: UnknownTypes::default(),
+            other_section: "Other".into(),
+            migration_notes: false,
         }
     }
+
+    /// Rewrite commit descriptions with `transform` when rendering.
+    pub fn with_transform(mut self, transform: DescriptionTransform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Whether version headings link to the release page of their tag, for
+    /// entries with both `repo_url` and `tag_name` (default: true).
+    pub fn with_version_links(mut self, link_versions: bool) -> Self {
+        self.link_versions = link_versions;
+        self
+    }
+
+    /// Where commits of types not in `types` go; `other_section` is the
+    /// heading of their own section with [`UnknownTypes::OwnSection`].
+    pub fn with_unknown_types(
+        mut self,
+        unknown_types: UnknownTypes,
+        other_section: String,
+    ) -> Self {
+        self.unknown_types = unknown_types;
+        self.other_section = other_section;
+        self
+    }
+
+    /// Whether each entry ends with a "Migration notes" section gathering the
+    /// `BREAKING CHANGE` footers of its breaking changes (default: false).
+    pub fn with_migration_notes(mut self, migration_notes: bool) -> Self {
+        self.migration_notes = migration_notes;
+        self
+    }
+
+    fn section(&self, commit: &ConventionalCommit) -> Option<ChangelogSection<'_>> {
+        self.classifier
+            .commit_section(commit)
+            .or_else(|| self.unknown_types.section())
+    }
 }
 
 impl ChangelogFormatter for DefaultChangelogFormatter {
     fn format(&self, entries: &[ChangelogEntry]) -> Result<String, ReleaseError> {
+        let transformed: Vec<ChangelogEntry>;
+        let entries = if self.transform.is_identity() {
+            entries
+        } else {
+            transformed = entries.iter().map(|e| self.transform.entry(e)).collect();
+            &transformed
+        };
         if let Some(ref template_str) = self.template {
             return render_template(template_str, entries);
         }
 
         let mut output = String::new();
 
-        // Build ordered list of unique sections, preserving definition order.
-        let mut seen_sections = Vec::new();
-        let mut section_map: BTreeMap<&str, &str> = BTreeMap::new();
-        for ct in &self.types {
-            if let Some(ref section) = ct.section {
-                if !seen_sections.contains(&section.as_str()) {
-                    seen_sections.push(section.as_str());
-                }
-                section_map.insert(&ct.name, section.as_str());
-            }
+        // Breaking changes first, then named sections in definition order, then
+        // the miscellaneous catch-all and the section for unknown types.
+        let mut sections = vec![(ChangelogSection::Breaking, self.breaking_section.as_str())];
+        sections.extend(
+            self.classifier
+                .section_order()
+                .into_iter()
+                .map(|name| (ChangelogSection::Named(name), name)),
+        );
+        sections.push((ChangelogSection::Misc, self.misc_section.as_str()));
+        if self.unknown_types == UnknownTypes::OwnSection {
+            sections.push((ChangelogSection::Other, self.other_section.as_str()));
         }
 
-        // Set of type names that have an explicit mapping (section or no-section).
-        let known_types: BTreeSet<&str> = self.types.iter().map(|t| t.name.as_str()).collect();
-
         for entry in entries {
-            output.push_str(&format!("## {} ({})\n", entry.version, entry.date));
-
-            // 1. Breaking changes section (at the top).
-            let breaking: Vec<_> = entry.commits.iter().filter(|c| c.breaking).collect();
-            if !breaking.is_empty() {
-                output.push_str(&format!("\n### {}\n\n", self.breaking_section));
-                for commit in &breaking {
-                    format_commit_line(&mut output, commit, entry.repo_url.as_deref());
+            let version = match (&entry.repo_url, &entry.tag_name) {
+                (Some(url), Some(tag)) if self.link_versions => {
+                    format!("[{}]({url}/releases/tag/{tag})", entry.version)
                 }
-            }
+                _ => entry.version.clone(),
+            };
+            output.push_str(&format!("## {version} ({})\n", entry.date));
 
-            // 2. Type sections (Features, Bug Fixes, Performance, Documentation, etc.)
-            for section_name in &seen_sections {
-                let commits_in_section: Vec<_> = entry
+            for (section, heading) in &sections {
+                let commits: Vec<_> = entry
                     .commits
                     .iter()
-                    .filter(|c| {
-                        !c.breaking
-                            && section_map
-                                .get(c.r#type.as_str())
-                                .is_some_and(|s| s == section_name)
-                    })
+                    .filter(|c| self.section(c) == Some(*section))
                     .collect();
-
-                if !commits_in_section.is_empty() {
-                    output.push_str(&format!("\n### {section_name}\n\n"));
-                    for commit in &commits_in_section {
+                if !commits.is_empty() {
+                    output.push_str(&format!("\n### {heading}\n\n"));
+                    for (i, commit) in commits.iter().enumerate() {
                         format_commit_line(&mut output, commit, entry.repo_url.as_deref());
+                        // A breaking change's footer explains it under its bullet
+                        if *section == ChangelogSection::Breaking
+                            && let Some(note) = commit.breaking_note()
+                        {
+                            output.push('\n');
+                            push_indented(&mut output, &note);
+                            if i + 1 < commits.len() {
+                                output.push('\n');
+                            }
+                        }
                     }
                 }
             }
 
-            // 3. Miscellaneous catch-all (commits with no section mapping, excluding breaking).
-            let misc: Vec<_> = entry
-                .commits
-                .iter()
-                .filter(|c| {
-                    !c.breaking
-                        && !section_map.contains_key(c.r#type.as_str())
-                        && known_types.contains(c.r#type.as_str())
-                })
-                .collect();
-            if !misc.is_empty() {
-                output.push_str(&format!("\n### {}\n\n", self.misc_section));
-                for commit in &misc {
-                    format_commit_line(&mut output, commit, entry.repo_url.as_deref());
+            if self.migration_notes {
+                // Oldest first: entries list commits newest first
+                let notes: Vec<String> = entry
+                    .commits
+                    .iter()
+                    .rev()
+                    .filter(|c| self.section(c) == Some(ChangelogSection::Breaking))
+                    .filter_map(ConventionalCommit::breaking_note)
+                    .collect();
+                if !notes.is_empty() {
+                    output.push_str(&format!(
+                        "\n### {MIGRATION_NOTES_HEADING}\n\n{}\n",
+                        notes.join("\n\n")
+                    ));
                 }
             }
 
@@ -142,6 +333,161 @@ fn render_template(template_str: &str, entries: &[ChangelogEntry]) -> Result<Str
     Ok(output.trim_end().to_string())
 }
 
+/// A package release rendered as a subsection of the aggregated root changelog:
+/// the entry's `## x.y.z` heading becomes `### {package} x.y.z` and every other
+/// heading moves one level down.
+pub fn package_subsection(package: &str, version: &str, body: &str) -> String {
+    let mut lines = body.lines().peekable();
+    if lines.peek().is_some_and(|line| line.starts_with("## ")) {
+        lines.next();
+    }
+    let mut out = format!("### {package} {version}\n");
+    for line in lines {
+        if line.starts_with('#') {
+            out.push('#');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.trim_end().to_string()
+}
+
+/// Add a package `subsection` to the aggregated root changelog. It goes under
+/// the newest `## {date}` heading when that is today's, otherwise under a new
+/// `## {date}` heading above the newest release.
+pub fn insert_aggregate(existing: &str, date: &str, subsection: &str) -> String {
+    if existing.is_empty() {
+        return format!("# Changelog\n\n## {date}\n\n{subsection}\n");
+    }
+    let mut offset = 0;
+    for line in existing.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            let (before, after) = existing.split_at(offset);
+            return if line.trim_end() == format!("## {date}") {
+                let rest = &after[line.len()..];
+                format!(
+                    "{before}{}\n\n{subsection}\n\n{}",
+                    line.trim_end(),
+                    rest.trim_start_matches('\n')
+                )
+            } else {
+                format!("{before}## {date}\n\n{subsection}\n\n{after}")
+            };
+        }
+        offset += line.len();
+    }
+    format!("{}\n\n## {date}\n\n{subsection}\n", existing.trim_end())
+}
+
+/// Render the aggregated root changelog body from package subsections, given
+/// as `(date, subsection)` pairs newest first. Consecutive releases on the same
+/// date share one `## {date}` heading.
+pub fn aggregate_sections(releases: &[(String, String)]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut current: Option<&str> = None;
+    for (date, subsection) in releases {
+        if current != Some(date.as_str()) {
+            out.push(format!("## {date}"));
+            current = Some(date);
+        }
+        out.push(subsection.clone());
+    }
+    out.join("\n\n")
+}
+
+/// Version of a `## x.y.z` release heading, tolerating `## [x.y.z](url)` and a
+/// leading `v`. Returns `None` for any other line.
+pub fn section_version(line: &str) -> Option<Version> {
+    let rest = line.strip_prefix("## ")?.trim_start();
+    let rest = rest.strip_prefix('[').unwrap_or(rest);
+    let rest = rest.strip_prefix('v').unwrap_or(rest);
+    let end = rest
+        .find(|c: char| c.is_whitespace() || c == ']' || c == '(')
+        .unwrap_or(rest.len());
+    crate::version::parse_tag_version(&rest[..end])
+}
+
+/// Splice rendered release `sections` into an existing changelog.
+///
+/// A section spans from its `## x.y.z` heading to the next `## ` heading. Sections
+/// whose version is in `sections` are replaced in place (later duplicates of that
+/// version are dropped); versions not yet in the file are inserted above the
+/// first older release, or appended. Everything else is kept byte-for-byte.
+pub fn splice_sections(existing: &str, sections: &[(Version, String)]) -> String {
+    let mut pending: Vec<&(Version, String)> = sections.iter().collect();
+    pending.sort_by(|a, b| b.0.cmp(&a.0));
+
+    // Byte offsets of every `## ` heading line.
+    let mut starts = Vec::new();
+    let mut offset = 0;
+    for line in existing.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+    let present: BTreeSet<Version> = starts
+        .iter()
+        .filter_map(|&s| section_version(&existing[s..]))
+        .collect();
+
+    let mut output = String::from(&existing[..starts.first().copied().unwrap_or(existing.len())]);
+    let mut replaced = BTreeSet::new();
+    for (i, &start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).copied().unwrap_or(existing.len());
+        let span = &existing[start..end];
+        let Some(version) = section_version(span) else {
+            output.push_str(span);
+            continue;
+        };
+
+        // New releases newer than this one go above it.
+        while let Some(pos) = pending
+            .iter()
+            .position(|(v, _)| *v > version && !present.contains(v))
+        {
+            output.push_str(pending.remove(pos).1.trim_end());
+            output.push_str("\n\n");
+        }
+
+        if replaced.contains(&version) {
+            continue;
+        }
+        match pending.iter().position(|(v, _)| *v == version) {
+            Some(pos) => {
+                let body = span.trim_end();
+                output.push_str(pending.remove(pos).1.trim_end());
+                output.push_str(&span[body.len()..]);
+                replaced.insert(version);
+            }
+            None => output.push_str(span),
+        }
+    }
+
+    if !pending.is_empty() {
+        if !output.is_empty() {
+            let trimmed = output.trim_end_matches('\n').len();
+            output.truncate(trimmed);
+            output.push_str("\n\n");
+        }
+        let rest: Vec<&str> = pending.iter().map(|(_, s)| s.trim_end()).collect();
+        output.push_str(&rest.join("\n\n"));
+        output.push('\n');
+    }
+    output
+}
+
+/// Append `text` indented under a list item, leaving blank lines empty.
+fn push_indented(output: &mut String, text: &str) {
+    for line in text.lines() {
+        if !line.is_empty() {
+            output.push_str("  ");
+            output.push_str(line);
+        }
+        output.push('\n');
+    }
+}
+
 fn format_commit_line(output: &mut String, commit: &ConventionalCommit, repo_url: Option<&str>) {
     let short_sha = &commit.sha[..7.min(commit.sha.len())];
     let sha_display = match repo_url {
@@ -161,7 +507,6 @@ fn format_commit_line(output: &mut String, commit: &ConventionalCommit, repo_url
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::commit::default_commit_types;
 
     fn make_commit(
         type_: &str,
@@ -182,6 +527,7 @@ mod tests {
     fn entry(commits: Vec<ConventionalCommit>, compare_url: Option<&str>) -> ChangelogEntry {
         ChangelogEntry {
             version: "1.0.0".into(),
+            tag_name: None,
             date: "2025-01-01".into(),
             commits,
             compare_url: compare_url.map(Into::into),
@@ -192,7 +538,7 @@ mod tests {
     fn format(entries: &[ChangelogEntry]) -> String {
         DefaultChangelogFormatter::new(
             None,
-            default_commit_types(),
+            DefaultCommitClassifier::default(),
             "Breaking Changes".into(),
             "Miscellaneous".into(),
         )
@@ -230,6 +576,108 @@ mod tests {
         assert!(out.contains("### Breaking Changes"));
     }
 
+    fn breaking(desc: &str, body: Option<&str>) -> ConventionalCommit {
+        ConventionalCommit {
+            body: body.map(Into::into),
+            ..make_commit("feat", desc, None, true)
+        }
+    }
+
+    #[test]
+    fn breaking_footers_are_indented_under_their_bullet() {
+        // Newest first, as entries list them
+        let commits = vec![
+            breaking(
+                "new config format",
+                Some("BREAKING CHANGE: rename sr.yml to sr.yaml.\n\nRun `sr init --merge` after."),
+            ),
+            breaking("drop node 16", None),
+            breaking(
+                "new API",
+                Some("Rewrite.\n\nBREAKING-CHANGE: /v1 is gone.\nRefs #7"),
+            ),
+        ];
+        let out = format(&[entry(commits.clone(), None)]);
+        assert_eq!(
+            out,
+            "## 1.0.0 (2025-01-01)\n\n### Breaking Changes\n\n\
+             - new config format (abc1234)\n\n  rename sr.yml to sr.yaml.\n\n\
+             \x20 Run `sr init --merge` after.\n\n\
+             - drop node 16 (abc1234)\n\
+             - new API (abc1234)\n\n  /v1 is gone."
+        );
+        assert!(!out.contains(MIGRATION_NOTES_HEADING));
+
+        let out = DefaultChangelogFormatter::new(
+            None,
+            DefaultCommitClassifier::default(),
+            "Breaking Changes".into(),
+            "Miscellaneous".into(),
+        )
+        .with_migration_notes(true)
+        .format(&[entry(commits, Some("https://example.com/compare"))])
+        .unwrap();
+        assert!(
+            out.contains(
+                "### Migration notes\n\n/v1 is gone.\n\n\
+                 rename sr.yml to sr.yaml.\n\nRun `sr init --merge` after.\n\n\
+                 [Full Changelog]"
+            ),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn migration_notes_need_a_footer() {
+        let formatter = DefaultChangelogFormatter::new(
+            None,
+            DefaultCommitClassifier::default(),
+            "Breaking Changes".into(),
+            "Miscellaneous".into(),
+        )
+        .with_migration_notes(true);
+        let out = formatter
+            .format(&[entry(vec![breaking("drop node 16", None)], None)])
+            .unwrap();
+        assert!(out.contains("- drop node 16 (abc1234)"), "{out}");
+        assert!(!out.contains(MIGRATION_NOTES_HEADING), "{out}");
+    }
+
+    #[test]
+    fn format_places_commits_by_rule() {
+        let rules = vec![crate::commit::CommitRule {
+            when: crate::commit::RuleCondition {
+                commit_type: Some("fix".into()),
+                scope: Some("security".into()),
+                ..Default::default()
+            },
+            section: Some("Security".into()),
+            bump: None,
+        }];
+        let formatter = DefaultChangelogFormatter::new(
+            None,
+            DefaultCommitClassifier::default().with_rules(rules),
+            "Breaking Changes".into(),
+            "Miscellaneous".into(),
+        );
+        let out = formatter
+            .format(&[entry(
+                vec![
+                    make_commit("fix", "escape input", Some("security"), false),
+                    make_commit("fix", "null check", None, false),
+                    make_commit("feat", "add button", None, false),
+                ],
+                None,
+            )])
+            .unwrap();
+        assert_eq!(
+            out,
+            "## 1.0.0 (2025-01-01)\n\n### Features\n\n- add button (abc1234)\n\n\
+             ### Bug Fixes\n\n- null check (abc1234)\n\n\
+             ### Security\n\n- **security**: escape input (abc1234)"
+        );
+    }
+
     #[test]
     fn format_mixed_commits() {
         let commits = vec![
@@ -305,6 +753,37 @@ mod tests {
         assert!(out.contains("fix pipeline"));
     }
 
+    #[test]
+    fn unknown_types_are_hidden_or_listed() {
+        let commits = vec![
+            make_commit("feat", "add button", None, false),
+            make_commit("hotfix", "patch prod", None, false),
+        ];
+        let formatter = |unknown_types| {
+            DefaultChangelogFormatter::new(
+                None,
+                DefaultCommitClassifier::default(),
+                "Breaking Changes".into(),
+                "Miscellaneous".into(),
+            )
+            .with_unknown_types(unknown_types, "Sonstiges".into())
+            .format(&[entry(commits.clone(), None)])
+            .unwrap()
+        };
+
+        assert!(!formatter(UnknownTypes::Hide).contains("patch prod"));
+        assert_eq!(
+            formatter(UnknownTypes::Misc),
+            "## 1.0.0 (2025-01-01)\n\n### Features\n\n- add button (abc1234)\n\n\
+             ### Miscellaneous\n\n- patch prod (abc1234)"
+        );
+        assert_eq!(
+            formatter(UnknownTypes::OwnSection),
+            "## 1.0.0 (2025-01-01)\n\n### Features\n\n- add button (abc1234)\n\n\
+             ### Sonstiges\n\n- patch prod (abc1234)"
+        );
+    }
+
     #[test]
     fn format_breaking_excluded_from_type_sections() {
         let commits = vec![
@@ -338,6 +817,97 @@ mod tests {
         assert!(out.contains("### Reverts"));
     }
 
+    #[test]
+    fn transform_rewrites_descriptions() {
+        let transform = DescriptionTransform {
+            capitalize_first: true,
+            strip_trailing_period: true,
+            replacements: vec![
+                Replacement {
+                    pattern: r"^\[internal\] ".into(),
+                    replace: String::new(),
+                },
+                Replacement {
+                    pattern: r"\b(PROJ-\d+)".into(),
+                    replace: "[$1](https://jira/$1)".into(),
+                },
+            ],
+        };
+        assert_eq!(
+            transform.apply("[internal] fix PROJ-12 crash."),
+            "Fix [PROJ-12](https://jira/PROJ-12) crash"
+        );
+        assert_eq!(transform.apply("wait for it..."), "Wait for it...");
+        assert_eq!(transform.apply("élan"), "Élan");
+        assert_eq!(transform.apply(""), "");
+        assert!(DescriptionTransform::default().is_identity());
+    }
+
+    #[test]
+    fn format_applies_transform_to_every_section() {
+        let formatter = DefaultChangelogFormatter::new(
+            None,
+            DefaultCommitClassifier::default(),
+            "Breaking Changes".into(),
+            "Miscellaneous".into(),
+        )
+        .with_transform(DescriptionTransform {
+            capitalize_first: true,
+            strip_trailing_period: true,
+            replacements: vec![],
+        });
+        let commits = vec![
+            make_commit("feat", "drop the v1 api.", None, true),
+            make_commit("fix", "handle empty input.", Some("cli"), false),
+        ];
+        let out = formatter.format(&[entry(commits.clone(), None)]).unwrap();
+        assert!(
+            out.contains("### Breaking Changes\n\n- Drop the v1 api (abc1234)"),
+            "{out}"
+        );
+        assert!(
+            out.contains("- **cli**: Handle empty input (abc1234)"),
+            "{out}"
+        );
+        assert_eq!(
+            commits[0].description, "drop the v1 api.",
+            "input untouched"
+        );
+    }
+
+    #[test]
+    fn version_heading_links_to_the_release_page() {
+        let mut linked = entry(vec![make_commit("feat", "add button", None, false)], None);
+        linked.tag_name = Some("v1.0.0".into());
+        linked.repo_url = Some("https://github.com/o/r".into());
+        let out = format(std::slice::from_ref(&linked));
+        assert!(
+            out.starts_with(
+                "## [1.0.0](https://github.com/o/r/releases/tag/v1.0.0) (2025-01-01)\n"
+            ),
+            "{out}"
+        );
+        assert_eq!(
+            section_version(out.lines().next().unwrap()),
+            Some(Version::new(1, 0, 0))
+        );
+
+        let plain = DefaultChangelogFormatter::new(
+            None,
+            DefaultCommitClassifier::default(),
+            "Breaking Changes".into(),
+            "Miscellaneous".into(),
+        )
+        .with_version_links(false)
+        .format(std::slice::from_ref(&linked))
+        .unwrap();
+        assert!(plain.starts_with("## 1.0.0 (2025-01-01)\n"), "{plain}");
+
+        // Without a repository URL there is nothing to link to.
+        linked.repo_url = None;
+        assert!(format(&[linked]).starts_with("## 1.0.0 (2025-01-01)\n"));
+    }
+
     #[test]
     fn custom_template_renders() {
         let template = r#"{% for entry in entries %}Release {{ entry.version }}
@@ -345,7 +915,7 @@ mod tests {
 {% endfor %}{% endfor %}"#;
         let formatter = DefaultChangelogFormatter::new(
             Some(template.into()),
-            default_commit_types(),
+            DefaultCommitClassifier::default(),
             "Breaking Changes".into(),
             "Miscellaneous".into(),
         );
@@ -372,7 +942,7 @@ mod tests {
 {% endfor %}{% endfor %}"#;
         let formatter = DefaultChangelogFormatter::new(
             Some(template.into()),
-            default_commit_types(),
+            DefaultCommitClassifier::default(),
             "Breaking Changes".into(),
             "Miscellaneous".into(),
         );
@@ -395,7 +965,7 @@ mod tests {
         let template = "{% invalid %}";
         let formatter = DefaultChangelogFormatter::new(
             Some(template.into()),
-            default_commit_types(),
+            DefaultCommitClassifier::default(),
             "Breaking Changes".into(),
             "Miscellaneous".into(),
         );
@@ -414,4 +984,129 @@ mod tests {
         assert!(out.contains("### Features"));
         assert!(out.contains("- add button"));
     }
+
+    fn section(version: &str, body: &str) -> (Version, String) {
+        (
+            Version::parse(version).unwrap(),
+            format!("## {version} (2026-01-01)\n\n{body}"),
+        )
+    }
+
+    const EXISTING: &str = "# Changelog\n\n\
+        ## 1.2.0 (2025-03-01)\n\n- old 1.2.0\n\n\
+        ## 1.1.0 (2025-02-01)\n\n- hand-edited *1.1.0*   \n\n\n\
+        ## 1.0.0 (2025-01-01)\n\n- old 1.0.0\n";
+
+    #[test]
+    fn section_version_parses_heading_variants() {
+        let v = |s: &str| section_version(s).map(|v| v.to_string());
+        assert_eq!(v("## 1.2.3 (2026-01-01)").as_deref(), Some("1.2.3"));
+        assert_eq!(
+            v("## [1.2.3](https://x/compare) - today").as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(v("## v2.0.0-rc.1\n").as_deref(), Some("2.0.0-rc.1"));
+        assert_eq!(v("## Unreleased"), None);
+        assert_eq!(v("### 1.2.3"), None);
+    }
+
+    #[test]
+    fn package_subsection_demotes_headings() {
+        let body = "## 1.2.0 (2026-10-16)\n\n### Features\n\n- login (abc1234)";
+        assert_eq!(
+            package_subsection("api", "1.2.0", body),
+            "### api 1.2.0\n\n#### Features\n\n- login (abc1234)"
+        );
+    }
+
+    #[test]
+    fn insert_aggregate_groups_packages_by_date() {
+        let api = "### api 1.2.0\n\n- login";
+        let first = insert_aggregate("", "2026-10-16", api);
+        assert_eq!(
+            first,
+            "# Changelog\n\n## 2026-10-16\n\n### api 1.2.0\n\n- login\n"
+        );
+
+        let same_day = insert_aggregate(&first, "2026-10-16", "### web 0.3.0\n\n- theme");
+        assert_eq!(
+            same_day,
+            "# Changelog\n\n## 2026-10-16\n\n### web 0.3.0\n\n- theme\n\n### api 1.2.0\n\n- login\n"
+        );
+
+        let next_day = insert_aggregate(&same_day, "2026-10-17", "### api 1.2.1\n\n- fix");
+        assert!(
+            next_day.starts_with(
+                "# Changelog\n\n## 2026-10-17\n\n### api 1.2.1\n\n- fix\n\n## 2026-10-16\n"
+            ),
+            "{next_day}"
+        );
+    }
+
+    #[test]
+    fn aggregate_sections_shares_headings_for_the_same_date() {
+        let releases = [
+            ("2026-10-17".to_string(), "### api 1.2.1".to_string()),
+            ("2026-10-16".to_string(), "### web 0.3.0".to_string()),
+            ("2026-10-16".to_string(), "### api 1.2.0".to_string()),
+        ];
+        assert_eq!(
+            aggregate_sections(&releases),
+            "## 2026-10-17\n\n### api 1.2.1\n\n## 2026-10-16\n\n### web 0.3.0\n\n### api 1.2.0"
+        );
+    }
+
+    #[test]
+    fn splice_replaces_only_covered_sections() {
+        let out = splice_sections(EXISTING, &[section("1.2.0", "- new 1.2.0")]);
+        assert_eq!(
+            out,
+            "# Changelog\n\n\
+             ## 1.2.0 (2026-01-01)\n\n- new 1.2.0\n\n\
+             ## 1.1.0 (2025-02-01)\n\n- hand-edited *1.1.0*   \n\n\n\
+             ## 1.0.0 (2025-01-01)\n\n- old 1.0.0\n"
+        );
+
+        let out = splice_sections(EXISTING, &[section("1.0.0", "- new 1.0.0\n")]);
+        assert!(out.starts_with(&EXISTING[..EXISTING.find("## 1.0.0").unwrap()]));
+        assert!(out.ends_with("## 1.0.0 (2026-01-01)\n\n- new 1.0.0\n"));
+    }
+
+    #[test]
+    fn splice_inserts_missing_versions_in_sorted_position() {
+        let out = splice_sections(
+            EXISTING,
+            &[
+                section("0.9.0", "- first"),
+                section("1.3.0", "- newest"),
+                section("1.1.1", "- patch"),
+            ],
+        );
+        let headings: Vec<_> = out
+            .lines()
+            .filter_map(|l| section_version(l).map(|v| v.to_string()))
+            .collect();
+        assert_eq!(
+            headings,
+            ["1.3.0", "1.2.0", "1.1.1", "1.1.0", "1.0.0", "0.9.0"]
+        );
+        assert!(out.contains("- patch\n\n## 1.1.0"));
+        assert!(out.contains("- hand-edited *1.1.0*   \n\n\n## 1.0.0"));
+        assert!(out.ends_with("- old 1.0.0\n\n## 0.9.0 (2026-01-01)\n\n- first\n"));
+    }
+
+    #[test]
+    fn splice_drops_duplicate_sections_of_replaced_versions() {
+        let existing = "# Changelog\n\n## 1.0.0 (b)\n\n- second\n\n## 1.0.0 (a)\n\n- first\n";
+        let out = splice_sections(existing, &[section("1.0.0", "- only")]);
+        assert_eq!(out, "# Changelog\n\n## 1.0.0 (2026-01-01)\n\n- only\n\n");
+    }
+
+    #[test]
+    fn splice_into_file_without_sections_appends() {
+        let out = splice_sections("# Changelog\n", &[section("1.0.0", "- a")]);
+        assert_eq!(out, "# Changelog\n\n## 1.0.0 (2026-01-01)\n\n- a\n");
+        let out = splice_sections("", &[section("1.0.0", "- a")]);
+        assert_eq!(out, "## 1.0.0 (2026-01-01)\n\n- a\n");
+    }
 }
@@ -1,4 +1,7 @@
+use std::sync::LazyLock;
+
 use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::error::ReleaseError;
@@ -8,11 +11,17 @@ use crate::version::BumpLevel;
 #[derive(Debug, Clone)]
 pub struct Commit {
     pub sha: String,
+    /// Author email; empty when unknown.
+    pub author: String,
     pub message: String,
+    /// Files the commit changed against its first parent, relative to the
+    /// repository root. Empty unless the repository was opened to read them
+    /// (`with_changed_paths` on the git backends).
+    pub paths: Vec<String>,
 }
 
 /// A commit parsed according to the Conventional Commits specification.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConventionalCommit {
     pub sha: String,
     pub r#type: String,
@@ -22,10 +31,41 @@ pub struct ConventionalCommit {
     pub breaking: bool,
 }
 
+/// A footer line of a commit body: `Token: value` or `Token #value`, where the
+/// token is a word (hyphens allowed) or `BREAKING CHANGE`.
+static FOOTER_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:[A-Za-z][\w-]*|BREAKING CHANGE)(?:: | #)").expect("valid footer regex")
+});
+
+impl ConventionalCommit {
+    /// Text of the `BREAKING CHANGE:` (or `BREAKING-CHANGE:`) footer, from the
+    /// token up to the next footer or the end of the body, paragraphs kept.
+    /// None without such a footer, e.g. for a commit marked breaking by `!`.
+    pub fn breaking_note(&self) -> Option<String> {
+        let mut lines = self.body.as_deref()?.lines();
+        let first = lines.by_ref().find_map(|line| {
+            let line = line.trim_start();
+            line.strip_prefix("BREAKING CHANGE:")
+                .or_else(|| line.strip_prefix("BREAKING-CHANGE:"))
+        })?;
+        let mut note = vec![first.trim()];
+        note.extend(
+            lines
+                .take_while(|line| !FOOTER_LINE.is_match(line.trim_start()))
+                .map(str::trim_end),
+        );
+        let note = note.join("\n").trim().to_string();
+        (!note.is_empty()).then_some(note)
+    }
+}
+
 /// Describes a recognised commit type.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct CommitType {
+    /// Commit type as written in the subject (e.g. `feat`, `fix`).
     pub name: String,
+    /// Version bump triggered by this type. None = no release.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bump: Option<BumpLevel>,
     /// Changelog section heading (e.g. "Features"). None = exclude from changelog.
@@ -33,13 +73,125 @@ pub struct CommitType {
     pub section: Option<String>,
 }
 
+/// Overrides the `types` mapping for commits matching `when`, e.g. listing
+/// `fix(security)` commits under "Security" with at least a minor bump.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CommitRule {
+    /// Conditions a commit must meet, all of them, for the rule to apply.
+    pub when: RuleCondition,
+    /// Changelog section heading for matching commits, taking precedence over
+    /// the type's section and the breaking changes section.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// Lowest bump for matching commits. Raises the type's bump, never lowers it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bump: Option<BumpLevel>,
+}
+
+/// What a [`CommitRule`] matches on. Unset fields match any commit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RuleCondition {
+    /// Commit type, e.g. `fix`.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub commit_type: Option<String>,
+    /// Scope, matched exactly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Regex the scope must match, e.g. `^(security|auth)$`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope_regex: Option<String>,
+    /// Whether the commit is a breaking change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breaking: Option<bool>,
+}
+
+impl RuleCondition {
+    /// Whether `commit` meets every condition set. A commit without a scope
+    /// never matches a scope condition; an invalid `scope_regex` (rejected
+    /// when the config loads) matches nothing.
+    pub fn matches(&self, commit: &ConventionalCommit) -> bool {
+        let scope = commit.scope.as_deref();
+        self.commit_type
+            .as_ref()
+            .is_none_or(|t| *t == commit.r#type)
+            && self.scope.as_deref().is_none_or(|s| scope == Some(s))
+            && self.scope_regex.as_deref().is_none_or(|pattern| {
+                scope.is_some_and(|scope| Regex::new(pattern).is_ok_and(|re| re.is_match(scope)))
+            })
+            && self.breaking.is_none_or(|b| b == commit.breaking)
+    }
+}
+
+/// Where a commit is listed in the built-in changelog format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangelogSection<'a> {
+    /// The breaking changes section.
+    Breaking,
+    /// A section named by a rule or commit type.
+    Named(&'a str),
+    /// The miscellaneous section, for configured types without a section.
+    Misc,
+    /// The section for types not in `types`, with `changelog.unknown_types:
+    /// own-section`.
+    Other,
+}
+
 /// Single source of truth for commit type classification.
 pub trait CommitClassifier: Send + Sync {
     fn types(&self) -> &[CommitType];
 
+    /// Rules checked before the `types` lookup, first match wins.
+    fn rules(&self) -> &[CommitRule] {
+        &[]
+    }
+
     /// Commit message regex with named groups: type, scope, breaking, description.
     fn pattern(&self) -> &str;
 
+    /// The first rule matching `commit`.
+    fn rule_for(&self, commit: &ConventionalCommit) -> Option<&CommitRule> {
+        self.rules().iter().find(|rule| rule.when.matches(commit))
+    }
+
+    /// Bump for `commit`: its type's (or major when breaking), raised to a
+    /// matching rule's bump.
+    fn commit_bump(&self, commit: &ConventionalCommit) -> Option<BumpLevel> {
+        let bump = self.bump_level(&commit.r#type, commit.breaking);
+        bump.max(self.rule_for(commit).and_then(|rule| rule.bump))
+    }
+
+    /// Changelog section for `commit`: a matching rule's section, then the
+    /// breaking changes section, then its type's. None for a type not in
+    /// `types`, which the built-in format leaves out.
+    fn commit_section(&self, commit: &ConventionalCommit) -> Option<ChangelogSection<'_>> {
+        if let Some(section) = self.rule_for(commit).and_then(|r| r.section.as_deref()) {
+            return Some(ChangelogSection::Named(section));
+        }
+        if commit.breaking {
+            return Some(ChangelogSection::Breaking);
+        }
+        let commit_type = self.types().iter().find(|t| t.name == commit.r#type)?;
+        Some(match commit_type.section.as_deref() {
+            Some(section) => ChangelogSection::Named(section),
+            None => ChangelogSection::Misc,
+        })
+    }
+
+    /// Named section headings in the order the built-in format writes them:
+    /// those of `types`, then those only `rules` use.
+    fn section_order(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        let sections = self.types().iter().filter_map(|t| t.section.as_deref());
+        for section in sections.chain(self.rules().iter().filter_map(|r| r.section.as_deref())) {
+            if !names.contains(&section) {
+                names.push(section);
+            }
+        }
+        names
+    }
+
     fn bump_level(&self, type_name: &str, breaking: bool) -> Option<BumpLevel> {
         if breaking {
             return Some(BumpLevel::Major);
@@ -65,20 +217,58 @@ pub trait CommitClassifier: Send + Sync {
 pub const DEFAULT_COMMIT_PATTERN: &str =
     r"^(?P<type>\w+)(?:\((?P<scope>[^)]+)\))?(?P<breaking>!)?:\s+(?P<description>.+)";
 
+/// Compile a commit pattern, requiring the `type` and `description` groups.
+fn compile_pattern(pattern: &str) -> Result<Regex, ReleaseError> {
+    let re = Regex::new(pattern)
+        .map_err(|e| ReleaseError::Config(format!("invalid commit_pattern: {e}")))?;
+    for group in ["type", "description"] {
+        if !re.capture_names().any(|name| name == Some(group)) {
+            return Err(ReleaseError::Config(format!(
+                "commit_pattern must contain a named group (?P<{group}>...)"
+            )));
+        }
+    }
+    Ok(re)
+}
+
+#[derive(Clone)]
 pub struct DefaultCommitClassifier {
     types: Vec<CommitType>,
-    pattern: String,
+    rules: Vec<CommitRule>,
+    regex: Regex,
 }
 
 impl DefaultCommitClassifier {
-    pub fn new(types: Vec<CommitType>, pattern: String) -> Self {
-        Self { types, pattern }
+    /// Fails if `pattern` is not a valid regex with `type` and `description` groups.
+    pub fn new(types: Vec<CommitType>, pattern: &str) -> Result<Self, ReleaseError> {
+        Ok(Self {
+            types,
+            rules: Vec::new(),
+            regex: compile_pattern(pattern)?,
+        })
+    }
+
+    /// Check `rules` before the `types` lookup.
+    pub fn with_rules(mut self, rules: Vec<CommitRule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// A parser sharing this classifier's compiled pattern.
+    pub fn parser(&self) -> DefaultCommitParser {
+        DefaultCommitParser {
+            regex: self.regex.clone(),
+        }
     }
 }
 
 impl Default for DefaultCommitClassifier {
     fn default() -> Self {
-        Self::new(default_commit_types(), DEFAULT_COMMIT_PATTERN.into())
+        Self {
+            types: default_commit_types(),
+            rules: Vec::new(),
+            regex: DefaultCommitParser::default().regex,
+        }
     }
 }
 
@@ -86,8 +276,11 @@ impl CommitClassifier for DefaultCommitClassifier {
     fn types(&self) -> &[CommitType] {
         &self.types
     }
+    fn rules(&self) -> &[CommitRule] {
+        &self.rules
+    }
     fn pattern(&self) -> &str {
-        &self.pattern
+        self.regex.as_str()
     }
 }
 
@@ -156,32 +349,44 @@ pub trait CommitParser: Send + Sync {
     fn parse(&self, commit: &Commit) -> Result<ConventionalCommit, ReleaseError>;
 }
 
-/// Default parser using the built-in `DEFAULT_COMMIT_PATTERN` regex.
-pub struct DefaultCommitParser;
+/// Parser for a commit pattern (named groups: type, scope, breaking,
+/// description), compiled once when the parser is built.
+#[derive(Clone)]
+pub struct DefaultCommitParser {
+    regex: Regex,
+}
+
+impl DefaultCommitParser {
+    /// Fails if `pattern` is not a valid regex with `type` and `description` groups.
+    pub fn new(pattern: &str) -> Result<Self, ReleaseError> {
+        Ok(Self {
+            regex: compile_pattern(pattern)?,
+        })
+    }
+}
+
+impl Default for DefaultCommitParser {
+    /// Parser for [`DEFAULT_COMMIT_PATTERN`].
+    fn default() -> Self {
+        Self::new(DEFAULT_COMMIT_PATTERN).expect("DEFAULT_COMMIT_PATTERN is valid")
+    }
+}
 
 impl CommitParser for DefaultCommitParser {
     fn parse(&self, commit: &Commit) -> Result<ConventionalCommit, ReleaseError> {
-        let re =
-            Regex::new(DEFAULT_COMMIT_PATTERN).map_err(|e| ReleaseError::Config(e.to_string()))?;
-
-        let caps = re.captures(&commit.message).ok_or_else(|| {
+        let caps = self.regex.captures(&commit.message).ok_or_else(|| {
             ReleaseError::Config(format!("not a conventional commit: {}", commit.message))
         })?;
 
-        let r#type = caps.name("type").unwrap().as_str().to_string();
-        let scope = caps.name("scope").map(|m| m.as_str().to_string());
+        // Both groups are guaranteed by `compile_pattern`, but an optional
+        // group in a custom pattern may still not participate in the match
+        let group = |name| caps.name(name).map_or("", |m| m.as_str());
         let breaking = caps.name("breaking").is_some();
-        let description = caps.name("description").unwrap().as_str().to_string();
-
-        let body = commit
-            .message
-            .split_once("\n\n")
-            .map(|x| x.1)
-            .map(|b| b.to_string());
+        let body = commit.message.split_once("\n\n").map(|x| x.1);
 
         // Detect BREAKING CHANGE / BREAKING-CHANGE footers in the body
         let breaking = breaking
-            || body.as_deref().is_some_and(|b| {
+            || body.is_some_and(|b| {
                 b.lines().any(|line| {
                     let trimmed = line.trim();
                     trimmed.starts_with("BREAKING CHANGE:")
@@ -193,15 +398,114 @@ impl CommitParser for DefaultCommitParser {
 
         Ok(ConventionalCommit {
             sha: commit.sha.clone(),
-            r#type,
-            scope,
-            description,
-            body,
+            r#type: group("type").to_string(),
+            scope: caps.name("scope").map(|m| m.as_str().to_string()),
+            description: group("description").to_string(),
+            body: body.map(str::to_string),
             breaking,
         })
     }
 }
 
+/// How a range of commits lines up with the configured `types`, for spotting
+/// a type list that silently drops commits (e.g. `feature` configured while
+/// everyone writes `feat`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TypeUsage {
+    /// Number of conventional commits analysed.
+    pub commits: usize,
+    /// Configured types no commit used, in config order.
+    pub unused: Vec<String>,
+    /// Types used by commits but not configured, with how many commits used
+    /// each, most used first. These commits trigger no bump and are left out
+    /// of changelogs.
+    pub unconfigured: Vec<(String, usize)>,
+    /// Type names configured more than once; only the first entry is used.
+    pub duplicates: Vec<String>,
+    /// Section heading commits of unconfigured types are listed under
+    /// (`changelog.unknown_types`). None = left out of changelogs.
+    pub unknown_section: Option<String>,
+}
+
+impl TypeUsage {
+    pub fn analyze(types: &[CommitType], commits: &[ConventionalCommit]) -> Self {
+        let configured = |name: &str| types.iter().find(|t| t.name == name);
+
+        let mut duplicates: Vec<String> = Vec::new();
+        for (i, t) in types.iter().enumerate() {
+            if types[..i].iter().any(|u| u.name == t.name) && !duplicates.contains(&t.name) {
+                duplicates.push(t.name.clone());
+            }
+        }
+
+        let mut unused: Vec<String> = Vec::new();
+        for t in types {
+            if !commits.iter().any(|c| c.r#type == t.name) && !unused.contains(&t.name) {
+                unused.push(t.name.clone());
+            }
+        }
+
+        let mut unconfigured: Vec<(String, usize)> = Vec::new();
+        for c in commits.iter().filter(|c| configured(&c.r#type).is_none()) {
+            match unconfigured.iter_mut().find(|(name, _)| *name == c.r#type) {
+                Some((_, count)) => *count += 1,
+                None => unconfigured.push((c.r#type.clone(), 1)),
+            }
+        }
+        unconfigured.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        Self {
+            commits: commits.len(),
+            unused,
+            unconfigured,
+            duplicates,
+            unknown_section: None,
+        }
+    }
+
+    /// Report commits of unconfigured types as listed under `section`.
+    pub fn with_unknown_section(mut self, section: Option<&str>) -> Self {
+        self.unknown_section = section.map(Into::into);
+        self
+    }
+
+    /// One line per problem worth a warning.
+    ///
+    /// Unused types alone are normal (not every range has a `perf` commit), so
+    /// they are only reported next to commits of an unconfigured type, where
+    /// they are likely the misspelling.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for name in &self.duplicates {
+            warnings.push(format!(
+                "type '{name}' is configured more than once; only the first entry is used"
+            ));
+        }
+        let listed = match &self.unknown_section {
+            Some(section) => format!("are listed under '{section}'"),
+            None => "are left out of changelogs".into(),
+        };
+        for (name, count) in &self.unconfigured {
+            warnings.push(format!(
+                "{count} commit(s) of type '{name}' match no configured type; \
+                 they trigger no bump and {listed}"
+            ));
+        }
+        if !self.unused.is_empty() && !self.unconfigured.is_empty() {
+            warnings.push(format!(
+                "configured type(s) {} matched none of the {} commit(s) analysed",
+                self.unused
+                    .iter()
+                    .map(|n| format!("'{n}'"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                self.commits
+            ));
+        }
+        warnings
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,13 +513,17 @@ mod tests {
     fn raw(message: &str) -> Commit {
         Commit {
             sha: "abc1234".into(),
+            author: String::new(),
             message: message.into(),
+            paths: Vec::new(),
         }
     }
 
     #[test]
     fn parse_simple_feat() {
-        let result = DefaultCommitParser.parse(&raw("feat: add button")).unwrap();
+        let result = DefaultCommitParser::default()
+            .parse(&raw("feat: add button"))
+            .unwrap();
         assert_eq!(result.r#type, "feat");
         assert_eq!(result.description, "add button");
         assert_eq!(result.scope, None);
@@ -224,7 +532,7 @@ mod tests {
 
     #[test]
     fn parse_scoped_fix() {
-        let result = DefaultCommitParser
+        let result = DefaultCommitParser::default()
             .parse(&raw("fix(core): null check"))
             .unwrap();
         assert_eq!(result.r#type, "fix");
@@ -233,13 +541,15 @@ mod tests {
 
     #[test]
     fn parse_breaking_bang() {
-        let result = DefaultCommitParser.parse(&raw("feat!: new API")).unwrap();
+        let result = DefaultCommitParser::default()
+            .parse(&raw("feat!: new API"))
+            .unwrap();
         assert!(result.breaking);
     }
 
     #[test]
     fn parse_with_body() {
-        let result = DefaultCommitParser
+        let result = DefaultCommitParser::default()
             .parse(&raw("fix: x\n\ndetails"))
             .unwrap();
         assert_eq!(result.body.as_deref(), Some("details"));
@@ -247,7 +557,7 @@ mod tests {
 
     #[test]
     fn parse_breaking_change_footer() {
-        let result = DefaultCommitParser
+        let result = DefaultCommitParser::default()
             .parse(&raw(
                 "feat: new API\n\nBREAKING CHANGE: removed old endpoint",
             ))
@@ -258,7 +568,7 @@ mod tests {
 
     #[test]
     fn parse_breaking_change_hyphenated_footer() {
-        let result = DefaultCommitParser
+        let result = DefaultCommitParser::default()
             .parse(&raw("fix: update schema\n\nBREAKING-CHANGE: field renamed"))
             .unwrap();
         assert!(result.breaking);
@@ -267,7 +577,7 @@ mod tests {
     #[test]
     fn parse_breaking_change_footer_with_bang() {
         // Both bang and footer — should still be breaking
-        let result = DefaultCommitParser
+        let result = DefaultCommitParser::default()
             .parse(&raw(
                 "feat!: overhaul\n\nBREAKING CHANGE: everything changed",
             ))
@@ -275,10 +585,41 @@ mod tests {
         assert!(result.breaking);
     }
 
+    #[test]
+    fn breaking_note_reads_the_footer_paragraphs() {
+        let parse = |message: &str| DefaultCommitParser::default().parse(&raw(message)).unwrap();
+
+        let commit = parse(
+            "feat(api)!: drop v1\n\nLong explanation.\n\n\
+             BREAKING CHANGE: the /v1 routes are gone.\n\
+             Point clients at /v2.\n\n\
+             Tokens issued before 2.0 must be reissued.\n\
+             Refs #42\n\
+             Signed-off-by: Dev <dev@example.com>",
+        );
+        assert_eq!(
+            commit.breaking_note().as_deref(),
+            Some(
+                "the /v1 routes are gone.\nPoint clients at /v2.\n\n\
+                 Tokens issued before 2.0 must be reissued."
+            )
+        );
+
+        let commit = parse("fix: rename\n\nBREAKING-CHANGE:\n  `id` is now `key`\n");
+        assert_eq!(commit.breaking_note().as_deref(), Some("`id` is now `key`"));
+
+        assert_eq!(parse("feat!: overhaul").breaking_note(), None);
+        assert_eq!(
+            parse("feat!: overhaul\n\nBREAKING CHANGE:  ").breaking_note(),
+            None
+        );
+        assert_eq!(parse("fix: tweak\n\nbody only").breaking_note(), None);
+    }
+
     #[test]
     fn parse_no_breaking_change_in_body() {
         // Body text that mentions "BREAKING CHANGE" but not as a footer line
-        let result = DefaultCommitParser
+        let result = DefaultCommitParser::default()
             .parse(&raw("fix: tweak\n\nThis is not a BREAKING CHANGE footer"))
             .unwrap();
         assert!(!result.breaking);
@@ -286,7 +627,7 @@ mod tests {
 
     #[test]
     fn parse_invalid_message() {
-        let result = DefaultCommitParser.parse(&raw("not conventional"));
+        let result = DefaultCommitParser::default().parse(&raw("not conventional"));
         assert!(result.is_err());
     }
 
@@ -337,6 +678,86 @@ mod tests {
         assert_eq!(c.changelog_section("unknown"), None);
     }
 
+    fn scoped(type_: &str, scope: Option<&str>, breaking: bool) -> ConventionalCommit {
+        ConventionalCommit {
+            sha: "abc1234".into(),
+            r#type: type_.into(),
+            scope: scope.map(Into::into),
+            description: "test".into(),
+            body: None,
+            breaking,
+        }
+    }
+
+    fn security_rules() -> Vec<CommitRule> {
+        vec![
+            CommitRule {
+                when: RuleCondition {
+                    commit_type: Some("fix".into()),
+                    scope: Some("security".into()),
+                    ..Default::default()
+                },
+                section: Some("Security".into()),
+                bump: Some(BumpLevel::Minor),
+            },
+            CommitRule {
+                when: RuleCondition {
+                    scope_regex: Some("^deps?$".into()),
+                    breaking: Some(false),
+                    ..Default::default()
+                },
+                section: Some("Dependencies".into()),
+                bump: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn rules_override_sections_and_raise_bumps() {
+        let c = DefaultCommitClassifier::default().with_rules(security_rules());
+
+        let security = scoped("fix", Some("security"), false);
+        assert_eq!(c.commit_bump(&security), Some(BumpLevel::Minor));
+        assert_eq!(
+            c.commit_section(&security),
+            Some(ChangelogSection::Named("Security"))
+        );
+        // A rule's bump never lowers the type's
+        let breaking = scoped("fix", Some("security"), true);
+        assert_eq!(c.commit_bump(&breaking), Some(BumpLevel::Major));
+
+        let ui = scoped("fix", Some("ui"), false);
+        assert_eq!(c.commit_bump(&ui), Some(BumpLevel::Patch));
+        assert_eq!(
+            c.commit_section(&ui),
+            Some(ChangelogSection::Named("Bug Fixes"))
+        );
+
+        let deps = scoped("chore", Some("dep"), false);
+        assert_eq!(c.commit_bump(&deps), None);
+        assert_eq!(
+            c.commit_section(&deps),
+            Some(ChangelogSection::Named("Dependencies"))
+        );
+        let breaking_deps = scoped("chore", Some("deps"), true);
+        assert_eq!(
+            c.commit_section(&breaking_deps),
+            Some(ChangelogSection::Breaking)
+        );
+        assert_eq!(
+            c.commit_section(&scoped("chore", None, false)),
+            Some(ChangelogSection::Misc)
+        );
+        assert_eq!(c.commit_section(&scoped("wip", None, false)), None);
+
+        let order = c.section_order();
+        assert_eq!(
+            &order[order.len() - 2..],
+            ["Security", "Dependencies"],
+            "rule-only sections come after the types'"
+        );
+    }
+
     #[test]
     fn classifier_is_allowed() {
         let c = DefaultCommitClassifier::default();
@@ -345,6 +766,55 @@ mod tests {
         assert!(!c.is_allowed("unknown"));
     }
 
+    #[test]
+    fn parser_uses_custom_pattern() {
+        let parser = DefaultCommitParser::new(r"^\[(?P<type>\w+)\] (?P<description>.+)").unwrap();
+        let result = parser.parse(&raw("[feat] add button")).unwrap();
+        assert_eq!(result.r#type, "feat");
+        assert_eq!(result.description, "add button");
+        assert!(parser.parse(&raw("feat: add button")).is_err());
+    }
+
+    #[test]
+    fn parser_rejects_invalid_patterns_up_front() {
+        let err = DefaultCommitParser::new("(unclosed").err().unwrap();
+        assert!(err.to_string().contains("invalid commit_pattern"), "{err}");
+        let err = DefaultCommitParser::new(r"^(?P<type>\w+):").err().unwrap();
+        assert!(err.to_string().contains("(?P<description>...)"), "{err}");
+        assert!(DefaultCommitClassifier::new(default_commit_types(), "(").is_err());
+    }
+
+    #[test]
+    fn classifier_parser_shares_pattern() {
+        let classifier =
+            DefaultCommitClassifier::new(default_commit_types(), DEFAULT_COMMIT_PATTERN).unwrap();
+        let result = classifier.parser().parse(&raw("fix(core): x")).unwrap();
+        assert_eq!(result.scope.as_deref(), Some("core"));
+    }
+
+    #[test]
+    fn parser_handles_large_history() {
+        let parser = DefaultCommitParser::default();
+        let commits: Vec<Commit> = (0..20_000)
+            .map(|i| Commit {
+                sha: format!("{i:040x}"),
+                author: String::new(),
+                message: match i % 3 {
+                    0 => format!("feat(api): endpoint {i}"),
+                    1 => format!("fix: bug {i}\n\nBREAKING CHANGE: renamed"),
+                    _ => format!("update {i}"),
+                },
+                paths: Vec::new(),
+            })
+            .collect();
+        let parsed: Vec<_> = commits
+            .iter()
+            .filter_map(|c| parser.parse(c).ok())
+            .collect();
+        assert_eq!(parsed.len(), 13_334);
+        assert_eq!(parsed.iter().filter(|c| c.breaking).count(), 6_667);
+    }
+
     #[test]
     fn classifier_pattern() {
         let c = DefaultCommitClassifier::default();
@@ -382,4 +852,64 @@ mod tests {
         let parsed: CommitType = serde_yaml_ng::from_str(&yaml).unwrap();
         assert_eq!(parsed, ct);
     }
+
+    fn typed(r#type: &str) -> ConventionalCommit {
+        ConventionalCommit {
+            sha: "abc1234".into(),
+            r#type: r#type.into(),
+            scope: None,
+            description: "x".into(),
+            body: None,
+            breaking: false,
+        }
+    }
+
+    #[test]
+    fn type_usage_reports_shadowed_and_unconfigured_types() {
+        let types = vec![
+            CommitType {
+                name: "feature".into(),
+                bump: Some(BumpLevel::Minor),
+                section: Some("Features".into()),
+            },
+            CommitType {
+                name: "fix".into(),
+                bump: Some(BumpLevel::Patch),
+                section: Some("Bug Fixes".into()),
+            },
+            CommitType {
+                name: "fix".into(),
+                bump: None,
+                section: None,
+            },
+        ];
+        let commits = [typed("feat"), typed("feat"), typed("chore"), typed("fix")];
+        let usage = TypeUsage::analyze(&types, &commits);
+        assert_eq!(usage.unused, ["feature"]);
+        assert_eq!(
+            usage.unconfigured,
+            [("feat".to_string(), 2), ("chore".to_string(), 1)]
+        );
+        assert_eq!(usage.duplicates, ["fix"]);
+
+        let warnings = usage.warnings();
+        assert_eq!(warnings.len(), 4, "{warnings:?}");
+        assert!(warnings[0].contains("'fix' is configured more than once"));
+        assert!(warnings[1].starts_with("2 commit(s) of type 'feat'"));
+        assert!(warnings[3].contains("'feature' matched none of the 4 commit(s)"));
+        assert!(warnings[1].ends_with("are left out of changelogs"));
+
+        let warnings = usage.with_unknown_section(Some("Other")).warnings();
+        assert!(
+            warnings[1].ends_with("they trigger no bump and are listed under 'Other'"),
+            "{warnings:?}"
+        );
+    }
+
+    #[test]
+    fn unused_types_alone_are_not_worth_a_warning() {
+        let usage = TypeUsage::analyze(&default_commit_types(), &[typed("fix")]);
+        assert!(usage.unused.contains(&"feat".to_string()));
+        assert!(usage.warnings().is_empty());
+    }
 }
@@ -1,12 +1,22 @@
 use std::collections::BTreeMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::commit::{CommitType, DEFAULT_COMMIT_PATTERN, default_commit_types};
+use crate::changelog::{
+    ChangelogEntry, DefaultChangelogFormatter, DescriptionTransform, Destination, UnknownTypes,
+};
+use crate::commit::{
+    ChangelogSection, Commit, CommitClassifier, CommitParser, CommitRule, CommitType,
+    ConventionalCommit, DEFAULT_COMMIT_PATTERN, DefaultCommitClassifier, DefaultCommitParser,
+    RuleCondition, TypeUsage, default_commit_types,
+};
 use crate::error::ReleaseError;
-use crate::version::BumpLevel;
-use crate::version_files::detect_version_files;
+use crate::git::tag_display_name;
+use crate::version::{BumpLevel, CalverFormat};
+use crate::version_files::{cargo_package_name, detect_version_files};
 
 /// Preferred config file name for new projects.
 pub const DEFAULT_CONFIG_FILE: &str = "sr.yaml";
@@ -14,80 +24,409 @@ pub const DEFAULT_CONFIG_FILE: &str = "sr.yaml";
 /// Legacy config file name (deprecated, will be removed in a future release).
 pub const LEGACY_CONFIG_FILE: &str = ".urmzd.sr.yml";
 
+/// Default `branches`.
+pub const DEFAULT_BRANCHES: &[&str] = &["main", "master"];
+
+/// Default message for the release commit.
+pub const DEFAULT_RELEASE_COMMIT_MESSAGE: &str = "chore(release): {tag} [skip ci]";
+
+/// Placeholders available in `release_commit_message`.
+const TEMPLATE_PLACEHOLDERS: &[&str] = &["version", "tag", "date"];
+/// Placeholders available in `release.name_template`.
+const RELEASE_NAME_PLACEHOLDERS: &[&str] = &["version", "tag", "display_name", "date"];
+/// Placeholders allowed in `version_file_urls`, `artifacts` and
+/// `release.notes_file_template` templates.
+const URL_PLACEHOLDERS: &[&str] = &["version", "tag"];
+/// Placeholders allowed in the `rename` template of an `artifacts` glob.
+const RENAME_PLACEHOLDERS: &[&str] = &["version", "tag", "basename", "ext"];
+
+/// Placeholders available in notification templates. `{error}` is only set
+/// in `failure_template`.
+pub const NOTIFICATION_PLACEHOLDERS: &[&str] = &[
+    "version",
+    "tag",
+    "date",
+    "release_url",
+    "changelog",
+    "error",
+];
+
+/// Host-project manifests that may embed config, checked after `CONFIG_CANDIDATES`:
+/// `[package.metadata.sr]` / `[workspace.metadata.sr]` in Cargo.toml, or an `"sr"`
+/// key in package.json.
+pub const MANIFEST_CONFIG_FILES: &[&str] = &["Cargo.toml", "package.json"];
+
+/// Published JSON Schema for the config file, pinned to this release.
+pub const CONFIG_SCHEMA_URL: &str = concat!(
+    "https://raw.githubusercontent.com/urmzd/sr/v",
+    env!("CARGO_PKG_VERSION"),
+    "/sr.schema.json"
+);
+
 /// Config file candidates, checked in priority order.
-pub const CONFIG_CANDIDATES: &[&str] = &["sr.yaml", "sr.yml", LEGACY_CONFIG_FILE];
+pub const CONFIG_CANDIDATES: &[&str] = &[
+    "sr.yaml",
+    "sr.yml",
+    ".sr.yaml",
+    ".sr.yml",
+    "sr.config.yml",
+    LEGACY_CONFIG_FILE,
+];
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
+#[schemars(deny_unknown_fields)]
 pub struct ReleaseConfig {
+    /// Base config to inherit from: a path (relative to this file) or an `https://`
+    /// URL. Resolved at load time by deep-merging this file over the base.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Branches that releases may be cut from. Left at the default when the
+    /// repository has neither `main` nor `master`, the remote's default branch
+    /// is used instead.
     pub branches: Vec<String>,
-    pub tag_prefix: String,
+    /// Prefix for release tags (e.g. `v` for `v1.2.0`). A list keeps earlier
+    /// prefixes in play: new tags use the first, and the current version is
+    /// the highest tag under any of them.
+    pub tag_prefix: TagPrefix,
+    /// Read two-component tags such as `v1.2` as `1.2.0` instead of ignoring
+    /// them.
+    pub lenient_tags: bool,
+    /// Version scheme: `semver` bumps from commits, `calver` numbers releases
+    /// by date and only uses commits to decide whether to release.
+    pub versioning: Versioning,
+    /// CalVer format for `versioning: calver`: a year token (`YYYY`, `YY`,
+    /// `0Y`), a month (`MM`, `0M`) or ISO week (`WW`, `0W`) token, then `MICRO`.
+    pub calver_format: String,
+    /// Regex for parsing commit subjects. Must contain `type` and `description`
+    /// named groups; `scope` and `breaking` are optional.
     pub commit_pattern: String,
+    /// Changelog section heading for breaking changes.
     pub breaking_section: String,
+    /// Changelog section heading for commits whose type has no section.
     pub misc_section: String,
+    /// Changelog section heading for commits whose type is not in `types`,
+    /// with `changelog.unknown_types: own-section`.
+    pub other_section: String,
+    /// Recognised commit types, their bump levels, and changelog sections.
     pub types: Vec<CommitType>,
+    /// How `types` combines with the `extends` base: `replace` the base list, or
+    /// `extend` it (entries merged by name, new ones appended).
+    pub types_merge: MergeMode,
+    /// How `types` combines with the built-in commit types: `extend` merges entries
+    /// onto them by name (overriding only the fields given), `replace` uses `types`
+    /// as the complete list.
+    pub types_mode: MergeMode,
+    /// Rules overriding the section and raising the bump of matching commits,
+    /// checked in order before the `types` lookup.
+    pub rules: Vec<CommitRule>,
+    /// Changelog generation settings.
     pub changelog: ChangelogConfig,
+    /// Manifest files whose version is bumped on release (e.g. `Cargo.toml`).
     pub version_files: Vec<String>,
+    /// Fail the release when a version file is missing or unsupported.
     pub version_files_strict: bool,
-    pub artifacts: Vec<String>,
+    /// Lowest version to bump from, e.g. `2.3.1` when adopting sr on a project
+    /// without tags. A newer tag wins.
+    pub version_floor: Option<String>,
+    /// Download URL templates for Homebrew formulae and Scoop manifests in
+    /// `version_files`, keyed by file path. `{version}` and `{tag}` are
+    /// substituted; checksums are left for a later hook to fill in.
+    pub version_file_urls: BTreeMap<String, String>,
+    /// Files uploaded to the remote release: glob patterns, archives built at
+    /// release time, and files downloaded from URLs.
+    pub artifacts: Vec<ArtifactEntry>,
+    /// Fail the release, before anything is committed or tagged, when a glob
+    /// entry in `artifacts` matches no files. Entries written as
+    /// `{path, required}` decide for themselves.
+    pub artifacts_required: bool,
+    /// SBOM and provenance files generated and uploaded with every release.
+    pub attestations: AttestationsConfig,
+    /// Maintain a floating major tag (e.g. `v1`) pointing at the latest release.
     pub floating_tags: bool,
-    pub build_command: Option<String>,
+    /// Shell command run after the version bump, before the release commit.
+    pub build_command: Option<HookCommand>,
     /// Additional files/globs to stage after `build_command` runs (e.g. `Cargo.lock`).
     pub stage_files: Vec<String>,
     /// Pre-release identifier (e.g. "alpha", "beta", "rc"). When set, versions are
     /// formatted as X.Y.Z-<id>.N where N auto-increments.
     pub prerelease: Option<String>,
     /// Shell command to run before the release starts (validation, checks).
-    pub pre_release_command: Option<String>,
+    pub pre_release_command: Option<HookCommand>,
     /// Shell command to run after the release completes (notifications, deployments).
-    pub post_release_command: Option<String>,
-    /// Sign annotated tags with GPG/SSH (git tag -s).
+    pub post_release_command: Option<HookCommand>,
+    /// Steps run after the tag is pushed and the remote release is created
+    /// (e.g. `cargo publish`, `npm publish`).
+    pub publish: Vec<PublishStep>,
+    /// Webhook and chat notifications sent after the release.
+    pub notifications: Vec<NotificationConfig>,
+    /// JSON record of each release (`release-summary.json`) for audit trails.
+    pub summary: SummaryConfig,
+    /// Sign annotated tags with GPG/SSH (git tag -s). Shorthand for `signing.tags`.
     pub sign_tags: bool,
+    /// GPG/SSH signing for release tags and commits.
+    pub signing: SigningConfig,
+    /// How release tags are created.
+    pub tags: TagsConfig,
+    /// Committer identity for the release commit and tags.
+    pub git: GitConfig,
     /// Create GitHub releases as drafts (requires manual publishing).
     pub draft: bool,
-    /// Minijinja template for the GitHub release name.
-    /// Available variables: `version`, `tag_name`, `tag_prefix`.
-    /// Default when None: uses the tag name (e.g. "v1.2.0").
+    /// Refuse `sr release` (except `--dry-run`) outside CI, unless `--allow-local`.
+    pub require_ci: bool,
+    /// Name and prerelease flag of the remote release.
+    pub release: RemoteReleaseConfig,
+    /// Deprecated: use `release.name_template`. Minijinja template for the
+    /// release name with variables `version`, `tag_name`, `display_name`,
+    /// `tag_prefix`; when set, it takes precedence over `release.name_template`.
     pub release_name_template: Option<String>,
+    /// Message for the release commit. Placeholders: `{version}`, `{tag}`, `{date}`.
+    pub release_commit_message: String,
+    /// Git trailers added to the release commit, e.g. `Skip-Checks: true`.
+    /// Values accept `{version}`, `{tag}`, `{date}`.
+    pub release_commit_trailers: BTreeMap<String, String>,
+    /// Advisory release lock on origin.
+    pub lock: LockConfig,
+    /// Conditions a release must meet, e.g. a minimum time between releases.
+    pub policy: PolicyConfig,
     /// Git hooks configuration.
     pub hooks: HooksConfig,
+    /// Remote release provider mode. `none` disables all API calls: only tags,
+    /// the release commit, and the changelog are produced.
+    pub provider: ProviderMode,
+    /// Remote release targets. When empty, a single GitHub provider is derived
+    /// from the `origin` remote and `GH_TOKEN`/`GITHUB_TOKEN`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<ProviderConfig>,
+    /// `owner/repo` override. Default: parsed from the `origin` remote, falling
+    /// back to `GITHUB_REPOSITORY`.
+    pub repository: Option<String>,
+    /// Hostname override (e.g. a GHES host). Default: parsed from the `origin`
+    /// remote, falling back to `GITHUB_SERVER_URL`, then `github.com`.
+    pub hostname: Option<String>,
+    /// GitHub API endpoints for the provider on the origin host.
+    pub github: GitHubConfig,
+    /// Only commits touching these paths (git pathspecs, e.g. `src`, `*.rs`) count
+    /// toward a release. Empty means all paths.
+    pub include_paths: Vec<String>,
+    /// Commits touching only these paths never trigger a release or appear in the
+    /// changelog (passed to git as `:(exclude)<path>`).
+    pub exclude_paths: Vec<String>,
+    /// Most commits read from history per release range, newest first. Older
+    /// commits are skipped with a warning. `0` means unlimited.
+    pub max_commits: usize,
     /// Monorepo packages. When non-empty, each package is released independently.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub packages: Vec<PackageConfig>,
     /// Internal: set when resolving a package config. Commits are filtered to this path.
     #[serde(skip)]
     pub path_filter: Option<String>,
+    /// Internal: the package name, set when resolving a package config.
+    #[serde(skip)]
+    pub package_name: Option<String>,
+    /// Internal: the root changelog a resolved package's releases are also
+    /// added to, when `changelog.aggregate` is set at the root.
+    #[serde(skip)]
+    pub aggregate_changelog: Option<String>,
 }
 
 impl Default for ReleaseConfig {
     fn default() -> Self {
         Self {
-            branches: vec!["main".into(), "master".into()],
+            extends: None,
+            branches: DEFAULT_BRANCHES.iter().map(|b| b.to_string()).collect(),
             tag_prefix: "v".into(),
+            lenient_tags: false,
+            versioning: Versioning::default(),
+            calver_format: DEFAULT_CALVER_FORMAT.into(),
             commit_pattern: DEFAULT_COMMIT_PATTERN.into(),
             breaking_section: "Breaking Changes".into(),
             misc_section: "Miscellaneous".into(),
+            other_section: "Other".into(),
             types: default_commit_types(),
+            types_merge: MergeMode::Replace,
+            types_mode: MergeMode::Extend,
+            rules: Vec::new(),
             changelog: ChangelogConfig::default(),
             version_files: vec![],
             version_files_strict: false,
+            version_floor: None,
+            version_file_urls: BTreeMap::new(),
             artifacts: vec![],
+            artifacts_required: false,
+            attestations: AttestationsConfig::default(),
             floating_tags: false,
             build_command: None,
             stage_files: vec![],
             prerelease: None,
             pre_release_command: None,
             post_release_command: None,
+            publish: vec![],
+            notifications: vec![],
+            summary: SummaryConfig::default(),
             sign_tags: false,
+            signing: SigningConfig::default(),
+            tags: TagsConfig::default(),
+            git: GitConfig::default(),
             draft: false,
+            require_ci: false,
+            release: RemoteReleaseConfig::default(),
             release_name_template: None,
+            release_commit_message: DEFAULT_RELEASE_COMMIT_MESSAGE.into(),
+            release_commit_trailers: BTreeMap::new(),
+            lock: LockConfig::default(),
+            policy: PolicyConfig::default(),
             hooks: HooksConfig::with_defaults(),
+            provider: ProviderMode::default(),
+            providers: vec![],
+            repository: None,
+            hostname: None,
+            github: GitHubConfig::default(),
+            include_paths: vec![],
+            exclude_paths: vec![],
+            max_commits: 5000,
             packages: vec![],
             path_filter: None,
+            package_name: None,
+            aggregate_changelog: None,
+        }
+    }
+}
+
+/// A rendered `{version}`: semver, or CalVer with zero-padded components.
+const VERSION_PATTERN: &str = r"\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?";
+
+/// Recognizes release commits by the subject line of `release_commit_message`.
+/// `{version}` and `{tag}` only match a version and a tag under one of the tag
+/// prefixes, `{date}` a run of text with a digit in it. A subject without
+/// literal text matches nothing. The legacy `chore(release):` prefix always matches.
+pub struct ReleaseCommitMatcher {
+    pattern: Option<regex::Regex>,
+}
+
+impl ReleaseCommitMatcher {
+    fn new(template: &str, tag_prefixes: &[&str]) -> Self {
+        let subject = template.lines().next().unwrap_or_default().trim();
+        let mut prefixes: Vec<String> = tag_prefixes.iter().map(|p| regex::escape(p)).collect();
+        // Longest first, so `v` does not shadow `pkg/v`
+        prefixes.sort_by_key(|p| std::cmp::Reverse(p.len()));
+        let tag = format!("(?:{}){VERSION_PATTERN}", prefixes.join("|"));
+        let pattern = template_parts(subject)
+            .ok()
+            .filter(|parts| has_literal_text(parts))
+            .and_then(|parts| {
+                let body: String = parts
+                    .iter()
+                    .map(|part| match part {
+                        TemplatePart::Literal(text) => regex::escape(text),
+                        TemplatePart::Placeholder("version") => VERSION_PATTERN.to_string(),
+                        TemplatePart::Placeholder("tag") => tag.clone(),
+                        TemplatePart::Placeholder(_) => r"[^\n]*?\d[^\n]*?".to_string(),
+                    })
+                    .collect();
+                regex::Regex::new(&format!("^{body}$")).ok()
+            });
+        Self { pattern }
+    }
+
+    /// Whether `message` was produced by the release step.
+    pub fn matches(&self, message: &str) -> bool {
+        let subject = message.lines().next().unwrap_or_default().trim();
+        subject.starts_with("chore(release):")
+            || self.pattern.as_ref().is_some_and(|p| p.is_match(subject))
+    }
+}
+
+/// Whether a template has literal text besides its placeholders, so it cannot
+/// match arbitrary text.
+fn has_literal_text(parts: &[TemplatePart<'_>]) -> bool {
+    parts.iter().any(|part| {
+        matches!(part, TemplatePart::Literal(text) if text.chars().any(char::is_alphanumeric))
+    })
+}
+
+/// Substitute `{version}`, `{tag}`, and `{date}` in a template.
+fn render_placeholders(template: &str, version: &str, tag: &str, date: &str) -> String {
+    template
+        .replace("{version}", version)
+        .replace("{tag}", tag)
+        .replace("{date}", date)
+}
+
+/// Substitute `{version}` and `{tag}` in a URL or artifact name template.
+pub(crate) fn render_url_template(template: &str, version: &str, tag: &str) -> String {
+    render_placeholders(template, version, tag, "")
+}
+
+/// Record problems with a `{placeholder}` template: empty, unclosed braces, or
+/// placeholders outside `allowed`.
+fn check_template(field: &str, template: &str, allowed: &[&str], problems: &mut Vec<String>) {
+    if template.trim().is_empty() {
+        problems.push(format!("{field} must not be empty"));
+    }
+    match template_parts(template) {
+        Ok(parts) => {
+            for part in parts {
+                if let TemplatePart::Placeholder(name) = part
+                    && !allowed.contains(&name)
+                {
+                    problems.push(format!(
+                        "{field}: unknown placeholder {{{name}}} (available: {})",
+                        allowed
+                            .iter()
+                            .map(|p| format!("{{{p}}}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            }
         }
+        Err(e) => problems.push(match e {
+            ReleaseError::Config(msg) => format!("{field}: {msg}"),
+            other => other.to_string(),
+        }),
     }
 }
 
+/// Record a problem when an asset name is a path (`/`, `\`, `..`) rather than
+/// a plain file name; it is joined onto the directory assets are prepared in.
+fn check_file_name(field: &str, name: &str, problems: &mut Vec<String>) {
+    if !is_file_name(name) {
+        problems.push(format!("{field} must be a file name, not a path"));
+    }
+}
+
+/// Whether `name` is a single file name that stays inside any directory it
+/// is joined onto.
+pub(crate) fn is_file_name(name: &str) -> bool {
+    !name.contains(['/', '\\']) && name != "." && name != ".."
+}
+
+enum TemplatePart<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split a `{placeholder}` template into literal and placeholder parts.
+fn template_parts(template: &str) -> Result<Vec<TemplatePart<'_>>, ReleaseError> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(TemplatePart::Literal(&rest[..start]));
+        }
+        let end = rest[start..].find('}').ok_or_else(|| {
+            ReleaseError::Config(format!("unclosed '{{' in template: {template}"))
+        })?;
+        parts.push(TemplatePart::Placeholder(&rest[start + 1..start + end]));
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest));
+    }
+    Ok(parts)
+}
+
 /// A package in a monorepo. Each package is released independently with its own
 /// version, tags, and changelog. Commits are filtered by `path`.
 ///
@@ -102,605 +441,4686 @@ impl Default for ReleaseConfig {
 ///     version_files:
 ///       - crates/cli/Cargo.toml
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct PackageConfig {
     /// Package name — used in the default tag prefix (`{name}/v`).
     pub name: String,
     /// Directory path relative to the repo root. Only commits touching this path trigger a release.
     pub path: String,
-    /// Tag prefix override (default: `{name}/v`).
+    /// Tag prefix override (default: `{name}/v`). Accepts a list like the root `tag_prefix`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub tag_prefix: Option<String>,
+    pub tag_prefix: Option<TagPrefix>,
     /// Version files override.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub version_files: Vec<String>,
     /// Changelog override.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub changelog: Option<ChangelogConfig>,
+    /// This package's changelog file. Default: `{path}/CHANGELOG.md` when the
+    /// root `changelog.file` is set; takes precedence over `changelog.file`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changelog_file: Option<String>,
     /// Build command override.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub build_command: Option<String>,
+    pub build_command: Option<HookCommand>,
     /// Stage files override.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub stage_files: Vec<String>,
 }
 
-/// A single entry in a hook's command list.
-///
-/// Can be either a simple shell command string or a structured step with
-/// file-pattern matching.
+/// Settings for the release object created on each provider.
 ///
 /// ```yaml
-/// hooks:
-///   commit-msg:
-///     - sr hook commit-msg          # simple command
-///   pre-commit:
-///     - step: format                # structured step
-///       patterns:
-///         - "*.rs"
-///       rules:
-///         - "rustfmt --check --edition 2024 {files}"
+/// release:
+///   name_template: "{tag} ({date})"
+///   prerelease: auto   # true | false | auto
+///   sections: [Breaking Changes, Features]
+///   notes_file_template: "docs/releases/{version}.md"
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum HookEntry {
-    Step {
-        step: String,
-        patterns: Vec<String>,
-        rules: Vec<String>,
-    },
-    Simple(String),
-}
-
-/// Git hooks configuration.
-///
-/// Each key is a git hook name (e.g. `commit-msg`, `pre-commit`, `pre-push`)
-/// and the value is a list of entries — either simple shell commands or
-/// structured steps with file-pattern matching.
-///
-/// Hook scripts in `.githooks/` are generated by `sr init`.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(transparent)]
-pub struct HooksConfig {
-    pub hooks: BTreeMap<String, Vec<HookEntry>>,
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct RemoteReleaseConfig {
+    /// Release name. Placeholders: `{version}`, `{tag}`, `{date}`, and
+    /// `{display_name}`: the tag without the path of a prefix like `pkg/foo-v`
+    /// (`pkg/foo-v1.2.3` shows as `foo-v1.2.3`).
+    pub name_template: String,
+    /// Mark the release as a prerelease: `true`, `false`, or `auto` (whenever the
+    /// version has a prerelease component, e.g. `1.2.0-rc.1`).
+    pub prerelease: PrereleaseFlag,
+    /// Section headings the release body includes, independently of
+    /// `changelog.sections`. Empty = every section.
+    pub sections: Vec<String>,
+    /// End the release body with a link comparing it to the previous release.
+    pub compare_link: bool,
+    /// File of curated release notes, e.g. `docs/releases/{version}.md`
+    /// (placeholders: `{version}`, `{tag}`). When it exists it becomes the
+    /// release body, with `{generated}` replaced by the generated notes; the
+    /// changelog file still gets the generated entry.
+    pub notes_file_template: Option<String>,
+    /// Fail the release when the `notes_file_template` file does not exist,
+    /// instead of falling back to the generated notes.
+    pub notes_file_required: bool,
 }
 
-impl HooksConfig {
-    pub fn with_defaults() -> Self {
-        let mut hooks = BTreeMap::new();
-        hooks.insert(
-            "commit-msg".into(),
-            vec![HookEntry::Simple("sr hook commit-msg".into())],
-        );
-        Self { hooks }
+impl Default for RemoteReleaseConfig {
+    fn default() -> Self {
+        Self {
+            name_template: "{display_name}".into(),
+            prerelease: PrereleaseFlag::Auto,
+            sections: Vec::new(),
+            compare_link: true,
+            notes_file_template: None,
+            notes_file_required: false,
+        }
     }
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(default)]
-pub struct ChangelogConfig {
-    pub file: Option<String>,
-    pub template: Option<String>,
+/// `release.prerelease`: a boolean, or `auto` to follow the version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrereleaseFlag {
+    Always,
+    Never,
+    #[default]
+    Auto,
 }
 
-impl ReleaseConfig {
-    /// Find the first config file that exists in the given directory.
-    /// Returns `(path, is_legacy)`.
-    pub fn find_config(dir: &Path) -> Option<(std::path::PathBuf, bool)> {
-        for &candidate in CONFIG_CANDIDATES {
-            let path = dir.join(candidate);
-            if path.exists() {
-                let is_legacy = candidate == LEGACY_CONFIG_FILE;
-                return Some((path, is_legacy));
-            }
+impl PrereleaseFlag {
+    /// Whether a release of `version` should be marked as a prerelease.
+    pub fn resolve(self, version: &semver::Version) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => !version.pre.is_empty(),
         }
-        None
     }
+}
 
-    /// Load config from a YAML file. Falls back to defaults if the file doesn't exist.
-    pub fn load(path: &Path) -> Result<Self, ReleaseError> {
-        if !path.exists() {
-            return Ok(Self::default());
+impl Serialize for PrereleaseFlag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Always => serializer.serialize_bool(true),
+            Self::Never => serializer.serialize_bool(false),
+            Self::Auto => serializer.serialize_str("auto"),
         }
-
-        let contents =
-            std::fs::read_to_string(path).map_err(|e| ReleaseError::Config(e.to_string()))?;
-
-        serde_yaml_ng::from_str(&contents).map_err(|e| ReleaseError::Config(e.to_string()))
     }
+}
 
-    /// Resolve a package into a full release config by merging package overrides with root config.
-    pub fn resolve_package(&self, pkg: &PackageConfig) -> Self {
-        let mut config = self.clone();
-        config.tag_prefix = pkg
-            .tag_prefix
-            .clone()
-            .unwrap_or_else(|| format!("{}/v", pkg.name));
-        config.path_filter = Some(pkg.path.clone());
-        if !pkg.version_files.is_empty() {
-            config.version_files = pkg.version_files.clone();
-        } else if config.version_files.is_empty() {
-            // Auto-detect version files in the package directory
-            let detected = detect_version_files(Path::new(&pkg.path));
-            if !detected.is_empty() {
-                config.version_files = detected
-                    .into_iter()
-                    .map(|f| format!("{}/{f}", pkg.path))
-                    .collect();
-            }
-        }
-        if let Some(ref cl) = pkg.changelog {
-            config.changelog = cl.clone();
-        }
-        if let Some(ref cmd) = pkg.build_command {
-            config.build_command = Some(cmd.clone());
+impl<'de> Deserialize<'de> for PrereleaseFlag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Bool(bool),
+            Str(String),
         }
-        if !pkg.stage_files.is_empty() {
-            config.stage_files = pkg.stage_files.clone();
+        match Repr::deserialize(deserializer)? {
+            Repr::Bool(true) => Ok(Self::Always),
+            Repr::Bool(false) => Ok(Self::Never),
+            Repr::Str(s) if s == "auto" => Ok(Self::Auto),
+            Repr::Str(s) => Err(serde::de::Error::custom(format!(
+                "invalid prerelease value: {s} (expected true, false, or auto)"
+            ))),
         }
-        // Clear packages to avoid recursion
-        config.packages = vec![];
-        config
     }
+}
 
-    /// Find a package by name. Returns an error if the package is not found.
-    pub fn find_package(&self, name: &str) -> Result<&PackageConfig, ReleaseError> {
-        self.packages
-            .iter()
-            .find(|p| p.name == name)
-            .ok_or_else(|| {
-                let available: Vec<&str> = self.packages.iter().map(|p| p.name.as_str()).collect();
-                ReleaseError::Config(format!(
-                    "package '{name}' not found. Available: {}",
-                    if available.is_empty() {
-                        "(none — no packages configured)".to_string()
-                    } else {
-                        available.join(", ")
-                    }
-                ))
-            })
+impl JsonSchema for PrereleaseFlag {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "PrereleaseFlag".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "true, false, or auto (prerelease whenever the version has a prerelease component).",
+            "anyOf": [
+                { "type": "boolean" },
+                { "const": "auto" },
+            ],
+        })
     }
 }
 
-/// Generate a fully-commented default `sr.yaml` template.
+/// A step run after the tag is pushed and the remote release is created.
 ///
-/// The returned string is valid YAML with inline comments documenting every field.
-/// `version_files` is injected dynamically (typically from auto-detection).
-pub fn default_config_template(version_files: &[String]) -> String {
-    let vf = if version_files.is_empty() {
-        "version_files: []\n".to_string()
-    } else {
-        let mut s = "version_files:\n".to_string();
-        for f in version_files {
-            s.push_str(&format!("  - {f}\n"));
-        }
-        s
-    };
+/// ```yaml
+/// publish:
+///   - type: cargo
+///     args: ["--locked"]
+///   - type: npm
+///     tag: latest
+///   - type: command
+///     run: ./scripts/deploy.sh
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PublishStep {
+    /// `cargo publish`; workspace members are published in dependency order.
+    Cargo {
+        /// Directory containing `Cargo.toml` (default: the current directory).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        /// Extra arguments for `cargo publish` (e.g. `--locked`).
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+    },
+    /// `npm publish`.
+    Npm {
+        /// Directory containing `package.json` (default: the current directory).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        /// Dist-tag to publish under (`npm publish --tag`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tag: Option<String>,
+        /// Extra arguments for `npm publish` (e.g. `--access public`).
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+    },
+    /// A shell command, run with `SR_VERSION` and `SR_TAG` set.
+    Command {
+        /// The command line to run.
+        run: String,
+    },
+}
 
-    format!(
-        r#"# sr configuration
-# Full reference: https://github.com/urmzd/sr#configuration
+/// Release tag prefix: one string, or a list whose first entry names new tags.
+/// The other entries are still read when looking for the current version, e.g.
+/// after moving from `release-` to `v` tags:
+///
+/// ```yaml
+/// tag_prefix: [v, release-]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagPrefix(Vec<String>);
 
-# Branches that trigger releases when commits are pushed.
-branches:
-  - main
-  - master
+impl TagPrefix {
+    /// The prefix new tags are created with.
+    pub fn primary(&self) -> &str {
+        self.0.first().map(String::as_str).unwrap_or_default()
+    }
 
-# Prefix prepended to version tags (e.g. "v1.2.0").
-tag_prefix: "v"
+    /// Every prefix, the primary one first, for looking up existing tags.
+    pub fn all(&self) -> Vec<&str> {
+        self.0.iter().map(String::as_str).collect()
+    }
 
-# Regex for parsing conventional commits.
-# Required named groups: type, description.
-# Optional named groups: scope, breaking.
-commit_pattern: '^(?P<type>\w+)(?:\((?P<scope>[^)]+)\))?(?P<breaking>!)?:\s+(?P<description>.+)'
+    /// Whether the list is empty or any prefix in it is.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty() || self.0.iter().any(String::is_empty)
+    }
 
-# Changelog section heading for breaking changes.
-breaking_section: Breaking Changes
+    /// Record prefixes that cannot start a git tag name. Path separators and
+    /// dashes are fine (`pkg/foo-v`); what git rejects in ref names, or reads
+    /// as an option or a glob when listing tags, is not.
+    fn check(&self, field: &str, problems: &mut Vec<String>) {
+        for prefix in &self.0 {
+            let reason = if prefix.starts_with('-') {
+                "must not start with '-'"
+            } else if prefix.starts_with('/') || prefix.contains("//") {
+                "must not start with '/' or contain '//'"
+            } else if prefix.split('/').any(|segment| segment.starts_with('.')) {
+                "must not have a path segment starting with '.'"
+            } else if prefix.contains("..") || prefix.contains("@{") {
+                "must not contain '..' or '@{'"
+            } else if prefix
+                .chars()
+                .any(|c| c.is_whitespace() || c.is_control() || "~^:?*[\\".contains(c))
+            {
+                "must not contain whitespace or any of ~ ^ : ? * [ \\"
+            } else if prefix
+                .split('/')
+                .rev()
+                .skip(1)
+                .any(|segment| segment.ends_with(".lock"))
+            {
+                "must not have a directory ending in '.lock'"
+            } else {
+                continue;
+            };
+            problems.push(format!("{field}: '{prefix}' {reason}"));
+        }
+    }
 
-# Fallback changelog section for unrecognised commit types.
-misc_section: Miscellaneous
+    /// The prefixes as written in messages: `'v'` or `'v', 'release-'`.
+    pub fn describe(&self) -> String {
+        self.0
+            .iter()
+            .map(|p| format!("'{p}'"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
 
-# Commit type definitions.
-# name:    commit type prefix (e.g. "feat", "fix")
-# bump:    version bump level — major, minor, patch, or omit for no bump
-# section: changelog section heading, or omit to exclude from changelog
-types:
-  - name: feat
-    bump: minor
-    section: Features
-  - name: fix
-    bump: patch
-    section: Bug Fixes
-  - name: perf
-    bump: patch
-    section: Performance
-  - name: docs
-    section: Documentation
-  - name: refactor
-    section: Refactoring
-  - name: revert
-    section: Reverts
-  - name: chore
-  - name: ci
-  - name: test
-  - name: build
-  - name: style
+impl From<&str> for TagPrefix {
+    fn from(prefix: &str) -> Self {
+        Self(vec![prefix.to_string()])
+    }
+}
 
-# Changelog configuration.
-# file:     path to the changelog file (e.g. CHANGELOG.md), or omit to skip writing
-# template: custom Minijinja template string for changelog rendering
-changelog:
-  file:
-  template:
+impl From<String> for TagPrefix {
+    fn from(prefix: String) -> Self {
+        Self(vec![prefix])
+    }
+}
 
-# Manifest files to bump on release (e.g. Cargo.toml, package.json, pyproject.toml).
-# Auto-detected if empty.
-{vf}
-# Fail if a version file uses an unsupported format (default: skip unknown files).
-version_files_strict: false
+impl PartialEq<&str> for TagPrefix {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.len() == 1 && self.primary() == *other
+    }
+}
 
-# Glob patterns for release assets to upload to GitHub (e.g. "dist/*.tar.gz").
-artifacts: []
+impl Serialize for TagPrefix {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0.as_slice() {
+            [prefix] => serializer.serialize_str(prefix),
+            prefixes => prefixes.serialize(serializer),
+        }
+    }
+}
 
-# Create floating major version tags (e.g. "v3" pointing to latest v3.x.x).
-floating_tags: false
+impl<'de> Deserialize<'de> for TagPrefix {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            One(String),
+            Many(Vec<String>),
+        }
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::One(prefix) => Self(vec![prefix]),
+            Repr::Many(prefixes) => Self(prefixes),
+        })
+    }
+}
 
-# Shell command to run after version files are bumped (e.g. "cargo build --release").
-build_command:
+impl JsonSchema for TagPrefix {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "TagPrefix".into()
+    }
 
-# Additional files/globs to stage after build_command runs (e.g. Cargo.lock).
-stage_files: []
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A prefix, or a list of prefixes whose first entry names new tags.",
+            "anyOf": [
+                { "type": "string" },
+                { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+            ],
+        })
+    }
+}
 
-# Pre-release identifier (e.g. "alpha", "beta", "rc").
-# When set, versions are formatted as X.Y.Z-<id>.N where N auto-increments.
-prerelease:
+/// How release versions are numbered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Versioning {
+    /// Semantic versioning: the bump level from commits shapes the version.
+    #[default]
+    Semver,
+    /// Calendar versioning in `calver_format`, e.g. `2025.03.2`.
+    Calver,
+}
 
-# Shell command to run before the release starts (validation, checks).
-pre_release_command:
+/// CalVer format used when `calver_format` is not set.
+pub const DEFAULT_CALVER_FORMAT: &str = "YYYY.0M.MICRO";
 
-# Shell command to run after the release completes (notifications, deployments).
-post_release_command:
+/// How a list from one config layer combines with the layer beneath it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMode {
+    /// The overriding list replaces the base list.
+    #[default]
+    Replace,
+    /// Entries are merged by name onto the base list; new entries are appended.
+    Extend,
+}
 
-# Sign annotated tags with GPG/SSH (git tag -s).
-sign_tags: false
+/// How remote releases are published.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderMode {
+    /// Create releases on GitHub (or the hosts listed in `providers`).
+    #[default]
+    Github,
+    /// Tag-only releases: never construct a provider or call a remote API.
+    None,
+}
 
-# Create GitHub releases as drafts (requires manual publishing).
-draft: false
+/// A remote release target. The tag is pushed once to `origin`; a release object
+/// is then created on every configured provider.
+///
+/// ```yaml
+/// providers:
+///   - hostname: github.com
+///   - hostname: ghes.internal.example.com
+///     owner: mirrors
+///     token_env: GHES_TOKEN
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProviderConfig {
+    /// Hostname of the provider (e.g. `github.com` or a GHES host).
+    pub hostname: String,
+    /// Repository owner override (default: parsed from the `origin` remote).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Repository name override (default: parsed from the `origin` remote).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Environment variable holding the API token (default: `GH_TOKEN`, then `GITHUB_TOKEN`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
 
-# Minijinja template for the GitHub release name.
-# Available variables: version, tag_name, tag_prefix.
-# Default: uses the tag name (e.g. "v1.2.0").
-release_name_template:
+/// GPG/SSH signing for release tags and commits.
+///
+/// ```yaml
+/// signing:
+///   tags: true
+///   commits: true
+///   key: ~/.ssh/release_ed25519.pub   # or a GPG key id
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct SigningConfig {
+    /// Sign release tags, including floating tags (git tag -s).
+    pub tags: bool,
+    /// Sign the release commit (git commit -S).
+    pub commits: bool,
+    /// Signing key: a GPG key id, or an SSH key path / `ssh-...` public key
+    /// (which switches git to `gpg.format=ssh`). Default: git's `user.signingkey`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
 
-# Git hooks configuration.
-# Each key is a git hook name. Values can be simple commands or structured steps.
-# Steps with patterns only run when staged files match the globs.
-# Rules containing {{files}} receive the matched file list.
-# Hook scripts are generated in .githooks/ by "sr init".
-hooks:
-  commit-msg:
-    - sr hook commit-msg
-  # pre-commit:
-  #   - step: format
-  #     patterns:
-  #       - "*.rs"
-  #     rules:
-  #       - "rustfmt --check --edition 2024 {{files}}"
-  #   - step: lint
-  #     patterns:
-  #       - "*.rs"
-  #     rules:
-  #       - "cargo clippy --workspace -- -D warnings"
+impl SigningConfig {
+    /// Whether `key` refers to an SSH key rather than a GPG key id.
+    pub fn is_ssh_key(&self) -> bool {
+        self.key.as_deref().is_some_and(|key| {
+            key.starts_with("ssh-")
+                || key.starts_with("key::")
+                || key.ends_with(".pub")
+                || key.contains('/')
+                || Path::new(key).is_file()
+        })
+    }
+}
 
-# Monorepo packages (uncomment and configure if needed).
-# Each package is released independently with its own version, tags, and changelog.
-# packages:
-#   - name: core
-#     path: crates/core
-#     tag_prefix: "core/v"          # default: "<name>/v"
-#     version_files:
-#       - crates/core/Cargo.toml
-#     changelog:
-#       file: crates/core/CHANGELOG.md
-#     build_command: cargo build -p core
-#     stage_files:
-#       - crates/core/Cargo.lock
-"#
-    )
+/// Committer identity used for the release commit and tags, and how long git
+/// commands may run. The identity is passed to git as
+/// `-c user.name=... -c user.email=...`, so global git config is never modified.
+/// When unset, git's own identity (`user.name`/`user.email`, or the
+/// `GIT_AUTHOR_*`/`GIT_COMMITTER_*` environment variables) is used.
+///
+/// ```yaml
+/// git:
+///   user_name: sr-bot
+///   user_email: sr-bot@users.noreply.github.com
+///   timeout_seconds: 300
+///   release_notes_ref: releases   # refs/notes/releases
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct GitConfig {
+    /// Name for the release commit and tags (`user.name`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_name: Option<String>,
+    /// Email for the release commit and tags (`user.email`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_email: Option<String>,
+    /// Seconds a git command (push, fetch, ...) may run before it is killed.
+    /// `0` disables the limit.
+    pub timeout_seconds: u64,
+    /// Git notes ref to annotate the tagged commit in with the version, date,
+    /// and release URL, pushed alongside the tag. A bare name like `releases`
+    /// means `refs/notes/releases`. Unset: no note is written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_notes_ref: Option<String>,
 }
 
-/// Merge new default fields into an existing config YAML string.
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            user_name: None,
+            user_email: None,
+            timeout_seconds: 300,
+            release_notes_ref: None,
+        }
+    }
+}
+
+impl GitConfig {
+    /// `timeout_seconds` as a duration, or `None` when the limit is disabled.
+    pub fn timeout(&self) -> Option<std::time::Duration> {
+        (self.timeout_seconds > 0).then(|| std::time::Duration::from_secs(self.timeout_seconds))
+    }
+
+    /// `release_notes_ref` as a full ref name (`releases` → `refs/notes/releases`).
+    pub fn notes_ref(&self) -> Option<String> {
+        let name = self.release_notes_ref.as_deref()?.trim();
+        if name.is_empty() {
+            None
+        } else if name.starts_with("refs/") {
+            Some(name.to_string())
+        } else {
+            Some(format!("refs/notes/{name}"))
+        }
+    }
+}
+
+/// How release tags are created. Floating tags are always lightweight
+/// unless signed.
 ///
-/// Adds any top-level or nested mapping keys present in the defaults but missing
-/// from the existing config. Arrays are never merged (user's array = user's intent).
-/// Returns the merged YAML with a header comment.
-pub fn merge_config_yaml(existing_yaml: &str) -> Result<String, ReleaseError> {
-    let mut existing: serde_yaml_ng::Value = serde_yaml_ng::from_str(existing_yaml)
-        .map_err(|e| ReleaseError::Config(format!("failed to parse existing config: {e}")))?;
+/// ```yaml
+/// tags:
+///   annotated: false   # lightweight tags: the tag SHA is the commit SHA
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct TagsConfig {
+    /// Create annotated release tags carrying the changelog as their message.
+    /// `false` creates lightweight tags, which cannot be signed.
+    pub annotated: bool,
+}
 
-    let default_config = ReleaseConfig::default();
-    let default_yaml = serde_yaml_ng::to_string(&default_config)
-        .map_err(|e| ReleaseError::Config(e.to_string()))?;
-    let defaults: serde_yaml_ng::Value =
-        serde_yaml_ng::from_str(&default_yaml).map_err(|e| ReleaseError::Config(e.to_string()))?;
+impl Default for TagsConfig {
+    fn default() -> Self {
+        Self { annotated: true }
+    }
+}
 
-    deep_merge_value(&mut existing, &defaults);
+/// Audit record written after every successful `sr release`: the plan, the
+/// released commit, the changelog, bumped files, uploaded assets with their
+/// SHA-256 digests, release URLs, and step timestamps.
+///
+/// ```yaml
+/// summary:
+///   file: release-summary.json
+///   upload: true   # also attach it to the remote release
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct SummaryConfig {
+    /// Where to write the summary. `None` writes none.
+    pub file: Option<String>,
+    /// Upload the summary as an asset of the remote release.
+    pub upload: bool,
+}
 
-    let merged =
-        serde_yaml_ng::to_string(&existing).map_err(|e| ReleaseError::Config(e.to_string()))?;
+/// Endpoints of the GitHub API, for proxies and GHES setups that serve it
+/// somewhere other than `https://<host>/api/v3`. Applies to the provider on
+/// the origin host.
+///
+/// ```yaml
+/// github:
+///   api_url: https://api.ghes.example.com
+///   upload_url: https://ghes.example.com/api/uploads
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct GitHubConfig {
+    /// REST API base URL. Default: `GITHUB_API_URL` when it is set for this
+    /// host (GitHub Actions sets it), else `https://api.github.com` for
+    /// github.com and `https://<host>/api/v3` for other hosts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// Base URL for asset uploads, replacing the scheme, host, and path before
+    /// `/repos/` in the `upload_url` of each release. Default: the release's
+    /// own `upload_url` (`uploads.github.com`, or `/api/uploads` on GHES).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_url: Option<String>,
+}
 
-    Ok(format!(
-        "# sr configuration — merged with new defaults\n\
-         # Run 'sr init --force' for a fully-commented template.\n\n\
-         {merged}"
-    ))
+/// Whether `url` is plain HTTP to the loopback interface (a local mock or
+/// proxy), the only place GitHub API calls may skip HTTPS.
+pub fn is_loopback_http(url: &str) -> bool {
+    ["http://127.0.0.1", "http://localhost", "http://[::1]"]
+        .iter()
+        .any(|host| {
+            url.strip_prefix(host)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with([':', '/']))
+        })
 }
 
-/// Recursively insert missing keys from `defaults` into `base`.
-/// Only mapping keys are merged; arrays and scalars are left untouched.
-fn deep_merge_value(base: &mut serde_yaml_ng::Value, defaults: &serde_yaml_ng::Value) {
-    use serde_yaml_ng::Value;
-    if let (Value::Mapping(base_map), Value::Mapping(default_map)) = (base, defaults) {
-        for (key, default_val) in default_map {
-            match base_map.get_mut(key) {
-                Some(existing_val) => {
-                    // Recurse into nested mappings only
-                    if matches!(default_val, Value::Mapping(_)) {
-                        deep_merge_value(existing_val, default_val);
-                    }
-                }
-                None => {
-                    base_map.insert(key.clone(), default_val.clone());
-                }
+/// Advisory lock held on `origin` (as `refs/sr-lock/release`) while `sr release` runs, so
+/// two concurrent jobs cannot both publish the same version. The lock commit
+/// records an expiry; a lock past it (left by a crashed job) is taken over.
+///
+/// ```yaml
+/// lock:
+///   enabled: true
+///   stale_after: 1800   # seconds
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct LockConfig {
+    /// Take the lock before publishing.
+    pub enabled: bool,
+    /// Seconds after which an unreleased lock is considered stale.
+    pub stale_after: u64,
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            stale_after: 1800,
+        }
+    }
+}
+
+/// A release asset: existing files, an archive built at release time, or a
+/// file downloaded and re-uploaded. `{version}` and `{tag}` are substituted in
+/// archive names, URLs and `rename` templates.
+///
+/// ```yaml
+/// artifacts:
+///   - dist/*.tar.gz
+///   - path: dist/*.sha256
+///     required: true
+///   - path: dist/myapp-*.zip
+///     rename: "{basename}-{version}{ext}"
+///   - archive:
+///       name: source-{version}.tar.gz
+///       paths: ["src/**", "LICENSE"]
+///       exclude: ["*.tmp", "fixtures/"]
+///   - url: https://internal.example.com/build/{version}/app.zip
+///     token_env: BUILD_TOKEN
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ArtifactEntry {
+    /// Glob pattern for files that already exist, e.g. `dist/*.tar.gz`.
+    Glob(String),
+    /// A glob pattern with options of its own.
+    Pattern {
+        path: String,
+        /// Fail the release when `path` matches no files. Default:
+        /// `artifacts_required`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        required: Option<bool>,
+        /// Upload each matched file under this name instead of its own.
+        /// `{version}`, `{tag}`, `{basename}` (the file name without its
+        /// extension) and `{ext}` (the extension with its dot, e.g. `.tar.gz`)
+        /// are substituted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rename: Option<String>,
+    },
+    /// A `.tar.gz` built from repository files at release time.
+    Archive { archive: ArchiveArtifact },
+    /// A file downloaded at release time and uploaded as an asset.
+    Url {
+        url: String,
+        /// Environment variable holding a token sent as `Authorization: Bearer`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_env: Option<String>,
+        /// Asset name. Default: the last segment of the URL path.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+}
+
+impl From<&str> for ArtifactEntry {
+    fn from(pattern: &str) -> Self {
+        Self::Glob(pattern.to_string())
+    }
+}
+
+impl From<String> for ArtifactEntry {
+    fn from(pattern: String) -> Self {
+        Self::Glob(pattern)
+    }
+}
+
+/// A `.tar.gz` archive assembled by sr.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ArchiveArtifact {
+    /// File name of the archive, ending in `.tar.gz` or `.tgz`.
+    pub name: String,
+    /// Glob patterns of files and directories to include, relative to the
+    /// directory holding the config file. Directories are added recursively.
+    pub paths: Vec<String>,
+    /// `.gitignore`-style patterns to leave out: `*.log` matches at any depth,
+    /// `docs/internal` is anchored, `build/` only matches directories, and
+    /// `!keep.log` re-includes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Directory `paths` are matched in and archive members are named
+    /// relative to, set by [`ReleaseConfig::resolve_paths_relative_to`].
+    /// Empty means the current directory.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub base: PathBuf,
+}
+
+/// Supply-chain attestations (SBOMs, provenance statements) generated after
+/// `build_command` and uploaded with the artifacts. Each generator's stdout is
+/// saved as `<name>-<version>.<format>`; the commands get `SR_VERSION` and
+/// `SR_TAG` like the build command.
+///
+/// ```yaml
+/// attestations:
+///   enabled: true
+///   required: true
+///   embed_digests: true
+///   generators:
+///     - format: spdx.json
+///       run: syft . -o spdx-json
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct AttestationsConfig {
+    /// Generate and upload the attestations.
+    pub enabled: bool,
+    /// Abort the release when a generator fails. Otherwise its file is skipped
+    /// with a warning.
+    pub required: bool,
+    /// Append the SHA-256 digests of every uploaded file to the release body.
+    pub embed_digests: bool,
+    /// Base name of the generated files. Default: the package name, else the
+    /// name of the current directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// One generator per output format.
+    pub generators: Vec<AttestationGenerator>,
+}
+
+/// A command whose stdout becomes one attestation file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct AttestationGenerator {
+    /// File extension of the output, e.g. `spdx.json` or `intoto.jsonl`.
+    pub format: String,
+    /// Command that writes the attestation to stdout.
+    pub run: HookCommand,
+}
+
+/// Release train: batch changes instead of releasing every merge. A release is
+/// deferred until every configured threshold is met; `--override-policy`
+/// releases anyway.
+///
+/// ```yaml
+/// policy:
+///   min_commits: 3      # releasable commits since the last tag
+///   min_interval: 24h   # time since the last tag
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct PolicyConfig {
+    /// Releasable (version-bumping) commits needed since the last tag. `0` disables.
+    pub min_commits: usize,
+    /// Minimum time since the last tag, e.g. `30m`, `24h`, `7d`, or `1h30m`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_interval: Option<String>,
+}
+
+impl PolicyConfig {
+    /// `min_interval` in seconds, or `None` when unset.
+    pub fn min_interval_secs(&self) -> Result<Option<u64>, ReleaseError> {
+        self.min_interval
+            .as_deref()
+            .map(|interval| {
+                parse_duration(interval)
+                    .map_err(|e| ReleaseError::Config(format!("policy.min_interval: {e}")))
+            })
+            .transpose()
+    }
+}
+
+/// Parse a duration such as `90s`, `30m`, `24h`, `7d`, `2w`, or a combination
+/// like `1h30m`, into seconds.
+pub fn parse_duration(input: &str) -> Result<u64, String> {
+    let text = input.trim();
+    if text.is_empty() {
+        return Err("empty duration".into());
+    }
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86_400,
+            'w' => 604_800,
+            _ => return Err(format!("invalid duration '{input}' (unknown unit '{c}')")),
+        };
+        let amount: u64 = digits
+            .parse()
+            .map_err(|_| format!("invalid duration '{input}' (expected e.g. 24h, 30m, 7d)"))?;
+        total = amount
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| format!("duration '{input}' is too large"))?;
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        return Err(format!(
+            "invalid duration '{input}' (missing unit, e.g. {digits}h)"
+        ));
+    }
+    Ok(total)
+}
+
+/// Preset that decides how a notification body is built.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationKind {
+    /// Generic webhook: `body_template` is sent as-is, or a JSON summary by default.
+    #[default]
+    Webhook,
+    /// Slack incoming webhook: the template becomes `{"text": ...}`.
+    Slack,
+    /// Discord webhook: the template becomes `{"content": ...}`.
+    Discord,
+}
+
+/// A webhook or chat notification sent after `sr release`.
+///
+/// Templates accept `{version}`, `{tag}`, `{date}`, `{release_url}`, and
+/// `{changelog}` (truncated); `failure_template` also gets `{error}`.
+///
+/// ```yaml
+/// notifications:
+///   - type: slack
+///     url_env: SLACK_WEBHOOK_URL
+///     failure_template: "Release {tag} failed: {error}"
+///   - url: https://example.com/hooks/release
+///     method: PUT
+///     body_template: '{"version": "{version}"}'
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct NotificationConfig {
+    /// `webhook` (default), `slack`, or `discord`.
+    #[serde(rename = "type")]
+    pub kind: NotificationKind,
+    /// Webhook URL (`https://` only). Prefer `url_env` when the URL embeds a secret.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Environment variable holding the webhook URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_env: Option<String>,
+    /// HTTP method: POST (default), PUT, or PATCH.
+    pub method: String,
+    /// Request body (webhook) or message text (slack, discord) after a successful
+    /// release. Defaults to a JSON summary, or a short message for chat presets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
+    /// Sent instead when the release fails. Without it, failures are not notified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_template: Option<String>,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            kind: NotificationKind::Webhook,
+            url: None,
+            url_env: None,
+            method: "POST".into(),
+            body_template: None,
+            failure_template: None,
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// Resolve the webhook URL from `url` or the `url_env` variable.
+    pub fn resolve_url(&self) -> Result<String, ReleaseError> {
+        if let Some(ref url) = self.url {
+            return Ok(url.clone());
+        }
+        let var = self.url_env.as_deref().unwrap_or_default();
+        std::env::var(var)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| ReleaseError::Notify(format!("${var} is not set")))
+    }
+
+    /// Record configuration problems under `field`.
+    fn check(&self, field: &str, problems: &mut Vec<String>) {
+        match (&self.url, &self.url_env) {
+            (None, None) => problems.push(format!("{field}: one of url or url_env is required")),
+            (Some(_), Some(_)) => {
+                problems.push(format!("{field}: url and url_env are mutually exclusive"))
             }
+            (Some(url), None) if !url.starts_with("https://") => {
+                problems.push(format!("{field}.url must start with https://"))
+            }
+            _ => {}
+        }
+        if !["POST", "PUT", "PATCH"].contains(&self.method.as_str()) {
+            problems.push(format!(
+                "{field}.method must be POST, PUT, or PATCH (got {})",
+                self.method
+            ));
+        }
+        let success = &NOTIFICATION_PLACEHOLDERS[..NOTIFICATION_PLACEHOLDERS.len() - 1];
+        if let Some(ref template) = self.body_template {
+            check_template(
+                &format!("{field}.body_template"),
+                template,
+                success,
+                problems,
+            );
+        }
+        if let Some(ref template) = self.failure_template {
+            check_template(
+                &format!("{field}.failure_template"),
+                template,
+                NOTIFICATION_PLACEHOLDERS,
+                problems,
+            );
+        }
+    }
+}
+
+/// A single entry in a hook's command list.
+///
+/// Can be either a simple shell command string or a structured step with
+/// file-pattern matching.
+///
+/// ```yaml
+/// hooks:
+///   commit-msg:
+///     - sr hook commit-msg          # simple command
+///   pre-commit:
+///     - step: format                # structured step
+///       patterns:
+///         - "*.rs"
+///       rules:
+///         - "rustfmt --check --edition 2024 {files}"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum HookEntry {
+    /// Run `rules` against staged files matching `patterns`.
+    Step {
+        step: String,
+        patterns: Vec<String>,
+        rules: Vec<String>,
+    },
+    /// A shell command, as a plain string or with per-command options.
+    Command(HookCommand),
+}
+
+/// A shell command run by a hook or release lifecycle step.
+///
+/// Accepts a plain string or a structured form with per-command options:
+///
+/// ```yaml
+/// pre_release_command:
+///   run: ./scripts/check.sh
+///   timeout: 300
+///   continue_on_error: false
+///   cwd: scripts
+///   shell: bash
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookCommand {
+    /// The command line to run.
+    pub run: String,
+    /// Seconds before the command (and its whole process group) is killed.
+    pub timeout: Option<u64>,
+    /// Report a failure as a warning instead of aborting.
+    pub continue_on_error: bool,
+    /// Working directory, relative to the current directory.
+    pub cwd: Option<String>,
+    /// Shell used to run the command (default: `sh`, or `cmd` on Windows without
+    /// `sh`). `cmd` runs it with `/C`, `powershell` and `pwsh` with `-Command`,
+    /// anything else with `-c`.
+    pub shell: Option<String>,
+}
+
+impl HookCommand {
+    /// Whether any option beyond `run` is set.
+    fn has_options(&self) -> bool {
+        self.timeout.is_some()
+            || self.continue_on_error
+            || self.cwd.is_some()
+            || self.shell.is_some()
+    }
+}
+
+impl From<&str> for HookCommand {
+    fn from(run: &str) -> Self {
+        Self {
+            run: run.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<String> for HookCommand {
+    fn from(run: String) -> Self {
+        Self {
+            run,
+            ..Default::default()
+        }
+    }
+}
+
+impl std::fmt::Display for HookCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.run)
+    }
+}
+
+/// Structured form of [`HookCommand`] as it appears in the config file.
+#[derive(Serialize, Deserialize)]
+struct HookCommandFields {
+    run: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    continue_on_error: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shell: Option<String>,
+}
+
+impl Serialize for HookCommand {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !self.has_options() {
+            return serializer.serialize_str(&self.run);
+        }
+        HookCommandFields {
+            run: self.run.clone(),
+            timeout: self.timeout,
+            continue_on_error: self.continue_on_error,
+            cwd: self.cwd.clone(),
+            shell: self.shell.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HookCommand {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Run(String),
+            Fields(HookCommandFields),
+        }
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Run(run) => run.into(),
+            Repr::Fields(f) => Self {
+                run: f.run,
+                timeout: f.timeout,
+                continue_on_error: f.continue_on_error,
+                cwd: f.cwd,
+                shell: f.shell,
+            },
+        })
+    }
+}
+
+impl JsonSchema for HookCommand {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "HookCommand".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A shell command, as a string or with per-command options.",
+            "anyOf": [
+                { "type": "string" },
+                {
+                    "type": "object",
+                    "properties": {
+                        "run": { "type": "string", "description": "The command line to run." },
+                        "timeout": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Seconds before the command (and its whole process group) is killed.",
+                        },
+                        "continue_on_error": {
+                            "type": "boolean",
+                            "description": "Report a failure as a warning instead of aborting.",
+                        },
+                        "cwd": { "type": "string", "description": "Working directory, relative to the current directory." },
+                        "shell": { "type": "string", "description": "Shell used to run the command." },
+                    },
+                    "required": ["run"],
+                    "additionalProperties": false,
+                },
+            ],
+        })
+    }
+}
+
+/// Git hooks configuration.
+///
+/// Each key is a git hook name (e.g. `commit-msg`, `pre-commit`, `pre-push`)
+/// and the value is a list of entries — either simple shell commands or
+/// structured steps with file-pattern matching.
+///
+/// Hook scripts in `.githooks/` are generated by `sr init`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(transparent)]
+pub struct HooksConfig {
+    pub hooks: BTreeMap<String, Vec<HookEntry>>,
+}
+
+impl HooksConfig {
+    pub fn with_defaults() -> Self {
+        let mut hooks = BTreeMap::new();
+        hooks.insert(
+            "commit-msg".into(),
+            vec![HookEntry::Command("sr hook commit-msg".into())],
+        );
+        Self { hooks }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct ChangelogConfig {
+    /// Changelog file to prepend release notes to. None = no changelog file.
+    pub file: Option<String>,
+    /// Minijinja template for a changelog entry. None = built-in format.
+    pub template: Option<String>,
+    /// strftime-style format for release dates (`{date}` in changelog headings,
+    /// release names, and the release commit message). None = `%Y-%m-%d`.
+    pub date_format: Option<String>,
+    /// IANA time zone release dates are computed in (e.g. `Europe/Berlin`). None = UTC.
+    pub timezone: Option<String>,
+    /// Where a new release's date comes from.
+    pub date_source: DateSource,
+    /// Root only, monorepo mode: also add every package release to the root
+    /// `file`, as a per-package subsection under the release date.
+    pub aggregate: bool,
+    /// Section headings the changelog file includes, e.g. `[Breaking Changes,
+    /// Features]`. Empty = every section.
+    pub sections: Vec<String>,
+    /// End each changelog entry with a link comparing it to the previous release.
+    pub compare_link: bool,
+    /// Link each version heading to its release page, when the repository URL is known.
+    pub link_versions: bool,
+    /// Where commits of types not in `types` go: left out (`hide`), under
+    /// `misc_section` (`misc`), or under `other_section` (`own-section`).
+    pub unknown_types: UnknownTypes,
+    /// End each entry with a "Migration notes" section gathering the
+    /// `BREAKING CHANGE` footer texts of its breaking changes, oldest first.
+    pub migration_notes: bool,
+    /// Rewrites applied to commit descriptions in the changelog and release body.
+    pub transform: DescriptionTransform,
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            template: None,
+            date_format: None,
+            timezone: None,
+            date_source: DateSource::default(),
+            aggregate: false,
+            sections: Vec::new(),
+            compare_link: true,
+            link_versions: true,
+            unknown_types: UnknownTypes::default(),
+            migration_notes: false,
+            transform: DescriptionTransform::default(),
+        }
+    }
+}
+
+/// Where a release's date comes from. Dating a release by a commit keeps it
+/// stable when the release is re-run later, so regenerated changelogs match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DateSource {
+    /// Commit time of the commit being released (HEAD when planned).
+    #[default]
+    Commit,
+    /// Commit time of the release tag when it already exists (re-runs).
+    Tag,
+    /// The wall clock.
+    Now,
+}
+
+/// Release date format used when `changelog.date_format` is not set.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+impl ChangelogConfig {
+    /// Format a Unix timestamp (seconds) as a release date, using `date_format`
+    /// in `timezone`. New releases and tags read back from git go through here,
+    /// so regenerated changelogs agree with the ones written at release time.
+    pub fn format_date(&self, unix_seconds: i64) -> Result<String, ReleaseError> {
+        let timestamp = jiff::Timestamp::from_second(unix_seconds)
+            .map_err(|e| ReleaseError::Config(format!("invalid timestamp {unix_seconds}: {e}")))?;
+        let format = self.date_format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT);
+        jiff::fmt::strtime::format(format, &timestamp.to_zoned(self.time_zone()?))
+            .map_err(|e| ReleaseError::Config(format!("changelog.date_format '{format}': {e}")))
+    }
+
+    /// Today's release date.
+    pub fn today(&self) -> Result<String, ReleaseError> {
+        self.format_date(jiff::Timestamp::now().as_second())
+    }
+
+    /// Today's calendar date in `timezone`, which CalVer versions are numbered by.
+    pub fn today_date(&self) -> Result<jiff::civil::Date, ReleaseError> {
+        Ok(jiff::Timestamp::now().to_zoned(self.time_zone()?).date())
+    }
+
+    fn time_zone(&self) -> Result<jiff::tz::TimeZone, ReleaseError> {
+        match self.timezone.as_deref() {
+            Some(name) => jiff::tz::TimeZone::get(name)
+                .map_err(|e| ReleaseError::Config(format!("changelog.timezone '{name}': {e}"))),
+            None => Ok(jiff::tz::TimeZone::UTC),
+        }
+    }
+}
+
+/// The config classifies commits by its `types` and `rules` without compiling
+/// `commit_pattern`; use [`ReleaseConfig::classifier`] to parse commits too.
+impl CommitClassifier for ReleaseConfig {
+    fn types(&self) -> &[CommitType] {
+        &self.types
+    }
+    fn rules(&self) -> &[CommitRule] {
+        &self.rules
+    }
+    fn pattern(&self) -> &str {
+        &self.commit_pattern
+    }
+}
+
+impl ReleaseConfig {
+    /// Find the first config file that exists in the given directory, falling
+    /// back to a manifest that embeds config. Returns `(path, is_legacy)`.
+    pub fn find_config(dir: &Path) -> Option<(PathBuf, bool)> {
+        for &candidate in CONFIG_CANDIDATES {
+            let path = dir.join(candidate);
+            if path.exists() {
+                let is_legacy = candidate == LEGACY_CONFIG_FILE;
+                return Some((path, is_legacy));
+            }
+        }
+        for &manifest in MANIFEST_CONFIG_FILES {
+            let path = dir.join(manifest);
+            if matches!(manifest_config(&path), Ok(Some(_))) {
+                return Some((path, false));
+            }
+        }
+        None
+    }
+
+    /// Find the nearest config file, starting in `start` and walking up parent
+    /// directories until the git root (the first directory containing `.git`).
+    /// Returned paths stay relative to `start` (e.g. `../sr.yaml`).
+    /// Returns `(path, is_legacy)`.
+    pub fn discover_config(start: &Path) -> Option<(PathBuf, bool)> {
+        let mut abs = start.canonicalize().ok()?;
+        let mut dir = start.to_path_buf();
+        loop {
+            if let Some(found) = Self::find_config(&dir) {
+                return Some(found);
+            }
+            if abs.join(".git").exists() || !abs.pop() {
+                return None;
+            }
+            dir = if dir == Path::new(".") {
+                PathBuf::from("..")
+            } else {
+                dir.join("..")
+            };
+        }
+    }
+
+    /// Rewrite relative `version_files` and `changelog.file` paths (including
+    /// per-package ones) so they resolve against `base`, the directory holding
+    /// the config file, rather than the current directory. Archive artifacts
+    /// are built from files under `base`.
+    pub fn resolve_paths_relative_to(&mut self, base: &Path) {
+        if base.as_os_str().is_empty() || base == Path::new(".") {
+            return;
+        }
+        let rebase = |path: &mut String| {
+            if Path::new(path.as_str()).is_relative() {
+                *path = base.join(path.as_str()).to_string_lossy().into_owned();
+            }
+        };
+        self.version_files.iter_mut().for_each(rebase);
+        self.version_file_urls = std::mem::take(&mut self.version_file_urls)
+            .into_iter()
+            .map(|(mut file, url)| {
+                rebase(&mut file);
+                (file, url)
+            })
+            .collect();
+        if let Some(file) = self.changelog.file.as_mut() {
+            rebase(file);
+        }
+        if let Some(file) = self.summary.file.as_mut() {
+            rebase(file);
+        }
+        for artifact in &mut self.artifacts {
+            if let ArtifactEntry::Archive { archive } = artifact {
+                archive.base = base.join(&archive.base);
+            }
+        }
+        for pkg in &mut self.packages {
+            pkg.version_files.iter_mut().for_each(rebase);
+            if let Some(file) = pkg.changelog.as_mut().and_then(|c| c.file.as_mut()) {
+                rebase(file);
+            }
+            if let Some(file) = pkg.changelog_file.as_mut() {
+                rebase(file);
+            }
+        }
+    }
+
+    /// Load config from a YAML file, or from the embedded section of a manifest
+    /// (see [`MANIFEST_CONFIG_FILES`]). Falls back to defaults if the file doesn't
+    /// exist or the manifest has no `sr` section. Remote `extends` URLs cannot be
+    /// fetched; use [`load_with`](Self::load_with) for those.
+    pub fn load(path: &Path) -> Result<Self, ReleaseError> {
+        Self::load_with(path, None)
+    }
+
+    /// Load config like [`load`](Self::load), resolving `extends` chains. Remote
+    /// bases are retrieved with `fetch`; with `None` (offline) they are an error.
+    pub fn load_with(path: &Path, fetch: Option<&ConfigFetcher<'_>>) -> Result<Self, ReleaseError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let location = ConfigLocation::File(path.to_path_buf());
+        let Some((value, contents)) = location.read(fetch)? else {
+            return Ok(Self::default());
+        };
+        if value.get("extends").is_none() {
+            return Self::parse(&contents);
+        }
+
+        let mut problems = Vec::new();
+        let merged = resolve_extends(&location, value, fetch, &mut Vec::new(), &mut problems)?;
+        let config = serde_yaml_ng::from_value::<Self>(merged.clone());
+        Self::finish(config, &merged, problems)
+    }
+
+    /// Parse and strictly validate config YAML. Unknown keys, type errors, and
+    /// semantic violations are all collected and reported together.
+    pub fn parse(contents: &str) -> Result<Self, ReleaseError> {
+        let value: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(contents).map_err(|e| ReleaseError::Config(e.to_string()))?;
+        let mut problems = Vec::new();
+        unknown_keys(&value, "", "", &mut problems);
+        Self::finish(serde_yaml_ng::from_str(contents), &value, problems)
+    }
+
+    /// Apply `types_mode` to a deserialized config and fold every problem found
+    /// so far, plus semantic violations, into a single result. `value` is the
+    /// document the config was deserialized from.
+    fn finish(
+        config: Result<Self, serde_yaml_ng::Error>,
+        value: &serde_yaml_ng::Value,
+        mut problems: Vec<String>,
+    ) -> Result<Self, ReleaseError> {
+        match config {
+            Ok(mut config) => {
+                if config.types_mode == MergeMode::Extend
+                    && let Some(serde_yaml_ng::Value::Sequence(types)) = value.get("types")
+                {
+                    match merge_with_default_types(types.clone()) {
+                        Ok(types) => config.types = types,
+                        Err(e) => problems.push(format!("types: {e}")),
+                    }
+                }
+                problems.extend(config.violations());
+                if problems.is_empty() {
+                    return Ok(config);
+                }
+            }
+            Err(e) => problems.push(e.to_string()),
+        }
+        Err(config_problems(problems))
+    }
+
+    /// Check values that serde cannot validate on its own.
+    pub fn validate(&self) -> Result<(), ReleaseError> {
+        let problems = self.violations();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(config_problems(problems))
+        }
+    }
+
+    /// Every semantic constraint the config violates.
+    pub fn violations(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        check_template(
+            "release_commit_message",
+            &self.release_commit_message,
+            TEMPLATE_PLACEHOLDERS,
+            &mut problems,
+        );
+        let subject = self
+            .release_commit_message
+            .lines()
+            .next()
+            .unwrap_or_default();
+        if let Ok(parts) = template_parts(subject)
+            && !subject.trim().is_empty()
+            && !has_literal_text(&parts)
+        {
+            problems.push(
+                "release_commit_message: the subject line needs text besides placeholders, \
+                 or every commit would look like a release commit"
+                    .into(),
+            );
+        }
+        for (key, value) in &self.release_commit_trailers {
+            let valid_key = !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid_key {
+                problems.push(format!(
+                    "release_commit_trailers: invalid trailer key '{key}' (use letters, digits, '-' and '_')"
+                ));
+            }
+            if value.contains('\n') {
+                problems.push(format!(
+                    "release_commit_trailers.{key}: value must be a single line"
+                ));
+            }
+            check_template(
+                &format!("release_commit_trailers.{key}"),
+                value,
+                TEMPLATE_PLACEHOLDERS,
+                &mut problems,
+            );
+        }
+        if let Err(e) = CalverFormat::parse(&self.calver_format) {
+            problems.push(format!("calver_format: {e}"));
+        }
+        if self.versioning == Versioning::Calver && self.prerelease.is_some() {
+            problems.push("prerelease: not supported with versioning: calver".into());
+        }
+        if let Some(floor) = &self.version_floor
+            && let Err(e) = semver::Version::parse(floor.trim_start_matches('v'))
+        {
+            problems.push(format!(
+                "version_floor: '{floor}' is not a semver version: {e}"
+            ));
+        }
+        if self.summary.upload && self.summary.file.is_none() {
+            problems.push("summary.upload: set summary.file to upload the release summary".into());
+        }
+        if !self.tags.annotated && self.effective_signing().tags {
+            problems.push(
+                "tags.annotated: lightweight tags cannot be signed; drop signing.tags / sign_tags \
+                 or keep annotated tags"
+                    .into(),
+            );
+        }
+        check_template(
+            "release.name_template",
+            &self.release.name_template,
+            RELEASE_NAME_PLACEHOLDERS,
+            &mut problems,
+        );
+        match &self.release.notes_file_template {
+            Some(template) => check_template(
+                "release.notes_file_template",
+                template,
+                URL_PLACEHOLDERS,
+                &mut problems,
+            ),
+            None if self.release.notes_file_required => problems.push(
+                "release.notes_file_required: set release.notes_file_template to require a \
+                 notes file"
+                    .into(),
+            ),
+            None => {}
+        }
+        for (i, notification) in self.notifications.iter().enumerate() {
+            notification.check(&format!("notifications[{i}]"), &mut problems);
+        }
+        for (file, url) in &self.version_file_urls {
+            check_template(
+                &format!("version_file_urls.{file}"),
+                url,
+                URL_PLACEHOLDERS,
+                &mut problems,
+            );
+            let listed = self.version_files.contains(file)
+                || self.packages.iter().any(|p| p.version_files.contains(file));
+            if !listed {
+                problems.push(format!(
+                    "version_file_urls.{file}: not listed in version_files"
+                ));
+            }
+        }
+        if let Err(ReleaseError::Config(problem)) = self.policy.min_interval_secs() {
+            problems.push(problem);
+        }
+        if let Err(ReleaseError::Config(problem)) = self.changelog.format_date(0) {
+            problems.push(problem);
+        }
+        self.changelog
+            .transform
+            .check("changelog.transform", &mut problems);
+        for pkg in &self.packages {
+            let Some(changelog) = &pkg.changelog else {
+                continue;
+            };
+            if let Err(ReleaseError::Config(problem)) = changelog.format_date(0) {
+                problems.push(format!("packages.{}.{problem}", pkg.name));
+            }
+            changelog.transform.check(
+                &format!("packages.{}.changelog.transform", pkg.name),
+                &mut problems,
+            );
+        }
+
+        if self.tag_prefix.is_empty() {
+            problems.push("tag_prefix must not be empty".into());
+        }
+        self.tag_prefix.check("tag_prefix", &mut problems);
+        for pkg in &self.packages {
+            if pkg.changelog.as_ref().is_some_and(|c| c.aggregate) {
+                problems.push(format!(
+                    "packages.{}.changelog.aggregate only applies to the root changelog",
+                    pkg.name
+                ));
+            }
+        }
+        let known_sections = self.section_names();
+        let selections = [
+            ("changelog.sections".to_string(), &self.changelog.sections),
+            ("release.sections".to_string(), &self.release.sections),
+        ]
+        .into_iter()
+        .chain(self.packages.iter().filter_map(|pkg| {
+            let changelog = pkg.changelog.as_ref()?;
+            Some((
+                format!("packages.{}.changelog.sections", pkg.name),
+                &changelog.sections,
+            ))
+        }));
+        for (field, sections) in selections {
+            for section in sections {
+                if !known_sections.contains(&section.as_str()) {
+                    problems.push(format!(
+                        "{field}: unknown section '{section}' (available: {})",
+                        known_sections.join(", ")
+                    ));
+                }
+            }
+        }
+        if self.changelog.aggregate && self.changelog.file.is_none() {
+            problems.push("changelog.aggregate requires changelog.file".into());
+        }
+        for (i, artifact) in self.artifacts.iter().enumerate() {
+            match artifact {
+                ArtifactEntry::Glob(_) | ArtifactEntry::Pattern { rename: None, .. } => {}
+                ArtifactEntry::Pattern {
+                    rename: Some(rename),
+                    ..
+                } => {
+                    let field = format!("artifacts[{i}].rename");
+                    check_template(&field, rename, RENAME_PLACEHOLDERS, &mut problems);
+                    check_file_name(&field, rename, &mut problems);
+                }
+                ArtifactEntry::Archive { archive } => {
+                    let field = format!("artifacts[{i}].archive.name");
+                    check_template(&field, &archive.name, URL_PLACEHOLDERS, &mut problems);
+                    if !archive.name.ends_with(".tar.gz") && !archive.name.ends_with(".tgz") {
+                        problems.push(format!("{field} must end in .tar.gz or .tgz"));
+                    }
+                    check_file_name(&field, &archive.name, &mut problems);
+                    if archive.paths.is_empty() {
+                        problems.push(format!("artifacts[{i}].archive.paths must not be empty"));
+                    }
+                }
+                ArtifactEntry::Url { url, name, .. } => {
+                    check_template(
+                        &format!("artifacts[{i}].url"),
+                        url,
+                        URL_PLACEHOLDERS,
+                        &mut problems,
+                    );
+                    if !url.starts_with("https://") {
+                        problems.push(format!("artifacts[{i}].url must be an https:// URL"));
+                    }
+                    if let Some(name) = name {
+                        let field = format!("artifacts[{i}].name");
+                        check_template(&field, name, URL_PLACEHOLDERS, &mut problems);
+                        check_file_name(&field, name, &mut problems);
+                    }
+                }
+            }
+        }
+        for (i, generator) in self.attestations.generators.iter().enumerate() {
+            let format = generator.format.trim_start_matches('.');
+            if format.is_empty() || format.contains(['/', '\\']) {
+                problems.push(format!(
+                    "attestations.generators[{i}].format must be a file extension like `spdx.json`"
+                ));
+            }
+        }
+        for pkg in &self.packages {
+            if pkg.tag_prefix.as_ref().is_some_and(TagPrefix::is_empty) {
+                problems.push(format!(
+                    "packages.{}.tag_prefix must not be empty",
+                    pkg.name
+                ));
+            }
+            if let Some(prefix) = &pkg.tag_prefix {
+                prefix.check(&format!("packages.{}.tag_prefix", pkg.name), &mut problems);
+            }
+        }
+
+        match regex::Regex::new(&self.commit_pattern) {
+            Ok(re) => {
+                for group in ["type", "description"] {
+                    if !re.capture_names().any(|name| name == Some(group)) {
+                        problems.push(format!(
+                            "commit_pattern must contain a named group (?P<{group}>...)"
+                        ));
+                    }
+                }
+            }
+            Err(e) => problems.push(format!("commit_pattern is not a valid regex: {e}")),
+        }
+
+        for (i, rule) in self.rules.iter().enumerate() {
+            let when = &rule.when;
+            if *when == RuleCondition::default() {
+                problems.push(format!(
+                    "rules[{i}].when must set at least one of type, scope, scope_regex, breaking"
+                ));
+            }
+            if when.scope.is_some() && when.scope_regex.is_some() {
+                problems.push(format!(
+                    "rules[{i}].when: set scope or scope_regex, not both"
+                ));
+            }
+            if let Some(pattern) = &when.scope_regex
+                && let Err(e) = regex::Regex::new(pattern)
+            {
+                problems.push(format!(
+                    "rules[{i}].when.scope_regex is not a valid regex: {e}"
+                ));
+            }
+            if rule.section.is_none() && rule.bump.is_none() {
+                problems.push(format!("rules[{i}] must set section or bump"));
+            }
+        }
+
+        for (key, url) in [
+            ("github.api_url", &self.github.api_url),
+            ("github.upload_url", &self.github.upload_url),
+        ] {
+            if let Some(url) = url
+                && !(url.starts_with("https://") || is_loopback_http(url))
+            {
+                problems.push(format!(
+                    "{key}: '{url}' must be an https URL (plain http only on localhost)"
+                ));
+            }
+        }
+
+        if let Some(notes_ref) = self.git.notes_ref() {
+            let valid = notes_ref
+                .strip_prefix("refs/notes/")
+                .is_some_and(|name| !name.is_empty())
+                && !notes_ref.contains(|c: char| c.is_whitespace() || "~^:?*[\\".contains(c))
+                && !notes_ref.contains("..");
+            if !valid {
+                problems.push(format!(
+                    "git.release_notes_ref: '{notes_ref}' is not a ref under refs/notes/"
+                ));
+            }
+        }
+
+        let mut seen = std::collections::BTreeSet::new();
+        for t in &self.types {
+            if !seen.insert(t.name.as_str()) {
+                problems.push(format!("types: duplicate entry '{}'", t.name));
+            }
+        }
+
+        problems
+    }
+
+    /// Render `release_commit_message` for a release.
+    pub fn render_release_commit_message(&self, version: &str, tag: &str, date: &str) -> String {
+        render_placeholders(&self.release_commit_message, version, tag, date)
+    }
+
+    /// Render `release_commit_trailers` for a release, as (key, value) pairs.
+    pub fn render_release_commit_trailers(
+        &self,
+        version: &str,
+        tag: &str,
+        date: &str,
+    ) -> Vec<(String, String)> {
+        self.release_commit_trailers
+            .iter()
+            .map(|(key, value)| (key.clone(), render_placeholders(value, version, tag, date)))
+            .collect()
+    }
+
+    /// Download URL for version file `file` in a release, from `version_file_urls`.
+    pub fn version_file_url(&self, file: &str, version: &str, tag: &str) -> Option<String> {
+        self.version_file_urls
+            .get(file)
+            .map(|template| render_url_template(template, version, tag))
+    }
+
+    /// Render `release.name_template` for a release.
+    pub fn render_release_name(&self, version: &str, tag: &str, date: &str) -> String {
+        let template = self
+            .release
+            .name_template
+            .replace("{display_name}", &tag_display_name(tag));
+        render_placeholders(&template, version, tag, date)
+    }
+
+    /// Path of the curated release notes for a release, from
+    /// `release.notes_file_template`.
+    pub fn release_notes_file(&self, version: &str, tag: &str) -> Option<String> {
+        self.release
+            .notes_file_template
+            .as_deref()
+            .map(|template| render_url_template(template, version, tag))
+    }
+
+    /// Formatter for changelog entries, set up from `changelog` and the commit types.
+    pub fn changelog_formatter(&self) -> Result<DefaultChangelogFormatter, ReleaseError> {
+        Ok(DefaultChangelogFormatter::new(
+            self.changelog.template.clone(),
+            self.classifier()?,
+            self.breaking_section.clone(),
+            self.misc_section.clone(),
+        )
+        .with_transform(self.changelog.transform.clone())
+        .with_version_links(self.changelog.link_versions)
+        .with_unknown_types(self.changelog.unknown_types, self.other_section.clone())
+        .with_migration_notes(self.changelog.migration_notes))
+    }
+
+    /// Classifier for `types` and `rules`, parsing with `commit_pattern`.
+    pub fn classifier(&self) -> Result<DefaultCommitClassifier, ReleaseError> {
+        Ok(
+            DefaultCommitClassifier::new(self.types.clone(), &self.commit_pattern)?
+                .with_rules(self.rules.clone()),
+        )
+    }
+
+    /// Every changelog section heading, in the order the built-in format
+    /// writes them: breaking changes, the `types` and `rules` sections,
+    /// miscellaneous, and other with `changelog.unknown_types: own-section`.
+    pub fn section_names(&self) -> Vec<&str> {
+        let mut names = vec![self.breaking_section.as_str()];
+        for section in self.section_order() {
+            if !names.contains(&section) {
+                names.push(section);
+            }
+        }
+        names.push(&self.misc_section);
+        if self.changelog.unknown_types == UnknownTypes::OwnSection {
+            names.push(&self.other_section);
+        }
+        names
+    }
+
+    /// The section heading `commit` is listed under, or None for a type not
+    /// in `types` that no rule places, with `changelog.unknown_types: hide`
+    /// (which the built-in format leaves out).
+    pub fn section_of(&self, commit: &ConventionalCommit) -> Option<&str> {
+        let section = self
+            .commit_section(commit)
+            .or_else(|| self.changelog.unknown_types.section())?;
+        Some(self.section_heading(section))
+    }
+
+    fn section_heading<'a>(&'a self, section: ChangelogSection<'a>) -> &'a str {
+        match section {
+            ChangelogSection::Breaking => &self.breaking_section,
+            ChangelogSection::Named(section) => section,
+            ChangelogSection::Misc => &self.misc_section,
+            ChangelogSection::Other => &self.other_section,
+        }
+    }
+
+    /// The heading commits of types not in `types` are listed under, per
+    /// `changelog.unknown_types`. None = left out.
+    pub fn unknown_types_section(&self) -> Option<&str> {
+        let section = self.changelog.unknown_types.section()?;
+        Some(self.section_heading(section))
+    }
+
+    /// `entry` cut down to what `destination` shows: the commits in its
+    /// `sections` (all when empty), and the compare link only with `compare_link`.
+    pub fn entry_for(&self, mut entry: ChangelogEntry, destination: Destination) -> ChangelogEntry {
+        let (sections, compare_link) = match destination {
+            Destination::Changelog => (&self.changelog.sections, self.changelog.compare_link),
+            Destination::Release => (&self.release.sections, self.release.compare_link),
+        };
+        if !sections.is_empty() {
+            entry.commits.retain(|c| {
+                self.section_of(c)
+                    .is_some_and(|section| sections.iter().any(|s| s == section))
+            });
+        }
+        if !compare_link {
+            entry.compare_url = None;
+        }
+        entry
+    }
+
+    /// Matcher for commits created by the release step, so they can be kept out
+    /// of changelogs even when `release_commit_message` is customized.
+    pub fn release_commit_matcher(&self) -> ReleaseCommitMatcher {
+        // Every package's tags, as a monorepo's history holds all their release commits
+        let mut prefixes: Vec<String> = self
+            .tag_prefix
+            .all()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        for pkg in &self.packages {
+            match &pkg.tag_prefix {
+                Some(prefix) => prefixes.extend(prefix.all().into_iter().map(String::from)),
+                None => prefixes.push(format!("{}/v", pkg.name)),
+            }
+        }
+        let prefixes: Vec<&str> = prefixes.iter().map(String::as_str).collect();
+        ReleaseCommitMatcher::new(&self.release_commit_message, &prefixes)
+    }
+
+    /// How `commits` line up with `types`, skipping release commits and
+    /// commits that do not match `commit_pattern`.
+    pub fn type_usage(&self, commits: &[Commit]) -> Result<TypeUsage, ReleaseError> {
+        let parser = DefaultCommitParser::new(&self.commit_pattern)?;
+        let release_commits = self.release_commit_matcher();
+        let parsed: Vec<ConventionalCommit> = commits
+            .iter()
+            .filter(|c| !release_commits.matches(&c.message))
+            .filter_map(|c| parser.parse(c).ok())
+            .collect();
+        Ok(TypeUsage::analyze(&self.types, &parsed)
+            .with_unknown_section(self.unknown_types_section()))
+    }
+
+    /// The CalVer format when `versioning` is `calver`.
+    pub fn calver(&self) -> Result<Option<CalverFormat>, ReleaseError> {
+        if self.versioning != Versioning::Calver {
+            return Ok(None);
+        }
+        CalverFormat::parse(&self.calver_format)
+            .map(Some)
+            .map_err(|e| ReleaseError::Config(format!("calver_format: {e}")))
+    }
+
+    /// `version` as written to tags, changelogs, and version files: semver as
+    /// is, or padded per `calver_format` (e.g. `2025.03.2`).
+    pub fn format_version(&self, version: &semver::Version) -> String {
+        match self.calver() {
+            Ok(Some(format)) => format.format(version),
+            _ => version.to_string(),
+        }
+    }
+
+    /// `version_floor` as a version, or `None` when unset or invalid.
+    pub fn version_floor(&self) -> Option<semver::Version> {
+        let floor = self.version_floor.as_deref()?;
+        semver::Version::parse(floor.trim_start_matches('v')).ok()
+    }
+
+    /// Signing settings with the `sign_tags` shorthand folded in.
+    pub fn effective_signing(&self) -> SigningConfig {
+        let mut signing = self.signing.clone();
+        signing.tags |= self.sign_tags;
+        signing
+    }
+
+    /// Git pathspecs restricting commit collection: the package path (if any),
+    /// `include_paths`, then `exclude_paths` as `:(exclude)` entries.
+    /// Empty means no restriction.
+    pub fn pathspecs(&self) -> Vec<String> {
+        let mut specs: Vec<String> = self.path_filter.iter().cloned().collect();
+        specs.extend(self.include_paths.iter().cloned());
+        specs.extend(self.exclude_paths.iter().map(|p| format!(":(exclude){p}")));
+        specs
+    }
+
+    /// Resolve a package into a full release config by merging package overrides with root config.
+    pub fn resolve_package(&self, pkg: &PackageConfig) -> Self {
+        let mut config = self.clone();
+        config.tag_prefix = pkg
+            .tag_prefix
+            .clone()
+            .unwrap_or_else(|| format!("{}/v", pkg.name).into());
+        config.path_filter = Some(pkg.path.clone());
+        // The package path replaces root includes; root excludes still apply
+        config.include_paths = vec![];
+        if !pkg.version_files.is_empty() {
+            config.version_files = pkg.version_files.clone();
+        } else if config.version_files.is_empty() {
+            // Auto-detect version files in the package directory
+            let detected = detect_version_files(Path::new(&pkg.path));
+            if !detected.is_empty() {
+                config.version_files = detected
+                    .into_iter()
+                    .map(|f| format!("{}/{f}", pkg.path))
+                    .collect();
+            }
+        }
+        // Each package writes its own changelog, so its entries never land in
+        // another package's file.
+        if let Some(ref cl) = pkg.changelog {
+            config.changelog = cl.clone();
+        } else if self.changelog.file.is_some() {
+            config.changelog.file = Some(format!("{}/CHANGELOG.md", pkg.path));
+        }
+        if let Some(ref file) = pkg.changelog_file {
+            config.changelog.file = Some(file.clone());
+        }
+        config.changelog.aggregate = false;
+        config.aggregate_changelog = self.changelog.file.clone().filter(|root| {
+            self.changelog.aggregate && config.changelog.file.as_ref() != Some(root)
+        });
+        config.package_name = Some(pkg.name.clone());
+        if let Some(ref cmd) = pkg.build_command {
+            config.build_command = Some(cmd.clone());
+        }
+        if !pkg.stage_files.is_empty() {
+            config.stage_files = pkg.stage_files.clone();
+        }
+        // Clear packages to avoid recursion
+        config.packages = vec![];
+        config
+    }
+
+    /// File `sr changelog --write` writes to: `changelog.file`, else
+    /// `CHANGELOG.md` in the package directory (or the repo root).
+    pub fn changelog_path(&self) -> String {
+        match (&self.changelog.file, &self.path_filter) {
+            (Some(file), _) => file.clone(),
+            (None, Some(path)) => format!("{path}/CHANGELOG.md"),
+            (None, None) => "CHANGELOG.md".into(),
+        }
+    }
+
+    /// Changelog files a release writes: `changelog.file`, plus the aggregated
+    /// root changelog for a package release.
+    pub fn changelog_files(&self) -> Vec<&str> {
+        self.changelog
+            .file
+            .iter()
+            .chain(&self.aggregate_changelog)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Find a package by name. Returns an error if the package is not found.
+    pub fn find_package(&self, name: &str) -> Result<&PackageConfig, ReleaseError> {
+        self.packages
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| {
+                let available: Vec<&str> = self.packages.iter().map(|p| p.name.as_str()).collect();
+                ReleaseError::Config(format!(
+                    "package '{name}' not found. Available: {}",
+                    if available.is_empty() {
+                        "(none — no packages configured)".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                ))
+            })
+    }
+}
+
+/// What `sr init` found in a project directory to pre-fill the config with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DetectedDefaults {
+    /// Manifests and source files carrying the version (see [`detect_version_files`]).
+    pub version_files: Vec<String>,
+    /// Artifact globs for the build output directories found, suggested
+    /// commented out since the directories may hold more than release assets.
+    pub artifacts: Vec<String>,
+    /// An existing changelog to prepend release notes to.
+    pub changelog_file: Option<String>,
+}
+
+impl DetectedDefaults {
+    pub fn is_empty(&self) -> bool {
+        self.version_files.is_empty() && self.artifacts.is_empty() && self.changelog_file.is_none()
+    }
+}
+
+/// Inspect the project in `dir` for [`DetectedDefaults`]: version files,
+/// build output (`dist/`, and `target/` next to a Cargo package), and a
+/// `CHANGELOG.md`.
+pub fn detect_project(dir: &Path) -> DetectedDefaults {
+    let mut artifacts = Vec::new();
+    if dir.join("dist").is_dir() {
+        artifacts.push("dist/*".to_string());
+    }
+    if dir.join("target").is_dir()
+        && let Some(name) = cargo_package_name(dir)
+    {
+        artifacts.push(format!("target/release/{name}"));
+    }
+    DetectedDefaults {
+        version_files: detect_version_files(dir),
+        artifacts,
+        changelog_file: dir
+            .join("CHANGELOG.md")
+            .is_file()
+            .then(|| "CHANGELOG.md".to_string()),
+    }
+}
+
+/// Generate a fully-commented default `sr.yaml` template.
+///
+/// The returned string is valid YAML with inline comments documenting every field.
+/// `detected` fills in `version_files`, `changelog.file`, and commented-out
+/// `artifacts` suggestions.
+pub fn default_config_template(detected: &DetectedDefaults) -> String {
+    let vf = if detected.version_files.is_empty() {
+        "version_files: []\n".to_string()
+    } else {
+        let mut s = "version_files:\n".to_string();
+        for f in &detected.version_files {
+            s.push_str(&format!("  - {f}\n"));
+        }
+        s
+    };
+    let mut artifacts = "artifacts: []\n".to_string();
+    if !detected.artifacts.is_empty() {
+        artifacts.push_str("# Build output found in this project:\n");
+        for glob in &detected.artifacts {
+            artifacts.push_str(&format!("#   - {glob}\n"));
+        }
+        artifacts.push_str("# Other forms:\n");
+    }
+    let changelog_file = detected
+        .changelog_file
+        .as_deref()
+        .map(|file| format!(" {file}"))
+        .unwrap_or_default();
+
+    format!(
+        r#"# yaml-language-server: $schema={CONFIG_SCHEMA_URL}
+# sr configuration
+# Full reference: https://github.com/urmzd/sr#configuration
+
+# Base config to inherit from: a path (relative to this file) or an https:// URL.
+# This file is deep-merged over the base; lists replace the base's lists.
+# extends: ../shared/sr.yaml
+
+# Branches that trigger releases when commits are pushed.
+branches:
+  - main
+  - master
+
+# Prefix prepended to version tags (e.g. "v1.2.0"). A list like [v, release-]
+# names new tags with the first prefix and still finds the current version under
+# the others, e.g. after renaming release-* tags to v*.
+tag_prefix: "v"
+
+# Read two-component tags like v1.2 as 1.2.0 instead of ignoring them.
+lenient_tags: false
+
+# Version scheme: semver (bump from commits) or calver (numbered by date; commits
+# only decide whether to release).
+versioning: semver
+
+# CalVer format for versioning: calver. A year token (YYYY, YY, 0Y), a month
+# (MM, 0M) or ISO week (WW, 0W) token, then MICRO, which counts releases within
+# the period. Weekly formats number years by ISO week.
+calver_format: "YYYY.0M.MICRO"
+
+# Regex for parsing conventional commits.
+# Required named groups: type, description.
+# Optional named groups: scope, breaking.
+commit_pattern: '^(?P<type>\w+)(?:\((?P<scope>[^)]+)\))?(?P<breaking>!)?:\s+(?P<description>.+)'
+
+# Changelog section heading for breaking changes.
+breaking_section: Breaking Changes
+
+# Fallback changelog section for unrecognised commit types.
+misc_section: Miscellaneous
+
+# Changelog section for commit types not listed below, with
+# changelog.unknown_types: own-section.
+other_section: Other
+
+# Commit type definitions.
+# name:    commit type prefix (e.g. "feat", "fix")
+# bump:    version bump level — major, minor, patch, or omit for no bump
+# section: changelog section heading, or omit to exclude from changelog
+types:
+  - name: feat
+    bump: minor
+    section: Features
+  - name: fix
+    bump: patch
+    section: Bug Fixes
+  - name: perf
+    bump: patch
+    section: Performance
+  - name: docs
+    section: Documentation
+  - name: refactor
+    section: Refactoring
+  - name: revert
+    section: Reverts
+  - name: chore
+  - name: ci
+  - name: test
+  - name: build
+  - name: style
+
+# How types combine with the built-in types above: extend (default) merges
+# entries by name, so a single entry can add a type or override one field of a
+# default (e.g. just the section of fix); replace uses the list as-is.
+types_mode: extend
+
+# How types combine with the extends base: replace (default) or extend
+# (entries merged by name, new ones appended).
+types_merge: replace
+
+# Rules checked before the types lookup, first match wins. `when` matches on
+# type, scope (exact), scope_regex, and breaking; a matching commit is listed
+# under `section` and bumps at least `bump`.
+rules: []
+#   - when: {{type: fix, scope: security}}
+#     section: Security
+#     bump: minor
+
+# Changelog configuration.
+# file:     path to the changelog file (e.g. CHANGELOG.md), or omit to skip writing
+# template: custom Minijinja template string for changelog rendering
+# date_format: strftime-style format for release dates (default: %Y-%m-%d)
+# timezone: IANA time zone for release dates (e.g. Europe/Berlin); omit for UTC
+# date_source: where release dates come from: commit (the released commit),
+#              tag (the existing release tag), or now; today when unavailable
+# aggregate: monorepo only; also add each package release to this file, grouped
+#            by date with a subsection per package
+changelog:
+  file:{changelog_file}
+  template:
+  date_format: "%Y-%m-%d"
+  timezone:
+  date_source: commit
+  aggregate: false
+  # Section headings to include (e.g. [Breaking Changes, Features]); empty = all.
+  sections: []
+  compare_link: true
+  # Link each version heading to its release page.
+  link_versions: true
+  # Commits of types not in types: hide, misc (under misc_section), or
+  # own-section (under other_section).
+  unknown_types: hide
+  # Breaking changes show their BREAKING CHANGE footer under the bullet; this
+  # also gathers the footers into a "Migration notes" section per release.
+  migration_notes: false
+  # Rewrites of commit descriptions in the changelog and release body.
+  # replacements: regexes applied in order; replace may use $1 or ${{name}}.
+  transform:
+    capitalize_first: false
+    strip_trailing_period: false
+    replacements: []
+  #   - pattern: "^\\[internal\\] "
+  #     replace: ""
+
+# Manifest files to bump on release (e.g. Cargo.toml, package.json, pyproject.toml).
+# Auto-detected if empty.
+{vf}
+# Fail if a version file uses an unsupported format (default: skip unknown files).
+version_files_strict: false
+
+# Lowest version to bump from (e.g. "2.3.1"); a newer tag wins. Without tags,
+# the highest version found in version_files is used as the base.
+version_floor:
+
+# Download URL templates for Homebrew formulae (*.rb) and Scoop manifests (*.json)
+# in version_files, rewritten on release. Placeholders: {{version}}, {{tag}}.
+# Checksums are not touched; fill them in from a later hook.
+version_file_urls: {{}}
+#   Formula/myapp.rb: https://github.com/me/myapp/archive/refs/tags/{{tag}}.tar.gz
+
+# Release assets to upload to GitHub: glob patterns (e.g. "dist/*.tar.gz"),
+# archives built at release time, and files downloaded from URLs.
+# Placeholders in archive names and URLs: {{version}}, {{tag}}.
+{artifacts}#   - dist/*.tar.gz
+#   - path: dist/*.sha256
+#     required: true                      # overrides artifacts_required
+#   - path: dist/myapp-*.zip              # uploaded as e.g. myapp-linux-1.4.0.zip
+#     rename: "{{basename}}-{{version}}{{ext}}"   # also {{tag}}
+#   - archive:
+#       name: source-{{version}}.tar.gz
+#       paths: ["src/**", "LICENSE"]
+#       exclude: ["*.tmp", "fixtures/"]   # .gitignore-style
+#   - url: https://internal.example.com/build/{{version}}/app.zip
+#     token_env: BUILD_TOKEN              # sent as Authorization: Bearer
+
+# Fail the release (before committing or tagging) when an artifacts glob
+# matches no files, instead of releasing without those assets.
+artifacts_required: false
+
+# SBOM / provenance files generated after build_command and uploaded with the
+# artifacts as <name>-<version>.<format> (each generator's stdout is the file).
+# required: fail the release when a generator fails (default: warn and skip).
+# embed_digests: list SHA-256 digests of all uploaded files in the release body.
+attestations:
+  enabled: false
+  required: false
+  embed_digests: false
+  # name: myapp   # default: package name, else the current directory name
+  generators: []
+  #   - format: spdx.json
+  #     run: syft . -o spdx-json
+
+# Create floating major version tags (e.g. "v3" pointing to latest v3.x.x).
+floating_tags: false
+
+# Shell command to run after version files are bumped (e.g. "cargo build --release").
+build_command:
+
+# Additional files/globs to stage after build_command runs (e.g. Cargo.lock).
+stage_files: []
+
+# Pre-release identifier (e.g. "alpha", "beta", "rc").
+# When set, versions are formatted as X.Y.Z-<id>.N where N auto-increments.
+prerelease:
+
+# Shell command to run before the release starts (validation, checks).
+pre_release_command:
+
+# Shell command to run after the release completes (notifications, deployments).
+post_release_command:
+
+# Any of the commands above (and hook entries below) also accept a structured
+# form with per-command options:
+#   post_release_command:
+#     run: ./scripts/notify.sh
+#     timeout: 300              # seconds; the whole process group is killed
+#     continue_on_error: true   # warn instead of aborting
+#     cwd: scripts
+#     shell: bash               # default: sh (cmd on Windows without sh)
+
+# Publish steps, run after the tag is pushed and the remote release is created.
+# Types: cargo (workspace members in dependency order), npm, command.
+# CARGO_REGISTRY_TOKEN / NODE_AUTH_TOKEN are passed through from the environment.
+# A failing step is reported but does not roll back the tag.
+publish: []
+#   - type: cargo
+#     args: ["--locked"]
+#   - type: npm
+#     tag: latest
+#   - type: command
+#     run: ./scripts/deploy.sh
+
+# Webhook and chat notifications sent after a release (https:// URLs only).
+# type: webhook (default), slack, or discord. Prefer url_env for secret URLs.
+# Templates accept {{version}}, {{tag}}, {{date}}, {{release_url}}, and {{changelog}};
+# failure_template also gets {{error}} and enables failure notifications.
+# Delivery errors are warnings and never fail the release.
+notifications: []
+#   - type: slack
+#     url_env: SLACK_WEBHOOK_URL
+#     body_template: "Released {{tag}}: {{release_url}}"
+#     failure_template: "Release {{tag}} failed: {{error}}"
+#   - url: https://example.com/hooks/release
+#     method: POST
+#     body_template: '{{"version": "{{version}}", "tag": "{{tag}}"}}'
+
+# JSON audit record of each release: plan, released commit, changelog, bumped
+# files, uploaded assets with SHA-256 digests, release URLs, step timestamps.
+# file: where to write it (e.g. release-summary.json); upload: attach it to the release.
+summary:
+  file:
+  upload: false
+
+# Shorthand for signing.tags.
+sign_tags: false
+
+# GPG/SSH signing for release tags and commits.
+# key: GPG key id, or SSH key path / public key (uses gpg.format=ssh).
+# Default key: git's user.signingkey.
+signing:
+  tags: false
+  commits: false
+
+# How release tags are created. annotated: false makes lightweight tags (the
+# tag SHA equals the commit SHA); they carry no message and cannot be signed.
+tags:
+  annotated: true
+
+# Committer identity for the release commit and tags (passed via git -c).
+# Default: git's user.name/user.email or GIT_AUTHOR_*/GIT_COMMITTER_* env vars.
+# Example: user_name: sr-bot, user_email: sr-bot@users.noreply.github.com
+git:
+  user_name:
+  user_email:
+  # Seconds a git command (push, fetch, ...) may run before it is killed; 0 disables.
+  timeout_seconds: 300
+  # Notes ref to annotate each tagged commit in with the version, date, and
+  # release URL, pushed with the tag (releases = refs/notes/releases). Default: none.
+  release_notes_ref:
+
+# Create GitHub releases as drafts (requires manual publishing).
+draft: false
+
+# Refuse to release outside CI (CI, GITHUB_ACTIONS, GITLAB_CI, ... unset);
+# --allow-local overrides. Dry runs are always allowed.
+require_ci: false
+
+# Remote release settings.
+# name_template: release name. Placeholders: {{version}}, {{tag}}, {{date}}, and
+#                {{display_name}}: the tag without the path of a prefix like
+#                pkg/foo-v (pkg/foo-v1.2.3 shows as foo-v1.2.3).
+# prerelease:    true, false, or auto (prerelease when the version has a
+#                prerelease component, e.g. 1.2.0-rc.1).
+release:
+  name_template: "{{display_name}}"
+  prerelease: auto
+  # Section headings in the release body; empty = all. Independent of changelog.sections.
+  sections: []
+  compare_link: true
+  # Curated notes used as the release body when the file exists, e.g.
+  # docs/releases/{{version}}.md; {{generated}} in it is replaced by the
+  # generated notes. notes_file_required fails the release when it is missing.
+  notes_file_template:
+  notes_file_required: false
+
+# Deprecated: use release.name_template. Minijinja template for the release
+# name (variables: version, tag_name, display_name, tag_prefix); overrides
+# release.name_template.
+release_name_template:
+
+# Message for the release commit. Placeholders: {{version}}, {{tag}}, {{date}}.
+# Use "[ci skip]" for GitLab, or drop the marker entirely.
+release_commit_message: "chore(release): {{tag}} [skip ci]"
+
+# Git trailers added to the release commit (values accept {{version}}, {{tag}},
+# {{date}}), e.g. {{ "Skip-Checks": "true" }}.
+release_commit_trailers: {{}}
+
+# Advisory lock so concurrent `sr release` runs cannot publish the same version.
+# Pushes refs/sr-lock/release to origin for the duration of the release; a concurrent
+# run fails fast. A lock older than stale_after seconds (crashed job) is taken over.
+lock:
+  enabled: false
+  stale_after: 1800
+
+# Release train: defer releases until these thresholds are met
+# (sr release --override-policy releases anyway).
+# min_commits:  releasable commits needed since the last tag (0 disables).
+# min_interval: time since the last tag, e.g. 30m, 24h, 7d (unset disables).
+policy:
+  min_commits: 0
+  # min_interval: 24h
+
+# Git hooks configuration.
+# Each key is a git hook name. Values can be commands (strings, or the structured
+# run/timeout/continue_on_error/cwd/shell form) or structured steps.
+# Steps with patterns only run when staged files match the globs.
+# Rules containing {{files}} receive the matched file list.
+# Hook scripts are generated in .githooks/ by "sr init".
+hooks:
+  commit-msg:
+    - sr hook commit-msg
+  # pre-commit:
+  #   - step: format
+  #     patterns:
+  #       - "*.rs"
+  #     rules:
+  #       - "rustfmt --check --edition 2024 {{files}}"
+  #   - step: lint
+  #     patterns:
+  #       - "*.rs"
+  #     rules:
+  #       - "cargo clippy --workspace -- -D warnings"
+  # pre-push:
+  #   - run: cargo test --workspace
+  #     timeout: 600
+
+# Remote release provider: "github" (default) or "none".
+# "none" makes tag-only releases: tags, release commit, and changelog are
+# pushed, but no remote release is created and no API calls are made.
+provider: github
+
+# Remote release targets (uncomment to publish to several hosts at once).
+# The tag is pushed once to origin; a release is created on every provider.
+# Default: a single GitHub provider derived from the origin remote.
+# providers:
+#   - hostname: github.com
+#   - hostname: ghes.internal.example.com
+#     owner: mirrors                # default: owner of the origin remote
+#     repo: sr                      # default: repo of the origin remote
+#     token_env: GHES_TOKEN         # default: GH_TOKEN, then GITHUB_TOKEN
+
+# Repository (owner/repo) and hostname overrides.
+# Default: parsed from the origin remote, falling back to GITHUB_REPOSITORY
+# and GITHUB_SERVER_URL (then github.com) when there is no usable remote.
+repository:
+hostname:
+
+# GitHub API endpoints for the provider on the origin host, for proxies and
+# GHES setups that serve the API elsewhere.
+# api_url default: GITHUB_API_URL when set for the host (GitHub Actions sets
+# it), else https://api.github.com or https://<host>/api/v3.
+# upload_url default: the upload_url of each release (uploads.github.com, or
+# /api/uploads on GHES); when set, replaces everything before /repos/ in it.
+github:
+  api_url:
+  upload_url:
+
+# Only commits touching these paths (git pathspecs) count toward a release.
+# Empty means all paths.
+include_paths: []
+
+# Commits touching only these paths never trigger a release or appear in the
+# changelog (e.g. docs, .github).
+exclude_paths: []
+
+# Most commits read per release range (newest first); older ones are skipped
+# with a warning. 0 means unlimited.
+max_commits: 5000
+
+# Monorepo packages (uncomment and configure if needed).
+# Each package is released independently with its own version, tags, and changelog.
+# packages:
+#   - name: core
+#     path: crates/core
+#     tag_prefix: "core/v"          # default: "<name>/v"
+#     version_files:
+#       - crates/core/Cargo.toml
+#     changelog_file: crates/core/CHANGELOG.md   # default: "<path>/CHANGELOG.md"
+#     build_command: cargo build -p core
+#     stage_files:
+#       - crates/core/Cargo.lock
+"#
+    )
+}
+
+/// Fetches the body of a remote `extends` URL.
+pub type ConfigFetcher<'a> = dyn Fn(&str) -> Result<String, ReleaseError> + 'a;
+
+/// Where a config layer lives.
+enum ConfigLocation {
+    File(PathBuf),
+    Url(String),
+}
+
+impl std::fmt::Display for ConfigLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Url(url) => f.write_str(url),
+        }
+    }
+}
+
+impl ConfigLocation {
+    /// Identity used for cycle detection.
+    fn key(&self) -> String {
+        match self {
+            Self::File(path) => path
+                .canonicalize()
+                .unwrap_or_else(|_| path.clone())
+                .display()
+                .to_string(),
+            Self::Url(url) => url.clone(),
+        }
+    }
+
+    /// Resolve an `extends` target relative to this layer.
+    fn join(&self, target: &str) -> Result<Self, ReleaseError> {
+        if target.starts_with("https://") {
+            return Ok(Self::Url(target.to_string()));
+        }
+        if target.contains("://") {
+            return Err(ReleaseError::Config(format!(
+                "extends: only https:// URLs are supported: {target}"
+            )));
+        }
+        match self {
+            Self::File(path) => {
+                let dir = path.parent().unwrap_or(Path::new("."));
+                Ok(Self::File(dir.join(target)))
+            }
+            Self::Url(url) => {
+                if Path::new(target).is_absolute() {
+                    return Err(ReleaseError::Config(format!(
+                        "extends: {target} cannot be resolved from remote config {url}"
+                    )));
+                }
+                let base = url.rsplit_once('/').map_or(url.as_str(), |(dir, _)| dir);
+                Ok(Self::Url(format!("{base}/{target}")))
+            }
+        }
+    }
+
+    /// Read the layer as a YAML value plus its source text. `None` when a
+    /// manifest has no `sr` section.
+    fn read(
+        &self,
+        fetch: Option<&ConfigFetcher<'_>>,
+    ) -> Result<Option<(serde_yaml_ng::Value, String)>, ReleaseError> {
+        let contents = match self {
+            Self::File(path) if is_manifest(path) => match manifest_config(path)? {
+                Some((section, _)) => serde_yaml_ng::to_string(&section)
+                    .map_err(|e| ReleaseError::Config(e.to_string()))?,
+                None => return Ok(None),
+            },
+            Self::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| ReleaseError::Config(format!("{}: {e}", path.display())))?,
+            Self::Url(url) => {
+                let fetch = fetch.ok_or_else(|| {
+                    ReleaseError::Config(format!("extends: cannot fetch {url} in offline mode"))
+                })?;
+                fetch(url)?
+            }
+        };
+        let value = serde_yaml_ng::from_str(&contents)
+            .map_err(|e| ReleaseError::Config(format!("{self}: {e}")))?;
+        Ok(Some((value, contents)))
+    }
+}
+
+/// Merge `value` (read from `location`) over its `extends` chain. Unknown keys
+/// are collected per layer; `chain` holds the layers currently being resolved.
+fn resolve_extends(
+    location: &ConfigLocation,
+    value: serde_yaml_ng::Value,
+    fetch: Option<&ConfigFetcher<'_>>,
+    chain: &mut Vec<String>,
+    problems: &mut Vec<String>,
+) -> Result<serde_yaml_ng::Value, ReleaseError> {
+    let mut layer_problems = Vec::new();
+    unknown_keys(&value, "", "", &mut layer_problems);
+    if chain.is_empty() {
+        problems.extend(layer_problems);
+    } else {
+        problems.extend(
+            layer_problems
+                .into_iter()
+                .map(|p| format!("{location}: {p}")),
+        );
+    }
+
+    chain.push(location.key());
+    let merged = match value.get("extends") {
+        None => value,
+        Some(serde_yaml_ng::Value::String(target)) => {
+            let base_location = location.join(target)?;
+            if chain.contains(&base_location.key()) {
+                return Err(ReleaseError::Config(format!(
+                    "extends cycle: {} -> {}",
+                    chain.join(" -> "),
+                    base_location.key()
+                )));
+            }
+            let (base, _) = base_location.read(fetch)?.ok_or_else(|| {
+                ReleaseError::Config(format!("extends: {base_location} has no sr section"))
+            })?;
+            let base = resolve_extends(&base_location, base, fetch, chain, problems)?;
+            merge_layer(base, value)
+        }
+        Some(_) => {
+            problems.push(format!("{location}: extends must be a path or URL string"));
+            value
+        }
+    };
+    chain.pop();
+    Ok(merged)
+}
+
+/// Deep-merge one config layer over its base. Mappings merge key by key; lists
+/// and scalars replace, except `types` under `types_merge: extend`.
+fn merge_layer(base: serde_yaml_ng::Value, layer: serde_yaml_ng::Value) -> serde_yaml_ng::Value {
+    use serde_yaml_ng::Value;
+    let extend_types = layer
+        .get("types_merge")
+        .or_else(|| base.get("types_merge"))
+        .and_then(Value::as_str)
+        == Some("extend");
+    let Value::Mapping(layer_map) = layer else {
+        return base;
+    };
+    let mut base_map = match base {
+        Value::Mapping(map) => map,
+        _ => serde_yaml_ng::Mapping::new(),
+    };
+    for (key, value) in layer_map {
+        if key.as_str() == Some("extends") {
+            continue;
+        }
+        match base_map.get_mut(&key) {
+            Some(Value::Sequence(base_types)) if extend_types && key.as_str() == Some("types") => {
+                if let Value::Sequence(types) = value {
+                    merge_named_entries(base_types, types);
+                }
+            }
+            Some(existing) => override_value(existing, value),
+            None => {
+                base_map.insert(key, value);
+            }
+        }
+    }
+    Value::Mapping(base_map)
+}
+
+/// Merge user-defined `types` entries onto [`default_commit_types`] by name.
+/// Only the fields an entry sets override the matching default.
+fn merge_with_default_types(
+    types: Vec<serde_yaml_ng::Value>,
+) -> Result<Vec<CommitType>, serde_yaml_ng::Error> {
+    let mut merged = match serde_yaml_ng::to_value(default_commit_types())? {
+        serde_yaml_ng::Value::Sequence(defaults) => defaults,
+        _ => Vec::new(),
+    };
+    merge_named_entries(&mut merged, types);
+    serde_yaml_ng::from_value(serde_yaml_ng::Value::Sequence(merged))
+}
+
+/// Merge list entries onto `base` by their `name` field; unmatched entries are appended.
+fn merge_named_entries(base: &mut Vec<serde_yaml_ng::Value>, entries: Vec<serde_yaml_ng::Value>) {
+    for entry in entries {
+        let existing = entry
+            .get("name")
+            .and_then(|name| base.iter_mut().find(|b| b.get("name") == Some(name)));
+        match existing {
+            Some(existing) => override_value(existing, entry),
+            None => base.push(entry),
+        }
+    }
+}
+
+/// Recursively overlay `value` onto `base`: mappings merge, everything else replaces.
+fn override_value(base: &mut serde_yaml_ng::Value, value: serde_yaml_ng::Value) {
+    use serde_yaml_ng::Value;
+    match (base, value) {
+        (Value::Mapping(base_map), Value::Mapping(map)) => {
+            for (key, value) in map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => override_value(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, value) => *base = value,
+    }
+}
+
+/// Whether `path` names a manifest that can embed config.
+pub fn is_manifest(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| MANIFEST_CONFIG_FILES.contains(&name))
+}
+
+/// Read the `sr` section embedded in a manifest, returning it with the key it
+/// was found under (e.g. `package.metadata.sr`). `None` when the manifest has
+/// no such section or `path` is not a manifest.
+pub fn manifest_config(
+    path: &Path,
+) -> Result<Option<(serde_json::Value, &'static str)>, ReleaseError> {
+    let name = path.file_name().and_then(|name| name.to_str());
+    if !matches!(name, Some("Cargo.toml" | "package.json")) || !path.is_file() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| ReleaseError::Config(format!("{}: {e}", path.display())))?;
+
+    if name == Some("package.json") {
+        let doc: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|e| ReleaseError::Config(format!("{}: {e}", path.display())))?;
+        return Ok(doc.get("sr").cloned().map(|section| (section, "sr")));
+    }
+
+    let doc: toml_edit::DocumentMut = contents
+        .parse()
+        .map_err(|e| ReleaseError::Config(format!("{}: {e}", path.display())))?;
+    for (table, key) in [
+        ("package", "package.metadata.sr"),
+        ("workspace", "workspace.metadata.sr"),
+    ] {
+        if let Some(section) = doc
+            .get(table)
+            .and_then(|item| item.get("metadata"))
+            .and_then(|item| item.get("sr"))
+        {
+            return Ok(Some((toml_item_to_json(section), key)));
+        }
+    }
+    Ok(None)
+}
+
+fn toml_item_to_json(item: &toml_edit::Item) -> serde_json::Value {
+    use toml_edit::Item;
+    match item {
+        Item::None => serde_json::Value::Null,
+        Item::Value(value) => toml_value_to_json(value),
+        Item::Table(table) => table
+            .iter()
+            .map(|(k, v)| (k.to_string(), toml_item_to_json(v)))
+            .collect(),
+        Item::ArrayOfTables(tables) => tables
+            .iter()
+            .map(|table| {
+                serde_json::Value::Object(
+                    table
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), toml_item_to_json(v)))
+                        .collect(),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn toml_value_to_json(value: &toml_edit::Value) -> serde_json::Value {
+    use toml_edit::Value;
+    match value {
+        Value::String(s) => s.value().clone().into(),
+        Value::Integer(i) => (*i.value()).into(),
+        Value::Float(f) => (*f.value()).into(),
+        Value::Boolean(b) => (*b.value()).into(),
+        Value::Datetime(d) => d.value().to_string().into(),
+        Value::Array(items) => items.iter().map(toml_value_to_json).collect(),
+        Value::InlineTable(table) => table
+            .iter()
+            .map(|(k, v)| (k.to_string(), toml_value_to_json(v)))
+            .collect(),
+    }
+}
+
+/// JSON Schema describing the config file, derived from [`ReleaseConfig`].
+pub fn config_schema() -> serde_json::Value {
+    let mut schema = schemars::schema_for!(ReleaseConfig);
+    schema.insert("title".into(), "sr configuration".into());
+    schema.to_value()
+}
+
+/// [`config_schema`], built once for key lookups.
+static SCHEMA: LazyLock<serde_json::Value> = LazyLock::new(config_schema);
+
+/// Known keys for the mapping at `schema` (a dotted path, `[]` for list items),
+/// read from the properties in [`config_schema`]. `None` means the mapping is
+/// free-form or not a struct (e.g. `hooks`).
+fn known_keys(schema: &str) -> Option<Vec<&'static str>> {
+    let mut nodes = schema_variants(&SCHEMA);
+    for segment in schema.split('.').filter(|s| !s.is_empty()) {
+        let name = segment.trim_end_matches("[]");
+        nodes = nodes
+            .iter()
+            .filter_map(|node| node.get("properties")?.get(name))
+            .flat_map(schema_variants)
+            .collect();
+        for _ in 0..(segment.len() - name.len()) / 2 {
+            nodes = nodes
+                .iter()
+                .filter_map(|node| node.get("items"))
+                .flat_map(schema_variants)
+                .collect();
+        }
+    }
+    let mut keys: Vec<&'static str> = nodes
+        .iter()
+        .filter_map(|node| node.get("properties")?.as_object())
+        .flat_map(|properties| properties.keys().map(String::as_str))
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    (!keys.is_empty()).then_some(keys)
+}
+
+/// The schemas `node` may stand for: `$ref`s resolved and `anyOf`/`oneOf`
+/// alternatives (options, untagged and tagged enums) expanded.
+fn schema_variants(node: &'static serde_json::Value) -> Vec<&'static serde_json::Value> {
+    if let Some(name) = node
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+    {
+        return SCHEMA["$defs"]
+            .get(name)
+            .map(schema_variants)
+            .unwrap_or_default();
+    }
+    match node.get("anyOf").or_else(|| node.get("oneOf")) {
+        Some(serde_json::Value::Array(alternatives)) => {
+            alternatives.iter().flat_map(schema_variants).collect()
+        }
+        _ => vec![node],
+    }
+}
+
+/// Collect keys that no config struct accepts, with a "did you mean" hint.
+fn unknown_keys(
+    value: &serde_yaml_ng::Value,
+    schema: &str,
+    path: &str,
+    problems: &mut Vec<String>,
+) {
+    use serde_yaml_ng::Value;
+    match value {
+        Value::Mapping(map) => {
+            let Some(known) = known_keys(schema) else {
+                return;
+            };
+            for (key, child) in map {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let full = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                if !known.contains(&key) {
+                    let hint = closest_key(key, &known)
+                        .map(|k| format!(" (did you mean {k}?)"))
+                        .unwrap_or_default();
+                    problems.push(format!("unknown key `{full}`{hint}"));
+                    continue;
+                }
+                let child_schema = if schema.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{schema}.{key}")
+                };
+                unknown_keys(child, &child_schema, &full, problems);
+            }
+        }
+        Value::Sequence(items) => {
+            for (i, item) in items.iter().enumerate() {
+                unknown_keys(
+                    item,
+                    &format!("{schema}[]"),
+                    &format!("{path}[{i}]"),
+                    problems,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The known key closest to `key` by edit distance, if it is close enough to be a typo.
+fn closest_key(key: &str, known: &[&'static str]) -> Option<&'static str> {
+    let threshold = (key.len() / 3).max(2);
+    known
+        .iter()
+        .map(|k| (edit_distance(key, k), *k))
+        .filter(|(d, _)| *d <= threshold)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur.push((prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// Fold a list of problems into a single config error.
+fn config_problems(problems: Vec<String>) -> ReleaseError {
+    if problems.len() == 1 {
+        return ReleaseError::Config(problems.into_iter().next().unwrap_or_default());
+    }
+    ReleaseError::Config(format!(
+        "{} problems found:\n{}",
+        problems.len(),
+        problems
+            .iter()
+            .map(|p| format!("  - {p}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))
+}
+
+/// Merge new default fields into an existing config YAML string.
+///
+/// Adds any top-level or nested mapping keys present in the defaults but missing
+/// from the existing config. Arrays are never merged (user's array = user's intent).
+/// Returns the merged YAML with a header comment.
+pub fn merge_config_yaml(existing_yaml: &str) -> Result<String, ReleaseError> {
+    let mut existing: serde_yaml_ng::Value = serde_yaml_ng::from_str(existing_yaml)
+        .map_err(|e| ReleaseError::Config(format!("failed to parse existing config: {e}")))?;
+
+    let default_config = ReleaseConfig::default();
+    let default_yaml = serde_yaml_ng::to_string(&default_config)
+        .map_err(|e| ReleaseError::Config(e.to_string()))?;
+    let defaults: serde_yaml_ng::Value =
+        serde_yaml_ng::from_str(&default_yaml).map_err(|e| ReleaseError::Config(e.to_string()))?;
+
+    deep_merge_value(&mut existing, &defaults);
+
+    let merged =
+        serde_yaml_ng::to_string(&existing).map_err(|e| ReleaseError::Config(e.to_string()))?;
+
+    Ok(format!(
+        "# yaml-language-server: $schema={CONFIG_SCHEMA_URL}\n\
+         # sr configuration — merged with new defaults\n\
+         # Run 'sr init --force' for a fully-commented template.\n\n\
+         {merged}"
+    ))
+}
+
+/// Recursively insert missing keys from `defaults` into `base`.
+/// Only mapping keys are merged; arrays and scalars are left untouched.
+fn deep_merge_value(base: &mut serde_yaml_ng::Value, defaults: &serde_yaml_ng::Value) {
+    use serde_yaml_ng::Value;
+    if let (Value::Mapping(base_map), Value::Mapping(default_map)) = (base, defaults) {
+        for (key, default_val) in default_map {
+            match base_map.get_mut(key) {
+                Some(existing_val) => {
+                    // Recurse into nested mappings only
+                    if matches!(default_val, Value::Mapping(_)) {
+                        deep_merge_value(existing_val, default_val);
+                    }
+                }
+                None => {
+                    base_map.insert(key.clone(), default_val.clone());
+                }
+            }
+        }
+    }
+}
+
+impl JsonSchema for BumpLevel {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "BumpLevel".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Semantic version component to bump.",
+            "type": "string",
+            "enum": ["major", "minor", "patch"],
+        })
+    }
+}
+
+// Custom deserialization for BumpLevel so it can appear in YAML config.
+impl<'de> Deserialize<'de> for BumpLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "major" => Ok(BumpLevel::Major),
+            "minor" => Ok(BumpLevel::Minor),
+            "patch" => Ok(BumpLevel::Patch),
+            _ => Err(serde::de::Error::custom(format!("unknown bump level: {s}"))),
+        }
+    }
+}
+
+impl Serialize for BumpLevel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let s = match self {
+            BumpLevel::Major => "major",
+            BumpLevel::Minor => "minor",
+            BumpLevel::Patch => "patch",
+        };
+        serializer.serialize_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn default_values() {
+        let config = ReleaseConfig::default();
+        assert_eq!(config.branches, vec!["main", "master"]);
+        assert_eq!(config.tag_prefix, "v");
+        assert_eq!(config.commit_pattern, DEFAULT_COMMIT_PATTERN);
+        assert_eq!(config.breaking_section, "Breaking Changes");
+        assert_eq!(config.misc_section, "Miscellaneous");
+        assert!(!config.types.is_empty());
+        assert!(!config.version_files_strict);
+        assert!(config.artifacts.is_empty());
+        assert!(!config.floating_tags);
+    }
+
+    #[test]
+    fn load_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nonexistent.yml");
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.tag_prefix, "v");
+    }
+
+    #[test]
+    fn discover_config_walks_up_to_git_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        let nested = root.join("packages/app");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::write(root.join(".sr.yml"), "tag_prefix: v\n").unwrap();
+
+        let (path, is_legacy) = ReleaseConfig::discover_config(&nested).unwrap();
+        assert!(!is_legacy);
+        assert_eq!(
+            path.canonicalize().unwrap(),
+            root.join(".sr.yml").canonicalize().unwrap()
+        );
+
+        // A closer config wins over one further up.
+        std::fs::write(nested.join("sr.config.yml"), "tag_prefix: app-v\n").unwrap();
+        let (path, _) = ReleaseConfig::discover_config(&nested).unwrap();
+        assert_eq!(path, nested.join("sr.config.yml"));
+    }
+
+    #[test]
+    fn discover_config_stops_at_git_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        // Above the git root: must not be picked up.
+        std::fs::write(dir.path().join("sr.yaml"), "tag_prefix: v\n").unwrap();
+
+        assert!(ReleaseConfig::discover_config(&root).is_none());
+    }
+
+    #[test]
+    fn load_from_cargo_toml_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &path,
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n\
+             [package.metadata.sr]\ntag_prefix = \"demo-v\"\nfloating_tags = true\n\
+             version_files = [\"Cargo.toml\"]\ntypes_mode = \"replace\"\n\n\
+             [package.metadata.sr.lock]\nenabled = true\n\n\
+             [[package.metadata.sr.types]]\nname = \"feat\"\nbump = \"minor\"\n",
+        )
+        .unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.tag_prefix, "demo-v");
+        assert!(config.floating_tags);
+        assert!(config.lock.enabled);
+        assert_eq!(config.version_files, vec!["Cargo.toml"]);
+        assert_eq!(config.types.len(), 1);
+        assert_eq!(config.types[0].bump, Some(BumpLevel::Minor));
+
+        let (_, key) = manifest_config(&path).unwrap().unwrap();
+        assert_eq!(key, "package.metadata.sr");
+    }
+
+    #[test]
+    fn load_from_workspace_metadata_and_package_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &cargo,
+            "[workspace]\nmembers = []\n\n[workspace.metadata.sr]\ntag_prefix = \"ws-\"\n",
+        )
+        .unwrap();
+        assert_eq!(ReleaseConfig::load(&cargo).unwrap().tag_prefix, "ws-");
+        assert_eq!(
+            manifest_config(&cargo).unwrap().unwrap().1,
+            "workspace.metadata.sr"
+        );
+
+        let pkg = dir.path().join("package.json");
+        std::fs::write(
+            &pkg,
+            r#"{"name": "demo", "version": "1.0.0", "sr": {"tag_prefix": "js-v", "branches": ["trunk"]}}"#,
+        )
+        .unwrap();
+        let config = ReleaseConfig::load(&pkg).unwrap();
+        assert_eq!(config.tag_prefix, "js-v");
+        assert_eq!(config.branches, vec!["trunk"]);
+    }
+
+    #[test]
+    fn manifest_config_is_strictly_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("package.json");
+        std::fs::write(&pkg, r#"{"sr": {"tag_prfix": "v"}}"#).unwrap();
+        let err = ReleaseConfig::load(&pkg).unwrap_err().to_string();
+        assert!(err.contains("did you mean tag_prefix?"), "{err}");
+    }
+
+    #[test]
+    fn find_config_prefers_yaml_over_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        // A manifest without an sr section is not a config source.
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        std::fs::write(dir.path().join("package.json"), r#"{"name": "x"}"#).unwrap();
+        assert!(ReleaseConfig::find_config(dir.path()).is_none());
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "x", "sr": {"tag_prefix": "v"}}"#,
+        )
+        .unwrap();
+        let (path, _) = ReleaseConfig::find_config(dir.path()).unwrap();
+        assert_eq!(path, dir.path().join("package.json"));
+
+        std::fs::write(dir.path().join(LEGACY_CONFIG_FILE), "tag_prefix: v\n").unwrap();
+        let (path, is_legacy) = ReleaseConfig::find_config(dir.path()).unwrap();
+        assert_eq!(path, dir.path().join(LEGACY_CONFIG_FILE));
+        assert!(is_legacy);
+    }
+
+    #[test]
+    fn types_extend_defaults_by_name() {
+        let config =
+            ReleaseConfig::parse("types:\n  - name: fix\n    section: Fixes\n  - name: deps\n    bump: patch\n    section: Dependencies\n")
+                .unwrap();
+        let defaults = default_commit_types();
+        assert_eq!(config.types.len(), defaults.len() + 1);
+
+        // Overriding only the section keeps the default bump.
+        let fix = config.types.iter().find(|t| t.name == "fix").unwrap();
+        assert_eq!(fix.section.as_deref(), Some("Fixes"));
+        assert_eq!(fix.bump, Some(BumpLevel::Patch));
+
+        // Untouched defaults still trigger releases.
+        let feat = config.types.iter().find(|t| t.name == "feat").unwrap();
+        assert_eq!(feat.bump, Some(BumpLevel::Minor));
+
+        let deps = config.types.last().unwrap();
+        assert_eq!(deps.name, "deps");
+        assert_eq!(deps.bump, Some(BumpLevel::Patch));
+    }
+
+    #[test]
+    fn types_extend_can_clear_a_default_bump() {
+        let config = ReleaseConfig::parse("types:\n  - name: perf\n    bump: null\n").unwrap();
+        let perf = config.types.iter().find(|t| t.name == "perf").unwrap();
+        assert_eq!(perf.bump, None);
+        assert_eq!(perf.section.as_deref(), Some("Performance"));
+    }
+
+    #[test]
+    fn types_replace_uses_list_as_is() {
+        let config =
+            ReleaseConfig::parse("types_mode: replace\ntypes:\n  - name: deps\n    bump: patch\n")
+                .unwrap();
+        assert_eq!(config.types.len(), 1);
+        assert_eq!(config.types[0].name, "deps");
+
+        // Without a types list the defaults apply in either mode.
+        let config = ReleaseConfig::parse("types_mode: replace\n").unwrap();
+        assert_eq!(config.types, default_commit_types());
+    }
+
+    #[test]
+    fn hook_commands_accept_string_or_structured_form() {
+        let yaml = "pre_release_command: ./check.sh\n\
+                    post_release_command:\n  run: ./notify.sh\n  timeout: 300\n  continue_on_error: true\n  cwd: scripts\n  shell: bash\n\
+                    hooks:\n  pre-push:\n    - run: cargo test\n      timeout: 600\n    - echo pushed\n";
+        let config = ReleaseConfig::parse(yaml).unwrap();
+        assert_eq!(config.pre_release_command, Some("./check.sh".into()));
+        assert_eq!(
+            config.post_release_command,
+            Some(HookCommand {
+                run: "./notify.sh".into(),
+                timeout: Some(300),
+                continue_on_error: true,
+                cwd: Some("scripts".into()),
+                shell: Some("bash".into()),
+            })
+        );
+        let pre_push = &config.hooks.hooks["pre-push"];
+        assert!(matches!(&pre_push[0], HookEntry::Command(c) if c.timeout == Some(600)));
+        assert!(matches!(&pre_push[1], HookEntry::Command(c) if c.run == "echo pushed"));
+
+        // Commands without options serialize back to plain strings.
+        let out = serde_yaml_ng::to_string(&config).unwrap();
+        assert!(out.contains("pre_release_command: ./check.sh"), "{out}");
+        assert!(out.contains("timeout: 300"), "{out}");
+    }
+
+    #[test]
+    fn hook_command_unknown_option_is_reported() {
+        let err = ReleaseConfig::parse("build_command:\n  run: make\n  timout: 5\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unknown key `build_command.timout` (did you mean timeout?)"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn notifications_parse_presets() {
+        let yaml = "notifications:\n  - type: slack\n    url_env: SLACK_WEBHOOK_URL\n    failure_template: \"{tag} failed: {error}\"\n  - url: https://example.com/hook\n    method: PUT\n";
+        let config = ReleaseConfig::parse(yaml).unwrap();
+        assert_eq!(config.notifications.len(), 2);
+        assert_eq!(config.notifications[0].kind, NotificationKind::Slack);
+        assert_eq!(config.notifications[0].method, "POST");
+        assert_eq!(config.notifications[1].kind, NotificationKind::Webhook);
+        assert_eq!(config.notifications[1].method, "PUT");
+    }
+
+    #[test]
+    fn notifications_are_validated() {
+        let yaml = "notifications:\n  - type: discord\n  - url: http://example.com\n    method: GET\n    body_template: \"{error}\"\n";
+        let err = ReleaseConfig::parse(yaml).unwrap_err().to_string();
+        assert!(
+            err.contains("notifications[0]: one of url or url_env is required"),
+            "{err}"
+        );
+        assert!(
+            err.contains("notifications[1].url must start with https://"),
+            "{err}"
+        );
+        assert!(
+            err.contains("notifications[1].method must be POST, PUT, or PATCH"),
+            "{err}"
+        );
+        assert!(
+            err.contains("notifications[1].body_template: unknown placeholder {error}"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn extends_deep_merges_local_over_base() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("shared")).unwrap();
+        std::fs::write(
+            dir.path().join("shared/base.yaml"),
+            "tag_prefix: base-\nfloating_tags: true\nbranches: [main, release]\n\
+             lock:\n  enabled: true\n  stale_after: 60\n\
+             types:\n  - name: feat\n    bump: minor\n    section: Features\n",
+        )
+        .unwrap();
+        let path = dir.path().join("sr.yaml");
+        std::fs::write(
+            &path,
+            "extends: shared/base.yaml\nbranches: [trunk]\nlock:\n  stale_after: 120\n\
+             types_mode: replace\ntypes:\n  - name: fix\n    bump: patch\n",
+        )
+        .unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.tag_prefix, "base-");
+        assert!(config.floating_tags);
+        // Lists replace, mappings merge.
+        assert_eq!(config.branches, vec!["trunk"]);
+        assert!(config.lock.enabled);
+        assert_eq!(config.lock.stale_after, 120);
+        assert_eq!(config.types.len(), 1);
+        assert_eq!(config.types[0].name, "fix");
+        // The resolved config no longer points at its base.
+        assert!(config.extends.is_none());
+    }
+
+    #[test]
+    fn extends_types_merge_extend_merges_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("base.yaml"),
+            "types:\n  - name: feat\n    bump: minor\n    section: Features\n  - name: fix\n    bump: patch\n",
+        )
+        .unwrap();
+        let path = dir.path().join("sr.yaml");
+        std::fs::write(
+            &path,
+            "extends: base.yaml\ntypes_merge: extend\ntypes_mode: replace\n\
+             types:\n  - name: feat\n    section: New Stuff\n  - name: deps\n    bump: patch\n",
+        )
+        .unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        let names: Vec<&str> = config.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["feat", "fix", "deps"]);
+        assert_eq!(config.types[0].bump, Some(BumpLevel::Minor));
+        assert_eq!(config.types[0].section.as_deref(), Some("New Stuff"));
+    }
+
+    #[test]
+    fn extends_chains_and_detects_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.yaml"),
+            "extends: b.yaml\ntag_prefix: a-\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("b.yaml"), "extends: c.yaml\ndraft: true\n").unwrap();
+        std::fs::write(dir.path().join("c.yaml"), "floating_tags: true\n").unwrap();
+        let config = ReleaseConfig::load(&dir.path().join("a.yaml")).unwrap();
+        assert_eq!(config.tag_prefix, "a-");
+        assert!(config.draft);
+        assert!(config.floating_tags);
+
+        std::fs::write(dir.path().join("c.yaml"), "extends: a.yaml\n").unwrap();
+        let err = ReleaseConfig::load(&dir.path().join("a.yaml"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("extends cycle"), "{err}");
+    }
+
+    #[test]
+    fn extends_reports_unknown_keys_per_layer() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("base.yaml"), "flaoting_tags: true\n").unwrap();
+        let path = dir.path().join("sr.yaml");
+        std::fs::write(&path, "extends: base.yaml\ntag_prfix: v\n").unwrap();
+        let err = ReleaseConfig::load(&path).unwrap_err().to_string();
+        assert!(err.contains("2 problems found"), "{err}");
+        assert!(err.contains("unknown key `tag_prfix`"), "{err}");
+        assert!(
+            err.contains("base.yaml: unknown key `flaoting_tags` (did you mean floating_tags?)"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn extends_remote_uses_fetcher_and_fails_offline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sr.yaml");
+        std::fs::write(
+            &path,
+            "extends: https://example.com/presets/sr.yaml\ntag_prefix: local-\n",
+        )
+        .unwrap();
+
+        let err = ReleaseConfig::load(&path).unwrap_err().to_string();
+        assert!(err.contains("offline mode"), "{err}");
+
+        let fetched = std::cell::RefCell::new(Vec::new());
+        let fetch = |url: &str| -> Result<String, ReleaseError> {
+            fetched.borrow_mut().push(url.to_string());
+            Ok(match url {
+                "https://example.com/presets/sr.yaml" => "extends: common.yaml\ndraft: true\n",
+                "https://example.com/presets/common.yaml" => "floating_tags: true\n",
+                other => panic!("unexpected fetch {other}"),
+            }
+            .to_string())
+        };
+        let config = ReleaseConfig::load_with(&path, Some(&fetch)).unwrap();
+        assert_eq!(config.tag_prefix, "local-");
+        assert!(config.draft);
+        assert!(config.floating_tags);
+        assert_eq!(fetched.borrow().len(), 2);
+
+        std::fs::write(&path, "extends: http://example.com/sr.yaml\n").unwrap();
+        let err = ReleaseConfig::load_with(&path, Some(&fetch))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("only https://"), "{err}");
+    }
+
+    #[test]
+    fn resolve_paths_relative_to_config_dir() {
+        let mut config = ReleaseConfig {
+            version_files: vec!["Cargo.toml".into(), "/abs/package.json".into()],
+            changelog: ChangelogConfig {
+                file: Some("CHANGELOG.md".into()),
+                template: None,
+                ..Default::default()
+            },
+            packages: vec![PackageConfig {
+                name: "core".into(),
+                path: "crates/core".into(),
+                version_files: vec!["crates/core/Cargo.toml".into()],
+                changelog: Some(ChangelogConfig {
+                    file: Some("crates/core/CHANGELOG.md".into()),
+                    template: None,
+                    ..Default::default()
+                }),
+                tag_prefix: None,
+                changelog_file: None,
+                build_command: None,
+                stage_files: vec![],
+            }],
+            ..Default::default()
+        };
+        config.resolve_paths_relative_to(Path::new(".."));
+
+        assert_eq!(
+            config.version_files,
+            vec!["../Cargo.toml", "/abs/package.json"]
+        );
+        assert_eq!(config.changelog.file.as_deref(), Some("../CHANGELOG.md"));
+        assert_eq!(
+            config.packages[0].version_files,
+            vec!["../crates/core/Cargo.toml"]
+        );
+        assert_eq!(
+            config.packages[0]
+                .changelog
+                .as_ref()
+                .and_then(|c| c.file.as_deref()),
+            Some("../crates/core/CHANGELOG.md")
+        );
+        // Package paths are git pathspecs and stay untouched.
+        assert_eq!(config.packages[0].path, "crates/core");
+    }
+
+    #[test]
+    fn resolve_paths_relative_to_current_dir_is_noop() {
+        let mut config = ReleaseConfig {
+            version_files: vec!["Cargo.toml".into()],
+            ..Default::default()
+        };
+        config.resolve_paths_relative_to(Path::new(""));
+        config.resolve_paths_relative_to(Path::new("."));
+        assert_eq!(config.version_files, vec!["Cargo.toml"]);
+    }
+
+    #[test]
+    fn load_valid_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        let mut f = std::fs::File::create(&path).unwrap();
+        writeln!(f, "branches:\n  - develop\ntag_prefix: release-").unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.branches, vec!["develop"]);
+        assert_eq!(config.tag_prefix, "release-");
+    }
+
+    #[test]
+    fn load_partial_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(&path, "tag_prefix: rel-\n").unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.tag_prefix, "rel-");
+        assert_eq!(config.branches, vec!["main", "master"]);
+        // defaults should still apply for types/pattern/breaking_section
+        assert_eq!(config.commit_pattern, DEFAULT_COMMIT_PATTERN);
+        assert_eq!(config.breaking_section, "Breaking Changes");
+        assert!(!config.types.is_empty());
+    }
+
+    #[test]
+    fn load_yaml_with_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(
+            &path,
+            "artifacts:\n  - \"dist/*.tar.gz\"\n  - \"build/output-*\"\n",
+        )
+        .unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(
+            config.artifacts,
+            vec!["dist/*.tar.gz".into(), "build/output-*".into()]
+        );
+        // defaults still apply
+        assert_eq!(config.tag_prefix, "v");
+    }
+
+    #[test]
+    fn rules_add_sections_and_are_validated() {
+        let config = ReleaseConfig::parse(
+            "rules:\n  - when: {type: fix, scope: security}\n    section: Security\n    bump: minor\n\
+             changelog:\n  sections: [Security]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.section_names().last_chunk::<2>(),
+            Some(&["Security", "Miscellaneous"])
+        );
+        let commit = ConventionalCommit {
+            sha: "abc".into(),
+            r#type: "fix".into(),
+            scope: Some("security".into()),
+            description: "escape input".into(),
+            body: None,
+            breaking: false,
+        };
+        assert_eq!(config.section_of(&commit), Some("Security"));
+        assert_eq!(
+            config.classifier().unwrap().commit_bump(&commit),
+            Some(BumpLevel::Minor)
+        );
+
+        let err = ReleaseConfig::parse(
+            "rules:\n  - when: {}\n  - when: {scope: a, scope_regex: \"(\"}\n    bump: patch\n  - when: {typ: fix}\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("rules[0].when must set at least one of"),
+            "{err}"
+        );
+        assert!(err.contains("rules[0] must set section or bump"), "{err}");
+        assert!(
+            err.contains("rules[1].when: set scope or scope_regex, not both"),
+            "{err}"
+        );
+        assert!(
+            err.contains("rules[1].when.scope_regex is not a valid regex"),
+            "{err}"
+        );
+        assert!(err.contains("typ"), "{err}");
+    }
+
+    #[test]
+    fn unknown_types_pick_their_section() {
+        let hotfix = ConventionalCommit {
+            sha: "abc".into(),
+            r#type: "hotfix".into(),
+            scope: None,
+            description: "patch prod".into(),
+            body: None,
+            breaking: false,
+        };
+        let config = ReleaseConfig::default();
+        assert_eq!(config.section_of(&hotfix), None);
+        assert_eq!(config.unknown_types_section(), None);
+
+        let config = ReleaseConfig::parse("changelog:\n  unknown_types: misc\n").unwrap();
+        assert_eq!(config.section_of(&hotfix), Some("Miscellaneous"));
+        assert_eq!(config.section_names().last(), Some(&"Miscellaneous"));
+
+        let config = ReleaseConfig::parse(
+            "other_section: Sonstiges\nchangelog:\n  unknown_types: own-section\n  \
+             sections: [Sonstiges]\n",
+        )
+        .unwrap();
+        assert_eq!(config.section_of(&hotfix), Some("Sonstiges"));
+        assert_eq!(config.unknown_types_section(), Some("Sonstiges"));
+        assert_eq!(config.section_names().last(), Some(&"Sonstiges"));
+        let usage = config
+            .type_usage(&[Commit {
+                sha: "abc".into(),
+                author: String::new(),
+                message: "hotfix: patch prod".into(),
+                paths: Vec::new(),
+            }])
+            .unwrap();
+        assert!(
+            usage.warnings()[0].ends_with("are listed under 'Sonstiges'"),
+            "{:?}",
+            usage.warnings()
+        );
+    }
+
+    #[test]
+    fn changelog_transform_regexes_are_validated() {
+        let config = ReleaseConfig::parse(
+            "changelog:\n  transform:\n    capitalize_first: true\n    replacements:\n      \
+             - {pattern: \"^wip: \"}\n",
+        )
+        .unwrap();
+        assert!(config.changelog.transform.capitalize_first);
+        assert_eq!(config.changelog.transform.replacements[0].replace, "");
+
+        let err = ReleaseConfig::parse(
+            "changelog:\n  transform:\n    replacements:\n      - {pattern: \"(\", replace: x}\n\
+             packages:\n  - name: core\n    path: core\n    changelog:\n      transform:\n        \
+             replacements:\n          - {pattern: \"[\"}\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("changelog.transform.replacements[0].pattern is not a valid regex"),
+            "{err}"
+        );
+        assert!(
+            err.contains("packages.core.changelog.transform.replacements[0].pattern"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn release_notes_file_is_rendered_and_validated() {
+        let config = ReleaseConfig::parse(
+            "release:\n  notes_file_template: \"docs/releases/{version}.md\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.release_notes_file("1.4.0", "v1.4.0").as_deref(),
+            Some("docs/releases/1.4.0.md")
+        );
+        assert_eq!(
+            ReleaseConfig::default().release_notes_file("1.4.0", "v1.4.0"),
+            None
+        );
+
+        let err = ReleaseConfig::parse(
+            "release:\n  notes_file_template: \"docs/{date}.md\"\n  notes_file_required: true\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("release.notes_file_template: unknown placeholder {date}"),
+            "{err}"
+        );
+        let err = ReleaseConfig::parse("release:\n  notes_file_required: true\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("set release.notes_file_template"), "{err}");
+    }
+
+    #[test]
+    fn artifact_rename_templates_are_validated() {
+        let config = ReleaseConfig::parse(
+            "artifacts:\n  - path: dist/*.zip\n    rename: \"{basename}-{version}{ext}\"\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            &config.artifacts[0],
+            ArtifactEntry::Pattern { rename: Some(r), .. } if r == "{basename}-{version}{ext}"
+        ));
+
+        let err = ReleaseConfig::parse(
+            "artifacts:\n  - path: dist/*.zip\n    rename: \"out/{name}.zip\"\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("artifacts[0].rename"), "{err}");
+        assert!(err.contains("{name}"), "{err}");
+        assert!(err.contains("must be a file name, not a path"), "{err}");
+    }
+
+    #[test]
+    fn artifact_url_names_must_be_file_names() {
+        for name in [
+            "../escape.zip",
+            "/tmp/app.zip",
+            "sub/app.zip",
+            "..",
+            "dir\\\\app.zip",
+        ] {
+            let err = ReleaseConfig::parse(&format!(
+                "artifacts:\n  - url: https://ci.example.com/app.zip\n    name: \"{name}\"\n"
+            ))
+            .unwrap_err()
+            .to_string();
+            assert!(
+                err.contains("artifacts[0].name must be a file name, not a path"),
+                "{name}: {err}"
+            );
+        }
+        ReleaseConfig::parse(
+            "artifacts:\n  - url: https://ci.example.com/app.zip\n    name: app..{version}.zip\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn load_yaml_with_floating_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(&path, "floating_tags: true\n").unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert!(config.floating_tags);
+        // defaults still apply
+        assert_eq!(config.tag_prefix, "v");
+    }
+
+    #[test]
+    fn bump_level_roundtrip() {
+        for (level, expected) in [
+            (BumpLevel::Major, "major"),
+            (BumpLevel::Minor, "minor"),
+            (BumpLevel::Patch, "patch"),
+        ] {
+            let yaml = serde_yaml_ng::to_string(&level).unwrap();
+            assert!(yaml.contains(expected));
+            let parsed: BumpLevel = serde_yaml_ng::from_str(&yaml).unwrap();
+            assert_eq!(parsed, level);
+        }
+    }
+
+    #[test]
+    fn types_roundtrip() {
+        let config = ReleaseConfig::default();
+        let yaml = serde_yaml_ng::to_string(&config).unwrap();
+        let parsed: ReleaseConfig = serde_yaml_ng::from_str(&yaml).unwrap();
+        assert_eq!(parsed.types.len(), config.types.len());
+        assert_eq!(parsed.types[0].name, "feat");
+        assert_eq!(parsed.commit_pattern, config.commit_pattern);
+        assert_eq!(parsed.breaking_section, config.breaking_section);
+    }
+
+    #[test]
+    fn load_yaml_with_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(
+            &path,
+            r#"
+packages:
+  - name: core
+    path: crates/core
+    version_files:
+      - crates/core/Cargo.toml
+  - name: cli
+    path: crates/cli
+    tag_prefix: "cli-v"
+"#,
+        )
+        .unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.packages.len(), 2);
+        assert_eq!(config.packages[0].name, "core");
+        assert_eq!(config.packages[0].path, "crates/core");
+        assert_eq!(config.packages[1].tag_prefix, Some("cli-v".into()));
+    }
+
+    #[test]
+    fn resolve_package_defaults() {
+        let config = ReleaseConfig {
+            packages: vec![PackageConfig {
+                name: "core".into(),
+                path: "crates/core".into(),
+                tag_prefix: None,
+                version_files: vec![],
+                changelog: None,
+                changelog_file: None,
+                build_command: None,
+                stage_files: vec![],
+            }],
+            ..Default::default()
+        };
+
+        let resolved = config.resolve_package(&config.packages[0]);
+        assert_eq!(resolved.tag_prefix, "core/v");
+        assert_eq!(resolved.path_filter.as_deref(), Some("crates/core"));
+        // Inherits root config values
+        assert_eq!(resolved.branches, config.branches);
+        assert!(resolved.packages.is_empty());
+    }
+
+    #[test]
+    fn resolve_package_overrides() {
+        let config = ReleaseConfig {
+            version_files: vec!["Cargo.toml".into()],
+            packages: vec![PackageConfig {
+                name: "cli".into(),
+                path: "crates/cli".into(),
+                tag_prefix: Some("cli-v".into()),
+                version_files: vec!["crates/cli/Cargo.toml".into()],
+                changelog: Some(ChangelogConfig {
+                    file: Some("crates/cli/CHANGELOG.md".into()),
+                    template: None,
+                    ..Default::default()
+                }),
+                changelog_file: None,
+                build_command: Some("cargo build -p cli".into()),
+                stage_files: vec!["crates/cli/Cargo.lock".into()],
+            }],
+            ..Default::default()
+        };
+
+        let resolved = config.resolve_package(&config.packages[0]);
+        assert_eq!(resolved.tag_prefix, "cli-v");
+        assert_eq!(resolved.version_files, vec!["crates/cli/Cargo.toml"]);
+        assert_eq!(
+            resolved.changelog.file.as_deref(),
+            Some("crates/cli/CHANGELOG.md")
+        );
+        assert_eq!(
+            resolved.build_command.as_ref().map(|c| c.run.as_str()),
+            Some("cargo build -p cli")
+        );
+        assert_eq!(resolved.stage_files, vec!["crates/cli/Cargo.lock"]);
+    }
+
+    #[test]
+    fn resolve_package_gets_its_own_changelog() {
+        let pkg = |name: &str, changelog_file: Option<&str>| PackageConfig {
+            name: name.into(),
+            path: format!("crates/{name}"),
+            tag_prefix: None,
+            version_files: vec![],
+            changelog: None,
+            changelog_file: changelog_file.map(String::from),
+            build_command: None,
+            stage_files: vec![],
+        };
+        let mut config = ReleaseConfig {
+            changelog: ChangelogConfig {
+                file: Some("CHANGELOG.md".into()),
+                ..Default::default()
+            },
+            packages: vec![pkg("api", None), pkg("web", Some("web/NEWS.md"))],
+            ..Default::default()
+        };
+
+        let api = config.resolve_package(&config.packages[0]);
+        assert_eq!(
+            api.changelog.file.as_deref(),
+            Some("crates/api/CHANGELOG.md")
+        );
+        assert_eq!(api.package_name.as_deref(), Some("api"));
+        assert_eq!(api.changelog_files(), vec!["crates/api/CHANGELOG.md"]);
+        let web = config.resolve_package(&config.packages[1]);
+        assert_eq!(web.changelog.file.as_deref(), Some("web/NEWS.md"));
+
+        config.changelog.aggregate = true;
+        let api = config.resolve_package(&config.packages[0]);
+        assert!(!api.changelog.aggregate);
+        assert_eq!(
+            api.changelog_files(),
+            vec!["crates/api/CHANGELOG.md", "CHANGELOG.md"]
+        );
+    }
+
+    #[test]
+    fn changelog_path_defaults_to_the_package_directory() {
+        let config = ReleaseConfig {
+            path_filter: Some("crates/api".into()),
+            ..Default::default()
+        };
+        assert_eq!(config.changelog_path(), "crates/api/CHANGELOG.md");
+        assert_eq!(ReleaseConfig::default().changelog_path(), "CHANGELOG.md");
+    }
+
+    #[test]
+    fn aggregate_is_a_root_only_setting() {
+        let config = ReleaseConfig {
+            changelog: ChangelogConfig {
+                aggregate: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(
+            config
+                .violations()
+                .contains(&"changelog.aggregate requires changelog.file".to_string())
+        );
+    }
+
+    #[test]
+    fn version_file_urls_render_and_must_be_listed() {
+        let config = ReleaseConfig::parse(
+            "version_files: [Formula/app.rb]\nversion_file_urls:\n  Formula/app.rb: https://x/{tag}/app-{version}.tar.gz\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config
+                .version_file_url("Formula/app.rb", "1.2.0", "v1.2.0")
+                .as_deref(),
+            Some("https://x/v1.2.0/app-1.2.0.tar.gz")
+        );
+        assert_eq!(
+            config.version_file_url("Cargo.toml", "1.2.0", "v1.2.0"),
+            None
+        );
+
+        let err =
+            ReleaseConfig::parse("version_file_urls:\n  scoop/app.json: https://x/{date}.zip\n")
+                .unwrap_err()
+                .to_string();
+        assert!(
+            err.contains("version_file_urls.scoop/app.json: unknown placeholder {date}"),
+            "{err}"
+        );
+        assert!(
+            err.contains("version_file_urls.scoop/app.json: not listed in version_files"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn attestation_generators_parse_and_validate_formats() {
+        let err = ReleaseConfig::parse(
+            "attestations:\n  enabled: true\n  generators:\n    - format: spdx.json\n      run: syft . -o spdx-json\n    - format: ../evil\n      run: slsa\n",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("attestations.generators[1].format must be a file extension"),
+            "{err}"
+        );
+
+        let config = ReleaseConfig::parse(
+            "attestations:\n  enabled: true\n  generators:\n    - format: spdx.json\n      run: {run: syft ., timeout: 60}\n",
+        )
+        .unwrap();
+        assert_eq!(config.attestations.generators[0].run.timeout, Some(60));
+    }
+
+    #[test]
+    fn find_package_found() {
+        let config = ReleaseConfig {
+            packages: vec![PackageConfig {
+                name: "core".into(),
+                path: "crates/core".into(),
+                tag_prefix: None,
+                version_files: vec![],
+                changelog: None,
+                changelog_file: None,
+                build_command: None,
+                stage_files: vec![],
+            }],
+            ..Default::default()
+        };
+
+        let pkg = config.find_package("core").unwrap();
+        assert_eq!(pkg.name, "core");
+    }
+
+    #[test]
+    fn find_package_not_found() {
+        let config = ReleaseConfig::default();
+        let err = config.find_package("nonexistent").unwrap_err();
+        assert!(err.to_string().contains("nonexistent"));
+        assert!(err.to_string().contains("no packages configured"));
+    }
+
+    #[test]
+    fn load_yaml_with_providers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(
+            &path,
+            r#"
+providers:
+  - hostname: github.com
+  - hostname: ghes.example.com
+    owner: mirrors
+    token_env: GHES_TOKEN
+"#,
+        )
+        .unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.providers.len(), 2);
+        assert_eq!(config.providers[0].hostname, "github.com");
+        assert!(config.providers[0].owner.is_none());
+        assert_eq!(config.providers[1].owner.as_deref(), Some("mirrors"));
+        assert_eq!(config.providers[1].token_env.as_deref(), Some("GHES_TOKEN"));
+    }
+
+    #[test]
+    fn load_yaml_with_provider_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(&path, "provider: none\n").unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.provider, ProviderMode::None);
+        assert_eq!(ReleaseConfig::default().provider, ProviderMode::Github);
+    }
+
+    #[test]
+    fn summary_upload_requires_a_file() {
+        let err = ReleaseConfig::parse("summary:\n  upload: true\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("summary.upload: set summary.file"), "{err}");
+        let config =
+            ReleaseConfig::parse("summary:\n  file: release-summary.json\n  upload: true\n")
+                .unwrap();
+        assert_eq!(config.summary.file.as_deref(), Some("release-summary.json"));
+    }
+
+    #[test]
+    fn calver_format_is_validated() {
+        let config = ReleaseConfig::parse("versioning: calver\n").unwrap();
+        let calver = config.calver().unwrap().unwrap();
+        let version = semver::Version::new(2025, 3, 2);
+        assert_eq!(calver.format(&version), "2025.03.2");
+        assert_eq!(config.format_version(&version), "2025.03.2");
+        assert_eq!(
+            ReleaseConfig::default().format_version(&version),
+            "2025.3.2"
+        );
+
+        let err = ReleaseConfig::parse("versioning: calver\ncalver_format: YYYY.MICRO\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("calver_format:"), "{err}");
+
+        let err = ReleaseConfig::parse("versioning: calver\nprerelease: rc\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("prerelease: not supported with versioning: calver"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn version_floor_must_be_semver() {
+        let config = ReleaseConfig::parse("version_floor: v2.3.1\n").unwrap();
+        assert_eq!(config.version_floor(), Some(semver::Version::new(2, 3, 1)));
+        let err = ReleaseConfig::parse("version_floor: \"2.3\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("version_floor: '2.3' is not a semver version"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn lightweight_tags_cannot_be_signed() {
+        let config = ReleaseConfig::parse("tags:\n  annotated: false\n").unwrap();
+        assert!(!config.tags.annotated);
+        assert!(ReleaseConfig::default().tags.annotated);
+
+        let err = ReleaseConfig::parse("sign_tags: true\ntags:\n  annotated: false\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("lightweight tags cannot be signed"), "{err}");
+    }
+
+    #[test]
+    fn load_yaml_with_signing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(
+            &path,
+            "sign_tags: true\nsigning:\n  commits: true\n  key: ~/.ssh/id_ed25519.pub\n",
+        )
+        .unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert!(!config.signing.tags);
+        let signing = config.effective_signing();
+        assert!(signing.tags);
+        assert!(signing.commits);
+        assert!(signing.is_ssh_key());
+    }
+
+    #[test]
+    fn load_yaml_with_git_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(
+            &path,
+            "git:\n  user_name: sr-bot\n  user_email: sr-bot@users.noreply.github.com\n",
+        )
+        .unwrap();
+
+        let config = ReleaseConfig::load(&path).unwrap();
+        assert_eq!(config.git.user_name.as_deref(), Some("sr-bot"));
+        assert_eq!(
+            config.git.user_email.as_deref(),
+            Some("sr-bot@users.noreply.github.com")
+        );
+        assert_eq!(ReleaseConfig::default().git, GitConfig::default());
+        assert_eq!(
+            config.git.timeout(),
+            Some(std::time::Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn parse_duration_accepts_units_and_combinations() {
+        assert_eq!(parse_duration("90s"), Ok(90));
+        assert_eq!(parse_duration("24h"), Ok(86_400));
+        assert_eq!(parse_duration("1h30m"), Ok(5_400));
+        assert_eq!(parse_duration("2w"), Ok(1_209_600));
+        assert!(parse_duration("24").unwrap_err().contains("missing unit"));
+        assert!(parse_duration("1y").unwrap_err().contains("unknown unit"));
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn invalid_policy_interval_is_a_config_problem() {
+        let config = ReleaseConfig {
+            policy: PolicyConfig {
+                min_interval: Some("soon".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let problems = config.violations();
+        assert!(
+            problems
+                .iter()
+                .any(|p| p.starts_with("policy.min_interval: invalid duration 'soon'")),
+            "{problems:?}"
+        );
+    }
+
+    #[test]
+    fn release_notes_ref_expands_bare_names() {
+        let git = |name: &str| GitConfig {
+            release_notes_ref: Some(name.into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            git("releases").notes_ref().as_deref(),
+            Some("refs/notes/releases")
+        );
+        assert_eq!(
+            git("refs/notes/sr").notes_ref().as_deref(),
+            Some("refs/notes/sr")
+        );
+        assert_eq!(GitConfig::default().notes_ref(), None);
+
+        let config = ReleaseConfig {
+            git: git("refs/heads/main"),
+            ..Default::default()
+        };
+        assert!(
+            config
+                .violations()
+                .iter()
+                .any(|p| p.starts_with("git.release_notes_ref:")),
+            "{:?}",
+            config.violations()
+        );
+    }
+
+    #[test]
+    fn github_urls_allow_plain_http_only_on_loopback() {
+        let problems = |url: &str| {
+            ReleaseConfig {
+                github: GitHubConfig {
+                    api_url: Some(url.into()),
+                    upload_url: Some(url.into()),
+                },
+                ..Default::default()
+            }
+            .violations()
+            .into_iter()
+            .filter(|p| p.starts_with("github."))
+            .count()
+        };
+        assert_eq!(problems("https://api.ghes.example.com"), 0);
+        assert_eq!(problems("http://127.0.0.1:8080/api"), 0);
+        assert_eq!(problems("http://localhost"), 0);
+        assert_eq!(problems("http://ghes.example.com/api/v3"), 2);
+        assert_eq!(problems("http://localhost.example.com"), 2);
+        assert_eq!(problems("ghes.example.com"), 2);
+    }
+
+    #[test]
+    fn git_timeout_zero_disables_the_limit() {
+        let git: GitConfig = serde_yaml_ng::from_str("timeout_seconds: 0").unwrap();
+        assert_eq!(git.timeout(), None);
+    }
+
+    #[test]
+    fn pathspecs_combine_package_includes_and_excludes() {
+        let config = ReleaseConfig {
+            include_paths: vec!["src".into()],
+            exclude_paths: vec!["docs".into(), "*.md".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.pathspecs(),
+            vec!["src", ":(exclude)docs", ":(exclude)*.md"]
+        );
+        assert!(ReleaseConfig::default().pathspecs().is_empty());
+
+        // A package path replaces root includes but keeps root excludes
+        let pkg = PackageConfig {
+            name: "core".into(),
+            path: "crates/core".into(),
+            tag_prefix: None,
+            version_files: vec!["crates/core/Cargo.toml".into()],
+            changelog: None,
+            changelog_file: None,
+            build_command: None,
+            stage_files: vec![],
+        };
+        assert_eq!(
+            config.resolve_package(&pkg).pathspecs(),
+            vec!["crates/core", ":(exclude)docs", ":(exclude)*.md"]
+        );
+    }
+
+    #[test]
+    fn release_commit_message_renders_placeholders() {
+        let config = ReleaseConfig {
+            release_commit_message: "release {version} ({tag}, {date}) [ci skip]".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.render_release_commit_message("1.2.3", "v1.2.3", "2026-01-01"),
+            "release 1.2.3 (v1.2.3, 2026-01-01) [ci skip]"
+        );
+        assert_eq!(
+            ReleaseConfig::default().render_release_commit_message("1.2.3", "v1.2.3", "2026-01-01"),
+            "chore(release): v1.2.3 [skip ci]"
+        );
+    }
+
+    #[test]
+    fn release_commit_trailers_render_and_validate() {
+        let config = ReleaseConfig::parse(
+            "release_commit_trailers:\n  Skip-Checks: \"true\"\n  Release-Version: \"{version}\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.render_release_commit_trailers("1.2.3", "v1.2.3", "2026-01-01"),
+            [
+                ("Release-Version".to_string(), "1.2.3".to_string()),
+                ("Skip-Checks".to_string(), "true".to_string()),
+            ]
+        );
+
+        let err = ReleaseConfig::parse("release_commit_trailers:\n  \"Skip Checks\": \"true\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid trailer key 'Skip Checks'"), "{err}");
+        let err = ReleaseConfig::parse("release_commit_trailers:\n  Release: \"{verison}\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown placeholder {verison}"), "{err}");
+    }
+
+    #[test]
+    fn release_commit_message_validated_at_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(&path, "release_commit_message: \"release {verison}\"\n").unwrap();
+        let err = ReleaseConfig::load(&path).unwrap_err().to_string();
+        assert!(err.contains("unknown placeholder {verison}"), "{err}");
+
+        std::fs::write(&path, "release_commit_message: \"release {version\"\n").unwrap();
+        let err = ReleaseConfig::load(&path).unwrap_err().to_string();
+        assert!(err.contains("unclosed"), "{err}");
+    }
+
+    #[test]
+    fn unknown_keys_suggest_closest_match() {
+        let err = ReleaseConfig::parse("tag_prfix: rel-\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("unknown key `tag_prfix` (did you mean tag_prefix?)"),
+            "{err}"
+        );
+
+        let err = ReleaseConfig::parse("completely_unrelated: true\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown key `completely_unrelated`"), "{err}");
+        assert!(!err.contains("did you mean"), "{err}");
+    }
+
+    #[test]
+    fn unknown_keys_checked_in_nested_sections() {
+        let yaml = "changelog:\n  fiel: CHANGELOG.md\ntypes:\n  - name: feat\n    bupm: minor\npackages:\n  - name: core\n    path: crates/core\n    changelog:\n      templat: x\nhooks:\n  my-custom-hook:\n    - echo hi\n";
+        let err = ReleaseConfig::parse(yaml).unwrap_err().to_string();
+        assert!(err.contains("3 problems found"), "{err}");
+        assert!(
+            err.contains("unknown key `changelog.fiel` (did you mean file?)"),
+            "{err}"
+        );
+        assert!(
+            err.contains("unknown key `types[0].bupm` (did you mean bump?)"),
+            "{err}"
+        );
+        assert!(
+            err.contains("unknown key `packages[0].changelog.templat` (did you mean template?)"),
+            "{err}"
+        );
+        // Hook names are free-form.
+        assert!(!err.contains("my-custom-hook"), "{err}");
+    }
+
+    #[test]
+    fn known_keys_follow_the_schema() {
+        // Every variant of a tagged or untagged enum contributes its fields
+        let publish = known_keys("publish[]").unwrap();
+        for key in ["type", "path", "args", "tag", "run"] {
+            assert!(publish.contains(&key), "{publish:?}");
+        }
+        let build = known_keys("packages[].build_command").unwrap();
+        assert!(build.contains(&"timeout"), "{build:?}");
+        // Free-form maps and scalars have none
+        assert_eq!(known_keys("hooks"), None);
+        assert_eq!(known_keys("tag_prefix"), None);
+        assert_eq!(known_keys("no_such_section"), None);
+    }
+
+    #[test]
+    fn tag_prefix_accepts_a_string_or_a_list() {
+        let config = ReleaseConfig::parse("tag_prefix: [v, release-]\n").unwrap();
+        assert_eq!(config.tag_prefix.primary(), "v");
+        assert_eq!(config.tag_prefix.all(), ["v", "release-"]);
+        assert_eq!(config.tag_prefix.describe(), "'v', 'release-'");
+        let yaml = serde_yaml_ng::to_string(&config).unwrap();
+        assert!(yaml.contains("tag_prefix:\n- v\n- release-\n"), "{yaml}");
+
+        let config = ReleaseConfig::parse("tag_prefix: rel-\n").unwrap();
+        assert_eq!(config.tag_prefix.all(), ["rel-"]);
+        let yaml = serde_yaml_ng::to_string(&config).unwrap();
+        assert!(yaml.contains("tag_prefix: rel-\n"), "{yaml}");
+
+        for bad in ["tag_prefix: []\n", "tag_prefix: [v, \"\"]\n"] {
+            let err = ReleaseConfig::parse(bad).unwrap_err().to_string();
+            assert!(err.contains("tag_prefix must not be empty"), "{bad}: {err}");
+        }
+    }
+
+    #[test]
+    fn tag_prefixes_may_be_paths_but_not_invalid_refs() {
+        let config = ReleaseConfig::parse("tag_prefix: pkg/foo-v\n").unwrap();
+        assert_eq!(config.tag_prefix.primary(), "pkg/foo-v");
+        assert_eq!(
+            config.render_release_name("1.2.3", "pkg/foo-v1.2.3", "2026-01-02"),
+            "foo-v1.2.3"
+        );
+
+        for (bad, reason) in [
+            ("-v", "must not start with '-'"),
+            ("/pkg/v", "must not start with '/'"),
+            ("pkg//v", "must not start with '/' or contain '//'"),
+            ("pkg/.v", "must not have a path segment starting with '.'"),
+            ("pkg v", "must not contain whitespace"),
+            ("pkg/v*", "must not contain whitespace"),
+            ("pkg.lock/v", "must not have a directory ending in '.lock'"),
+        ] {
+            let err = ReleaseConfig::parse(&format!("tag_prefix: [v, \"{bad}\"]\n"))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.contains(&format!("tag_prefix: '{bad}' {reason}")),
+                "{err}"
+            );
         }
+        let err = ReleaseConfig::parse(
+            "packages:\n  - name: foo\n    path: foo\n    tag_prefix: \"foo:v\"\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("packages.foo.tag_prefix: 'foo:v'"), "{err}");
     }
-}
 
-// Custom deserialization for BumpLevel so it can appear in YAML config.
-impl<'de> Deserialize<'de> for BumpLevel {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "major" => Ok(BumpLevel::Major),
-            "minor" => Ok(BumpLevel::Minor),
-            "patch" => Ok(BumpLevel::Patch),
-            _ => Err(serde::de::Error::custom(format!("unknown bump level: {s}"))),
+    #[test]
+    fn display_name_drops_the_prefix_path() {
+        let config =
+            ReleaseConfig::parse("release:\n  name_template: \"{display_name} ({tag})\"\n")
+                .unwrap();
+        assert_eq!(
+            config.render_release_name("1.2.3", "crates/sr-core/v1.2.3", ""),
+            "sr-core v1.2.3 (crates/sr-core/v1.2.3)"
+        );
+        for (tag, name) in [
+            ("v1.2.3", "v1.2.3"),
+            ("release-1.2.3", "release-1.2.3"),
+            ("pkg/foo-v1.2.3", "foo-v1.2.3"),
+            ("pkg/foo-v1", "foo-v1"),
+            ("core/v2.0.0-rc.1", "core v2.0.0-rc.1"),
+            ("pkg/1.2.3", "pkg 1.2.3"),
+        ] {
+            assert_eq!(tag_display_name(tag), name, "{tag}");
         }
     }
-}
 
-impl Serialize for BumpLevel {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let s = match self {
-            BumpLevel::Major => "major",
-            BumpLevel::Minor => "minor",
-            BumpLevel::Patch => "patch",
+    #[test]
+    fn entry_for_keeps_the_selected_sections() {
+        let config = ReleaseConfig::parse(
+            "changelog:\n  compare_link: false\nrelease:\n  sections: [Breaking Changes, Features]\n",
+        )
+        .unwrap();
+        let commit = |r#type: &str, breaking: bool| ConventionalCommit {
+            sha: "abc".into(),
+            r#type: r#type.into(),
+            scope: None,
+            description: "x".into(),
+            body: None,
+            breaking,
         };
-        serializer.serialize_str(s)
-    }
-}
+        let entry = ChangelogEntry {
+            version: "1.0.0".into(),
+            tag_name: None,
+            date: "2026-01-01".into(),
+            commits: vec![
+                commit("feat", false),
+                commit("fix", true),
+                commit("fix", false),
+                commit("chore", false),
+                commit("wip", false),
+            ],
+            compare_url: Some("https://example.com/compare".into()),
+            repo_url: None,
+        };
+        assert_eq!(
+            config.section_of(&entry.commits[1]),
+            Some("Breaking Changes")
+        );
+        assert_eq!(config.section_of(&entry.commits[3]), Some("Miscellaneous"));
+        assert_eq!(config.section_of(&entry.commits[4]), None);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Write;
+        let release = config.entry_for(entry.clone(), Destination::Release);
+        let kept: Vec<(&str, bool)> = release
+            .commits
+            .iter()
+            .map(|c| (c.r#type.as_str(), c.breaking))
+            .collect();
+        assert_eq!(kept, [("feat", false), ("fix", true)]);
+        assert!(release.compare_url.is_some());
+
+        let changelog = config.entry_for(entry, Destination::Changelog);
+        assert_eq!(changelog.commits.len(), 5);
+        assert!(changelog.compare_url.is_none());
+    }
 
     #[test]
-    fn default_values() {
-        let config = ReleaseConfig::default();
-        assert_eq!(config.branches, vec!["main", "master"]);
-        assert_eq!(config.tag_prefix, "v");
-        assert_eq!(config.commit_pattern, DEFAULT_COMMIT_PATTERN);
-        assert_eq!(config.breaking_section, "Breaking Changes");
-        assert_eq!(config.misc_section, "Miscellaneous");
-        assert!(!config.types.is_empty());
-        assert!(!config.version_files_strict);
-        assert!(config.artifacts.is_empty());
-        assert!(!config.floating_tags);
+    fn unknown_sections_are_reported() {
+        let err = ReleaseConfig::parse("changelog:\n  sections: [Featurse]\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("changelog.sections: unknown section 'Featurse' (available: Breaking Changes, Features"),
+            "{err}"
+        );
     }
 
     #[test]
-    fn load_missing_file() {
-        let dir = tempfile::tempdir().unwrap();
-        let path = dir.path().join("nonexistent.yml");
-        let config = ReleaseConfig::load(&path).unwrap();
-        assert_eq!(config.tag_prefix, "v");
+    fn semantic_violations_all_reported() {
+        let yaml = "tag_prefix: \"\"\ncommit_pattern: \"^(?P<kind>\\\\w+): (?P<description>.+)\"\ntypes_mode: replace\ntypes:\n  - name: feat\n    bump: minor\n  - name: feat\n    bump: patch\n";
+        let err = ReleaseConfig::parse(yaml).unwrap_err().to_string();
+        assert!(err.contains("3 problems found"), "{err}");
+        assert!(err.contains("tag_prefix must not be empty"), "{err}");
+        assert!(
+            err.contains("commit_pattern must contain a named group (?P<type>...)"),
+            "{err}"
+        );
+        assert!(err.contains("types: duplicate entry 'feat'"), "{err}");
+
+        let err = ReleaseConfig::parse("commit_pattern: \"(unclosed\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("commit_pattern is not a valid regex"), "{err}");
     }
 
     #[test]
-    fn load_valid_yaml() {
-        let dir = tempfile::tempdir().unwrap();
-        let path = dir.path().join("config.yml");
-        let mut f = std::fs::File::create(&path).unwrap();
-        writeln!(f, "branches:\n  - develop\ntag_prefix: release-").unwrap();
+    fn generated_configs_pass_strict_validation() {
+        ReleaseConfig::parse(&default_config_template(&DetectedDefaults {
+            version_files: vec!["Cargo.toml".into()],
+            artifacts: vec!["dist/*".into()],
+            changelog_file: Some("CHANGELOG.md".into()),
+        }))
+        .unwrap();
+        ReleaseConfig::parse(&merge_config_yaml("tag_prefix: v\n").unwrap()).unwrap();
+        ReleaseConfig::parse("").unwrap();
+    }
 
-        let config = ReleaseConfig::load(&path).unwrap();
-        assert_eq!(config.branches, vec!["develop"]);
-        assert_eq!(config.tag_prefix, "release-");
+    #[test]
+    fn known_keys_match_serialized_fields() {
+        let value = serde_yaml_ng::to_value(ReleaseConfig::default()).unwrap();
+        let keys: Vec<&str> = value
+            .as_mapping()
+            .unwrap()
+            .keys()
+            .filter_map(|k| k.as_str())
+            .collect();
+        let known = known_keys("").unwrap();
+        for key in &keys {
+            assert!(known.contains(key), "missing schema key {key}");
+        }
+        for key in &known {
+            // Skipped when empty.
+            if *key == "extends" || *key == "packages" || *key == "providers" {
+                continue;
+            }
+            assert!(keys.contains(key), "stale schema key {key}");
+        }
     }
 
     #[test]
-    fn load_partial_yaml() {
-        let dir = tempfile::tempdir().unwrap();
-        let path = dir.path().join("config.yml");
-        std::fs::write(&path, "tag_prefix: rel-\n").unwrap();
+    fn schema_covers_config_keys() {
+        let schema = config_schema();
+        let properties = schema["properties"].as_object().unwrap();
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(
+            schema["$defs"]["BumpLevel"]["enum"],
+            serde_json::json!(["major", "minor", "patch"])
+        );
+        assert!(
+            properties["tag_prefix"]["description"]
+                .as_str()
+                .unwrap()
+                .contains("Prefix for release tags")
+        );
+    }
 
-        let config = ReleaseConfig::load(&path).unwrap();
-        assert_eq!(config.tag_prefix, "rel-");
-        assert_eq!(config.branches, vec!["main", "master"]);
-        // defaults should still apply for types/pattern/breaking_section
-        assert_eq!(config.commit_pattern, DEFAULT_COMMIT_PATTERN);
-        assert_eq!(config.breaking_section, "Breaking Changes");
-        assert!(!config.types.is_empty());
+    #[test]
+    fn published_schema_is_up_to_date() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../sr.schema.json");
+        let published: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert!(
+            published == config_schema(),
+            "sr.schema.json is stale; regenerate with `sr config --schema > sr.schema.json`"
+        );
     }
 
     #[test]
-    fn load_yaml_with_artifacts() {
-        let dir = tempfile::tempdir().unwrap();
-        let path = dir.path().join("config.yml");
-        std::fs::write(
-            &path,
-            "artifacts:\n  - \"dist/*.tar.gz\"\n  - \"build/output-*\"\n",
-        )
-        .unwrap();
+    fn readme_example_config_is_the_default_template() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../README.md");
+        let readme = std::fs::read_to_string(path).unwrap();
+        let template = default_config_template(&DetectedDefaults::default());
+        // Skip the schema line: its URL names the current version
+        let (_, body) = template.split_once('\n').unwrap();
+        assert!(
+            readme.contains(&format!("```yaml\n{body}```")),
+            "README.md's example config is stale; paste in `sr init --minimal` output"
+        );
+    }
 
-        let config = ReleaseConfig::load(&path).unwrap();
-        assert_eq!(config.artifacts, vec!["dist/*.tar.gz", "build/output-*"]);
-        // defaults still apply
-        assert_eq!(config.tag_prefix, "v");
+    #[test]
+    fn generated_configs_reference_schema() {
+        let line = format!("# yaml-language-server: $schema={CONFIG_SCHEMA_URL}\n");
+        assert!(default_config_template(&DetectedDefaults::default()).starts_with(&line));
+        assert!(
+            merge_config_yaml("tag_prefix: v\n")
+                .unwrap()
+                .starts_with(&line)
+        );
     }
 
     #[test]
-    fn load_yaml_with_floating_tags() {
-        let dir = tempfile::tempdir().unwrap();
-        let path = dir.path().join("config.yml");
-        std::fs::write(&path, "floating_tags: true\n").unwrap();
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance("tag_prefix", "tag_prefix"), 0);
+        assert_eq!(edit_distance("tag_prfix", "tag_prefix"), 1);
+        assert_eq!(edit_distance("brnaches", "branches"), 2);
+        assert_eq!(edit_distance("", "git"), 3);
+    }
 
-        let config = ReleaseConfig::load(&path).unwrap();
-        assert!(config.floating_tags);
-        // defaults still apply
-        assert_eq!(config.tag_prefix, "v");
+    #[test]
+    fn changelog_dates_default_to_utc_iso() {
+        // 2026-03-01T23:30:00Z
+        let ts = 1_772_407_800;
+        assert_eq!(
+            ChangelogConfig::default().format_date(ts).unwrap(),
+            "2026-03-01"
+        );
     }
 
     #[test]
-    fn bump_level_roundtrip() {
-        for (level, expected) in [
-            (BumpLevel::Major, "major"),
-            (BumpLevel::Minor, "minor"),
-            (BumpLevel::Patch, "patch"),
-        ] {
-            let yaml = serde_yaml_ng::to_string(&level).unwrap();
-            assert!(yaml.contains(expected));
-            let parsed: BumpLevel = serde_yaml_ng::from_str(&yaml).unwrap();
-            assert_eq!(parsed, level);
-        }
+    fn changelog_dates_use_custom_format_and_timezone() {
+        let ts = 1_772_407_800;
+        let changelog = ChangelogConfig {
+            date_format: Some("%B %-d, %Y".into()),
+            ..Default::default()
+        };
+        assert_eq!(changelog.format_date(ts).unwrap(), "March 1, 2026");
+
+        let changelog = ChangelogConfig {
+            date_format: Some("%Y-%m-%d %H:%M %Z".into()),
+            timezone: Some("Asia/Tokyo".into()),
+            ..Default::default()
+        };
+        assert_eq!(changelog.format_date(ts).unwrap(), "2026-03-02 08:30 JST");
     }
 
     #[test]
-    fn types_roundtrip() {
-        let config = ReleaseConfig::default();
-        let yaml = serde_yaml_ng::to_string(&config).unwrap();
-        let parsed: ReleaseConfig = serde_yaml_ng::from_str(&yaml).unwrap();
-        assert_eq!(parsed.types.len(), config.types.len());
-        assert_eq!(parsed.types[0].name, "feat");
-        assert_eq!(parsed.commit_pattern, config.commit_pattern);
-        assert_eq!(parsed.breaking_section, config.breaking_section);
+    fn invalid_date_format_and_timezone_are_reported() {
+        let err = ReleaseConfig::parse(
+            "changelog:\n  timezone: Mars/Olympus\npackages:\n  - name: core\n    path: core\n    changelog:\n      date_format: \"%J\"\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("changelog.timezone 'Mars/Olympus'"), "{err}");
+        assert!(
+            err.contains("packages.core.changelog.date_format '%J'"),
+            "{err}"
+        );
     }
 
     #[test]
-    fn load_yaml_with_packages() {
-        let dir = tempfile::tempdir().unwrap();
-        let path = dir.path().join("config.yml");
-        std::fs::write(
-            &path,
-            r#"
-packages:
-  - name: core
-    path: crates/core
-    version_files:
-      - crates/core/Cargo.toml
-  - name: cli
-    path: crates/cli
-    tag_prefix: "cli-v"
-"#,
+    fn release_section_parses_and_validates() {
+        let config = ReleaseConfig::parse("").unwrap();
+        assert_eq!(config.release, RemoteReleaseConfig::default());
+        assert_eq!(
+            config.render_release_name("1.2.3", "v1.2.3", "2026-01-02"),
+            "v1.2.3"
+        );
+
+        let config = ReleaseConfig::parse(
+            "release:\n  name_template: \"{version} ({date})\"\n  prerelease: true\n",
         )
         .unwrap();
+        assert_eq!(config.release.prerelease, PrereleaseFlag::Always);
+        assert_eq!(
+            config.render_release_name("1.2.3", "v1.2.3", "2026-01-02"),
+            "1.2.3 (2026-01-02)"
+        );
 
-        let config = ReleaseConfig::load(&path).unwrap();
-        assert_eq!(config.packages.len(), 2);
-        assert_eq!(config.packages[0].name, "core");
-        assert_eq!(config.packages[0].path, "crates/core");
-        assert_eq!(config.packages[1].tag_prefix.as_deref(), Some("cli-v"));
+        for (value, expected) in [
+            ("false", PrereleaseFlag::Never),
+            ("auto", PrereleaseFlag::Auto),
+        ] {
+            let config =
+                ReleaseConfig::parse(&format!("release:\n  prerelease: {value}\n")).unwrap();
+            assert_eq!(config.release.prerelease, expected);
+        }
+
+        let err = ReleaseConfig::parse("release:\n  prerelease: sometimes\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expected true, false, or auto"), "{err}");
+        let err = ReleaseConfig::parse("release:\n  name_template: \"{name}\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("release.name_template: unknown placeholder {name}"),
+            "{err}"
+        );
     }
 
     #[test]
-    fn resolve_package_defaults() {
+    fn prerelease_flag_auto_follows_version() {
+        let stable = semver::Version::new(1, 2, 3);
+        let rc = semver::Version::parse("1.2.3-rc.1").unwrap();
+        assert!(!PrereleaseFlag::Auto.resolve(&stable));
+        assert!(PrereleaseFlag::Auto.resolve(&rc));
+        assert!(PrereleaseFlag::Always.resolve(&stable));
+        assert!(!PrereleaseFlag::Never.resolve(&rc));
+    }
+
+    #[test]
+    fn release_commit_matcher_follows_template() {
         let config = ReleaseConfig {
-            packages: vec![PackageConfig {
-                name: "core".into(),
-                path: "crates/core".into(),
-                tag_prefix: None,
-                version_files: vec![],
-                changelog: None,
-                build_command: None,
-                stage_files: vec![],
-            }],
+            release_commit_message: "build: release {version} [ci skip]".into(),
             ..Default::default()
         };
+        let matcher = config.release_commit_matcher();
+        assert!(matcher.matches("build: release 1.2.3 [ci skip]"));
+        assert!(matcher.matches("chore(release): v1.0.0 [skip ci]"));
+        assert!(!matcher.matches("build: release tooling"));
+        assert!(!matcher.matches("feat: add release notes"));
 
-        let resolved = config.resolve_package(&config.packages[0]);
-        assert_eq!(resolved.tag_prefix, "core/v");
-        assert_eq!(resolved.path_filter.as_deref(), Some("crates/core"));
-        // Inherits root config values
-        assert_eq!(resolved.branches, config.branches);
-        assert!(resolved.packages.is_empty());
+        let default = ReleaseConfig::default().release_commit_matcher();
+        assert!(default.matches("chore(release): v1.2.3 [skip ci]\n\nbody"));
     }
 
     #[test]
-    fn resolve_package_overrides() {
+    fn release_commit_matcher_only_matches_versions_and_tags() {
         let config = ReleaseConfig {
-            version_files: vec!["Cargo.toml".into()],
-            packages: vec![PackageConfig {
-                name: "cli".into(),
-                path: "crates/cli".into(),
-                tag_prefix: Some("cli-v".into()),
-                version_files: vec!["crates/cli/Cargo.toml".into()],
-                changelog: Some(ChangelogConfig {
-                    file: Some("crates/cli/CHANGELOG.md".into()),
-                    template: None,
-                }),
-                build_command: Some("cargo build -p cli".into()),
-                stage_files: vec!["crates/cli/Cargo.lock".into()],
-            }],
+            release_commit_message: "{version} released".into(),
             ..Default::default()
         };
+        let matcher = config.release_commit_matcher();
+        assert!(matcher.matches("1.2.3 released"));
+        assert!(matcher.matches("2025.03.0 released"));
+        assert!(matcher.matches("2.0.0-rc.1 released"));
+        assert!(!matcher.matches("fix: something released"));
+        assert!(!matcher.matches("1.2.3 released and more"));
 
-        let resolved = config.resolve_package(&config.packages[0]);
-        assert_eq!(resolved.tag_prefix, "cli-v");
-        assert_eq!(resolved.version_files, vec!["crates/cli/Cargo.toml"]);
-        assert_eq!(
-            resolved.changelog.file.as_deref(),
-            Some("crates/cli/CHANGELOG.md")
-        );
-        assert_eq!(
-            resolved.build_command.as_deref(),
-            Some("cargo build -p cli")
-        );
-        assert_eq!(resolved.stage_files, vec!["crates/cli/Cargo.lock"]);
-    }
-
-    #[test]
-    fn find_package_found() {
         let config = ReleaseConfig {
+            release_commit_message: "release {tag} on {date}".into(),
             packages: vec![PackageConfig {
                 name: "core".into(),
                 path: "crates/core".into(),
-                tag_prefix: None,
                 version_files: vec![],
                 changelog: None,
+                tag_prefix: None,
+                changelog_file: None,
                 build_command: None,
                 stage_files: vec![],
             }],
             ..Default::default()
         };
+        let matcher = config.release_commit_matcher();
+        assert!(matcher.matches("release v1.2.3 on 2026-01-01"));
+        assert!(matcher.matches("release core/v0.4.0 on 2026-01-01"));
+        assert!(!matcher.matches("release notes on 2026-01-01"));
+        assert!(!matcher.matches("release v1.2.3 on friday"));
 
-        let pkg = config.find_package("core").unwrap();
-        assert_eq!(pkg.name, "core");
+        let bare = ReleaseConfig {
+            release_commit_message: "{tag}".into(),
+            ..Default::default()
+        };
+        assert!(!bare.release_commit_matcher().matches("feat: add x"));
+        assert!(!bare.release_commit_matcher().matches("v1.2.3"));
+        let err = bare.validate().unwrap_err().to_string();
+        assert!(err.contains("needs text besides placeholders"), "{err}");
     }
 
     #[test]
-    fn find_package_not_found() {
-        let config = ReleaseConfig::default();
-        let err = config.find_package("nonexistent").unwrap_err();
-        assert!(err.to_string().contains("nonexistent"));
-        assert!(err.to_string().contains("no packages configured"));
+    fn signing_gpg_key_id_is_not_ssh() {
+        let signing = SigningConfig {
+            key: Some("3AA5C34371567BD2".into()),
+            ..Default::default()
+        };
+        assert!(!signing.is_ssh_key());
+        assert!(!SigningConfig::default().is_ssh_key());
     }
 
     #[test]
@@ -712,7 +5132,7 @@ packages:
 
     #[test]
     fn default_template_parses() {
-        let template = default_config_template(&[]);
+        let template = default_config_template(&DetectedDefaults::default());
         let config: ReleaseConfig = serde_yaml_ng::from_str(&template).unwrap();
         let default = ReleaseConfig::default();
         assert_eq!(config.branches, default.branches);
@@ -727,35 +5147,138 @@ packages:
 
     #[test]
     fn default_template_with_version_files() {
-        let template = default_config_template(&["Cargo.toml".into(), "package.json".into()]);
+        let template = default_config_template(&DetectedDefaults {
+            version_files: vec!["Cargo.toml".into(), "package.json".into()],
+            ..Default::default()
+        });
         let config: ReleaseConfig = serde_yaml_ng::from_str(&template).unwrap();
         assert_eq!(config.version_files, vec!["Cargo.toml", "package.json"]);
+        assert_eq!(config.changelog.file, None);
+    }
+
+    #[test]
+    fn default_template_with_detected_changelog_and_artifacts() {
+        let template = default_config_template(&DetectedDefaults {
+            changelog_file: Some("CHANGELOG.md".into()),
+            artifacts: vec!["dist/*".into(), "target/release/app".into()],
+            ..Default::default()
+        });
+        let config = ReleaseConfig::parse(&template).unwrap();
+        assert_eq!(config.changelog.file.as_deref(), Some("CHANGELOG.md"));
+        assert!(config.artifacts.is_empty(), "suggestions are commented out");
+        assert!(
+            template.contains("artifacts: []\n# Build output found in this project:\n#   - dist/*\n#   - target/release/app\n"),
+            "{template}"
+        );
+    }
+
+    #[test]
+    fn detect_project_finds_manifests_build_output_and_changelog() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(detect_project(dir.path()).is_empty());
+
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("CHANGELOG.md"), "# Changelog\n").unwrap();
+        std::fs::create_dir(dir.path().join("target")).unwrap();
+        std::fs::create_dir(dir.path().join("dist")).unwrap();
+        assert_eq!(
+            detect_project(dir.path()),
+            DetectedDefaults {
+                version_files: vec!["Cargo.toml".into()],
+                artifacts: vec!["dist/*".into(), "target/release/app".into()],
+                changelog_file: Some("CHANGELOG.md".into()),
+            }
+        );
     }
 
     #[test]
     fn default_template_contains_all_fields() {
-        let template = default_config_template(&[]);
+        let template = default_config_template(&DetectedDefaults::default());
         for field in [
+            "extends",
             "branches",
             "tag_prefix",
+            "lenient_tags",
+            "versioning",
+            "calver_format",
             "commit_pattern",
             "breaking_section",
             "misc_section",
+            "other_section",
             "types",
+            "types_merge",
+            "types_mode",
+            "rules",
             "changelog",
+            "date_format",
+            "timezone",
+            "date_source",
+            "transform",
+            "capitalize_first",
+            "strip_trailing_period",
+            "replacements",
+            "aggregate",
+            "sections",
+            "compare_link",
+            "link_versions",
+            "unknown_types",
+            "migration_notes",
+            "changelog_file",
             "version_files",
             "version_files_strict",
+            "version_floor",
+            "version_file_urls",
             "artifacts",
+            "artifacts_required",
+            "attestations",
+            "embed_digests",
+            "generators",
             "floating_tags",
             "build_command",
             "stage_files",
             "prerelease",
             "pre_release_command",
             "post_release_command",
+            "publish",
+            "notifications",
+            "summary",
             "sign_tags",
+            "signing",
+            "tags",
+            "annotated",
+            "git",
+            "user_name",
+            "user_email",
+            "timeout_seconds",
+            "release_notes_ref",
             "draft",
+            "require_ci",
+            "release",
+            "name_template",
+            "notes_file_template",
+            "notes_file_required",
             "release_name_template",
+            "release_commit_message",
+            "release_commit_trailers",
+            "lock",
+            "policy",
+            "min_commits",
+            "min_interval",
             "hooks",
+            "provider",
+            "providers",
+            "repository",
+            "hostname",
+            "github",
+            "api_url",
+            "upload_url",
+            "include_paths",
+            "exclude_paths",
+            "max_commits",
             "packages",
         ] {
             assert!(template.contains(field), "template missing field: {field}");
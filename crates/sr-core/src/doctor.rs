@@ -0,0 +1,421 @@
+//! Environment diagnostics for `sr doctor`.
+//!
+//! Each check takes the already-gathered facts it judges (a git version, a
+//! branch name, a provider permission lookup) rather than querying the
+//! environment itself, so every check can be exercised with plain values.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::config::ReleaseConfig;
+use crate::error::ReleaseError;
+use crate::git::TagInfo;
+use crate::release::{DefaultBranch, check_release_branch};
+use crate::version_files::read_version_file;
+
+/// Outcome of a single check. A `Fail` means `sr release` would not succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl std::fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CheckStatus::Pass => "pass",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "fail",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    pub fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, detail)
+    }
+
+    pub fn warn(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Warn, detail)
+    }
+
+    pub fn fail(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail)
+    }
+
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Render checks as an aligned `STATUS  NAME  DETAIL` table. `paint` styles
+/// the status column (e.g. with terminal colors).
+pub fn render_table(checks: &[CheckResult], paint: &dyn Fn(CheckStatus, &str) -> String) -> String {
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    checks
+        .iter()
+        .map(|c| {
+            let status = format!("{:<4}", c.status.to_string().to_uppercase());
+            let status = paint(c.status, &status);
+            format!("{status}  {:<width$}  {}\n", c.name, c.detail)
+        })
+        .collect()
+}
+
+/// `git --version` output, or `None` when no git binary is on `PATH`.
+pub fn git_binary(version: Option<&str>) -> CheckResult {
+    match version {
+        Some(v) => CheckResult::pass("git", v),
+        None => CheckResult::fail("git", "git not found on PATH (needed to push tags)"),
+    }
+}
+
+/// Whether the current directory is inside a git repository.
+pub fn repository(opened: Result<(), &ReleaseError>) -> CheckResult {
+    match opened {
+        Ok(()) => CheckResult::pass("repository", "git repository detected"),
+        Err(e) => CheckResult::fail("repository", e.to_string()),
+    }
+}
+
+/// The `origin` remote, parsed into (hostname, owner, repo).
+pub fn remote(parsed: &Result<(String, String, String), ReleaseError>) -> CheckResult {
+    match parsed {
+        Ok((host, owner, repo)) => CheckResult::pass("remote", format!("{host}/{owner}/{repo}")),
+        Err(e) => CheckResult::fail("remote", e.to_string()),
+    }
+}
+
+/// Shallow clones hide tags and history, so the next version may be computed wrong.
+pub fn history(shallow: Result<bool, ReleaseError>) -> CheckResult {
+    match shallow {
+        Ok(false) => CheckResult::pass("history", "full clone"),
+        Ok(true) => CheckResult::warn(
+            "history",
+            "shallow clone; sr will fetch tags and unshallow before releasing \
+             (use fetch-depth: 0 in CI to avoid it)",
+        ),
+        Err(e) => CheckResult::fail("history", e.to_string()),
+    }
+}
+
+/// Release tags matching `tag_prefix`, given as [`TagPrefix::describe`] puts
+/// it. None is fine for a first release.
+///
+/// [`TagPrefix::describe`]: crate::config::TagPrefix::describe
+pub fn tags(tags: Result<Vec<TagInfo>, ReleaseError>, prefix: &str) -> CheckResult {
+    match tags {
+        Ok(tags) => match tags.iter().max_by(|a, b| a.version.cmp(&b.version)) {
+            Some(latest) => CheckResult::pass(
+                "tags",
+                format!("{} tag(s), latest {}", tags.len(), latest.name),
+            ),
+            None => CheckResult::warn(
+                "tags",
+                format!("no tags with prefix {prefix}; the next release will be the first"),
+            ),
+        },
+        Err(e) => CheckResult::fail("tags", e.to_string()),
+    }
+}
+
+/// The current branch (or `GITHUB_REF_NAME` on a detached HEAD) against `branches`.
+pub fn branch(
+    current: Result<Option<String>, ReleaseError>,
+    github_ref_name: Option<&str>,
+    branches: &[String],
+) -> CheckResult {
+    let current = match current {
+        Ok(current) => current,
+        Err(e) => return CheckResult::fail("branch", e.to_string()),
+    };
+    match check_release_branch(branches, current.as_deref(), github_ref_name) {
+        Ok(()) => {
+            let name = current
+                .as_deref()
+                .or(github_ref_name)
+                .unwrap_or("detached HEAD");
+            if branches.is_empty() {
+                CheckResult::pass("branch", format!("{name} (any branch allowed)"))
+            } else {
+                CheckResult::pass("branch", format!("{name} is a release branch"))
+            }
+        }
+        Err(e) => CheckResult::fail("branch", e.to_string()),
+    }
+}
+
+/// The default branch (see `detect_default_branch`) and where it came from.
+/// Not knowing it only matters when `branches` would fall back to it.
+pub fn default_branch(detected: &Result<Option<DefaultBranch>, ReleaseError>) -> CheckResult {
+    match detected {
+        Ok(Some(branch)) => CheckResult::pass(
+            "default branch",
+            format!("{} (from {})", branch.name, branch.source),
+        ),
+        Ok(None) => CheckResult::warn(
+            "default branch",
+            "unknown; origin/HEAD is not set (`git remote set-head origin --auto` sets it)",
+        ),
+        Err(e) => CheckResult::warn("default branch", e.to_string()),
+    }
+}
+
+/// Uncommitted changes. `sr release` refuses to run with changes outside the
+/// files it manages, so any change is worth a warning.
+pub fn working_tree(dirty: Result<Vec<String>, ReleaseError>) -> CheckResult {
+    match dirty {
+        Ok(paths) if paths.is_empty() => CheckResult::pass("working tree", "clean"),
+        Ok(paths) => CheckResult::warn(
+            "working tree",
+            format!(
+                "{} uncommitted change(s): {}",
+                paths.len(),
+                paths.join(", ")
+            ),
+        ),
+        Err(e) => CheckResult::fail("working tree", e.to_string()),
+    }
+}
+
+/// API token presence and, when the provider can tell, its push permission.
+///
+/// `access` is the provider's answer (see `VcsProvider::can_push`), or `None`
+/// when no provider could be built.
+pub fn token(
+    present: bool,
+    remote_release: bool,
+    access: Option<Result<Option<bool>, ReleaseError>>,
+) -> CheckResult {
+    if !remote_release {
+        return CheckResult::pass("token", "not needed (provider: none)");
+    }
+    if !present {
+        return CheckResult::fail(
+            "token",
+            "neither GH_TOKEN nor GITHUB_TOKEN is set; only --dry-run and --no-push will work",
+        );
+    }
+    match access {
+        Some(Ok(Some(true))) => CheckResult::pass("token", "set, with push access"),
+        Some(Ok(Some(false))) => {
+            CheckResult::fail("token", "set, but lacks push access to the repository")
+        }
+        Some(Ok(None)) | None => CheckResult::pass("token", "set (permissions not checked)"),
+        Some(Err(e)) => CheckResult::fail("token", e.to_string()),
+    }
+}
+
+/// Configured version files and the changelog file.
+///
+/// A missing or unreadable version file fails the release; a missing changelog
+/// only warns, since `sr` creates it on the first release.
+pub fn files(config: &ReleaseConfig) -> Vec<CheckResult> {
+    let mut checks = Vec::new();
+    for file in &config.version_files {
+        let path = Path::new(file);
+        let check = if !path.exists() {
+            CheckResult::fail("version file", format!("{file} not found"))
+        } else {
+            match read_version_file(path) {
+                Ok(Some(version)) => {
+                    CheckResult::pass("version file", format!("{file} ({version})"))
+                }
+                Ok(None) => CheckResult::warn("version file", format!("{file} has no version")),
+                Err(e) => CheckResult::fail("version file", e.to_string()),
+            }
+        };
+        checks.push(check);
+    }
+    if let Some(ref file) = config.changelog.file {
+        let path = Path::new(file);
+        let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+        let check = if path.exists() {
+            CheckResult::pass("changelog", file.as_str())
+        } else if parent.is_some_and(|p| !p.exists()) {
+            CheckResult::fail("changelog", format!("directory for {file} does not exist"))
+        } else {
+            CheckResult::warn("changelog", format!("{file} will be created"))
+        };
+        checks.push(check);
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::Version;
+
+    fn tag(name: &str, version: &str) -> TagInfo {
+        TagInfo {
+            name: name.into(),
+            version: Version::parse(version).unwrap(),
+            sha: "abc".into(),
+        }
+    }
+
+    #[test]
+    fn git_binary_fails_when_missing() {
+        assert_eq!(
+            git_binary(Some("git version 2.43.0")).status,
+            CheckStatus::Pass
+        );
+        assert_eq!(git_binary(None).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn remote_reports_parsed_origin() {
+        let check = remote(&Ok(("github.com".into(), "o".into(), "r".into())));
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert_eq!(check.detail, "github.com/o/r");
+
+        let check = remote(&Err(ReleaseError::Git("no origin".into())));
+        assert_eq!(check.status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn shallow_clone_warns() {
+        assert_eq!(history(Ok(false)).status, CheckStatus::Pass);
+        assert_eq!(history(Ok(true)).status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn tags_report_latest_or_warn_when_none() {
+        let check = tags(
+            Ok(vec![tag("v1.2.0", "1.2.0"), tag("v1.10.0", "1.10.0")]),
+            "v",
+        );
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert_eq!(check.detail, "2 tag(s), latest v1.10.0");
+
+        assert_eq!(tags(Ok(vec![]), "'v'").status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn branch_checks_release_branches() {
+        let branches = vec!["main".to_string(), "release/*".to_string()];
+        assert_eq!(
+            branch(Ok(Some("release/1.x".into())), None, &branches).status,
+            CheckStatus::Pass
+        );
+        assert_eq!(
+            branch(Ok(Some("feature".into())), None, &branches).status,
+            CheckStatus::Fail
+        );
+        assert_eq!(
+            branch(Ok(None), Some("main"), &branches).status,
+            CheckStatus::Pass
+        );
+        assert_eq!(branch(Ok(None), None, &[]).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn default_branch_reports_its_source() {
+        use crate::release::DefaultBranchSource;
+
+        let check = default_branch(&Ok(Some(DefaultBranch {
+            name: "trunk".into(),
+            source: DefaultBranchSource::OriginHead,
+        })));
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert_eq!(check.detail, "trunk (from origin/HEAD)");
+
+        let check = default_branch(&Ok(Some(DefaultBranch {
+            name: "develop".into(),
+            source: DefaultBranchSource::Provider,
+        })));
+        assert_eq!(check.detail, "develop (from the provider API)");
+        assert_eq!(default_branch(&Ok(None)).status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn dirty_working_tree_warns() {
+        assert_eq!(working_tree(Ok(vec![])).status, CheckStatus::Pass);
+        let check = working_tree(Ok(vec!["a.rs".into(), "b.rs".into()]));
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert_eq!(check.detail, "2 uncommitted change(s): a.rs, b.rs");
+    }
+
+    #[test]
+    fn token_checks_presence_and_push_access() {
+        assert_eq!(token(false, false, None).status, CheckStatus::Pass);
+        assert_eq!(token(false, true, None).status, CheckStatus::Fail);
+        assert_eq!(
+            token(true, true, Some(Ok(Some(true)))).status,
+            CheckStatus::Pass
+        );
+        assert_eq!(
+            token(true, true, Some(Ok(Some(false)))).status,
+            CheckStatus::Fail
+        );
+        assert_eq!(token(true, true, Some(Ok(None))).status, CheckStatus::Pass);
+        assert_eq!(
+            token(true, true, Some(Err(ReleaseError::Vcs("401".into())))).status,
+            CheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn files_fail_for_missing_version_file_and_warn_for_missing_changelog() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo = dir.path().join("Cargo.toml");
+        std::fs::write(&cargo, "[package]\nname = \"x\"\nversion = \"0.3.1\"\n").unwrap();
+        let config = ReleaseConfig {
+            version_files: vec![
+                cargo.to_string_lossy().into_owned(),
+                dir.path()
+                    .join("missing.json")
+                    .to_string_lossy()
+                    .into_owned(),
+            ],
+            changelog: crate::config::ChangelogConfig {
+                file: Some(
+                    dir.path()
+                        .join("CHANGELOG.md")
+                        .to_string_lossy()
+                        .into_owned(),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let checks = files(&config);
+        let statuses: Vec<_> = checks.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![CheckStatus::Pass, CheckStatus::Fail, CheckStatus::Warn]
+        );
+        assert!(checks[0].detail.ends_with("(0.3.1)"));
+    }
+
+    #[test]
+    fn table_aligns_names() {
+        let table = render_table(
+            &[
+                CheckResult::pass("git", "git version 2.43.0"),
+                CheckResult::warn("working tree", "1 uncommitted change(s): a"),
+            ],
+            &|_, status| status.to_string(),
+        );
+        assert_eq!(
+            table,
+            "PASS  git           git version 2.43.0\n\
+             WARN  working tree  1 uncommitted change(s): a\n"
+        );
+    }
+}
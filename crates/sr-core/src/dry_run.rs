@@ -0,0 +1,331 @@
+//! Structured description of what `sr release --dry-run` would do.
+//!
+//! The report is built once by the release strategy and rendered either as JSON
+//! (`--format json`) or as the human-readable `[dry-run]` log, so the two views
+//! cannot drift apart.
+
+use serde::Serialize;
+
+use crate::artifacts::PlannedArtifact;
+use crate::attestations::PlannedAttestation;
+use crate::config::HookCommand;
+use crate::publish::PublishCommand;
+use crate::release::ReleasePlan;
+
+/// Everything a dry-run release would do, in execution order.
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunReport {
+    pub plan: ReleasePlan,
+    /// Problems that would abort (or be warned about in) a real release.
+    pub warnings: Vec<String>,
+    /// Problems that would make the real release fail after the build, such
+    /// as required artifacts that match no files.
+    pub errors: Vec<String>,
+    /// `--tag-only`: no version files, changelog file, or release commit.
+    pub tag_only: bool,
+    /// `--no-push`: nothing is pushed and no remote release is created.
+    pub no_push: bool,
+    pub pre_release_command: Option<HookCommand>,
+    /// Whether the release commit would be signed.
+    pub sign_commit: bool,
+    /// Signing key, when one is configured.
+    pub signing_key: Option<String>,
+    /// Whether the release lock would be taken on origin.
+    pub lock: bool,
+    pub tag: TagAction,
+    pub floating_tag: Option<TagAction>,
+    /// Notes ref the tagged commit would be annotated in (`git.release_notes_ref`).
+    pub notes_ref: Option<String>,
+    /// Remote release, or `None` when it would be skipped.
+    pub release: Option<ReleaseAction>,
+    /// Commands to publish a `--no-push` release later.
+    pub push_later: Vec<String>,
+    pub version_files: Vec<VersionFileChange>,
+    pub artifacts: Vec<Artifact>,
+    /// Archives that would be built and files that would be downloaded, then
+    /// uploaded with the artifacts.
+    pub built_artifacts: Vec<PlannedArtifact>,
+    /// Attestation files generated after the build and uploaded with the artifacts.
+    pub attestations: Vec<PlannedAttestation>,
+    /// Whether artifact digests would be appended to the release body.
+    pub embed_digests: bool,
+    pub build_command: Option<HookCommand>,
+    pub stage_files: Vec<String>,
+    pub publish: Vec<PublishCommand>,
+    pub post_release_command: Option<HookCommand>,
+    pub notifications: Vec<NotificationTarget>,
+    pub changelog: String,
+}
+
+/// A tag that would be created (or moved) and possibly pushed.
+#[derive(Debug, Clone, Serialize)]
+pub struct TagAction {
+    pub name: String,
+    pub signed: bool,
+    pub push: bool,
+    /// The tag is already on origin, so the push would be skipped. Only
+    /// checked when a provider is configured and sr is not offline.
+    pub on_remote: bool,
+}
+
+/// The remote release that would be created or updated.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseAction {
+    pub name: String,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub providers: Vec<ProviderAction>,
+    /// The release body, rendered with `release.sections` and `release.compare_link`.
+    pub notes: String,
+    /// Curated notes file the body comes from (`release.notes_file_template`);
+    /// `None` = generated from commits.
+    pub notes_file: Option<String>,
+}
+
+/// What a single provider would do with the release.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderAction {
+    pub provider: String,
+    pub action: ReleaseActionKind,
+    /// Assets already on the existing release that the upload would replace.
+    pub replaced_assets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseActionKind {
+    Create,
+    Update,
+}
+
+/// A version file that would be bumped.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionFileChange {
+    pub path: String,
+    /// Version currently in the file; `None` when it has none or cannot be read.
+    pub old_version: Option<String>,
+    pub new_version: String,
+    /// Download URL the manifest would point at, from `version_file_urls`.
+    pub url: Option<String>,
+}
+
+/// An artifact that would be uploaded.
+#[derive(Debug, Clone, Serialize)]
+pub struct Artifact {
+    pub path: String,
+    pub size: u64,
+}
+
+/// A notification that would be sent; the target is redacted.
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationTarget {
+    pub kind: String,
+    pub target: String,
+}
+
+impl DryRunReport {
+    /// Render the report as `[dry-run]`-prefixed log lines.
+    pub fn render_human(&self) -> String {
+        let mut lines = Vec::new();
+        for error in &self.errors {
+            lines.push(format!("error: {error}"));
+        }
+        for warning in &self.warnings {
+            lines.push(format!("warning: {warning}"));
+        }
+        if self.plan.forced {
+            lines.push(format!(
+                "Re-releasing existing tag {} (forced)",
+                self.plan.tag_name
+            ));
+        }
+        if let Some(ref cmd) = self.pre_release_command {
+            lines.push(format!("Would run pre-release command: {cmd}"));
+        }
+        let key_label = self
+            .signing_key
+            .as_deref()
+            .map(|k| format!(" with key {k}"))
+            .unwrap_or_default();
+        if self.tag_only {
+            lines.push(
+                "Tag only: would skip version files, the changelog file, and the release commit"
+                    .into(),
+            );
+        } else if self.sign_commit {
+            lines.push(format!("Would sign the release commit{key_label}"));
+        }
+        if self.lock {
+            lines.push(format!(
+                "Would take release lock {} on origin",
+                crate::release::LOCK_REF
+            ));
+        }
+        let sign_label = |tag: &TagAction| {
+            if tag.signed {
+                format!(" (signed{key_label})")
+            } else {
+                String::new()
+            }
+        };
+        lines.push(format!(
+            "Would create tag: {}{}",
+            self.tag.name,
+            sign_label(&self.tag)
+        ));
+        if self.tag.push && self.tag.on_remote {
+            lines.push(format!(
+                "Tag {} is already on origin: would skip pushing it",
+                self.tag.name
+            ));
+        } else if self.tag.push {
+            lines.push(format!("Would push tag: {}", self.tag.name));
+        }
+        if let Some(ref floating) = self.floating_tag {
+            lines.push(format!(
+                "Would create/update floating tag: {}{}",
+                floating.name,
+                sign_label(floating)
+            ));
+            if floating.push {
+                lines.push(format!("Would force-push floating tag: {}", floating.name));
+            }
+        }
+        if let Some(ref notes_ref) = self.notes_ref {
+            let push = if self.no_push { "" } else { " and push it" };
+            lines.push(format!(
+                "Would add a release note to {notes_ref} on the tagged commit{push}"
+            ));
+        }
+        if self.no_push {
+            lines.push("--no-push: would push nothing and skip the remote release".into());
+            lines.push("To publish it later, run:".into());
+            lines.extend(self.push_later.iter().map(|cmd| format!("  {cmd}")));
+        } else if let Some(ref release) = self.release {
+            let label = match (release.draft, release.prerelease) {
+                (true, true) => " (draft, prerelease)",
+                (true, false) => " (draft)",
+                (false, true) => " (prerelease)",
+                (false, false) => "",
+            };
+            lines.push(format!(
+                "Would publish release \"{}\" for {}{label} on {} provider(s):",
+                release.name,
+                self.tag.name,
+                release.providers.len()
+            ));
+            for p in &release.providers {
+                let action = match p.action {
+                    ReleaseActionKind::Create => "create",
+                    ReleaseActionKind::Update => "update existing release",
+                };
+                if p.replaced_assets.is_empty() {
+                    lines.push(format!("  {}: {action}", p.provider));
+                } else {
+                    lines.push(format!(
+                        "  {}: {action}, replacing {} asset(s): {}",
+                        p.provider,
+                        p.replaced_assets.len(),
+                        p.replaced_assets.join(", ")
+                    ));
+                }
+            }
+            match &release.notes_file {
+                Some(file) => lines.push(format!("  notes: {file}")),
+                None => lines.push("  notes: generated from commits".into()),
+            }
+        } else {
+            lines.push("No VCS provider: would skip remote release and asset upload".into());
+        }
+        for file in &self.version_files {
+            let old = file.old_version.as_deref().unwrap_or("?");
+            lines.push(format!(
+                "Would bump version in: {} ({old} -> {})",
+                file.path, file.new_version
+            ));
+            if let Some(ref url) = file.url {
+                lines.push(format!("  and point its download URL at {url}"));
+            }
+        }
+        if !self.no_push && !self.artifacts.is_empty() {
+            lines.push(format!(
+                "Would upload {} artifact(s):",
+                self.artifacts.len()
+            ));
+            for a in &self.artifacts {
+                lines.push(format!("  {} ({} bytes)", a.path, a.size));
+            }
+        }
+        for a in &self.built_artifacts {
+            match a {
+                PlannedArtifact::Archive { name, files, .. } => lines.push(format!(
+                    "Would build and upload archive {name} from {} file(s)",
+                    files.len()
+                )),
+                PlannedArtifact::Download {
+                    name,
+                    url,
+                    token_env,
+                } => {
+                    let auth = token_env
+                        .as_ref()
+                        .map(|var| format!(" (token from ${var})"))
+                        .unwrap_or_default();
+                    lines.push(format!(
+                        "Would download {url}{auth} and upload it as {name}"
+                    ));
+                }
+                PlannedArtifact::Renamed { name, path } => {
+                    lines.push(format!("Would upload {path} as {name}"))
+                }
+            }
+        }
+        if let Some(ref cmd) = self.build_command {
+            lines.push(format!("Would run build command: {cmd}"));
+        }
+        for a in &self.attestations {
+            lines.push(format!(
+                "Would generate attestation: {} (from `{}`)",
+                a.file, a.run
+            ));
+        }
+        if self.embed_digests {
+            lines.push("Would embed artifact digests in the release body".into());
+        }
+        if !self.stage_files.is_empty() {
+            lines.push(format!(
+                "Would stage additional files: {}",
+                self.stage_files.join(", ")
+            ));
+        }
+        if !self.publish.is_empty() {
+            lines.push("Would publish:".into());
+            for cmd in &self.publish {
+                match cmd.cwd {
+                    Some(ref dir) => lines.push(format!("  {}   # in {dir}", cmd.run)),
+                    None => lines.push(format!("  {}", cmd.run)),
+                }
+            }
+        }
+        if let Some(ref cmd) = self.post_release_command {
+            lines.push(format!("Would run post-release command: {cmd}"));
+        }
+        for n in &self.notifications {
+            lines.push(format!(
+                "Would send {} notification to {}",
+                n.kind, n.target
+            ));
+        }
+        lines.push(format!("Changelog:\n{}", self.changelog));
+        if let Some(release) = &self.release
+            && release.notes != self.changelog
+        {
+            lines.push(format!("Release notes:\n{}", release.notes));
+        }
+
+        lines
+            .iter()
+            .map(|line| format!("[dry-run] {line}\n"))
+            .collect()
+    }
+}
@@ -1,5 +1,10 @@
+use std::error::Error as StdError;
+
 use thiserror::Error;
 
+/// A boxed error from an underlying library (gix, ureq, serde), kept as the `source`.
+pub type BoxError = Box<dyn StdError + Send + Sync>;
+
 #[derive(Debug, Error)]
 pub enum ReleaseError {
     #[error("no commits found since tag {tag} ({sha})")]
@@ -10,15 +15,74 @@ pub enum ReleaseError {
     )]
     NoBump { tag: String, commit_count: usize },
 
+    /// Releasable changes exist, but the `policy` thresholds are not met yet.
+    #[error("release deferred by policy: {reason}")]
+    Deferred { reason: String },
+
+    /// No tag under the given prefixes: nothing has been released yet.
+    #[error("no release tag found with prefix {prefixes}")]
+    NoTag { prefixes: String },
+
+    /// A version checked against the latest release is not newer than it.
+    #[error("{version} is not newer than the latest release {latest}")]
+    NotNewer { version: String, latest: String },
+
     #[error("configuration error: {0}")]
     Config(String),
 
     #[error("git error: {0}")]
     Git(String),
 
+    /// A `git` subprocess failed. `status` is `None` when git could not be run
+    /// at all, in which case `source` holds the spawn error.
+    #[error("git error: {}", git_command_message(.args, .stderr, .source.as_ref()))]
+    GitCommand {
+        args: Vec<String>,
+        stderr: String,
+        status: Option<i32>,
+        #[source]
+        source: Option<std::io::Error>,
+    },
+
+    /// A git library call (the gix backend) failed.
+    #[error("git error: {context}: {source}")]
+    GitLibrary {
+        context: String,
+        #[source]
+        source: BoxError,
+    },
+
+    #[error(
+        "HEAD moved from {planned} to {actual} since the release was planned; re-run sr or pass --ignore-head-drift"
+    )]
+    HeadMoved { planned: String, actual: String },
+
+    #[error("release conflict: {0}")]
+    Conflict(String),
+
+    #[error("branch check failed: {0}")]
+    Branch(String),
+
+    /// `require_ci` is set and none of the CI variables in `checked` is set.
+    #[error(
+        "require_ci: no CI environment detected (checked {checked}); release from CI or pass --allow-local"
+    )]
+    NotCi { checked: String },
+
     #[error("vcs provider error: {0}")]
     Vcs(String),
 
+    /// An HTTP request to the VCS provider failed. `status` is the response
+    /// status, or `None` when no response arrived (DNS, TLS, connection errors).
+    #[error("vcs provider error: {method} {url}: {source}")]
+    Http {
+        method: String,
+        url: String,
+        status: Option<u16>,
+        #[source]
+        source: BoxError,
+    },
+
     #[error("changelog error: {0}")]
     Changelog(String),
 
@@ -28,9 +92,26 @@ pub enum ReleaseError {
     #[error("build command failed: {0}")]
     BuildCommand(String),
 
+    /// Required release artifacts are missing, or two would be uploaded under
+    /// one name.
+    #[error("artifacts: {0}")]
+    Artifacts(String),
+
     #[error("hook failed: {0}")]
     Hook(String),
 
+    #[error("publish failed: {0}")]
+    Publish(String),
+
+    #[error("notification failed: {0}")]
+    Notify(String),
+
+    #[error("{0}")]
+    CommitMessage(String),
+
+    #[error("internal error: network access attempted in offline mode: {0}")]
+    Offline(String),
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -38,3 +119,96 @@ pub enum ReleaseError {
 // Keep anyhow available for conversions even though it's pulled transitively through thiserror.
 // sr-core re-exports it so downstream crates don't need a direct dep.
 pub use anyhow;
+
+impl ReleaseError {
+    /// The HTTP status of a failed provider request, if a response arrived.
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            ReleaseError::Http { status, .. } => *status,
+            _ => None,
+        }
+    }
+
+    /// Whether retrying the same request may succeed: no response at all, a
+    /// server error, or rate limiting.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ReleaseError::Http { status: None, .. } => true,
+            ReleaseError::Http {
+                status: Some(status),
+                ..
+            } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+fn git_command_message(args: &[String], stderr: &str, source: Option<&std::io::Error>) -> String {
+    match source {
+        Some(e) => format!("failed to run git {}: {e}", args.join(" ")),
+        None => format!("git {} failed: {stderr}", args.join(" ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http(status: Option<u16>) -> ReleaseError {
+        ReleaseError::Http {
+            method: "GET".into(),
+            url: "https://api.github.com/repos/o/r".into(),
+            status,
+            source: "boom".into(),
+        }
+    }
+
+    #[test]
+    fn git_command_display_and_source() {
+        let failed = ReleaseError::GitCommand {
+            args: vec!["push".into(), "origin".into(), "v1.0.0".into()],
+            stderr: "rejected".into(),
+            status: Some(1),
+            source: None,
+        };
+        assert_eq!(
+            failed.to_string(),
+            "git error: git push origin v1.0.0 failed: rejected"
+        );
+        assert!(failed.source().is_none());
+
+        let spawn = ReleaseError::GitCommand {
+            args: vec!["status".into()],
+            stderr: String::new(),
+            status: None,
+            source: Some(std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+        assert!(
+            spawn
+                .to_string()
+                .starts_with("git error: failed to run git status: ")
+        );
+        let io = spawn.source().unwrap().downcast_ref::<std::io::Error>();
+        assert_eq!(io.unwrap().kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn http_error_exposes_status_and_source() {
+        let err = http(Some(404));
+        assert_eq!(
+            err.to_string(),
+            "vcs provider error: GET https://api.github.com/repos/o/r: boom"
+        );
+        assert_eq!(err.http_status(), Some(404));
+        assert_eq!(err.source().unwrap().to_string(), "boom");
+    }
+
+    #[test]
+    fn transient_errors() {
+        assert!(http(None).is_transient());
+        assert!(http(Some(502)).is_transient());
+        assert!(http(Some(429)).is_transient());
+        assert!(!http(Some(404)).is_transient());
+        assert!(!ReleaseError::Vcs("x".into()).is_transient());
+    }
+}
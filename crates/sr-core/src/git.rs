@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use semver::Version;
 
 use crate::commit::Commit;
@@ -11,10 +13,69 @@ pub struct TagInfo {
     pub sha: String,
 }
 
+/// The version in tag `name` under the first of `prefixes` it parses with, and
+/// that prefix's index. Trying each prefix in turn keeps `v` from swallowing a
+/// `version-1.0.0` tag that a later `version-` prefix would read. With
+/// `lenient`, `v1.2` reads as `1.2.0`.
+pub fn parse_prefixed_tag(
+    name: &str,
+    prefixes: &[&str],
+    lenient: bool,
+) -> Option<(usize, Version)> {
+    let parse = if lenient {
+        crate::version::parse_tag_version_lenient
+    } else {
+        crate::version::parse_tag_version
+    };
+    prefixes.iter().enumerate().find_map(|(i, prefix)| {
+        let version = parse(name.strip_prefix(prefix)?)?;
+        Some((i, version))
+    })
+}
+
+/// Tag `name` without the path a monorepo prefix puts in front of it, for
+/// release titles: `pkg/foo-v1.2.3` shows as `foo-v1.2.3`. When the last path
+/// segment is only the version, the directory above it names the package:
+/// `crates/sr-core/v1.2.3` shows as `sr-core v1.2.3`.
+pub fn tag_display_name(name: &str) -> String {
+    let Some((path, last)) = name.rsplit_once('/') else {
+        return name.to_string();
+    };
+    let bare_version = last
+        .strip_prefix('v')
+        .unwrap_or(last)
+        .starts_with(|c: char| c.is_ascii_digit());
+    match path.rsplit('/').next() {
+        Some(package) if bare_version && !package.is_empty() => format!("{package} {last}"),
+        _ => last.to_string(),
+    }
+}
+
+/// Tags found under `prefixes` (each with the index of its prefix), sorted by
+/// version ascending. When two tags share a version the one under the earlier
+/// prefix sorts last, so it wins as the latest tag.
+pub fn sort_tags(mut tags: Vec<(usize, TagInfo)>) -> Vec<TagInfo> {
+    tags.sort_by(|(a_rank, a), (b_rank, b)| a.version.cmp(&b.version).then(b_rank.cmp(a_rank)));
+    tags.into_iter().map(|(_, tag)| tag).collect()
+}
+
+/// `message` followed by a blank line and one `Key: value` line per trailer,
+/// the layout `git interpret-trailers` recognizes.
+pub fn append_trailers(message: &str, trailers: &[(String, String)]) -> String {
+    if trailers.is_empty() {
+        return message.to_string();
+    }
+    let block: Vec<String> = trailers
+        .iter()
+        .map(|(key, value)| format!("{key}: {value}"))
+        .collect();
+    format!("{}\n\n{}", message.trim_end(), block.join("\n"))
+}
+
 /// Abstraction over git operations.
 pub trait GitRepository: Send + Sync {
-    /// Find the latest semver tag matching the configured prefix.
-    fn latest_tag(&self, prefix: &str) -> Result<Option<TagInfo>, ReleaseError>;
+    /// Find the latest semver tag under any of `prefixes`.
+    fn latest_tag(&self, prefixes: &[&str]) -> Result<Option<TagInfo>, ReleaseError>;
 
     /// List commits between a starting point (exclusive) and HEAD (inclusive).
     /// If `from` is `None`, returns all commits reachable from HEAD.
@@ -29,43 +90,165 @@ pub trait GitRepository: Send + Sync {
     /// Stage files and commit. Returns Ok(false) if nothing to commit.
     fn stage_and_commit(&self, paths: &[&str], message: &str) -> Result<bool, ReleaseError>;
 
+    /// Like `stage_and_commit`, adding `trailers` as `Key: value` git trailers.
+    /// The default appends them to the message as a trailer block.
+    fn stage_and_commit_with_trailers(
+        &self,
+        paths: &[&str],
+        message: &str,
+        trailers: &[(String, String)],
+    ) -> Result<bool, ReleaseError> {
+        self.stage_and_commit(paths, &append_trailers(message, trailers))
+    }
+
     /// Push current branch to origin.
     fn push(&self) -> Result<(), ReleaseError>;
 
+    /// Push HEAD to `branch` on origin (`HEAD:refs/heads/<branch>`), for a
+    /// detached HEAD that has no branch of its own.
+    fn push_to_branch(&self, branch: &str) -> Result<(), ReleaseError>;
+
     /// Check if a tag exists locally.
     fn tag_exists(&self, name: &str) -> Result<bool, ReleaseError>;
 
     /// Check if a tag exists on the remote.
     fn remote_tag_exists(&self, name: &str) -> Result<bool, ReleaseError>;
 
-    /// List all semver tags matching prefix, sorted by version ascending.
-    fn all_tags(&self, prefix: &str) -> Result<Vec<TagInfo>, ReleaseError>;
+    /// List all semver tags under any of `prefixes`, sorted by version
+    /// ascending (see [`sort_tags`] for ties across prefixes).
+    fn all_tags(&self, prefixes: &[&str]) -> Result<Vec<TagInfo>, ReleaseError>;
+
+    /// Like `all_tags`, but only tags whose commit is reachable from `rev`.
+    fn tags_reachable(&self, prefixes: &[&str], rev: &str) -> Result<Vec<TagInfo>, ReleaseError>;
+
+    /// Find the latest semver tag under any of `prefixes` that is reachable from `rev`.
+    fn latest_tag_reachable(
+        &self,
+        prefixes: &[&str],
+        rev: &str,
+    ) -> Result<Option<TagInfo>, ReleaseError> {
+        Ok(self.tags_reachable(prefixes, rev)?.pop())
+    }
+
+    /// Full SHA of the commit `rev` (tag, branch, or SHA) points to.
+    /// Fails with a readable error when `rev` does not name a commit.
+    fn resolve_rev(&self, rev: &str) -> Result<String, ReleaseError>;
 
     /// List commits between two refs (exclusive `from`, inclusive `to`).
     /// If `from` is None, returns all commits reachable from `to`.
     fn commits_between(&self, from: Option<&str>, to: &str) -> Result<Vec<Commit>, ReleaseError>;
 
-    /// Get the date (YYYY-MM-DD) of the commit a tag points to.
-    fn tag_date(&self, tag_name: &str) -> Result<String, ReleaseError>;
+    /// Commit time (Unix seconds) of the commit a tag points to.
+    fn tag_timestamp(&self, tag_name: &str) -> Result<i64, ReleaseError>;
+
+    /// Message of annotated tag `name`, without any signature; `None` for a
+    /// lightweight tag. Fails if the tag does not exist.
+    fn tag_message(&self, name: &str) -> Result<Option<String>, ReleaseError>;
+
+    /// Commit time (Unix seconds) of commit `sha`.
+    fn commit_date(&self, sha: &str) -> Result<i64, ReleaseError>;
 
     /// Force-create a lightweight tag at HEAD, overwriting if it already exists.
     fn force_create_tag(&self, name: &str) -> Result<(), ReleaseError>;
 
+    /// Force-create a lightweight tag at `target` (any revision), overwriting if it exists.
+    fn force_create_tag_at(&self, name: &str, target: &str) -> Result<(), ReleaseError>;
+
+    /// Delete a local tag. Returns `Ok(false)` if it did not exist.
+    fn delete_tag(&self, name: &str) -> Result<bool, ReleaseError>;
+
+    /// Delete a tag on the remote. Returns `Ok(false)` if it did not exist there.
+    fn delete_remote_tag(&self, name: &str) -> Result<bool, ReleaseError>;
+
+    /// Create a commit reverting `sha` on the current branch.
+    fn revert_commit(&self, sha: &str) -> Result<(), ReleaseError>;
+
+    /// Read `refname` on the remote: its commit SHA and that commit's message,
+    /// or `None` if the ref does not exist.
+    fn remote_ref(&self, refname: &str) -> Result<Option<(String, String)>, ReleaseError>;
+
+    /// Atomically create `refname` on the remote, pointing at a new commit that
+    /// carries `message`. Returns the commit SHA, or `None` if the ref already exists.
+    fn create_remote_ref(
+        &self,
+        refname: &str,
+        message: &str,
+    ) -> Result<Option<String>, ReleaseError>;
+
+    /// Delete `refname` on the remote, provided it still points at `expected_sha`.
+    fn delete_remote_ref(&self, refname: &str, expected_sha: &str) -> Result<(), ReleaseError>;
+
     /// Force-push a tag to the remote, overwriting the remote tag if it exists.
     fn force_push_tag(&self, name: &str) -> Result<(), ReleaseError>;
 
+    /// Attach `content` as the note on commit `sha` under `notes_ref`
+    /// (e.g. `refs/notes/releases`), replacing any note already there.
+    fn add_note(&self, notes_ref: &str, sha: &str, content: &str) -> Result<(), ReleaseError>;
+
+    /// Push `notes_ref` to the remote.
+    fn push_notes(&self, notes_ref: &str) -> Result<(), ReleaseError>;
+
     /// Return the full SHA of HEAD.
     fn head_sha(&self) -> Result<String, ReleaseError>;
 
+    /// Tracked paths (relative to the repo root) with staged or unstaged changes.
+    /// Untracked files are not included.
+    fn dirty_paths(&self) -> Result<Vec<String>, ReleaseError>;
+
+    /// Root of the work tree. Fails for a bare repository.
+    fn workdir(&self) -> Result<PathBuf, ReleaseError>;
+
+    /// Return the short name of the checked-out branch, or `None` for a detached HEAD.
+    fn current_branch(&self) -> Result<Option<String>, ReleaseError>;
+
+    /// The branch `refs/remotes/origin/HEAD` points to (e.g. `main`), or `None`
+    /// when it is unset, as after `git remote add` without `git remote set-head`.
+    fn default_branch(&self) -> Result<Option<String>, ReleaseError>;
+
+    /// Check if the repository is a shallow clone.
+    fn is_shallow(&self) -> Result<bool, ReleaseError>;
+
+    /// Fetch tags from origin, unshallowing the repository if it is a shallow clone.
+    /// Fails if no `origin` remote is configured.
+    fn fetch_tags(&self) -> Result<(), ReleaseError>;
+
+    /// Fail when this repository has no way to reach its remotes (push, fetch),
+    /// so a release can stop before it changes anything. Default: always able.
+    fn check_remote_access(&self) -> Result<(), ReleaseError> {
+        Ok(())
+    }
+
+    /// Like `commits_since`, but only includes commits that touched files matching
+    /// the git pathspecs in `paths` (e.g. `crates/core`, `*.rs`, `:(exclude)docs`).
+    /// A commit touching only excluded paths is not returned.
+    fn commits_since_paths(
+        &self,
+        from: Option<&str>,
+        paths: &[&str],
+    ) -> Result<Vec<Commit>, ReleaseError> {
+        // Default: ignore path filter (for test fakes and backwards compat)
+        let _ = paths;
+        self.commits_since(from)
+    }
+
+    /// Like `commits_between`, but only includes commits that touched files matching `paths`.
+    fn commits_between_paths(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        paths: &[&str],
+    ) -> Result<Vec<Commit>, ReleaseError> {
+        let _ = paths;
+        self.commits_between(from, to)
+    }
+
     /// Like `commits_since`, but only includes commits that touched files under `path`.
     fn commits_since_in_path(
         &self,
         from: Option<&str>,
         path: &str,
     ) -> Result<Vec<Commit>, ReleaseError> {
-        // Default: ignore path filter (for test fakes and backwards compat)
-        let _ = path;
-        self.commits_since(from)
+        self.commits_since_paths(from, &[path])
     }
 
     /// Like `commits_between`, but only includes commits that touched files under `path`.
@@ -75,7 +258,6 @@ pub trait GitRepository: Send + Sync {
         to: &str,
         path: &str,
     ) -> Result<Vec<Commit>, ReleaseError> {
-        let _ = path;
-        self.commits_between(from, to)
+        self.commits_between_paths(from, to, &[path])
     }
 }
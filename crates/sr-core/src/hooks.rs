@@ -7,7 +7,9 @@ use std::collections::BTreeSet;
 use std::hash::{Hash, Hasher};
 use std::path::Path;
 
-use crate::config::{DEFAULT_CONFIG_FILE, HookEntry, HooksConfig, ReleaseConfig};
+use tracing::{info, warn};
+
+use crate::config::{DEFAULT_CONFIG_FILE, HookCommand, HookEntry, HooksConfig, ReleaseConfig};
 use crate::error::ReleaseError;
 
 /// Marker comment embedded in generated hook scripts to identify sr-managed hooks.
@@ -79,7 +81,7 @@ pub fn sync_hooks(
                         "failed to backup .githooks/{hook_name}: {e}"
                     ))
                 })?;
-                eprintln!("backed up .githooks/{hook_name} → .githooks/{hook_name}.bak");
+                info!("backed up .githooks/{hook_name} → .githooks/{hook_name}.bak");
                 write_shim(&hook_path, &expected)?;
                 changed = true;
             }
@@ -151,7 +153,7 @@ fn write_shim(path: &Path, content: &str) -> Result<(), crate::error::ReleaseErr
     }
 
     if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-        eprintln!("synced .githooks/{name}");
+        info!("synced .githooks/{name}");
     }
 
     Ok(())
@@ -200,7 +202,7 @@ fn remove_stale_hooks(
                     "failed to remove .githooks/{name}: {e}"
                 ))
             })?;
-            eprintln!("removed stale .githooks/{name}");
+            info!("removed stale .githooks/{name}");
             removed = true;
         }
     }
@@ -215,6 +217,53 @@ fn is_sr_managed(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Marker identifying a standalone hook written by `sr init --hooks`.
+const INSTALLED_MARKER: &str = "# Installed by sr init --hooks";
+
+/// Standalone `commit-msg` hook that validates the message with `sr check`.
+/// Also usable as-is as a husky hook.
+pub fn commit_msg_hook_script() -> String {
+    format!(
+        "#!/usr/bin/env sh\n\
+         {INSTALLED_MARKER} — validates commit messages against {DEFAULT_CONFIG_FILE}.\n\
+         exec sr check --message-file \"$1\"\n"
+    )
+}
+
+/// Write the `commit-msg` hook into `hooks_dir` and return its path. A hook that
+/// `sr init --hooks` did not write is only replaced with `force`, after backing
+/// it up to `commit-msg.bak`.
+pub fn install_commit_msg_hook(
+    hooks_dir: &Path,
+    force: bool,
+) -> Result<std::path::PathBuf, ReleaseError> {
+    let path = hooks_dir.join("commit-msg");
+    if let Ok(existing) = std::fs::read_to_string(&path)
+        && !existing.contains(INSTALLED_MARKER)
+    {
+        if !force {
+            return Err(ReleaseError::Config(format!(
+                "{} already exists (use --force to replace it; it is backed up to commit-msg.bak)",
+                path.display()
+            )));
+        }
+        std::fs::rename(&path, hooks_dir.join("commit-msg.bak"))
+            .map_err(|e| ReleaseError::Config(format!("failed to back up commit-msg: {e}")))?;
+    }
+    std::fs::create_dir_all(hooks_dir).map_err(|e| {
+        ReleaseError::Config(format!("failed to create {}: {e}", hooks_dir.display()))
+    })?;
+    std::fs::write(&path, commit_msg_hook_script())
+        .map_err(|e| ReleaseError::Config(format!("failed to write hook: {e}")))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| ReleaseError::Config(format!("failed to chmod hook: {e}")))?;
+    }
+    Ok(path)
+}
+
 /// Set `core.hooksPath` to `.githooks/`.
 fn set_hooks_path(repo_root: &Path) {
     let _ = std::process::Command::new("git")
@@ -235,33 +284,140 @@ pub fn run_shell(
     stdin_data: Option<&str>,
     env: &[(&str, &str)],
 ) -> Result<(), ReleaseError> {
-    let mut child = {
-        let mut builder = std::process::Command::new("sh");
-        builder.args(["-c", cmd]);
-        for &(k, v) in env {
-            builder.env(k, v);
-        }
-        if stdin_data.is_some() {
-            builder.stdin(std::process::Stdio::piped());
-        } else {
-            builder.stdin(std::process::Stdio::inherit());
+    spawn_and_wait(&HookCommand::from(cmd), None, stdin_data, env, None)
+}
+
+/// Run a configured hook command, replaying its output prefixed with `label`.
+///
+/// When the command has `continue_on_error` set, a failure is reported as a
+/// warning and pushed onto `failures` instead of being returned.
+pub fn run_hook_command(
+    hook: &HookCommand,
+    label: &str,
+    stdin_data: Option<&str>,
+    env: &[(&str, &str)],
+    failures: &mut Vec<String>,
+) -> Result<(), ReleaseError> {
+    match spawn_and_wait(hook, Some(label), stdin_data, env, None) {
+        Err(e) if hook.continue_on_error => {
+            warn!("{label}: {e} (continue_on_error is set)");
+            failures.push(format!("{label}: {e}"));
+            Ok(())
         }
-        builder
-            .spawn()
-            .map_err(|e| ReleaseError::Hook(format!("{cmd}: {e}")))?
-    };
+        result => result,
+    }
+}
+
+/// Run `hook` with its stdout written to `output` (e.g. an SBOM generator),
+/// replaying only stderr prefixed with `label`.
+pub fn run_hook_command_to_file(
+    hook: &HookCommand,
+    label: &str,
+    env: &[(&str, &str)],
+    output: &Path,
+) -> Result<(), ReleaseError> {
+    let file = std::fs::File::create(output)
+        .map_err(|e| ReleaseError::Hook(format!("{}: {e}", output.display())))?;
+    spawn_and_wait(hook, Some(label), None, env, Some(file))
+}
+
+/// Spawn `hook` under its shell and wait for it, enforcing the timeout.
+///
+/// With a `label`, stdout and stderr are captured and replayed line by line
+/// on stderr as `[label] ...`; otherwise the child inherits them. A
+/// `stdout_file` receives stdout instead.
+fn spawn_and_wait(
+    hook: &HookCommand,
+    label: Option<&str>,
+    stdin_data: Option<&str>,
+    env: &[(&str, &str)],
+    stdout_file: Option<std::fs::File>,
+) -> Result<(), ReleaseError> {
+    use std::process::Stdio;
+
+    let cmd = hook.run.as_str();
+    let shell = ShellInvocation::resolve(hook.shell.as_deref(), cfg!(windows), sh_available);
+    let program = &shell.program;
+    let mut builder = shell.command(cmd);
+    for &(k, v) in env {
+        builder.env(k, v);
+    }
+    if let Some(ref dir) = hook.cwd {
+        builder.current_dir(dir);
+    }
+    builder.stdin(if stdin_data.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::inherit()
+    });
+    if label.is_some() {
+        builder.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+    if let Some(file) = stdout_file {
+        builder.stdout(file);
+    }
+    // Run in its own process group so a timeout can take down everything it
+    // spawned. Only when a timeout is set: a separate group loses the terminal.
+    #[cfg(unix)]
+    if hook.timeout.is_some() {
+        use std::os::unix::process::CommandExt;
+        builder.process_group(0);
+    }
+
+    let mut child = builder
+        .spawn()
+        .map_err(|e| ReleaseError::Hook(format!("{cmd}: {program}: {e}")))?;
 
     if let Some(data) = stdin_data
-        && let Some(ref mut stdin) = child.stdin
+        && let Some(mut stdin) = child.stdin.take()
     {
         use std::io::Write;
         let _ = stdin.write_all(data.as_bytes());
     }
 
-    let status = child
-        .wait()
-        .map_err(|e| ReleaseError::Hook(format!("{cmd}: {e}")))?;
+    let replay: Vec<_> = match label {
+        Some(label) => [
+            child.stdout.take().map(|r| replay_lines(r, label)),
+            child.stderr.take().map(|r| replay_lines(r, label)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        None => Vec::new(),
+    };
 
+    let status = match hook.timeout {
+        Some(secs) => {
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(secs);
+            loop {
+                match child.try_wait() {
+                    Ok(Some(status)) => break Some(status),
+                    Ok(None) if std::time::Instant::now() >= deadline => {
+                        kill_process_tree(&mut child);
+                        break None;
+                    }
+                    Ok(None) => std::thread::sleep(std::time::Duration::from_millis(50)),
+                    Err(e) => return Err(ReleaseError::Hook(format!("{cmd}: {e}"))),
+                }
+            }
+        }
+        None => Some(
+            child
+                .wait()
+                .map_err(|e| ReleaseError::Hook(format!("{cmd}: {e}")))?,
+        ),
+    };
+
+    for handle in replay {
+        let _ = handle.join();
+    }
+
+    let Some(status) = status else {
+        return Err(ReleaseError::Hook(format!(
+            "{cmd} timed out after {}s",
+            hook.timeout.unwrap_or_default()
+        )));
+    };
     if !status.success() {
         let code = status.code().unwrap_or(1);
         return Err(ReleaseError::Hook(format!("{cmd} exited with code {code}")));
@@ -270,6 +426,110 @@ pub fn run_shell(
     Ok(())
 }
 
+/// How a command string is handed to a shell: `program flag <command>`.
+#[derive(Debug, PartialEq, Eq)]
+struct ShellInvocation {
+    program: String,
+    flag: &'static str,
+    /// Append the command to the command line verbatim. `cmd` parses its own
+    /// command line, so the quoting Windows applies to a single argument
+    /// would reach it as literal quotes.
+    raw: bool,
+}
+
+impl ShellInvocation {
+    /// Resolve `shell` (from the command's `shell:` option) for the platform.
+    ///
+    /// Defaults to `sh -c`; on Windows without `sh` on `PATH`, falls back to
+    /// `cmd /C`. `powershell` and `pwsh` take `-Command`. `sh_available` is
+    /// only consulted for the Windows fallback.
+    fn resolve(shell: Option<&str>, windows: bool, sh_available: impl FnOnce() -> bool) -> Self {
+        let program = match shell {
+            Some(shell) => shell.to_string(),
+            None if windows && !sh_available() => "cmd".to_string(),
+            None => "sh".to_string(),
+        };
+        let stem = Path::new(&program)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let flag = match stem.as_str() {
+            "cmd" => "/C",
+            "powershell" | "pwsh" => "-Command",
+            _ => "-c",
+        };
+        Self {
+            raw: windows && stem == "cmd",
+            program,
+            flag,
+        }
+    }
+
+    /// A `Command` running `cmd` through this shell.
+    fn command(&self, cmd: &str) -> std::process::Command {
+        let mut builder = std::process::Command::new(&self.program);
+        builder.arg(self.flag);
+        #[cfg(windows)]
+        if self.raw {
+            use std::os::windows::process::CommandExt;
+            builder.raw_arg(cmd);
+            return builder;
+        }
+        builder.arg(cmd);
+        builder
+    }
+}
+
+/// Whether a runnable `sh` is on `PATH` (checked once).
+fn sh_available() -> bool {
+    static AVAILABLE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        std::process::Command::new("sh")
+            .args(["-c", "exit 0"])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    })
+}
+
+/// Kill a timed-out child together with everything it spawned.
+fn kill_process_tree(child: &mut std::process::Child) {
+    let pid = child.id().to_string();
+    #[cfg(unix)]
+    let _ = std::process::Command::new("kill")
+        .args(["-KILL", "--", &format!("-{pid}")])
+        .stderr(std::process::Stdio::null())
+        .status();
+    #[cfg(windows)]
+    let _ = std::process::Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Forward each line read from `reader` to stderr, prefixed with `[label]`.
+fn replay_lines<R: std::io::Read + Send + 'static>(
+    reader: R,
+    label: &str,
+) -> std::thread::JoinHandle<()> {
+    use std::io::BufRead;
+    let prefix = format!("[{label}]");
+    std::thread::spawn(move || {
+        let mut reader = std::io::BufReader::new(reader);
+        let mut line = Vec::new();
+        while matches!(reader.read_until(b'\n', &mut line), Ok(n) if n > 0) {
+            let text = String::from_utf8_lossy(&line);
+            info!("{prefix} {}", text.trim_end_matches(['\r', '\n']));
+            line.clear();
+        }
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Hook execution
 // ---------------------------------------------------------------------------
@@ -384,7 +644,9 @@ fn match_files(files: &[String], patterns: &[String]) -> Vec<String> {
 
 /// Run all entries for a configured git hook.
 ///
-/// Simple entries run as shell commands with JSON context piped to stdin.
+/// Command entries run as shell commands with JSON context piped to stdin; their
+/// output is prefixed with the hook name, and failures of entries marked
+/// `continue_on_error` are reported without aborting the hook.
 /// Step entries match staged files against patterns and run rules only when matches exist.
 /// Rules containing `{files}` receive the matched file list.
 pub fn run_hook(
@@ -408,11 +670,12 @@ pub fn run_hook(
 
     // Lazily fetch staged files only when a Step entry needs them.
     let mut cached_staged: Option<Vec<String>> = None;
+    let mut failures = Vec::new();
 
     for entry in entries {
         match entry {
-            HookEntry::Simple(cmd) => {
-                run_shell(cmd, Some(&json_str), &[])?;
+            HookEntry::Command(cmd) => {
+                run_hook_command(cmd, hook_name, Some(&json_str), &[], &mut failures)?;
             }
             HookEntry::Step {
                 step,
@@ -428,13 +691,13 @@ pub fn run_hook(
                 };
 
                 if all_staged.is_empty() {
-                    eprintln!("{hook_name}: no staged files, skipping steps.");
+                    info!("{hook_name}: no staged files, skipping steps.");
                     break;
                 }
 
                 let matched = match_files(all_staged, patterns);
                 if matched.is_empty() {
-                    eprintln!("{hook_name} [{step}]: no files match {patterns:?}, skipping.");
+                    info!("{hook_name} [{step}]: no files match {patterns:?}, skipping.");
                     continue;
                 }
 
@@ -447,18 +710,24 @@ pub fn run_hook(
                         rule.clone()
                     };
 
-                    eprintln!("{hook_name} [{step}]: {cmd}");
+                    info!("{hook_name} [{step}]: {cmd}");
                     run_shell(&cmd, None, &[])?;
                 }
             }
         }
     }
 
+    if !failures.is_empty() {
+        warn!(
+            "{hook_name}: {} command(s) failed with continue_on_error set",
+            failures.len()
+        );
+    }
+
     Ok(())
 }
 
-/// Validate a commit message file against the configured conventional commit pattern and types.
-/// Reads hook JSON from stdin to get the message_file path.
+/// Validate the commit message named by the `commit-msg` hook JSON on stdin.
 pub fn validate_commit_msg(config: &ReleaseConfig) -> Result<(), ReleaseError> {
     use std::io::Read;
     let mut input = String::new();
@@ -476,55 +745,104 @@ pub fn validate_commit_msg(config: &ReleaseConfig) -> Result<(), ReleaseError> {
     let content = std::fs::read_to_string(file)
         .map_err(|e| ReleaseError::Hook(format!("cannot read commit message file: {e}")))?;
 
-    let first_line = content.lines().next().unwrap_or("").trim();
-
-    // Allow merge commits
-    if first_line.starts_with("Merge ") {
-        return Ok(());
-    }
+    check_commit_message(config, &content)
+}
 
-    // Allow fixup/squash/amend commits (from rebase -i)
-    if first_line.starts_with("fixup! ")
-        || first_line.starts_with("squash! ")
-        || first_line.starts_with("amend! ")
+/// Validate a commit message against `commit_pattern` and the configured `types`.
+///
+/// Only the subject (the first line that is not blank or a `#` comment) is
+/// checked. Merge subjects and `fixup!`/`squash!`/`amend!` commits from
+/// `rebase -i` are always accepted. Shared by `sr check` (ranges and single
+/// messages) and the `commit-msg` hook.
+pub fn check_commit_message(config: &ReleaseConfig, message: &str) -> Result<(), ReleaseError> {
+    let subject = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .unwrap_or("");
+
+    if subject.starts_with("Merge ")
+        || subject.starts_with("fixup! ")
+        || subject.starts_with("squash! ")
+        || subject.starts_with("amend! ")
     {
         return Ok(());
     }
 
     let re = regex::Regex::new(&config.commit_pattern)
-        .map_err(|e| ReleaseError::Hook(format!("invalid commit_pattern: {e}")))?;
+        .map_err(|e| ReleaseError::Config(format!("invalid commit_pattern: {e}")))?;
+    let type_names: Vec<&str> = config.types.iter().map(|t| t.name.as_str()).collect();
 
-    if !re.is_match(first_line) {
-        let type_names: Vec<&str> = config.types.iter().map(|t| t.name.as_str()).collect();
-        return Err(ReleaseError::Hook(format!(
+    let Some(caps) = re.captures(subject) else {
+        let pattern = if config.commit_pattern == crate::commit::DEFAULT_COMMIT_PATTERN {
+            String::new()
+        } else {
+            format!("\x20 Pattern:  {}\n", config.commit_pattern)
+        };
+        return Err(ReleaseError::CommitMessage(format!(
             "commit message does not follow Conventional Commits.\n\n\
+             \x20 Problem:  {}\n\
              \x20 Expected: <type>(<scope>): <description>\n\
-             \x20 Got:      {first_line}\n\n\
+             \x20 Got:      {subject}\n\
+             {pattern}\n\
              \x20 Valid types: {}\n\
              \x20 Breaking:    append '!' before the colon, e.g. feat!: ...\n\n\
              \x20 Examples:\n\
              \x20   feat: add release dry-run flag\n\
              \x20   fix(core): handle empty tag list\n\
              \x20   feat!: redesign config format",
+            subject_problem(subject),
+            type_names.join(", "),
+        )));
+    };
+
+    let msg_type = caps.name("type").map(|m| m.as_str()).unwrap_or_default();
+    if !type_names.contains(&msg_type) {
+        return Err(ReleaseError::CommitMessage(format!(
+            "commit type '{msg_type}' is not allowed.\n\n\
+             \x20 Got:         {subject}\n\
+             \x20 Valid types: {}",
             type_names.join(", "),
         )));
     }
 
-    // Extract and validate the type
-    if let Some(caps) = re.captures(first_line) {
-        let msg_type = caps.name("type").map(|m| m.as_str()).unwrap_or_default();
+    Ok(())
+}
 
-        if !config.types.iter().any(|t| t.name == msg_type) {
-            let type_names: Vec<&str> = config.types.iter().map(|t| t.name.as_str()).collect();
-            return Err(ReleaseError::Hook(format!(
-                "commit type '{msg_type}' is not allowed.\n\n\
-                 \x20 Valid types: {}",
-                type_names.join(", "),
-            )));
+/// Best guess at which part of a subject breaks `<type>(<scope>)!: <description>`.
+fn subject_problem(subject: &str) -> String {
+    if subject.is_empty() {
+        return "the subject line is empty".into();
+    }
+    let Some((head, description)) = subject.split_once(':') else {
+        return "missing ':' between the type and the description".into();
+    };
+    let head = head.strip_suffix('!').unwrap_or(head);
+    let (kind, scope) = match head.split_once('(') {
+        Some((t, rest)) => (t, Some(rest)),
+        None => (head, None),
+    };
+    if kind.is_empty() {
+        return "missing type before ':'".into();
+    }
+    if !kind.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return format!("type '{kind}' may only contain letters, digits, and '_'");
+    }
+    if let Some(scope) = scope {
+        if !scope.ends_with(')') {
+            return "scope is missing its closing ')'".into();
+        }
+        if scope == ")" {
+            return "scope is empty; drop the parentheses or name a scope".into();
         }
     }
-
-    Ok(())
+    if !description.starts_with(char::is_whitespace) {
+        return "missing space after ':'".into();
+    }
+    if description.trim().is_empty() {
+        return "missing description after ':'".into();
+    }
+    "the subject does not match commit_pattern".into()
 }
 
 #[cfg(test)]
@@ -544,7 +862,7 @@ mod tests {
     #[test]
     fn creates_hook_scripts() {
         let dir = tempfile::tempdir().unwrap();
-        let config = make_config(&[("pre-commit", vec![HookEntry::Simple("echo hi".into())])]);
+        let config = make_config(&[("pre-commit", vec![HookEntry::Command("echo hi".into())])]);
 
         let changed = sync_hooks(dir.path(), &config).unwrap();
         assert!(changed);
@@ -561,7 +879,7 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let config = make_config(&[(
             "commit-msg",
-            vec![HookEntry::Simple("sr hook commit-msg".into())],
+            vec![HookEntry::Command("sr hook commit-msg".into())],
         )]);
 
         assert!(sync_hooks(dir.path(), &config).unwrap());
@@ -585,7 +903,7 @@ mod tests {
         // Write a non-sr-managed hook that should be left alone.
         std::fs::write(hooks_dir.join("post-checkout"), "#!/bin/sh\necho custom").unwrap();
 
-        let config = make_config(&[("pre-commit", vec![HookEntry::Simple("echo hi".into())])]);
+        let config = make_config(&[("pre-commit", vec![HookEntry::Command("echo hi".into())])]);
 
         sync_hooks(dir.path(), &config).unwrap();
 
@@ -612,7 +930,7 @@ mod tests {
 
         let config = make_config(&[(
             "commit-msg",
-            vec![HookEntry::Simple("sr hook commit-msg".into())],
+            vec![HookEntry::Command("sr hook commit-msg".into())],
         )]);
 
         sync_hooks(dir.path(), &config).unwrap();
@@ -650,7 +968,7 @@ mod tests {
     #[test]
     fn needs_sync_detects_changes() {
         let dir = tempfile::tempdir().unwrap();
-        let config = make_config(&[("pre-commit", vec![HookEntry::Simple("echo hi".into())])]);
+        let config = make_config(&[("pre-commit", vec![HookEntry::Command("echo hi".into())])]);
 
         assert!(needs_sync(dir.path(), &config));
 
@@ -658,8 +976,175 @@ mod tests {
         assert!(!needs_sync(dir.path(), &config));
 
         // Change config.
-        let config2 =
-            make_config(&[("pre-commit", vec![HookEntry::Simple("echo changed".into())])]);
+        let config2 = make_config(&[(
+            "pre-commit",
+            vec![HookEntry::Command("echo changed".into())],
+        )]);
         assert!(needs_sync(dir.path(), &config2));
     }
+
+    #[test]
+    fn hook_command_is_killed_after_timeout() {
+        let hook = HookCommand {
+            run: "sleep 30 & sleep 30".into(),
+            timeout: Some(1),
+            ..Default::default()
+        };
+        let start = std::time::Instant::now();
+        let err = run_hook_command(&hook, "slow", None, &[], &mut Vec::new()).unwrap_err();
+        assert!(err.to_string().contains("timed out after 1s"), "{err}");
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+    }
+
+    #[test]
+    fn hook_command_failure_aborts_by_default() {
+        let hook = HookCommand::from("exit 3");
+        let mut failures = Vec::new();
+        let err = run_hook_command(&hook, "check", None, &[], &mut failures).unwrap_err();
+        assert!(err.to_string().contains("exited with code 3"));
+        assert!(failures.is_empty());
+    }
+
+    #[test]
+    fn hook_command_continue_on_error_collects_failure() {
+        let hook = HookCommand {
+            run: "echo failing; exit 3".into(),
+            continue_on_error: true,
+            ..Default::default()
+        };
+        let mut failures = Vec::new();
+        run_hook_command(&hook, "check", None, &[], &mut failures).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("check: "));
+        assert!(failures[0].contains("exited with code 3"));
+    }
+
+    #[test]
+    fn hook_command_runs_in_cwd_with_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let hook = HookCommand {
+            run: "echo $SR_VERSION > version.txt".into(),
+            cwd: Some(dir.path().to_string_lossy().into_owned()),
+            shell: Some("sh".into()),
+            ..Default::default()
+        };
+        run_hook_command(
+            &hook,
+            "build",
+            None,
+            &[("SR_VERSION", "1.2.3")],
+            &mut Vec::new(),
+        )
+        .unwrap();
+        let written = std::fs::read_to_string(dir.path().join("version.txt")).unwrap();
+        assert_eq!(written.trim(), "1.2.3");
+    }
+
+    fn invocation(program: &str, flag: &'static str, raw: bool) -> ShellInvocation {
+        ShellInvocation {
+            program: program.into(),
+            flag,
+            raw,
+        }
+    }
+
+    #[test]
+    fn shell_invocation_picks_flag_for_shell() {
+        let resolve = |shell, windows| ShellInvocation::resolve(shell, windows, || true);
+        assert_eq!(
+            resolve(Some("bash"), false),
+            invocation("bash", "-c", false)
+        );
+        assert_eq!(
+            resolve(Some("pwsh"), false),
+            invocation("pwsh", "-Command", false)
+        );
+        assert_eq!(resolve(None, false), invocation("sh", "-c", false));
+        assert_eq!(
+            resolve(Some("cmd.exe"), true),
+            invocation("cmd.exe", "/C", true)
+        );
+        assert_eq!(
+            resolve(Some("powershell"), true),
+            invocation("powershell", "-Command", false)
+        );
+        // Windows with `sh` (e.g. Git Bash) on PATH keeps sh
+        assert_eq!(resolve(None, true), invocation("sh", "-c", false));
+    }
+
+    #[test]
+    fn shell_invocation_falls_back_to_cmd_on_windows_without_sh() {
+        let cmd = ShellInvocation::resolve(None, true, || false);
+        assert_eq!(cmd, invocation("cmd", "/C", true));
+        let unix = ShellInvocation::resolve(None, false, || panic!("not consulted off Windows"));
+        assert_eq!(unix, invocation("sh", "-c", false));
+    }
+
+    #[test]
+    fn shell_invocation_passes_the_command_as_one_argument() {
+        let run = "echo \"$SR_VERSION\" > out.txt";
+        for (shell, flag) in [("sh", "-c"), ("pwsh", "-Command")] {
+            let command = ShellInvocation::resolve(Some(shell), false, || true).command(run);
+            assert_eq!(command.get_program(), shell);
+            let args: Vec<_> = command.get_args().collect();
+            assert_eq!(args, [flag, run]);
+        }
+    }
+
+    fn check(message: &str) -> Result<(), String> {
+        check_commit_message(&ReleaseConfig::default(), message).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn commit_message_accepts_conventional_subjects() {
+        assert!(check("feat(core): add x\n\nbody").is_ok());
+        assert!(check("# comment\n\nfix!: drop y\n").is_ok());
+        assert!(check("Merge branch 'main'").is_ok());
+        assert!(check("fixup! feat: add x").is_ok());
+    }
+
+    #[test]
+    fn commit_message_rejects_unknown_type() {
+        let err = check("wip: stuff").unwrap_err();
+        assert!(err.contains("commit type 'wip' is not allowed"), "{err}");
+        assert!(err.contains("feat, fix"), "{err}");
+    }
+
+    #[test]
+    fn commit_message_explains_what_failed() {
+        for (message, problem) in [
+            ("", "the subject line is empty"),
+            ("add a feature", "missing ':'"),
+            ("feat:add x", "missing space after ':'"),
+            ("feat(core: add x", "missing its closing ')'"),
+            ("feat(): add x", "scope is empty"),
+            ("new feature: add x", "type 'new feature' may only contain"),
+        ] {
+            let err = check(message).unwrap_err();
+            assert!(err.contains(problem), "{message:?}: {err}");
+            assert!(err.contains("Valid types: feat"), "{err}");
+        }
+    }
+
+    #[test]
+    fn install_commit_msg_hook_respects_foreign_hooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = install_commit_msg_hook(dir.path(), false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            commit_msg_hook_script()
+        );
+        // Re-installing over our own hook is fine
+        install_commit_msg_hook(dir.path(), false).unwrap();
+
+        std::fs::write(&path, "#!/bin/sh\nexit 0\n").unwrap();
+        let err = install_commit_msg_hook(dir.path(), false).unwrap_err();
+        assert!(err.to_string().contains("--force"), "{err}");
+
+        install_commit_msg_hook(dir.path(), true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("commit-msg.bak")).unwrap(),
+            "#!/bin/sh\nexit 0\n"
+        );
+    }
 }
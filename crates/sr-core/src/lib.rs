@@ -1,9 +1,18 @@
+pub mod artifacts;
+pub mod attestations;
 pub mod changelog;
 pub mod commit;
 pub mod config;
+pub mod doctor;
+pub mod dry_run;
 pub mod error;
 pub mod git;
 pub mod hooks;
+pub mod notify;
+pub mod offline;
+pub mod publish;
+pub mod redact;
 pub mod release;
+pub mod report;
 pub mod version;
 pub mod version_files;
@@ -0,0 +1,48 @@
+//! Process-wide offline switch (`--offline` / `SR_OFFLINE`).
+//!
+//! Commands substitute local-only behaviour when offline; the git and HTTP
+//! layers call [`ensure_online`] before touching the network, so a code path
+//! that forgets to check fails loudly instead of hanging on a dead network.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::ReleaseError;
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Turn offline mode on or off for the rest of the process.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail with [`ReleaseError::Offline`] if offline mode is on. `what` names the
+/// network operation that was about to run (e.g. `git fetch`, `GET <url>`).
+pub fn ensure_online(what: &str) -> Result<(), ReleaseError> {
+    if is_offline() {
+        return Err(ReleaseError::Offline(what.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_online_fails_only_when_offline() {
+        assert!(ensure_online("git fetch").is_ok());
+        set_offline(true);
+        let err = ensure_online("git fetch").unwrap_err();
+        set_offline(false);
+        assert!(matches!(err, ReleaseError::Offline(_)));
+        assert_eq!(
+            err.to_string(),
+            "internal error: network access attempted in offline mode: git fetch"
+        );
+        assert!(ensure_online("git fetch").is_ok());
+    }
+}
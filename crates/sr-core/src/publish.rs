@@ -0,0 +1,399 @@
+//! Publish steps — `cargo publish`, `npm publish`, and custom commands run
+//! after the tag is pushed and the remote release is created.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tracing::{error, info};
+
+use crate::config::{HookCommand, PublishStep};
+use crate::error::ReleaseError;
+use crate::version_files::{extract_toml_string_array, resolve_member_globs};
+
+/// A single shell command produced by expanding a [`PublishStep`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PublishCommand {
+    /// Label used to prefix the command's output (e.g. `cargo publish sr-core`).
+    pub label: String,
+    /// The command line to run.
+    pub run: String,
+    /// Working directory; `None` means the current directory.
+    pub cwd: Option<String>,
+}
+
+/// Expand a publish step into the commands it runs, in order.
+///
+/// A cargo step yields one `cargo publish -p <crate>` per publishable package,
+/// ordered so each crate follows the workspace crates it depends on.
+pub fn step_commands(step: &PublishStep) -> Result<Vec<PublishCommand>, ReleaseError> {
+    match step {
+        PublishStep::Cargo { path, args } => {
+            let dir = path.as_deref().unwrap_or(".");
+            let order = cargo_publish_order(&Path::new(dir).join("Cargo.toml"))?;
+            Ok(order
+                .into_iter()
+                .map(|name| PublishCommand {
+                    label: format!("cargo publish {name}"),
+                    run: command_line(&["cargo", "publish", "-p", &name], args),
+                    cwd: path.clone(),
+                })
+                .collect())
+        }
+        PublishStep::Npm { path, tag, args } => {
+            let mut base = vec!["npm", "publish"];
+            if let Some(tag) = tag {
+                base.extend(["--tag", tag]);
+            }
+            Ok(vec![PublishCommand {
+                label: "npm publish".into(),
+                run: command_line(&base, args),
+                cwd: path.clone(),
+            }])
+        }
+        PublishStep::Command { run } => Ok(vec![PublishCommand {
+            label: "publish".into(),
+            run: run.clone(),
+            cwd: None,
+        }]),
+    }
+}
+
+/// Run every publish step with `SR_VERSION` and `SR_TAG` set, returning the failures.
+///
+/// Registry tokens (`CARGO_REGISTRY_TOKEN`, `NODE_AUTH_TOKEN`) are inherited
+/// from the environment. A failing step does not stop the steps after it, but
+/// stops the rest of its own commands (later crates depend on earlier ones).
+pub fn run_publish_steps(steps: &[PublishStep], version: &str, tag: &str) -> Vec<String> {
+    let env = [("SR_VERSION", version), ("SR_TAG", tag)];
+    let mut failures = Vec::new();
+    for step in steps {
+        let commands = match step_commands(step) {
+            Ok(commands) => commands,
+            Err(e) => {
+                error!("{e}");
+                failures.push(e.to_string());
+                continue;
+            }
+        };
+        for cmd in commands {
+            info!("Publishing: {}", cmd.run);
+            let hook = HookCommand {
+                run: cmd.run,
+                cwd: cmd.cwd,
+                ..Default::default()
+            };
+            if let Err(e) =
+                crate::hooks::run_hook_command(&hook, &cmd.label, None, &env, &mut Vec::new())
+            {
+                error!("{} failed: {e}", cmd.label);
+                failures.push(format!("{}: {e}", cmd.label));
+                break;
+            }
+        }
+    }
+    failures
+}
+
+/// Publishable packages of the Cargo manifest at `manifest`, in dependency order.
+///
+/// For a workspace root this covers the root package (if any) and every member;
+/// packages with `publish = false` are skipped. Each package comes after the
+/// workspace packages it depends on through `[dependencies]` or `[build-dependencies]`.
+pub fn cargo_publish_order(manifest: &Path) -> Result<Vec<String>, ReleaseError> {
+    let doc = read_manifest(manifest)?;
+    let root_dir = manifest.parent().unwrap_or(Path::new("."));
+
+    let mut manifests: Vec<PathBuf> = Vec::new();
+    if doc.get("package").is_some() {
+        manifests.push(manifest.to_path_buf());
+    }
+    if doc.get("workspace").is_some() {
+        let members = extract_toml_string_array(&doc, &["workspace", "members"]);
+        let excluded: Vec<PathBuf> = extract_toml_string_array(&doc, &["workspace", "exclude"])
+            .iter()
+            .map(|e| root_dir.join(e))
+            .collect();
+        for member in resolve_member_globs(root_dir, &members, "Cargo.toml") {
+            let dir = member.parent().map(Path::to_path_buf).unwrap_or_default();
+            if !excluded.contains(&dir) && !manifests.contains(&member) {
+                manifests.push(member);
+            }
+        }
+    }
+
+    // (name, workspace dependencies by package name)
+    let mut packages: Vec<(String, Vec<String>)> = Vec::new();
+    for path in &manifests {
+        let member = if path == manifest {
+            doc.clone()
+        } else {
+            read_manifest(path)?
+        };
+        let Some(name) = member
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+        else {
+            continue;
+        };
+        if !is_publishable(&member) {
+            continue;
+        }
+        packages.push((name.to_string(), dependency_names(&member)));
+    }
+
+    let names: Vec<String> = packages.iter().map(|(n, _)| n.clone()).collect();
+    for (_, deps) in &mut packages {
+        deps.retain(|d| names.contains(d));
+    }
+
+    let mut order: Vec<String> = Vec::new();
+    while order.len() < packages.len() {
+        let next = packages.iter().find(|(name, deps)| {
+            !order.contains(name) && deps.iter().all(|d| d == name || order.contains(d))
+        });
+        match next {
+            Some((name, _)) => order.push(name.clone()),
+            None => {
+                let remaining: Vec<&str> = names
+                    .iter()
+                    .filter(|n| !order.contains(n))
+                    .map(String::as_str)
+                    .collect();
+                return Err(ReleaseError::Publish(format!(
+                    "dependency cycle between workspace crates: {}",
+                    remaining.join(", ")
+                )));
+            }
+        }
+    }
+    Ok(order)
+}
+
+fn read_manifest(path: &Path) -> Result<toml_edit::DocumentMut, ReleaseError> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| ReleaseError::Publish(format!("failed to read {}: {e}", path.display())))?;
+    contents
+        .parse()
+        .map_err(|e| ReleaseError::Publish(format!("failed to parse {}: {e}", path.display())))
+}
+
+/// `publish = false` and `publish = []` opt a package out of publishing.
+fn is_publishable(doc: &toml_edit::DocumentMut) -> bool {
+    match doc.get("package").and_then(|p| p.get("publish")) {
+        Some(item) => {
+            item.as_bool() != Some(false) && item.as_array().is_none_or(|a| !a.is_empty())
+        }
+        None => true,
+    }
+}
+
+/// Package names of a manifest's normal and build dependencies, including
+/// target-specific ones. Renamed dependencies resolve to their `package` name.
+fn dependency_names(doc: &toml_edit::DocumentMut) -> Vec<String> {
+    const TABLES: &[&str] = &["dependencies", "build-dependencies"];
+    let mut tables: Vec<&toml_edit::Item> = TABLES.iter().filter_map(|t| doc.get(t)).collect();
+    if let Some(targets) = doc.get("target").and_then(|t| t.as_table_like()) {
+        for (_, target) in targets.iter() {
+            tables.extend(TABLES.iter().filter_map(|t| target.get(t)));
+        }
+    }
+
+    let mut names = Vec::new();
+    for table in tables {
+        let Some(table) = table.as_table_like() else {
+            continue;
+        };
+        for (key, dep) in table.iter() {
+            let name = dep
+                .get("package")
+                .and_then(|p| p.as_str())
+                .unwrap_or(key)
+                .to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Join `base` and `args` into a shell command line, quoting where needed.
+fn command_line(base: &[&str], args: &[String]) -> String {
+    base.iter()
+        .copied()
+        .chain(args.iter().map(String::as_str))
+        .map(shell_quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@+,".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, rel: &str, contents: &str) {
+        let path = dir.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/scratch\"]\n",
+        );
+        write(
+            root,
+            "crates/cli/Cargo.toml",
+            "[package]\nname = \"app-cli\"\n\n[dependencies]\ncore = { package = \"app-core\", path = \"../core\" }\napp-git = { path = \"../git\" }\nserde = \"1\"\n",
+        );
+        write(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"app-core\"\n\n[dev-dependencies]\napp-testkit = { path = \"../testkit\" }\n",
+        );
+        write(
+            root,
+            "crates/git/Cargo.toml",
+            "[package]\nname = \"app-git\"\n\n[target.'cfg(unix)'.dependencies]\napp-core = { path = \"../core\" }\n",
+        );
+        write(
+            root,
+            "crates/testkit/Cargo.toml",
+            "[package]\nname = \"app-testkit\"\npublish = false\n",
+        );
+        write(
+            root,
+            "crates/scratch/Cargo.toml",
+            "[package]\nname = \"scratch\"\n",
+        );
+        dir
+    }
+
+    #[test]
+    fn cargo_order_follows_dependency_graph() {
+        let dir = workspace();
+        let order = cargo_publish_order(&dir.path().join("Cargo.toml")).unwrap();
+        assert_eq!(order, vec!["app-core", "app-git", "app-cli"]);
+    }
+
+    #[test]
+    fn cargo_order_single_package() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "Cargo.toml", "[package]\nname = \"solo\"\n");
+        let order = cargo_publish_order(&dir.path().join("Cargo.toml")).unwrap();
+        assert_eq!(order, vec!["solo"]);
+    }
+
+    #[test]
+    fn cargo_order_reports_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"a\", \"b\"]\n",
+        );
+        write(
+            root,
+            "a/Cargo.toml",
+            "[package]\nname = \"a\"\n[dependencies]\nb = { path = \"../b\" }\n",
+        );
+        write(
+            root,
+            "b/Cargo.toml",
+            "[package]\nname = \"b\"\n[build-dependencies]\na = { path = \"../a\" }\n",
+        );
+        let err = cargo_publish_order(&root.join("Cargo.toml")).unwrap_err();
+        assert!(err.to_string().contains("dependency cycle"), "{err}");
+        assert!(err.to_string().contains("a, b"), "{err}");
+    }
+
+    #[test]
+    fn step_commands_expand_each_step() {
+        let dir = workspace();
+        let path = dir.path().to_string_lossy().into_owned();
+        let cargo = step_commands(&PublishStep::Cargo {
+            path: Some(path.clone()),
+            args: vec!["--locked".into()],
+        })
+        .unwrap();
+        let runs: Vec<&str> = cargo.iter().map(|c| c.run.as_str()).collect();
+        assert_eq!(
+            runs,
+            vec![
+                "cargo publish -p app-core --locked",
+                "cargo publish -p app-git --locked",
+                "cargo publish -p app-cli --locked",
+            ]
+        );
+        assert_eq!(cargo[0].cwd.as_deref(), Some(path.as_str()));
+        assert_eq!(cargo[0].label, "cargo publish app-core");
+
+        let npm = step_commands(&PublishStep::Npm {
+            path: None,
+            tag: Some("next".into()),
+            args: vec!["--access".into(), "public".into()],
+        })
+        .unwrap();
+        assert_eq!(npm[0].run, "npm publish --tag next --access public");
+
+        let cmd = step_commands(&PublishStep::Command {
+            run: "echo $SR_TAG".into(),
+        })
+        .unwrap();
+        assert_eq!(cmd[0].run, "echo $SR_TAG");
+    }
+
+    #[test]
+    fn step_commands_missing_manifest_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = step_commands(&PublishStep::Cargo {
+            path: Some(dir.path().to_string_lossy().into_owned()),
+            args: vec![],
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("failed to read"), "{err}");
+    }
+
+    #[test]
+    fn run_publish_steps_continues_past_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("published");
+        let steps = vec![
+            PublishStep::Command {
+                run: "exit 1".into(),
+            },
+            PublishStep::Command {
+                run: format!("echo $SR_VERSION > {}", marker.display()),
+            },
+        ];
+        let failures = run_publish_steps(&steps, "1.2.3", "v1.2.3");
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("exited with code 1"));
+        assert_eq!(std::fs::read_to_string(marker).unwrap().trim(), "1.2.3");
+    }
+
+    #[test]
+    fn shell_quote_escapes_unsafe_args() {
+        assert_eq!(shell_quote("--locked"), "--locked");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
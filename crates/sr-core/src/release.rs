@@ -2,19 +2,36 @@ use std::fs;
 use std::path::Path;
 
 use semver::Version;
-use serde::Serialize;
-
-use crate::changelog::{ChangelogEntry, ChangelogFormatter};
-use crate::commit::{CommitParser, ConventionalCommit, DefaultCommitClassifier};
-use crate::config::ReleaseConfig;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, warn};
+
+use crate::changelog::{ChangelogEntry, ChangelogFormatter, Destination};
+use crate::commit::{
+    Commit, CommitClassifier, CommitParser, ConventionalCommit, DefaultCommitClassifier, TypeUsage,
+};
+use crate::config::{DateSource, HookCommand, ReleaseConfig};
+use crate::dry_run::{
+    Artifact, DryRunReport, NotificationTarget, ProviderAction, ReleaseAction, ReleaseActionKind,
+    TagAction, VersionFileChange,
+};
 use crate::error::ReleaseError;
-use crate::git::GitRepository;
+use crate::git::{GitRepository, TagInfo, tag_display_name};
+use crate::report::{ProviderRelease, ReleaseReport};
 use crate::version::{BumpLevel, apply_bump, apply_prerelease_bump, determine_bump};
-use crate::version_files::{bump_version_file, discover_lock_files, is_supported_version_file};
+use crate::version_files::{
+    bump_version_file, bump_version_file_with_url, discover_lock_files, is_supported_version_file,
+    read_version_file,
+};
+
+/// Version of the saved plan format (`sr plan --save`). Bump it whenever
+/// `ReleasePlan` changes shape so old plan files are rejected instead of misread.
+pub const PLAN_SCHEMA_VERSION: u32 = 2;
 
 /// The computed plan for a release, before execution.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReleasePlan {
+    /// Always [`PLAN_SCHEMA_VERSION`] for plans made by this version of sr.
+    pub schema_version: u32,
     pub current_version: Option<Version>,
     pub next_version: Version,
     pub bump: BumpLevel,
@@ -22,6 +39,172 @@ pub struct ReleasePlan {
     pub tag_name: String,
     pub floating_tag_name: Option<String>,
     pub prerelease: bool,
+    /// HEAD at plan time; `execute` refuses to tag a different commit.
+    pub head_sha: String,
+    /// Forced re-release of the existing `tag_name` (`--force` with HEAD at the tag).
+    pub forced: bool,
+    /// How many of `commits` touched each top-level directory, most first.
+    /// Empty unless the repository was opened to read changed paths.
+    #[serde(default)]
+    pub touched_paths: Vec<TouchedPath>,
+    /// Distinct author emails of `commits`.
+    #[serde(default)]
+    pub contributors: usize,
+}
+
+/// Commits in a release that changed files under one top-level directory;
+/// `.` stands for files at the repository root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TouchedPath {
+    pub path: String,
+    pub commits: usize,
+}
+
+/// Count the commits touching each top-level directory of `commits`' changed
+/// paths. A commit counts once per directory, however many files it changed there.
+pub fn touched_paths(commits: &[&Commit]) -> Vec<TouchedPath> {
+    let mut counts: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+    for commit in commits {
+        let areas: std::collections::BTreeSet<&str> = commit
+            .paths
+            .iter()
+            .map(|path| path.split_once('/').map_or(".", |(dir, _)| dir))
+            .collect();
+        for area in areas {
+            *counts.entry(area).or_default() += 1;
+        }
+    }
+    let mut touched: Vec<TouchedPath> = counts
+        .into_iter()
+        .map(|(path, commits)| TouchedPath {
+            path: path.to_string(),
+            commits,
+        })
+        .collect();
+    touched.sort_by_key(|t| std::cmp::Reverse(t.commits));
+    touched
+}
+
+/// Distinct author emails of `commits`, compared case-insensitively.
+fn contributors(commits: &[&Commit]) -> usize {
+    commits
+        .iter()
+        .filter(|c| !c.author.is_empty())
+        .map(|c| c.author.to_lowercase())
+        .collect::<std::collections::BTreeSet<_>>()
+        .len()
+}
+
+/// The conventional commits of a history range, as
+/// [`TrunkReleaseStrategy::analyze_range`] reads them.
+#[derive(Debug, Clone)]
+pub struct RangeAnalysis {
+    pub commits: Vec<ConventionalCommit>,
+    /// Distinct author emails of `commits`.
+    pub contributors: usize,
+}
+
+/// How big a release is: its commits, their distinct authors, the breaking
+/// changes among them, and the commits in each changelog section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseStats {
+    pub commits: usize,
+    pub contributors: usize,
+    pub breaking: usize,
+    /// In changelog order; empty sections are left out.
+    pub sections: Vec<SectionStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionStats {
+    pub section: String,
+    pub commits: usize,
+}
+
+impl ReleaseStats {
+    /// Stats of `commits` by `contributors` distinct authors, with commits
+    /// sectioned as the changelog lists them.
+    pub fn new(
+        config: &ReleaseConfig,
+        commits: &[ConventionalCommit],
+        contributors: usize,
+    ) -> Self {
+        let sections = config
+            .section_names()
+            .into_iter()
+            .filter_map(|section| {
+                let count = commits
+                    .iter()
+                    .filter(|c| config.section_of(c) == Some(section))
+                    .count();
+                (count > 0).then(|| SectionStats {
+                    section: section.to_string(),
+                    commits: count,
+                })
+            })
+            .collect();
+        Self {
+            commits: commits.len(),
+            contributors,
+            breaking: commits.iter().filter(|c| c.breaking).count(),
+            sections,
+        }
+    }
+
+    /// Commits in `section`, 0 when it has none.
+    pub fn section(&self, section: &str) -> usize {
+        self.sections
+            .iter()
+            .find(|s| s.section == section)
+            .map_or(0, |s| s.commits)
+    }
+}
+
+/// A planned release next to the one before it (`sr plan --compare`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseComparison {
+    /// Stats of the planned release.
+    pub stats: ReleaseStats,
+    /// `None` before the first release.
+    pub previous_release: Option<PreviousRelease>,
+}
+
+/// An existing release and its [`ReleaseStats`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PreviousRelease {
+    pub tag: String,
+    #[serde(flatten)]
+    pub stats: ReleaseStats,
+}
+
+impl ReleasePlan {
+    /// Read a plan saved by `sr plan --save`, rejecting files written with a
+    /// different [`PLAN_SCHEMA_VERSION`] before looking at anything else.
+    pub fn from_json(json: &str) -> Result<Self, ReleaseError> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| ReleaseError::Config(format!("invalid plan file: {e}")))?;
+        match value
+            .get("schema_version")
+            .and_then(serde_json::Value::as_u64)
+        {
+            Some(v) if v == u64::from(PLAN_SCHEMA_VERSION) => {}
+            Some(v) => {
+                return Err(ReleaseError::Config(format!(
+                    "plan file has schema version {v}, but this sr reads version \
+                     {PLAN_SCHEMA_VERSION}; re-create it with `sr plan --save`"
+                )));
+            }
+            None => {
+                return Err(ReleaseError::Config(
+                    "plan file has no schema_version (written by an older sr); \
+                     re-create it with `sr plan --save`"
+                        .into(),
+                ));
+            }
+        }
+        serde_json::from_value(value)
+            .map_err(|e| ReleaseError::Config(format!("invalid plan file: {e}")))
+    }
 }
 
 /// Orchestrates the release flow.
@@ -33,6 +216,25 @@ pub trait ReleaseStrategy: Send + Sync {
     fn execute(&self, plan: &ReleasePlan, dry_run: bool) -> Result<(), ReleaseError>;
 }
 
+/// A release created or updated on a provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRelease {
+    pub tag: String,
+    /// The provider's ID for the release, when it has one. A GitHub draft is
+    /// not found by tag, so follow-up calls on drafts go through the ID.
+    pub id: Option<u64>,
+    pub url: String,
+}
+
+/// A file attached to a remote release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteAsset {
+    pub name: String,
+    pub size: u64,
+    /// Where the file downloads from.
+    pub url: String,
+}
+
 /// Abstraction over a remote VCS provider (e.g. GitHub, GitLab).
 pub trait VcsProvider: Send + Sync {
     /// Create a release on the remote VCS.
@@ -43,7 +245,7 @@ pub trait VcsProvider: Send + Sync {
         body: &str,
         prerelease: bool,
         draft: bool,
-    ) -> Result<String, ReleaseError>;
+    ) -> Result<RemoteRelease, ReleaseError>;
 
     /// Generate a compare URL between two refs.
     fn compare_url(&self, base: &str, head: &str) -> Result<String, ReleaseError>;
@@ -59,6 +261,11 @@ pub trait VcsProvider: Send + Sync {
         None
     }
 
+    /// Human-readable label identifying this provider in logs (e.g. `github.com/owner/repo`).
+    fn name(&self) -> String {
+        self.repo_url().unwrap_or_else(|| "remote".into())
+    }
+
     /// Update an existing release (name and body) using PATCH semantics,
     /// preserving any previously uploaded assets.
     fn update_release(
@@ -68,11 +275,46 @@ pub trait VcsProvider: Send + Sync {
         body: &str,
         prerelease: bool,
         draft: bool,
-    ) -> Result<String, ReleaseError> {
+    ) -> Result<RemoteRelease, ReleaseError> {
         self.delete_release(tag)?;
         self.create_release(tag, name, body, prerelease, draft)
     }
 
+    /// Whether a draft from `create_release` can take assets and be published
+    /// through [`upload_release_assets`](Self::upload_release_assets) and
+    /// [`publish_release`](Self::publish_release). Without draft support a new
+    /// release is published first and its assets uploaded after.
+    fn supports_drafts(&self) -> bool {
+        false
+    }
+
+    /// Upload asset files to `release`, which may be a draft.
+    fn upload_release_assets(
+        &self,
+        release: &RemoteRelease,
+        files: &[&str],
+    ) -> Result<(), ReleaseError> {
+        self.upload_assets(&release.tag, files)
+    }
+
+    /// The files attached to `release`, or `None` when the provider cannot list them.
+    fn release_assets(
+        &self,
+        _release: &RemoteRelease,
+    ) -> Result<Option<Vec<RemoteAsset>>, ReleaseError> {
+        Ok(None)
+    }
+
+    /// Publish the draft `release`, returning it as published.
+    fn publish_release(&self, release: &RemoteRelease) -> Result<RemoteRelease, ReleaseError> {
+        Ok(release.clone())
+    }
+
+    /// Delete `release`, which may be a draft.
+    fn discard_release(&self, release: &RemoteRelease) -> Result<(), ReleaseError> {
+        self.delete_release(&release.tag)
+    }
+
     /// Sync a floating tag release (e.g. v3) with the versioned release (e.g. v3.4.0).
     /// Creates or updates the floating release and copies assets from the versioned release.
     fn sync_floating_release(
@@ -92,1305 +334,5880 @@ pub trait VcsProvider: Send + Sync {
     fn verify_release(&self, _tag: &str) -> Result<(), ReleaseError> {
         Ok(())
     }
+
+    /// Whether the configured token may push to the repository, or `None` when
+    /// the provider cannot tell. Errors mean the token was rejected outright.
+    fn can_push(&self) -> Result<Option<bool>, ReleaseError> {
+        Ok(None)
+    }
+
+    /// The repository's default branch as the provider records it, or `None`
+    /// when the provider cannot tell.
+    fn default_branch(&self) -> Result<Option<String>, ReleaseError> {
+        Ok(None)
+    }
+}
+
+/// Which parts of the release `execute` performs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecuteOptions {
+    /// Write files, commit, and tag locally, but push nothing and make no
+    /// provider calls. The commands to publish later are printed instead.
+    pub no_push: bool,
+    /// Skip version files, the changelog file, and the release commit: only the
+    /// tag (and the remote release) are created.
+    pub tag_only: bool,
+}
+
+/// How [`TrunkReleaseStrategy::upload`] treats the release it uploads to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UploadOptions {
+    /// Create a missing release, with the annotated tag's message as its body.
+    pub create_missing: bool,
+    /// Upload a SHA256 `.sha256` sidecar with each file.
+    pub checksums: bool,
+    /// Log what would be uploaded. Providers are only queried, to report
+    /// whether the release would be created or uploaded to.
+    pub dry_run: bool,
+}
+
+/// Assets [`TrunkReleaseStrategy::upload`] attached on one provider: their
+/// download URLs, or their file names when the provider cannot list them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedAssets {
+    pub provider: String,
+    pub urls: Vec<String>,
 }
 
 /// Concrete release strategy implementing the trunk-based release flow.
-pub struct TrunkReleaseStrategy<G, V, C, F> {
+pub struct TrunkReleaseStrategy<G, C, F> {
     pub git: G,
-    pub vcs: Option<V>,
+    /// Remote providers to publish releases to. The first provider is used for
+    /// changelog links. Empty means tag-only (no remote releases).
+    pub vcs: Vec<Box<dyn VcsProvider>>,
     pub parser: C,
     pub formatter: F,
     pub config: ReleaseConfig,
     /// When true, re-release the current tag if HEAD is at the latest tag.
     pub force: bool,
+    /// When true, skip the `config.branches` check.
+    pub allow_any_branch: bool,
+    /// When true, uncommitted changes outside sr-managed files only warn.
+    pub allow_dirty: bool,
+    /// When true, execute even if HEAD moved since the plan was made.
+    pub ignore_head_drift: bool,
+    /// When true, never contact origin: a shallow clone is planned as-is
+    /// instead of fetching its missing tags.
+    pub offline: bool,
+    /// `--no-push` / `--tag-only` behaviour.
+    pub options: ExecuteOptions,
+    /// Plan as if HEAD were at this revision (`sr plan --ref`): only tags
+    /// reachable from it count. `None` plans HEAD.
+    pub target_ref: Option<String>,
+    /// When true, release even if `config.policy` would defer it.
+    pub override_policy: bool,
+    /// Branch to push the release commit to when HEAD is detached
+    /// (`--push-branch`). Default: `GITHUB_REF_NAME` unless a tag triggered the run.
+    pub push_branch: Option<String>,
+    /// HTTP client for `url:` artifacts. Without one, releases with URL
+    /// artifacts fail before anything is committed.
+    pub download: Option<Box<crate::artifacts::Downloader>>,
 }
 
-impl<G, V, C, F> TrunkReleaseStrategy<G, V, C, F>
+impl<G, C, F> TrunkReleaseStrategy<G, C, F>
 where
     G: GitRepository,
-    V: VcsProvider,
     C: CommitParser,
     F: ChangelogFormatter,
 {
-    fn format_changelog(&self, plan: &ReleasePlan) -> Result<String, ReleaseError> {
-        let today = today_string();
-        let compare_url = self.vcs.as_ref().and_then(|vcs| {
+    /// Defer the release unless every `config.policy` threshold is met. The
+    /// first release (no tag yet) is never held back by `min_interval`.
+    fn check_policy(
+        &self,
+        latest: Option<&TagInfo>,
+        commits: &[ConventionalCommit],
+        classifier: &DefaultCommitClassifier,
+    ) -> Result<(), ReleaseError> {
+        let policy = &self.config.policy;
+        let since = latest
+            .map(|t| format!(" since {}", t.name))
+            .unwrap_or_default();
+        let mut unmet = Vec::new();
+
+        if policy.min_commits > 0 {
+            let releasable = commits
+                .iter()
+                .filter(|c| classifier.commit_bump(c).is_some())
+                .count();
+            if releasable < policy.min_commits {
+                unmet.push(format!(
+                    "{releasable} of {} releasable commit(s){since}",
+                    policy.min_commits
+                ));
+            }
+        }
+        if let (Some(min_interval), Some(tag)) = (policy.min_interval_secs()?, latest) {
+            let released_at = self.git.tag_timestamp(&tag.name)?;
+            let elapsed = (unix_now() as i64 - released_at).max(0) as u64;
+            if elapsed < min_interval {
+                unmet.push(format!(
+                    "{} released {} ago, min_interval is {}",
+                    tag.name,
+                    human_duration(elapsed),
+                    human_duration(min_interval)
+                ));
+            }
+        }
+
+        if unmet.is_empty() {
+            Ok(())
+        } else {
+            Err(ReleaseError::Deferred {
+                reason: unmet.join("; "),
+            })
+        }
+    }
+
+    /// The existing tag for `version`, which may carry an older prefix from
+    /// `tag_prefix`; the primary prefix when no such tag is found.
+    fn tag_name_for(&self, version: &Version) -> String {
+        let prefixes = self.config.tag_prefix.all();
+        if prefixes.len() > 1
+            && let Ok(tags) = self.git.all_tags(&prefixes)
+            && let Some(tag) = tags.iter().rev().find(|t| &t.version == version)
+        {
+            return tag.name.clone();
+        }
+        format!(
+            "{}{}",
+            self.config.tag_prefix.primary(),
+            self.config.format_version(version)
+        )
+    }
+
+    /// The entry for `plan`, rendered for `destination`.
+    fn format_changelog(
+        &self,
+        plan: &ReleasePlan,
+        destination: Destination,
+    ) -> Result<String, ReleaseError> {
+        if plan.forced
+            && let Some(entry) = self.forced_entry(plan)?
+        {
+            return self.render_entry(entry, destination);
+        }
+        let date = self.release_date(plan)?;
+        let compare_url = self.vcs.first().and_then(|vcs| {
             let base = match &plan.current_version {
-                Some(v) => format!("{}{v}", self.config.tag_prefix),
+                Some(v) => self.tag_name_for(v),
                 None => return None,
             };
             vcs.compare_url(&base, &plan.tag_name).ok()
         });
         let entry = ChangelogEntry {
-            version: plan.next_version.to_string(),
-            date: today,
+            version: self.config.format_version(&plan.next_version),
+            tag_name: Some(plan.tag_name.clone()),
+            date,
             commits: plan.commits.clone(),
             compare_url,
-            repo_url: self.vcs.as_ref().and_then(|v| v.repo_url()),
+            repo_url: self.vcs.first().and_then(|v| v.repo_url()),
         };
-        self.formatter.format(&[entry])
+        self.render_entry(entry, destination)
     }
 
-    /// Render the release name from the configured template, or fall back to the tag name.
-    fn release_name(&self, plan: &ReleasePlan) -> String {
-        if let Some(ref template_str) = self.config.release_name_template {
-            let mut env = minijinja::Environment::new();
-            if env.add_template("release_name", template_str).is_ok()
-                && let Ok(tmpl) = env.get_template("release_name")
-                && let Ok(rendered) = tmpl.render(minijinja::context! {
-                    version => plan.next_version.to_string(),
-                    tag_name => &plan.tag_name,
-                    tag_prefix => &self.config.tag_prefix,
-                })
-            {
-                return rendered;
+    /// Body of the remote release and the curated notes file it came from:
+    /// the `release.notes_file_template` file when it exists, with
+    /// `{generated}` replaced by the generated notes, else the generated notes.
+    fn release_notes(&self, plan: &ReleasePlan) -> Result<(String, Option<String>), ReleaseError> {
+        let generated = self.format_changelog(plan, Destination::Release)?;
+        let version = self.config.format_version(&plan.next_version);
+        let Some(file) = self.config.release_notes_file(&version, &plan.tag_name) else {
+            return Ok((generated, None));
+        };
+        match fs::read_to_string(&file) {
+            Ok(curated) => Ok((curated.replace("{generated}", &generated), Some(file))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                if self.config.release.notes_file_required {
+                    return Err(ReleaseError::Changelog(format!(
+                        "release notes file {file} does not exist (release.notes_file_required)"
+                    )));
+                }
+                debug!("no release notes file {file}, using the generated notes");
+                Ok((generated, None))
             }
-            eprintln!("warning: invalid release_name_template, falling back to tag name");
+            Err(e) => Err(ReleaseError::Changelog(format!(
+                "cannot read release notes file {file}: {e}"
+            ))),
         }
-        plan.tag_name.clone()
     }
-}
-
-impl<G, V, C, F> ReleaseStrategy for TrunkReleaseStrategy<G, V, C, F>
-where
-    G: GitRepository,
-    V: VcsProvider,
-    C: CommitParser,
-    F: ChangelogFormatter,
-{
-    fn plan(&self) -> Result<ReleasePlan, ReleaseError> {
-        let is_prerelease = self.config.prerelease.is_some();
-
-        // For stable releases, find the latest stable tag (skip pre-release tags).
-        // For pre-releases, find the latest tag of any kind to determine commits since.
-        let all_tags = self.git.all_tags(&self.config.tag_prefix)?;
-        let latest_stable = all_tags.iter().rev().find(|t| t.version.pre.is_empty());
-        let latest_any = all_tags.last();
 
-        // Use the latest tag (any kind) for commit range, but the latest stable for base version
-        let tag_info = if is_prerelease {
-            latest_any
-        } else {
-            latest_stable.or(latest_any)
+    /// Entry for a forced re-release of an existing tag. The plan has no new
+    /// commits, so the notes are regenerated from the range the original
+    /// release covered, as `sr notes` would. None if the tag is not found.
+    fn forced_entry(&self, plan: &ReleasePlan) -> Result<Option<ChangelogEntry>, ReleaseError> {
+        let tags = self.git.all_tags(&self.config.tag_prefix.all())?;
+        let Some(index) = tags.iter().position(|t| t.name == plan.tag_name) else {
+            return Ok(None);
         };
+        let repo_url = self.vcs.first().and_then(|v| v.repo_url());
+        self.tag_entry(&tags, index, repo_url.as_deref()).map(Some)
+    }
 
-        let (current_version, from_sha) = match tag_info {
-            Some(info) => (Some(info.version.clone()), Some(info.sha.as_str())),
-            None => (None, None),
-        };
+    /// Format `entry` with the sections and compare link `destination` keeps.
+    pub fn render_entry(
+        &self,
+        entry: ChangelogEntry,
+        destination: Destination,
+    ) -> Result<String, ReleaseError> {
+        self.formatter
+            .format(&[self.config.entry_for(entry, destination)])
+    }
 
-        let raw_commits = if let Some(ref path) = self.config.path_filter {
-            self.git.commits_since_in_path(from_sha, path)?
-        } else {
-            self.git.commits_since(from_sha)?
-        };
-        if raw_commits.is_empty() {
-            // Force mode: re-release if HEAD is exactly at the latest tag
-            if self.force
-                && let Some(info) = tag_info
-            {
-                let head = self.git.head_sha()?;
-                if head == info.sha {
-                    let floating_tag_name = if self.config.floating_tags {
-                        Some(format!("{}{}", self.config.tag_prefix, info.version.major))
-                    } else {
-                        None
-                    };
-                    return Ok(ReleasePlan {
-                        current_version: Some(info.version.clone()),
-                        next_version: info.version.clone(),
-                        bump: BumpLevel::Patch,
-                        commits: vec![],
-                        tag_name: info.name.clone(),
-                        floating_tag_name,
-                        prerelease: is_prerelease,
-                    });
+    /// Changelog entry for the existing tag `tags[index]`: the commits since the
+    /// previous tag (or since the root for the first tag), dated with the tag's
+    /// date. `tags` must be sorted oldest first, as returned by `all_tags`.
+    pub fn tag_entry(
+        &self,
+        tags: &[TagInfo],
+        index: usize,
+        repo_url: Option<&str>,
+    ) -> Result<ChangelogEntry, ReleaseError> {
+        let previous = index.checked_sub(1).map(|i| &tags[i]);
+        self.tag_entry_since(&tags[index], previous, repo_url)
+    }
+
+    /// Changelog entries for the tags in `tags` (sorted oldest first) that
+    /// `selected` picks, passed to `visit` oldest first with their index.
+    ///
+    /// Entries are built one at a time, so only one release's commits are held
+    /// in memory. A selected tag whose entry cannot be built (say its history
+    /// was garbage-collected) is skipped with a warning, and the next entry
+    /// covers and compares against the tag before it. Any other error (git not
+    /// runnable, a bad date format) is returned. Unselected tags are not read.
+    pub fn visit_tag_entries(
+        &self,
+        tags: &[TagInfo],
+        repo_url: Option<&str>,
+        selected: impl Fn(usize) -> bool,
+        mut visit: impl FnMut(usize, ChangelogEntry) -> Result<(), ReleaseError>,
+    ) -> Result<(), ReleaseError> {
+        let mut previous = None;
+        for (i, tag) in tags.iter().enumerate() {
+            if selected(i) {
+                match self.tag_entry_since(tag, previous, repo_url) {
+                    Ok(entry) => visit(i, entry)?,
+                    Err(e) if is_unreadable_history(&e) => {
+                        warn!("skipping tag {}: {e}", tag.name);
+                        continue;
+                    }
+                    Err(e) => return Err(e),
                 }
             }
-            let (tag, sha) = match tag_info {
-                Some(info) => (info.name.clone(), info.sha.clone()),
-                None => ("(none)".into(), "(none)".into()),
-            };
-            return Err(ReleaseError::NoCommits { tag, sha });
+            previous = Some(tag);
         }
+        Ok(())
+    }
 
-        let conventional_commits: Vec<ConventionalCommit> = raw_commits
-            .iter()
-            .filter(|c| !c.message.starts_with("chore(release):"))
-            .filter_map(|c| self.parser.parse(c).ok())
-            .collect();
-
-        let classifier = DefaultCommitClassifier::new(
-            self.config.types.clone(),
-            self.config.commit_pattern.clone(),
-        );
-        let tag_for_err = tag_info
-            .map(|i| i.name.clone())
-            .unwrap_or_else(|| "(none)".into());
-        let commit_count = conventional_commits.len();
-        let bump = match determine_bump(&conventional_commits, &classifier) {
-            Some(b) => b,
-            None if self.force => BumpLevel::Patch,
-            None => {
-                return Err(ReleaseError::NoBump {
-                    tag: tag_for_err,
-                    commit_count,
-                });
-            }
-        };
+    /// Changelog entry for the existing tag `tag`: the commits since `previous`
+    /// (or since the root), dated with the tag's date.
+    fn tag_entry_since(
+        &self,
+        tag: &TagInfo,
+        previous: Option<&TagInfo>,
+        repo_url: Option<&str>,
+    ) -> Result<ChangelogEntry, ReleaseError> {
+        let from = previous.map(|t| t.sha.as_str());
+        let commits = self.analyze_range(from, &tag.name)?.commits;
+
+        let compare_url = previous.and_then(|prev| {
+            repo_url.map(|url| format!("{url}/compare/{}...{}", prev.name, tag.name))
+        });
+        Ok(ChangelogEntry {
+            version: self.config.format_version(&tag.version),
+            tag_name: Some(tag.name.clone()),
+            date: self.tag_date(&tag.name)?,
+            commits,
+            compare_url,
+            repo_url: repo_url.map(String::from),
+        })
+    }
 
-        // For pre-releases, base the version on the latest *stable* tag
-        let base_version = if is_prerelease {
-            latest_stable
-                .map(|t| t.version.clone())
-                .or(current_version.clone())
-                .unwrap_or(Version::new(0, 0, 0))
+    /// The conventional commits in `from..to` (from the root without `from`)
+    /// that touch the configured paths, release commits left out.
+    pub fn analyze_range(
+        &self,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<RangeAnalysis, ReleaseError> {
+        let pathspecs = self.config.pathspecs();
+        let pathspecs: Vec<&str> = pathspecs.iter().map(String::as_str).collect();
+        let raw_commits = if pathspecs.is_empty() {
+            self.git.commits_between(from, to)?
         } else {
-            current_version.clone().unwrap_or(Version::new(0, 0, 0))
+            self.git.commits_between_paths(from, to, &pathspecs)?
         };
+        let release_commits = self.config.release_commit_matcher();
+        let mut parsed_commits = Vec::new();
+        let commits = raw_commits
+            .iter()
+            .filter(|c| !release_commits.matches(&c.message))
+            .filter_map(|c| {
+                let parsed = self.parser.parse(c).ok()?;
+                parsed_commits.push(c);
+                Some(parsed)
+            })
+            .collect();
+        Ok(RangeAnalysis {
+            commits,
+            contributors: contributors(&parsed_commits),
+        })
+    }
 
-        // v0 protection: downshift Major → Minor when version is 0.x.y
-        // to prevent accidentally leaving v0. Use --force to bump to v1.
-        let bump = if base_version.major == 0 && bump == BumpLevel::Major && !self.force {
-            eprintln!(
-                "v0 protection: breaking change detected at v{base_version}, \
-                 downshifting major → minor (use --force to bump to v1)"
-            );
-            BumpLevel::Minor
+    /// Stats of `plan` and of the release before it: the range between the
+    /// two tags before the plan, or from the root to the only one.
+    pub fn compare_with_previous(
+        &self,
+        plan: &ReleasePlan,
+    ) -> Result<ReleaseComparison, ReleaseError> {
+        let tags = self.git.all_tags(&self.config.tag_prefix.all())?;
+        // The tag the planned release follows; a forced plan re-releases its own
+        // tag and carries no commits, so that tag's range is read instead
+        let (base, stats) = if plan.forced {
+            let base = tags
+                .iter()
+                .rposition(|t| t.name == plan.tag_name)
+                .and_then(|i| i.checked_sub(1));
+            let current = self.analyze_range(base.map(|i| tags[i].sha.as_str()), &plan.tag_name)?;
+            let stats = ReleaseStats::new(&self.config, &current.commits, current.contributors);
+            (base, stats)
         } else {
-            bump
+            let base = plan
+                .current_version
+                .as_ref()
+                .and_then(|v| tags.iter().rposition(|t| &t.version == v));
+            let stats = ReleaseStats::new(&self.config, &plan.commits, plan.contributors);
+            (base, stats)
         };
-
-        let next_version = if let Some(ref prerelease_id) = self.config.prerelease {
-            let existing_versions: Vec<Version> =
-                all_tags.iter().map(|t| t.version.clone()).collect();
-            apply_prerelease_bump(&base_version, bump, prerelease_id, &existing_versions)
-        } else {
-            apply_bump(&base_version, bump)
+        let previous_release = match base {
+            Some(i) => {
+                let from = i.checked_sub(1).map(|p| tags[p].sha.as_str());
+                let analysis = self.analyze_range(from, &tags[i].name)?;
+                Some(PreviousRelease {
+                    tag: tags[i].name.clone(),
+                    stats: ReleaseStats::new(
+                        &self.config,
+                        &analysis.commits,
+                        analysis.contributors,
+                    ),
+                })
+            }
+            None => None,
         };
+        Ok(ReleaseComparison {
+            stats,
+            previous_release,
+        })
+    }
 
-        let tag_name = format!("{}{next_version}", self.config.tag_prefix);
-
-        // Don't update floating tags for pre-releases
-        let floating_tag_name = if self.config.floating_tags && !is_prerelease {
-            Some(format!("{}{}", self.config.tag_prefix, next_version.major))
-        } else {
-            None
+    /// Release notes for an existing tag (default: the latest tag with the
+    /// configured prefix). Returns the tag and the formatted notes.
+    pub fn notes(
+        &self,
+        tag: Option<&str>,
+        repo_url: Option<&str>,
+    ) -> Result<(TagInfo, String), ReleaseError> {
+        let prefix = self.config.tag_prefix.describe();
+        let tags = self.git.all_tags(&self.config.tag_prefix.all())?;
+        let index = match tag {
+            Some(name) => tags.iter().position(|t| t.name == name).ok_or_else(|| {
+                ReleaseError::Git(format!("tag {name} not found with prefix {prefix}"))
+            })?,
+            None => tags
+                .len()
+                .checked_sub(1)
+                .ok_or_else(|| ReleaseError::Git(format!("no tags found with prefix {prefix}")))?,
         };
-
-        Ok(ReleasePlan {
-            current_version,
-            next_version,
-            bump,
-            commits: conventional_commits,
-            tag_name,
-            floating_tag_name,
-            prerelease: is_prerelease,
-        })
+        let entry = self.tag_entry(&tags, index, repo_url)?;
+        let body = self.render_entry(entry, Destination::Release)?;
+        Ok((tags[index].clone(), body))
     }
 
-    fn execute(&self, plan: &ReleasePlan, dry_run: bool) -> Result<(), ReleaseError> {
-        let version_str = plan.next_version.to_string();
+    /// The latest tag under `prefix` (default: the configured `tag_prefix`) and
+    /// its release date, or `None` before the first release.
+    pub fn latest(&self, prefix: Option<&str>) -> Result<Option<(TagInfo, String)>, ReleaseError> {
+        let prefixes = match prefix {
+            Some(prefix) => vec![prefix],
+            None => self.config.tag_prefix.all(),
+        };
+        let Some(tag) = self.git.latest_tag(&prefixes)? else {
+            return Ok(None);
+        };
+        let date = self.tag_date(&tag.name)?;
+        Ok(Some((tag, date)))
+    }
 
-        if dry_run {
-            let changelog_body = self.format_changelog(plan)?;
-            if let Some(ref cmd) = self.config.pre_release_command {
-                eprintln!("[dry-run] Would run pre-release command: {cmd}");
-            }
-            let sign_label = if self.config.sign_tags {
-                " (signed)"
-            } else {
-                ""
-            };
-            eprintln!("[dry-run] Would create tag: {}{sign_label}", plan.tag_name);
-            eprintln!("[dry-run] Would push tag: {}", plan.tag_name);
-            if let Some(ref floating) = plan.floating_tag_name {
-                eprintln!("[dry-run] Would create/update floating tag: {floating}");
-                eprintln!("[dry-run] Would force-push floating tag: {floating}");
-            }
-            if self.vcs.is_some() {
-                let draft_label = if self.config.draft { " (draft)" } else { "" };
-                let release_name = self.release_name(plan);
-                eprintln!(
-                    "[dry-run] Would create GitHub release \"{release_name}\" for {}{draft_label}",
-                    plan.tag_name
-                );
-            }
-            for file in &self.config.version_files {
-                let filename = Path::new(file)
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or_default();
-                if is_supported_version_file(filename) {
-                    eprintln!("[dry-run] Would bump version in: {file}");
-                } else if self.config.version_files_strict {
-                    return Err(ReleaseError::VersionBump(format!(
-                        "unsupported version file: {filename}"
-                    )));
+    /// Create or update the remote release for an existing tag with `body` on
+    /// every provider. A failing provider does not stop the others.
+    pub fn push_notes(&self, tag: &TagInfo, body: &str) -> Result<(), ReleaseError> {
+        let name = self.release_name_for(&tag.version, &tag.name, &self.tag_date(&tag.name)?);
+        let prerelease = self.config.release.prerelease.resolve(&tag.version);
+        let mut failures: Vec<String> = Vec::new();
+        for vcs in &self.vcs {
+            let result = vcs.release_exists(&tag.name).and_then(|exists| {
+                if exists {
+                    vcs.update_release(&tag.name, &name, body, prerelease, self.config.draft)
+                        .map(|_| "Updated")
                 } else {
-                    eprintln!("[dry-run] warning: unsupported version file, would skip: {file}");
+                    vcs.create_release(&tag.name, &name, body, prerelease, self.config.draft)
+                        .map(|_| "Created")
                 }
-            }
-            if !self.config.artifacts.is_empty() {
-                let resolved = resolve_artifact_globs(&self.config.artifacts)?;
-                if resolved.is_empty() {
-                    eprintln!("[dry-run] Artifact patterns matched no files");
-                } else {
-                    eprintln!("[dry-run] Would upload {} artifact(s):", resolved.len());
-                    for f in &resolved {
-                        eprintln!("[dry-run]   {f}");
-                    }
+            });
+            match result {
+                Ok(action) => info!("{action} release {} on {}", tag.name, vcs.name()),
+                Err(e) => {
+                    error!("release on {} failed: {e}", vcs.name());
+                    failures.push(format!("{}: {e}", vcs.name()));
                 }
             }
-            if let Some(ref cmd) = self.config.build_command {
-                eprintln!("[dry-run] Would run build command: {cmd}");
-            }
-            if !self.config.stage_files.is_empty() {
-                eprintln!(
-                    "[dry-run] Would stage additional files: {}",
-                    self.config.stage_files.join(", ")
-                );
-            }
-            if let Some(ref cmd) = self.config.post_release_command {
-                eprintln!("[dry-run] Would run post-release command: {cmd}");
-            }
-            eprintln!("[dry-run] Changelog:\n{changelog_body}");
-            return Ok(());
         }
+        if !failures.is_empty() {
+            return Err(ReleaseError::Vcs(format!(
+                "release {} failed on {} of {} provider(s): {}",
+                tag.name,
+                failures.len(),
+                self.vcs.len(),
+                failures.join("; ")
+            )));
+        }
+        Ok(())
+    }
 
-        // 0. Run pre-release command if configured
-        if let Some(ref cmd) = self.config.pre_release_command {
-            eprintln!("Running pre-release command: {cmd}");
-            run_lifecycle_hook(cmd, &version_str, &plan.tag_name, "pre_release_command")?;
+    /// Upload files to the release of an existing tag (default: the latest
+    /// tag) on every provider, replacing assets of the same name. `patterns`
+    /// are globs; empty means the configured `artifacts`. A failing provider
+    /// does not stop the others.
+    pub fn upload(
+        &self,
+        tag: Option<&str>,
+        patterns: &[String],
+        options: UploadOptions,
+    ) -> Result<Vec<UploadedAssets>, ReleaseError> {
+        let prefix = self.config.tag_prefix.describe();
+        let tags = self.git.all_tags(&self.config.tag_prefix.all())?;
+        let tag = match tag {
+            Some(name) => tags.iter().find(|t| t.name == name).ok_or_else(|| {
+                ReleaseError::Git(format!("tag {name} not found with prefix {prefix}"))
+            })?,
+            None => tags
+                .last()
+                .ok_or_else(|| ReleaseError::Git(format!("no tags found with prefix {prefix}")))?,
+        };
+        // A dry-run without a token has no providers; it still lists the files
+        if self.vcs.is_empty() && !options.dry_run {
+            return Err(ReleaseError::Vcs(
+                "no VCS provider configured to upload to".into(),
+            ));
         }
 
-        // 1. Format changelog
-        let changelog_body = self.format_changelog(plan)?;
-
-        // 2. Snapshot files before mutation (for rollback on failure)
-        let mut file_snapshots: Vec<(String, Option<String>)> = Vec::new();
-        for file in &self.config.version_files {
-            let path = Path::new(file);
-            let contents = if path.exists() {
-                Some(
-                    fs::read_to_string(path)
-                        .map_err(|e| ReleaseError::VersionBump(e.to_string()))?,
-                )
-            } else {
-                None
-            };
-            file_snapshots.push((file.clone(), contents));
-        }
-        if let Some(ref changelog_file) = self.config.changelog.file {
-            let path = Path::new(changelog_file);
-            let contents = if path.exists() {
-                Some(fs::read_to_string(path).map_err(|e| ReleaseError::Changelog(e.to_string()))?)
+        let mut files = if patterns.is_empty() {
+            resolve_artifact_globs(&crate::artifacts::globs(&self.config))?
+        } else {
+            resolve_artifact_globs(patterns)?
+        };
+        let planned = if patterns.is_empty() {
+            crate::artifacts::planned(
+                &self.config,
+                &self.config.format_version(&tag.version),
+                &tag.name,
+            )?
+        } else {
+            Vec::new()
+        };
+        crate::artifacts::check_unique_names(&files, &planned, &[])?;
+        if files.is_empty() && planned.is_empty() {
+            return Err(ReleaseError::Artifacts(if patterns.is_empty() {
+                "no files to upload: pass globs or configure artifacts".into()
             } else {
-                None
-            };
-            file_snapshots.push((changelog_file.clone(), contents));
+                format!("no files match {}", patterns.join(", "))
+            }));
         }
 
-        // Run the mutable pre-commit steps with rollback on failure
-        let bumped_files = match self.execute_pre_commit(plan, &version_str, &changelog_body) {
-            Ok(files) => files,
-            Err(e) => {
-                eprintln!("error during pre-commit steps, restoring files...");
-                restore_snapshots(&file_snapshots);
-                return Err(e);
-            }
-        };
-
-        // 4. Resolve stage_files globs and collect all paths to stage
-        {
-            let mut paths_to_stage: Vec<String> = Vec::new();
-            if let Some(ref changelog_file) = self.config.changelog.file {
-                paths_to_stage.push(changelog_file.clone());
+        if options.dry_run {
+            for file in &files {
+                let size = fs::metadata(file).map(|m| m.len()).unwrap_or(0);
+                info!("[dry-run] Would upload {file} ({size} bytes)");
             }
-            for file in &bumped_files {
-                paths_to_stage.push(file.clone());
+            for artifact in &planned {
+                info!("[dry-run] Would upload {}", artifact.name());
             }
-            if !self.config.stage_files.is_empty() {
-                let extra = resolve_glob_patterns(&self.config.stage_files)?;
-                paths_to_stage.extend(extra);
+            if options.checksums {
+                info!("[dry-run] Would upload a .sha256 checksum for each file");
             }
-            if !paths_to_stage.is_empty() {
-                let refs: Vec<&str> = paths_to_stage.iter().map(|s| s.as_str()).collect();
-                let commit_msg = format!("chore(release): {} [skip ci]", plan.tag_name);
-                self.git.stage_and_commit(&refs, &commit_msg)?;
+            for vcs in &self.vcs {
+                let action = match vcs.release_exists(&tag.name) {
+                    Ok(true) => "upload to the existing release",
+                    Ok(false) if options.create_missing => {
+                        "create the missing release and upload to it"
+                    }
+                    Ok(false) => "fail: no release exists (pass --create-missing)",
+                    Err(e) => {
+                        warn!(
+                            "could not check for an existing release of {} on {}: {e}",
+                            tag.name,
+                            vcs.name()
+                        );
+                        continue;
+                    }
+                };
+                info!("[dry-run] Would {action} {} on {}", tag.name, vcs.name());
             }
+            return Ok(Vec::new());
         }
 
-        // 5. Create tag (skip if it already exists locally)
-        if !self.git.tag_exists(&plan.tag_name)? {
-            let tag_message = format!("{}\n\n{}", plan.tag_name, changelog_body);
-            self.git
-                .create_tag(&plan.tag_name, &tag_message, self.config.sign_tags)?;
+        let prepared = crate::artifacts::prepare(planned, self.download.as_deref())?;
+        files.extend(prepared.files.iter().cloned());
+        let checksum_files = if options.checksums {
+            generate_checksums(&files)?
+        } else {
+            Vec::new()
+        };
+        let result = self.upload_to_providers(tag, &files, &checksum_files, options);
+        for path in &checksum_files {
+            let _ = fs::remove_file(path);
         }
+        result
+    }
 
-        // 6. Push commit (safe to re-run — no-op if up to date)
-        self.git.push()?;
-
-        // 7. Push tag (skip if tag already exists on remote)
-        if !self.git.remote_tag_exists(&plan.tag_name)? {
-            self.git.push_tag(&plan.tag_name)?;
+    /// The upload step of [`Self::upload`] on every provider, failures
+    /// aggregated as in [`Self::push_notes`].
+    fn upload_to_providers(
+        &self,
+        tag: &TagInfo,
+        files: &[String],
+        checksum_files: &[String],
+        options: UploadOptions,
+    ) -> Result<Vec<UploadedAssets>, ReleaseError> {
+        let uploads: Vec<&str> = files
+            .iter()
+            .chain(checksum_files)
+            .map(|s| s.as_str())
+            .collect();
+        let mut uploaded = Vec::new();
+        let mut failures: Vec<String> = Vec::new();
+        for vcs in &self.vcs {
+            let result = self
+                .upload_release_for(vcs.as_ref(), tag, options.create_missing)
+                .and_then(|release| {
+                    self.upload_to_release(vcs.as_ref(), &release, &uploads, checksum_files.len())
+                });
+            match result {
+                Ok(listed) => {
+                    let urls = uploads
+                        .iter()
+                        .map(|file| {
+                            let name = Path::new(file).file_name().map_or_else(
+                                || file.to_string(),
+                                |n| n.to_string_lossy().into_owned(),
+                            );
+                            listed
+                                .iter()
+                                .flatten()
+                                .find(|a| a.name == name)
+                                .map_or(name, |a| a.url.clone())
+                        })
+                        .collect();
+                    uploaded.push(UploadedAssets {
+                        provider: vcs.name(),
+                        urls,
+                    });
+                }
+                Err(e) => {
+                    error!("upload to {} failed: {e}", vcs.name());
+                    failures.push(format!("{}: {e}", vcs.name()));
+                }
+            }
+        }
+        if !failures.is_empty() {
+            return Err(ReleaseError::Vcs(format!(
+                "upload to {} failed on {} of {} provider(s): {}",
+                tag.name,
+                failures.len(),
+                self.vcs.len(),
+                failures.join("; ")
+            )));
         }
+        Ok(uploaded)
+    }
 
-        // 8. Force-create and force-push floating tag (e.g. v3)
-        if let Some(ref floating) = plan.floating_tag_name {
-            self.git.force_create_tag(floating)?;
-            self.git.force_push_tag(floating)?;
+    /// The release of `tag` on `vcs`. A missing one is an error unless
+    /// `create_missing`, in which case it is created with the annotated tag's
+    /// message as its body, or the generated notes for a lightweight tag.
+    fn upload_release_for(
+        &self,
+        vcs: &dyn VcsProvider,
+        tag: &TagInfo,
+        create_missing: bool,
+    ) -> Result<RemoteRelease, ReleaseError> {
+        if vcs.release_exists(&tag.name)? {
+            let url = vcs
+                .repo_url()
+                .map(|url| format!("{url}/releases/tag/{}", tag.name))
+                .unwrap_or_default();
+            return Ok(RemoteRelease {
+                tag: tag.name.clone(),
+                id: None,
+                url,
+            });
+        }
+        if !create_missing {
+            return Err(ReleaseError::Vcs(format!(
+                "no release for {} (pass --create-missing to create it)",
+                tag.name
+            )));
         }
+        let body = match self.git.tag_message(&tag.name)? {
+            // sr writes the tag name as the message's first line
+            Some(message) => message
+                .strip_prefix(&tag.name)
+                .map_or(message.as_str(), str::trim_start)
+                .trim_end()
+                .to_string(),
+            None => self.notes(Some(&tag.name), vcs.repo_url().as_deref())?.1,
+        };
+        let name = self.release_name_for(&tag.version, &tag.name, &self.tag_date(&tag.name)?);
+        let prerelease = self.config.release.prerelease.resolve(&tag.version);
+        let release = vcs.create_release(&tag.name, &name, &body, prerelease, self.config.draft)?;
+        info!("Created release {} on {}", tag.name, vcs.name());
+        Ok(release)
+    }
 
-        // 9. Create or update GitHub release
-        let release_name = self.release_name(plan);
-        if let Some(ref vcs) = self.vcs {
-            if vcs.release_exists(&plan.tag_name)? {
-                // PATCH update preserves existing assets
-                vcs.update_release(
-                    &plan.tag_name,
-                    &release_name,
-                    &changelog_body,
-                    plan.prerelease,
-                    self.config.draft,
-                )?;
-            } else {
-                vcs.create_release(
-                    &plan.tag_name,
-                    &release_name,
-                    &changelog_body,
-                    plan.prerelease,
-                    self.config.draft,
-                )?;
+    /// Dirty paths other than the files this release is about to modify
+    /// (version files, the changelog, and `stage_files` globs) and the
+    /// summary the last release left behind.
+    fn unmanaged_dirty_paths(&self) -> Result<Vec<String>, ReleaseError> {
+        let root = self.workdir()?;
+        let normalize = |p: &str| {
+            repo_relative(&root, p).unwrap_or_else(|| p.trim_start_matches("./").to_string())
+        };
+        let mut managed: Vec<String> = self
+            .config
+            .version_files
+            .iter()
+            .map(|f| normalize(f))
+            .collect();
+        managed.extend(self.config.changelog_files().into_iter().map(normalize));
+        managed.extend(self.config.summary.file.as_deref().map(normalize));
+        let stage_patterns: Vec<glob::Pattern> = self
+            .config
+            .stage_files
+            .iter()
+            .filter_map(|p| glob::Pattern::new(p.trim_start_matches("./")).ok())
+            .collect();
+
+        Ok(self
+            .git
+            .dirty_paths()?
+            .into_iter()
+            .filter(|path| {
+                !managed.contains(path) && !stage_patterns.iter().any(|p| p.matches(path))
+            })
+            .collect())
+    }
+
+    /// The work tree root, with symlinks resolved so it compares with [`resolve_path`].
+    fn workdir(&self) -> Result<std::path::PathBuf, ReleaseError> {
+        let root = self.git.workdir()?;
+        Ok(root.canonicalize().unwrap_or(root))
+    }
+
+    /// Refuse version files and changelogs outside the work tree (absolute
+    /// paths elsewhere, or `..` escaping it): git cannot commit them.
+    fn check_paths_in_repository(&self) -> Result<(), ReleaseError> {
+        if self.options.tag_only {
+            return Ok(());
+        }
+        let root = self.workdir()?;
+        let files = self
+            .config
+            .version_files
+            .iter()
+            .map(|f| ("version_files", f.as_str()))
+            .chain(
+                self.config
+                    .changelog
+                    .file
+                    .iter()
+                    .map(|f| ("changelog.file", f.as_str())),
+            )
+            .chain(
+                self.config
+                    .aggregate_changelog
+                    .iter()
+                    .map(|f| ("changelog.aggregate", f.as_str())),
+            );
+        for (key, file) in files {
+            if repo_relative(&root, file).is_none() {
+                return Err(ReleaseError::Config(format!(
+                    "{key}: '{file}' is outside the repository ({}); git cannot commit it",
+                    root.display()
+                )));
             }
         }
+        Ok(())
+    }
 
-        // 10. Upload artifacts (with SHA256 checksums)
-        if let Some(ref vcs) = self.vcs
-            && !self.config.artifacts.is_empty()
-        {
-            let resolved = resolve_artifact_globs(&self.config.artifacts)?;
-            if !resolved.is_empty() {
-                // Generate SHA256 checksum sidecar files
-                let checksum_files = generate_checksums(&resolved)?;
-                let mut all_files = resolved.clone();
-                all_files.extend(checksum_files.iter().cloned());
-
-                let file_refs: Vec<&str> = all_files.iter().map(|s| s.as_str()).collect();
-                vcs.upload_assets(&plan.tag_name, &file_refs)?;
-                eprintln!(
-                    "Uploaded {} artifact(s) + {} checksum(s) to {}",
-                    resolved.len(),
-                    checksum_files.len(),
-                    plan.tag_name
-                );
+    /// Undo a release: delete its remote releases and the tag (locally and on
+    /// origin), re-point the floating major tag at the previous release of that
+    /// major, and optionally revert the release commit.
+    ///
+    /// Each step reports what it did and skips state that is already undone, so a
+    /// rollback that failed halfway can simply be re-run.
+    pub fn rollback(
+        &self,
+        tag: &str,
+        revert_commit: bool,
+        dry_run: bool,
+    ) -> Result<(), ReleaseError> {
+        let prefixes = self.config.tag_prefix.all();
+        let (_, version) = crate::git::parse_prefixed_tag(tag, &prefixes, self.config.lenient_tags)
+            .ok_or_else(|| {
+                ReleaseError::Config(format!(
+                    "{tag} is not a release tag (expected {}<semver>)",
+                    self.config.tag_prefix.primary()
+                ))
+            })?;
+        let tags = self.git.all_tags(&prefixes)?;
+        let local = tags.iter().find(|t| t.name == tag);
+        let on_remote = self.git.remote_tag_exists(tag)?;
+        let would = if dry_run {
+            "[dry-run] Would delete"
+        } else {
+            "Deleted"
+        };
 
-                // Clean up generated checksum files
-                for f in &checksum_files {
-                    let _ = fs::remove_file(f);
-                }
+        // 1. Remote releases
+        for vcs in &self.vcs {
+            let name = vcs.name();
+            if !vcs.release_exists(tag)? {
+                info!("Release {tag} on {name}: already deleted");
+                continue;
             }
+            if !dry_run {
+                vcs.delete_release(tag)?;
+            }
+            info!("{would} release {tag} on {name}");
         }
 
-        // 11. Verify release was created/updated successfully
-        if let Some(ref vcs) = self.vcs
-            && let Err(e) = vcs.verify_release(&plan.tag_name)
-        {
-            eprintln!("warning: post-release verification failed: {e}");
-            eprintln!(
-                "  The tag {} was pushed but the GitHub release may be incomplete.",
-                plan.tag_name
-            );
-            eprintln!("  Re-run with --force to retry.");
+        // 2. Release commit (before the tag goes, so a re-run can still find it)
+        if revert_commit {
+            self.rollback_release_commit(tag, local, &tags, dry_run)?;
         }
 
-        // 12. Sync floating tag release with versioned release assets
-        if let Some(ref floating) = plan.floating_tag_name
-            && let Some(ref vcs) = self.vcs
-            && let Err(e) = vcs.sync_floating_release(floating, &plan.tag_name)
-        {
-            eprintln!("warning: failed to sync floating release {floating}: {e}");
+        // 3. The tag itself
+        if local.is_some() || self.git.tag_exists(tag)? {
+            if !dry_run {
+                self.git.delete_tag(tag)?;
+            }
+            info!("{would} local tag {tag}");
+        } else {
+            info!("Local tag {tag}: already deleted");
         }
-
-        // 13. Run post-release command if configured
-        if let Some(ref cmd) = self.config.post_release_command {
-            eprintln!("Running post-release command: {cmd}");
-            run_lifecycle_hook(cmd, &version_str, &plan.tag_name, "post_release_command")?;
+        if on_remote {
+            if !dry_run {
+                self.git.delete_remote_tag(tag)?;
+            }
+            info!("{would} tag {tag} on origin");
+        } else {
+            info!("Tag {tag} on origin: already deleted");
         }
 
-        eprintln!("Released {}", plan.tag_name);
+        // 4. Floating major tag
+        if version.pre.is_empty() {
+            self.rollback_floating_tag(&version, &tags, dry_run)?;
+        }
         Ok(())
     }
-}
 
-impl<G, V, C, F> TrunkReleaseStrategy<G, V, C, F>
-where
-    G: GitRepository,
-    V: VcsProvider,
-    C: CommitParser,
-    F: ChangelogFormatter,
-{
-    /// Execute the mutable pre-commit steps: bump version files, write changelog, run build command.
-    /// Returns the list of bumped files on success. On error the caller restores snapshots.
-    fn execute_pre_commit(
+    /// Revert the commit `tag` points at, if it is a release commit.
+    fn rollback_release_commit(
         &self,
-        plan: &ReleasePlan,
-        version_str: &str,
-        changelog_body: &str,
-    ) -> Result<Vec<String>, ReleaseError> {
-        // 2. Bump version files
-        let mut bumped_files: Vec<String> = Vec::new();
-        for file in &self.config.version_files {
-            match bump_version_file(Path::new(file), version_str) {
-                Ok(extra) => {
-                    bumped_files.push(file.clone());
-                    for extra_path in extra {
-                        bumped_files.push(extra_path.to_string_lossy().into_owned());
-                    }
-                }
-                Err(e) if !self.config.version_files_strict => {
-                    eprintln!("warning: {e} — skipping {file}");
+        tag: &str,
+        local: Option<&TagInfo>,
+        tags: &[TagInfo],
+        dry_run: bool,
+    ) -> Result<(), ReleaseError> {
+        let Some(info) = local else {
+            info!("Tag {tag} not found locally; cannot locate the release commit to revert");
+            return Ok(());
+        };
+        let previous = tags
+            .iter()
+            .rfind(|t| t.version < info.version)
+            .map(|t| t.sha.as_str());
+        let commits = self.git.commits_between(previous, &info.sha)?;
+        let short = &info.sha[..info.sha.len().min(7)];
+        match commits.iter().find(|c| c.sha == info.sha) {
+            Some(commit)
+                if self
+                    .config
+                    .release_commit_matcher()
+                    .matches(&commit.message) =>
+            {
+                if dry_run {
+                    info!("[dry-run] Would revert release commit {short} and push");
+                } else {
+                    self.git.revert_commit(&info.sha)?;
+                    self.git.push()?;
+                    info!("Reverted release commit {short} and pushed");
                 }
-                Err(e) => return Err(e),
             }
-        }
-
-        // 2.5. Auto-discover and stage lock files associated with bumped manifests
-        for lock_file in discover_lock_files(&bumped_files) {
-            let lock_str = lock_file.to_string_lossy().into_owned();
-            if !bumped_files.contains(&lock_str) {
-                bumped_files.push(lock_str);
+            _ => {
+                info!("Commit {short} tagged {tag} is not a release commit; nothing to revert")
             }
         }
+        Ok(())
+    }
 
-        // 3. Write changelog file if configured
-        if let Some(ref changelog_file) = self.config.changelog.file {
-            let path = Path::new(changelog_file);
-            let existing = if path.exists() {
-                fs::read_to_string(path).map_err(|e| ReleaseError::Changelog(e.to_string()))?
-            } else {
-                String::new()
-            };
-            let new_content = if existing.is_empty() {
-                format!("# Changelog\n\n{changelog_body}\n")
-            } else {
-                match existing.find("\n\n") {
-                    Some(pos) => {
-                        let (header, rest) = existing.split_at(pos);
-                        format!("{header}\n\n{changelog_body}\n{rest}")
-                    }
-                    None => format!("{existing}\n\n{changelog_body}\n"),
-                }
-            };
-            fs::write(path, new_content).map_err(|e| ReleaseError::Changelog(e.to_string()))?;
+    /// Point `v<major>` back at the previous release of that major, or delete it
+    /// when the rolled-back tag was the only one. Left alone if a newer release exists.
+    fn rollback_floating_tag(
+        &self,
+        version: &Version,
+        tags: &[TagInfo],
+        dry_run: bool,
+    ) -> Result<(), ReleaseError> {
+        let floating = format!("{}{}", self.config.tag_prefix.primary(), version.major);
+        if !self.config.floating_tags && !self.git.tag_exists(&floating)? {
+            return Ok(());
         }
-
-        // 3.5. Run build command if configured
-        if let Some(ref cmd) = self.config.build_command {
-            eprintln!("Running build command: {cmd}");
-            run_lifecycle_hook(cmd, version_str, &plan.tag_name, "build_command")?;
+        let same_major =
+            |t: &&TagInfo| t.version.major == version.major && t.version.pre.is_empty();
+        if let Some(newer) = tags
+            .iter()
+            .filter(same_major)
+            .find(|t| t.version > *version)
+        {
+            info!(
+                "Floating tag {floating}: left alone ({} is newer)",
+                newer.name
+            );
+            return Ok(());
         }
-
-        Ok(bumped_files)
-    }
-}
-
-/// Restore file contents from snapshots (best-effort, used during rollback).
-fn restore_snapshots(snapshots: &[(String, Option<String>)]) {
-    for (file, contents) in snapshots {
-        let path = Path::new(file);
-        match contents {
-            Some(data) => {
-                if let Err(e) = fs::write(path, data) {
-                    eprintln!("warning: failed to restore {file}: {e}");
+        let previous = tags
+            .iter()
+            .filter(same_major)
+            .rfind(|t| t.version < *version);
+
+        match previous {
+            Some(prev) => {
+                if dry_run {
+                    info!(
+                        "[dry-run] Would move floating tag {floating} to {}",
+                        prev.name
+                    );
+                    return Ok(());
+                }
+                self.git.force_create_tag_at(&floating, &prev.sha)?;
+                self.git.force_push_tag(&floating)?;
+                for vcs in &self.vcs {
+                    if let Err(e) = vcs.sync_floating_release(&floating, &prev.name) {
+                        warn!(
+                            "failed to sync floating release {floating} on {}: {e}",
+                            vcs.name()
+                        );
+                    }
                 }
+                info!("Moved floating tag {floating} to {}", prev.name);
             }
             None => {
-                // File didn't exist before — remove it
-                if path.exists()
-                    && let Err(e) = fs::remove_file(path)
-                {
-                    eprintln!("warning: failed to remove {file}: {e}");
+                let would = if dry_run {
+                    "[dry-run] Would delete"
+                } else {
+                    "Deleted"
+                };
+                for vcs in &self.vcs {
+                    if vcs.release_exists(&floating)? {
+                        if !dry_run {
+                            vcs.delete_release(&floating)?;
+                        }
+                        info!("{would} release {floating} on {}", vcs.name());
+                    }
                 }
-            }
-        }
-    }
-}
-
-/// Run a release lifecycle command with SR_VERSION and SR_TAG env vars.
-fn run_lifecycle_hook(
-    cmd: &str,
-    version: &str,
-    tag: &str,
-    label: &str,
-) -> Result<(), ReleaseError> {
-    crate::hooks::run_shell(cmd, None, &[("SR_VERSION", version), ("SR_TAG", tag)])
-        .map_err(|e| ReleaseError::BuildCommand(format!("{label}: {e}")))
-}
-
-/// Resolve glob patterns into a list of file paths.
-fn resolve_glob_patterns(patterns: &[String]) -> Result<Vec<String>, ReleaseError> {
-    let mut files = Vec::new();
-    for pattern in patterns {
-        let paths = glob::glob(pattern)
-            .map_err(|e| ReleaseError::Config(format!("invalid glob pattern '{pattern}': {e}")))?;
-        for entry in paths {
-            match entry {
-                Ok(path) if path.is_file() => {
-                    files.push(path.to_string_lossy().into_owned());
+                if self.git.tag_exists(&floating)? {
+                    if !dry_run {
+                        self.git.delete_tag(&floating)?;
+                    }
+                    info!("{would} local floating tag {floating}");
                 }
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("warning: glob error: {e}");
+                if self.git.remote_tag_exists(&floating)? {
+                    if !dry_run {
+                        self.git.delete_remote_tag(&floating)?;
+                    }
+                    info!("{would} floating tag {floating} on origin");
                 }
             }
         }
+        Ok(())
     }
-    Ok(files)
-}
 
-fn resolve_artifact_globs(patterns: &[String]) -> Result<Vec<String>, ReleaseError> {
-    let mut files = std::collections::BTreeSet::new();
-    for pattern in patterns {
-        let paths = glob::glob(pattern)
-            .map_err(|e| ReleaseError::Vcs(format!("invalid glob pattern '{pattern}': {e}")))?;
-        for entry in paths {
-            match entry {
-                Ok(path) if path.is_file() => {
-                    files.insert(path.to_string_lossy().into_owned());
-                }
-                Ok(_) => {} // skip directories
-                Err(e) => {
-                    eprintln!("warning: glob error: {e}");
-                }
+    /// Render the release name from `release.name_template`, or from the deprecated
+    /// `release_name_template` when that is set.
+    fn release_name(&self, plan: &ReleasePlan) -> Result<String, ReleaseError> {
+        let date = self.release_date(plan)?;
+        Ok(self.release_name_for(&plan.next_version, &plan.tag_name, &date))
+    }
+
+    /// Release date of `plan`, taken from `changelog.date_source`. Today when
+    /// that source has no date, e.g. a `tag` source before the tag exists.
+    pub fn release_date(&self, plan: &ReleasePlan) -> Result<String, ReleaseError> {
+        let changelog = &self.config.changelog;
+        let seconds = match changelog.date_source {
+            DateSource::Commit => self.git.commit_date(&plan.head_sha),
+            DateSource::Tag => self.git.tag_timestamp(&plan.tag_name),
+            DateSource::Now => return changelog.today(),
+        };
+        match seconds {
+            Ok(seconds) => changelog.format_date(seconds),
+            Err(e) => {
+                debug!(
+                    "no release date from {:?}, using today: {e}",
+                    changelog.date_source
+                );
+                changelog.today()
             }
         }
     }
-    Ok(files.into_iter().collect())
-}
 
-/// Generate SHA256 checksum sidecar files for a list of artifact paths.
-/// Returns the paths to the generated `.sha256` files.
-fn generate_checksums(files: &[String]) -> Result<Vec<String>, ReleaseError> {
-    use sha2::{Digest, Sha256};
+    /// Release date of an existing tag, formatted like a new release's date.
+    fn tag_date(&self, tag_name: &str) -> Result<String, ReleaseError> {
+        let seconds = self.git.tag_timestamp(tag_name)?;
+        self.config.changelog.format_date(seconds)
+    }
 
-    let mut checksum_paths = Vec::new();
-    for file_path in files {
-        let data = fs::read(file_path).map_err(|e| {
-            ReleaseError::Vcs(format!("failed to read {file_path} for checksum: {e}"))
-        })?;
-        let hash = Sha256::digest(&data);
-        let hex = format!("{hash:x}");
-        let file_name = Path::new(file_path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown");
-        let checksum_content = format!("{hex}  {file_name}\n");
-        let checksum_path = format!("{file_path}.sha256");
-        fs::write(&checksum_path, checksum_content)
-            .map_err(|e| ReleaseError::Vcs(format!("failed to write checksum file: {e}")))?;
-        checksum_paths.push(checksum_path);
+    fn release_name_for(&self, version: &Version, tag: &str, date: &str) -> String {
+        if let Some(ref template_str) = self.config.release_name_template {
+            let mut env = minijinja::Environment::new();
+            if env.add_template("release_name", template_str).is_ok()
+                && let Ok(tmpl) = env.get_template("release_name")
+                && let Ok(rendered) = tmpl.render(minijinja::context! {
+                    version => self.config.format_version(version),
+                    tag_name => tag,
+                    display_name => tag_display_name(tag),
+                    tag_prefix => self.config.tag_prefix.primary(),
+                })
+            {
+                return rendered;
+            }
+            warn!("invalid release_name_template, falling back to tag name");
+            return tag.to_string();
+        }
+        self.config
+            .render_release_name(&self.config.format_version(version), tag, date)
     }
-    Ok(checksum_paths)
-}
 
-pub fn today_string() -> String {
-    // Portable date calculation from UNIX epoch (no external deps or subprocess).
-    // Uses Howard Hinnant's civil_from_days algorithm.
-    let secs = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
-
-    let z = secs / 86400 + 719468;
-    let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
-    let doe = (z - era * 146097) as u32;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y = yoe as i64 + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = doy - (153 * mp + 2) / 5 + 1;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 };
-    let y = if m <= 2 { y + 1 } else { y };
-
-    format!("{y:04}-{m:02}-{d:02}")
+    /// Whether the remote release should be marked as a prerelease.
+    fn mark_prerelease(&self, plan: &ReleasePlan) -> bool {
+        self.config.release.prerelease.resolve(&plan.next_version)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::sync::Mutex;
+impl<G, C, F> ReleaseStrategy for TrunkReleaseStrategy<G, C, F>
+where
+    G: GitRepository,
+    C: CommitParser,
+    F: ChangelogFormatter,
+{
+    fn plan(&self) -> Result<ReleasePlan, ReleaseError> {
+        // Shallow clones (e.g. actions/checkout's default fetch-depth: 1) hide
+        // older tags, which would make sr plan a first release over existing history.
+        if self.git.is_shallow()? {
+            if self.offline {
+                warn!(
+                    "offline: repository is a shallow clone and tags were not fetched; \
+                     older tags may be missing from the plan"
+                );
+            } else {
+                self.git.fetch_tags().map_err(|e| {
+                    ReleaseError::Git(format!(
+                        "repository is a shallow clone and full history could not be fetched: \
+                         {e}; clone with full history (e.g. actions/checkout with `fetch-depth: 0`)"
+                    ))
+                })?;
+            }
+        }
 
-    use super::*;
-    use crate::changelog::DefaultChangelogFormatter;
-    use crate::commit::{Commit, DefaultCommitParser};
-    use crate::config::ReleaseConfig;
-    use crate::git::{GitRepository, TagInfo};
+        let is_prerelease = self.config.prerelease.is_some();
 
-    // --- Fakes ---
+        // For stable releases, find the latest stable tag (skip pre-release tags).
+        // For pre-releases, find the latest tag of any kind to determine commits since.
+        let target_sha = match &self.target_ref {
+            Some(rev) => Some(self.git.resolve_rev(rev)?),
+            None => None,
+        };
+        let all_tags = match &target_sha {
+            Some(sha) => self
+                .git
+                .tags_reachable(&self.config.tag_prefix.all(), sha)?,
+            None => self.git.all_tags(&self.config.tag_prefix.all())?,
+        };
+        let latest_stable = all_tags.iter().rev().find(|t| t.version.pre.is_empty());
+        let latest_any = all_tags.last();
 
-    struct FakeGit {
-        tags: Vec<TagInfo>,
-        commits: Vec<Commit>,
-        /// Commits returned when path filtering is active (None = fall back to `commits`).
-        path_commits: Option<Vec<Commit>>,
-        head: String,
-        created_tags: Mutex<Vec<String>>,
-        pushed_tags: Mutex<Vec<String>>,
-        committed: Mutex<Vec<(Vec<String>, String)>>,
-        push_count: Mutex<u32>,
-        force_created_tags: Mutex<Vec<String>>,
-        force_pushed_tags: Mutex<Vec<String>>,
-    }
+        // Use the latest tag (any kind) for commit range, but the latest stable for base version
+        let tag_info = if is_prerelease {
+            latest_any
+        } else {
+            latest_stable.or(latest_any)
+        };
 
-    impl FakeGit {
-        fn new(tags: Vec<TagInfo>, commits: Vec<Commit>) -> Self {
-            let head = tags
-                .last()
-                .map(|t| t.sha.clone())
-                .unwrap_or_else(|| "0".repeat(40));
-            Self {
-                tags,
-                commits,
-                path_commits: None,
-                head,
-                created_tags: Mutex::new(Vec::new()),
-                pushed_tags: Mutex::new(Vec::new()),
-                committed: Mutex::new(Vec::new()),
-                push_count: Mutex::new(0),
-                force_created_tags: Mutex::new(Vec::new()),
-                force_pushed_tags: Mutex::new(Vec::new()),
+        let (current_version, from_sha) = match tag_info {
+            Some(info) => (Some(info.version.clone()), Some(info.sha.as_str())),
+            None => (None, None),
+        };
+
+        let pathspecs = self.config.pathspecs();
+        let refs: Vec<&str> = pathspecs.iter().map(String::as_str).collect();
+        let mut raw_commits = match (&target_sha, refs.is_empty()) {
+            (None, true) => self.git.commits_since(from_sha)?,
+            (None, false) => self.git.commits_since_paths(from_sha, &refs)?,
+            (Some(sha), true) => self.git.commits_between(from_sha, sha)?,
+            (Some(sha), false) => self.git.commits_between_paths(from_sha, sha, &refs)?,
+        };
+        // Commits made by an earlier release (e.g. a re-run on the release
+        // commit itself) are not changes; with nothing else there is nothing
+        // to release rather than nothing releasable.
+        let release_commits = self.config.release_commit_matcher();
+        raw_commits.retain(|c| !release_commits.matches(&c.message));
+        let head_sha = || match &target_sha {
+            Some(sha) => Ok(sha.clone()),
+            None => self.git.head_sha(),
+        };
+        if raw_commits.is_empty() {
+            // Force mode: re-release if HEAD is exactly at the latest tag
+            if self.force
+                && let Some(info) = tag_info
+            {
+                let head = head_sha()?;
+                if head == info.sha {
+                    let floating_tag_name = if self.config.floating_tags {
+                        Some(format!(
+                            "{}{}",
+                            self.config.tag_prefix.primary(),
+                            info.version.major
+                        ))
+                    } else {
+                        None
+                    };
+                    return Ok(ReleasePlan {
+                        schema_version: PLAN_SCHEMA_VERSION,
+                        current_version: Some(info.version.clone()),
+                        next_version: info.version.clone(),
+                        bump: BumpLevel::Patch,
+                        commits: vec![],
+                        tag_name: info.name.clone(),
+                        floating_tag_name,
+                        prerelease: is_prerelease,
+                        head_sha: head,
+                        forced: true,
+                        touched_paths: Vec::new(),
+                        contributors: 0,
+                    });
+                }
             }
+            let (tag, sha) = match tag_info {
+                Some(info) => (info.name.clone(), info.sha.clone()),
+                None => ("(none)".into(), "(none)".into()),
+            };
+            return Err(ReleaseError::NoCommits { tag, sha });
         }
-    }
 
-    impl GitRepository for FakeGit {
-        fn latest_tag(&self, _prefix: &str) -> Result<Option<TagInfo>, ReleaseError> {
-            Ok(self.tags.last().cloned())
+        let mut parsed_commits = Vec::new();
+        let conventional_commits: Vec<ConventionalCommit> = raw_commits
+            .iter()
+            .filter_map(|c| {
+                let parsed = self.parser.parse(c).ok()?;
+                parsed_commits.push(c);
+                Some(parsed)
+            })
+            .collect();
+        let usage = TypeUsage::analyze(&self.config.types, &conventional_commits)
+            .with_unknown_section(self.config.unknown_types_section());
+        for warning in usage.warnings() {
+            warn!("{warning}");
         }
 
-        fn commits_since(&self, _from: Option<&str>) -> Result<Vec<Commit>, ReleaseError> {
-            Ok(self.commits.clone())
+        let classifier = self.config.classifier()?;
+        let tag_for_err = tag_info
+            .map(|i| i.name.clone())
+            .unwrap_or_else(|| "(none)".into());
+        let commit_count = conventional_commits.len();
+        let bump = match determine_bump(&conventional_commits, &classifier) {
+            Some(b) => b,
+            None if self.force => BumpLevel::Patch,
+            None => {
+                return Err(ReleaseError::NoBump {
+                    tag: tag_for_err,
+                    commit_count,
+                });
+            }
+        };
+        if !self.override_policy {
+            self.check_policy(tag_info, &conventional_commits, &classifier)?;
         }
 
-        fn create_tag(&self, name: &str, _message: &str, _sign: bool) -> Result<(), ReleaseError> {
-            self.created_tags.lock().unwrap().push(name.to_string());
-            Ok(())
-        }
+        let (bump, next_version) = if let Some(calver) = self.config.calver()? {
+            if is_prerelease {
+                return Err(ReleaseError::Config(
+                    "prerelease is not supported with versioning: calver".into(),
+                ));
+            }
+            // The bump level only decided that there is something to release
+            let existing: Vec<Version> = all_tags.iter().map(|t| t.version.clone()).collect();
+            let date = self.config.changelog.today_date()?;
+            (bump, calver.next_version(date, &existing))
+        } else {
+            // For pre-releases, base the version on the latest *stable* tag
+            let tagged_version = if is_prerelease {
+                latest_stable
+                    .map(|t| t.version.clone())
+                    .or(current_version.clone())
+            } else {
+                current_version.clone()
+            };
+            // Without a tag, start from the manifests so an adopted project never
+            // releases backwards
+            let mut base_version = tagged_version
+                .or_else(|| self.manifest_version())
+                .unwrap_or(Version::new(0, 0, 0));
+            if let Some(floor) = self.config.version_floor()
+                && floor > base_version
+            {
+                info!("version_floor {floor} is newer than {base_version}; bumping from {floor}");
+                base_version = floor;
+            }
 
-        fn push_tag(&self, name: &str) -> Result<(), ReleaseError> {
-            self.pushed_tags.lock().unwrap().push(name.to_string());
-            Ok(())
-        }
+            // v0 protection: downshift Major → Minor when version is 0.x.y
+            // to prevent accidentally leaving v0. Use --force to bump to v1.
+            let bump = if base_version.major == 0 && bump == BumpLevel::Major && !self.force {
+                info!(
+                    "v0 protection: breaking change detected at v{base_version}, \
+                     downshifting major → minor (use --force to bump to v1)"
+                );
+                BumpLevel::Minor
+            } else {
+                bump
+            };
 
-        fn stage_and_commit(&self, paths: &[&str], message: &str) -> Result<bool, ReleaseError> {
-            self.committed.lock().unwrap().push((
-                paths.iter().map(|s| s.to_string()).collect(),
-                message.to_string(),
-            ));
-            Ok(true)
-        }
+            let next_version = if let Some(ref prerelease_id) = self.config.prerelease {
+                let existing_versions: Vec<Version> =
+                    all_tags.iter().map(|t| t.version.clone()).collect();
+                apply_prerelease_bump(&base_version, bump, prerelease_id, &existing_versions)
+            } else {
+                apply_bump(&base_version, bump)
+            };
+            (bump, next_version)
+        };
 
-        fn push(&self) -> Result<(), ReleaseError> {
-            *self.push_count.lock().unwrap() += 1;
-            Ok(())
-        }
+        let tag_name = format!(
+            "{}{}",
+            self.config.tag_prefix.primary(),
+            self.config.format_version(&next_version)
+        );
 
-        fn tag_exists(&self, name: &str) -> Result<bool, ReleaseError> {
-            Ok(self
-                .created_tags
-                .lock()
-                .unwrap()
-                .contains(&name.to_string()))
-        }
+        // Don't update floating tags for pre-releases
+        let floating_tag_name = if self.config.floating_tags && !is_prerelease {
+            Some(format!(
+                "{}{}",
+                self.config.tag_prefix.primary(),
+                next_version.major
+            ))
+        } else {
+            None
+        };
 
-        fn remote_tag_exists(&self, name: &str) -> Result<bool, ReleaseError> {
-            Ok(self.pushed_tags.lock().unwrap().contains(&name.to_string()))
-        }
+        Ok(ReleasePlan {
+            schema_version: PLAN_SCHEMA_VERSION,
+            current_version,
+            next_version,
+            bump,
+            commits: conventional_commits,
+            tag_name,
+            floating_tag_name,
+            prerelease: is_prerelease,
+            head_sha: head_sha()?,
+            forced: false,
+            touched_paths: touched_paths(&parsed_commits),
+            contributors: contributors(&parsed_commits),
+        })
+    }
 
-        fn all_tags(&self, _prefix: &str) -> Result<Vec<TagInfo>, ReleaseError> {
-            Ok(self.tags.clone())
+    fn execute(&self, plan: &ReleasePlan, dry_run: bool) -> Result<(), ReleaseError> {
+        if dry_run {
+            info!("{}", self.dry_run_report(plan)?.render_human().trim_end());
+            return Ok(());
         }
 
-        fn commits_between(
-            &self,
-            _from: Option<&str>,
-            _to: &str,
-        ) -> Result<Vec<Commit>, ReleaseError> {
-            Ok(self.commits.clone())
-        }
+        self.execute_report(plan).map(drop)
+    }
+}
 
-        fn tag_date(&self, _tag_name: &str) -> Result<String, ReleaseError> {
-            Ok("2026-01-01".into())
+impl<G, C, F> TrunkReleaseStrategy<G, C, F>
+where
+    G: GitRepository,
+    C: CommitParser,
+    F: ChangelogFormatter,
+{
+    /// Carry out `plan` (never a dry run) and return a record of what was done.
+    /// With `summary.file` set, the record is also written there once the
+    /// release has succeeded.
+    pub fn execute_report(&self, plan: &ReleasePlan) -> Result<ReleaseReport, ReleaseError> {
+        let version_str = self.config.format_version(&plan.next_version);
+        let mut report = ReleaseReport::new(plan);
+        report.step("preflight");
+        self.preflight(plan, false)?;
+
+        if plan.forced {
+            info!("Re-releasing existing tag {} (forced)", plan.tag_name);
         }
 
-        fn force_create_tag(&self, name: &str) -> Result<(), ReleaseError> {
-            self.force_created_tags
-                .lock()
-                .unwrap()
-                .push(name.to_string());
-            Ok(())
+        if self.options.no_push {
+            // Nothing leaves the machine with --no-push, so there is nothing to race
+            self.publish(plan, &version_str, &mut report)?;
+        } else {
+            // Hold the lock for the whole release; release it whatever the outcome
+            report.step("acquire lock");
+            let lock = self.acquire_lock(&plan.tag_name)?;
+            let result = self
+                .check_tag_not_taken(plan)
+                .and_then(|()| self.publish(plan, &version_str, &mut report));
+            if let Some(sha) = lock {
+                match self.git.delete_remote_ref(LOCK_REF, &sha) {
+                    Ok(()) => info!("Released lock {LOCK_REF}"),
+                    Err(e) => warn!("failed to release lock {LOCK_REF}: {e}"),
+                }
+            }
+            result?;
         }
 
-        fn force_push_tag(&self, name: &str) -> Result<(), ReleaseError> {
-            self.force_pushed_tags
-                .lock()
-                .unwrap()
-                .push(name.to_string());
-            Ok(())
-        }
+        report.finish();
+        self.write_summary(plan, &report)?;
+        Ok(report)
+    }
 
-        fn head_sha(&self) -> Result<String, ReleaseError> {
-            Ok(self.head.clone())
-        }
+    /// Write `report` to `summary.file` and, with `summary.upload`, attach it to
+    /// the remote release. The release is already out, so a failed upload only warns.
+    fn write_summary(
+        &self,
+        plan: &ReleasePlan,
+        report: &ReleaseReport,
+    ) -> Result<(), ReleaseError> {
+        let Some(ref file) = self.config.summary.file else {
+            return Ok(());
+        };
+        let json =
+            serde_json::to_string_pretty(report).map_err(|e| ReleaseError::Other(e.into()))?;
+        fs::write(file, format!("{json}\n")).map_err(|e| {
+            ReleaseError::Other(crate::error::anyhow::anyhow!(
+                "failed to write release summary {file}: {e}. The tag {} was pushed and is kept",
+                plan.tag_name
+            ))
+        })?;
+        info!("Wrote release summary to {file}");
 
-        fn commits_since_in_path(
-            &self,
-            _from: Option<&str>,
-            _path: &str,
-        ) -> Result<Vec<Commit>, ReleaseError> {
-            Ok(self
-                .path_commits
-                .clone()
-                .unwrap_or_else(|| self.commits.clone()))
+        if self.config.summary.upload && !self.options.no_push {
+            for vcs in &self.vcs {
+                if let Err(e) = vcs.upload_assets(&plan.tag_name, &[file.as_str()]) {
+                    warn!("failed to upload release summary to {}: {e}", vcs.name());
+                }
+            }
         }
+        Ok(())
     }
 
-    struct FakeVcs {
-        releases: Mutex<Vec<(String, String)>>,
-        deleted_releases: Mutex<Vec<String>>,
-        uploaded_assets: Mutex<Vec<(String, Vec<String>)>>,
-    }
-
-    impl FakeVcs {
-        fn new() -> Self {
-            Self {
-                releases: Mutex::new(Vec::new()),
-                deleted_releases: Mutex::new(Vec::new()),
-                uploaded_assets: Mutex::new(Vec::new()),
+    /// Take the advisory release lock on origin, if `lock.enabled`. Returns the
+    /// lock commit SHA to release afterwards. A held lock fails fast; one past its
+    /// expiry (e.g. left by a crashed job) is taken over.
+    fn acquire_lock(&self, tag: &str) -> Result<Option<String>, ReleaseError> {
+        if !self.config.lock.enabled {
+            return Ok(None);
+        }
+        let now = unix_now();
+        let message = format!(
+            "sr-lock: {tag}\n\nexpires: {}",
+            now + self.config.lock.stale_after
+        );
+        // Second attempt only after removing a stale lock (or one released meanwhile)
+        for _ in 0..2 {
+            if let Some(sha) = self.git.create_remote_ref(LOCK_REF, &message)? {
+                info!("Acquired lock {LOCK_REF}");
+                return Ok(Some(sha));
+            }
+            let Some((sha, held)) = self.git.remote_ref(LOCK_REF)? else {
+                continue;
+            };
+            let holder = held.lines().next().unwrap_or_default();
+            match lock_expiry(&held) {
+                Some(expires) if expires > now => {
+                    return Err(ReleaseError::Conflict(format!(
+                        "another release is in progress ({holder}); {LOCK_REF} on origin \
+                         expires in {}s",
+                        expires - now
+                    )));
+                }
+                _ => {
+                    warn!("taking over stale release lock ({holder})");
+                    self.git.delete_remote_ref(LOCK_REF, &sha)?;
+                }
             }
         }
+        Err(ReleaseError::Conflict(format!(
+            "could not acquire {LOCK_REF}: it changed while a stale lock was being taken over"
+        )))
     }
 
-    impl VcsProvider for FakeVcs {
-        fn create_release(
-            &self,
-            tag: &str,
-            _name: &str,
-            body: &str,
-            _prerelease: bool,
-            _draft: bool,
-        ) -> Result<String, ReleaseError> {
-            self.releases
-                .lock()
-                .unwrap()
-                .push((tag.to_string(), body.to_string()));
-            Ok(format!("https://github.com/test/release/{tag}"))
+    /// Refuse to publish a tag another run pushed since this release was planned.
+    /// A tag that also exists locally came from an earlier attempt of this
+    /// release (re-runs are idempotent), and force mode re-releases on purpose.
+    fn check_tag_not_taken(&self, plan: &ReleasePlan) -> Result<(), ReleaseError> {
+        if self.force
+            || self.git.tag_exists(&plan.tag_name)?
+            || !self.git.remote_tag_exists(&plan.tag_name)?
+        {
+            return Ok(());
         }
+        Err(ReleaseError::Conflict(format!(
+            "tag {} appeared on origin after this release was planned (another release \
+             probably ran concurrently); fetch tags and re-run",
+            plan.tag_name
+        )))
+    }
 
-        fn compare_url(&self, base: &str, head: &str) -> Result<String, ReleaseError> {
-            Ok(format!("https://github.com/test/compare/{base}...{head}"))
+    /// Fail when a required `artifacts` glob matches no files.
+    fn check_required_artifacts(&self) -> Result<(), ReleaseError> {
+        let mut missing = Vec::new();
+        for pattern in crate::artifacts::required_globs(&self.config) {
+            if resolve_artifact_globs(std::slice::from_ref(&pattern))?.is_empty() {
+                missing.push(format!("'{pattern}'"));
+            }
         }
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(ReleaseError::Artifacts(format!(
+            "required pattern(s) {} matched no files",
+            missing.join(", ")
+        )))
+    }
 
-        fn release_exists(&self, tag: &str) -> Result<bool, ReleaseError> {
-            Ok(self.releases.lock().unwrap().iter().any(|(t, _)| t == tag))
+    /// Print the commands that publish a release prepared with `--no-push`.
+    fn print_publish_commands(&self, plan: &ReleasePlan, prefix: &str) {
+        let mut warnings = Vec::new();
+        let commands = self.push_later_commands(plan, &mut warnings);
+        info!("{prefix}To publish it later, run:");
+        for cmd in commands {
+            info!("{prefix}  {cmd}");
+        }
+        for warning in warnings {
+            warn!("{prefix}  {warning}");
         }
+    }
 
-        fn delete_release(&self, tag: &str) -> Result<(), ReleaseError> {
-            self.deleted_releases.lock().unwrap().push(tag.to_string());
-            self.releases.lock().unwrap().retain(|(t, _)| t != tag);
-            Ok(())
+    /// Write the `git.release_notes_ref` note for `plan` on `sha` (the tagged
+    /// commit) and optionally push it. The release is already out, so a
+    /// failure is only a warning.
+    fn annotate_release(
+        &self,
+        plan: &ReleasePlan,
+        sha: Option<&str>,
+        url: Option<&str>,
+        push: bool,
+    ) {
+        let (Some(notes_ref), Some(sha)) = (self.config.git.notes_ref(), sha) else {
+            return;
+        };
+        let date = match self.config.changelog.today_date() {
+            Ok(date) => date.to_string(),
+            Err(e) => {
+                warn!("failed to write release note to {notes_ref}: {e}");
+                return;
+            }
+        };
+        let mut content = format!(
+            "version: {}\ndate: {date}\n",
+            self.config.format_version(&plan.next_version)
+        );
+        if let Some(url) = url {
+            content.push_str(&format!("url: {url}\n"));
+        }
+        let result = self.git.add_note(&notes_ref, sha, &content).and_then(|()| {
+            if push {
+                self.git.push_notes(&notes_ref)
+            } else {
+                Ok(())
+            }
+        });
+        match result {
+            Ok(()) => info!("Added release note for {} to {notes_ref}", plan.tag_name),
+            Err(e) => warn!("failed to write release note to {notes_ref}: {e}"),
         }
+    }
 
-        fn upload_assets(&self, tag: &str, files: &[&str]) -> Result<(), ReleaseError> {
-            self.uploaded_assets.lock().unwrap().push((
-                tag.to_string(),
-                files.iter().map(|s| s.to_string()).collect(),
-            ));
-            Ok(())
+    /// `plan.floating_tag_name`, unless a newer release of the same major
+    /// exists (e.g. `v1.9.1` from a maintenance branch after `v1.10.0`): the
+    /// floating tag then stays where it is, with a warning.
+    fn floating_tag<'a>(&self, plan: &'a ReleasePlan) -> Result<Option<&'a str>, ReleaseError> {
+        let Some(floating) = plan.floating_tag_name.as_deref() else {
+            return Ok(None);
+        };
+        let released = &plan.next_version;
+        let tags = self.git.all_tags(&self.config.tag_prefix.all())?;
+        let newest = tags
+            .iter()
+            .filter(|t| t.version.major == released.major && t.version.pre.is_empty())
+            .max_by(|a, b| a.version.cmp(&b.version));
+        match newest {
+            Some(newest) if newest.version > *released => {
+                warn!(
+                    "not moving floating tag {floating}: {} is newer than {} in the same major",
+                    newest.name, plan.tag_name
+                );
+                Ok(None)
+            }
+            _ => Ok(Some(floating)),
         }
+    }
 
-        fn repo_url(&self) -> Option<String> {
-            Some("https://github.com/test/repo".into())
+    /// Commands that publish a `--no-push` release by hand.
+    fn push_later_commands(&self, plan: &ReleasePlan, warnings: &mut Vec<String>) -> Vec<String> {
+        let mut commands = Vec::new();
+        if !self.options.tag_only {
+            commands.push("git push origin HEAD".to_string());
+        }
+        commands.push(format!("git push origin {}", plan.tag_name));
+        let floating = self.floating_tag(plan).unwrap_or_else(|e| {
+            warnings.push(format!("floating tag left alone: {e}"));
+            None
+        });
+        if let Some(floating) = floating {
+            commands.push(format!("git push --force origin {floating}"));
+        }
+        if let Some(notes_ref) = self.config.git.notes_ref() {
+            commands.push(format!("git push origin {notes_ref}"));
         }
+        if !self.vcs.is_empty() {
+            commands.push("sr release --force   # create the remote release".to_string());
+        }
+        for cmd in self.publish_commands(warnings) {
+            match cmd.cwd {
+                Some(dir) => commands.push(format!("{}   # in {dir}", cmd.run)),
+                None => commands.push(cmd.run),
+            }
+        }
+        commands
     }
 
-    // --- Helpers ---
+    /// Send the configured notifications for `plan`. `error` is the release
+    /// failure, if any. Delivery problems are logged as warnings, never returned.
+    pub fn notify(
+        &self,
+        plan: &ReleasePlan,
+        error: Option<&ReleaseError>,
+        send: &crate::notify::WebhookSender<'_>,
+    ) {
+        if self.config.notifications.is_empty() {
+            return;
+        }
+        let release_url = self
+            .vcs
+            .first()
+            .and_then(|vcs| vcs.repo_url())
+            .map(|url| format!("{url}/releases/tag/{}", plan.tag_name))
+            .unwrap_or_default();
+        let event = crate::notify::NotificationEvent {
+            version: self.config.format_version(&plan.next_version),
+            tag: plan.tag_name.clone(),
+            date: self.release_date(plan).unwrap_or_default(),
+            release_url,
+            changelog: self
+                .format_changelog(plan, Destination::Release)
+                .unwrap_or_default(),
+            error: error.map(ToString::to_string),
+        };
+        crate::notify::send_all(&self.config.notifications, &event, send);
+    }
 
-    fn raw_commit(msg: &str) -> Commit {
-        Commit {
-            sha: "a".repeat(40),
-            message: msg.into(),
+    /// Expanded commands of every publish step; steps that cannot be expanded
+    /// are reported in `warnings`.
+    fn publish_commands(&self, warnings: &mut Vec<String>) -> Vec<crate::publish::PublishCommand> {
+        let mut commands = Vec::new();
+        for step in &self.config.publish {
+            match crate::publish::step_commands(step) {
+                Ok(cmds) => commands.extend(cmds),
+                Err(e) => warnings.push(e.to_string()),
+            }
         }
+        commands
     }
 
-    fn make_strategy(
-        tags: Vec<TagInfo>,
-        commits: Vec<Commit>,
-        config: ReleaseConfig,
-    ) -> TrunkReleaseStrategy<FakeGit, FakeVcs, DefaultCommitParser, DefaultChangelogFormatter>
-    {
-        let types = config.types.clone();
-        let breaking_section = config.breaking_section.clone();
-        let misc_section = config.misc_section.clone();
-        TrunkReleaseStrategy {
-            git: FakeGit::new(tags, commits),
-            vcs: Some(FakeVcs::new()),
-            parser: DefaultCommitParser,
-            formatter: DefaultChangelogFormatter::new(None, types, breaking_section, misc_section),
-            config,
-            force: false,
+    /// Whether `execute` makes a release commit: something is staged unless
+    /// `--tag-only` is set.
+    fn creates_release_commit(&self) -> bool {
+        !self.options.tag_only
+            && (!self.config.changelog_files().is_empty()
+                || !self.config.version_files.is_empty()
+                || !self.config.stage_files.is_empty())
+    }
+
+    /// `config.branches`, or the default branch in their place (see
+    /// [`release_branches`]). The provider is not asked when offline.
+    fn release_branches(&self) -> Result<Vec<String>, ReleaseError> {
+        release_branches(&self.config.branches, &self.git, || {
+            let vcs = self.vcs.first().filter(|_| !self.offline);
+            detect_default_branch(&self.git, vcs.map(|v| v.as_ref()))
+        })
+    }
+
+    /// Branch to push a release commit made on a detached HEAD to.
+    fn detached_push_branch(&self) -> Option<String> {
+        detached_push_branch(
+            self.push_branch.as_deref(),
+            std::env::var("GITHUB_REF_NAME").ok().as_deref(),
+            std::env::var("GITHUB_REF_TYPE").ok().as_deref(),
+        )
+    }
+
+    /// Highest version declared in `version_files`, used as the base when
+    /// there is no tag yet. Unreadable files are skipped.
+    fn manifest_version(&self) -> Option<Version> {
+        let (file, version) = self
+            .config
+            .version_files
+            .iter()
+            .filter_map(|file| {
+                let declared = read_version_file(Path::new(file)).ok().flatten()?;
+                let version = Version::parse(declared.trim_start_matches('v')).ok()?;
+                Some((file, version))
+            })
+            .max_by(|a, b| a.1.cmp(&b.1))?;
+        info!("no release tag found; bumping from {version} declared in {file}");
+        Some(version)
+    }
+
+    /// Checks that must pass before anything is touched. With `dry_run` the
+    /// failures are returned as warnings instead of aborting.
+    fn preflight(&self, plan: &ReleasePlan, dry_run: bool) -> Result<Vec<String>, ReleaseError> {
+        let mut warnings = Vec::new();
+
+        // Another merge may have landed since planning; the tag must go on the analysed commit
+        if !self.ignore_head_drift {
+            let actual = self.git.head_sha()?;
+            if actual != plan.head_sha {
+                let err = ReleaseError::HeadMoved {
+                    planned: plan.head_sha.clone(),
+                    actual,
+                };
+                if !dry_run {
+                    return Err(err);
+                }
+                warnings.push(err.to_string());
+            }
+        }
+
+        if !self.allow_any_branch {
+            let branch = self.git.current_branch()?;
+            let github_ref = std::env::var("GITHUB_REF_NAME").ok();
+            if let Err(e) = check_release_branch(
+                &self.release_branches()?,
+                branch.as_deref(),
+                github_ref.as_deref(),
+            ) {
+                if !dry_run {
+                    return Err(e);
+                }
+                warnings.push(e.to_string());
+            }
+        }
+
+        // A release commit on a detached HEAD is on no branch and would be lost
+        if self.creates_release_commit()
+            && !self.options.no_push
+            && self.git.current_branch()?.is_none()
+            && self.detached_push_branch().is_none()
+        {
+            let e = ReleaseError::Branch(DETACHED_HEAD_COMMIT.into());
+            if !dry_run {
+                return Err(e);
+            }
+            warnings.push(e.to_string());
+        }
+
+        // Without a way to push, the tag and release commit would be stranded locally
+        if !self.options.no_push
+            && let Err(e) = self.git.check_remote_access()
+        {
+            if !dry_run {
+                return Err(e);
+            }
+            warnings.push(e.to_string());
         }
+
+        if let Err(e) = self.check_paths_in_repository() {
+            if !dry_run {
+                return Err(e);
+            }
+            warnings.push(e.to_string());
+        }
+
+        // Uncommitted edits to other files would be swept into the release commit
+        let dirty = self.unmanaged_dirty_paths()?;
+        if !dirty.is_empty() {
+            let list = dirty.join("\n  ");
+            let msg = format!("working tree has uncommitted changes:\n  {list}");
+            if dry_run {
+                warnings.push(msg);
+            } else if self.allow_dirty {
+                warn!("{msg}");
+            } else {
+                return Err(ReleaseError::Git(format!(
+                    "{msg}\ncommit or stash them, or pass --allow-dirty"
+                )));
+            }
+        }
+        Ok(warnings)
     }
 
-    // --- plan() tests ---
+    /// Describe what `execute` would do for `plan` without changing anything.
+    /// Pre-flight failures are reported as warnings.
+    pub fn dry_run_report(&self, plan: &ReleasePlan) -> Result<DryRunReport, ReleaseError> {
+        let mut warnings = self.preflight(plan, true)?;
+        if self.offline {
+            warnings.push("offline: remote release and provider checks skipped".into());
+        }
+        let changelog = self.format_changelog(plan, Destination::Changelog)?;
+        let ExecuteOptions { no_push, tag_only } = self.options;
+        let signing = self.config.effective_signing();
+        // Read-only remote checks, so the report says what a re-run would
+        // really do. Without a provider, or offline, the wording stays generic.
+        let probe = !no_push && !self.offline && !self.vcs.is_empty();
+        let on_remote = probe
+            && self
+                .git
+                .remote_tag_exists(&plan.tag_name)
+                .unwrap_or_else(|e| {
+                    warnings.push(format!(
+                        "could not check whether {} is on origin: {e}",
+                        plan.tag_name
+                    ));
+                    false
+                });
+        let tag = TagAction {
+            name: plan.tag_name.clone(),
+            signed: signing.tags,
+            push: !no_push,
+            on_remote,
+        };
+        // Floating tags are force-pushed whether or not they exist
+        let floating_tag = self.floating_tag(plan)?.map(|name| TagAction {
+            name: name.to_string(),
+            signed: signing.tags,
+            push: !no_push,
+            on_remote: false,
+        });
 
-    #[test]
-    fn plan_no_commits_returns_error() {
-        let s = make_strategy(vec![], vec![], ReleaseConfig::default());
-        let err = s.plan().unwrap_err();
-        assert!(matches!(err, ReleaseError::NoCommits { .. }));
+        let mut notes_error = None;
+        let mut release = if no_push || self.vcs.is_empty() {
+            None
+        } else {
+            let providers = self
+                .vcs
+                .iter()
+                .map(|vcs| {
+                    let exists = if probe {
+                        vcs.release_exists(&plan.tag_name)
+                    } else {
+                        Ok(false)
+                    };
+                    let action = match exists {
+                        Ok(true) => ReleaseActionKind::Update,
+                        Ok(false) => ReleaseActionKind::Create,
+                        Err(e) => {
+                            warnings.push(format!(
+                                "could not check for an existing release on {}: {e}",
+                                vcs.name()
+                            ));
+                            ReleaseActionKind::Create
+                        }
+                    };
+                    ProviderAction {
+                        provider: vcs.name(),
+                        action,
+                        replaced_assets: Vec::new(),
+                    }
+                })
+                .collect();
+            let (notes, notes_file) = match self.release_notes(plan) {
+                Ok(notes) => notes,
+                Err(e) => {
+                    notes_error = Some(e.to_string());
+                    (self.format_changelog(plan, Destination::Release)?, None)
+                }
+            };
+            Some(ReleaseAction {
+                name: self.release_name(plan)?,
+                draft: self.config.draft,
+                prerelease: self.mark_prerelease(plan),
+                providers,
+                notes,
+                notes_file,
+            })
+        };
+        let push_later = if no_push {
+            self.push_later_commands(plan, &mut warnings)
+        } else {
+            Vec::new()
+        };
+
+        let mut version_files = Vec::new();
+        for file in self.config.version_files.iter().filter(|_| !tag_only) {
+            let filename = Path::new(file)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            if !is_supported_version_file(filename) {
+                if self.config.version_files_strict {
+                    return Err(ReleaseError::VersionBump(format!(
+                        "unsupported version file: {filename}"
+                    )));
+                }
+                warnings.push(format!("unsupported version file, would skip: {file}"));
+                continue;
+            }
+            let old_version = read_version_file(Path::new(file)).unwrap_or_else(|e| {
+                warnings.push(e.to_string());
+                None
+            });
+            version_files.push(VersionFileChange {
+                path: file.clone(),
+                old_version,
+                new_version: self.config.format_version(&plan.next_version),
+                url: self.config.version_file_url(
+                    file,
+                    &self.config.format_version(&plan.next_version),
+                    &plan.tag_name,
+                ),
+            });
+        }
+
+        let mut artifacts = Vec::new();
+        let mut built_artifacts = Vec::new();
+        let mut errors: Vec<String> = notes_error.into_iter().collect();
+        if !self.config.artifacts.is_empty() && !no_push {
+            let globs = crate::artifacts::globs(&self.config);
+            let resolved = resolve_artifact_globs(&globs)?;
+            if let Err(e) = self.check_required_artifacts() {
+                let note = if self.config.build_command.is_some() {
+                    " (build_command has not run in this dry run)"
+                } else {
+                    ""
+                };
+                errors.push(format!("{e}{note}"));
+            } else if !globs.is_empty() && resolved.is_empty() {
+                warnings.push("artifact patterns matched no files".into());
+            }
+            for path in resolved {
+                let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                artifacts.push(Artifact { path, size });
+            }
+            match crate::artifacts::planned(
+                &self.config,
+                &self.config.format_version(&plan.next_version),
+                &plan.tag_name,
+            ) {
+                Ok(planned) => built_artifacts = planned,
+                Err(e) => warnings.push(e.to_string()),
+            }
+        }
+
+        let attestations = if no_push {
+            Vec::new()
+        } else {
+            crate::attestations::planned(
+                &self.config,
+                &self.config.format_version(&plan.next_version),
+            )
+        };
+        if !attestations.is_empty() && self.vcs.is_empty() {
+            warnings.push("attestations configured but no VCS provider, would skip them".into());
+        }
+        let globbed: Vec<String> = artifacts.iter().map(|a| a.path.clone()).collect();
+        let attestation_files: Vec<String> = attestations.iter().map(|a| a.file.clone()).collect();
+        if let Err(e) =
+            crate::artifacts::check_unique_names(&globbed, &built_artifacts, &attestation_files)
+        {
+            errors.push(e.to_string());
+        }
+
+        // Name clashes with assets on an existing release are replaced on upload
+        let uploads: Vec<String> = artifacts
+            .iter()
+            .map(|a| {
+                Path::new(&a.path)
+                    .file_name()
+                    .map_or_else(|| a.path.clone(), |n| n.to_string_lossy().into_owned())
+            })
+            .chain(built_artifacts.iter().map(|a| a.name().to_string()))
+            .chain(attestations.iter().map(|a| a.file.clone()))
+            .flat_map(|name| [format!("{name}.sha256"), name])
+            .collect();
+        if let Some(ref mut release) = release
+            && !uploads.is_empty()
+        {
+            for (vcs, provider) in self.vcs.iter().zip(&mut release.providers) {
+                if provider.action != ReleaseActionKind::Update {
+                    continue;
+                }
+                let existing = RemoteRelease {
+                    tag: plan.tag_name.clone(),
+                    id: None,
+                    url: String::new(),
+                };
+                match vcs.release_assets(&existing) {
+                    Ok(Some(assets)) => {
+                        provider.replaced_assets = uploads
+                            .iter()
+                            .filter(|name| assets.iter().any(|a| &a.name == *name))
+                            .cloned()
+                            .collect();
+                        provider.replaced_assets.sort();
+                    }
+                    Ok(None) => {}
+                    Err(e) => warnings.push(format!(
+                        "could not list the assets of the existing release on {}: {e}",
+                        vcs.name()
+                    )),
+                }
+            }
+        }
+
+        let publish = if no_push {
+            Vec::new()
+        } else {
+            self.publish_commands(&mut warnings)
+        };
+        let notifications = if no_push {
+            Vec::new()
+        } else {
+            self.config
+                .notifications
+                .iter()
+                .map(|n| NotificationTarget {
+                    kind: format!("{:?}", n.kind).to_lowercase(),
+                    target: match (&n.url, &n.url_env) {
+                        (Some(url), _) => crate::notify::redact_url(url),
+                        (None, Some(var)) => format!("${var}"),
+                        (None, None) => String::new(),
+                    },
+                })
+                .collect()
+        };
+
+        Ok(DryRunReport {
+            plan: plan.clone(),
+            warnings,
+            errors,
+            tag_only,
+            no_push,
+            pre_release_command: self.config.pre_release_command.clone(),
+            sign_commit: signing.commits && !tag_only,
+            signing_key: signing.key.clone(),
+            lock: self.config.lock.enabled && !no_push,
+            tag,
+            floating_tag,
+            notes_ref: self.config.git.notes_ref(),
+            release,
+            push_later,
+            version_files,
+            artifacts,
+            built_artifacts,
+            attestations,
+            embed_digests: self.config.attestations.embed_digests && !no_push,
+            build_command: self.config.build_command.clone(),
+            stage_files: if tag_only {
+                Vec::new()
+            } else {
+                self.config.stage_files.clone()
+            },
+            publish,
+            post_release_command: self
+                .config
+                .post_release_command
+                .clone()
+                .filter(|_| !no_push),
+            notifications,
+            changelog,
+        })
     }
 
-    #[test]
-    fn plan_no_releasable_returns_error() {
-        let s = make_strategy(
-            vec![],
-            vec![raw_commit("chore: tidy up")],
-            ReleaseConfig::default(),
-        );
-        let err = s.plan().unwrap_err();
-        assert!(matches!(err, ReleaseError::NoBump { .. }));
+    /// Everything after the pre-flight checks: files, commit, tags, and releases.
+    fn publish(
+        &self,
+        plan: &ReleasePlan,
+        version_str: &str,
+        report: &mut ReleaseReport,
+    ) -> Result<(), ReleaseError> {
+        // 0. Run pre-release command if configured
+        debug!(step = 0, "pre-release command");
+        report.step("pre-release command");
+        if let Some(ref cmd) = self.config.pre_release_command {
+            info!("Running pre-release command: {cmd}");
+            run_lifecycle_hook(cmd, version_str, &plan.tag_name, "pre_release_command")?;
+        }
+
+        // 1. Format changelog
+        debug!(step = 1, "format changelog");
+        report.step("format changelog");
+        let changelog_body = self.format_changelog(plan, Destination::Changelog)?;
+        let (notes, _) = self.release_notes(plan)?;
+        report.changelog = changelog_body.clone();
+
+        // 2. Snapshot files before mutation (for rollback on failure)
+        debug!(step = 2, "snapshot files");
+        report.step("snapshot files");
+        let mut file_snapshots: Vec<(String, Option<String>)> = Vec::new();
+        for file in &self.config.version_files {
+            let path = Path::new(file);
+            let contents = if path.exists() {
+                Some(
+                    fs::read_to_string(path)
+                        .map_err(|e| ReleaseError::VersionBump(e.to_string()))?,
+                )
+            } else {
+                None
+            };
+            file_snapshots.push((file.clone(), contents));
+        }
+        for changelog_file in self.config.changelog_files() {
+            let path = Path::new(changelog_file);
+            let contents = if path.exists() {
+                Some(fs::read_to_string(path).map_err(|e| ReleaseError::Changelog(e.to_string()))?)
+            } else {
+                None
+            };
+            file_snapshots.push((changelog_file.to_string(), contents));
+        }
+
+        // Run the mutable pre-commit steps with rollback on failure
+        let bumped_files = match self.execute_pre_commit(plan, version_str, &changelog_body, report)
+        {
+            Ok(files) => files,
+            Err(e) => {
+                info!("error during pre-commit steps, restoring files...");
+                restore_snapshots(&file_snapshots);
+                return Err(e);
+            }
+        };
+
+        // 3.6. Generate attestations now that the build has run, before anything
+        // is committed, so a required generator can still abort the release.
+        debug!(step = 3, "generate attestations");
+        report.step("generate attestations");
+        let attestations = if self.options.no_push || !self.config.attestations.enabled {
+            crate::attestations::GeneratedAttestations::default()
+        } else if self.vcs.is_empty() {
+            warn!("attestations configured but no VCS provider — skipping generation");
+            crate::attestations::GeneratedAttestations::default()
+        } else {
+            match crate::attestations::generate(&self.config, version_str, &plan.tag_name) {
+                Ok(generated) => generated,
+                Err(e) => {
+                    info!("attestation generation failed, restoring files...");
+                    restore_snapshots(&file_snapshots);
+                    return Err(e);
+                }
+            }
+        };
+
+        // 3.7. Build archives, download URL artifacts and copy renamed files,
+        // also before the commit. Asset names must be settled before any upload.
+        debug!(step = 3, "prepare artifacts");
+        report.step("prepare artifacts");
+        let prepared = if self.options.no_push || self.vcs.is_empty() {
+            crate::artifacts::PreparedArtifacts::default()
+        } else {
+            let prepared = crate::artifacts::planned(&self.config, version_str, &plan.tag_name)
+                .and_then(|planned| {
+                    let globbed = resolve_artifact_globs(&crate::artifacts::globs(&self.config))?;
+                    crate::artifacts::check_unique_names(&globbed, &planned, &attestations.files)?;
+                    crate::artifacts::prepare(planned, self.download.as_deref())
+                });
+            match prepared {
+                Ok(prepared) => prepared,
+                Err(e) => {
+                    info!("preparing artifacts failed, restoring files...");
+                    restore_snapshots(&file_snapshots);
+                    return Err(e);
+                }
+            }
+        };
+
+        // 3.8. The build must have produced every required artifact
+        debug!(step = 3, "check artifacts");
+        report.step("check artifacts");
+        if !self.options.no_push
+            && !self.vcs.is_empty()
+            && let Err(e) = self.check_required_artifacts()
+        {
+            info!("required artifacts are missing, restoring files...");
+            restore_snapshots(&file_snapshots);
+            return Err(e);
+        }
+
+        // 4. Resolve stage_files globs and collect all paths to stage
+        debug!(step = 4, "resolve stage_files");
+        report.step("release commit");
+        let committed = {
+            let mut paths_to_stage: Vec<String> = Vec::new();
+            if !self.options.tag_only {
+                paths_to_stage.extend(self.config.changelog_files().into_iter().map(String::from));
+            }
+            for file in &bumped_files {
+                paths_to_stage.push(file.clone());
+            }
+            if !self.config.stage_files.is_empty() {
+                let extra = resolve_glob_patterns(&self.config.stage_files)?;
+                paths_to_stage.extend(extra);
+            }
+            if !paths_to_stage.is_empty() && !self.options.tag_only {
+                let refs: Vec<&str> = paths_to_stage.iter().map(|s| s.as_str()).collect();
+                let date = self.release_date(plan)?;
+                let commit_msg =
+                    self.config
+                        .render_release_commit_message(version_str, &plan.tag_name, &date);
+                let trailers =
+                    self.config
+                        .render_release_commit_trailers(version_str, &plan.tag_name, &date);
+                self.git
+                    .stage_and_commit_with_trailers(&refs, &commit_msg, &trailers)?
+            } else {
+                false
+            }
+        };
+
+        // 5. Create tag (skip if it already exists locally)
+        debug!(step = 5, "create tag");
+        report.step("create tag");
+        let created_tag = !self.git.tag_exists(&plan.tag_name)?;
+        if created_tag {
+            let tag_message = format!("{}\n\n{}", plan.tag_name, changelog_body);
+            self.git.create_tag(
+                &plan.tag_name,
+                &tag_message,
+                self.config.effective_signing().tags,
+            )?;
+        }
+        report.head_sha = Some(self.git.head_sha()?);
+
+        let floating_tag = self.floating_tag(plan)?;
+        if self.options.no_push {
+            if let Some(floating) = floating_tag {
+                self.git.force_create_tag(floating)?;
+            }
+            let url = self
+                .vcs
+                .first()
+                .and_then(|vcs| vcs.repo_url())
+                .map(|url| format!("{url}/releases/tag/{}", plan.tag_name));
+            self.annotate_release(plan, report.head_sha.as_deref(), url.as_deref(), false);
+            info!(
+                "Prepared {} locally; nothing was pushed (--no-push).",
+                plan.tag_name
+            );
+            self.print_publish_commands(plan, "");
+            return Ok(());
+        }
+
+        // 6-7. Push commit and tag. A tag created here that never reached the
+        // remote is deleted again: left behind, a later run would skip creating
+        // it and push it pointing at this, older commit.
+        if let Err(e) = self.push_release(plan, committed, report) {
+            if created_tag {
+                info!("push failed, deleting local tag {}...", plan.tag_name);
+                if let Err(delete) = self.git.delete_tag(&plan.tag_name) {
+                    warn!("failed to delete local tag {}: {delete}", plan.tag_name);
+                }
+            }
+            return Err(e);
+        }
+
+        // 8. Force-create and force-push floating tag (e.g. v3)
+        debug!(step = 8, "floating tag");
+        report.step("floating tag");
+        if let Some(floating) = floating_tag {
+            self.git.force_create_tag(floating)?;
+            self.git.force_push_tag(floating)?;
+        }
+
+        // 9. Resolve artifacts once and generate SHA256 checksum sidecars
+        debug!(step = 9, "resolve artifacts");
+        report.step("resolve artifacts");
+        let release_name = self.release_name(plan)?;
+        let mut assets: Vec<String> = Vec::new();
+        let mut checksum_files: Vec<String> = Vec::new();
+        if self.vcs.is_empty() && !self.config.artifacts.is_empty() {
+            warn!("artifacts configured but no VCS provider — skipping upload");
+        } else if !self.config.artifacts.is_empty() {
+            assets = resolve_artifact_globs(&crate::artifacts::globs(&self.config))?;
+            assets.extend(prepared.files.iter().cloned());
+            for asset in &assets {
+                let size = fs::metadata(asset).map(|m| m.len()).unwrap_or(0);
+                info!("Artifact {asset} ({size} bytes)");
+            }
+        }
+        assets.extend(attestations.files.iter().cloned());
+        if !assets.is_empty() {
+            checksum_files = generate_checksums(&assets)?;
+        }
+        let release_body = if self.config.attestations.embed_digests && !assets.is_empty() {
+            format!(
+                "{}\n\n{}",
+                notes.trim_end(),
+                crate::attestations::digest_table(&assets)?
+            )
+        } else {
+            notes
+        };
+
+        // 10. Create or update the release and upload artifacts on every provider.
+        // A failing provider does not abort the others; failures are reported at the end.
+        debug!(step = 10, "create or update release");
+        report.step("create or update release");
+        let mut failures: Vec<String> = Vec::new();
+        for vcs in &self.vcs {
+            match self.publish_to_provider(
+                vcs.as_ref(),
+                plan,
+                &release_name,
+                &release_body,
+                &assets,
+                &checksum_files,
+            ) {
+                Ok(url) => report.releases.push(ProviderRelease {
+                    provider: vcs.name(),
+                    url,
+                }),
+                Err(e) => {
+                    error!("release on {} failed: {e}", vcs.name());
+                    failures.push(format!("{}: {e}", vcs.name()));
+                    continue;
+                }
+            }
+
+            // 11. Verify release was created/updated successfully
+            debug!(step = 11, "verify release");
+            if let Err(e) = vcs.verify_release(&plan.tag_name) {
+                warn!(
+                    "post-release verification failed on {}: {e}\n  \
+                     The tag {} was pushed but the release may be incomplete.\n  \
+                     Re-run with --force to retry.",
+                    vcs.name(),
+                    plan.tag_name
+                );
+            }
+
+            // 12. Sync floating tag release with versioned release assets
+            debug!(step = 12, "sync floating release");
+            if let Some(floating) = floating_tag
+                && let Err(e) = vcs.sync_floating_release(floating, &plan.tag_name)
+            {
+                warn!(
+                    "failed to sync floating release {floating} on {}: {e}",
+                    vcs.name()
+                );
+            }
+        }
+
+        // Record digests before the generated files are cleaned up
+        for file in assets.iter().chain(&checksum_files) {
+            report
+                .add_asset(file)
+                .map_err(|e| ReleaseError::Vcs(format!("failed to read {file} for digest: {e}")))?;
+        }
+
+        // Clean up generated checksum, attestation, and artifact files
+        for f in &checksum_files {
+            let _ = fs::remove_file(f);
+        }
+        drop(attestations);
+        drop(prepared);
+
+        if !failures.is_empty() {
+            return Err(ReleaseError::Vcs(format!(
+                "release {} failed on {} of {} provider(s): {}",
+                plan.tag_name,
+                failures.len(),
+                self.vcs.len(),
+                failures.join("; ")
+            )));
+        }
+
+        // 12.5. Annotate the tagged commit in the release notes ref
+        debug!(step = 12, "release note");
+        report.step("release note");
+        let url = report.releases.first().map(|r| r.url.clone());
+        self.annotate_release(plan, report.head_sha.as_deref(), url.as_deref(), true);
+
+        // 13. Run publish steps. The tag and release are already out, so a failure
+        // is reported but nothing is rolled back.
+        debug!(step = 13, "publish steps");
+        report.step("publish steps");
+        if !self.config.publish.is_empty() {
+            let failures = crate::publish::run_publish_steps(
+                &self.config.publish,
+                version_str,
+                &plan.tag_name,
+            );
+            if !failures.is_empty() {
+                return Err(ReleaseError::Publish(format!(
+                    "{} of {} step(s) failed: {}. The tag {} was pushed and is kept; re-run the failed steps manually",
+                    failures.len(),
+                    self.config.publish.len(),
+                    failures.join("; "),
+                    plan.tag_name
+                )));
+            }
+        }
+
+        // 14. Run post-release command if configured
+        debug!(step = 14, "post-release command");
+        report.step("post-release command");
+        if let Some(ref cmd) = self.config.post_release_command {
+            info!("Running post-release command: {cmd}");
+            run_lifecycle_hook(cmd, version_str, &plan.tag_name, "post_release_command")?;
+        }
+
+        if self.vcs.is_empty() {
+            info!(
+                "Released {} (tag only — no remote release was created)",
+                plan.tag_name
+            );
+        } else {
+            info!("Released {}", plan.tag_name);
+        }
+        Ok(())
+    }
+
+    /// Create or update the release on a single provider and upload its assets.
+    ///
+    /// A new release with assets is created as a draft where the provider
+    /// supports it, and published only once every asset is uploaded and
+    /// listed, so watchers are never notified of a release without its files.
+    fn publish_to_provider(
+        &self,
+        vcs: &dyn VcsProvider,
+        plan: &ReleasePlan,
+        release_name: &str,
+        changelog_body: &str,
+        assets: &[String],
+        checksum_files: &[String],
+    ) -> Result<String, ReleaseError> {
+        let files: Vec<&str> = assets
+            .iter()
+            .chain(checksum_files)
+            .map(|s| s.as_str())
+            .collect();
+        let prerelease = self.mark_prerelease(plan);
+
+        if vcs.release_exists(&plan.tag_name)? {
+            // PATCH update preserves existing assets
+            let release = vcs.update_release(
+                &plan.tag_name,
+                release_name,
+                changelog_body,
+                prerelease,
+                self.config.draft,
+            )?;
+            self.upload_to_release(vcs, &release, &files, checksum_files.len())?;
+            return Ok(release.url);
+        }
+
+        let staged = !files.is_empty() && vcs.supports_drafts();
+        let release = vcs.create_release(
+            &plan.tag_name,
+            release_name,
+            changelog_body,
+            prerelease,
+            self.config.draft || staged,
+        )?;
+        if let Err(e) = self.upload_to_release(vcs, &release, &files, checksum_files.len()) {
+            if staged {
+                // A draft is not found by tag, so a re-run would create another
+                match vcs.discard_release(&release) {
+                    Ok(()) => info!("Deleted draft release {} on {}", release.tag, vcs.name()),
+                    Err(d) => warn!("failed to delete draft release {}: {d}", release.url),
+                }
+            }
+            return Err(e);
+        }
+        if staged && !self.config.draft {
+            let published = vcs.publish_release(&release)?;
+            info!("Published release {} on {}", published.tag, vcs.name());
+            return Ok(published.url);
+        }
+        Ok(release.url)
+    }
+
+    /// Upload `files` to `release` and check the provider lists each of them
+    /// at its local size. Returns the listed assets, `None` when the provider
+    /// cannot list them.
+    fn upload_to_release(
+        &self,
+        vcs: &dyn VcsProvider,
+        release: &RemoteRelease,
+        files: &[&str],
+        checksums: usize,
+    ) -> Result<Option<Vec<RemoteAsset>>, ReleaseError> {
+        if files.is_empty() {
+            return Ok(None);
+        }
+        vcs.upload_release_assets(release, files)?;
+        let listed = vcs.release_assets(release)?;
+        if let Some(ref listed) = listed {
+            let mut problems = Vec::new();
+            for file in files {
+                let name = Path::new(file)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| file.to_string());
+                let size = fs::metadata(file).map(|m| m.len()).unwrap_or(0);
+                match listed.iter().find(|a| a.name == name) {
+                    Some(asset) if asset.size == size => {}
+                    Some(asset) => problems.push(format!(
+                        "{name} is {} bytes on the release, {size} locally",
+                        asset.size
+                    )),
+                    None => problems.push(format!("{name} is missing from the release")),
+                }
+            }
+            if !problems.is_empty() {
+                return Err(ReleaseError::Vcs(format!(
+                    "uploaded assets do not match: {}",
+                    problems.join("; ")
+                )));
+            }
+        }
+        info!(
+            "Uploaded {} artifact(s) + {checksums} checksum(s) to {} on {}",
+            files.len() - checksums,
+            release.tag,
+            vcs.name()
+        );
+        Ok(listed)
+    }
+
+    /// Steps 6 and 7 of [`Self::publish`]: push the release commit, then the tag.
+    fn push_release(
+        &self,
+        plan: &ReleasePlan,
+        committed: bool,
+        report: &mut ReleaseReport,
+    ) -> Result<(), ReleaseError> {
+        // 6. Push commit (safe to re-run — no-op if up to date). A detached HEAD
+        // without a release commit has nothing to push but the tag.
+        debug!(step = 6, "push commit");
+        report.step("push commit");
+        if self.git.current_branch()?.is_some() {
+            self.git.push()?;
+        } else if committed {
+            let branch = self
+                .detached_push_branch()
+                .ok_or_else(|| ReleaseError::Branch(DETACHED_HEAD_COMMIT.into()))?;
+            info!("HEAD is detached; pushing the release commit to {branch}");
+            self.git.push_to_branch(&branch)?;
+        } else {
+            info!("HEAD is detached and no release commit was made; pushing only the tag");
+        }
+
+        // 7. Push tag (skip if tag already exists on remote)
+        debug!(step = 7, "push tag");
+        report.step("push tag");
+        if !self.git.remote_tag_exists(&plan.tag_name)? {
+            self.git.push_tag(&plan.tag_name)?;
+        }
+        Ok(())
+    }
+
+    /// Execute the mutable pre-commit steps: bump version files, write changelog, run build command.
+    /// Returns the list of bumped files on success. On error the caller restores snapshots.
+    fn execute_pre_commit(
+        &self,
+        plan: &ReleasePlan,
+        version_str: &str,
+        changelog_body: &str,
+        report: &mut ReleaseReport,
+    ) -> Result<Vec<String>, ReleaseError> {
+        // 2. Bump version files
+        debug!(step = 2, "bump version files");
+        report.step("bump version files");
+        let mut bumped_files: Vec<String> = Vec::new();
+        let version_files: &[String] = if self.options.tag_only {
+            &[]
+        } else {
+            &self.config.version_files
+        };
+        for file in version_files {
+            let old_version = read_version_file(Path::new(file)).ok().flatten();
+            let url = self
+                .config
+                .version_file_url(file, version_str, &plan.tag_name);
+            let bumped = match url {
+                Some(ref url) => bump_version_file_with_url(Path::new(file), version_str, url),
+                None => bump_version_file(Path::new(file), version_str),
+            };
+            match bumped {
+                Ok(extra) => {
+                    report.version_files.push(VersionFileChange {
+                        path: file.clone(),
+                        old_version,
+                        new_version: version_str.into(),
+                        url,
+                    });
+                    bumped_files.push(file.clone());
+                    for extra_path in extra {
+                        bumped_files.push(extra_path.to_string_lossy().into_owned());
+                    }
+                }
+                Err(e) if !self.config.version_files_strict => {
+                    warn!("{e} — skipping {file}");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // 2.5. Auto-discover and stage lock files associated with bumped manifests
+        for lock_file in discover_lock_files(&bumped_files) {
+            let lock_str = lock_file.to_string_lossy().into_owned();
+            if !bumped_files.contains(&lock_str) {
+                bumped_files.push(lock_str);
+            }
+        }
+
+        // 3. Write changelog file if configured
+        debug!(step = 3, "write changelog");
+        report.step("write changelog");
+        if let Some(ref changelog_file) = self.config.changelog.file
+            && !self.options.tag_only
+        {
+            let path = Path::new(changelog_file);
+            let existing = if path.exists() {
+                fs::read_to_string(path).map_err(|e| ReleaseError::Changelog(e.to_string()))?
+            } else {
+                String::new()
+            };
+            let new_content = if existing.is_empty() {
+                format!("# Changelog\n\n{changelog_body}\n")
+            } else {
+                match existing.find("\n\n") {
+                    Some(pos) => {
+                        let (header, rest) = existing.split_at(pos);
+                        format!("{header}\n\n{changelog_body}\n{rest}")
+                    }
+                    None => format!("{existing}\n\n{changelog_body}\n"),
+                }
+            };
+            fs::write(path, new_content).map_err(|e| ReleaseError::Changelog(e.to_string()))?;
+        }
+        if let (Some(root), Some(package)) =
+            (&self.config.aggregate_changelog, &self.config.package_name)
+            && !self.options.tag_only
+        {
+            let path = Path::new(root);
+            let existing = if path.exists() {
+                fs::read_to_string(path).map_err(|e| ReleaseError::Changelog(e.to_string()))?
+            } else {
+                String::new()
+            };
+            let subsection =
+                crate::changelog::package_subsection(package, version_str, changelog_body);
+            let date = self.release_date(plan)?;
+            let new_content = crate::changelog::insert_aggregate(&existing, &date, &subsection);
+            fs::write(path, new_content).map_err(|e| ReleaseError::Changelog(e.to_string()))?;
+        }
+
+        // 3.5. Run build command if configured
+        if let Some(ref cmd) = self.config.build_command {
+            report.step("build command");
+            info!("Running build command: {cmd}");
+            run_lifecycle_hook(cmd, version_str, &plan.tag_name, "build_command")?;
+        }
+
+        Ok(bumped_files)
+    }
+}
+
+const DETACHED_HEAD_COMMIT: &str = "HEAD is detached, so the release commit would not land on \
+     any branch; pass --push-branch <name>, check out a branch, or use --tag-only";
+
+/// Branch to push a release commit made on a detached HEAD to: `push_branch`,
+/// else `github_ref_name` (`GITHUB_REF_NAME`) unless `github_ref_type`
+/// (`GITHUB_REF_TYPE`) says a tag triggered the run.
+pub fn detached_push_branch(
+    push_branch: Option<&str>,
+    github_ref_name: Option<&str>,
+    github_ref_type: Option<&str>,
+) -> Option<String> {
+    let from_ci = github_ref_name.filter(|_| github_ref_type != Some("tag"));
+    push_branch
+        .or(from_ci)
+        .filter(|branch| !branch.is_empty())
+        .map(String::from)
+}
+
+/// Where [`detect_default_branch`] learned the default branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultBranchSource {
+    /// `refs/remotes/origin/HEAD` in the local clone.
+    OriginHead,
+    /// The provider's repository API.
+    Provider,
+}
+
+impl std::fmt::Display for DefaultBranchSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DefaultBranchSource::OriginHead => "origin/HEAD",
+            DefaultBranchSource::Provider => "the provider API",
+        })
+    }
+}
+
+/// The repository's default branch and how it was determined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefaultBranch {
+    pub name: String,
+    pub source: DefaultBranchSource,
+}
+
+/// The default branch from `origin/HEAD`, else from `vcs`. A provider that
+/// fails to answer is logged and treated as not knowing.
+pub fn detect_default_branch<G: GitRepository + ?Sized>(
+    git: &G,
+    vcs: Option<&dyn VcsProvider>,
+) -> Result<Option<DefaultBranch>, ReleaseError> {
+    if let Some(name) = git.default_branch()? {
+        return Ok(Some(DefaultBranch {
+            name,
+            source: DefaultBranchSource::OriginHead,
+        }));
+    }
+    let Some(vcs) = vcs else {
+        return Ok(None);
+    };
+    match vcs.default_branch() {
+        Ok(name) => Ok(name.map(|name| DefaultBranch {
+            name,
+            source: DefaultBranchSource::Provider,
+        })),
+        Err(e) => {
+            debug!("could not ask {} for the default branch: {e}", vcs.name());
+            Ok(None)
+        }
+    }
+}
+
+/// The branches a release may run from: `branches`, except that when it is
+/// left at [`DEFAULT_BRANCHES`] and the repository has neither branch, the
+/// default branch `detect` finds takes their place.
+///
+/// [`DEFAULT_BRANCHES`]: crate::config::DEFAULT_BRANCHES
+pub fn release_branches<G: GitRepository + ?Sized>(
+    branches: &[String],
+    git: &G,
+    detect: impl FnOnce() -> Result<Option<DefaultBranch>, ReleaseError>,
+) -> Result<Vec<String>, ReleaseError> {
+    let is_default = branches
+        .iter()
+        .map(String::as_str)
+        .eq(crate::config::DEFAULT_BRANCHES.iter().copied());
+    let exists = |branch: &str| {
+        git.resolve_rev(&format!("refs/heads/{branch}")).is_ok()
+            || git
+                .resolve_rev(&format!("refs/remotes/origin/{branch}"))
+                .is_ok()
+    };
+    if !is_default || branches.iter().any(|b| exists(b)) {
+        return Ok(branches.to_vec());
+    }
+    Ok(match detect()? {
+        Some(default) => {
+            debug!(
+                "neither {} exists; releasing from {} (from {})",
+                branches.join(" nor "),
+                default.name,
+                default.source
+            );
+            vec![default.name]
+        }
+        None => branches.to_vec(),
+    })
+}
+
+/// Verify that a release may run from the current branch.
+///
+/// `branches` entries are exact names or globs (e.g. `release/*`); an empty list
+/// allows any branch. On a detached HEAD (common in CI, which checks out a SHA),
+/// `github_ref_name` (`GITHUB_REF_NAME`) is checked instead.
+pub fn check_release_branch(
+    branches: &[String],
+    current: Option<&str>,
+    github_ref_name: Option<&str>,
+) -> Result<(), ReleaseError> {
+    if branches.is_empty() {
+        return Ok(());
+    }
+    let allowed = branches.join(", ");
+    let (branch, detached) = match (current, github_ref_name) {
+        (Some(branch), _) => (branch, false),
+        (None, Some(name)) if !name.is_empty() => (name, true),
+        (None, _) => {
+            return Err(ReleaseError::Branch(format!(
+                "HEAD is detached and GITHUB_REF_NAME is not set, so the release branch \
+                 cannot be determined (allowed: {allowed}); check out a branch or pass \
+                 --allow-any-branch"
+            )));
+        }
+    };
+    let matches = branches.iter().any(|pattern| {
+        pattern == branch || glob::Pattern::new(pattern).is_ok_and(|p| p.matches(branch))
+    });
+    if matches {
+        return Ok(());
+    }
+    let source = if detached {
+        " (from GITHUB_REF_NAME, HEAD is detached)"
+    } else {
+        ""
+    };
+    Err(ReleaseError::Branch(format!(
+        "refusing to release from branch '{branch}'{source}; allowed branches: {allowed} \
+         (configure `branches` or pass --allow-any-branch)"
+    )))
+}
+
+/// Whether `e` is git reporting that it could not read a tag's history (a
+/// missing or corrupt object), as opposed to git not running at all.
+fn is_unreadable_history(e: &ReleaseError) -> bool {
+    matches!(
+        e,
+        ReleaseError::Git(_)
+            | ReleaseError::GitLibrary { .. }
+            | ReleaseError::GitCommand {
+                status: Some(_),
+                ..
+            }
+    )
+}
+
+/// Resolve the (hostname, owner, repo) to release against.
+///
+/// `repository` and `hostname` from the config take precedence, then the parsed
+/// `origin` remote, then `GITHUB_REPOSITORY` and the host of `GITHUB_SERVER_URL`
+/// (for CI checkouts without a usable remote). Hostname defaults to `github.com`
+/// when only the repository is known.
+pub fn resolve_origin(
+    config: &ReleaseConfig,
+    remote: Result<(String, String, String), ReleaseError>,
+    github_repository: Option<&str>,
+    github_server_host: Option<&str>,
+) -> Result<(String, String, String), ReleaseError> {
+    let configured = config
+        .repository
+        .as_deref()
+        .map(split_repository)
+        .transpose()?;
+    let remote_err = match remote {
+        Ok((hostname, owner, repo)) => {
+            let (owner, repo) = configured.unwrap_or((owner, repo));
+            return Ok((config.hostname.clone().unwrap_or(hostname), owner, repo));
+        }
+        Err(e) => e,
+    };
+    let (owner, repo) = match configured {
+        Some(pair) => pair,
+        None => match github_repository.filter(|r| !r.is_empty()) {
+            Some(r) => split_repository(r)?,
+            None => {
+                return Err(ReleaseError::Git(format!(
+                    "cannot parse remote URL from origin ({remote_err}), and neither \
+                     `repository` in the config nor GITHUB_REPOSITORY is set"
+                )));
+            }
+        },
+    };
+    let hostname = config
+        .hostname
+        .clone()
+        .or_else(|| github_server_host.map(String::from))
+        .unwrap_or_else(|| "github.com".into());
+    Ok((hostname, owner, repo))
+}
+
+fn split_repository(repository: &str) -> Result<(String, String), ReleaseError> {
+    repository
+        .trim_matches('/')
+        .split_once('/')
+        .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty())
+        .map(|(owner, repo)| (owner.to_string(), repo.to_string()))
+        .ok_or_else(|| {
+            ReleaseError::Config(format!("repository must be owner/repo, got '{repository}'"))
+        })
+}
+
+/// Restore file contents from snapshots (best-effort, used during rollback).
+fn restore_snapshots(snapshots: &[(String, Option<String>)]) {
+    for (file, contents) in snapshots {
+        let path = Path::new(file);
+        match contents {
+            Some(data) => {
+                if let Err(e) = fs::write(path, data) {
+                    warn!("failed to restore {file}: {e}");
+                }
+            }
+            None => {
+                // File didn't exist before — remove it
+                if path.exists()
+                    && let Err(e) = fs::remove_file(path)
+                {
+                    warn!("failed to remove {file}: {e}");
+                }
+            }
+        }
+    }
+}
+
+/// Run a release lifecycle command with SR_VERSION and SR_TAG env vars.
+fn run_lifecycle_hook(
+    cmd: &HookCommand,
+    version: &str,
+    tag: &str,
+    label: &str,
+) -> Result<(), ReleaseError> {
+    let mut failures = Vec::new();
+    crate::hooks::run_hook_command(
+        cmd,
+        label,
+        None,
+        &[("SR_VERSION", version), ("SR_TAG", tag)],
+        &mut failures,
+    )
+    .map_err(|e| ReleaseError::BuildCommand(format!("{label}: {e}")))
+}
+
+/// Resolve glob patterns into a list of file paths.
+fn resolve_glob_patterns(patterns: &[String]) -> Result<Vec<String>, ReleaseError> {
+    let mut files = Vec::new();
+    for pattern in patterns {
+        let paths = glob::glob(pattern)
+            .map_err(|e| ReleaseError::Config(format!("invalid glob pattern '{pattern}': {e}")))?;
+        for entry in paths {
+            match entry {
+                Ok(path) if path.is_file() => {
+                    files.push(path.to_string_lossy().into_owned());
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("glob error: {e}");
+                }
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn resolve_artifact_globs(patterns: &[String]) -> Result<Vec<String>, ReleaseError> {
+    let mut files = std::collections::BTreeSet::new();
+    for pattern in patterns {
+        let paths = glob::glob(pattern)
+            .map_err(|e| ReleaseError::Vcs(format!("invalid glob pattern '{pattern}': {e}")))?;
+        for entry in paths {
+            match entry {
+                Ok(path) if path.is_file() => {
+                    files.insert(path.to_string_lossy().into_owned());
+                }
+                Ok(_) => {} // skip directories
+                Err(e) => {
+                    warn!("glob error: {e}");
+                }
+            }
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// `path` as the release touches it (relative paths are taken from the current
+/// directory): absolute, with `..` resolved and symlinks in its existing
+/// ancestors followed, so it compares with a canonical work tree root.
+fn resolve_path(path: &Path) -> std::path::PathBuf {
+    use std::path::{Component, PathBuf};
+
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other),
+        }
+    }
+    // Canonicalize the longest existing prefix; the file itself may not exist yet
+    for ancestor in normalized.ancestors() {
+        if let Ok(canonical) = ancestor.canonicalize() {
+            let rest = normalized.strip_prefix(ancestor).unwrap_or(Path::new(""));
+            return if rest.as_os_str().is_empty() {
+                canonical
+            } else {
+                canonical.join(rest)
+            };
+        }
+    }
+    normalized
+}
+
+/// `path` relative to the work tree `root` (as git reports paths), or `None`
+/// when it resolves outside of it.
+fn repo_relative(root: &Path, path: &str) -> Option<String> {
+    let relative = resolve_path(Path::new(path))
+        .strip_prefix(root)
+        .ok()?
+        .to_string_lossy()
+        .replace('\\', "/");
+    Some(relative)
+}
+
+/// Generate SHA256 checksum sidecar files for a list of artifact paths.
+/// Returns the paths to the generated `.sha256` files.
+fn generate_checksums(files: &[String]) -> Result<Vec<String>, ReleaseError> {
+    let mut checksum_paths = Vec::new();
+    for file_path in files {
+        let hex = crate::attestations::sha256_file(Path::new(file_path)).map_err(|e| {
+            ReleaseError::Vcs(format!("failed to read {file_path} for checksum: {e}"))
+        })?;
+        let file_name = Path::new(file_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let checksum_content = format!("{hex}  {file_name}\n");
+        let checksum_path = format!("{file_path}.sha256");
+        fs::write(&checksum_path, checksum_content)
+            .map_err(|e| ReleaseError::Vcs(format!("failed to write checksum file: {e}")))?;
+        checksum_paths.push(checksum_path);
+    }
+    Ok(checksum_paths)
+}
+
+/// Remote ref holding the advisory release lock. Git refuses single-level
+/// refs like `refs/sr-lock`, hence the extra component.
+pub const LOCK_REF: &str = "refs/sr-lock/release";
+
+/// Seconds as the two largest units, e.g. `1d 2h`, `3h 12m`, `45s`.
+fn human_duration(secs: u64) -> String {
+    let units = [
+        ("w", 604_800),
+        ("d", 86_400),
+        ("h", 3600),
+        ("m", 60),
+        ("s", 1),
+    ];
+    let parts: Vec<String> = units
+        .iter()
+        .scan(secs, |rest, &(unit, size)| {
+            let n = *rest / size;
+            *rest %= size;
+            Some((n, unit))
+        })
+        .filter(|&(n, _)| n > 0)
+        .take(2)
+        .map(|(n, unit)| format!("{n}{unit}"))
+        .collect();
+    if parts.is_empty() {
+        "0s".into()
+    } else {
+        parts.join(" ")
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Parse the `expires: <unix seconds>` line of a lock commit message.
+fn lock_expiry(message: &str) -> Option<u64> {
+    message
+        .lines()
+        .find_map(|line| line.strip_prefix("expires:"))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::changelog::DefaultChangelogFormatter;
+    use crate::commit::{Commit, DefaultCommitParser};
+    use crate::config::{AttestationGenerator, AttestationsConfig, ReleaseConfig};
+    use crate::git::{GitRepository, TagInfo};
+
+    // --- Fakes ---
+
+    struct FakeGit {
+        tags: Vec<TagInfo>,
+        commits: Vec<Commit>,
+        /// Commits returned when path filtering is active (None = fall back to `commits`).
+        path_commits: Option<Vec<Commit>>,
+        /// Pathspecs passed to `commits_since_paths`.
+        seen_pathspecs: Mutex<Vec<String>>,
+        /// (from, to) ranges passed to `commits_between`.
+        seen_ranges: Mutex<Vec<(Option<String>, String)>>,
+        /// Commits returned by `commits_between` (None = fall back to `commits`).
+        range_commits: Option<Vec<Commit>>,
+        /// Commits `commits_between` returns for ranges ending at a given
+        /// revision, ahead of `range_commits`.
+        commits_by_tip: Vec<(String, Vec<Commit>)>,
+        head: String,
+        created_tags: Mutex<Vec<String>>,
+        pushed_tags: Mutex<Vec<String>>,
+        committed: Mutex<Vec<(Vec<String>, String)>>,
+        push_count: Mutex<u32>,
+        pushed_branches: Mutex<Vec<String>>,
+        force_created_tags: Mutex<Vec<String>>,
+        force_pushed_tags: Mutex<Vec<String>>,
+        /// (tag, target) pairs passed to `force_create_tag_at`.
+        retargeted_tags: Mutex<Vec<(String, String)>>,
+        deleted_tags: Mutex<Vec<String>>,
+        deleted_remote_tags: Mutex<Vec<String>>,
+        reverted: Mutex<Vec<String>>,
+        /// (refname, sha, message) of refs created on the fake remote.
+        remote_refs: Mutex<Vec<(String, String, String)>>,
+        /// (notes ref, sha, content) passed to `add_note`.
+        notes: Mutex<Vec<(String, String, String)>>,
+        pushed_notes: Mutex<Vec<String>>,
+        branch: Option<String>,
+        /// Branches `resolve_rev` finds locally or on origin; None = every branch.
+        branches: Option<Vec<String>>,
+        /// Where `origin/HEAD` points.
+        origin_head: Option<String>,
+        dirty: Vec<String>,
+        /// What `workdir` returns; `/` by default so any test path is inside it.
+        workdir: std::path::PathBuf,
+        /// Tags `tags_reachable` leaves out, as if they were on another branch.
+        unreachable_tags: Vec<String>,
+        /// Tags whose history `commits_between` cannot read, as if it were
+        /// garbage-collected.
+        unreadable_tags: Vec<String>,
+        shallow: Mutex<bool>,
+        fetch_fails: bool,
+        /// Whether `push` fails, as if the remote rejected the release commit.
+        push_fails: bool,
+        /// Whether `check_remote_access` fails, as if no `git` could push.
+        no_remote_access: bool,
+        fetch_count: Mutex<u32>,
+        /// What `tag_timestamp` returns for every tag.
+        tag_time: i64,
+        /// What `commit_date` returns for every commit; None = unknown commit.
+        commit_time: Option<i64>,
+        /// (tag, message) of annotated tags; other known tags are lightweight.
+        tag_messages: Vec<(String, String)>,
+    }
+
+    impl FakeGit {
+        fn new(tags: Vec<TagInfo>, commits: Vec<Commit>) -> Self {
+            let head = tags
+                .last()
+                .map(|t| t.sha.clone())
+                .unwrap_or_else(|| "0".repeat(40));
+            Self {
+                tags,
+                commits,
+                path_commits: None,
+                seen_pathspecs: Mutex::new(Vec::new()),
+                seen_ranges: Mutex::new(Vec::new()),
+                range_commits: None,
+                commits_by_tip: Vec::new(),
+                head,
+                created_tags: Mutex::new(Vec::new()),
+                pushed_tags: Mutex::new(Vec::new()),
+                committed: Mutex::new(Vec::new()),
+                push_count: Mutex::new(0),
+                pushed_branches: Mutex::new(Vec::new()),
+                force_created_tags: Mutex::new(Vec::new()),
+                force_pushed_tags: Mutex::new(Vec::new()),
+                retargeted_tags: Mutex::new(Vec::new()),
+                deleted_tags: Mutex::new(Vec::new()),
+                deleted_remote_tags: Mutex::new(Vec::new()),
+                reverted: Mutex::new(Vec::new()),
+                remote_refs: Mutex::new(Vec::new()),
+                notes: Mutex::new(Vec::new()),
+                pushed_notes: Mutex::new(Vec::new()),
+                branch: Some("main".into()),
+                branches: None,
+                origin_head: None,
+                dirty: Vec::new(),
+                workdir: "/".into(),
+                unreachable_tags: Vec::new(),
+                unreadable_tags: Vec::new(),
+                shallow: Mutex::new(false),
+                fetch_fails: false,
+                push_fails: false,
+                no_remote_access: false,
+                fetch_count: Mutex::new(0),
+                // 2026-01-01T12:00:00Z
+                tag_time: 1_767_268_800,
+                commit_time: None,
+                tag_messages: Vec::new(),
+            }
+        }
+    }
+
+    impl GitRepository for FakeGit {
+        fn latest_tag(&self, _prefixes: &[&str]) -> Result<Option<TagInfo>, ReleaseError> {
+            Ok(self.tags.last().cloned())
+        }
+
+        fn commits_since(&self, _from: Option<&str>) -> Result<Vec<Commit>, ReleaseError> {
+            Ok(self.commits.clone())
+        }
+
+        fn create_tag(&self, name: &str, _message: &str, _sign: bool) -> Result<(), ReleaseError> {
+            self.created_tags.lock().unwrap().push(name.to_string());
+            Ok(())
+        }
+
+        fn push_tag(&self, name: &str) -> Result<(), ReleaseError> {
+            self.pushed_tags.lock().unwrap().push(name.to_string());
+            Ok(())
+        }
+
+        fn stage_and_commit(&self, paths: &[&str], message: &str) -> Result<bool, ReleaseError> {
+            self.committed.lock().unwrap().push((
+                paths.iter().map(|s| s.to_string()).collect(),
+                message.to_string(),
+            ));
+            Ok(true)
+        }
+
+        fn push(&self) -> Result<(), ReleaseError> {
+            if self.push_fails {
+                return Err(ReleaseError::Git("push rejected".into()));
+            }
+            *self.push_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        fn push_to_branch(&self, branch: &str) -> Result<(), ReleaseError> {
+            self.pushed_branches
+                .lock()
+                .unwrap()
+                .push(branch.to_string());
+            Ok(())
+        }
+
+        fn tag_exists(&self, name: &str) -> Result<bool, ReleaseError> {
+            Ok(self
+                .created_tags
+                .lock()
+                .unwrap()
+                .contains(&name.to_string()))
+        }
+
+        fn remote_tag_exists(&self, name: &str) -> Result<bool, ReleaseError> {
+            Ok(self.pushed_tags.lock().unwrap().contains(&name.to_string()))
+        }
+
+        fn all_tags(&self, _prefixes: &[&str]) -> Result<Vec<TagInfo>, ReleaseError> {
+            Ok(self.tags.clone())
+        }
+
+        fn commits_between(
+            &self,
+            from: Option<&str>,
+            to: &str,
+        ) -> Result<Vec<Commit>, ReleaseError> {
+            self.seen_ranges
+                .lock()
+                .unwrap()
+                .push((from.map(String::from), to.to_string()));
+            if self.unreadable_tags.iter().any(|t| t == to) {
+                return Err(ReleaseError::Git(format!("bad object {to}")));
+            }
+            if let Some((_, commits)) = self.commits_by_tip.iter().find(|(tip, _)| tip == to) {
+                return Ok(commits.clone());
+            }
+            Ok(self
+                .range_commits
+                .clone()
+                .unwrap_or_else(|| self.commits.clone()))
+        }
+
+        fn tag_timestamp(&self, _tag_name: &str) -> Result<i64, ReleaseError> {
+            Ok(self.tag_time)
+        }
+
+        fn tag_message(&self, name: &str) -> Result<Option<String>, ReleaseError> {
+            if let Some((_, message)) = self.tag_messages.iter().find(|(t, _)| t == name) {
+                return Ok(Some(message.clone()));
+            }
+            if self.tags.iter().any(|t| t.name == name) {
+                Ok(None)
+            } else {
+                Err(ReleaseError::Git(format!("tag {name} not found")))
+            }
+        }
+
+        fn commit_date(&self, sha: &str) -> Result<i64, ReleaseError> {
+            self.commit_time
+                .ok_or_else(|| ReleaseError::Git(format!("unknown commit {sha}")))
+        }
+
+        fn force_create_tag(&self, name: &str) -> Result<(), ReleaseError> {
+            self.force_created_tags
+                .lock()
+                .unwrap()
+                .push(name.to_string());
+            Ok(())
+        }
+
+        fn force_create_tag_at(&self, name: &str, target: &str) -> Result<(), ReleaseError> {
+            self.retargeted_tags
+                .lock()
+                .unwrap()
+                .push((name.to_string(), target.to_string()));
+            Ok(())
+        }
+
+        fn delete_tag(&self, name: &str) -> Result<bool, ReleaseError> {
+            let mut created = self.created_tags.lock().unwrap();
+            let existed = created.iter().any(|t| t == name);
+            created.retain(|t| t != name);
+            if existed {
+                self.deleted_tags.lock().unwrap().push(name.to_string());
+            }
+            Ok(existed)
+        }
+
+        fn delete_remote_tag(&self, name: &str) -> Result<bool, ReleaseError> {
+            let mut pushed = self.pushed_tags.lock().unwrap();
+            let existed = pushed.iter().any(|t| t == name);
+            pushed.retain(|t| t != name);
+            if existed {
+                self.deleted_remote_tags
+                    .lock()
+                    .unwrap()
+                    .push(name.to_string());
+            }
+            Ok(existed)
+        }
+
+        fn revert_commit(&self, sha: &str) -> Result<(), ReleaseError> {
+            self.reverted.lock().unwrap().push(sha.to_string());
+            Ok(())
+        }
+
+        fn remote_ref(&self, refname: &str) -> Result<Option<(String, String)>, ReleaseError> {
+            Ok(self
+                .remote_refs
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(r, _, _)| r == refname)
+                .map(|(_, sha, msg)| (sha.clone(), msg.clone())))
+        }
+
+        fn create_remote_ref(
+            &self,
+            refname: &str,
+            message: &str,
+        ) -> Result<Option<String>, ReleaseError> {
+            let mut refs = self.remote_refs.lock().unwrap();
+            if refs.iter().any(|(r, _, _)| r == refname) {
+                return Ok(None);
+            }
+            let sha = format!("{:040}", refs.len() + 1);
+            refs.push((refname.into(), sha.clone(), message.into()));
+            Ok(Some(sha))
+        }
+
+        fn delete_remote_ref(&self, refname: &str, expected_sha: &str) -> Result<(), ReleaseError> {
+            self.remote_refs
+                .lock()
+                .unwrap()
+                .retain(|(r, sha, _)| !(r == refname && sha == expected_sha));
+            Ok(())
+        }
+
+        fn force_push_tag(&self, name: &str) -> Result<(), ReleaseError> {
+            self.force_pushed_tags
+                .lock()
+                .unwrap()
+                .push(name.to_string());
+            Ok(())
+        }
+
+        fn add_note(&self, notes_ref: &str, sha: &str, content: &str) -> Result<(), ReleaseError> {
+            self.notes
+                .lock()
+                .unwrap()
+                .push((notes_ref.into(), sha.into(), content.into()));
+            Ok(())
+        }
+
+        fn push_notes(&self, notes_ref: &str) -> Result<(), ReleaseError> {
+            self.pushed_notes.lock().unwrap().push(notes_ref.into());
+            Ok(())
+        }
+
+        fn head_sha(&self) -> Result<String, ReleaseError> {
+            Ok(self.head.clone())
+        }
+
+        fn current_branch(&self) -> Result<Option<String>, ReleaseError> {
+            Ok(self.branch.clone())
+        }
+
+        fn default_branch(&self) -> Result<Option<String>, ReleaseError> {
+            Ok(self.origin_head.clone())
+        }
+
+        fn dirty_paths(&self) -> Result<Vec<String>, ReleaseError> {
+            Ok(self.dirty.clone())
+        }
+
+        fn workdir(&self) -> Result<std::path::PathBuf, ReleaseError> {
+            Ok(self.workdir.clone())
+        }
+
+        fn is_shallow(&self) -> Result<bool, ReleaseError> {
+            Ok(*self.shallow.lock().unwrap())
+        }
+
+        fn fetch_tags(&self) -> Result<(), ReleaseError> {
+            *self.fetch_count.lock().unwrap() += 1;
+            if self.fetch_fails {
+                return Err(ReleaseError::Git("no origin remote configured".into()));
+            }
+            *self.shallow.lock().unwrap() = false;
+            Ok(())
+        }
+
+        fn check_remote_access(&self) -> Result<(), ReleaseError> {
+            if self.no_remote_access {
+                return Err(ReleaseError::Git("no git binary to push with".into()));
+            }
+            Ok(())
+        }
+
+        fn tags_reachable(
+            &self,
+            _prefixes: &[&str],
+            _rev: &str,
+        ) -> Result<Vec<TagInfo>, ReleaseError> {
+            Ok(self
+                .tags
+                .iter()
+                .filter(|t| !self.unreachable_tags.contains(&t.name))
+                .cloned()
+                .collect())
+        }
+
+        fn resolve_rev(&self, rev: &str) -> Result<String, ReleaseError> {
+            let branch = rev
+                .strip_prefix("refs/heads/")
+                .or_else(|| rev.strip_prefix("refs/remotes/origin/"));
+            let missing_branch = match (branch, &self.branches) {
+                (Some(branch), Some(branches)) => !branches.iter().any(|b| b == branch),
+                _ => false,
+            };
+            if rev == "missing" || missing_branch {
+                return Err(ReleaseError::Git(format!(
+                    "unknown revision '{rev}': no such commit"
+                )));
+            }
+            Ok(format!("{rev}-sha"))
+        }
+
+        fn commits_since_paths(
+            &self,
+            _from: Option<&str>,
+            paths: &[&str],
+        ) -> Result<Vec<Commit>, ReleaseError> {
+            self.seen_pathspecs
+                .lock()
+                .unwrap()
+                .extend(paths.iter().map(|p| p.to_string()));
+            Ok(self
+                .path_commits
+                .clone()
+                .unwrap_or_else(|| self.commits.clone()))
+        }
+    }
+
+    /// (tag, uploaded file paths)
+    type Upload = (String, Vec<String>);
+
+    /// Cloneable handle: clones share state so tests can inspect a provider
+    /// after handing a boxed copy to the strategy.
+    #[derive(Clone)]
+    struct FakeVcs {
+        releases: Arc<Mutex<Vec<(String, String)>>>,
+        /// `(name, prerelease)` passed to each create/update call.
+        release_meta: Arc<Mutex<Vec<(String, bool)>>>,
+        deleted_releases: Arc<Mutex<Vec<String>>>,
+        uploaded_assets: Arc<Mutex<Vec<Upload>>>,
+        fail_create: bool,
+        fail_upload: bool,
+        /// Whether the provider stages new releases as drafts.
+        drafts: bool,
+        /// Release lifecycle calls in order, e.g. `create v1.0.0 draft`.
+        calls: Arc<Mutex<Vec<String>>>,
+        /// Tags passed to `release_exists`.
+        queried: Arc<Mutex<Vec<String>>>,
+        /// What `default_branch` reports.
+        default_branch: Option<String>,
+    }
+
+    impl FakeVcs {
+        fn new() -> Self {
+            Self {
+                releases: Arc::new(Mutex::new(Vec::new())),
+                release_meta: Arc::new(Mutex::new(Vec::new())),
+                deleted_releases: Arc::new(Mutex::new(Vec::new())),
+                uploaded_assets: Arc::new(Mutex::new(Vec::new())),
+                fail_create: false,
+                fail_upload: false,
+                drafts: false,
+                calls: Arc::new(Mutex::new(Vec::new())),
+                queried: Arc::new(Mutex::new(Vec::new())),
+                default_branch: None,
+            }
+        }
+
+        fn failing() -> Self {
+            Self {
+                fail_create: true,
+                ..Self::new()
+            }
+        }
+    }
+
+    impl VcsProvider for FakeVcs {
+        fn create_release(
+            &self,
+            tag: &str,
+            name: &str,
+            body: &str,
+            prerelease: bool,
+            draft: bool,
+        ) -> Result<RemoteRelease, ReleaseError> {
+            if self.fail_create {
+                return Err(ReleaseError::Vcs("provider unavailable".into()));
+            }
+            let state = if draft { "draft" } else { "published" };
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("create {tag} {state}"));
+            self.release_meta
+                .lock()
+                .unwrap()
+                .push((name.to_string(), prerelease));
+            self.releases
+                .lock()
+                .unwrap()
+                .push((tag.to_string(), body.to_string()));
+            Ok(RemoteRelease {
+                tag: tag.to_string(),
+                id: None,
+                url: format!("https://github.com/test/release/{tag}"),
+            })
+        }
+
+        fn compare_url(&self, base: &str, head: &str) -> Result<String, ReleaseError> {
+            Ok(format!("https://github.com/test/compare/{base}...{head}"))
+        }
+
+        fn release_exists(&self, tag: &str) -> Result<bool, ReleaseError> {
+            self.queried.lock().unwrap().push(tag.to_string());
+            Ok(self.releases.lock().unwrap().iter().any(|(t, _)| t == tag))
+        }
+
+        fn delete_release(&self, tag: &str) -> Result<(), ReleaseError> {
+            self.deleted_releases.lock().unwrap().push(tag.to_string());
+            self.releases.lock().unwrap().retain(|(t, _)| t != tag);
+            Ok(())
+        }
+
+        fn upload_assets(&self, tag: &str, files: &[&str]) -> Result<(), ReleaseError> {
+            self.calls.lock().unwrap().push(format!("upload {tag}"));
+            if self.fail_upload {
+                return Err(ReleaseError::Vcs("upload timed out".into()));
+            }
+            self.uploaded_assets.lock().unwrap().push((
+                tag.to_string(),
+                files.iter().map(|s| s.to_string()).collect(),
+            ));
+            Ok(())
+        }
+
+        fn repo_url(&self) -> Option<String> {
+            Some("https://github.com/test/repo".into())
+        }
+
+        fn supports_drafts(&self) -> bool {
+            self.drafts
+        }
+
+        fn default_branch(&self) -> Result<Option<String>, ReleaseError> {
+            Ok(self.default_branch.clone())
+        }
+
+        fn release_assets(
+            &self,
+            release: &RemoteRelease,
+        ) -> Result<Option<Vec<RemoteAsset>>, ReleaseError> {
+            if !self.drafts {
+                return Ok(None);
+            }
+            let uploads = self.uploaded_assets.lock().unwrap();
+            let listed = uploads
+                .iter()
+                .filter(|(tag, _)| *tag == release.tag)
+                .flat_map(|(_, files)| files)
+                .map(|file| RemoteAsset {
+                    name: Path::new(file)
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned(),
+                    size: std::fs::metadata(file).unwrap().len(),
+                    url: format!(
+                        "https://github.com/test/repo/releases/download/{}/{}",
+                        release.tag,
+                        Path::new(file).file_name().unwrap().to_string_lossy()
+                    ),
+                })
+                .collect();
+            Ok(Some(listed))
+        }
+
+        fn publish_release(&self, release: &RemoteRelease) -> Result<RemoteRelease, ReleaseError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("publish {}", release.tag));
+            Ok(release.clone())
+        }
+
+        fn discard_release(&self, release: &RemoteRelease) -> Result<(), ReleaseError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("discard {}", release.tag));
+            self.delete_release(&release.tag)
+        }
+    }
+
+    // --- Helpers ---
+
+    fn raw_commit(msg: &str) -> Commit {
+        Commit {
+            sha: "a".repeat(40),
+            author: "dev@example.com".into(),
+            message: msg.into(),
+            paths: Vec::new(),
+        }
+    }
+
+    fn commit_touching(msg: &str, paths: &[&str]) -> Commit {
+        Commit {
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            ..raw_commit(msg)
+        }
+    }
+
+    type TestStrategy =
+        TrunkReleaseStrategy<FakeGit, DefaultCommitParser, DefaultChangelogFormatter>;
+
+    fn make_strategy(
+        tags: Vec<TagInfo>,
+        commits: Vec<Commit>,
+        config: ReleaseConfig,
+    ) -> TestStrategy {
+        make_strategy_with_vcs(tags, commits, config).0
+    }
+
+    fn make_strategy_with_vcs(
+        tags: Vec<TagInfo>,
+        commits: Vec<Commit>,
+        config: ReleaseConfig,
+    ) -> (TestStrategy, FakeVcs) {
+        let vcs = FakeVcs::new();
+        let strategy = TrunkReleaseStrategy {
+            git: FakeGit::new(tags, commits),
+            vcs: vec![Box::new(vcs.clone())],
+            parser: DefaultCommitParser::default(),
+            formatter: config.changelog_formatter().unwrap(),
+            config,
+            force: false,
+            allow_any_branch: false,
+            allow_dirty: false,
+            ignore_head_drift: false,
+            offline: false,
+            options: ExecuteOptions::default(),
+            target_ref: None,
+            override_policy: false,
+            download: None,
+            push_branch: None,
+        };
+        (strategy, vcs)
+    }
+
+    // --- plan() tests ---
+
+    #[test]
+    fn plan_no_commits_returns_error() {
+        let s = make_strategy(vec![], vec![], ReleaseConfig::default());
+        let err = s.plan().unwrap_err();
+        assert!(matches!(err, ReleaseError::NoCommits { .. }));
+    }
+
+    #[test]
+    fn plan_no_releasable_returns_error() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("chore: tidy up")],
+            ReleaseConfig::default(),
+        );
+        let err = s.plan().unwrap_err();
+        assert!(matches!(err, ReleaseError::NoBump { .. }));
+    }
+
+    fn policy_strategy(min_commits: usize, min_interval: Option<&str>) -> TestStrategy {
+        let config = ReleaseConfig {
+            policy: crate::config::PolicyConfig {
+                min_commits,
+                min_interval: min_interval.map(String::from),
+            },
+            ..Default::default()
+        };
+        make_strategy(
+            vec![tag("v1.0.0", Version::new(1, 0, 0), 'a')],
+            vec![
+                raw_commit("feat: one"),
+                raw_commit("chore: tidy"),
+                raw_commit("fix: two"),
+            ],
+            config,
+        )
+    }
+
+    #[test]
+    fn policy_defers_until_min_commits_accumulate() {
+        let s = policy_strategy(3, None);
+        let err = s.plan().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "release deferred by policy: 2 of 3 releasable commit(s) since v1.0.0"
+        );
+        assert_eq!(policy_strategy(2, None).plan().unwrap().commits.len(), 3);
+    }
+
+    #[test]
+    fn policy_defers_until_min_interval_elapses() {
+        let mut s = policy_strategy(0, Some("24h"));
+        s.git.tag_time = unix_now() as i64 - 3 * 3600 - 120;
+        let err = s.plan().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "release deferred by policy: v1.0.0 released 3h 2m ago, min_interval is 1d"
+        );
+        s.git.tag_time = unix_now() as i64 - 25 * 3600;
+        assert!(s.plan().is_ok());
+    }
+
+    #[test]
+    fn policy_requires_every_threshold_and_can_be_overridden() {
+        let mut s = policy_strategy(3, Some("1h"));
+        s.git.tag_time = unix_now() as i64 - 60;
+        let err = s.plan().unwrap_err().to_string();
+        assert!(err.contains("2 of 3 releasable commit(s)"), "{err}");
+        assert!(err.contains("; v1.0.0 released 1m ago"), "{err}");
+
+        // Enough time has passed, but still too few commits
+        s.git.tag_time = unix_now() as i64 - 7200;
+        assert!(matches!(s.plan(), Err(ReleaseError::Deferred { .. })));
+
+        s.override_policy = true;
+        assert_eq!(s.plan().unwrap().next_version, Version::new(1, 1, 0));
+    }
+
+    #[test]
+    fn policy_interval_does_not_hold_back_the_first_release() {
+        let config = ReleaseConfig {
+            policy: crate::config::PolicyConfig {
+                min_commits: 0,
+                min_interval: Some("7d".into()),
+            },
+            ..Default::default()
+        };
+        let s = make_strategy(vec![], vec![raw_commit("feat: first")], config);
+        assert!(s.plan().is_ok());
+    }
+
+    #[test]
+    fn human_duration_keeps_two_largest_units() {
+        assert_eq!(human_duration(0), "0s");
+        assert_eq!(human_duration(45), "45s");
+        assert_eq!(human_duration(3 * 3600 + 12 * 60 + 5), "3h 12m");
+        assert_eq!(human_duration(86_400 + 7200), "1d 2h");
+    }
+
+    #[test]
+    fn force_releases_patch_when_no_releasable_commits() {
+        let tag = TagInfo {
+            name: "v1.2.3".into(),
+            version: Version::new(1, 2, 3),
+            sha: "d".repeat(40),
+        };
+        let mut s = make_strategy(
+            vec![tag],
+            vec![raw_commit("chore: rename package")],
+            ReleaseConfig::default(),
+        );
+        s.force = true;
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.next_version, Version::new(1, 2, 4));
+        assert_eq!(plan.bump, BumpLevel::Patch);
+    }
+
+    #[test]
+    fn plan_shallow_clone_fetches_tags() {
+        let mut s = make_strategy(
+            vec![TagInfo {
+                name: "v3.1.0".into(),
+                version: Version::new(3, 1, 0),
+                sha: "d".repeat(40),
+            }],
+            vec![raw_commit("fix: bug")],
+            ReleaseConfig::default(),
+        );
+        *s.git.shallow.get_mut().unwrap() = true;
+        let plan = s.plan().unwrap();
+        assert_eq!(*s.git.fetch_count.lock().unwrap(), 1);
+        assert!(!*s.git.shallow.lock().unwrap());
+        assert_eq!(plan.next_version, Version::new(3, 1, 1));
+    }
+
+    #[test]
+    fn plan_full_clone_does_not_fetch() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: x")],
+            ReleaseConfig::default(),
+        );
+        s.plan().unwrap();
+        assert_eq!(*s.git.fetch_count.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn plan_shallow_clone_fetch_failure_errors() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: x")],
+            ReleaseConfig::default(),
+        );
+        *s.git.shallow.get_mut().unwrap() = true;
+        s.git.fetch_fails = true;
+        let err = s.plan().unwrap_err().to_string();
+        assert!(err.contains("shallow clone"), "{err}");
+        assert!(err.contains("fetch-depth: 0"), "{err}");
+    }
+
+    #[test]
+    fn plan_offline_shallow_clone_does_not_fetch() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: x")],
+            ReleaseConfig::default(),
+        );
+        *s.git.shallow.get_mut().unwrap() = true;
+        s.git.fetch_fails = true;
+        s.offline = true;
+        let plan = s.plan().unwrap();
+        assert_eq!(*s.git.fetch_count.lock().unwrap(), 0);
+        assert_eq!(plan.next_version, Version::new(0, 1, 0));
+
+        let report = s.dry_run_report(&plan).unwrap();
+        assert!(report.warnings.iter().any(|w| w.starts_with("offline:")));
+    }
+
+    #[test]
+    fn plan_first_release() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: initial feature")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.next_version, Version::new(0, 1, 0));
+        assert_eq!(plan.tag_name, "v0.1.0");
+        assert!(plan.current_version.is_none());
+    }
+
+    #[test]
+    fn plan_without_tags_bumps_from_manifest_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo = dir.path().join("Cargo.toml");
+        std::fs::write(&cargo, "[package]\nname = \"app\"\nversion = \"2.3.1\"\n").unwrap();
+        let package = dir.path().join("package.json");
+        std::fs::write(&package, "{\"version\": \"1.9.0\"}\n").unwrap();
+        let config = ReleaseConfig {
+            version_files: vec![
+                package.to_str().unwrap().to_string(),
+                cargo.to_str().unwrap().to_string(),
+                dir.path()
+                    .join("missing/Cargo.toml")
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            ],
+            ..Default::default()
+        };
+        let s = make_strategy(vec![], vec![raw_commit("feat: adopt sr")], config);
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.next_version, Version::new(2, 4, 0));
+        assert!(plan.current_version.is_none());
+    }
+
+    #[test]
+    fn plan_calver_numbers_releases_by_date() {
+        let today = jiff::Timestamp::now()
+            .to_zoned(jiff::tz::TimeZone::UTC)
+            .date();
+        let (year, month) = (today.year() as u64, today.month() as u64);
+        let tag = TagInfo {
+            name: format!("v{year}.{month:02}.0"),
+            version: Version::new(year, month, 0),
+            sha: "b".repeat(40),
+        };
+        let config = ReleaseConfig {
+            versioning: crate::config::Versioning::Calver,
+            ..Default::default()
+        };
+
+        let s = make_strategy(
+            vec![tag.clone()],
+            vec![raw_commit("feat!: breaking change")],
+            config.clone(),
+        );
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.next_version, Version::new(year, month, 1));
+        assert_eq!(plan.tag_name, format!("v{year}.{month:02}.1"));
+
+        // Commits still decide whether there is a release at all
+        let s = make_strategy(vec![tag], vec![raw_commit("chore: tidy")], config);
+        assert!(matches!(s.plan(), Err(ReleaseError::NoBump { .. })));
+    }
+
+    #[test]
+    fn plan_version_floor_only_raises_the_base() {
+        let config = ReleaseConfig {
+            version_floor: Some("3.0.0".into()),
+            ..Default::default()
+        };
+        let s = make_strategy(vec![], vec![raw_commit("fix: bug")], config.clone());
+        assert_eq!(s.plan().unwrap().next_version, Version::new(3, 0, 1));
+
+        let tag = TagInfo {
+            name: "v3.2.0".into(),
+            version: Version::new(3, 2, 0),
+            sha: "b".repeat(40),
+        };
+        let s = make_strategy(vec![tag], vec![raw_commit("fix: bug")], config);
+        assert_eq!(s.plan().unwrap().next_version, Version::new(3, 2, 1));
+    }
+
+    #[test]
+    fn plan_increments_existing() {
+        let tag = TagInfo {
+            name: "v1.2.3".into(),
+            version: Version::new(1, 2, 3),
+            sha: "b".repeat(40),
+        };
+        let s = make_strategy(
+            vec![tag],
+            vec![raw_commit("fix: patch bug")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.next_version, Version::new(1, 2, 4));
+    }
+
+    #[test]
+    fn plan_breaking_bump() {
+        let tag = TagInfo {
+            name: "v1.2.3".into(),
+            version: Version::new(1, 2, 3),
+            sha: "c".repeat(40),
+        };
+        let s = make_strategy(
+            vec![tag],
+            vec![raw_commit("feat!: breaking change")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.next_version, Version::new(2, 0, 0));
+    }
+
+    #[test]
+    fn plan_v0_breaking_downshifts_to_minor() {
+        let tag = TagInfo {
+            name: "v0.5.0".into(),
+            version: Version::new(0, 5, 0),
+            sha: "c".repeat(40),
+        };
+        let s = make_strategy(
+            vec![tag],
+            vec![raw_commit("feat!: breaking change")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        // v0 protection: Major → Minor, so 0.5.0 → 0.6.0 (not 1.0.0)
+        assert_eq!(plan.next_version, Version::new(0, 6, 0));
+        assert_eq!(plan.bump, BumpLevel::Minor);
+    }
+
+    #[test]
+    fn plan_v0_breaking_with_force_bumps_major() {
+        let tag = TagInfo {
+            name: "v0.5.0".into(),
+            version: Version::new(0, 5, 0),
+            sha: "c".repeat(40),
+        };
+        let mut s = make_strategy(
+            vec![tag],
+            vec![raw_commit("feat!: breaking change")],
+            ReleaseConfig::default(),
+        );
+        s.force = true;
+        let plan = s.plan().unwrap();
+        // --force bypasses v0 protection
+        assert_eq!(plan.next_version, Version::new(1, 0, 0));
+        assert_eq!(plan.bump, BumpLevel::Major);
+    }
+
+    #[test]
+    fn plan_v0_feat_stays_minor() {
+        let tag = TagInfo {
+            name: "v0.5.0".into(),
+            version: Version::new(0, 5, 0),
+            sha: "c".repeat(40),
+        };
+        let s = make_strategy(
+            vec![tag],
+            vec![raw_commit("feat: new feature")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        // Non-breaking feat in v0 stays as minor bump
+        assert_eq!(plan.next_version, Version::new(0, 6, 0));
+        assert_eq!(plan.bump, BumpLevel::Minor);
+    }
+
+    #[test]
+    fn plan_v0_fix_stays_patch() {
+        let tag = TagInfo {
+            name: "v0.5.0".into(),
+            version: Version::new(0, 5, 0),
+            sha: "c".repeat(40),
+        };
+        let s = make_strategy(
+            vec![tag],
+            vec![raw_commit("fix: bug fix")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        // Fix in v0 stays as patch
+        assert_eq!(plan.next_version, Version::new(0, 5, 1));
+        assert_eq!(plan.bump, BumpLevel::Patch);
+    }
+
+    // --- execute() tests ---
+
+    #[test]
+    fn execute_dry_run_no_side_effects() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        s.execute(&plan, true).unwrap();
+
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+        assert!(s.git.pushed_tags.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_creates_and_pushes_tag() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert_eq!(*s.git.pushed_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_without_remote_access_fails_before_tagging() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.no_remote_access = true;
+        let plan = s.plan().unwrap();
+        let err = s.execute(&plan, false).unwrap_err().to_string();
+        assert!(err.contains("no git binary"), "{err}");
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+        assert!(s.git.committed.lock().unwrap().is_empty());
+
+        // A local-only release never needs the remote
+        s.options.no_push = true;
+        s.execute(&plan, false).unwrap();
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_deletes_the_local_tag_when_the_push_fails() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.push_fails = true;
+        let plan = s.plan().unwrap();
+        assert!(s.execute(&plan, false).is_err());
+        assert_eq!(*s.git.deleted_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert!(!s.git.tag_exists("v0.1.0").unwrap());
+        assert!(s.git.pushed_tags.lock().unwrap().is_empty());
+
+        // The next run tags its own HEAD instead of reusing a stale tag.
+        s.git.push_fails = false;
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert_eq!(*s.git.deleted_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert_eq!(*s.git.pushed_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_keeps_a_preexisting_local_tag_when_the_push_fails() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.push_fails = true;
+        let plan = s.plan().unwrap();
+        s.git.created_tags.lock().unwrap().push("v0.1.0".into());
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(err.to_string().contains("push rejected"), "{err}");
+        assert!(s.git.deleted_tags.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_calls_vcs_create_release() {
+        let (s, vcs) = make_strategy_with_vcs(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        let releases = vcs.releases.lock().unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].0, "v0.1.0");
+        assert!(!releases[0].1.is_empty());
+    }
+
+    #[test]
+    fn execute_release_name_defaults_to_tag() {
+        let (s, vcs) = make_strategy_with_vcs(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(
+            *vcs.release_meta.lock().unwrap(),
+            vec![("v0.1.0".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn execute_renders_release_name_template() {
+        let config = ReleaseConfig {
+            release: crate::config::RemoteReleaseConfig {
+                name_template: "Release {version} ({date})".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        let meta = vcs.release_meta.lock().unwrap();
+        let today = crate::config::ChangelogConfig::default().today().unwrap();
+        assert_eq!(meta[0].0, format!("Release 0.1.0 ({today})"));
+    }
+
+    #[test]
+    fn release_date_follows_date_source() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        let today = crate::config::ChangelogConfig::default().today().unwrap();
+        assert_eq!(s.release_date(&plan).unwrap(), today, "unknown commit");
+
+        // 2023-11-14T22:13:20Z
+        s.git.commit_time = Some(1_700_000_000);
+        assert_eq!(s.release_date(&plan).unwrap(), "2023-11-14");
+
+        s.config.changelog.date_source = DateSource::Tag;
+        assert_eq!(s.release_date(&plan).unwrap(), "2026-01-01");
+
+        s.config.changelog.date_source = DateSource::Now;
+        assert_eq!(s.release_date(&plan).unwrap(), today);
+    }
+
+    #[test]
+    fn execute_dates_the_changelog_by_the_released_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog = dir.path().join("CHANGELOG.md");
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(changelog.to_str().unwrap().into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        s.git.commit_time = Some(1_700_000_000);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        let written = std::fs::read_to_string(&changelog).unwrap();
+        assert!(
+            written.contains(
+                "## [0.1.0](https://github.com/test/repo/releases/tag/v0.1.0) (2023-11-14)"
+            ),
+            "{written}"
+        );
+    }
+
+    #[test]
+    fn execute_legacy_release_name_template_takes_precedence() {
+        let config = ReleaseConfig {
+            release_name_template: Some("{{ tag_prefix }}{{ version }} final".into()),
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(vcs.release_meta.lock().unwrap()[0].0, "v0.1.0 final");
+    }
+
+    #[test]
+    fn path_like_prefix_names_tags_and_release() {
+        let tag = TagInfo {
+            name: "pkg/foo-v1.4.0".into(),
+            version: Version::new(1, 4, 0),
+            sha: "a".repeat(40),
+        };
+        let config = ReleaseConfig {
+            tag_prefix: "pkg/foo-v".into(),
+            floating_tags: true,
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![tag], vec![raw_commit("fix: bug")], config);
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.tag_name, "pkg/foo-v1.4.1");
+        assert_eq!(plan.floating_tag_name.as_deref(), Some("pkg/foo-v1"));
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(vcs.release_meta.lock().unwrap()[0].0, "foo-v1.4.1");
+        assert_eq!(*s.git.created_tags.lock().unwrap(), ["pkg/foo-v1.4.1"]);
+        assert_eq!(*s.git.force_created_tags.lock().unwrap(), ["pkg/foo-v1"]);
+    }
+
+    #[test]
+    fn release_body_carries_migration_notes() {
+        let mut config = ReleaseConfig::default();
+        config.changelog.migration_notes = true;
+        let commits = vec![
+            raw_commit("feat!: drop node 16"),
+            raw_commit("feat(api)!: drop v1\n\nBREAKING CHANGE: /v1 is gone.\n\nUse /v2."),
+        ];
+        let (s, vcs) = make_strategy_with_vcs(vec![], commits, config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        let body = &vcs.releases.lock().unwrap()[0].1;
+        assert!(
+            body.contains(&format!(
+                "/commit/{}))\n\n  /v1 is gone.\n\n  Use /v2.\n\n###",
+                "a".repeat(40)
+            )),
+            "{body}"
+        );
+        assert!(
+            body.contains("### Migration notes\n\n/v1 is gone.\n\nUse /v2."),
+            "{body}"
+        );
+    }
+
+    #[test]
+    fn legacy_release_name_template_gets_the_display_name() {
+        let config = ReleaseConfig {
+            tag_prefix: "crates/sr-core/v".into(),
+            release_name_template: Some("{{ display_name }}".into()),
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(vcs.release_meta.lock().unwrap()[0].0, "sr-core v0.1.0");
+    }
+
+    #[test]
+    fn execute_prerelease_flag_follows_config() {
+        use crate::config::{PrereleaseFlag, RemoteReleaseConfig};
+
+        let cases = [
+            (None, PrereleaseFlag::Auto, false),
+            (Some("rc"), PrereleaseFlag::Auto, true),
+            (Some("rc"), PrereleaseFlag::Never, false),
+            (None, PrereleaseFlag::Always, true),
+        ];
+        for (prerelease_id, flag, expected) in cases {
+            let config = ReleaseConfig {
+                prerelease: prerelease_id.map(String::from),
+                release: RemoteReleaseConfig {
+                    prerelease: flag,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let (s, vcs) =
+                make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+            let plan = s.plan().unwrap();
+            s.execute(&plan, false).unwrap();
+            assert_eq!(
+                vcs.release_meta.lock().unwrap()[0].1,
+                expected,
+                "prerelease id {prerelease_id:?}, flag {flag:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn execute_publishes_to_every_provider() {
+        let (mut s, primary) = make_strategy_with_vcs(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let mirror = FakeVcs::new();
+        s.vcs.push(Box::new(mirror.clone()));
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(primary.releases.lock().unwrap().len(), 1);
+        assert_eq!(mirror.releases.lock().unwrap().len(), 1);
+        assert_eq!(mirror.releases.lock().unwrap()[0].0, "v0.1.0");
+    }
+
+    #[test]
+    fn execute_provider_failure_does_not_abort_others() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.tar.gz"), "fake tarball").unwrap();
+        let config = ReleaseConfig {
+            artifacts: vec![
+                dir.path()
+                    .join("*.tar.gz")
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+                    .into(),
+            ],
+            ..Default::default()
+        };
+
+        let (mut s, primary) =
+            make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        s.vcs.insert(0, Box::new(FakeVcs::failing()));
+        let plan = s.plan().unwrap();
+        let err = s.execute(&plan, false).unwrap_err();
+
+        assert!(matches!(err, ReleaseError::Vcs(_)));
+        assert!(err.to_string().contains("1 of 2 provider(s)"));
+        // The healthy provider still received the release and its assets
+        assert_eq!(primary.releases.lock().unwrap().len(), 1);
+        assert_eq!(primary.uploaded_assets.lock().unwrap().len(), 1);
+        // Tag was still created and pushed once
+        assert_eq!(*s.git.pushed_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_without_providers_is_tag_only() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.vcs.clear();
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(*s.git.pushed_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn plan_counts_commits_by_top_level_directory() {
+        let s = make_strategy(
+            vec![],
+            vec![
+                commit_touching(
+                    "feat: api",
+                    &["crates/api/src/lib.rs", "crates/api/Cargo.toml"],
+                ),
+                commit_touching("fix: docs and core", &["docs/guide.md", "crates/core/a.rs"]),
+                commit_touching("chore: root", &["Cargo.lock"]),
+                commit_touching("not conventional", &["scripts/x.sh"]),
+            ],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        let touched: Vec<(&str, usize)> = plan
+            .touched_paths
+            .iter()
+            .map(|t| (t.path.as_str(), t.commits))
+            .collect();
+        assert_eq!(touched, [("crates", 2), (".", 1), ("docs", 1)]);
+
+        let plain = make_strategy(
+            vec![],
+            vec![raw_commit("feat: x")],
+            ReleaseConfig::default(),
+        );
+        assert!(plain.plan().unwrap().touched_paths.is_empty());
+    }
+
+    #[test]
+    fn execute_refuses_branch_not_in_config() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.branch = Some("feature/x".into());
+        let plan = s.plan().unwrap();
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(matches!(err, ReleaseError::Branch(_)));
+        assert!(err.to_string().contains("main, master"), "{err}");
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+
+        // Dry-run only warns
+        s.execute(&plan, true).unwrap();
+    }
+
+    #[test]
+    fn execute_allow_any_branch_skips_check() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.branch = Some("feature/x".into());
+        s.allow_any_branch = true;
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_allows_the_default_branch_without_main_or_master() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.branch = Some("trunk".into());
+        s.git.branches = Some(vec!["trunk".into()]);
+        s.git.origin_head = Some("trunk".into());
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_keeps_configured_branches_over_the_default_branch() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig {
+                branches: vec!["release".into()],
+                ..Default::default()
+            },
+        );
+        s.git.branch = Some("trunk".into());
+        s.git.branches = Some(vec!["trunk".into()]);
+        s.git.origin_head = Some("trunk".into());
+        let plan = s.plan().unwrap();
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(
+            err.to_string().contains("allowed branches: release"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn release_branches_fall_back_to_the_default_branch() {
+        let defaults = ReleaseConfig::default().branches;
+        let mut git = FakeGit::new(vec![], vec![]);
+        let trunk = || {
+            Ok(Some(DefaultBranch {
+                name: "trunk".into(),
+                source: DefaultBranchSource::Provider,
+            }))
+        };
+        assert_eq!(
+            release_branches(&defaults, &git, trunk).unwrap(),
+            defaults,
+            "main exists"
+        );
+
+        git.branches = Some(vec!["master".into()]);
+        assert_eq!(release_branches(&defaults, &git, trunk).unwrap(), defaults);
+
+        git.branches = Some(vec!["trunk".into()]);
+        assert_eq!(release_branches(&defaults, &git, trunk).unwrap(), ["trunk"]);
+        assert_eq!(
+            release_branches(&defaults, &git, || Ok(None)).unwrap(),
+            defaults,
+            "no default branch known"
+        );
+        let custom = vec!["main".to_string()];
+        assert_eq!(release_branches(&custom, &git, trunk).unwrap(), custom);
+    }
+
+    #[test]
+    fn default_branch_prefers_origin_head_over_the_provider() {
+        let mut git = FakeGit::new(vec![], vec![]);
+        let mut vcs = FakeVcs::new();
+        vcs.default_branch = Some("develop".into());
+        assert_eq!(detect_default_branch(&git, None).unwrap(), None);
+        assert_eq!(
+            detect_default_branch(&git, Some(&vcs)).unwrap(),
+            Some(DefaultBranch {
+                name: "develop".into(),
+                source: DefaultBranchSource::Provider,
+            })
+        );
+
+        git.origin_head = Some("trunk".into());
+        let found = detect_default_branch(&git, Some(&vcs)).unwrap().unwrap();
+        assert_eq!(found.name, "trunk");
+        assert_eq!(found.source, DefaultBranchSource::OriginHead);
+        assert_eq!(found.source.to_string(), "origin/HEAD");
+    }
+
+    #[test]
+    fn execute_refuses_unrelated_dirty_files() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.dirty = vec!["Cargo.toml".into(), "src/main.rs".into()];
+        let plan = s.plan().unwrap();
+        let err = s.execute(&plan, false).unwrap_err().to_string();
+        assert!(err.contains("Cargo.toml"), "{err}");
+        assert!(err.contains("src/main.rs"), "{err}");
+        assert!(err.contains("--allow-dirty"), "{err}");
+        assert!(s.git.committed.lock().unwrap().is_empty());
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+
+        // Dry-run only warns
+        s.execute(&plan, true).unwrap();
+    }
+
+    #[test]
+    fn plan_records_head_sha() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.head_sha, s.git.head);
+    }
+
+    #[test]
+    fn saved_plan_round_trips() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat!: new api")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        let json = serde_json::to_string(&plan).unwrap();
+        let loaded = ReleasePlan::from_json(&json).unwrap();
+        assert_eq!(loaded.schema_version, PLAN_SCHEMA_VERSION);
+        assert_eq!(loaded.tag_name, plan.tag_name);
+        assert_eq!(loaded.head_sha, plan.head_sha);
+        assert_eq!(loaded.bump, plan.bump);
+        assert!(loaded.commits[0].breaking);
+    }
+
+    #[test]
+    fn saved_plan_rejects_other_schema_versions() {
+        let err = ReleasePlan::from_json(r#"{"schema_version": 99}"#).unwrap_err();
+        assert!(matches!(err, ReleaseError::Config(_)));
+        assert!(err.to_string().contains("schema version 99"), "{err}");
+
+        let err = ReleasePlan::from_json(r#"{"tag_name": "v1.0.0"}"#).unwrap_err();
+        assert!(err.to_string().contains("no schema_version"), "{err}");
+
+        let err = ReleasePlan::from_json("not json").unwrap_err();
+        assert!(err.to_string().contains("invalid plan file"), "{err}");
+    }
+
+    #[test]
+    fn plan_target_ref_uses_reachable_tags_and_commits_up_to_ref() {
+        let mut s = make_strategy(
+            vec![
+                tag("v1.0.0", Version::new(1, 0, 0), 'a'),
+                tag("v2.0.0", Version::new(2, 0, 0), 'b'),
+            ],
+            vec![raw_commit("fix: backport")],
+            ReleaseConfig::default(),
+        );
+        s.git.unreachable_tags = vec!["v2.0.0".into()];
+        s.target_ref = Some("release/1.x".into());
+
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.current_version, Some(Version::new(1, 0, 0)));
+        assert_eq!(plan.next_version, Version::new(1, 0, 1));
+        assert_eq!(plan.head_sha, "release/1.x-sha");
+        assert_eq!(
+            *s.git.seen_ranges.lock().unwrap(),
+            vec![(Some("a".repeat(40)), "release/1.x-sha".to_string())]
+        );
+    }
+
+    #[test]
+    fn plan_target_ref_unknown_is_an_error() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.target_ref = Some("missing".into());
+        let err = s.plan().unwrap_err();
+        assert!(matches!(err, ReleaseError::Git(_)));
+        assert!(err.to_string().contains("unknown revision 'missing'"));
+    }
+
+    #[test]
+    fn execute_refuses_when_head_moved() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        s.git.head = "f".repeat(40);
+
+        let err = s.execute(&plan, false).unwrap_err();
+        match err {
+            ReleaseError::HeadMoved { planned, actual } => {
+                assert_eq!(planned, plan.head_sha);
+                assert_eq!(actual, "f".repeat(40));
+            }
+            other => panic!("expected HeadMoved, got {other}"),
+        }
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+
+        // Dry-run only warns
+        s.execute(&plan, true).unwrap();
+    }
+
+    #[test]
+    fn execute_ignore_head_drift_continues() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        s.git.head = "f".repeat(40);
+        s.ignore_head_drift = true;
+
+        s.execute(&plan, false).unwrap();
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_allow_dirty_warns_and_continues() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.dirty = vec!["notes.txt".into()];
+        s.allow_dirty = true;
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_ignores_dirty_files_sr_manages() {
+        let config = ReleaseConfig {
+            version_files: vec!["./does-not-exist/Cargo.toml".into()],
+            stage_files: vec!["*.lock".into()],
+            changelog: crate::config::ChangelogConfig {
+                file: Some("CHANGELOG.md".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        s.git.workdir = std::env::current_dir().unwrap();
+        s.git.dirty = vec![
+            "does-not-exist/Cargo.toml".into(),
+            "Cargo.lock".into(),
+            "CHANGELOG.md".into(),
+        ];
+        assert!(s.unmanaged_dirty_paths().unwrap().is_empty());
+    }
+
+    #[test]
+    fn dirty_check_matches_absolute_paths_inside_the_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo = dir.path().join("crates/core/Cargo.toml");
+        let config = ReleaseConfig {
+            version_files: vec![cargo.to_str().unwrap().to_string()],
+            ..Default::default()
+        };
+        let mut s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        s.git.workdir = dir.path().to_path_buf();
+        s.git.dirty = vec!["crates/core/Cargo.toml".into()];
+        assert!(s.unmanaged_dirty_paths().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_refuses_files_outside_the_repository() {
+        let repo = tempfile::tempdir().unwrap();
+        let elsewhere = tempfile::tempdir().unwrap();
+        let changelog = elsewhere.path().join("CHANGELOG.md");
+        let escaping = format!("{}/../outside/Cargo.toml", repo.path().display());
+
+        for (config, key, path) in [
+            (
+                ReleaseConfig {
+                    changelog: crate::config::ChangelogConfig {
+                        file: Some(changelog.to_str().unwrap().to_string()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                "changelog.file",
+                changelog.to_str().unwrap().to_string(),
+            ),
+            (
+                ReleaseConfig {
+                    version_files: vec![escaping.clone()],
+                    ..Default::default()
+                },
+                "version_files",
+                escaping.clone(),
+            ),
+        ] {
+            let mut s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+            s.git.workdir = repo.path().to_path_buf();
+            let plan = s.plan().unwrap();
+            let err = s.execute(&plan, false).unwrap_err();
+            assert!(matches!(err, ReleaseError::Config(_)), "{err}");
+            let msg = err.to_string();
+            assert!(
+                msg.contains(&format!("{key}: '{path}' is outside the repository")),
+                "{msg}"
+            );
+            assert!(s.git.committed.lock().unwrap().is_empty());
+
+            // Dry-run only warns
+            let report = s.dry_run_report(&plan).unwrap();
+            assert!(
+                report
+                    .warnings
+                    .iter()
+                    .any(|w| w.contains("outside the repository")),
+                "{:?}",
+                report.warnings
+            );
+
+            // --tag-only touches no files, so there is nothing to check
+            s.options.tag_only = true;
+            s.execute(&plan, false).unwrap();
+        }
+    }
+
+    #[test]
+    fn check_release_branch_matches_exact_and_glob() {
+        let branches = vec!["main".to_string(), "release/*".to_string()];
+        assert!(check_release_branch(&branches, Some("main"), None).is_ok());
+        assert!(check_release_branch(&branches, Some("release/1.x"), None).is_ok());
+        assert!(check_release_branch(&branches, Some("develop"), None).is_err());
+        // Empty list allows any branch
+        assert!(check_release_branch(&[], Some("develop"), None).is_ok());
+    }
+
+    #[test]
+    fn check_release_branch_detached_head_uses_github_ref() {
+        let branches = vec!["main".to_string()];
+        assert!(check_release_branch(&branches, None, Some("main")).is_ok());
+
+        let err = check_release_branch(&branches, None, Some("feature"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("GITHUB_REF_NAME"), "{err}");
+
+        let err = check_release_branch(&branches, None, None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("detached"), "{err}");
+    }
+
+    #[test]
+    fn detached_push_branch_prefers_flag_then_branch_ref() {
+        assert_eq!(
+            detached_push_branch(Some("release"), Some("main"), Some("branch")).as_deref(),
+            Some("release")
+        );
+        assert_eq!(
+            detached_push_branch(None, Some("main"), Some("branch")).as_deref(),
+            Some("main")
+        );
+        assert_eq!(
+            detached_push_branch(None, Some("v1.0.0"), Some("tag")),
+            None
+        );
+        assert_eq!(detached_push_branch(None, Some(""), None), None);
+        assert_eq!(detached_push_branch(None, None, None), None);
+    }
+
+    #[test]
+    fn execute_on_detached_head_pushes_commit_to_push_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(
+                    dir.path()
+                        .join("CHANGELOG.md")
+                        .to_str()
+                        .unwrap()
+                        .to_string(),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        s.git.branch = None;
+        s.allow_any_branch = true;
+        s.push_branch = Some("main".into());
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(s.git.committed.lock().unwrap().len(), 1);
+        assert_eq!(*s.git.pushed_branches.lock().unwrap(), ["main"]);
+        assert_eq!(*s.git.push_count.lock().unwrap(), 0);
+        assert_eq!(*s.git.pushed_tags.lock().unwrap(), ["v0.1.0"]);
+    }
+
+    #[test]
+    fn execute_on_detached_head_without_commit_pushes_only_the_tag() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.git.branch = None;
+        s.allow_any_branch = true;
+        s.options.tag_only = true;
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert!(s.git.committed.lock().unwrap().is_empty());
+        assert!(s.git.pushed_branches.lock().unwrap().is_empty());
+        assert_eq!(*s.git.push_count.lock().unwrap(), 0);
+        assert_eq!(*s.git.pushed_tags.lock().unwrap(), ["v0.1.0"]);
+    }
+
+    fn remote(host: &str) -> Result<(String, String, String), ReleaseError> {
+        Ok((host.into(), "remote-owner".into(), "remote-repo".into()))
+    }
+
+    fn no_remote() -> Result<(String, String, String), ReleaseError> {
+        Err(ReleaseError::Git("no such remote 'origin'".into()))
+    }
+
+    #[test]
+    fn resolve_origin_prefers_remote_over_env() {
+        let origin = resolve_origin(
+            &ReleaseConfig::default(),
+            remote("github.com"),
+            Some("env/repo"),
+            Some("ghes.example.com"),
+        )
+        .unwrap();
+        assert_eq!(
+            origin,
+            (
+                "github.com".into(),
+                "remote-owner".into(),
+                "remote-repo".into()
+            )
+        );
+    }
+
+    #[test]
+    fn resolve_origin_falls_back_to_github_env() {
+        let origin = resolve_origin(
+            &ReleaseConfig::default(),
+            no_remote(),
+            Some("env-owner/env-repo"),
+            Some("ghes.example.com"),
+        )
+        .unwrap();
+        assert_eq!(
+            origin,
+            (
+                "ghes.example.com".into(),
+                "env-owner".into(),
+                "env-repo".into()
+            )
+        );
+
+        let (host, _, _) = resolve_origin(
+            &ReleaseConfig::default(),
+            no_remote(),
+            Some("env-owner/env-repo"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(host, "github.com");
+    }
+
+    #[test]
+    fn resolve_origin_config_overrides_everything() {
+        let config = ReleaseConfig {
+            repository: Some("cfg-owner/cfg-repo".into()),
+            hostname: Some("git.example.com".into()),
+            ..Default::default()
+        };
+        let expected = (
+            "git.example.com".into(),
+            "cfg-owner".into(),
+            "cfg-repo".into(),
+        );
+        assert_eq!(
+            resolve_origin(&config, remote("github.com"), Some("env/repo"), None).unwrap(),
+            expected
+        );
+        assert_eq!(
+            resolve_origin(&config, no_remote(), Some("env/repo"), None).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn resolve_origin_errors_when_exhausted() {
+        let err = resolve_origin(&ReleaseConfig::default(), no_remote(), None, None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("cannot parse remote URL"), "{err}");
+        assert!(err.contains("GITHUB_REPOSITORY"), "{err}");
+
+        let err = resolve_origin(&ReleaseConfig::default(), no_remote(), Some("bad"), None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("owner/repo"), "{err}");
+    }
+
+    #[test]
+    fn execute_commits_changelog_before_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog_path = dir.path().join("CHANGELOG.md");
+
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(changelog_path.to_str().unwrap().to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        // Verify changelog was committed
+        let committed = s.git.committed.lock().unwrap();
+        assert_eq!(committed.len(), 1);
+        assert_eq!(
+            committed[0].0,
+            vec![changelog_path.to_str().unwrap().to_string()]
+        );
+        assert!(committed[0].1.contains("chore(release): v0.1.0"));
+
+        // Verify tag was created after commit
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+    }
+
+    #[test]
+    fn release_body_and_changelog_use_their_own_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog_path = dir.path().join("CHANGELOG.md");
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(changelog_path.to_str().unwrap().to_string()),
+                ..Default::default()
+            },
+            release: crate::config::RemoteReleaseConfig {
+                sections: vec!["Breaking Changes".into(), "Features".into()],
+                compare_link: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let tags = vec![TagInfo {
+            name: "v1.0.0".into(),
+            version: Version::new(1, 0, 0),
+            sha: "d".repeat(40),
+        }];
+        let commits = vec![
+            raw_commit("feat: login"),
+            raw_commit("fix: crash"),
+            raw_commit("chore: tidy"),
+        ];
+        let (s, vcs) = make_strategy_with_vcs(tags, commits, config);
+        let plan = s.plan().unwrap();
+
+        let report = s.dry_run_report(&plan).unwrap();
+        assert_ne!(report.release.unwrap().notes, report.changelog);
+
+        s.execute(&plan, false).unwrap();
+        let releases = vcs.releases.lock().unwrap();
+        let body = &releases[0].1;
+        assert!(body.contains("### Features"), "{body}");
+        assert!(!body.contains("Bug Fixes"), "{body}");
+        assert!(!body.contains("Miscellaneous"), "{body}");
+        assert!(!body.contains("Full Changelog"), "{body}");
+
+        let changelog = std::fs::read_to_string(&changelog_path).unwrap();
+        for heading in ["### Features", "### Bug Fixes", "### Miscellaneous"] {
+            assert!(changelog.contains(heading), "{changelog}");
+        }
+        assert!(
+            changelog.contains("[Full Changelog](https://github.com/test/compare/v1.0.0...v1.1.0)"),
+            "{changelog}"
+        );
+    }
+
+    #[test]
+    fn curated_notes_file_becomes_the_release_body() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog_path = dir.path().join("CHANGELOG.md");
+        let template = dir.path().join("{version}.md");
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(changelog_path.to_str().unwrap().to_string()),
+                ..Default::default()
+            },
+            release: crate::config::RemoteReleaseConfig {
+                notes_file_template: Some(template.to_str().unwrap().into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: login")], config);
+        let plan = s.plan().unwrap();
+
+        let report = s.dry_run_report(&plan).unwrap();
+        assert_eq!(report.release.as_ref().unwrap().notes_file, None);
+        assert!(
+            report
+                .render_human()
+                .contains("notes: generated from commits")
+        );
+
+        let curated = dir.path().join("0.1.0.md");
+        std::fs::write(&curated, "Highlights: faster logins.\n\n{generated}").unwrap();
+        let report = s.dry_run_report(&plan).unwrap();
+        let release = report.release.as_ref().unwrap();
+        assert_eq!(release.notes_file.as_deref(), curated.to_str());
+        assert!(
+            report
+                .render_human()
+                .contains(&format!("notes: {}", curated.display()))
+        );
+
+        s.execute(&plan, false).unwrap();
+        let body = &vcs.releases.lock().unwrap()[0].1;
+        assert!(body.starts_with("Highlights: faster logins."), "{body}");
+        assert!(
+            body.contains("login"),
+            "generated notes substituted: {body}"
+        );
+        let changelog = std::fs::read_to_string(&changelog_path).unwrap();
+        assert!(!changelog.contains("Highlights"), "{changelog}");
+    }
+
+    #[test]
+    fn missing_required_notes_file_fails_the_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ReleaseConfig {
+            release: crate::config::RemoteReleaseConfig {
+                notes_file_template: Some(dir.path().join("{tag}.md").to_str().unwrap().into()),
+                notes_file_required: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: login")], config);
+        let plan = s.plan().unwrap();
+
+        let report = s.dry_run_report(&plan).unwrap();
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        assert!(report.errors[0].contains("v0.1.0.md does not exist"));
+
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(
+            err.to_string().contains("release.notes_file_required"),
+            "{err}"
+        );
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+        assert!(vcs.releases.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_writes_package_and_aggregated_changelogs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name).to_str().unwrap().to_string();
+        std::fs::create_dir_all(dir.path().join("crates/api")).unwrap();
+        let root = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(path("CHANGELOG.md")),
+                aggregate: true,
+                ..Default::default()
+            },
+            packages: vec![crate::config::PackageConfig {
+                name: "api".into(),
+                path: path("crates/api"),
+                tag_prefix: None,
+                version_files: vec![],
+                changelog: None,
+                changelog_file: None,
+                build_command: None,
+                stage_files: vec![],
+            }],
+            ..Default::default()
+        };
+        let config = root.resolve_package(&root.packages[0]);
+
+        let s = make_strategy(vec![], vec![raw_commit("feat(api): login")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        let package = std::fs::read_to_string(path("crates/api/CHANGELOG.md")).unwrap();
+        assert!(
+            package.starts_with("# Changelog\n\n## [0.1.0]("),
+            "{package}"
+        );
+        let aggregated = std::fs::read_to_string(path("CHANGELOG.md")).unwrap();
+        assert!(
+            aggregated.contains("\n\n### api 0.1.0\n\n#### Features\n"),
+            "{aggregated}"
+        );
+        assert_eq!(
+            s.git.committed.lock().unwrap()[0].0,
+            vec![path("crates/api/CHANGELOG.md"), path("CHANGELOG.md")]
+        );
+    }
+
+    #[test]
+    fn execute_uses_custom_release_commit_message() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(
+                    dir.path()
+                        .join("CHANGELOG.md")
+                        .to_str()
+                        .unwrap()
+                        .to_string(),
+                ),
+                ..Default::default()
+            },
+            release_commit_message: "release {version} [ci skip]".into(),
+            ..Default::default()
+        };
+
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        let committed = s.git.committed.lock().unwrap();
+        assert_eq!(committed[0].1, "release 0.1.0 [ci skip]");
+    }
+
+    #[test]
+    fn execute_adds_release_commit_trailers() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(
+                    dir.path()
+                        .join("CHANGELOG.md")
+                        .to_str()
+                        .unwrap()
+                        .to_string(),
+                ),
+                ..Default::default()
+            },
+            release_commit_message: "release {version}".into(),
+            release_commit_trailers: [
+                ("Skip-Checks".to_string(), "true".to_string()),
+                ("Release-Tag".to_string(), "{tag}".to_string()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        let committed = s.git.committed.lock().unwrap();
+        assert_eq!(
+            committed[0].1,
+            "release 0.1.0\n\nRelease-Tag: v0.1.0\nSkip-Checks: true"
+        );
+    }
+
+    #[test]
+    fn plan_skips_custom_release_commits() {
+        let config = ReleaseConfig {
+            release_commit_message: "release {version} [ci skip]".into(),
+            ..Default::default()
+        };
+        let s = make_strategy(
+            vec![],
+            vec![
+                raw_commit("release 1.0.0 [ci skip]"),
+                raw_commit("fix: bug"),
+            ],
+            config,
+        );
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.commits.len(), 1);
+        assert_eq!(plan.commits[0].description, "bug");
+    }
+
+    #[test]
+    fn plan_keeps_conventional_commits_that_read_like_the_template() {
+        for template in ["{version} released", "fix: {tag}", "{tag} {date}"] {
+            let config = ReleaseConfig {
+                release_commit_message: template.into(),
+                ..Default::default()
+            };
+            let s = make_strategy(
+                vec![],
+                vec![
+                    raw_commit("fix: something released"),
+                    raw_commit("fix: release notes"),
+                    raw_commit("feat: v2 layout 2026"),
+                ],
+                config,
+            );
+            let plan = s.plan().unwrap();
+            assert_eq!(plan.commits.len(), 3, "{template}");
+            assert_eq!(plan.bump, BumpLevel::Minor, "{template}");
+        }
+    }
+
+    #[test]
+    fn plan_with_only_release_commits_has_nothing_to_release() {
+        let tag = TagInfo {
+            name: "v1.0.0".into(),
+            version: Version::new(1, 0, 0),
+            sha: "a".repeat(40),
+        };
+        let s = make_strategy(
+            vec![tag],
+            vec![raw_commit("chore(release): v1.0.0 [skip ci]")],
+            ReleaseConfig::default(),
+        );
+        let err = s.plan().unwrap_err();
+        assert!(
+            matches!(err, ReleaseError::NoCommits { ref tag, .. } if tag == "v1.0.0"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn execute_skips_existing_tag() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+
+        // Pre-populate the tag to simulate it already existing
+        s.git
+            .created_tags
+            .lock()
+            .unwrap()
+            .push("v0.1.0".to_string());
+
+        s.execute(&plan, false).unwrap();
+
+        // Tag should not be created again (still only the one we pre-populated)
+        assert_eq!(s.git.created_tags.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn execute_skips_existing_release() {
+        let (s, vcs) = make_strategy_with_vcs(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+
+        // Pre-populate a release to simulate it already existing
+        vcs.releases
+            .lock()
+            .unwrap()
+            .push(("v0.1.0".to_string(), "old notes".to_string()));
+
+        s.execute(&plan, false).unwrap();
+
+        // Should have deleted the old release and created a new one
+        let deleted = vcs.deleted_releases.lock().unwrap();
+        assert_eq!(*deleted, vec!["v0.1.0"]);
+
+        let releases = vcs.releases.lock().unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].0, "v0.1.0");
+        assert_ne!(releases[0].1, "old notes");
+    }
+
+    #[test]
+    fn execute_idempotent_rerun() {
+        let (s, vcs) = make_strategy_with_vcs(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+
+        // First run
+        s.execute(&plan, false).unwrap();
+
+        // Second run should also succeed (idempotent)
+        s.execute(&plan, false).unwrap();
+
+        // Tag should only have been created once (second run skips because tag_exists)
+        assert_eq!(s.git.created_tags.lock().unwrap().len(), 1);
+
+        // Tag push should only happen once (second run skips because remote_tag_exists)
+        assert_eq!(s.git.pushed_tags.lock().unwrap().len(), 1);
+
+        // Push (commit) should happen twice (always safe)
+        assert_eq!(*s.git.push_count.lock().unwrap(), 2);
+
+        // Release should be deleted and recreated on second run
+        let deleted = vcs.deleted_releases.lock().unwrap();
+        assert_eq!(*deleted, vec!["v0.1.0"]);
+
+        let releases = vcs.releases.lock().unwrap();
+        // One entry: delete removed the first, create added a replacement
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].0, "v0.1.0");
+    }
+
+    #[test]
+    fn execute_bumps_version_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo_path = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &cargo_path,
+            "[package]\nname = \"test\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+
+        let config = ReleaseConfig {
+            version_files: vec![cargo_path.to_str().unwrap().to_string()],
+            ..Default::default()
+        };
+
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        // Verify the file was bumped
+        let contents = std::fs::read_to_string(&cargo_path).unwrap();
+        assert!(contents.contains("version = \"0.1.0\""));
+
+        // Verify it was staged alongside the commit
+        let committed = s.git.committed.lock().unwrap();
+        assert_eq!(committed.len(), 1);
+        assert!(
+            committed[0]
+                .0
+                .contains(&cargo_path.to_str().unwrap().to_string())
+        );
     }
 
     #[test]
-    fn force_releases_patch_when_no_releasable_commits() {
-        let tag = TagInfo {
-            name: "v1.2.3".into(),
-            version: Version::new(1, 2, 3),
-            sha: "d".repeat(40),
+    fn execute_stages_changelog_and_version_files_together() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo_path = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &cargo_path,
+            "[package]\nname = \"test\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+
+        let changelog_path = dir.path().join("CHANGELOG.md");
+
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(changelog_path.to_str().unwrap().to_string()),
+                ..Default::default()
+            },
+            version_files: vec![cargo_path.to_str().unwrap().to_string()],
+            ..Default::default()
         };
-        let mut s = make_strategy(
-            vec![tag],
-            vec![raw_commit("chore: rename package")],
-            ReleaseConfig::default(),
-        );
-        s.force = true;
+
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
-        assert_eq!(plan.next_version, Version::new(1, 2, 4));
-        assert_eq!(plan.bump, BumpLevel::Patch);
-    }
+        s.execute(&plan, false).unwrap();
 
-    #[test]
-    fn plan_first_release() {
-        let s = make_strategy(
-            vec![],
-            vec![raw_commit("feat: initial feature")],
-            ReleaseConfig::default(),
+        // Both changelog and version file should be staged in a single commit
+        let committed = s.git.committed.lock().unwrap();
+        assert_eq!(committed.len(), 1);
+        assert!(
+            committed[0]
+                .0
+                .contains(&changelog_path.to_str().unwrap().to_string())
+        );
+        assert!(
+            committed[0]
+                .0
+                .contains(&cargo_path.to_str().unwrap().to_string())
         );
-        let plan = s.plan().unwrap();
-        assert_eq!(plan.next_version, Version::new(0, 1, 0));
-        assert_eq!(plan.tag_name, "v0.1.0");
-        assert!(plan.current_version.is_none());
     }
 
+    // --- artifact upload tests ---
+
     #[test]
-    fn plan_increments_existing() {
-        let tag = TagInfo {
-            name: "v1.2.3".into(),
-            version: Version::new(1, 2, 3),
-            sha: "b".repeat(40),
+    fn execute_uploads_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.tar.gz"), "fake tarball").unwrap();
+        std::fs::write(dir.path().join("app.zip"), "fake zip").unwrap();
+
+        let config = ReleaseConfig {
+            artifacts: vec![
+                dir.path()
+                    .join("*.tar.gz")
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+                    .into(),
+                dir.path()
+                    .join("*.zip")
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+                    .into(),
+            ],
+            ..Default::default()
         };
-        let s = make_strategy(
-            vec![tag],
-            vec![raw_commit("fix: patch bug")],
-            ReleaseConfig::default(),
-        );
+
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
-        assert_eq!(plan.next_version, Version::new(1, 2, 4));
-    }
+        s.execute(&plan, false).unwrap();
 
-    #[test]
-    fn plan_breaking_bump() {
-        let tag = TagInfo {
-            name: "v1.2.3".into(),
-            version: Version::new(1, 2, 3),
-            sha: "c".repeat(40),
-        };
-        let s = make_strategy(
-            vec![tag],
-            vec![raw_commit("feat!: breaking change")],
-            ReleaseConfig::default(),
+        let uploaded = vcs.uploaded_assets.lock().unwrap();
+        assert_eq!(uploaded.len(), 1);
+        assert_eq!(uploaded[0].0, "v0.1.0");
+        // 2 artifacts + 2 SHA256 checksum sidecar files
+        assert_eq!(uploaded[0].1.len(), 4);
+        assert!(uploaded[0].1.iter().any(|f| f.ends_with("app.tar.gz")));
+        assert!(uploaded[0].1.iter().any(|f| f.ends_with("app.zip")));
+        assert!(
+            uploaded[0]
+                .1
+                .iter()
+                .any(|f| f.ends_with("app.tar.gz.sha256"))
         );
-        let plan = s.plan().unwrap();
-        assert_eq!(plan.next_version, Version::new(2, 0, 0));
+        assert!(uploaded[0].1.iter().any(|f| f.ends_with("app.zip.sha256")));
     }
 
     #[test]
-    fn plan_v0_breaking_downshifts_to_minor() {
-        let tag = TagInfo {
-            name: "v0.5.0".into(),
-            version: Version::new(0, 5, 0),
-            sha: "c".repeat(40),
+    fn execute_report_records_files_assets_and_releases() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo_path = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &cargo_path,
+            "[package]\nname = \"test\"\nversion = \"0.0.5\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("app.tar.gz"), "hello").unwrap();
+
+        let config = ReleaseConfig {
+            version_files: vec![cargo_path.to_str().unwrap().to_string()],
+            artifacts: vec![
+                dir.path()
+                    .join("*.tar.gz")
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+                    .into(),
+            ],
+            ..Default::default()
         };
-        let s = make_strategy(
-            vec![tag],
-            vec![raw_commit("feat!: breaking change")],
-            ReleaseConfig::default(),
-        );
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
-        // v0 protection: Major → Minor, so 0.5.0 → 0.6.0 (not 1.0.0)
-        assert_eq!(plan.next_version, Version::new(0, 6, 0));
-        assert_eq!(plan.bump, BumpLevel::Minor);
-    }
+        let report = s.execute_report(&plan).unwrap();
 
-    #[test]
-    fn plan_v0_breaking_with_force_bumps_major() {
-        let tag = TagInfo {
-            name: "v0.5.0".into(),
-            version: Version::new(0, 5, 0),
-            sha: "c".repeat(40),
-        };
-        let mut s = make_strategy(
-            vec![tag],
-            vec![raw_commit("feat!: breaking change")],
-            ReleaseConfig::default(),
+        assert_eq!(report.plan.tag_name, "v0.1.0");
+        assert_eq!(report.head_sha.as_deref(), Some(plan.head_sha.as_str()));
+        assert!(report.changelog.contains("something"));
+        assert_eq!(report.version_files.len(), 1);
+        assert_eq!(
+            report.version_files[0].old_version.as_deref(),
+            Some("0.0.5")
         );
-        s.force = true;
-        let plan = s.plan().unwrap();
-        // --force bypasses v0 protection
-        assert_eq!(plan.next_version, Version::new(1, 0, 0));
-        assert_eq!(plan.bump, BumpLevel::Major);
+        assert_eq!(report.version_files[0].new_version, "0.1.0");
+        let names: Vec<_> = report.assets.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["app.tar.gz", "app.tar.gz.sha256"]);
+        assert_eq!(
+            report.assets[0].sha256,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(
+            report.releases[0].url,
+            "https://github.com/test/release/v0.1.0"
+        );
+        let steps: Vec<_> = report.steps.iter().map(|s| s.name.as_str()).collect();
+        assert!(
+            steps.starts_with(&["preflight", "acquire lock"]),
+            "{steps:?}"
+        );
+        assert!(steps.contains(&"create tag"), "{steps:?}");
+        assert!(report.steps.iter().all(|s| s.finished_at.is_some()));
+        assert!(report.finished_at.is_some());
     }
 
     #[test]
-    fn plan_v0_feat_stays_minor() {
-        let tag = TagInfo {
-            name: "v0.5.0".into(),
-            version: Version::new(0, 5, 0),
-            sha: "c".repeat(40),
+    fn execute_writes_and_uploads_release_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let summary = dir.path().join("release-summary.json");
+        let config = ReleaseConfig {
+            summary: crate::config::SummaryConfig {
+                file: Some(summary.to_str().unwrap().to_string()),
+                upload: true,
+            },
+            ..Default::default()
         };
-        let s = make_strategy(
-            vec![tag],
-            vec![raw_commit("feat: new feature")],
-            ReleaseConfig::default(),
-        );
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
-        // Non-breaking feat in v0 stays as minor bump
-        assert_eq!(plan.next_version, Version::new(0, 6, 0));
-        assert_eq!(plan.bump, BumpLevel::Minor);
+        s.execute(&plan, false).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&summary).unwrap()).unwrap();
+        assert_eq!(json["plan"]["tag_name"], "v0.1.0");
+        assert_eq!(json["sr_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            json["releases"][0]["url"],
+            "https://github.com/test/release/v0.1.0"
+        );
+        let uploaded = vcs.uploaded_assets.lock().unwrap();
+        assert_eq!(uploaded.len(), 1);
+        assert_eq!(uploaded[0].1, vec![summary.to_str().unwrap().to_string()]);
     }
 
     #[test]
-    fn plan_v0_fix_stays_patch() {
-        let tag = TagInfo {
-            name: "v0.5.0".into(),
-            version: Version::new(0, 5, 0),
-            sha: "c".repeat(40),
+    fn execute_dry_run_writes_no_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let summary = dir.path().join("release-summary.json");
+        let config = ReleaseConfig {
+            summary: crate::config::SummaryConfig {
+                file: Some(summary.to_str().unwrap().to_string()),
+                upload: false,
+            },
+            ..Default::default()
         };
-        let s = make_strategy(
-            vec![tag],
-            vec![raw_commit("fix: bug fix")],
-            ReleaseConfig::default(),
-        );
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
-        // Fix in v0 stays as patch
-        assert_eq!(plan.next_version, Version::new(0, 5, 1));
-        assert_eq!(plan.bump, BumpLevel::Patch);
+        s.execute(&plan, true).unwrap();
+        assert!(!summary.exists());
     }
 
-    // --- execute() tests ---
-
     #[test]
-    fn execute_dry_run_no_side_effects() {
-        let s = make_strategy(
-            vec![],
-            vec![raw_commit("feat: something")],
-            ReleaseConfig::default(),
-        );
+    fn execute_dry_run_shows_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.tar.gz"), "fake tarball").unwrap();
+
+        let config = ReleaseConfig {
+            artifacts: vec![
+                dir.path()
+                    .join("*.tar.gz")
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+                    .into(),
+            ],
+            ..Default::default()
+        };
+
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
         s.execute(&plan, true).unwrap();
 
-        assert!(s.git.created_tags.lock().unwrap().is_empty());
-        assert!(s.git.pushed_tags.lock().unwrap().is_empty());
+        // No uploads should happen during dry-run
+        let uploaded = vcs.uploaded_assets.lock().unwrap();
+        assert!(uploaded.is_empty());
     }
 
-    #[test]
-    fn execute_creates_and_pushes_tag() {
-        let s = make_strategy(
-            vec![],
-            vec![raw_commit("feat: something")],
-            ReleaseConfig::default(),
-        );
-        let plan = s.plan().unwrap();
-        s.execute(&plan, false).unwrap();
-
-        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
-        assert_eq!(*s.git.pushed_tags.lock().unwrap(), vec!["v0.1.0"]);
+    fn attestations_config(run: &str, required: bool) -> AttestationsConfig {
+        AttestationsConfig {
+            enabled: true,
+            required,
+            embed_digests: true,
+            name: Some("app".into()),
+            generators: vec![AttestationGenerator {
+                format: "spdx.json".into(),
+                run: run.into(),
+            }],
+        }
     }
 
     #[test]
-    fn execute_calls_vcs_create_release() {
-        let s = make_strategy(
-            vec![],
-            vec![raw_commit("feat: something")],
-            ReleaseConfig::default(),
-        );
+    fn execute_uploads_attestations_and_embeds_digests() {
+        let config = ReleaseConfig {
+            attestations: attestations_config("echo '{\"spdxVersion\": \"SPDX-2.3\"}'", true),
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
         s.execute(&plan, false).unwrap();
 
-        let releases = s.vcs.as_ref().unwrap().releases.lock().unwrap();
-        assert_eq!(releases.len(), 1);
-        assert_eq!(releases[0].0, "v0.1.0");
-        assert!(!releases[0].1.is_empty());
+        let uploaded = vcs.uploaded_assets.lock().unwrap();
+        assert_eq!(uploaded.len(), 1);
+        let files = &uploaded[0].1;
+        assert_eq!(files.len(), 2, "{files:?}");
+        assert!(files[0].ends_with("app-0.1.0.spdx.json"), "{files:?}");
+        assert!(
+            files[1].ends_with("app-0.1.0.spdx.json.sha256"),
+            "{files:?}"
+        );
+        // The temporary attestation directory is gone after the upload.
+        assert!(!Path::new(&files[0]).exists());
+
+        let releases = vcs.releases.lock().unwrap();
+        let body = &releases[0].1;
+        assert!(body.contains("### Artifact digests"), "{body}");
+        assert!(body.contains("| `app-0.1.0.spdx.json` | `"), "{body}");
     }
 
     #[test]
-    fn execute_commits_changelog_before_tag() {
-        let dir = tempfile::tempdir().unwrap();
-        let changelog_path = dir.path().join("CHANGELOG.md");
+    fn execute_required_attestation_failure_aborts_before_commit() {
+        let config = ReleaseConfig {
+            attestations: attestations_config("exit 1", true),
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(matches!(err, ReleaseError::BuildCommand(_)), "{err}");
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+        assert!(vcs.releases.lock().unwrap().is_empty());
+    }
 
+    #[test]
+    fn execute_optional_attestation_failure_is_skipped() {
         let config = ReleaseConfig {
-            changelog: crate::config::ChangelogConfig {
-                file: Some(changelog_path.to_str().unwrap().to_string()),
-                ..Default::default()
-            },
+            attestations: attestations_config("exit 1", false),
             ..Default::default()
         };
-
-        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
         s.execute(&plan, false).unwrap();
+        assert!(vcs.uploaded_assets.lock().unwrap().is_empty());
+        assert_eq!(vcs.releases.lock().unwrap().len(), 1);
+    }
 
-        // Verify changelog was committed
-        let committed = s.git.committed.lock().unwrap();
-        assert_eq!(committed.len(), 1);
-        assert_eq!(
-            committed[0].0,
-            vec![changelog_path.to_str().unwrap().to_string()]
+    #[test]
+    fn dry_run_report_lists_attestations() {
+        let config = ReleaseConfig {
+            attestations: attestations_config("syft . -o spdx-json", true),
+            ..Default::default()
+        };
+        let (s, _vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        let report = s.dry_run_report(&plan).unwrap();
+        assert_eq!(report.attestations.len(), 1);
+        assert_eq!(report.attestations[0].file, "app-0.1.0.spdx.json");
+        assert!(report.embed_digests);
+        let human = report.render_human();
+        assert!(
+            human.contains(
+                "Would generate attestation: app-0.1.0.spdx.json (from `syft . -o spdx-json`)"
+            ),
+            "{human}"
         );
-        assert!(committed[0].1.contains("chore(release): v0.1.0"));
-
-        // Verify tag was created after commit
-        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert!(human.contains("Would embed artifact digests"), "{human}");
     }
 
     #[test]
-    fn execute_skips_existing_tag() {
-        let s = make_strategy(
-            vec![],
+    fn dry_run_report_describes_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("package.json");
+        std::fs::write(&manifest, r#"{"name": "x", "version": "1.0.0"}"#).unwrap();
+        std::fs::write(dir.path().join("app.tar.gz"), "fake tarball").unwrap();
+
+        let config = ReleaseConfig {
+            version_files: vec![manifest.to_str().unwrap().to_string()],
+            artifacts: vec![
+                dir.path()
+                    .join("*.tar.gz")
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+                    .into(),
+            ],
+            build_command: Some("make dist".into()),
+            post_release_command: Some("echo done".into()),
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(
+            vec![TagInfo {
+                name: "v1.0.0".into(),
+                version: Version::new(1, 0, 0),
+                sha: "d".repeat(40),
+            }],
             vec![raw_commit("feat: something")],
-            ReleaseConfig::default(),
+            config,
         );
         let plan = s.plan().unwrap();
-
-        // Pre-populate the tag to simulate it already existing
-        s.git
-            .created_tags
+        vcs.releases
             .lock()
             .unwrap()
-            .push("v0.1.0".to_string());
-
-        s.execute(&plan, false).unwrap();
+            .push((plan.tag_name.clone(), String::new()));
 
-        // Tag should not be created again (still only the one we pre-populated)
-        assert_eq!(s.git.created_tags.lock().unwrap().len(), 1);
+        let report = s.dry_run_report(&plan).unwrap();
+        assert_eq!(report.plan.tag_name, "v1.1.0");
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+        assert_eq!(report.version_files.len(), 1);
+        assert_eq!(
+            report.version_files[0].old_version.as_deref(),
+            Some("1.0.0")
+        );
+        assert_eq!(report.version_files[0].new_version, "1.1.0");
+        assert_eq!(report.artifacts.len(), 1);
+        assert_eq!(report.artifacts[0].size, "fake tarball".len() as u64);
+        let release = report.release.as_ref().unwrap();
+        assert_eq!(release.providers[0].action, ReleaseActionKind::Update);
+        assert!(report.changelog.contains("something"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["release"]["providers"][0]["action"], "update");
+        assert_eq!(json["build_command"], "make dist");
+
+        let human = report.render_human();
+        assert!(human.contains("[dry-run] Would bump version in: "));
+        assert!(human.contains("(1.0.0 -> 1.1.0)"));
+        assert!(human.contains("[dry-run] Would run post-release command: echo done\n"));
+
+        // Nothing was touched
+        assert!(
+            std::fs::read_to_string(&manifest)
+                .unwrap()
+                .contains("1.0.0")
+        );
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
     }
 
     #[test]
-    fn execute_skips_existing_release() {
-        let s = make_strategy(
-            vec![],
-            vec![raw_commit("feat: something")],
-            ReleaseConfig::default(),
-        );
+    fn dry_run_report_checks_what_a_rerun_would_skip_or_replace() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset = dir.path().join("app.tar.gz");
+        std::fs::write(&asset, "fake tarball").unwrap();
+        let config = ReleaseConfig {
+            artifacts: vec![asset.to_str().unwrap().to_string().into()],
+            ..Default::default()
+        };
+        let (mut s, mut vcs) =
+            make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        vcs.drafts = true;
+        s.vcs = vec![Box::new(vcs.clone())];
         let plan = s.plan().unwrap();
-
-        // Pre-populate a release to simulate it already existing
-        s.vcs
-            .as_ref()
+        s.git
+            .pushed_tags
+            .lock()
             .unwrap()
-            .releases
+            .push(plan.tag_name.clone());
+        vcs.releases
             .lock()
             .unwrap()
-            .push(("v0.1.0".to_string(), "old notes".to_string()));
-
-        s.execute(&plan, false).unwrap();
-
-        // Should have deleted the old release and created a new one
-        let deleted = s.vcs.as_ref().unwrap().deleted_releases.lock().unwrap();
-        assert_eq!(*deleted, vec!["v0.1.0"]);
+            .push((plan.tag_name.clone(), String::new()));
+        vcs.uploaded_assets.lock().unwrap().push((
+            plan.tag_name.clone(),
+            vec![asset.to_str().unwrap().to_string()],
+        ));
+
+        let report = s.dry_run_report(&plan).unwrap();
+        assert!(report.tag.on_remote);
+        let provider = &report.release.as_ref().unwrap().providers[0];
+        assert_eq!(provider.action, ReleaseActionKind::Update);
+        assert_eq!(provider.replaced_assets, ["app.tar.gz"]);
+        let human = report.render_human();
+        assert!(
+            human.contains("Tag v0.1.0 is already on origin: would skip pushing it"),
+            "{human}"
+        );
+        assert!(
+            human.contains("update existing release, replacing 1 asset(s): app.tar.gz"),
+            "{human}"
+        );
 
-        let releases = s.vcs.as_ref().unwrap().releases.lock().unwrap();
-        assert_eq!(releases.len(), 1);
-        assert_eq!(releases[0].0, "v0.1.0");
-        assert_ne!(releases[0].1, "old notes");
+        // Offline, nothing is asked and the report stays generic
+        s.offline = true;
+        let report = s.dry_run_report(&plan).unwrap();
+        assert!(!report.tag.on_remote);
+        let provider = &report.release.as_ref().unwrap().providers[0];
+        assert_eq!(provider.action, ReleaseActionKind::Create);
+        assert!(provider.replaced_assets.is_empty());
     }
 
     #[test]
-    fn execute_idempotent_rerun() {
-        let s = make_strategy(
+    fn dry_run_report_turns_preflight_failures_into_warnings() {
+        let mut s = make_strategy(
             vec![],
             vec![raw_commit("feat: something")],
             ReleaseConfig::default(),
         );
+        s.git.branch = Some("feature/x".into());
         let plan = s.plan().unwrap();
 
-        // First run
-        s.execute(&plan, false).unwrap();
-
-        // Second run should also succeed (idempotent)
-        s.execute(&plan, false).unwrap();
-
-        // Tag should only have been created once (second run skips because tag_exists)
-        assert_eq!(s.git.created_tags.lock().unwrap().len(), 1);
+        let report = s.dry_run_report(&plan).unwrap();
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.render_human().starts_with("[dry-run] warning: "));
+        assert!(s.execute(&plan, false).is_err());
+    }
 
-        // Tag push should only happen once (second run skips because remote_tag_exists)
-        assert_eq!(s.git.pushed_tags.lock().unwrap().len(), 1);
+    #[test]
+    fn required_artifacts_missing_fail_before_tagging() {
+        let dir = tempfile::tempdir().unwrap();
+        let pattern = dir.path().join("*.tar.gz").to_str().unwrap().to_string();
+        let config = ReleaseConfig {
+            artifacts: vec![pattern.clone().into()],
+            artifacts_required: true,
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
 
-        // Push (commit) should happen twice (always safe)
-        assert_eq!(*s.git.push_count.lock().unwrap(), 2);
+        let report = s.dry_run_report(&plan).unwrap();
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        assert!(report.errors[0].contains(&pattern), "{}", report.errors[0]);
+        assert!(report.render_human().starts_with("[dry-run] error: "));
 
-        // Release should be deleted and recreated on second run
-        let deleted = s.vcs.as_ref().unwrap().deleted_releases.lock().unwrap();
-        assert_eq!(*deleted, vec!["v0.1.0"]);
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(matches!(err, ReleaseError::Artifacts(_)), "{err}");
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+        assert!(s.git.committed.lock().unwrap().is_empty());
+        assert!(vcs.releases.lock().unwrap().is_empty());
 
-        let releases = s.vcs.as_ref().unwrap().releases.lock().unwrap();
-        // One entry: delete removed the first, create added a replacement
-        assert_eq!(releases.len(), 1);
-        assert_eq!(releases[0].0, "v0.1.0");
+        // Once the build produces a match, the release goes ahead
+        std::fs::write(dir.path().join("app.tar.gz"), "tarball").unwrap();
+        assert!(s.dry_run_report(&plan).unwrap().errors.is_empty());
+        s.execute(&plan, false).unwrap();
+        assert_eq!(vcs.uploaded_assets.lock().unwrap().len(), 1);
     }
 
     #[test]
-    fn execute_bumps_version_files() {
+    fn renamed_artifacts_upload_under_their_templated_names() {
         let dir = tempfile::tempdir().unwrap();
-        let cargo_path = dir.path().join("Cargo.toml");
-        std::fs::write(
-            &cargo_path,
-            "[package]\nname = \"test\"\nversion = \"0.0.0\"\n",
-        )
-        .unwrap();
-
+        std::fs::write(dir.path().join("myapp-linux.tar.gz"), "tarball").unwrap();
+        std::fs::write(dir.path().join("myapp-mac.zip"), "zip").unwrap();
+        let renamed = |rename: &str| crate::config::ArtifactEntry::Pattern {
+            path: dir.path().join("myapp-*").to_str().unwrap().to_string(),
+            required: None,
+            rename: Some(rename.into()),
+        };
         let config = ReleaseConfig {
-            version_files: vec![cargo_path.to_str().unwrap().to_string()],
+            artifacts: vec![renamed("{basename}-{version}{ext}")],
             ..Default::default()
         };
-
-        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
         let plan = s.plan().unwrap();
-        s.execute(&plan, false).unwrap();
 
-        // Verify the file was bumped
-        let contents = std::fs::read_to_string(&cargo_path).unwrap();
-        assert!(contents.contains("version = \"0.1.0\""));
+        let report = s.dry_run_report(&plan).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        let human = report.render_human();
+        assert!(
+            human.contains("myapp-linux.tar.gz as myapp-linux-0.1.0.tar.gz"),
+            "{human}"
+        );
 
-        // Verify it was staged alongside the commit
-        let committed = s.git.committed.lock().unwrap();
-        assert_eq!(committed.len(), 1);
+        s.execute(&plan, false).unwrap();
+        let uploaded = vcs.uploaded_assets.lock().unwrap();
+        let names: Vec<_> = uploaded[0]
+            .1
+            .iter()
+            .filter_map(|path| Path::new(path).file_name()?.to_str())
+            .filter(|name| !name.ends_with(".sha256"))
+            .collect();
+        assert_eq!(names, ["myapp-linux-0.1.0.tar.gz", "myapp-mac-0.1.0.zip"]);
+        drop(uploaded);
+
+        // Two files templated to the same name stop the release before the tag
+        let config = ReleaseConfig {
+            artifacts: vec![renamed("myapp-{version}")],
+            ..Default::default()
+        };
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let report = s.dry_run_report(&plan).unwrap();
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
         assert!(
-            committed[0]
-                .0
-                .contains(&cargo_path.to_str().unwrap().to_string())
+            report.errors[0].contains("uploaded as myapp-0.1.0"),
+            "{}",
+            report.errors[0]
         );
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(matches!(err, ReleaseError::Artifacts(_)), "{err}");
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+        assert!(vcs.uploaded_assets.lock().unwrap().is_empty());
     }
 
     #[test]
-    fn execute_stages_changelog_and_version_files_together() {
+    fn new_release_is_published_after_its_assets_upload() {
         let dir = tempfile::tempdir().unwrap();
-        let cargo_path = dir.path().join("Cargo.toml");
-        std::fs::write(
-            &cargo_path,
-            "[package]\nname = \"test\"\nversion = \"0.0.0\"\n",
-        )
-        .unwrap();
-
-        let changelog_path = dir.path().join("CHANGELOG.md");
-
+        std::fs::write(dir.path().join("app.tar.gz"), "tarball").unwrap();
         let config = ReleaseConfig {
-            changelog: crate::config::ChangelogConfig {
-                file: Some(changelog_path.to_str().unwrap().to_string()),
-                ..Default::default()
-            },
-            version_files: vec![cargo_path.to_str().unwrap().to_string()],
+            artifacts: vec![dir.path().join("*.tar.gz").to_str().unwrap().into()],
             ..Default::default()
         };
-
-        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let (mut s, _) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: x")], config);
+        let vcs = FakeVcs {
+            drafts: true,
+            ..FakeVcs::new()
+        };
+        s.vcs = vec![Box::new(vcs.clone())];
         let plan = s.plan().unwrap();
         s.execute(&plan, false).unwrap();
 
-        // Both changelog and version file should be staged in a single commit
-        let committed = s.git.committed.lock().unwrap();
-        assert_eq!(committed.len(), 1);
-        assert!(
-            committed[0]
-                .0
-                .contains(&changelog_path.to_str().unwrap().to_string())
-        );
-        assert!(
-            committed[0]
-                .0
-                .contains(&cargo_path.to_str().unwrap().to_string())
+        assert_eq!(
+            *vcs.calls.lock().unwrap(),
+            ["create v0.1.0 draft", "upload v0.1.0", "publish v0.1.0"]
         );
+        // The asset and its checksum sidecar
+        assert_eq!(vcs.uploaded_assets.lock().unwrap()[0].1.len(), 2);
     }
 
-    // --- artifact upload tests ---
-
     #[test]
-    fn execute_uploads_artifacts() {
+    fn failed_upload_discards_the_draft_instead_of_publishing() {
         let dir = tempfile::tempdir().unwrap();
-        std::fs::write(dir.path().join("app.tar.gz"), "fake tarball").unwrap();
-        std::fs::write(dir.path().join("app.zip"), "fake zip").unwrap();
-
+        std::fs::write(dir.path().join("app.tar.gz"), "tarball").unwrap();
         let config = ReleaseConfig {
-            artifacts: vec![
-                dir.path().join("*.tar.gz").to_str().unwrap().to_string(),
-                dir.path().join("*.zip").to_str().unwrap().to_string(),
-            ],
+            artifacts: vec![dir.path().join("*.tar.gz").to_str().unwrap().into()],
             ..Default::default()
         };
-
-        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let (mut s, _) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: x")], config);
+        let vcs = FakeVcs {
+            drafts: true,
+            fail_upload: true,
+            ..FakeVcs::new()
+        };
+        s.vcs = vec![Box::new(vcs.clone())];
         let plan = s.plan().unwrap();
-        s.execute(&plan, false).unwrap();
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(err.to_string().contains("upload timed out"), "{err}");
 
-        let uploaded = s.vcs.as_ref().unwrap().uploaded_assets.lock().unwrap();
-        assert_eq!(uploaded.len(), 1);
-        assert_eq!(uploaded[0].0, "v0.1.0");
-        // 2 artifacts + 2 SHA256 checksum sidecar files
-        assert_eq!(uploaded[0].1.len(), 4);
-        assert!(uploaded[0].1.iter().any(|f| f.ends_with("app.tar.gz")));
-        assert!(uploaded[0].1.iter().any(|f| f.ends_with("app.zip")));
-        assert!(
-            uploaded[0]
-                .1
-                .iter()
-                .any(|f| f.ends_with("app.tar.gz.sha256"))
+        assert_eq!(
+            *vcs.calls.lock().unwrap(),
+            ["create v0.1.0 draft", "upload v0.1.0", "discard v0.1.0"]
         );
-        assert!(uploaded[0].1.iter().any(|f| f.ends_with("app.zip.sha256")));
+        assert!(vcs.releases.lock().unwrap().is_empty());
     }
 
     #[test]
-    fn execute_dry_run_shows_artifacts() {
+    fn providers_without_drafts_publish_before_uploading() {
         let dir = tempfile::tempdir().unwrap();
-        std::fs::write(dir.path().join("app.tar.gz"), "fake tarball").unwrap();
-
+        std::fs::write(dir.path().join("app.tar.gz"), "tarball").unwrap();
         let config = ReleaseConfig {
-            artifacts: vec![dir.path().join("*.tar.gz").to_str().unwrap().to_string()],
+            artifacts: vec![dir.path().join("*.tar.gz").to_str().unwrap().into()],
             ..Default::default()
         };
-
-        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let (s, vcs) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: x")], config);
         let plan = s.plan().unwrap();
-        s.execute(&plan, true).unwrap();
+        s.execute(&plan, false).unwrap();
 
-        // No uploads should happen during dry-run
-        let uploaded = s.vcs.as_ref().unwrap().uploaded_assets.lock().unwrap();
-        assert!(uploaded.is_empty());
+        assert_eq!(
+            *vcs.calls.lock().unwrap(),
+            ["create v0.1.0 published", "upload v0.1.0"]
+        );
     }
 
     #[test]
     fn execute_no_artifacts_skips_upload() {
-        let s = make_strategy(
+        let (s, vcs) = make_strategy_with_vcs(
             vec![],
             vec![raw_commit("feat: something")],
             ReleaseConfig::default(),
@@ -1398,7 +6215,7 @@ mod tests {
         let plan = s.plan().unwrap();
         s.execute(&plan, false).unwrap();
 
-        let uploaded = s.vcs.as_ref().unwrap().uploaded_assets.lock().unwrap();
+        let uploaded = vcs.uploaded_assets.lock().unwrap();
         assert!(uploaded.is_empty());
     }
 
@@ -1498,6 +6315,50 @@ mod tests {
         assert_eq!(*s.git.force_pushed_tags.lock().unwrap(), vec!["v1"]);
     }
 
+    /// A `fix` planned on top of `v1.9.0`, with `later` tagged after the plan
+    /// was made (as on a maintenance branch, where newer tags are not reachable).
+    fn maintenance_release(later: &[(&str, Version)], no_push: bool) -> TestStrategy {
+        let config = ReleaseConfig {
+            floating_tags: true,
+            ..Default::default()
+        };
+        let base = tag("v1.9.0", Version::new(1, 9, 0), 'a');
+        let mut s = make_strategy(vec![base], vec![raw_commit("fix: backport")], config);
+        s.options.no_push = no_push;
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.tag_name, "v1.9.1");
+        s.git.tags.extend(
+            later
+                .iter()
+                .map(|(name, version)| tag(name, version.clone(), 'b')),
+        );
+        s.execute(&plan, false).unwrap();
+        s
+    }
+
+    #[test]
+    fn execute_leaves_floating_tag_when_its_major_has_a_newer_release() {
+        let s = maintenance_release(&[("v1.9.2", Version::new(1, 9, 2))], false);
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v1.9.1"]);
+        assert!(s.git.force_created_tags.lock().unwrap().is_empty());
+        assert!(s.git.force_pushed_tags.lock().unwrap().is_empty());
+
+        let s = maintenance_release(&[("v1.10.0", Version::new(1, 10, 0))], true);
+        assert!(s.git.force_created_tags.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_moves_floating_tag_of_an_old_major_it_leads() {
+        let s = maintenance_release(
+            &[
+                ("v2.3.0", Version::new(2, 3, 0)),
+                ("v1.9.2-rc.1", Version::parse("1.9.2-rc.1").unwrap()),
+            ],
+            false,
+        );
+        assert_eq!(*s.git.force_pushed_tags.lock().unwrap(), vec!["v1"]);
+    }
+
     #[test]
     fn execute_no_floating_tags_when_disabled() {
         let s = make_strategy(
@@ -1565,16 +6426,92 @@ mod tests {
             version: Version::new(1, 2, 3),
             sha: "a".repeat(40),
         };
-        let mut s = make_strategy(vec![tag], vec![], ReleaseConfig::default());
-        // HEAD == tag SHA, and no new commits
+        let mut s = make_strategy(vec![tag], vec![], ReleaseConfig::default());
+        // HEAD == tag SHA, and no new commits
+        s.git.head = "a".repeat(40);
+        s.force = true;
+
+        let plan = s.plan().unwrap();
+        assert_eq!(plan.next_version, Version::new(1, 2, 3));
+        assert_eq!(plan.tag_name, "v1.2.3");
+        assert!(plan.commits.is_empty());
+        assert_eq!(plan.current_version, Some(Version::new(1, 2, 3)));
+        assert!(plan.forced);
+    }
+
+    #[test]
+    fn force_rerelease_updates_release_and_reuploads_assets() {
+        let dir = tempfile::tempdir().unwrap();
+        let artifact = dir.path().join("app.tar.gz");
+        std::fs::write(&artifact, "binary").unwrap();
+        let config = ReleaseConfig {
+            artifacts: vec![artifact.to_str().unwrap().to_string().into()],
+            ..Default::default()
+        };
+        let tag = TagInfo {
+            name: "v1.2.3".into(),
+            version: Version::new(1, 2, 3),
+            sha: "a".repeat(40),
+        };
+        let (mut s, vcs) = make_strategy_with_vcs(vec![tag], vec![], config);
         s.git.head = "a".repeat(40);
+        s.git.created_tags.lock().unwrap().push("v1.2.3".into());
+        s.git.pushed_tags.lock().unwrap().push("v1.2.3".into());
+        vcs.releases
+            .lock()
+            .unwrap()
+            .push(("v1.2.3".into(), "old notes".into()));
         s.force = true;
 
         let plan = s.plan().unwrap();
-        assert_eq!(plan.next_version, Version::new(1, 2, 3));
-        assert_eq!(plan.tag_name, "v1.2.3");
+        s.execute(&plan, false).unwrap();
+
+        // Tag reused, release replaced rather than duplicated, assets uploaded again
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v1.2.3"]);
+        let releases = vcs.releases.lock().unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].0, "v1.2.3");
+        let uploads = vcs.uploaded_assets.lock().unwrap();
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(uploads[0].0, "v1.2.3");
+    }
+
+    #[test]
+    fn force_rerelease_regenerates_the_original_notes() {
+        let tags = vec![
+            TagInfo {
+                name: "v1.2.2".into(),
+                version: Version::new(1, 2, 2),
+                sha: "b".repeat(40),
+            },
+            TagInfo {
+                name: "v1.2.3".into(),
+                version: Version::new(1, 2, 3),
+                sha: "a".repeat(40),
+            },
+        ];
+        let (mut s, vcs) = make_strategy_with_vcs(tags, vec![], ReleaseConfig::default());
+        s.git.head = "a".repeat(40);
+        s.git.range_commits = Some(vec![raw_commit("fix: the original fix")]);
+        s.force = true;
+
+        let plan = s.plan().unwrap();
+        assert!(plan.forced);
         assert!(plan.commits.is_empty());
-        assert_eq!(plan.current_version, Some(Version::new(1, 2, 3)));
+        s.execute(&plan, false).unwrap();
+
+        // The range the original release covered: previous tag to the re-released one
+        assert!(
+            s.git
+                .seen_ranges
+                .lock()
+                .unwrap()
+                .contains(&(Some("b".repeat(40)), "v1.2.3".to_string()))
+        );
+        let releases = vcs.releases.lock().unwrap();
+        let body = &releases[0].1;
+        assert!(body.contains("the original fix"), "{body}");
+        assert!(body.contains("compare/v1.2.2...v1.2.3"), "{body}");
     }
 
     #[test]
@@ -1601,10 +6538,9 @@ mod tests {
         let output_file = dir.path().join("sr_test_version");
 
         let config = ReleaseConfig {
-            build_command: Some(format!(
-                "echo $SR_VERSION > {}",
-                output_file.to_str().unwrap()
-            )),
+            build_command: Some(
+                format!("echo $SR_VERSION > {}", output_file.to_str().unwrap()).into(),
+            ),
             ..Default::default()
         };
 
@@ -1637,7 +6573,7 @@ mod tests {
         let output_file = dir.path().join("sr_test_should_not_exist");
 
         let config = ReleaseConfig {
-            build_command: Some(format!("echo test > {}", output_file.to_str().unwrap())),
+            build_command: Some(format!("echo test > {}", output_file.to_str().unwrap()).into()),
             ..Default::default()
         };
 
@@ -1666,7 +6602,9 @@ mod tests {
         std::fs::write(&lock_file, "old lock").unwrap();
 
         let config = ReleaseConfig {
-            build_command: Some(format!("echo 'new lock' > {}", lock_file.to_str().unwrap())),
+            build_command: Some(
+                format!("echo 'new lock' > {}", lock_file.to_str().unwrap()).into(),
+            ),
             stage_files: vec![lock_file.to_str().unwrap().to_string()],
             ..Default::default()
         };
@@ -1733,6 +6671,416 @@ mod tests {
         );
     }
 
+    // --- rollback command tests ---
+
+    fn tag(name: &str, version: Version, sha: char) -> TagInfo {
+        TagInfo {
+            name: name.into(),
+            version,
+            sha: sha.to_string().repeat(40),
+        }
+    }
+
+    /// v1.0.0 and v1.1.0 released, v1 floating at v1.1.0, release v1.1.0 published.
+    fn released_strategy(config: ReleaseConfig, commits: Vec<Commit>) -> (TestStrategy, FakeVcs) {
+        let tags = vec![
+            tag("v1.0.0", Version::new(1, 0, 0), 'a'),
+            tag("v1.1.0", Version::new(1, 1, 0), 'b'),
+        ];
+        let (s, vcs) = make_strategy_with_vcs(tags, commits, config);
+        for name in ["v1.0.0", "v1.1.0", "v1"] {
+            s.git.created_tags.lock().unwrap().push(name.into());
+            s.git.pushed_tags.lock().unwrap().push(name.into());
+        }
+        vcs.releases
+            .lock()
+            .unwrap()
+            .push(("v1.1.0".into(), String::new()));
+        (s, vcs)
+    }
+
+    #[test]
+    fn rollback_deletes_release_and_tags_and_moves_floating_tag() {
+        let config = ReleaseConfig {
+            floating_tags: true,
+            ..Default::default()
+        };
+        let (s, vcs) = released_strategy(config, vec![]);
+        s.rollback("v1.1.0", false, false).unwrap();
+
+        assert_eq!(*vcs.deleted_releases.lock().unwrap(), vec!["v1.1.0"]);
+        assert_eq!(*s.git.deleted_tags.lock().unwrap(), vec!["v1.1.0"]);
+        assert_eq!(*s.git.deleted_remote_tags.lock().unwrap(), vec!["v1.1.0"]);
+        assert_eq!(
+            *s.git.retargeted_tags.lock().unwrap(),
+            vec![("v1".to_string(), "a".repeat(40))]
+        );
+        assert_eq!(*s.git.force_pushed_tags.lock().unwrap(), vec!["v1"]);
+        assert!(s.git.reverted.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rollback_is_idempotent() {
+        let (s, vcs) = released_strategy(ReleaseConfig::default(), vec![]);
+        s.rollback("v1.1.0", false, false).unwrap();
+        s.rollback("v1.1.0", false, false).unwrap();
+
+        assert_eq!(*vcs.deleted_releases.lock().unwrap(), vec!["v1.1.0"]);
+        assert_eq!(*s.git.deleted_tags.lock().unwrap(), vec!["v1.1.0"]);
+        assert_eq!(*s.git.deleted_remote_tags.lock().unwrap(), vec!["v1.1.0"]);
+    }
+
+    #[test]
+    fn rollback_dry_run_changes_nothing() {
+        let config = ReleaseConfig {
+            floating_tags: true,
+            ..Default::default()
+        };
+        let release_commit = Commit {
+            sha: "b".repeat(40),
+            author: String::new(),
+            message: "chore(release): v1.1.0 [skip ci]".into(),
+            paths: Vec::new(),
+        };
+        let (s, vcs) = released_strategy(config, vec![release_commit]);
+        s.rollback("v1.1.0", true, true).unwrap();
+
+        assert!(vcs.deleted_releases.lock().unwrap().is_empty());
+        assert!(s.git.deleted_tags.lock().unwrap().is_empty());
+        assert!(s.git.deleted_remote_tags.lock().unwrap().is_empty());
+        assert!(s.git.retargeted_tags.lock().unwrap().is_empty());
+        assert!(s.git.reverted.lock().unwrap().is_empty());
+        assert_eq!(*s.git.push_count.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn rollback_reverts_release_commit() {
+        let release_commit = Commit {
+            sha: "b".repeat(40),
+            author: String::new(),
+            message: "chore(release): v1.1.0 [skip ci]".into(),
+            paths: Vec::new(),
+        };
+        let (s, _) = released_strategy(ReleaseConfig::default(), vec![release_commit]);
+        s.rollback("v1.1.0", true, false).unwrap();
+
+        assert_eq!(*s.git.reverted.lock().unwrap(), vec!["b".repeat(40)]);
+        assert_eq!(*s.git.push_count.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn rollback_does_not_revert_non_release_commit() {
+        let commit = Commit {
+            sha: "b".repeat(40),
+            author: String::new(),
+            message: "feat: tagged by hand".into(),
+            paths: Vec::new(),
+        };
+        let (s, _) = released_strategy(ReleaseConfig::default(), vec![commit]);
+        s.rollback("v1.1.0", true, false).unwrap();
+
+        assert!(s.git.reverted.lock().unwrap().is_empty());
+        assert_eq!(*s.git.deleted_tags.lock().unwrap(), vec!["v1.1.0"]);
+    }
+
+    #[test]
+    fn rollback_leaves_floating_tag_when_newer_release_exists() {
+        let config = ReleaseConfig {
+            floating_tags: true,
+            ..Default::default()
+        };
+        let (s, _) = released_strategy(config, vec![]);
+        s.rollback("v1.0.0", false, false).unwrap();
+
+        assert!(s.git.retargeted_tags.lock().unwrap().is_empty());
+        assert_eq!(*s.git.deleted_tags.lock().unwrap(), vec!["v1.0.0"]);
+    }
+
+    #[test]
+    fn rollback_deletes_floating_tag_of_only_release() {
+        let tags = vec![tag("v2.0.0", Version::new(2, 0, 0), 'c')];
+        let config = ReleaseConfig {
+            floating_tags: true,
+            ..Default::default()
+        };
+        let (s, _) = make_strategy_with_vcs(tags, vec![], config);
+        for name in ["v2.0.0", "v2"] {
+            s.git.created_tags.lock().unwrap().push(name.into());
+            s.git.pushed_tags.lock().unwrap().push(name.into());
+        }
+        s.rollback("v2.0.0", false, false).unwrap();
+
+        assert_eq!(*s.git.deleted_tags.lock().unwrap(), vec!["v2.0.0", "v2"]);
+        assert_eq!(
+            *s.git.deleted_remote_tags.lock().unwrap(),
+            vec!["v2.0.0", "v2"]
+        );
+    }
+
+    #[test]
+    fn rollback_rejects_non_release_tag() {
+        let s = make_strategy(vec![], vec![], ReleaseConfig::default());
+        let err = s.rollback("latest", false, false).unwrap_err().to_string();
+        assert!(err.contains("not a release tag"), "{err}");
+    }
+
+    // --- no-push / tag-only tests ---
+
+    #[test]
+    fn execute_no_push_prepares_locally() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog_path = dir.path().join("CHANGELOG.md");
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(changelog_path.to_str().unwrap().to_string()),
+                ..Default::default()
+            },
+            floating_tags: true,
+            ..Default::default()
+        };
+        let (mut s, vcs) =
+            make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        s.options.no_push = true;
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert!(changelog_path.exists());
+        assert_eq!(s.git.committed.lock().unwrap().len(), 1);
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert_eq!(*s.git.force_created_tags.lock().unwrap(), vec!["v0"]);
+        assert!(s.git.pushed_tags.lock().unwrap().is_empty());
+        assert!(s.git.force_pushed_tags.lock().unwrap().is_empty());
+        assert_eq!(*s.git.push_count.lock().unwrap(), 0);
+        assert!(vcs.releases.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_annotates_the_tagged_commit_in_the_notes_ref() {
+        let config = ReleaseConfig {
+            git: crate::config::GitConfig {
+                release_notes_ref: Some("releases".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (s, _) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+
+        let dry_run = s.dry_run_report(&plan).unwrap().render_human();
+        assert!(
+            dry_run.contains(
+                "Would add a release note to refs/notes/releases on the tagged commit and push it"
+            ),
+            "{dry_run}"
+        );
+
+        s.execute(&plan, false).unwrap();
+        let notes = s.git.notes.lock().unwrap();
+        let (notes_ref, sha, content) = &notes[0];
+        assert_eq!(notes_ref, "refs/notes/releases");
+        assert_eq!(*sha, s.git.head);
+        assert!(content.starts_with("version: 0.1.0\ndate: "), "{content}");
+        assert!(
+            content.ends_with("url: https://github.com/test/release/v0.1.0\n"),
+            "{content}"
+        );
+        assert_eq!(
+            *s.git.pushed_notes.lock().unwrap(),
+            vec!["refs/notes/releases"]
+        );
+    }
+
+    #[test]
+    fn release_note_uses_the_calver_version() {
+        let config = ReleaseConfig {
+            versioning: crate::config::Versioning::Calver,
+            git: crate::config::GitConfig {
+                release_notes_ref: Some("releases".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (s, _) = make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        let mut plan = s.plan().unwrap();
+        plan.next_version = Version::new(2026, 1, 0);
+        plan.tag_name = "v2026.01.0".into();
+
+        s.execute(&plan, false).unwrap();
+        let notes = s.git.notes.lock().unwrap();
+        assert!(
+            notes[0].2.starts_with("version: 2026.01.0\ndate: "),
+            "{}",
+            notes[0].2
+        );
+    }
+
+    #[test]
+    fn execute_no_push_keeps_the_release_note_local() {
+        let config = ReleaseConfig {
+            git: crate::config::GitConfig {
+                release_notes_ref: Some("releases".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (mut s, _) =
+            make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        s.options.no_push = true;
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(s.git.notes.lock().unwrap().len(), 1);
+        assert!(s.git.pushed_notes.lock().unwrap().is_empty());
+        let commands = s.push_later_commands(&plan, &mut Vec::new());
+        assert!(commands.contains(&"git push origin refs/notes/releases".to_string()));
+    }
+
+    #[test]
+    fn execute_tag_only_skips_files_and_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog_path = dir.path().join("CHANGELOG.md");
+        let cargo_toml = dir.path().join("Cargo.toml");
+        let manifest = "[package]\nname = \"test\"\nversion = \"0.0.0\"\n";
+        std::fs::write(&cargo_toml, manifest).unwrap();
+        let config = ReleaseConfig {
+            changelog: crate::config::ChangelogConfig {
+                file: Some(changelog_path.to_str().unwrap().to_string()),
+                ..Default::default()
+            },
+            version_files: vec![cargo_toml.to_str().unwrap().to_string()],
+            ..Default::default()
+        };
+        let (mut s, vcs) =
+            make_strategy_with_vcs(vec![], vec![raw_commit("feat: something")], config);
+        s.options.tag_only = true;
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert!(!changelog_path.exists());
+        assert_eq!(std::fs::read_to_string(&cargo_toml).unwrap(), manifest);
+        assert!(s.git.committed.lock().unwrap().is_empty());
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert_eq!(*s.git.pushed_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert_eq!(vcs.releases.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn execute_dry_run_with_no_push_and_tag_only() {
+        let mut s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        s.options = ExecuteOptions {
+            no_push: true,
+            tag_only: true,
+        };
+        let plan = s.plan().unwrap();
+        s.execute(&plan, true).unwrap();
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+    }
+
+    // --- concurrency guard tests ---
+
+    fn locking_config() -> ReleaseConfig {
+        ReleaseConfig {
+            lock: crate::config::LockConfig {
+                enabled: true,
+                stale_after: 600,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn execute_refuses_tag_pushed_since_plan() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+        s.git.pushed_tags.lock().unwrap().push("v0.1.0".into());
+
+        let err = s.execute(&plan, false).unwrap_err();
+        assert!(matches!(err, ReleaseError::Conflict(_)), "{err}");
+        assert!(
+            err.to_string().contains("v0.1.0 appeared on origin"),
+            "{err}"
+        );
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_takes_and_releases_lock() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            locking_config(),
+        );
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert!(s.git.remote_refs.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_releases_lock_on_failure() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            locking_config(),
+        );
+        let plan = s.plan().unwrap();
+        s.git.pushed_tags.lock().unwrap().push("v0.1.0".into());
+
+        assert!(s.execute(&plan, false).is_err());
+        assert!(s.git.remote_refs.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_fails_fast_when_lock_held() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            locking_config(),
+        );
+        let held = format!("sr-lock: v0.1.0\n\nexpires: {}", unix_now() + 300);
+        s.git.create_remote_ref(LOCK_REF, &held).unwrap().unwrap();
+        let plan = s.plan().unwrap();
+
+        let err = s.execute(&plan, false).unwrap_err().to_string();
+        assert!(err.contains("another release is in progress"), "{err}");
+        assert!(s.git.created_tags.lock().unwrap().is_empty());
+        // The other run's lock is untouched
+        assert_eq!(s.git.remote_refs.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn execute_takes_over_stale_lock() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: something")],
+            locking_config(),
+        );
+        s.git
+            .create_remote_ref(LOCK_REF, "sr-lock: v0.0.9\n\nexpires: 1")
+            .unwrap()
+            .unwrap();
+        let plan = s.plan().unwrap();
+
+        s.execute(&plan, false).unwrap();
+        assert_eq!(*s.git.created_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert!(s.git.remote_refs.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn lock_expiry_parses_message() {
+        assert_eq!(lock_expiry("sr-lock: v1.0.0\n\nexpires: 42"), Some(42));
+        assert_eq!(lock_expiry("garbage"), None);
+    }
+
     // --- pre/post release hook tests ---
 
     #[test]
@@ -1741,7 +7089,7 @@ mod tests {
         let marker = dir.path().join("pre_release_ran");
 
         let config = ReleaseConfig {
-            pre_release_command: Some(format!("touch {}", marker.to_str().unwrap())),
+            pre_release_command: Some(format!("touch {}", marker.to_str().unwrap()).into()),
             ..Default::default()
         };
 
@@ -1758,7 +7106,7 @@ mod tests {
         let marker = dir.path().join("post_release_ran");
 
         let config = ReleaseConfig {
-            post_release_command: Some(format!("touch {}", marker.to_str().unwrap())),
+            post_release_command: Some(format!("touch {}", marker.to_str().unwrap()).into()),
             ..Default::default()
         };
 
@@ -1792,10 +7140,13 @@ mod tests {
         let output_file = dir.path().join("hook_output");
 
         let config = ReleaseConfig {
-            post_release_command: Some(format!(
-                "echo $SR_VERSION $SR_TAG > {}",
-                output_file.to_str().unwrap()
-            )),
+            post_release_command: Some(
+                format!(
+                    "echo $SR_VERSION $SR_TAG > {}",
+                    output_file.to_str().unwrap()
+                )
+                .into(),
+            ),
             ..Default::default()
         };
 
@@ -1815,8 +7166,8 @@ mod tests {
         let post_marker = dir.path().join("post_hook");
 
         let config = ReleaseConfig {
-            pre_release_command: Some(format!("touch {}", pre_marker.to_str().unwrap())),
-            post_release_command: Some(format!("touch {}", post_marker.to_str().unwrap())),
+            pre_release_command: Some(format!("touch {}", pre_marker.to_str().unwrap()).into()),
+            post_release_command: Some(format!("touch {}", post_marker.to_str().unwrap()).into()),
             ..Default::default()
         };
 
@@ -1834,6 +7185,604 @@ mod tests {
         );
     }
 
+    #[test]
+    fn execute_runs_publish_steps_after_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("published");
+
+        let config = ReleaseConfig {
+            publish: vec![crate::config::PublishStep::Command {
+                run: format!("echo $SR_TAG > {}", marker.to_str().unwrap()),
+            }],
+            ..Default::default()
+        };
+
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, false).unwrap();
+
+        let contents = std::fs::read_to_string(&marker).unwrap();
+        assert_eq!(contents.trim(), "v0.1.0");
+    }
+
+    #[test]
+    fn execute_publish_failure_keeps_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let post_marker = dir.path().join("post_hook");
+
+        let config = ReleaseConfig {
+            publish: vec![crate::config::PublishStep::Command {
+                run: "exit 1".into(),
+            }],
+            post_release_command: Some(format!("touch {}", post_marker.to_str().unwrap()).into()),
+            ..Default::default()
+        };
+
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        let err = s.execute(&plan, false).unwrap_err();
+
+        assert!(matches!(err, ReleaseError::Publish(_)), "{err}");
+        assert!(err.to_string().contains("v0.1.0 was pushed and is kept"));
+        assert_eq!(*s.git.pushed_tags.lock().unwrap(), vec!["v0.1.0"]);
+        assert!(s.git.deleted_remote_tags.lock().unwrap().is_empty());
+        assert!(!post_marker.exists());
+    }
+
+    #[test]
+    fn execute_dry_run_skips_publish_steps() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("published");
+
+        let config = ReleaseConfig {
+            publish: vec![crate::config::PublishStep::Command {
+                run: format!("touch {}", marker.to_str().unwrap()),
+            }],
+            ..Default::default()
+        };
+
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+        s.execute(&plan, true).unwrap();
+
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn notify_renders_release_and_failure_events() {
+        let config = ReleaseConfig {
+            notifications: vec![crate::config::NotificationConfig {
+                kind: crate::config::NotificationKind::Slack,
+                url: Some("https://hooks.example.com/secret".into()),
+                body_template: Some("{tag} released".into()),
+                failure_template: Some("{tag} failed: {error}".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let s = make_strategy(vec![], vec![raw_commit("feat: something")], config);
+        let plan = s.plan().unwrap();
+
+        let sent = Mutex::new(Vec::new());
+        let send = |_: &str, _: &str, body: &str| {
+            sent.lock().unwrap().push(body.to_string());
+            Ok(())
+        };
+        s.notify(&plan, None, &send);
+        s.notify(
+            &plan,
+            Some(&ReleaseError::Git("push rejected".into())),
+            &send,
+        );
+
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                r#"{"text":"v0.1.0 released"}"#,
+                r#"{"text":"v0.1.0 failed: git error: push rejected"}"#,
+            ]
+        );
+    }
+
+    // --- notes tests ---
+
+    fn two_tags() -> Vec<TagInfo> {
+        vec![
+            TagInfo {
+                name: "v1.0.0".into(),
+                version: Version::new(1, 0, 0),
+                sha: "a".repeat(40),
+            },
+            TagInfo {
+                name: "v1.1.0".into(),
+                version: Version::new(1, 1, 0),
+                sha: "b".repeat(40),
+            },
+        ]
+    }
+
+    #[test]
+    fn notes_default_to_latest_tag_since_previous() {
+        let s = make_strategy(
+            two_tags(),
+            vec![
+                raw_commit("feat: add widgets"),
+                raw_commit("chore(release): v1.0.0 [skip ci]"),
+            ],
+            ReleaseConfig::default(),
+        );
+        let (tag, notes) = s.notes(None, Some("https://github.com/test/repo")).unwrap();
+
+        assert_eq!(tag.name, "v1.1.0");
+        assert_eq!(
+            *s.git.seen_ranges.lock().unwrap(),
+            vec![(Some("a".repeat(40)), "v1.1.0".to_string())]
+        );
+        assert!(notes.contains("1.1.0"));
+        assert!(notes.contains("2026-01-01"), "uses the tag date: {notes}");
+        assert!(notes.contains("add widgets"));
+        assert!(!notes.contains("chore(release)"));
+        assert!(notes.contains("https://github.com/test/repo/compare/v1.0.0...v1.1.0"));
+    }
+
+    #[test]
+    fn notes_for_first_tag_start_at_root() {
+        let s = make_strategy(
+            two_tags(),
+            vec![raw_commit("feat: initial")],
+            ReleaseConfig::default(),
+        );
+        let (tag, notes) = s
+            .notes(Some("v1.0.0"), Some("https://github.com/test/repo"))
+            .unwrap();
+
+        assert_eq!(tag.name, "v1.0.0");
+        assert_eq!(
+            *s.git.seen_ranges.lock().unwrap(),
+            vec![(None, "v1.0.0".to_string())]
+        );
+        assert!(notes.contains("initial"));
+        assert!(
+            !notes.contains("/compare/"),
+            "no previous tag to compare: {notes}"
+        );
+    }
+
+    #[test]
+    fn tag_entries_skip_unreadable_tags() {
+        let mut tags = two_tags();
+        tags.push(TagInfo {
+            name: "v1.2.0".into(),
+            version: Version::new(1, 2, 0),
+            sha: "c".repeat(40),
+        });
+        let mut s = make_strategy(
+            tags.clone(),
+            vec![raw_commit("feat: add widgets")],
+            ReleaseConfig::default(),
+        );
+        s.git.unreadable_tags = vec!["v1.1.0".into()];
+
+        let mut visited = Vec::new();
+        s.visit_tag_entries(
+            &tags,
+            Some("https://github.com/test/repo"),
+            |_| true,
+            |i, entry| {
+                visited.push((i, entry.compare_url));
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            visited,
+            vec![
+                (0, None),
+                (
+                    2,
+                    Some("https://github.com/test/repo/compare/v1.0.0...v1.2.0".into())
+                ),
+            ]
+        );
+        assert_eq!(
+            s.git.seen_ranges.lock().unwrap().last(),
+            Some(&(Some("a".repeat(40)), "v1.2.0".to_string())),
+            "v1.2.0 covers the commits since v1.0.0"
+        );
+
+        s.git.seen_ranges.lock().unwrap().clear();
+        let mut visited = Vec::new();
+        s.visit_tag_entries(
+            &tags,
+            None,
+            |i| i == 2,
+            |i, _| {
+                visited.push(i);
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(visited, vec![2]);
+        assert_eq!(
+            *s.git.seen_ranges.lock().unwrap(),
+            vec![(Some("b".repeat(40)), "v1.2.0".to_string())],
+            "unselected tags are not read"
+        );
+    }
+
+    #[test]
+    fn tag_entries_fail_on_errors_other_than_unreadable_history() {
+        let tags = two_tags();
+        let mut config = ReleaseConfig::default();
+        config.changelog.timezone = Some("Nowhere/Invalid".into());
+        let s = make_strategy(tags.clone(), vec![raw_commit("feat: add widgets")], config);
+
+        let mut visited = 0;
+        let err = s
+            .visit_tag_entries(
+                &tags,
+                None,
+                |_| true,
+                |_, _| {
+                    visited += 1;
+                    Ok(())
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, ReleaseError::Config(_)), "{err}");
+        assert_eq!(visited, 0);
+    }
+
+    #[test]
+    fn notes_unknown_or_missing_tags_error() {
+        let s = make_strategy(two_tags(), vec![], ReleaseConfig::default());
+        let err = s.notes(Some("v9.9.9"), None).unwrap_err();
+        assert!(err.to_string().contains("tag v9.9.9 not found"), "{err}");
+
+        let s = make_strategy(vec![], vec![], ReleaseConfig::default());
+        let err = s.notes(None, None).unwrap_err();
+        assert!(
+            err.to_string().contains("no tags found with prefix 'v'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn push_notes_creates_then_updates_release() {
+        let (s, vcs) = make_strategy_with_vcs(
+            two_tags(),
+            vec![raw_commit("fix: bug")],
+            ReleaseConfig::default(),
+        );
+        let (tag, notes) = s.notes(Some("v1.0.0"), None).unwrap();
+
+        s.push_notes(&tag, &notes).unwrap();
+        s.push_notes(&tag, "edited").unwrap();
+
+        let releases = vcs.releases.lock().unwrap();
+        assert_eq!(
+            *releases,
+            vec![("v1.0.0".to_string(), "edited".to_string())]
+        );
+        assert_eq!(vcs.deleted_releases.lock().unwrap().len(), 1);
+        assert_eq!(
+            vcs.release_meta.lock().unwrap()[0],
+            ("v1.0.0".into(), false)
+        );
+    }
+
+    fn commit_by(msg: &str, author: &str) -> Commit {
+        Commit {
+            author: author.into(),
+            ..raw_commit(msg)
+        }
+    }
+
+    #[test]
+    fn compare_with_previous_analyzes_the_range_between_the_last_two_tags() {
+        let (mut s, _) = make_strategy_with_vcs(
+            two_tags(),
+            vec![
+                commit_by("feat!: drop v1", "ann@example.com"),
+                commit_by("feat: add x", "bob@example.com"),
+                commit_by("fix: y", "Ann@example.com"),
+                commit_by("chore(release): v1.1.0 [skip ci]", "bot@example.com"),
+            ],
+            ReleaseConfig::default(),
+        );
+        s.git.commits_by_tip = vec![(
+            "v1.1.0".into(),
+            vec![
+                commit_by("fix: a", "ann@example.com"),
+                commit_by("not conventional", "cat@example.com"),
+            ],
+        )];
+        let plan = s.plan().unwrap();
+        let planned_ranges = s.git.seen_ranges.lock().unwrap().len();
+
+        let comparison = s.compare_with_previous(&plan).unwrap();
+
+        assert_eq!(
+            comparison.stats,
+            ReleaseStats {
+                commits: 3,
+                contributors: 2,
+                breaking: 1,
+                sections: vec![
+                    SectionStats {
+                        section: "Breaking Changes".into(),
+                        commits: 1
+                    },
+                    SectionStats {
+                        section: "Features".into(),
+                        commits: 1
+                    },
+                    SectionStats {
+                        section: "Bug Fixes".into(),
+                        commits: 1
+                    },
+                ],
+            }
+        );
+        let previous = comparison.previous_release.unwrap();
+        assert_eq!(previous.tag, "v1.1.0");
+        assert_eq!(
+            previous.stats,
+            ReleaseStats {
+                commits: 1,
+                contributors: 1,
+                breaking: 0,
+                sections: vec![SectionStats {
+                    section: "Bug Fixes".into(),
+                    commits: 1
+                }],
+            }
+        );
+        assert_eq!(previous.stats.section("Features"), 0);
+        // The planned range is not read again; only the previous release's is
+        let ranges = s.git.seen_ranges.lock().unwrap();
+        assert_eq!(
+            ranges[planned_ranges..],
+            [(Some("a".repeat(40)), "v1.1.0".into())]
+        );
+    }
+
+    #[test]
+    fn compare_with_previous_before_any_release() {
+        let s = make_strategy(
+            vec![],
+            vec![raw_commit("feat: first")],
+            ReleaseConfig::default(),
+        );
+        let plan = s.plan().unwrap();
+
+        let comparison = s.compare_with_previous(&plan).unwrap();
+
+        assert_eq!(comparison.stats.commits, 1);
+        assert_eq!(comparison.stats.contributors, 1);
+        assert_eq!(comparison.previous_release, None);
+        let json = serde_json::to_value(&comparison).unwrap();
+        assert!(json["previous_release"].is_null(), "{json}");
+    }
+
+    #[test]
+    fn previous_release_json_flattens_its_stats() {
+        let previous = PreviousRelease {
+            tag: "v1.0.0".into(),
+            stats: ReleaseStats {
+                commits: 2,
+                contributors: 1,
+                breaking: 0,
+                sections: vec![SectionStats {
+                    section: "Features".into(),
+                    commits: 2,
+                }],
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&previous).unwrap(),
+            serde_json::json!({
+                "tag": "v1.0.0",
+                "commits": 2,
+                "contributors": 1,
+                "breaking": 0,
+                "sections": [{"section": "Features", "commits": 2}],
+            })
+        );
+    }
+
+    fn upload_file(dir: &tempfile::TempDir, name: &str) -> String {
+        let path = dir.path().join(name);
+        std::fs::write(&path, name).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn upload_attaches_files_to_the_existing_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = upload_file(&dir, "app.tar.gz");
+        let (mut s, _) = make_strategy_with_vcs(two_tags(), vec![], ReleaseConfig::default());
+        let vcs = FakeVcs {
+            drafts: true,
+            ..FakeVcs::new()
+        };
+        vcs.releases
+            .lock()
+            .unwrap()
+            .push(("v1.1.0".into(), "notes".into()));
+        s.vcs = vec![Box::new(vcs.clone())];
+
+        let uploaded = s
+            .upload(None, std::slice::from_ref(&file), UploadOptions::default())
+            .unwrap();
+
+        assert_eq!(
+            uploaded,
+            vec![UploadedAssets {
+                provider: "https://github.com/test/repo".into(),
+                urls: vec![
+                    "https://github.com/test/repo/releases/download/v1.1.0/app.tar.gz".into()
+                ],
+            }]
+        );
+        assert_eq!(
+            *vcs.uploaded_assets.lock().unwrap(),
+            vec![("v1.1.0".to_string(), vec![file])]
+        );
+        assert!(
+            vcs.release_meta.lock().unwrap().is_empty(),
+            "nothing created"
+        );
+    }
+
+    #[test]
+    fn upload_needs_a_release_unless_asked_to_create_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = upload_file(&dir, "app.tar.gz");
+        let (mut s, vcs) = make_strategy_with_vcs(two_tags(), vec![], ReleaseConfig::default());
+        s.git.tag_messages = vec![("v1.0.0".into(), "v1.0.0\n\n### Bug Fixes\n".into())];
+
+        let err = s
+            .upload(
+                Some("v1.0.0"),
+                std::slice::from_ref(&file),
+                UploadOptions::default(),
+            )
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--create-missing"), "{err}");
+        assert!(vcs.uploaded_assets.lock().unwrap().is_empty());
+
+        let options = UploadOptions {
+            create_missing: true,
+            ..Default::default()
+        };
+        let uploaded = s.upload(Some("v1.0.0"), &[file], options).unwrap();
+        assert_eq!(uploaded[0].urls, vec!["app.tar.gz".to_string()]);
+        assert_eq!(
+            *vcs.releases.lock().unwrap(),
+            vec![("v1.0.0".to_string(), "### Bug Fixes".to_string())]
+        );
+        assert_eq!(
+            vcs.release_meta.lock().unwrap()[0],
+            ("v1.0.0".into(), false)
+        );
+    }
+
+    #[test]
+    fn upload_creates_a_lightweight_tags_release_from_its_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = upload_file(&dir, "app.tar.gz");
+        let (s, vcs) = make_strategy_with_vcs(
+            two_tags(),
+            vec![raw_commit("fix: bug")],
+            ReleaseConfig::default(),
+        );
+        let options = UploadOptions {
+            create_missing: true,
+            ..Default::default()
+        };
+
+        s.upload(Some("v1.1.0"), &[file], options).unwrap();
+
+        let (_, notes) = s
+            .notes(Some("v1.1.0"), Some("https://github.com/test/repo"))
+            .unwrap();
+        assert_eq!(
+            *vcs.releases.lock().unwrap(),
+            vec![("v1.1.0".to_string(), notes)]
+        );
+    }
+
+    #[test]
+    fn upload_checksums_are_uploaded_then_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = upload_file(&dir, "app.tar.gz");
+        let (s, vcs) = make_strategy_with_vcs(two_tags(), vec![], ReleaseConfig::default());
+        vcs.releases
+            .lock()
+            .unwrap()
+            .push(("v1.1.0".into(), "notes".into()));
+        let options = UploadOptions {
+            checksums: true,
+            ..Default::default()
+        };
+
+        let uploaded = s
+            .upload(None, std::slice::from_ref(&file), options)
+            .unwrap();
+
+        let checksum = format!("{file}.sha256");
+        assert_eq!(
+            vcs.uploaded_assets.lock().unwrap()[0].1,
+            vec![file, checksum.clone()]
+        );
+        assert_eq!(
+            uploaded[0].urls,
+            vec!["app.tar.gz".to_string(), "app.tar.gz.sha256".to_string()]
+        );
+        assert!(!Path::new(&checksum).exists());
+    }
+
+    #[test]
+    fn upload_dry_run_and_unmatched_globs_upload_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = upload_file(&dir, "app.tar.gz");
+        let (s, vcs) = make_strategy_with_vcs(two_tags(), vec![], ReleaseConfig::default());
+        let options = UploadOptions {
+            dry_run: true,
+            checksums: true,
+            create_missing: true,
+        };
+
+        assert!(
+            s.upload(None, std::slice::from_ref(&file), options)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(!Path::new(&format!("{file}.sha256")).exists());
+
+        let missing = format!("{}/*.zip", dir.path().display());
+        let err = s
+            .upload(None, &[missing], UploadOptions::default())
+            .unwrap_err();
+        assert!(matches!(err, ReleaseError::Artifacts(_)), "{err}");
+        let err = s
+            .upload(Some("v9.9.9"), &[file], UploadOptions::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("tag v9.9.9 not found"), "{err}");
+
+        assert!(vcs.calls.lock().unwrap().is_empty());
+        assert!(vcs.releases.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn upload_dry_run_only_queries_providers() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = upload_file(&dir, "app.tar.gz");
+        let (mut s, vcs) = make_strategy_with_vcs(two_tags(), vec![], ReleaseConfig::default());
+        let options = UploadOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        s.upload(None, std::slice::from_ref(&file), options)
+            .unwrap();
+        assert_eq!(*vcs.queried.lock().unwrap(), ["v1.1.0"]);
+        assert!(vcs.calls.lock().unwrap().is_empty());
+        assert!(vcs.uploaded_assets.lock().unwrap().is_empty());
+
+        // Without a token a dry-run has no provider, and still lists the files
+        s.vcs.clear();
+        s.upload(None, std::slice::from_ref(&file), options)
+            .unwrap();
+        let err = s
+            .upload(None, &[file], UploadOptions::default())
+            .unwrap_err();
+        assert!(err.to_string().contains("no VCS provider"), "{err}");
+    }
+
     // --- pre-release tests ---
 
     #[test]
@@ -2000,6 +7949,24 @@ mod tests {
         assert_eq!(plan.commits[0].description, "patch only in core");
     }
 
+    #[test]
+    fn plan_passes_include_and_exclude_pathspecs() {
+        let config = ReleaseConfig {
+            include_paths: vec!["src".into()],
+            exclude_paths: vec!["docs".into()],
+            ..Default::default()
+        };
+        let mut s = make_strategy(vec![], vec![raw_commit("feat: docs only")], config);
+        // Every commit touched only excluded paths
+        s.git.path_commits = Some(vec![]);
+        let err = s.plan().unwrap_err();
+        assert!(matches!(err, ReleaseError::NoCommits { .. }));
+        assert_eq!(
+            *s.git.seen_pathspecs.lock().unwrap(),
+            vec!["src", ":(exclude)docs"]
+        );
+    }
+
     #[test]
     fn plan_without_path_filter_uses_all_commits() {
         let config = ReleaseConfig::default();
@@ -0,0 +1,163 @@
+//! Record of what a real `sr release` did, for audit trails.
+//!
+//! `execute` fills a [`ReleaseReport`] step by step; with `summary.file` set it
+//! is written as JSON (and optionally uploaded with the release) once the
+//! release has succeeded. Bumped files use the same [`VersionFileChange`] shape
+//! as the dry-run report, so a dry run and the release it previews line up.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::dry_run::VersionFileChange;
+use crate::release::ReleasePlan;
+
+/// Inputs, decisions, and outputs of one release.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseReport {
+    /// Version of sr that made the release.
+    pub sr_version: String,
+    pub plan: ReleasePlan,
+    /// The commit the release tag points at (after the release commit, if any).
+    pub head_sha: Option<String>,
+    /// RFC 3339 timestamps.
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    /// Release notes, as written to the changelog and the remote release.
+    pub changelog: String,
+    pub version_files: Vec<VersionFileChange>,
+    pub assets: Vec<UploadedAsset>,
+    pub releases: Vec<ProviderRelease>,
+    /// Each step that ran, in order.
+    pub steps: Vec<StepRecord>,
+}
+
+/// A file uploaded to the remote release.
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadedAsset {
+    pub name: String,
+    pub sha256: String,
+}
+
+/// The release created or updated on one provider.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderRelease {
+    pub provider: String,
+    pub url: String,
+}
+
+/// When a release step started and finished.
+#[derive(Debug, Clone, Serialize)]
+pub struct StepRecord {
+    pub name: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+impl ReleaseReport {
+    pub fn new(plan: &ReleasePlan) -> Self {
+        Self {
+            sr_version: env!("CARGO_PKG_VERSION").into(),
+            plan: plan.clone(),
+            head_sha: None,
+            started_at: now(),
+            finished_at: None,
+            changelog: String::new(),
+            version_files: Vec::new(),
+            assets: Vec::new(),
+            releases: Vec::new(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Start step `name`, finishing the one before it.
+    pub fn step(&mut self, name: &str) {
+        self.finish_step();
+        self.steps.push(StepRecord {
+            name: name.into(),
+            started_at: now(),
+            finished_at: None,
+        });
+    }
+
+    /// Finish the last step and the release as a whole.
+    pub fn finish(&mut self) {
+        self.finish_step();
+        self.finished_at = Some(now());
+    }
+
+    /// Record `path` as uploaded, with its digest.
+    pub fn add_asset(&mut self, path: &str) -> std::io::Result<()> {
+        let name = Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.into());
+        let sha256 = crate::attestations::sha256_file(Path::new(path))?;
+        self.assets.push(UploadedAsset { name, sha256 });
+        Ok(())
+    }
+
+    fn finish_step(&mut self) {
+        if let Some(last) = self.steps.last_mut()
+            && last.finished_at.is_none()
+        {
+            last.finished_at = Some(now());
+        }
+    }
+}
+
+fn now() -> String {
+    jiff::Timestamp::now().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::BumpLevel;
+
+    fn plan() -> ReleasePlan {
+        ReleasePlan {
+            schema_version: crate::release::PLAN_SCHEMA_VERSION,
+            current_version: None,
+            next_version: semver::Version::new(0, 1, 0),
+            bump: BumpLevel::Minor,
+            commits: vec![],
+            tag_name: "v0.1.0".into(),
+            floating_tag_name: None,
+            prerelease: false,
+            head_sha: "abc".into(),
+            forced: false,
+            touched_paths: Vec::new(),
+            contributors: 0,
+        }
+    }
+
+    #[test]
+    fn steps_finish_when_the_next_one_starts() {
+        let mut report = ReleaseReport::new(&plan());
+        report.step("create tag");
+        report.step("push tag");
+        assert!(report.steps[0].finished_at.is_some());
+        assert!(report.steps[1].finished_at.is_none());
+
+        report.finish();
+        assert!(report.steps[1].finished_at.is_some());
+        assert!(report.finished_at.is_some());
+        assert_eq!(report.sr_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn add_asset_records_file_name_and_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.tar.gz");
+        std::fs::write(&file, "hello").unwrap();
+
+        let mut report = ReleaseReport::new(&plan());
+        report.add_asset(file.to_str().unwrap()).unwrap();
+        assert_eq!(report.assets[0].name, "app.tar.gz");
+        assert_eq!(
+            report.assets[0].sha256,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+}
@@ -31,7 +31,7 @@ pub fn determine_bump(
 ) -> Option<BumpLevel> {
     commits
         .iter()
-        .filter_map(|c| classifier.bump_level(&c.r#type, c.breaking))
+        .filter_map(|c| classifier.commit_bump(c))
         .max()
 }
 
@@ -77,6 +77,183 @@ pub fn apply_prerelease_bump(
     result
 }
 
+/// Parse the version part of a tag. Besides semver, zero-padded CalVer
+/// components such as `2025.03.2` are accepted and read as `2025.3.2`.
+pub fn parse_tag_version(s: &str) -> Option<Version> {
+    if let Ok(version) = Version::parse(s) {
+        return Some(version);
+    }
+    let (core, rest) = match s.find(['-', '+']) {
+        Some(i) => s.split_at(i),
+        None => (s, ""),
+    };
+    let parts: Vec<&str> = core.split('.').collect();
+    let numeric = |p: &&str| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit());
+    if parts.len() != 3 || !parts.iter().all(numeric) {
+        return None;
+    }
+    let numbers: Vec<String> = parts
+        .iter()
+        .map(|p| match p.trim_start_matches('0') {
+            "" => "0".to_string(),
+            digits => digits.to_string(),
+        })
+        .collect();
+    Version::parse(&format!("{}{rest}", numbers.join("."))).ok()
+}
+
+/// [`parse_tag_version`], also reading a two-component version such as `1.2`
+/// as `1.2.0` (`lenient_tags`).
+pub fn parse_tag_version_lenient(s: &str) -> Option<Version> {
+    if let Some(version) = parse_tag_version(s) {
+        return Some(version);
+    }
+    let (core, rest) = match s.find(['-', '+']) {
+        Some(i) => s.split_at(i),
+        None => (s, ""),
+    };
+    if core.split('.').count() != 2 {
+        return None;
+    }
+    parse_tag_version(&format!("{core}.0{rest}"))
+}
+
+/// One date component of a CalVer format (see <https://calver.org>).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CalverToken {
+    /// `YYYY`: full year.
+    Year,
+    /// `YY`: year minus 2000.
+    ShortYear,
+    /// `0Y`: year minus 2000, zero-padded.
+    PaddedYear,
+    /// `MM`: month.
+    Month,
+    /// `0M`: zero-padded month.
+    PaddedMonth,
+    /// `WW`: ISO week.
+    Week,
+    /// `0W`: zero-padded ISO week.
+    PaddedWeek,
+}
+
+impl CalverToken {
+    fn parse(token: &str) -> Option<Self> {
+        Some(match token {
+            "YYYY" => Self::Year,
+            "YY" => Self::ShortYear,
+            "0Y" => Self::PaddedYear,
+            "MM" => Self::Month,
+            "0M" => Self::PaddedMonth,
+            "WW" => Self::Week,
+            "0W" => Self::PaddedWeek,
+            _ => return None,
+        })
+    }
+
+    fn is_year(self) -> bool {
+        matches!(self, Self::Year | Self::ShortYear | Self::PaddedYear)
+    }
+
+    /// The token's value on `date`. With `iso_year`, years are ISO week-based,
+    /// so 2024-12-30 (2025-W01) is in 2025 and versions never go backwards.
+    fn value(self, date: jiff::civil::Date, iso_year: bool) -> u64 {
+        let year = if iso_year {
+            date.iso_week_date().year()
+        } else {
+            date.year()
+        };
+        let value = match self {
+            Self::Year => i64::from(year),
+            Self::ShortYear | Self::PaddedYear => i64::from(year) - 2000,
+            Self::Month | Self::PaddedMonth => i64::from(date.month()),
+            Self::Week | Self::PaddedWeek => i64::from(date.iso_week_date().week()),
+        };
+        value.max(0) as u64
+    }
+
+    fn format(self, value: u64) -> String {
+        match self {
+            Self::PaddedYear | Self::PaddedMonth | Self::PaddedWeek => {
+                format!("{value:02}")
+            }
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// A CalVer format of a year token, a month or week token, and `MICRO`,
+/// e.g. `YYYY.0M.MICRO`.
+///
+/// Versions are kept as semver (`2025.3.2`); [`CalverFormat::format`] renders
+/// the padded form (`2025.03.2`) used in tags, changelogs, and version files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalverFormat {
+    period: [CalverToken; 2],
+}
+
+impl CalverFormat {
+    pub fn parse(format: &str) -> Result<Self, String> {
+        let tokens: Vec<&str> = format.split('.').collect();
+        let [first, second, "MICRO"] = tokens.as_slice() else {
+            return Err(format!(
+                "'{format}' must be two date tokens followed by MICRO, e.g. YYYY.0M.MICRO"
+            ));
+        };
+        let token = |t: &str| {
+            CalverToken::parse(t).ok_or_else(|| match t {
+                "DD" | "0D" => format!(
+                    "'{format}' cannot use day token '{t}': after a year it repeats every month"
+                ),
+                _ => {
+                    format!("unknown token '{t}' in '{format}' (use YYYY, YY, 0Y, MM, 0M, WW, 0W)")
+                }
+            })
+        };
+        let period = [token(first)?, token(second)?];
+        if !period[0].is_year() || period[1].is_year() {
+            return Err(format!(
+                "'{format}' must be a year token (YYYY, YY, 0Y) then a month (MM, 0M) or \
+                 week (WW, 0W) token, so versions only grow"
+            ));
+        }
+        Ok(Self { period })
+    }
+
+    /// Whether the period is an ISO week, whose year is the ISO week-based year.
+    fn is_weekly(&self) -> bool {
+        matches!(self.period[1], CalverToken::Week | CalverToken::PaddedWeek)
+    }
+
+    /// The version released on `date`: MICRO is one past the highest of
+    /// `existing` in the same period, or 0 for the period's first release.
+    pub fn next_version(&self, date: jiff::civil::Date, existing: &[Version]) -> Version {
+        let [first, second] = self.period.map(|t| t.value(date, self.is_weekly()));
+        let micro = existing
+            .iter()
+            .filter(|v| v.major == first && v.minor == second && v.pre.is_empty())
+            .map(|v| v.patch + 1)
+            .max()
+            .unwrap_or(0);
+        Version::new(first, second, micro)
+    }
+
+    /// `version` in this format, e.g. `2025.03.2` for `2025.3.2` with `YYYY.0M.MICRO`.
+    pub fn format(&self, version: &Version) -> String {
+        let [first, second] = self.period;
+        let mut out = format!(
+            "{}.{}.{}",
+            first.format(version.major),
+            second.format(version.minor),
+            version.patch
+        );
+        if !version.pre.is_empty() {
+            out.push_str(&format!("-{}", version.pre));
+        }
+        out
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,6 +292,34 @@ mod tests {
         assert_eq!(apply_bump(&v, BumpLevel::Major), Version::new(2, 0, 0));
     }
 
+    #[test]
+    fn rules_raise_the_bump_of_matching_commits() {
+        use crate::commit::{CommitRule, RuleCondition};
+
+        let rules = vec![CommitRule {
+            when: RuleCondition {
+                commit_type: Some("fix".into()),
+                scope: Some("security".into()),
+                ..Default::default()
+            },
+            section: None,
+            bump: Some(BumpLevel::Minor),
+        }];
+        let mut fix = commit("fix", false);
+        assert_eq!(
+            determine_bump(
+                std::slice::from_ref(&fix),
+                &classifier().with_rules(rules.clone())
+            ),
+            Some(BumpLevel::Patch)
+        );
+        fix.scope = Some("security".into());
+        assert_eq!(
+            determine_bump(&[fix], &classifier().with_rules(rules)),
+            Some(BumpLevel::Minor)
+        );
+    }
+
     #[test]
     fn no_commits_returns_none() {
         assert_eq!(determine_bump(&[], &classifier()), None);
@@ -184,6 +389,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_tag_version_accepts_padded_calver() {
+        assert_eq!(parse_tag_version("1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(
+            parse_tag_version("2025.03.2"),
+            Some(Version::new(2025, 3, 2))
+        );
+        assert_eq!(parse_tag_version("25.01.00"), Some(Version::new(25, 1, 0)));
+        assert_eq!(
+            parse_tag_version("2025.03.0-rc.1").unwrap().to_string(),
+            "2025.3.0-rc.1"
+        );
+        assert_eq!(parse_tag_version("2025.03"), None);
+        assert_eq!(parse_tag_version("not-a-version"), None);
+    }
+
+    #[test]
+    fn lenient_tag_versions_fill_in_the_patch() {
+        assert_eq!(
+            parse_tag_version_lenient("1.2"),
+            Some(Version::new(1, 2, 0))
+        );
+        assert_eq!(
+            parse_tag_version_lenient("1.2-rc.1").unwrap().to_string(),
+            "1.2.0-rc.1"
+        );
+        assert_eq!(
+            parse_tag_version_lenient("1.2.3"),
+            Some(Version::new(1, 2, 3))
+        );
+        assert_eq!(parse_tag_version_lenient("1"), None);
+        assert_eq!(parse_tag_version_lenient("1.x"), None);
+        assert_eq!(parse_tag_version_lenient("build"), None);
+    }
+
+    #[test]
+    fn calver_next_version_increments_micro_within_a_period() {
+        let format = CalverFormat::parse("YYYY.0M.MICRO").unwrap();
+        let march = jiff::civil::date(2025, 3, 14);
+        assert_eq!(format.next_version(march, &[]), Version::new(2025, 3, 0));
+
+        let existing = [
+            Version::new(2025, 2, 7),
+            Version::new(2025, 3, 0),
+            Version::new(2025, 3, 1),
+        ];
+        let next = format.next_version(march, &existing);
+        assert_eq!(next, Version::new(2025, 3, 2));
+        assert_eq!(format.format(&next), "2025.03.2");
+
+        let april = jiff::civil::date(2025, 4, 1);
+        assert_eq!(
+            format.next_version(april, &existing),
+            Version::new(2025, 4, 0)
+        );
+    }
+
+    #[test]
+    fn calver_tokens_and_validation() {
+        let date = jiff::civil::date(2026, 1, 5);
+        let short = CalverFormat::parse("0Y.0W.MICRO").unwrap();
+        let next = short.next_version(date, &[]);
+        assert_eq!(short.format(&next), "26.02.0");
+        let monthly = CalverFormat::parse("YY.MM.MICRO").unwrap();
+        assert_eq!(monthly.format(&monthly.next_version(date, &[])), "26.1.0");
+
+        assert!(CalverFormat::parse("YYYY.0M").is_err());
+        let err = CalverFormat::parse("YY.DD.MICRO").unwrap_err();
+        assert!(err.contains("day token 'DD'"), "{err}");
+        assert!(CalverFormat::parse("YYYY.0W.MM").is_err());
+        for mixed in ["0M.YYYY.MICRO", "WW.0W.MICRO", "YY.0Y.MICRO"] {
+            let err = CalverFormat::parse(mixed).unwrap_err();
+            assert!(err.contains("must be a year token"), "{mixed}: {err}");
+        }
+        assert!(CalverFormat::parse("YYYY.MICRO.0M").is_err());
+        let err = CalverFormat::parse("YYYY.QQ.MICRO").unwrap_err();
+        assert!(err.contains("unknown token 'QQ'"), "{err}");
+    }
+
+    #[test]
+    fn calver_weeks_use_the_iso_year_at_the_year_boundary() {
+        let format = CalverFormat::parse("YYYY.0W.MICRO").unwrap();
+        let released = [Version::new(2024, 52, 0)];
+        // 2024-12-30 is in ISO week 2025-W01
+        let next = format.next_version(jiff::civil::date(2024, 12, 30), &released);
+        assert_eq!(format.format(&next), "2025.01.0");
+        assert!(next > released[0]);
+        // 2027-01-01 is in ISO week 2026-W53
+        let next = format.next_version(jiff::civil::date(2027, 1, 1), &[]);
+        assert_eq!(format.format(&next), "2026.53.0");
+
+        let monthly = CalverFormat::parse("YYYY.0M.MICRO").unwrap();
+        let next = monthly.next_version(jiff::civil::date(2024, 12, 30), &[]);
+        assert_eq!(monthly.format(&next), "2024.12.0");
+    }
+
     // --- pre-release version tests ---
 
     #[test]
@@ -2,6 +2,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use regex::Regex;
+use tracing::warn;
 
 use crate::error::ReleaseError;
 
@@ -27,6 +28,26 @@ pub trait VersionFileHandler: Send + Sync {
     /// Bump version in the manifest at `path`. Returns additional files that
     /// were auto-discovered and bumped (e.g. workspace members).
     fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError>;
+
+    /// Current version declared in the manifest at `path`, if any.
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError>;
+
+    /// Bump the version and point the manifest's download URL at `url` (already
+    /// rendered for the new version). Only packaging manifests that pin a
+    /// release download support this.
+    fn bump_with_url(
+        &self,
+        path: &Path,
+        new_version: &str,
+        url: &str,
+    ) -> Result<Vec<PathBuf>, ReleaseError> {
+        let _ = (new_version, url);
+        Err(ReleaseError::VersionBump(format!(
+            "{} has no download URL to update ({} manifests do not support version_file_urls)",
+            path.display(),
+            self.name()
+        )))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -48,6 +69,9 @@ impl VersionFileHandler for CargoHandler {
     fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
         bump_cargo_toml(path, new_version)
     }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_cargo_version(path)
+    }
 }
 
 struct NpmHandler;
@@ -60,11 +84,60 @@ impl VersionFileHandler for NpmHandler {
         &["package.json"]
     }
     fn lock_file_names(&self) -> &[&str] {
-        &["package-lock.json", "yarn.lock", "pnpm-lock.yaml"]
+        &[
+            "package-lock.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+            "bun.lock",
+            "bun.lockb",
+        ]
     }
     fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
         bump_package_json(path, new_version)
     }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_package_json_version(path)
+    }
+}
+
+struct DenoHandler;
+
+impl VersionFileHandler for DenoHandler {
+    fn name(&self) -> &str {
+        "Deno"
+    }
+    fn manifest_names(&self) -> &[&str] {
+        &["deno.json", "deno.jsonc"]
+    }
+    fn lock_file_names(&self) -> &[&str] {
+        &["deno.lock"]
+    }
+    fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_deno_json(path, new_version).map(|()| vec![])
+    }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_deno_version(path)
+    }
+}
+
+struct CondaHandler;
+
+impl VersionFileHandler for CondaHandler {
+    fn name(&self) -> &str {
+        "conda"
+    }
+    fn manifest_names(&self) -> &[&str] {
+        &["meta.yaml"]
+    }
+    fn lock_file_names(&self) -> &[&str] {
+        &[]
+    }
+    fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_conda_recipe(path, new_version).map(|()| vec![])
+    }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_conda_version(path)
+    }
 }
 
 struct PyprojectHandler;
@@ -82,6 +155,9 @@ impl VersionFileHandler for PyprojectHandler {
     fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
         bump_pyproject_toml(path, new_version)
     }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_pyproject_version(path)
+    }
 }
 
 struct MavenHandler;
@@ -99,6 +175,9 @@ impl VersionFileHandler for MavenHandler {
     fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
         bump_pom_xml(path, new_version).map(|()| vec![])
     }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_pom_version(path)
+    }
 }
 
 struct GradleHandler;
@@ -116,6 +195,51 @@ impl VersionFileHandler for GradleHandler {
     fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
         bump_gradle(path, new_version).map(|()| vec![])
     }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_gradle_version(path)
+    }
+}
+
+struct NuspecHandler;
+
+impl VersionFileHandler for NuspecHandler {
+    fn name(&self) -> &str {
+        "NuGet"
+    }
+    /// Named after the package; matched by the `.nuspec` extension.
+    fn manifest_names(&self) -> &[&str] {
+        &[]
+    }
+    fn lock_file_names(&self) -> &[&str] {
+        &[]
+    }
+    fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_nuspec(path, new_version).map(|()| vec![])
+    }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        let contents = read_file(path)?;
+        Ok(nuspec_version_range(&contents).map(|range| contents[range].to_string()))
+    }
+}
+
+struct AssemblyInfoHandler;
+
+impl VersionFileHandler for AssemblyInfoHandler {
+    fn name(&self) -> &str {
+        "AssemblyInfo"
+    }
+    fn manifest_names(&self) -> &[&str] {
+        &["AssemblyInfo.cs"]
+    }
+    fn lock_file_names(&self) -> &[&str] {
+        &[]
+    }
+    fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_assembly_info(path, new_version).map(|()| vec![])
+    }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_assembly_info_version(path)
+    }
 }
 
 struct GoHandler;
@@ -149,6 +273,91 @@ impl VersionFileHandler for GoHandler {
     fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
         bump_go_version(path, new_version).map(|()| vec![])
     }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_go_version(path)
+    }
+}
+
+struct HomebrewHandler;
+
+impl VersionFileHandler for HomebrewHandler {
+    fn name(&self) -> &str {
+        "Homebrew"
+    }
+    /// Formulae live in taps under arbitrary names; they are never auto-detected.
+    fn manifest_names(&self) -> &[&str] {
+        &[]
+    }
+    fn lock_file_names(&self) -> &[&str] {
+        &[]
+    }
+    fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_formula(path, new_version, None).map(|()| vec![])
+    }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_formula_version(path)
+    }
+    fn bump_with_url(
+        &self,
+        path: &Path,
+        new_version: &str,
+        url: &str,
+    ) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_formula(path, new_version, Some(url)).map(|()| vec![])
+    }
+}
+
+struct ScoopHandler;
+
+impl VersionFileHandler for ScoopHandler {
+    fn name(&self) -> &str {
+        "Scoop"
+    }
+    fn manifest_names(&self) -> &[&str] {
+        &[]
+    }
+    fn lock_file_names(&self) -> &[&str] {
+        &[]
+    }
+    fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_scoop_manifest(path, new_version, None).map(|()| vec![])
+    }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        let (_, value) = read_scoop_manifest(path)?;
+        Ok(value
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(String::from))
+    }
+    fn bump_with_url(
+        &self,
+        path: &Path,
+        new_version: &str,
+        url: &str,
+    ) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_scoop_manifest(path, new_version, Some(url)).map(|()| vec![])
+    }
+}
+
+struct OpenApiHandler;
+
+impl VersionFileHandler for OpenApiHandler {
+    fn name(&self) -> &str {
+        "OpenAPI"
+    }
+    /// Matched by basename (`openapi.*`, `swagger.*`); never auto-detected.
+    fn manifest_names(&self) -> &[&str] {
+        &[]
+    }
+    fn lock_file_names(&self) -> &[&str] {
+        &[]
+    }
+    fn bump(&self, path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
+        bump_openapi(path, new_version).map(|()| vec![])
+    }
+    fn read_version(&self, path: &Path) -> Result<Option<String>, ReleaseError> {
+        read_openapi_version(path)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -160,10 +369,17 @@ pub fn all_handlers() -> Vec<Box<dyn VersionFileHandler>> {
     vec![
         Box::new(CargoHandler),
         Box::new(NpmHandler),
+        Box::new(DenoHandler),
         Box::new(PyprojectHandler),
+        Box::new(CondaHandler),
         Box::new(MavenHandler),
         Box::new(GradleHandler),
+        Box::new(NuspecHandler),
+        Box::new(AssemblyInfoHandler),
         Box::new(GoHandler),
+        Box::new(HomebrewHandler),
+        Box::new(ScoopHandler),
+        Box::new(OpenApiHandler),
     ]
 }
 
@@ -203,6 +419,14 @@ pub fn detect_version_files(dir: &Path) -> Vec<String> {
     files
 }
 
+/// `package.name` in `dir/Cargo.toml`, if `dir` holds a Cargo package (not
+/// just a workspace root).
+pub fn cargo_package_name(dir: &Path) -> Option<String> {
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    let doc: toml_edit::DocumentMut = manifest.parse().ok()?;
+    Some(doc.get("package")?.get("name")?.as_str()?.to_string())
+}
+
 /// Look up the handler for a given filename.
 fn handler_for_file(filename: &str) -> Option<Box<dyn VersionFileHandler>> {
     for handler in all_handlers() {
@@ -210,10 +434,22 @@ fn handler_for_file(filename: &str) -> Option<Box<dyn VersionFileHandler>> {
             return Some(handler);
         }
     }
-    // Go files: any .go extension
+    // Files named by the project rather than the ecosystem, matched by extension
+    if is_openapi_file(filename) {
+        return Some(Box::new(OpenApiHandler));
+    }
+    if filename.ends_with(".nuspec") {
+        return Some(Box::new(NuspecHandler));
+    }
     if filename.ends_with(".go") {
         return Some(Box::new(GoHandler));
     }
+    if filename.ends_with(".rb") {
+        return Some(Box::new(HomebrewHandler));
+    }
+    if filename.ends_with(".json") {
+        return Some(Box::new(ScoopHandler));
+    }
     None
 }
 
@@ -225,11 +461,19 @@ fn handler_for_file(filename: &str) -> Option<Box<dyn VersionFileHandler>> {
 /// The file format is auto-detected from the filename:
 /// - `Cargo.toml`          → TOML (`package.version` or `workspace.package.version`)
 /// - `package.json`        → JSON (`.version`)
+/// - `deno.json(c)`        → JSON with comments (`.version`)
 /// - `pyproject.toml`      → TOML (`project.version` or `tool.poetry.version`)
+/// - `meta.yaml`           → conda recipe (`{% set version = "..." %}` or `package.version`)
 /// - `build.gradle`        → Gradle Groovy DSL (`version = '...'` or `version = "..."`)
 /// - `build.gradle.kts`    → Gradle Kotlin DSL (`version = "..."`)
 /// - `pom.xml`             → Maven (`<version>...</version>`, skipping `<parent>` block)
+/// - `*.nuspec`            → NuGet (`<version>` inside `<metadata>`)
+/// - `AssemblyInfo.cs`     → .NET (`AssemblyVersion`, `AssemblyFileVersion`, `AssemblyInformationalVersion`)
 /// - `*.go`                → Go (`var/const Version = "..."`)
+/// - `*.rb`                → Homebrew formula (`version "..."`), if it defines a `< Formula` class
+/// - `openapi.*`, `swagger.*` (YAML or JSON) → OpenAPI spec (`info.version`)
+/// - other `*.json`        → Scoop manifest (top-level `"version"`), if it also has
+///   a top-level `"url"` or `"architecture"`
 ///
 /// For workspace roots (Cargo, npm, uv), member manifests are auto-discovered
 /// and bumped without needing to list them in `version_files`.
@@ -247,6 +491,45 @@ pub fn bump_version_file(path: &Path, new_version: &str) -> Result<Vec<PathBuf>,
     }
 }
 
+/// Like [`bump_version_file`], also pointing the manifest's download URL at
+/// `url`. Supported for Homebrew formulae (the first `url` stanza) and Scoop
+/// manifests (the top-level `"url"`). Checksums are left untouched.
+pub fn bump_version_file_with_url(
+    path: &Path,
+    new_version: &str,
+    url: &str,
+) -> Result<Vec<PathBuf>, ReleaseError> {
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+
+    match handler_for_file(filename) {
+        Some(handler) => handler.bump_with_url(path, new_version, url),
+        None => Err(ReleaseError::VersionBump(format!(
+            "unsupported version file: {filename}"
+        ))),
+    }
+}
+
+/// Read the version currently declared in a supported version file.
+///
+/// Returns `Ok(None)` when the file has no version field (e.g. a Cargo member
+/// inheriting `version.workspace = true`).
+pub fn read_version_file(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+
+    match handler_for_file(filename) {
+        Some(handler) => handler.read_version(path),
+        None => Err(ReleaseError::VersionBump(format!(
+            "unsupported version file: {filename}"
+        ))),
+    }
+}
+
 /// Given a list of bumped manifest paths, discover associated lock files that exist on disk.
 /// Searches the manifest's directory and ancestors (for monorepo roots).
 /// Returns deduplicated paths.
@@ -292,6 +575,14 @@ pub fn is_supported_version_file(filename: &str) -> bool {
     handler_for_file(filename).is_some()
 }
 
+/// `openapi.{yaml,yml,json}` or `swagger.{yaml,yml,json}`.
+fn is_openapi_file(filename: &str) -> bool {
+    let Some((stem, ext)) = filename.split_once('.') else {
+        return false;
+    };
+    matches!(stem, "openapi" | "swagger") && matches!(ext, "yaml" | "yml" | "json")
+}
+
 /// Compile the Go Version variable regex (used in detection).
 fn go_version_re() -> Regex {
     Regex::new(r#"(?:var|const)\s+Version\s*(?:string\s*)?=\s*""#).unwrap()
@@ -354,7 +645,7 @@ fn bump_cargo_toml(path: &Path, new_version: &str) -> Result<Vec<PathBuf>, Relea
             match bump_cargo_member(&member_path, new_version) {
                 Ok(true) => extra.push(member_path),
                 Ok(false) => {}
-                Err(e) => eprintln!("warning: {e}"),
+                Err(e) => warn!("{e}"),
             }
         }
     }
@@ -425,7 +716,7 @@ fn bump_package_json(path: &Path, new_version: &str) -> Result<Vec<PathBuf>, Rel
             match bump_json_version(&member_path, new_version) {
                 Ok(true) => extra.push(member_path),
                 Ok(false) => {}
-                Err(e) => eprintln!("warning: {e}"),
+                Err(e) => warn!("{e}"),
             }
         }
     }
@@ -463,6 +754,132 @@ fn bump_json_version(path: &Path, new_version: &str) -> Result<bool, ReleaseErro
     Ok(true)
 }
 
+/// Bump the top-level `"version"` of a `deno.json`/`deno.jsonc`. Comments
+/// are tolerated and the value is replaced in place, so they survive.
+fn bump_deno_json(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
+    let contents = read_file(path)?;
+    let range = top_level_string_value(&strip_jsonc(&contents), "version").ok_or_else(|| {
+        ReleaseError::VersionBump(format!(
+            "no top-level \"version\" string in {}",
+            path.display()
+        ))
+    })?;
+    let quoted = serde_json::Value::String(new_version.into()).to_string();
+    let result = format!(
+        "{}{quoted}{}",
+        &contents[..range.start],
+        &contents[range.end..]
+    );
+    write_file(path, &result)
+}
+
+/// `contents` with comments and trailing commas blanked out by spaces, so it
+/// parses as JSON and byte offsets still match the original.
+fn strip_jsonc(contents: &str) -> String {
+    let mut bytes = contents.as_bytes().to_vec();
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'"', _) => i = string_end(&bytes, i),
+            (b'/', Some(b'/')) => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    bytes[i] = b' ';
+                    i += 1;
+                }
+            }
+            (b'/', Some(b'*')) => {
+                let end = contents[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |at| i + 2 + at + 2);
+                for b in &mut bytes[i..end] {
+                    if !b.is_ascii_whitespace() {
+                        *b = b' ';
+                    }
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    // Trailing commas, now that comments are out of the way
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = string_end(&bytes, i),
+            b',' => {
+                let next = bytes[i + 1..].iter().find(|b| !b.is_ascii_whitespace());
+                if matches!(next, Some(b'}' | b']')) {
+                    bytes[i] = b' ';
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    String::from_utf8(bytes).expect("only ASCII bytes were replaced")
+}
+
+/// Index just past the string literal starting at `bytes[start]` (a `"`).
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Byte range (quotes included) of the string value of `key` in the
+/// top-level object of comment-free JSON text.
+fn top_level_string_value(json: &str, key: &str) -> Option<std::ops::Range<usize>> {
+    let bytes = json.as_bytes();
+    let skip_ws = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        i
+    };
+    let mut depth = 0;
+    let mut expect_key = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' => {
+                depth += 1;
+                expect_key = depth == 1 && bytes[i] == b'{';
+                i += 1;
+            }
+            b'}' | b']' => {
+                depth -= 1;
+                i += 1;
+            }
+            b',' => {
+                expect_key = depth == 1;
+                i += 1;
+            }
+            b'"' => {
+                let end = string_end(bytes, i);
+                if expect_key {
+                    expect_key = false;
+                    let name: Option<String> = serde_json::from_str(&json[i..end]).ok();
+                    let colon = skip_ws(end);
+                    if name.as_deref() == Some(key) && bytes.get(colon) == Some(&b':') {
+                        let value = skip_ws(colon + 1);
+                        return (bytes.get(value) == Some(&b'"'))
+                            .then(|| value..string_end(bytes, value));
+                    }
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
 fn bump_pyproject_toml(path: &Path, new_version: &str) -> Result<Vec<PathBuf>, ReleaseError> {
     let contents = read_file(path)?;
     let mut doc: toml_edit::DocumentMut = contents.parse().map_err(|e| {
@@ -499,7 +916,7 @@ fn bump_pyproject_toml(path: &Path, new_version: &str) -> Result<Vec<PathBuf>, R
             match bump_pyproject_member(&member_path, new_version) {
                 Ok(true) => extra.push(member_path),
                 Ok(false) => {}
-                Err(e) => eprintln!("warning: {e}"),
+                Err(e) => warn!("{e}"),
             }
         }
     }
@@ -547,7 +964,23 @@ fn bump_gradle(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
 
 fn bump_pom_xml(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
     let contents = read_file(path)?;
+    if let Some(range) = pom_version_range(&contents) {
+        let replacement = format!("<version>{new_version}</version>");
+        let mut result = String::with_capacity(contents.len());
+        result.push_str(&contents[..range.start]);
+        result.push_str(&replacement);
+        result.push_str(&contents[range.end..]);
+        write_file(path, &result)
+    } else {
+        Err(ReleaseError::VersionBump(format!(
+            "no <version> element found in {}",
+            path.display()
+        )))
+    }
+}
 
+/// Byte range of the project's own `<version>` element, skipping the `<parent>` block.
+fn pom_version_range(contents: &str) -> Option<std::ops::Range<usize>> {
     // Determine search start: skip past </parent> if present, else after </modelVersion>
     let search_start = if let Some(pos) = contents.find("</parent>") {
         pos + "</parent>".len()
@@ -557,21 +990,69 @@ fn bump_pom_xml(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
         0
     };
 
-    let rest = &contents[search_start..];
     let re = Regex::new(r"<version>[^<]*</version>").unwrap();
-    if let Some(m) = re.find(rest) {
-        let replacement = format!("<version>{new_version}</version>");
-        let mut result = String::with_capacity(contents.len());
-        result.push_str(&contents[..search_start + m.start()]);
-        result.push_str(&replacement);
-        result.push_str(&contents[search_start + m.end()..]);
-        write_file(path, &result)
-    } else {
-        Err(ReleaseError::VersionBump(format!(
-            "no <version> element found in {}",
+    re.find(&contents[search_start..])
+        .map(|m| search_start + m.start()..search_start + m.end())
+}
+
+fn bump_nuspec(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
+    let mut contents = read_file(path)?;
+    let range = nuspec_version_range(&contents).ok_or_else(|| {
+        ReleaseError::VersionBump(format!(
+            "no <version> inside <metadata> in {}",
             path.display()
-        )))
+        ))
+    })?;
+    contents.replace_range(range, new_version);
+    write_file(path, &contents)
+}
+
+/// Byte range of the text of the `<version>` element inside `<metadata>`;
+/// `version="..."` attributes on dependencies are never matched.
+fn nuspec_version_range(contents: &str) -> Option<std::ops::Range<usize>> {
+    let metadata = Regex::new(r"(?s)<metadata\b[^>]*>(.*?)</metadata>")
+        .unwrap()
+        .captures(contents)?
+        .get(1)?;
+    let version = Regex::new(r"<version>\s*([^<]*?)\s*</version>")
+        .unwrap()
+        .captures(metadata.as_str())?
+        .get(1)?;
+    Some(metadata.start() + version.start()..metadata.start() + version.end())
+}
+
+/// `[assembly: Assembly{,File,Informational}Version("...")]`, capturing the
+/// prefix, the attribute kind, and the value.
+fn assembly_version_re() -> Regex {
+    Regex::new(
+        r#"(\[\s*assembly\s*:\s*(?:System\.Reflection\.)?Assembly(File|Informational)?Version(?:Attribute)?\s*\(\s*")([^"]*)""#,
+    )
+    .unwrap()
+}
+
+/// Update every assembly version attribute. `AssemblyVersion` and
+/// `AssemblyFileVersion` only take four numeric components, so they get
+/// `major.minor.patch.0`; `AssemblyInformationalVersion` gets `new_version`.
+fn bump_assembly_info(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
+    let contents = read_file(path)?;
+    let re = assembly_version_re();
+    if !re.is_match(&contents) {
+        return Err(ReleaseError::VersionBump(format!(
+            "no [assembly: AssemblyVersion(...)] attribute in {}",
+            path.display()
+        )));
     }
+    let version = crate::version::parse_tag_version(new_version)
+        .ok_or_else(|| ReleaseError::VersionBump(format!("invalid version {new_version}")))?;
+    let numeric = format!("{}.{}.{}.0", version.major, version.minor, version.patch);
+    let result = re.replace_all(&contents, |caps: &regex::Captures| {
+        let value = match caps.get(2).map(|m| m.as_str()) {
+            Some("Informational") => new_version,
+            _ => &numeric,
+        };
+        format!("{}{value}\"", &caps[1])
+    });
+    write_file(path, &result)
 }
 
 fn bump_go_version(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
@@ -587,8 +1068,409 @@ fn bump_go_version(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
     write_file(path, &result)
 }
 
+/// Bump a Homebrew formula: the `version` stanza when present, and the first
+/// `url` stanza when `url` is given. A formula without `version` derives it
+/// from the URL, so it can only be bumped through the URL.
+fn bump_formula(path: &Path, new_version: &str, url: Option<&str>) -> Result<(), ReleaseError> {
+    let contents = read_formula(path)?;
+    let version_re = formula_stanza_re(&contents, "version");
+    let has_version = version_re.is_match(&contents);
+    if !has_version && url.is_none() {
+        return Err(ReleaseError::VersionBump(format!(
+            "no version stanza in {}; its version comes from the url, so set a \
+             version_file_urls template for it",
+            path.display()
+        )));
+    }
+    let mut result = if has_version {
+        version_re
+            .replacen(&contents, 1, format!("${{1}}{new_version}${{3}}"))
+            .into_owned()
+    } else {
+        contents
+    };
+    if let Some(url) = url {
+        let url_re = formula_stanza_re(&result, "url");
+        if !url_re.is_match(&result) {
+            return Err(ReleaseError::VersionBump(format!(
+                "no url stanza in {}",
+                path.display()
+            )));
+        }
+        result = url_re
+            .replacen(&result, 1, |caps: &regex::Captures| {
+                format!("{}{url}{}", &caps[1], &caps[3])
+            })
+            .into_owned();
+    }
+    write_file(path, &result)
+}
+
+/// Read a `*.rb` file, failing unless it defines a `class ... < Formula`: any
+/// Ruby file matches the extension, and only formulae should be rewritten.
+fn read_formula(path: &Path) -> Result<String, ReleaseError> {
+    let contents = read_file(path)?;
+    let formula_re = Regex::new(r"(?m)^\s*class\s+\w+\s*<\s*Formula\b").unwrap();
+    if !formula_re.is_match(&contents) {
+        return Err(ReleaseError::VersionBump(format!(
+            "{} is not a Homebrew formula (no `class ... < Formula`)",
+            path.display()
+        )));
+    }
+    Ok(contents)
+}
+
+/// `<keyword> "..."` directly in the formula's class body (not in a `resource`
+/// or other nested block), capturing prefix, value, and closing quote.
+fn formula_stanza_re(contents: &str, keyword: &str) -> Regex {
+    let mut lines = contents
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("class "))
+        .skip(1)
+        .filter(|line| !line.trim().is_empty());
+    let indent: String = lines
+        .next()
+        .map(|line| line.chars().take_while(|c| c.is_whitespace()).collect())
+        .unwrap_or_else(|| "  ".into());
+    Regex::new(&format!(
+        r#"(?m)^({}{keyword}\s+")([^"]*)(")"#,
+        regex::escape(&indent)
+    ))
+    .unwrap()
+}
+
+/// Bump a Scoop manifest's top-level `"version"`, and its top-level `"url"`
+/// when `url` is given, editing the text in place to keep its formatting.
+fn bump_scoop_manifest(
+    path: &Path,
+    new_version: &str,
+    url: Option<&str>,
+) -> Result<(), ReleaseError> {
+    let (contents, value) = read_scoop_manifest(path)?;
+    let field = |name: &str| {
+        value.get(name).and_then(|v| v.as_str()).ok_or_else(|| {
+            ReleaseError::VersionBump(format!(
+                "no top-level \"{name}\" string in {}",
+                path.display()
+            ))
+        })
+    };
+    let mut result = replace_json_field(&contents, "version", field("version")?, new_version);
+    if let Some(url) = url {
+        result = replace_json_field(&result, "url", field("url")?, url);
+    }
+    write_file(path, &result)
+}
+
+/// Read and parse a `*.json` file, failing unless it looks like a Scoop
+/// manifest: a top-level `"version"` plus `"url"` or `"architecture"`. Any
+/// JSON file matches the extension, and only manifests should be rewritten.
+fn read_scoop_manifest(path: &Path) -> Result<(String, serde_json::Value), ReleaseError> {
+    let contents = read_file(path)?;
+    let value: serde_json::Value = serde_json::from_str(&contents).map_err(|e| {
+        ReleaseError::VersionBump(format!("failed to parse {}: {e}", path.display()))
+    })?;
+    let has = |key: &str| value.get(key).is_some();
+    if !has("version") || !(has("url") || has("architecture")) {
+        return Err(ReleaseError::VersionBump(format!(
+            "{} is not a Scoop manifest (needs a top-level \"version\" plus \"url\" or \
+             \"architecture\")",
+            path.display()
+        )));
+    }
+    Ok((contents, value))
+}
+
+/// Replace the first `"<key>": "<old>"` pair in JSON text with `new`.
+fn replace_json_field(contents: &str, key: &str, old: &str, new: &str) -> String {
+    let quote = |s: &str| serde_json::Value::String(s.into()).to_string();
+    let re = Regex::new(&format!(
+        r#"("{key}"\s*:\s*){}"#,
+        regex::escape(&quote(old))
+    ))
+    .unwrap();
+    re.replacen(contents, 1, |caps: &regex::Captures| {
+        format!("{}{}", &caps[1], quote(new))
+    })
+    .into_owned()
+}
+
+/// Bump a conda recipe: the Jinja `{% set version = "..." %}` assignment when
+/// present, otherwise the literal `package.version`. The recipe is templated,
+/// so it is edited line by line instead of parsed; `requirements` pins are
+/// never touched.
+fn bump_conda_recipe(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
+    let contents = read_file(path)?;
+    let jinja_re = conda_set_version_re();
+    if jinja_re.is_match(&contents) {
+        let result = jinja_re.replacen(&contents, 1, |caps: &regex::Captures| {
+            let quote = &caps[2][..1];
+            format!("{}{quote}{new_version}{quote}", &caps[1])
+        });
+        return write_file(path, &result);
+    }
+    let range = yaml_block_key_line(&contents, "package", "version").ok_or_else(|| {
+        ReleaseError::VersionBump(format!(
+            "no `{{% set version = ... %}}` or package.version in {}",
+            path.display()
+        ))
+    })?;
+    if contents[range.clone()].contains("{{") {
+        return Err(ReleaseError::VersionBump(format!(
+            "package.version in {} is templated but there is no \
+             `{{% set version = ... %}}` to bump",
+            path.display()
+        )));
+    }
+    write_file(
+        path,
+        &replace_yaml_line(&contents, range, "version", new_version),
+    )
+}
+
+/// `{% set version = "..." %}`, capturing the prefix and the quoted value.
+fn conda_set_version_re() -> Regex {
+    Regex::new(r#"(?m)^(\s*\{%-?\s*set\s+version\s*=\s*)("[^"]*"|'[^']*')"#).unwrap()
+}
+
+/// Bump `info.version` in an OpenAPI/Swagger spec. The document is edited as
+/// text, so YAML comments and JSON formatting survive.
+fn bump_openapi(path: &Path, new_version: &str) -> Result<(), ReleaseError> {
+    let contents = read_file(path)?;
+    let old = read_openapi_version(path)?.ok_or_else(|| missing_openapi_version(path))?;
+    let result = if is_json(path) {
+        // Start at "info" so a "version" elsewhere in the document is left alone
+        let info = Regex::new(r#""info"\s*:\s*\{"#)
+            .unwrap()
+            .find(&contents)
+            .map_or(0, |m| m.end());
+        let rest = replace_json_field(&contents[info..], "version", &old, new_version);
+        format!("{}{rest}", &contents[..info])
+    } else {
+        let range = yaml_block_key_line(&contents, "info", "version")
+            .ok_or_else(|| missing_openapi_version(path))?;
+        replace_yaml_line(&contents, range, "version", new_version)
+    };
+    if result == contents && old != new_version {
+        return Err(missing_openapi_version(path));
+    }
+    write_file(path, &result)
+}
+
+/// Byte range of the `<key>:` line directly under the top-level block-style
+/// `<block>:` mapping, excluding the line terminator.
+fn yaml_block_key_line(contents: &str, block: &str, key: &str) -> Option<std::ops::Range<usize>> {
+    let key_re = Regex::new(&format!(r"^\s*{}\s*:", regex::escape(key))).unwrap();
+    let header = format!("{block}:");
+    let mut offset = 0;
+    let mut in_block = false;
+    let mut child_indent = None;
+    for raw in contents.split_inclusive('\n') {
+        let start = offset;
+        offset += raw.len();
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let body = line.trim_start();
+        if body.is_empty() || body.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - body.len();
+        if !in_block {
+            in_block = indent == 0
+                && body
+                    .strip_prefix(&header)
+                    .is_some_and(|rest| rest.trim().is_empty() || rest.trim().starts_with('#'));
+            continue;
+        }
+        if indent == 0 {
+            return None;
+        }
+        let child = *child_indent.get_or_insert(indent);
+        if indent == child && key_re.is_match(line) {
+            return Some(start..start + line.len());
+        }
+    }
+    None
+}
+
+/// `<key>: <value>` and its value with quotes removed, from a single YAML line.
+fn yaml_scalar_re(key: &str) -> Regex {
+    Regex::new(&format!(
+        r#"^(\s*{}\s*:\s*)("[^"]*"|'[^']*'|[^\s#]+)"#,
+        regex::escape(key)
+    ))
+    .unwrap()
+}
+
+/// Replace the scalar on the `<key>:` line at `range`, keeping its quoting
+/// and any trailing comment.
+fn replace_yaml_line(
+    contents: &str,
+    range: std::ops::Range<usize>,
+    key: &str,
+    new_value: &str,
+) -> String {
+    let line = yaml_scalar_re(key)
+        .replacen(&contents[range.clone()], 1, |caps: &regex::Captures| {
+            let quote = &caps[2][..1];
+            if quote == "\"" || quote == "'" {
+                format!("{}{quote}{new_value}{quote}", &caps[1])
+            } else {
+                format!("{}{new_value}", &caps[1])
+            }
+        })
+        .into_owned();
+    format!(
+        "{}{line}{}",
+        &contents[..range.start],
+        &contents[range.end..]
+    )
+}
+
+fn missing_openapi_version(path: &Path) -> ReleaseError {
+    ReleaseError::VersionBump(format!("no /info/version in {}", path.display()))
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "json")
+}
+
+// ---------------------------------------------------------------------------
+// Private read implementations
+// ---------------------------------------------------------------------------
+
+fn parse_toml(path: &Path) -> Result<toml_edit::DocumentMut, ReleaseError> {
+    read_file(path)?
+        .parse()
+        .map_err(|e| ReleaseError::VersionBump(format!("failed to parse {}: {e}", path.display())))
+}
+
+/// String value at a nested TOML path (e.g. `["package", "version"]`).
+fn toml_string(doc: &toml_edit::DocumentMut, keys: &[&str]) -> Option<String> {
+    let (first, rest) = keys.split_first()?;
+    let mut item = doc.get(first)?;
+    for key in rest {
+        item = item.get(key)?;
+    }
+    item.as_str().map(String::from)
+}
+
+fn read_cargo_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let doc = parse_toml(path)?;
+    Ok(toml_string(&doc, &["package", "version"])
+        .or_else(|| toml_string(&doc, &["workspace", "package", "version"])))
+}
+
+fn read_package_json_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let value: serde_json::Value = serde_json::from_str(&read_file(path)?).map_err(|e| {
+        ReleaseError::VersionBump(format!("failed to parse {}: {e}", path.display()))
+    })?;
+    Ok(value
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(String::from))
+}
+
+fn read_deno_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let value: serde_json::Value =
+        serde_json::from_str(&strip_jsonc(&read_file(path)?)).map_err(|e| {
+            ReleaseError::VersionBump(format!("failed to parse {}: {e}", path.display()))
+        })?;
+    Ok(value
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(String::from))
+}
+
+fn read_pyproject_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let doc = parse_toml(path)?;
+    Ok(toml_string(&doc, &["project", "version"])
+        .or_else(|| toml_string(&doc, &["tool", "poetry", "version"])))
+}
+
+fn read_pom_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let contents = read_file(path)?;
+    Ok(pom_version_range(&contents).map(|range| {
+        contents[range]
+            .trim_start_matches("<version>")
+            .trim_end_matches("</version>")
+            .to_string()
+    }))
+}
+
+fn read_gradle_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let re = Regex::new(r#"version\s*=\s*["']([^"']*)["']"#).unwrap();
+    Ok(re.captures(&read_file(path)?).map(|c| c[1].to_string()))
+}
+
+/// The `version` stanza, else the version embedded in the first `url`.
+fn read_formula_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let contents = read_formula(path)?;
+    if let Some(c) = formula_stanza_re(&contents, "version").captures(&contents) {
+        return Ok(Some(c[2].to_string()));
+    }
+    let version_in_url = Regex::new(r"\d+\.\d+\.\d+(?:-[0-9A-Za-z]+(?:\.\d+)?)?").unwrap();
+    Ok(formula_stanza_re(&contents, "url")
+        .captures(&contents)
+        .and_then(|c| version_in_url.find(&c[2]).map(|m| m.as_str().to_string())))
+}
+
+fn read_openapi_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let contents = read_file(path)?;
+    let parse_err = |e: &dyn std::fmt::Display| {
+        ReleaseError::VersionBump(format!("failed to parse {}: {e}", path.display()))
+    };
+    let value: serde_json::Value = if is_json(path) {
+        serde_json::from_str(&contents).map_err(|e| parse_err(&e))?
+    } else {
+        serde_yaml_ng::from_str(&contents).map_err(|e| parse_err(&e))?
+    };
+    Ok(match value.pointer("/info/version") {
+        Some(serde_json::Value::String(v)) => Some(v.clone()),
+        Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+fn read_conda_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let contents = read_file(path)?;
+    let unquote = |v: &str| v.trim_matches(['"', '\'']).to_string();
+    if let Some(c) = conda_set_version_re().captures(&contents) {
+        return Ok(Some(unquote(&c[2])));
+    }
+    Ok(yaml_block_key_line(&contents, "package", "version")
+        .and_then(|range| yaml_scalar_re("version").captures(&contents[range]))
+        .map(|c| unquote(&c[2]))
+        .filter(|v| !v.contains("{{")))
+}
+
+/// `AssemblyInformationalVersion`, else `AssemblyVersion` without its fourth
+/// component.
+fn read_assembly_info_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let contents = read_file(path)?;
+    let mut numeric = None;
+    for caps in assembly_version_re().captures_iter(&contents) {
+        match caps.get(2).map(|m| m.as_str()) {
+            Some("Informational") => return Ok(Some(caps[3].to_string())),
+            None if numeric.is_none() => {
+                let parts: Vec<_> = caps[3].split('.').take(3).collect();
+                numeric = Some(parts.join("."));
+            }
+            _ => {}
+        }
+    }
+    Ok(numeric)
+}
+
+fn read_go_version(path: &Path) -> Result<Option<String>, ReleaseError> {
+    let re = Regex::new(r#"(?:var|const)\s+Version\s*(?:string\s*)?=\s*"([^"]*)""#).unwrap();
+    Ok(re.captures(&read_file(path)?).map(|c| c[1].to_string()))
+}
+
 /// Extract a string array from a nested TOML path (e.g. `["workspace", "members"]`).
-fn extract_toml_string_array(doc: &toml_edit::DocumentMut, keys: &[&str]) -> Vec<String> {
+pub(crate) fn extract_toml_string_array(
+    doc: &toml_edit::DocumentMut,
+    keys: &[&str],
+) -> Vec<String> {
     let mut item: Option<&toml_edit::Item> = None;
     for key in keys {
         item = match item {
@@ -611,7 +1493,11 @@ fn extract_toml_string_array(doc: &toml_edit::DocumentMut, keys: &[&str]) -> Vec
 /// Resolve workspace member glob patterns into manifest file paths.
 /// Each glob is resolved relative to `root_dir`, and `manifest_name` is appended
 /// to each matched directory (e.g. "Cargo.toml", "package.json", "pyproject.toml").
-fn resolve_member_globs(root_dir: &Path, patterns: &[String], manifest_name: &str) -> Vec<PathBuf> {
+pub(crate) fn resolve_member_globs(
+    root_dir: &Path,
+    patterns: &[String],
+    manifest_name: &str,
+) -> Vec<PathBuf> {
     let mut paths = Vec::new();
     for pattern in patterns {
         let full_pattern = root_dir.join(pattern).to_string_lossy().into_owned();
@@ -884,6 +1770,50 @@ dependencies {
         assert_eq!(version_count.len(), 2);
     }
 
+    #[test]
+    fn read_version_file_per_ecosystem() {
+        let dir = tempfile::tempdir().unwrap();
+        let cases = [
+            (
+                "Cargo.toml",
+                "[workspace.package]\nversion = \"0.3.0\"\n",
+                Some("0.3.0"),
+            ),
+            (
+                "package.json",
+                r#"{"name": "x", "version": "1.2.3"}"#,
+                Some("1.2.3"),
+            ),
+            (
+                "pyproject.toml",
+                "[tool.poetry]\nversion = \"2.0.0\"\n",
+                Some("2.0.0"),
+            ),
+            (
+                "pom.xml",
+                "<project><parent><version>5.0.0</version></parent><version>1.0.0</version></project>",
+                Some("1.0.0"),
+            ),
+            ("build.gradle", "version = '4.5.6'\n", Some("4.5.6")),
+            (
+                "version.go",
+                "package main\n\nconst Version = \"0.9.1\"\n",
+                Some("0.9.1"),
+            ),
+            ("package.json", r#"{"name": "x"}"#, None),
+        ];
+        for (name, contents, expected) in cases {
+            let path = dir.path().join(name);
+            fs::write(&path, contents).unwrap();
+            assert_eq!(
+                read_version_file(&path).unwrap().as_deref(),
+                expected,
+                "{name}"
+            );
+        }
+        assert!(read_version_file(&dir.path().join("README.md")).is_err());
+    }
+
     #[test]
     fn bump_cargo_toml_workspace_dependencies_with_path() {
         let dir = tempfile::tempdir().unwrap();
@@ -981,6 +1911,396 @@ func main() {}
         assert!(contents.contains(r#"const Version string = "0.6.0""#));
     }
 
+    // --- packaging manifest tests ---
+
+    const FORMULA: &str = r#"class Myapp < Formula
+  desc "My app"
+  homepage "https://example.com"
+  url "https://example.com/myapp-1.0.0.tar.gz"
+  version "1.0.0"
+  sha256 "abc123"
+
+  resource "helper" do
+    url "https://example.com/helper-9.9.9.tar.gz"
+    version "9.9.9"
+  end
+end
+"#;
+
+    #[test]
+    fn bump_homebrew_formula_version_and_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("myapp.rb");
+        fs::write(&path, FORMULA).unwrap();
+
+        bump_version_file(&path, "1.1.0").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("  version \"1.1.0\"\n"));
+        assert!(contents.contains("url \"https://example.com/myapp-1.0.0.tar.gz\""));
+        assert!(contents.contains("    version \"9.9.9\""));
+
+        bump_version_file_with_url(&path, "1.2.0", "https://example.com/myapp-1.2.0.tar.gz")
+            .unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("  url \"https://example.com/myapp-1.2.0.tar.gz\"\n"));
+        assert!(contents.contains("  version \"1.2.0\"\n"));
+        // Checksums and resources are left alone.
+        assert!(contents.contains("  sha256 \"abc123\"\n"));
+        assert!(contents.contains("    url \"https://example.com/helper-9.9.9.tar.gz\""));
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("1.2.0"));
+    }
+
+    #[test]
+    fn bump_homebrew_formula_without_version_stanza() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("myapp.rb");
+        let formula = FORMULA.replace("  version \"1.0.0\"\n", "");
+        fs::write(&path, &formula).unwrap();
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("1.0.0"));
+
+        // The version comes from the URL, so only the URL changes.
+        bump_version_file_with_url(&path, "2.0.0", "https://example.com/myapp-2.0.0.tar.gz")
+            .unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            formula.replace("myapp-1.0.0.tar.gz", "myapp-2.0.0.tar.gz")
+        );
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("2.0.0"));
+
+        let err = bump_version_file(&path, "3.0.0").unwrap_err();
+        assert!(err.to_string().contains("no version stanza"), "{err}");
+    }
+
+    #[test]
+    fn bump_scoop_manifest_version_and_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("myapp.json");
+        let manifest = r#"{
+    "version": "1.0.0",
+    "url": "https://example.com/v1.0.0/myapp.zip",
+    "hash": "abc123",
+    "autoupdate": {
+        "url": "https://example.com/v$version/myapp.zip"
+    }
+}
+"#;
+        fs::write(&path, manifest).unwrap();
+
+        bump_version_file_with_url(&path, "1.1.0", "https://example.com/v1.1.0/myapp.zip").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            manifest
+                .replace("\"1.0.0\"", "\"1.1.0\"")
+                .replace("v1.0.0/", "v1.1.0/")
+        );
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("1.1.0"));
+
+        bump_version_file(&path, "1.2.0").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("\"version\": \"1.2.0\""));
+        assert!(contents.contains("v1.1.0/myapp.zip"));
+    }
+
+    #[test]
+    fn json_and_ruby_files_need_packaging_markers() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let settings = dir.path().join("settings.json");
+        let json = "{\n  \"name\": \"x\",\n  \"version\": \"1.0.0\"\n}\n";
+        fs::write(&settings, json).unwrap();
+        let err = bump_version_file(&settings, "2.0.0").unwrap_err();
+        assert!(err.to_string().contains("not a Scoop manifest"), "{err}");
+        assert!(read_version_file(&settings).is_err());
+        assert_eq!(fs::read_to_string(&settings).unwrap(), json);
+
+        let per_arch = dir.path().join("myapp.json");
+        fs::write(
+            &per_arch,
+            r#"{"version": "1.0.0", "architecture": {"64bit": {"url": "https://x/1.0.0.zip"}}}"#,
+        )
+        .unwrap();
+        bump_version_file(&per_arch, "2.0.0").unwrap();
+        assert_eq!(
+            read_version_file(&per_arch).unwrap().as_deref(),
+            Some("2.0.0")
+        );
+
+        let script = dir.path().join("release.rb");
+        let ruby = "class Release\n  version \"1.0.0\"\nend\n";
+        fs::write(&script, ruby).unwrap();
+        let err = bump_version_file(&script, "2.0.0").unwrap_err();
+        assert!(err.to_string().contains("not a Homebrew formula"), "{err}");
+        assert_eq!(fs::read_to_string(&script).unwrap(), ruby);
+    }
+
+    #[test]
+    fn url_rewriting_is_limited_to_packaging_manifests() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Cargo.toml");
+        fs::write(&path, "[package]\nname = \"x\"\nversion = \"1.0.0\"\n").unwrap();
+        let err = bump_version_file_with_url(&path, "1.1.0", "https://x").unwrap_err();
+        assert!(err.to_string().contains("no download URL"), "{err}");
+    }
+
+    #[test]
+    fn bump_openapi_yaml_info_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("openapi.yaml");
+        let spec = r#"# Public API
+openapi: 3.1.0
+info:
+  title: Widgets
+  contact:
+    version: keep-me
+  version: "1.0.0" # bumped by sr
+servers:
+  - url: https://api.example.com
+components:
+  schemas:
+    Widget:
+      properties:
+        version:
+          type: string
+"#;
+        fs::write(&path, spec).unwrap();
+
+        bump_version_file(&path, "1.1.0").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            spec.replace("\"1.0.0\" # bumped", "\"1.1.0\" # bumped")
+        );
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("1.1.0"));
+    }
+
+    #[test]
+    fn bump_swagger_json_info_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("swagger.json");
+        let spec = r#"{
+  "swagger": "2.0",
+  "x-generator": { "version": "1.0.0" },
+  "info": {
+    "title": "Widgets",
+    "version": "1.0.0"
+  }
+}
+"#;
+        fs::write(&path, spec).unwrap();
+
+        bump_version_file(&path, "2.0.0").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains(r#""x-generator": { "version": "1.0.0" }"#));
+        assert!(contents.contains(r#""version": "2.0.0""#));
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("2.0.0"));
+    }
+
+    #[test]
+    fn openapi_without_info_version_names_the_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("openapi.yml");
+        fs::write(&path, "openapi: 3.0.0\ninfo:\n  title: Widgets\n").unwrap();
+
+        let err = bump_version_file(&path, "1.0.0").unwrap_err();
+        assert!(matches!(err, ReleaseError::VersionBump(_)));
+        assert!(err.to_string().contains("no /info/version in"), "{err}");
+        assert_eq!(read_version_file(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn bump_deno_jsonc_keeps_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deno.jsonc");
+        let manifest = r#"{
+  // Published to JSR
+  "name": "@acme/app", // scope matches the org
+  /* bumped by sr,
+     do not edit by hand */
+  "version": /* current */ "1.0.0", // release line
+  "imports": {
+    "version": "jsr:@std/semver@^1", // an import alias, not ours
+  },
+  "tasks": { "url": "https://example.com/*not-a-comment*/" },
+}
+"#;
+        fs::write(&path, manifest).unwrap();
+
+        bump_version_file(&path, "1.1.0").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            manifest.replace(r#""1.0.0", // release"#, r#""1.1.0", // release"#)
+        );
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("1.1.0"));
+    }
+
+    #[test]
+    fn bump_deno_json_without_version_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deno.json");
+        fs::write(&path, r#"{ "imports": { "version": "1.0.0" } }"#).unwrap();
+
+        let err = bump_version_file(&path, "1.1.0").unwrap_err();
+        assert!(
+            err.to_string().contains("no top-level \"version\""),
+            "{err}"
+        );
+        assert_eq!(read_version_file(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn detect_deno_and_bun_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("deno.jsonc"), r#"{ "version": "1.0.0" }"#).unwrap();
+        assert_eq!(detect_version_files(dir.path()), vec!["deno.jsonc"]);
+
+        fs::write(dir.path().join("deno.lock"), "{}").unwrap();
+        fs::write(dir.path().join("package.json"), r#"{"version":"1.0.0"}"#).unwrap();
+        fs::write(dir.path().join("bun.lock"), "{}").unwrap();
+        let bumped = [
+            dir.path().join("deno.jsonc").to_string_lossy().into_owned(),
+            dir.path()
+                .join("package.json")
+                .to_string_lossy()
+                .into_owned(),
+        ];
+        let locks = discover_lock_files(&bumped);
+        assert!(locks.contains(&dir.path().join("deno.lock")));
+        assert!(locks.contains(&dir.path().join("bun.lock")));
+    }
+
+    #[test]
+    fn bump_conda_recipe_jinja_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("meta.yaml");
+        let recipe = r#"{% set name = "acme" %}
+{% set version = "1.0.0" %}
+
+package:
+  name: {{ name|lower }}
+  version: {{ version }}
+
+source:
+  url: https://pypi.io/packages/source/a/acme/acme-{{ version }}.tar.gz
+
+requirements:
+  run:
+    - python >=3.9
+    - numpy ==1.0.0
+"#;
+        fs::write(&path, recipe).unwrap();
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("1.0.0"));
+
+        bump_version_file(&path, "1.1.0").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            recipe.replace(r#"set version = "1.0.0""#, r#"set version = "1.1.0""#)
+        );
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("1.1.0"));
+    }
+
+    #[test]
+    fn bump_conda_recipe_literal_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("meta.yaml");
+        let recipe = r#"package:
+  name: acme
+  version: "1.0.0"  # keep in sync
+
+requirements:
+  host:
+    - version: 1.0.0
+  run:
+    - acme-core 1.0.0
+"#;
+        fs::write(&path, recipe).unwrap();
+
+        bump_version_file(&path, "2.0.0").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            recipe.replace(r#""1.0.0"  # keep"#, r#""2.0.0"  # keep"#)
+        );
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("2.0.0"));
+    }
+
+    #[test]
+    fn bump_conda_recipe_templated_without_set_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("meta.yaml");
+        fs::write(&path, "package:\n  name: acme\n  version: {{ ver }}\n").unwrap();
+
+        let err = bump_version_file(&path, "1.1.0").unwrap_err();
+        assert!(err.to_string().contains("is templated"), "{err}");
+        assert_eq!(read_version_file(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn bump_nuspec_metadata_version_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Acme.Core.nuspec");
+        let nuspec = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://schemas.microsoft.com/packaging/2013/05/nuspec.xsd">
+  <metadata minClientVersion="3.3.0">
+    <id>Acme.Core</id>
+    <version>1.0.0</version>
+    <dependencies>
+      <dependency id="Newtonsoft.Json" version="1.0.0" />
+    </dependencies>
+  </metadata>
+  <files>
+    <file src="bin/Release/Acme.Core.dll" target="lib/net48" />
+  </files>
+</package>
+"#;
+        fs::write(&path, nuspec).unwrap();
+
+        bump_version_file(&path, "1.1.0").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            nuspec.replace("<version>1.0.0</version>", "<version>1.1.0</version>")
+        );
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("1.1.0"));
+    }
+
+    #[test]
+    fn bump_assembly_info_pads_numeric_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("AssemblyInfo.cs");
+        let source = r#"using System.Reflection;
+
+[assembly: AssemblyTitle("Acme.Core")]
+[assembly: AssemblyCompany("Acme 1.0.0 Ltd")]
+[assembly: AssemblyVersion("1.0.0.0")]
+[assembly: AssemblyFileVersion("1.0.0.0")]
+[assembly: AssemblyInformationalVersion("1.0.0")]
+"#;
+        fs::write(&path, source).unwrap();
+
+        bump_version_file(&path, "2.1.0-rc.1").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            source
+                .replace("Version(\"1.0.0.0\")", "Version(\"2.1.0.0\")")
+                .replace(
+                    "InformationalVersion(\"1.0.0\")",
+                    "InformationalVersion(\"2.1.0-rc.1\")"
+                )
+        );
+        assert_eq!(
+            read_version_file(&path).unwrap().as_deref(),
+            Some("2.1.0-rc.1")
+        );
+
+        fs::write(&path, "[assembly: AssemblyVersion(\"3.4.5.0\")]\n").unwrap();
+        assert_eq!(read_version_file(&path).unwrap().as_deref(), Some("3.4.5"));
+    }
+
     // --- workspace auto-discovery tests ---
 
     #[test]
@@ -1269,6 +2589,13 @@ version = "1.0.0"
         assert!(is_supported_version_file("build.gradle"));
         assert!(is_supported_version_file("build.gradle.kts"));
         assert!(is_supported_version_file("version.go"));
+        assert!(is_supported_version_file("myapp.rb"));
+        assert!(is_supported_version_file("myapp.json"));
+        assert!(is_supported_version_file("openapi.yaml"));
+        assert!(is_supported_version_file("Acme.nuspec"));
+        assert!(is_supported_version_file("AssemblyInfo.cs"));
+        assert!(is_supported_version_file("swagger.json"));
+        assert!(!is_supported_version_file("openapi.txt"));
         assert!(!is_supported_version_file("unknown.txt"));
     }
 }
@@ -0,0 +1,366 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::Duration;
+
+use sr_core::commit::Commit;
+use sr_core::config::{GitConfig, SigningConfig, TagsConfig};
+use sr_core::error::ReleaseError;
+use sr_core::git::{GitRepository, TagInfo};
+
+#[cfg(feature = "gix")]
+use crate::GixRepository;
+use crate::NativeGitRepository;
+
+/// Which `GitRepository` implementation to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitBackend {
+    /// Shell out to the `git` binary.
+    Native,
+    /// Pure-Rust implementation built on `gix`.
+    #[cfg(feature = "gix")]
+    Gix,
+}
+
+impl GitBackend {
+    /// Pick `Native` when a `git` binary is on `PATH`, otherwise `Gix` (when compiled in).
+    ///
+    /// `Gix` still needs the `git` binary to push and fetch, so a release that
+    /// pushes fails its pre-flight checks on it (see
+    /// [`GitRepository::check_remote_access`]); planning and local-only
+    /// releases work without `git`.
+    pub fn detect() -> Self {
+        #[cfg(feature = "gix")]
+        if !git_binary_available() {
+            return GitBackend::Gix;
+        }
+        GitBackend::Native
+    }
+}
+
+impl FromStr for GitBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "native" | "git" => Ok(GitBackend::Native),
+            #[cfg(feature = "gix")]
+            "gix" => Ok(GitBackend::Gix),
+            other => Err(format!(
+                "unknown git backend: {other} (expected native or gix)"
+            )),
+        }
+    }
+}
+
+/// Whether a runnable `git` binary is on `PATH`.
+pub fn git_binary_available() -> bool {
+    Command::new("git")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// `git --version` output (e.g. `git version 2.43.0`), or `None` when no runnable
+/// `git` binary is on `PATH`.
+pub fn git_version() -> Option<String> {
+    let output = Command::new("git")
+        .arg("--version")
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// A repository opened with a runtime-selected [`GitBackend`].
+pub enum AnyGitRepository {
+    Native(Box<NativeGitRepository>),
+    #[cfg(feature = "gix")]
+    Gix(Box<GixRepository>),
+}
+
+/// Forward a call to whichever backend is active.
+macro_rules! dispatch {
+    ($self:expr, $repo:ident => $call:expr) => {
+        match $self {
+            AnyGitRepository::Native($repo) => $call,
+            #[cfg(feature = "gix")]
+            AnyGitRepository::Gix($repo) => $call,
+        }
+    };
+}
+
+impl AnyGitRepository {
+    /// Open the repository at `path`. `None` auto-detects via [`GitBackend::detect`].
+    pub fn open(path: &Path, backend: Option<GitBackend>) -> Result<Self, ReleaseError> {
+        match backend.unwrap_or_else(GitBackend::detect) {
+            GitBackend::Native => {
+                NativeGitRepository::open(path).map(|r| Self::Native(Box::new(r)))
+            }
+            #[cfg(feature = "gix")]
+            GitBackend::Gix => GixRepository::open(path).map(|r| Self::Gix(Box::new(r))),
+        }
+    }
+
+    /// Enable HTTP Basic auth for remote operations targeting the given hostname.
+    pub fn with_http_auth(self, hostname: String, token: String) -> Self {
+        match self {
+            Self::Native(repo) => Self::Native(Box::new(repo.with_http_auth(hostname, token))),
+            #[cfg(feature = "gix")]
+            Self::Gix(repo) => Self::Gix(Box::new(repo.with_http_auth(hostname, token))),
+        }
+    }
+
+    /// Sign tags and/or commits created by this repository.
+    pub fn with_signing(self, signing: SigningConfig) -> Self {
+        match self {
+            Self::Native(repo) => Self::Native(Box::new(repo.with_signing(signing))),
+            #[cfg(feature = "gix")]
+            Self::Gix(repo) => Self::Gix(Box::new(repo.with_signing(signing))),
+        }
+    }
+
+    /// Create annotated or lightweight tags. Signed tags are always annotated.
+    pub fn with_tags(self, tags: TagsConfig) -> Self {
+        match self {
+            Self::Native(repo) => Self::Native(Box::new(repo.with_tags(tags))),
+            #[cfg(feature = "gix")]
+            Self::Gix(repo) => Self::Gix(Box::new(repo.with_tags(tags))),
+        }
+    }
+
+    /// Commit and tag as the given identity instead of git's configured user.
+    pub fn with_identity(self, identity: GitConfig) -> Self {
+        match self {
+            Self::Native(repo) => Self::Native(Box::new(repo.with_identity(identity))),
+            #[cfg(feature = "gix")]
+            Self::Gix(repo) => Self::Gix(Box::new(repo.with_identity(identity))),
+        }
+    }
+
+    /// Read at most `max` commits per history query; `0` means unlimited.
+    pub fn with_max_commits(self, max: usize) -> Self {
+        match self {
+            Self::Native(repo) => Self::Native(Box::new(repo.with_max_commits(max))),
+            #[cfg(feature = "gix")]
+            Self::Gix(repo) => Self::Gix(Box::new(repo.with_max_commits(max))),
+        }
+    }
+
+    /// Fill in `Commit::paths` for every commit read; history queries get slower.
+    pub fn with_changed_paths(self, changed_paths: bool) -> Self {
+        match self {
+            Self::Native(repo) => Self::Native(Box::new(repo.with_changed_paths(changed_paths))),
+            #[cfg(feature = "gix")]
+            Self::Gix(repo) => Self::Gix(Box::new(repo.with_changed_paths(changed_paths))),
+        }
+    }
+
+    /// Read two-component tags such as `v1.2` as `1.2.0` instead of ignoring them.
+    pub fn with_lenient_tags(self, lenient_tags: bool) -> Self {
+        match self {
+            Self::Native(repo) => Self::Native(Box::new(repo.with_lenient_tags(lenient_tags))),
+            #[cfg(feature = "gix")]
+            Self::Gix(repo) => Self::Gix(Box::new(repo.with_lenient_tags(lenient_tags))),
+        }
+    }
+
+    /// Kill any `git` command still running after `timeout`; `None` waits forever.
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        match self {
+            Self::Native(repo) => Self::Native(Box::new(repo.with_timeout(timeout))),
+            #[cfg(feature = "gix")]
+            Self::Gix(repo) => Self::Gix(Box::new(repo.with_timeout(timeout))),
+        }
+    }
+
+    /// Parse (hostname, owner, repo) from the `origin` remote URL.
+    pub fn parse_remote_full(&self) -> Result<(String, String, String), ReleaseError> {
+        dispatch!(self, repo => repo.parse_remote_full())
+    }
+}
+
+impl GitRepository for AnyGitRepository {
+    fn latest_tag(&self, prefixes: &[&str]) -> Result<Option<TagInfo>, ReleaseError> {
+        dispatch!(self, repo => repo.latest_tag(prefixes))
+    }
+
+    fn commits_since(&self, from: Option<&str>) -> Result<Vec<Commit>, ReleaseError> {
+        dispatch!(self, repo => repo.commits_since(from))
+    }
+
+    fn create_tag(&self, name: &str, message: &str, sign: bool) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.create_tag(name, message, sign))
+    }
+
+    fn push_tag(&self, name: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.push_tag(name))
+    }
+
+    fn stage_and_commit(&self, paths: &[&str], message: &str) -> Result<bool, ReleaseError> {
+        dispatch!(self, repo => repo.stage_and_commit(paths, message))
+    }
+
+    fn stage_and_commit_with_trailers(
+        &self,
+        paths: &[&str],
+        message: &str,
+        trailers: &[(String, String)],
+    ) -> Result<bool, ReleaseError> {
+        dispatch!(self, repo => repo.stage_and_commit_with_trailers(paths, message, trailers))
+    }
+
+    fn push(&self) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.push())
+    }
+
+    fn push_to_branch(&self, branch: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.push_to_branch(branch))
+    }
+
+    fn tag_exists(&self, name: &str) -> Result<bool, ReleaseError> {
+        dispatch!(self, repo => repo.tag_exists(name))
+    }
+
+    fn remote_tag_exists(&self, name: &str) -> Result<bool, ReleaseError> {
+        dispatch!(self, repo => repo.remote_tag_exists(name))
+    }
+
+    fn all_tags(&self, prefixes: &[&str]) -> Result<Vec<TagInfo>, ReleaseError> {
+        dispatch!(self, repo => repo.all_tags(prefixes))
+    }
+
+    fn tags_reachable(&self, prefixes: &[&str], rev: &str) -> Result<Vec<TagInfo>, ReleaseError> {
+        dispatch!(self, repo => repo.tags_reachable(prefixes, rev))
+    }
+
+    fn latest_tag_reachable(
+        &self,
+        prefixes: &[&str],
+        rev: &str,
+    ) -> Result<Option<TagInfo>, ReleaseError> {
+        dispatch!(self, repo => repo.latest_tag_reachable(prefixes, rev))
+    }
+
+    fn resolve_rev(&self, rev: &str) -> Result<String, ReleaseError> {
+        dispatch!(self, repo => repo.resolve_rev(rev))
+    }
+
+    fn commits_between(&self, from: Option<&str>, to: &str) -> Result<Vec<Commit>, ReleaseError> {
+        dispatch!(self, repo => repo.commits_between(from, to))
+    }
+
+    fn tag_timestamp(&self, tag_name: &str) -> Result<i64, ReleaseError> {
+        dispatch!(self, repo => repo.tag_timestamp(tag_name))
+    }
+
+    fn tag_message(&self, name: &str) -> Result<Option<String>, ReleaseError> {
+        dispatch!(self, repo => repo.tag_message(name))
+    }
+
+    fn commit_date(&self, sha: &str) -> Result<i64, ReleaseError> {
+        dispatch!(self, repo => repo.commit_date(sha))
+    }
+
+    fn force_create_tag(&self, name: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.force_create_tag(name))
+    }
+
+    fn force_create_tag_at(&self, name: &str, target: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.force_create_tag_at(name, target))
+    }
+
+    fn delete_tag(&self, name: &str) -> Result<bool, ReleaseError> {
+        dispatch!(self, repo => repo.delete_tag(name))
+    }
+
+    fn delete_remote_tag(&self, name: &str) -> Result<bool, ReleaseError> {
+        dispatch!(self, repo => repo.delete_remote_tag(name))
+    }
+
+    fn revert_commit(&self, sha: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.revert_commit(sha))
+    }
+
+    fn remote_ref(&self, refname: &str) -> Result<Option<(String, String)>, ReleaseError> {
+        dispatch!(self, repo => repo.remote_ref(refname))
+    }
+
+    fn create_remote_ref(
+        &self,
+        refname: &str,
+        message: &str,
+    ) -> Result<Option<String>, ReleaseError> {
+        dispatch!(self, repo => repo.create_remote_ref(refname, message))
+    }
+
+    fn delete_remote_ref(&self, refname: &str, expected_sha: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.delete_remote_ref(refname, expected_sha))
+    }
+
+    fn force_push_tag(&self, name: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.force_push_tag(name))
+    }
+
+    fn add_note(&self, notes_ref: &str, sha: &str, content: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.add_note(notes_ref, sha, content))
+    }
+
+    fn push_notes(&self, notes_ref: &str) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.push_notes(notes_ref))
+    }
+
+    fn head_sha(&self) -> Result<String, ReleaseError> {
+        dispatch!(self, repo => repo.head_sha())
+    }
+
+    fn dirty_paths(&self) -> Result<Vec<String>, ReleaseError> {
+        dispatch!(self, repo => repo.dirty_paths())
+    }
+
+    fn workdir(&self) -> Result<PathBuf, ReleaseError> {
+        dispatch!(self, repo => repo.workdir())
+    }
+
+    fn current_branch(&self) -> Result<Option<String>, ReleaseError> {
+        dispatch!(self, repo => repo.current_branch())
+    }
+
+    fn default_branch(&self) -> Result<Option<String>, ReleaseError> {
+        dispatch!(self, repo => repo.default_branch())
+    }
+
+    fn is_shallow(&self) -> Result<bool, ReleaseError> {
+        dispatch!(self, repo => repo.is_shallow())
+    }
+
+    fn fetch_tags(&self) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.fetch_tags())
+    }
+
+    fn check_remote_access(&self) -> Result<(), ReleaseError> {
+        dispatch!(self, repo => repo.check_remote_access())
+    }
+
+    fn commits_since_paths(
+        &self,
+        from: Option<&str>,
+        paths: &[&str],
+    ) -> Result<Vec<Commit>, ReleaseError> {
+        dispatch!(self, repo => repo.commits_since_paths(from, paths))
+    }
+
+    fn commits_between_paths(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        paths: &[&str],
+    ) -> Result<Vec<Commit>, ReleaseError> {
+        dispatch!(self, repo => repo.commits_between_paths(from, to, paths))
+    }
+}
@@ -0,0 +1,930 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use gix::bstr::{BString, ByteSlice};
+use gix::refs::transaction::PreviousValue;
+use sr_core::commit::Commit;
+use sr_core::config::{GitConfig, SigningConfig, TagsConfig};
+use sr_core::error::ReleaseError;
+use sr_core::git::{GitRepository, TagInfo, parse_prefixed_tag, sort_tags};
+use tracing::warn;
+
+use crate::{NativeGitRepository, git_binary_available, missing_identity_error, parse_remote_url};
+
+const NO_GIT_FOR_REMOTE: &str = "the gix backend pushes, fetches and signs through the `git` \
+     binary, which is not on PATH; install git, or release with --no-push";
+
+/// Git repository implementation backed by the pure-Rust `gix` crate.
+///
+/// Local operations (tag listing, history walks, tag and commit creation) run
+/// without a `git` binary. `gix` cannot push yet, so remote operations delegate
+/// to the `git` CLI with the same scoped-token auth. Without the binary,
+/// [`GitRepository::check_remote_access`] fails so a pushing release stops
+/// before it tags anything. Signed tags are delegated the same way.
+pub struct GixRepository {
+    repo: gix::ThreadSafeRepository,
+    path: PathBuf,
+    http_auth: Option<(String, String)>, // (hostname, token)
+    signing: SigningConfig,
+    tags: TagsConfig,
+    identity: GitConfig,
+    max_commits: usize,
+    changed_paths: bool,
+    lenient_tags: bool,
+    timeout: Option<Duration>,
+}
+
+fn gix_err(context: &str, e: impl Into<sr_core::error::BoxError>) -> ReleaseError {
+    ReleaseError::GitLibrary {
+        context: context.to_string(),
+        source: e.into(),
+    }
+}
+
+impl GixRepository {
+    pub fn open(path: &Path) -> Result<Self, ReleaseError> {
+        let repo = gix::ThreadSafeRepository::discover(path)
+            .map_err(|e| gix_err("not a git repository", e))?;
+        Ok(Self {
+            repo,
+            path: path.to_path_buf(),
+            http_auth: None,
+            signing: SigningConfig::default(),
+            tags: TagsConfig::default(),
+            identity: GitConfig::default(),
+            max_commits: 0,
+            changed_paths: false,
+            lenient_tags: false,
+            timeout: None,
+        })
+    }
+
+    /// Enable HTTP Basic auth for remote operations targeting the given hostname.
+    ///
+    /// Remote operations run through the `git` CLI, so this has the same
+    /// scoping as [`NativeGitRepository::with_http_auth`].
+    pub fn with_http_auth(mut self, hostname: String, token: String) -> Self {
+        self.http_auth = Some((hostname, token));
+        self
+    }
+
+    /// Sign tags and/or commits. Signing is delegated to the `git` CLI.
+    pub fn with_signing(mut self, signing: SigningConfig) -> Self {
+        self.signing = signing;
+        self
+    }
+
+    /// Create annotated or lightweight tags. Signed tags are always annotated.
+    pub fn with_tags(mut self, tags: TagsConfig) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Commit and tag as the given identity instead of git's configured user.
+    pub fn with_identity(mut self, identity: GitConfig) -> Self {
+        self.identity = identity;
+        self
+    }
+
+    /// Read at most `max` commits per history walk, newest first, warning
+    /// when more exist. `0` means unlimited.
+    pub fn with_max_commits(mut self, max: usize) -> Self {
+        self.max_commits = max;
+        self
+    }
+
+    /// Fill in [`Commit::paths`] for every commit read. Each commit's tree is
+    /// diffed against its first parent's, so history walks get slower.
+    ///
+    /// [`Commit::paths`]: sr_core::commit::Commit::paths
+    pub fn with_changed_paths(mut self, changed_paths: bool) -> Self {
+        self.changed_paths = changed_paths;
+        self
+    }
+
+    /// Read two-component tags such as `v1.2` as `1.2.0` instead of ignoring them.
+    pub fn with_lenient_tags(mut self, lenient_tags: bool) -> Self {
+        self.lenient_tags = lenient_tags;
+        self
+    }
+
+    /// Kill any `git` CLI command (push, fetch, signing) still running after
+    /// `timeout`. `gix` operations themselves are local and not limited.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Thread-local handle with the configured identity applied as in-memory
+    /// config overrides (the repository's config files are not modified).
+    fn local(&self) -> gix::Repository {
+        let mut repo = self.repo.to_thread_local();
+        let overrides: Vec<String> = [
+            self.identity
+                .user_name
+                .as_ref()
+                .map(|name| format!("user.name={name}")),
+            self.identity
+                .user_email
+                .as_ref()
+                .map(|email| format!("user.email={email}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !overrides.is_empty() {
+            let mut config = repo.config_snapshot_mut();
+            // Values come from our own config; an invalid override only loses the identity
+            let _ = config.append_config(
+                overrides.iter().map(String::as_str),
+                gix::config::Source::Api,
+            );
+            let _ = config.commit();
+        }
+        repo
+    }
+
+    /// Fail early with a pointer to the config options when no identity is available.
+    fn require_identity(repo: &gix::Repository) -> Result<(), ReleaseError> {
+        if repo.committer().is_none() || repo.author().is_none() {
+            return Err(missing_identity_error());
+        }
+        Ok(())
+    }
+
+    /// Native `git` CLI used for operations `gix` does not support (push, signing).
+    fn cli(&self) -> Result<NativeGitRepository, ReleaseError> {
+        if !git_binary_available() {
+            return Err(ReleaseError::Git(NO_GIT_FOR_REMOTE.into()));
+        }
+        let native = NativeGitRepository::open(&self.path).map_err(|e| {
+            ReleaseError::Git(format!(
+                "the gix backend needs the `git` binary for remote operations and signing: {e}"
+            ))
+        })?;
+        let native = native
+            .with_signing(self.signing.clone())
+            .with_tags(self.tags.clone())
+            .with_identity(self.identity.clone())
+            .with_timeout(self.timeout);
+        Ok(match &self.http_auth {
+            Some((hostname, token)) => native.with_http_auth(hostname.clone(), token.clone()),
+            None => native,
+        })
+    }
+
+    /// Parse (hostname, owner, repo) from the `origin` remote URL.
+    pub fn parse_remote_full(&self) -> Result<(String, String, String), ReleaseError> {
+        let repo = self.local();
+        let remote = repo
+            .find_remote("origin")
+            .map_err(|e| gix_err("failed to find remote origin", e))?;
+        let url = remote
+            .url(gix::remote::Direction::Fetch)
+            .ok_or_else(|| ReleaseError::Git("remote origin has no URL".into()))?;
+        parse_remote_url(&url.to_bstring().to_string())
+    }
+
+    /// Resolve a revision (tag, branch, SHA) to the commit it points to.
+    fn resolve_commit(repo: &gix::Repository, spec: &str) -> Result<gix::ObjectId, ReleaseError> {
+        let id = repo
+            .rev_parse_single(format!("{spec}^{{commit}}").as_str())
+            .map_err(|e| gix_err(&format!("failed to resolve {spec}"), e))?;
+        Ok(id.detach())
+    }
+
+    /// Walk commits reachable from `to` but not from `from`, newest first,
+    /// keeping only commits that touched `paths` (all commits when empty).
+    /// Stops after `max_commits` commits. Each commit's changed files are read
+    /// when `changed_paths` is set.
+    fn walk(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        paths: &[&str],
+    ) -> Result<Vec<Commit>, ReleaseError> {
+        use gix::revision::walk::Sorting;
+        use gix::traverse::commit::simple::CommitTimeOrder;
+
+        let repo = self.local();
+        let tip = Self::resolve_commit(&repo, to)?;
+        let mut platform = repo
+            .rev_walk([tip])
+            .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst));
+        if let Some(from) = from {
+            platform = platform.with_hidden([Self::resolve_commit(&repo, from)?]);
+        }
+        let pathspecs = (!paths.is_empty()).then(|| Pathspecs::parse(paths));
+
+        let mut commits = Vec::new();
+        let walk = platform
+            .all()
+            .map_err(|e| gix_err("failed to walk history", e))?;
+        for info in walk {
+            let info = info.map_err(|e| gix_err("failed to walk history", e))?;
+            let commit = info
+                .object()
+                .map_err(|e| gix_err("failed to read commit", e))?;
+            if let Some(ref pathspecs) = pathspecs
+                && !touches_paths(&repo, &commit, pathspecs)?
+            {
+                continue;
+            }
+            if self.max_commits > 0 && commits.len() == self.max_commits {
+                let range = match from {
+                    Some(from) => format!("{from}..{to}"),
+                    None => to.to_string(),
+                };
+                crate::warn_truncated(&range, self.max_commits);
+                break;
+            }
+            let paths = if self.changed_paths {
+                first_parent_changes(&repo, &commit)?
+            } else {
+                Vec::new()
+            };
+            commits.push(Commit {
+                sha: info.id().to_string(),
+                author: commit
+                    .author()
+                    .map(|author| author.email.to_string())
+                    .unwrap_or_default(),
+                message: commit
+                    .message_raw_sloppy()
+                    .to_str_lossy()
+                    .trim()
+                    .to_string(),
+                paths,
+            });
+        }
+        Ok(commits)
+    }
+
+    /// Convert a path (absolute or relative to the repo root) into a repo-relative path.
+    fn repo_relative(&self, repo: &gix::Repository, file: &str) -> Result<BString, ReleaseError> {
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| ReleaseError::Git("cannot commit in a bare repository".into()))?;
+        let path = Path::new(file);
+        let relative = if path.is_absolute() {
+            let workdir = workdir
+                .canonicalize()
+                .map_err(|e| gix_err("failed to resolve work tree", e))?;
+            let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            absolute
+                .strip_prefix(&workdir)
+                .map(Path::to_path_buf)
+                .map_err(|_| ReleaseError::Git(format!("{file} is outside the repository")))?
+        } else {
+            path.to_path_buf()
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        Ok(BString::from(relative.trim_start_matches("./")))
+    }
+}
+
+/// Parsed git pathspecs: plain entries include, `:(exclude)`/`:!`/`:^` entries exclude.
+/// Entries match a path exactly, as a directory prefix, or as a glob.
+struct Pathspecs {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl Pathspecs {
+    fn parse(paths: &[&str]) -> Self {
+        let normalize = |p: &str| p.trim_start_matches("./").trim_end_matches('/').to_string();
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+        for spec in paths {
+            match spec
+                .strip_prefix(":(exclude)")
+                .or_else(|| spec.strip_prefix(":!"))
+                .or_else(|| spec.strip_prefix(":^"))
+            {
+                Some(path) => exclude.push(normalize(path)),
+                None => include.push(normalize(spec)),
+            }
+        }
+        Self { include, exclude }
+    }
+
+    fn spec_matches(spec: &str, path: &str) -> bool {
+        spec.is_empty()
+            || spec == "."
+            || path == spec
+            || path
+                .strip_prefix(spec)
+                .is_some_and(|rest| rest.starts_with('/'))
+            || glob::Pattern::new(spec).is_ok_and(|p| p.matches(path))
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|spec| Self::spec_matches(spec, path));
+        included
+            && !self
+                .exclude
+                .iter()
+                .any(|spec| Self::spec_matches(spec, path))
+    }
+}
+
+/// Whether `commit` changed any path matching `pathspecs` compared to its parents.
+///
+/// Mirrors `git log -- <pathspecs>` closely enough for release planning: root
+/// commits are compared against an empty tree, merges count only when they
+/// differ from every parent.
+fn touches_paths(
+    repo: &gix::Repository,
+    commit: &gix::Commit<'_>,
+    pathspecs: &Pathspecs,
+) -> Result<bool, ReleaseError> {
+    let tree_of = |commit: &gix::Commit<'_>| -> Result<gix::ObjectId, ReleaseError> {
+        Ok(commit
+            .tree_id()
+            .map_err(|e| gix_err("failed to read commit tree", e))?
+            .detach())
+    };
+    let own = tree_of(commit)?;
+    let parents: Vec<gix::ObjectId> = commit.parent_ids().map(|id| id.detach()).collect();
+    if parents.is_empty() {
+        let mut changed = Vec::new();
+        changed_paths(repo, None, Some(own), "", &mut changed)?;
+        return Ok(changed.iter().any(|p| pathspecs.matches(p)));
+    }
+    for parent in parents {
+        let parent = repo
+            .find_commit(parent)
+            .map_err(|e| gix_err("failed to read parent commit", e))?;
+        let mut changed = Vec::new();
+        changed_paths(repo, Some(tree_of(&parent)?), Some(own), "", &mut changed)?;
+        if !changed.iter().any(|p| pathspecs.matches(p)) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Files `commit` changed against its first parent (every file for a root commit).
+fn first_parent_changes(
+    repo: &gix::Repository,
+    commit: &gix::Commit<'_>,
+) -> Result<Vec<String>, ReleaseError> {
+    let tree_of = |commit: &gix::Commit<'_>| -> Result<gix::ObjectId, ReleaseError> {
+        Ok(commit
+            .tree_id()
+            .map_err(|e| gix_err("failed to read commit tree", e))?
+            .detach())
+    };
+    let parent = match commit.parent_ids().next() {
+        Some(id) => Some(tree_of(
+            &repo
+                .find_commit(id)
+                .map_err(|e| gix_err("failed to read parent commit", e))?,
+        )?),
+        None => None,
+    };
+    let mut changed = Vec::new();
+    changed_paths(repo, parent, Some(tree_of(commit)?), "", &mut changed)?;
+    changed.sort();
+    Ok(changed)
+}
+
+/// Collect the paths of files that differ between two trees, descending only
+/// into subtrees whose ids differ. `None` stands for an empty tree.
+fn changed_paths(
+    repo: &gix::Repository,
+    old: Option<gix::ObjectId>,
+    new: Option<gix::ObjectId>,
+    prefix: &str,
+    out: &mut Vec<String>,
+) -> Result<(), ReleaseError> {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    if old == new {
+        return Ok(());
+    }
+    type Entries = BTreeMap<BString, (gix::objs::tree::EntryMode, gix::ObjectId)>;
+    let entries = |id: Option<gix::ObjectId>| -> Result<Entries, ReleaseError> {
+        let Some(id) = id else {
+            return Ok(Entries::new());
+        };
+        let tree = repo
+            .find_tree(id)
+            .map_err(|e| gix_err("failed to read tree", e))?;
+        let decoded = tree
+            .decode()
+            .map_err(|e| gix_err("failed to decode tree", e))?;
+        Ok(decoded
+            .entries
+            .iter()
+            .map(|e| (e.filename.to_owned(), (e.mode, e.oid.to_owned())))
+            .collect())
+    };
+    let old_entries = entries(old)?;
+    let new_entries = entries(new)?;
+    let names: BTreeSet<&BString> = old_entries.keys().chain(new_entries.keys()).collect();
+    for name in names {
+        let (a, b) = (old_entries.get(name), new_entries.get(name));
+        if a == b {
+            continue;
+        }
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}/{name}")
+        };
+        let subtree = |e: Option<&(gix::objs::tree::EntryMode, gix::ObjectId)>| {
+            e.filter(|(mode, _)| mode.is_tree()).map(|(_, id)| *id)
+        };
+        let (tree_a, tree_b) = (subtree(a), subtree(b));
+        if tree_a.is_some() || tree_b.is_some() {
+            changed_paths(repo, tree_a, tree_b, &path, out)?;
+        }
+        let is_file = |e: Option<&(gix::objs::tree::EntryMode, gix::ObjectId)>| {
+            e.is_some_and(|(mode, _)| !mode.is_tree())
+        };
+        if is_file(a) || is_file(b) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+impl GitRepository for GixRepository {
+    fn latest_tag(&self, prefixes: &[&str]) -> Result<Option<TagInfo>, ReleaseError> {
+        Ok(self.all_tags(prefixes)?.pop())
+    }
+
+    fn commits_since(&self, from: Option<&str>) -> Result<Vec<Commit>, ReleaseError> {
+        self.walk(from, "HEAD", &[])
+    }
+
+    fn create_tag(&self, name: &str, message: &str, sign: bool) -> Result<(), ReleaseError> {
+        if sign || self.signing.tags {
+            return self.cli()?.create_tag(name, message, true);
+        }
+        let repo = self.local();
+        let head = repo
+            .head_id()
+            .map_err(|e| gix_err("failed to resolve HEAD", e))?;
+        if !self.tags.annotated {
+            repo.tag_reference(name, head, PreviousValue::MustNotExist)
+                .map_err(|e| gix_err(&format!("failed to create tag {name}"), e))?;
+            return Ok(());
+        }
+        Self::require_identity(&repo)?;
+        let tagger = repo
+            .committer()
+            .transpose()
+            .map_err(|e| gix_err("invalid committer identity", e))?;
+        let message = if message.ends_with('\n') {
+            message.to_string()
+        } else {
+            format!("{message}\n")
+        };
+        repo.tag(
+            name,
+            head,
+            gix::objs::Kind::Commit,
+            tagger,
+            message,
+            PreviousValue::MustNotExist,
+        )
+        .map_err(|e| gix_err(&format!("failed to create tag {name}"), e))?;
+        Ok(())
+    }
+
+    fn push_tag(&self, name: &str) -> Result<(), ReleaseError> {
+        self.cli()?.push_tag(name)
+    }
+
+    fn stage_and_commit(&self, paths: &[&str], message: &str) -> Result<bool, ReleaseError> {
+        use gix::index::entry::{Flags, Mode, Stage, Stat};
+        use gix::objs::tree::EntryKind;
+
+        if self.signing.commits {
+            return self.cli()?.stage_and_commit(paths, message);
+        }
+        let repo = self.local();
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| ReleaseError::Git("cannot commit in a bare repository".into()))?
+            .to_path_buf();
+        let head = repo
+            .head_commit()
+            .map_err(|e| gix_err("failed to resolve HEAD", e))?;
+        let head_tree = head
+            .tree_id()
+            .map_err(|e| gix_err("failed to read HEAD tree", e))?
+            .detach();
+
+        let mut editor = repo
+            .edit_tree(head_tree)
+            .map_err(|e| gix_err("failed to edit tree", e))?;
+        // (repo-relative path, blob id) for each file present on disk; None = deleted
+        let mut staged: Vec<(BString, Option<gix::ObjectId>)> = Vec::new();
+        for file in paths {
+            let rela = self.repo_relative(&repo, file)?;
+            let abs = workdir.join(rela.to_str_lossy().as_ref());
+            if abs.is_file() {
+                let data = std::fs::read(&abs).map_err(|e| gix_err(&format!("read {file}"), e))?;
+                let id = repo
+                    .write_blob(data)
+                    .map_err(|e| gix_err(&format!("write blob for {file}"), e))?
+                    .detach();
+                editor
+                    .upsert(rela.as_bstr(), EntryKind::Blob, id)
+                    .map_err(|e| gix_err(&format!("stage {file}"), e))?;
+                staged.push((rela, Some(id)));
+            } else {
+                editor
+                    .remove(rela.as_bstr())
+                    .map_err(|e| gix_err(&format!("stage removal of {file}"), e))?;
+                staged.push((rela, None));
+            }
+        }
+        let new_tree = editor
+            .write()
+            .map_err(|e| gix_err("failed to write tree", e))?
+            .detach();
+        if new_tree == head_tree {
+            return Ok(false);
+        }
+
+        Self::require_identity(&repo)?;
+        repo.commit("HEAD", message, new_tree, [head.id])
+            .map_err(|e| gix_err("failed to commit", e))?;
+
+        // Keep the index in sync so the work tree is clean after the commit.
+        let mut index = match repo.open_index() {
+            Ok(index) => index,
+            Err(_) => gix::index::File::from_state(
+                gix::index::State::new(repo.object_hash()),
+                repo.index_path(),
+            ),
+        };
+        let mut pushed = false;
+        for (rela, id) in &staged {
+            match id {
+                Some(id) => {
+                    let abs = workdir.join(rela.to_str_lossy().as_ref());
+                    let stat = gix::index::fs::Metadata::from_path_no_follow(&abs)
+                        .ok()
+                        .and_then(|m| Stat::from_fs(&m).ok())
+                        .unwrap_or_default();
+                    match index.entry_mut_by_path_and_stage(rela.as_bstr(), Stage::Unconflicted) {
+                        Some(entry) => {
+                            entry.id = *id;
+                            entry.stat = stat;
+                        }
+                        None => {
+                            index.dangerously_push_entry(
+                                stat,
+                                *id,
+                                Flags::empty(),
+                                Mode::FILE,
+                                rela.as_bstr(),
+                            );
+                            pushed = true;
+                        }
+                    }
+                }
+                None => index.remove_entries(|_, path, _| path == rela.as_bstr()),
+            }
+        }
+        if pushed {
+            index.sort_entries();
+        }
+        // The cached tree extension is stale after editing entries.
+        index.remove_tree();
+        index
+            .write(Default::default())
+            .map_err(|e| gix_err("failed to write index", e))?;
+
+        Ok(true)
+    }
+
+    fn push(&self) -> Result<(), ReleaseError> {
+        self.cli()?.push()
+    }
+
+    fn push_to_branch(&self, branch: &str) -> Result<(), ReleaseError> {
+        self.cli()?.push_to_branch(branch)
+    }
+
+    fn tag_exists(&self, name: &str) -> Result<bool, ReleaseError> {
+        let repo = self.local();
+        let found = repo
+            .try_find_reference(format!("refs/tags/{name}").as_str())
+            .map_err(|e| gix_err(&format!("failed to look up tag {name}"), e))?;
+        Ok(found.is_some())
+    }
+
+    fn remote_tag_exists(&self, name: &str) -> Result<bool, ReleaseError> {
+        self.cli()?.remote_tag_exists(name)
+    }
+
+    fn all_tags(&self, prefixes: &[&str]) -> Result<Vec<TagInfo>, ReleaseError> {
+        let repo = self.local();
+        let references = repo
+            .references()
+            .map_err(|e| gix_err("failed to read references", e))?;
+        let iter = references
+            .tags()
+            .map_err(|e| gix_err("failed to list tags", e))?;
+
+        let mut tags = Vec::new();
+        for reference in iter {
+            let mut reference = reference.map_err(|e| gix_err("failed to read tag", e))?;
+            let tag_name = reference.name().shorten().to_string();
+            let Some((rank, version)) = parse_prefixed_tag(&tag_name, prefixes, self.lenient_tags)
+            else {
+                continue;
+            };
+            let Ok(sha) = reference.peel_to_id() else {
+                warn!("skipping tag {tag_name}: its commit cannot be read");
+                continue;
+            };
+            let sha = sha.to_string();
+            tags.push((
+                rank,
+                TagInfo {
+                    name: tag_name,
+                    version,
+                    sha,
+                },
+            ));
+        }
+
+        Ok(sort_tags(tags))
+    }
+
+    fn tags_reachable(&self, prefixes: &[&str], rev: &str) -> Result<Vec<TagInfo>, ReleaseError> {
+        let repo = self.local();
+        let tip = Self::resolve_commit(&repo, &self.resolve_rev(rev)?)?;
+        let mut reachable = std::collections::HashSet::new();
+        let walk = repo
+            .rev_walk([tip])
+            .all()
+            .map_err(|e| gix_err("failed to walk history", e))?;
+        for info in walk {
+            let info = info.map_err(|e| gix_err("failed to walk history", e))?;
+            reachable.insert(info.id().to_string());
+        }
+        let mut tags = self.all_tags(prefixes)?;
+        tags.retain(|t| reachable.contains(&t.sha));
+        Ok(tags)
+    }
+
+    fn resolve_rev(&self, rev: &str) -> Result<String, ReleaseError> {
+        Self::resolve_commit(&self.local(), rev)
+            .map(|id| id.to_string())
+            .map_err(|_| ReleaseError::Git(format!("unknown revision '{rev}': no such commit")))
+    }
+
+    fn commits_between(&self, from: Option<&str>, to: &str) -> Result<Vec<Commit>, ReleaseError> {
+        self.walk(from, to, &[])
+    }
+
+    fn tag_timestamp(&self, tag_name: &str) -> Result<i64, ReleaseError> {
+        self.commit_date(tag_name)
+    }
+
+    fn tag_message(&self, name: &str) -> Result<Option<String>, ReleaseError> {
+        let repo = self.local();
+        let reference = repo
+            .try_find_reference(format!("refs/tags/{name}").as_str())
+            .map_err(|e| gix_err(&format!("failed to look up tag {name}"), e))?
+            .ok_or_else(|| ReleaseError::Git(format!("tag {name} not found")))?;
+        let object = repo
+            .find_object(reference.id())
+            .map_err(|e| gix_err(&format!("failed to read tag {name}"), e))?;
+        let Ok(tag) = object.try_into_tag() else {
+            return Ok(None);
+        };
+        let tag = tag
+            .decode()
+            .map_err(|e| gix_err(&format!("failed to read tag {name}"), e))?;
+        Ok(Some(tag.message.to_string().trim().to_string()))
+    }
+
+    fn commit_date(&self, sha: &str) -> Result<i64, ReleaseError> {
+        let repo = self.local();
+        let id = Self::resolve_commit(&repo, sha)?;
+        let time = repo
+            .find_commit(id)
+            .map_err(|e| gix_err("failed to read commit", e))?
+            .time()
+            .map_err(|e| gix_err("failed to read commit time", e))?;
+        Ok(time.seconds)
+    }
+
+    fn force_create_tag(&self, name: &str) -> Result<(), ReleaseError> {
+        self.force_create_tag_at(name, "HEAD")
+    }
+
+    fn force_create_tag_at(&self, name: &str, target: &str) -> Result<(), ReleaseError> {
+        if self.signing.tags {
+            return self.cli()?.force_create_tag_at(name, target);
+        }
+        let repo = self.local();
+        let id = repo
+            .rev_parse_single(target)
+            .map_err(|e| gix_err(&format!("failed to resolve {target}"), e))?
+            .object()
+            .map_err(|e| gix_err(&format!("failed to resolve {target}"), e))?
+            .peel_to_commit()
+            .map_err(|e| gix_err(&format!("{target} is not a commit"), e))?
+            .id;
+        repo.tag_reference(name, id, PreviousValue::Any)
+            .map_err(|e| gix_err(&format!("failed to create tag {name}"), e))?;
+        Ok(())
+    }
+
+    fn delete_tag(&self, name: &str) -> Result<bool, ReleaseError> {
+        let repo = self.local();
+        let Some(reference) = repo
+            .try_find_reference(format!("refs/tags/{name}").as_str())
+            .map_err(|e| gix_err(&format!("failed to look up tag {name}"), e))?
+        else {
+            return Ok(false);
+        };
+        reference
+            .delete()
+            .map_err(|e| gix_err(&format!("failed to delete tag {name}"), e))?;
+        Ok(true)
+    }
+
+    fn delete_remote_tag(&self, name: &str) -> Result<bool, ReleaseError> {
+        self.cli()?.delete_remote_tag(name)
+    }
+
+    fn revert_commit(&self, sha: &str) -> Result<(), ReleaseError> {
+        self.cli()?.revert_commit(sha)
+    }
+
+    fn remote_ref(&self, refname: &str) -> Result<Option<(String, String)>, ReleaseError> {
+        self.cli()?.remote_ref(refname)
+    }
+
+    fn create_remote_ref(
+        &self,
+        refname: &str,
+        message: &str,
+    ) -> Result<Option<String>, ReleaseError> {
+        self.cli()?.create_remote_ref(refname, message)
+    }
+
+    fn delete_remote_ref(&self, refname: &str, expected_sha: &str) -> Result<(), ReleaseError> {
+        self.cli()?.delete_remote_ref(refname, expected_sha)
+    }
+
+    fn force_push_tag(&self, name: &str) -> Result<(), ReleaseError> {
+        self.cli()?.force_push_tag(name)
+    }
+
+    fn add_note(&self, notes_ref: &str, sha: &str, content: &str) -> Result<(), ReleaseError> {
+        self.cli()?.add_note(notes_ref, sha, content)
+    }
+
+    fn push_notes(&self, notes_ref: &str) -> Result<(), ReleaseError> {
+        self.cli()?.push_notes(notes_ref)
+    }
+
+    fn head_sha(&self) -> Result<String, ReleaseError> {
+        let repo = self.local();
+        let head = repo
+            .head_id()
+            .map_err(|e| gix_err("failed to resolve HEAD", e))?;
+        Ok(head.to_string())
+    }
+
+    fn dirty_paths(&self) -> Result<Vec<String>, ReleaseError> {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        let repo = self.local();
+        let Some(workdir) = repo.workdir().map(Path::to_path_buf) else {
+            return Ok(Vec::new());
+        };
+
+        // HEAD tree: path -> blob id
+        let mut recorder = gix::traverse::tree::Recorder::default();
+        if let Ok(tree) = repo.head_tree() {
+            tree.traverse()
+                .breadthfirst(&mut recorder)
+                .map_err(|e| gix_err("failed to read HEAD tree", e))?;
+        }
+        let mut head: BTreeMap<BString, gix::ObjectId> = recorder
+            .records
+            .into_iter()
+            .filter(|e| !e.mode.is_tree())
+            .map(|e| (e.filepath, e.oid))
+            .collect();
+
+        let index = repo
+            .index_or_empty()
+            .map_err(|e| gix_err("failed to read index", e))?;
+        let mut dirty = BTreeSet::new();
+        for entry in index.entries() {
+            let path = entry.path(&index);
+            // Staged: index differs from HEAD
+            if head.remove(path) != Some(entry.id) {
+                dirty.insert(path.to_owned());
+                continue;
+            }
+            // Unstaged: work tree differs from index. Unchanged stat data means
+            // unchanged content; otherwise hash the file to be sure.
+            let abs = workdir.join(path.to_str_lossy().as_ref());
+            let Ok(meta) = gix::index::fs::Metadata::from_path_no_follow(&abs) else {
+                dirty.insert(path.to_owned());
+                continue;
+            };
+            if gix::index::entry::Stat::from_fs(&meta).is_ok_and(|stat| stat == entry.stat) {
+                continue;
+            }
+            let data = if meta.is_symlink() {
+                std::fs::read_link(&abs)
+                    .map(|target| target.to_string_lossy().into_owned().into_bytes())
+            } else {
+                std::fs::read(&abs)
+            }
+            .map_err(|e| gix_err(&format!("failed to read {}", abs.display()), e))?;
+            let id = gix::objs::compute_hash(repo.object_hash(), gix::objs::Kind::Blob, &data)
+                .map_err(|e| gix_err("failed to hash file", e))?;
+            if id != entry.id {
+                dirty.insert(path.to_owned());
+            }
+        }
+        // Staged deletions: in HEAD but no longer in the index
+        dirty.extend(head.into_keys());
+
+        Ok(dirty.into_iter().map(|p| p.to_string()).collect())
+    }
+
+    fn workdir(&self) -> Result<PathBuf, ReleaseError> {
+        self.local()
+            .workdir()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| ReleaseError::Git("a bare repository has no work tree".into()))
+    }
+
+    fn current_branch(&self) -> Result<Option<String>, ReleaseError> {
+        let name = self
+            .local()
+            .head_name()
+            .map_err(|e| gix_err("failed to read HEAD", e))?;
+        Ok(name.map(|n| n.shorten().to_string()))
+    }
+
+    fn default_branch(&self) -> Result<Option<String>, ReleaseError> {
+        let repo = self.local();
+        let found = repo
+            .try_find_reference("refs/remotes/origin/HEAD")
+            .map_err(|e| gix_err("failed to read origin/HEAD", e))?;
+        Ok(found.and_then(|reference| {
+            let target = reference.target();
+            target
+                .try_name()?
+                .as_bstr()
+                .to_str_lossy()
+                .strip_prefix("refs/remotes/origin/")
+                .map(str::to_string)
+        }))
+    }
+
+    fn is_shallow(&self) -> Result<bool, ReleaseError> {
+        Ok(self.local().is_shallow())
+    }
+
+    fn fetch_tags(&self) -> Result<(), ReleaseError> {
+        self.cli()?.fetch_tags()
+    }
+
+    fn check_remote_access(&self) -> Result<(), ReleaseError> {
+        if git_binary_available() {
+            return Ok(());
+        }
+        Err(ReleaseError::Git(NO_GIT_FOR_REMOTE.into()))
+    }
+
+    fn commits_since_paths(
+        &self,
+        from: Option<&str>,
+        paths: &[&str],
+    ) -> Result<Vec<Commit>, ReleaseError> {
+        self.walk(from, "HEAD", paths)
+    }
+
+    fn commits_between_paths(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        paths: &[&str],
+    ) -> Result<Vec<Commit>, ReleaseError> {
+        self.walk(from, to, paths)
+    }
+}
@@ -1,10 +1,17 @@
+//! Backend parity tests: every test runs against `NativeGitRepository` and,
+//! when the `gix` feature is enabled, against `GixRepository`.
+
+use std::path::Path;
 use std::process::Command;
 
+use sr_core::config::{GitConfig, SigningConfig, TagsConfig};
+use sr_core::error::ReleaseError;
 use sr_core::git::GitRepository;
-use sr_git::NativeGitRepository;
 use tempfile::TempDir;
 
-fn init_repo() -> (TempDir, NativeGitRepository) {
+type Opener<R> = fn(&Path) -> Result<R, ReleaseError>;
+
+fn init_repo<R: GitRepository>(open: Opener<R>) -> (TempDir, R) {
     let dir = TempDir::new().unwrap();
     let path = dir.path();
 
@@ -28,7 +35,7 @@ fn init_repo() -> (TempDir, NativeGitRepository) {
     git(&["config", "user.name", "Test"]);
     git(&["commit", "--allow-empty", "-m", "feat: initial"]);
 
-    let repo = NativeGitRepository::open(path).unwrap();
+    let repo = open(path).unwrap();
     (dir, repo)
 }
 
@@ -48,50 +55,124 @@ fn git_in(dir: &TempDir, args: &[&str]) -> String {
     String::from_utf8_lossy(&out.stdout).trim().to_string()
 }
 
-#[test]
-fn open_valid_repo() {
-    let (dir, _repo) = init_repo();
-    assert!(NativeGitRepository::open(dir.path()).is_ok());
+/// Generate one `#[test]` per backend for each generic test function.
+macro_rules! parity_tests {
+    ($($name:ident),* $(,)?) => {
+        mod native {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name(sr_git::NativeGitRepository::open);
+                }
+            )*
+        }
+
+        #[cfg(feature = "gix")]
+        mod gix {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name(sr_git::GixRepository::open);
+                }
+            )*
+        }
+    };
 }
 
-#[test]
-fn open_non_repo() {
+parity_tests!(
+    open_valid_repo,
+    open_non_repo,
+    latest_tag_none,
+    latest_tag_finds_latest,
+    latest_tag_reachable_ignores_other_branches,
+    commits_since_all,
+    commits_since_partial,
+    commits_since_in_path_filters,
+    create_tag_exists,
+    tag_exists_and_force_create,
+    tag_timestamp_matches_commit,
+    tag_message_of_annotated_and_lightweight_tags,
+    commit_date_reads_committer_time,
+    stage_and_commit_files,
+    stage_and_commit_nothing_to_commit,
+    stage_and_commit_with_trailers,
+    shallow_clone_fetch_tags,
+    fetch_tags_without_remote_fails,
+    current_branch_and_detached_head,
+    default_branch_from_origin_head,
+    dirty_paths_reports_tracked_changes,
+    workdir_is_the_repository_root,
+    commits_since_paths_include_and_exclude,
+    all_tags_mixed_annotated_and_lightweight,
+    all_tags_skips_tags_with_missing_commits,
+    tags_under_several_prefixes,
+    tags_under_path_like_prefixes,
+    delete_tag_local_and_remote,
+    push_detached_head_to_branch,
+    force_create_tag_at_revision,
+    revert_commit_creates_commit,
+    remote_ref_create_read_delete,
+    add_and_push_release_notes,
+);
+
+fn open_valid_repo<R: GitRepository>(open: Opener<R>) {
+    let (dir, _repo) = init_repo(open);
+    assert!(open(dir.path()).is_ok());
+}
+
+fn open_non_repo<R: GitRepository>(open: Opener<R>) {
     let dir = TempDir::new().unwrap();
-    assert!(NativeGitRepository::open(dir.path()).is_err());
+    assert!(open(dir.path()).is_err());
 }
 
-#[test]
-fn latest_tag_none() {
-    let (_dir, repo) = init_repo();
-    let tag = repo.latest_tag("v").unwrap();
+fn latest_tag_none<R: GitRepository>(open: Opener<R>) {
+    let (_dir, repo) = init_repo(open);
+    let tag = repo.latest_tag(&["v"]).unwrap();
     assert!(tag.is_none());
 }
 
-#[test]
-fn latest_tag_finds_latest() {
-    let (dir, repo) = init_repo();
+fn latest_tag_finds_latest<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
     git_in(&dir, &["tag", "v1.0.0"]);
     git_in(&dir, &["commit", "--allow-empty", "-m", "feat: second"]);
-    git_in(&dir, &["tag", "v1.1.0"]);
+    git_in(&dir, &["tag", "-a", "v1.1.0", "-m", "v1.1.0"]);
 
-    let tag = repo.latest_tag("v").unwrap().unwrap();
+    let tag = repo.latest_tag(&["v"]).unwrap().unwrap();
     assert_eq!(tag.name, "v1.1.0");
     assert_eq!(tag.version, semver::Version::new(1, 1, 0));
+    // Annotated tags resolve to the commit they point at
+    assert_eq!(tag.sha, git_in(&dir, &["rev-parse", "HEAD"]));
 }
 
-#[test]
-fn commits_since_all() {
-    let (dir, repo) = init_repo();
+fn latest_tag_reachable_ignores_other_branches<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    git_in(&dir, &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+    let base = git_in(&dir, &["rev-parse", "HEAD"]);
+    git_in(&dir, &["commit", "--allow-empty", "-m", "feat: second"]);
+    git_in(&dir, &["tag", "v1.1.0"]);
+
+    let tag = repo.latest_tag_reachable(&["v"], &base).unwrap().unwrap();
+    assert_eq!(tag.name, "v1.0.0");
+    assert_eq!(repo.tags_reachable(&["v"], "HEAD").unwrap().len(), 2);
+    assert_eq!(repo.resolve_rev("v1.0.0").unwrap(), base);
+
+    let err = repo.resolve_rev("no-such-ref").unwrap_err();
+    assert!(err.to_string().contains("unknown revision 'no-such-ref'"));
+    assert!(repo.tags_reachable(&["v"], "no-such-ref").is_err());
+}
+
+fn commits_since_all<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
     git_in(&dir, &["commit", "--allow-empty", "-m", "fix: second"]);
     git_in(&dir, &["commit", "--allow-empty", "-m", "feat: third"]);
 
     let commits = repo.commits_since(None).unwrap();
     assert_eq!(commits.len(), 3);
+    assert_eq!(commits[0].message, "feat: third");
 }
 
-#[test]
-fn commits_since_partial() {
-    let (dir, repo) = init_repo();
+fn commits_since_partial<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
     let first_sha = git_in(&dir, &["rev-parse", "HEAD"]);
     git_in(&dir, &["commit", "--allow-empty", "-m", "fix: second"]);
     git_in(&dir, &["commit", "--allow-empty", "-m", "feat: third"]);
@@ -100,11 +181,864 @@ fn commits_since_partial() {
     assert_eq!(commits.len(), 2);
 }
 
-#[test]
-fn create_tag_exists() {
-    let (dir, repo) = init_repo();
+fn commits_since_in_path_filters<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    std::fs::create_dir(dir.path().join("core")).unwrap();
+    std::fs::write(dir.path().join("core/lib.rs"), "fn main() {}").unwrap();
+    git_in(&dir, &["add", "."]);
+    git_in(&dir, &["commit", "-m", "feat(core): add lib"]);
+    std::fs::write(dir.path().join("README.md"), "readme").unwrap();
+    git_in(&dir, &["add", "."]);
+    git_in(&dir, &["commit", "-m", "docs: readme"]);
+
+    let commits = repo.commits_since_in_path(None, "core").unwrap();
+    assert_eq!(commits.len(), 1);
+    assert_eq!(commits[0].message, "feat(core): add lib");
+}
+
+fn commits_since_paths_include_and_exclude<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let commit_file = |file: &str, msg: &str| {
+        let path = dir.path().join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, msg).unwrap();
+        git_in(&dir, &["add", "."]);
+        git_in(&dir, &["commit", "-m", msg]);
+    };
+    commit_file("src/lib.rs", "feat: library");
+    commit_file("docs/guide.md", "docs: guide");
+    commit_file("crates/core/src/lib.rs", "fix(core): bug");
+    commit_file("README.md", "docs: readme");
+
+    let messages = |commits: Vec<sr_core::commit::Commit>| -> Vec<String> {
+        commits.into_iter().map(|c| c.message).collect()
+    };
+
+    // Excludes only: commits touching just docs/ or *.md are dropped (the
+    // initial commit is empty, so it touches nothing)
+    let commits = repo
+        .commits_since_paths(None, &[":(exclude)docs", ":(exclude)*.md"])
+        .unwrap();
+    assert_eq!(messages(commits), vec!["fix(core): bug", "feat: library"]);
+
+    // Includes and excludes combined
+    let commits = repo
+        .commits_since_paths(None, &["src", "crates", ":(exclude)crates/core"])
+        .unwrap();
+    assert_eq!(messages(commits), vec!["feat: library"]);
+
+    // Range form
+    let first = git_in(&dir, &["rev-parse", "HEAD~3"]);
+    let commits = repo
+        .commits_between_paths(Some(&first), "HEAD", &["docs", "README.md"])
+        .unwrap();
+    assert_eq!(messages(commits), vec!["docs: readme", "docs: guide"]);
+}
+
+fn all_tags_mixed_annotated_and_lightweight<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let tags = [
+        ("v0.9.0", false),
+        ("v0.10.0", true),
+        ("core/v1.0.0", true),
+        ("v1.0.0-rc.1", false),
+        ("v1.0.0", true),
+        ("v1.1.0", false),
+    ];
+    for (name, annotated) in tags {
+        git_in(
+            &dir,
+            &["commit", "--allow-empty", "-m", &format!("feat: {name}")],
+        );
+        if annotated {
+            git_in(&dir, &["tag", "-a", name, "-m", name]);
+        } else {
+            git_in(&dir, &["tag", name]);
+        }
+    }
+    git_in(&dir, &["tag", "not-semver"]);
+
+    let all = repo.all_tags(&["v"]).unwrap();
+    let names: Vec<&str> = all.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["v0.9.0", "v0.10.0", "v1.0.0-rc.1", "v1.0.0", "v1.1.0"]
+    );
+    // Same SHAs the per-tag `git rev-list -1 <tag>` lookup produced
+    for tag in &all {
+        assert_eq!(
+            tag.sha,
+            git_in(&dir, &["rev-list", "-1", &tag.name]),
+            "{}",
+            tag.name
+        );
+    }
+    assert_eq!(repo.latest_tag(&["v"]).unwrap().unwrap().name, "v1.1.0");
+
+    let core = repo.all_tags(&["core/v"]).unwrap();
+    assert_eq!(core.len(), 1);
+    assert_eq!(
+        core[0].sha,
+        git_in(&dir, &["rev-list", "-1", "core/v1.0.0"])
+    );
+}
+
+fn tags_under_several_prefixes<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    git_in(&dir, &["tag", "-a", "release-2.8.0", "-m", "release-2.8.0"]);
+    let migrated = git_in(&dir, &["rev-parse", "HEAD"]);
+    git_in(&dir, &["commit", "--allow-empty", "-m", "feat: second"]);
+    git_in(&dir, &["tag", "release-10.0.0-rc.1"]);
+
+    let tag = repo.latest_tag(&["v", "release-"]).unwrap().unwrap();
+    assert_eq!(tag.name, "release-10.0.0-rc.1");
+    assert!(repo.latest_tag(&["v"]).unwrap().is_none());
+
+    git_in(&dir, &["tag", "v2.9.0"]);
+    let names: Vec<String> = repo
+        .all_tags(&["v", "release-"])
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, ["release-2.8.0", "v2.9.0", "release-10.0.0-rc.1"]);
+
+    let reachable = repo
+        .latest_tag_reachable(&["v", "release-"], &migrated)
+        .unwrap()
+        .unwrap();
+    assert_eq!(reachable.name, "release-2.8.0");
+}
+
+fn tags_under_path_like_prefixes<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let remote = TempDir::new().unwrap();
+    git_in(&remote, &["init", "--bare", "-q"]);
+    git_in(
+        &dir,
+        &["remote", "add", "origin", remote.path().to_str().unwrap()],
+    );
+    git_in(&dir, &["tag", "v1.0.0"]);
+    git_in(
+        &dir,
+        &["tag", "-a", "pkg/foo-v1.1.0", "-m", "pkg/foo-v1.1.0"],
+    );
+    git_in(&dir, &["tag", "pkg/foo-v1"]);
+    git_in(&dir, &["tag", "pkg/v2.0.0"]);
+
+    let names = |prefixes: &[&str]| -> Vec<String> {
+        repo.all_tags(prefixes)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect()
+    };
+    assert_eq!(names(&["pkg/foo-v"]), ["pkg/foo-v1.1.0"]);
+    assert_eq!(names(&["pkg/v"]), ["pkg/v2.0.0"]);
+    assert_eq!(names(&["v"]), ["v1.0.0"]);
+    let latest = repo.latest_tag(&["pkg/foo-v"]).unwrap().unwrap();
+    assert_eq!(latest.version, semver::Version::new(1, 1, 0));
+
+    // Only `pkg/v2.0.0` is on the remote; `v2.0.0` is a different tag
+    git_in(&dir, &["push", "-q", "origin", "pkg/v2.0.0"]);
+    assert!(repo.remote_tag_exists("pkg/v2.0.0").unwrap());
+    assert!(!repo.remote_tag_exists("v2.0.0").unwrap());
+}
+
+fn create_tag_exists<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
     repo.create_tag("v1.0.0", "release v1.0.0", false).unwrap();
 
     let tags = git_in(&dir, &["tag", "-l"]);
     assert!(tags.contains("v1.0.0"));
+    let kind = git_in(&dir, &["cat-file", "-t", "v1.0.0"]);
+    assert_eq!(kind, "tag");
+}
+
+fn tag_exists_and_force_create<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    assert!(!repo.tag_exists("v1").unwrap());
+    repo.force_create_tag("v1").unwrap();
+    assert!(repo.tag_exists("v1").unwrap());
+
+    git_in(&dir, &["commit", "--allow-empty", "-m", "fix: second"]);
+    repo.force_create_tag("v1").unwrap();
+    assert_eq!(
+        git_in(&dir, &["rev-parse", "v1"]),
+        git_in(&dir, &["rev-parse", "HEAD"])
+    );
+    assert_eq!(
+        repo.head_sha().unwrap(),
+        git_in(&dir, &["rev-parse", "HEAD"])
+    );
+}
+
+fn tag_timestamp_matches_commit<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    git_in(&dir, &["tag", "v1.0.0"]);
+    let expected = git_in(&dir, &["log", "-1", "--format=%ct", "v1.0.0"]);
+    assert_eq!(repo.tag_timestamp("v1.0.0").unwrap().to_string(), expected);
+}
+
+fn tag_message_of_annotated_and_lightweight_tags<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    git_in(&dir, &["tag", "v1.0.0"]);
+    git_in(
+        &dir,
+        &[
+            "tag",
+            "--cleanup=verbatim",
+            "-a",
+            "v1.1.0",
+            "-m",
+            "v1.1.0\n\n### Features\n\n- thing\n",
+        ],
+    );
+
+    assert_eq!(repo.tag_message("v1.0.0").unwrap(), None);
+    assert_eq!(
+        repo.tag_message("v1.1.0").unwrap().as_deref(),
+        Some("v1.1.0\n\n### Features\n\n- thing")
+    );
+    assert!(repo.tag_message("v9.9.9").is_err());
+}
+
+fn commit_date_reads_committer_time<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let sha = git_in(&dir, &["rev-parse", "HEAD"]);
+    let expected = git_in(&dir, &["log", "-1", "--format=%ct", &sha]);
+    assert_eq!(repo.commit_date(&sha).unwrap().to_string(), expected);
+    assert!(repo.commit_date(&"f".repeat(40)).is_err());
+}
+
+fn stage_and_commit_files<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    std::fs::write(dir.path().join("CHANGELOG.md"), "# Changelog\n").unwrap();
+
+    let committed = repo
+        .stage_and_commit(&["CHANGELOG.md"], "chore(release): v1.0.0")
+        .unwrap();
+    assert!(committed);
+    assert_eq!(
+        git_in(&dir, &["log", "-1", "--format=%s"]),
+        "chore(release): v1.0.0"
+    );
+    assert_eq!(git_in(&dir, &["show", "HEAD:CHANGELOG.md"]), "# Changelog");
+    // Work tree and index are clean after the commit
+    assert_eq!(git_in(&dir, &["status", "--porcelain"]), "");
+}
+
+fn stage_and_commit_nothing_to_commit<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+    git_in(&dir, &["add", "a.txt"]);
+    git_in(&dir, &["commit", "-m", "chore: add a"]);
+
+    let committed = repo.stage_and_commit(&["a.txt"], "no-op").unwrap();
+    assert!(!committed);
+}
+
+fn stage_and_commit_with_trailers<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    std::fs::write(dir.path().join("CHANGELOG.md"), "# Changelog\n").unwrap();
+
+    let trailers = [
+        ("Skip-Checks".to_string(), "true".to_string()),
+        ("Release-Tag".to_string(), "v1.0.0".to_string()),
+    ];
+    let committed = repo
+        .stage_and_commit_with_trailers(&["CHANGELOG.md"], "chore(release): v1.0.0", &trailers)
+        .unwrap();
+    assert!(committed);
+    assert_eq!(
+        git_in(&dir, &["log", "-1", "--format=%s"]),
+        "chore(release): v1.0.0"
+    );
+    assert_eq!(
+        git_in(&dir, &["log", "-1", "--format=%(trailers:only,unfold)"]),
+        "Skip-Checks: true\nRelease-Tag: v1.0.0"
+    );
+}
+
+fn shallow_clone_fetch_tags<R: GitRepository>(open: Opener<R>) {
+    let (upstream, _) = init_repo(open);
+    git_in(&upstream, &["tag", "-a", "v3.0.0", "-m", "v3.0.0"]);
+    git_in(&upstream, &["commit", "--allow-empty", "-m", "fix: second"]);
+    git_in(&upstream, &["commit", "--allow-empty", "-m", "fix: third"]);
+
+    // file:// is required for --depth to take effect on a local clone
+    let clone = TempDir::new().unwrap();
+    let url = format!("file://{}", upstream.path().display());
+    let out = Command::new("git")
+        .args(["clone", "--depth", "1", &url])
+        .arg(clone.path())
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let repo = open(clone.path()).unwrap();
+    assert!(repo.is_shallow().unwrap());
+    assert!(repo.latest_tag(&["v"]).unwrap().is_none());
+
+    repo.fetch_tags().unwrap();
+    assert!(!repo.is_shallow().unwrap());
+    assert_eq!(repo.latest_tag(&["v"]).unwrap().unwrap().name, "v3.0.0");
+    assert_eq!(repo.commits_since(None).unwrap().len(), 3);
+}
+
+fn fetch_tags_without_remote_fails<R: GitRepository>(open: Opener<R>) {
+    let (_dir, repo) = init_repo(open);
+    assert!(!repo.is_shallow().unwrap());
+    let err = repo.fetch_tags().unwrap_err().to_string();
+    assert!(err.contains("no origin remote"), "{err}");
+}
+
+fn current_branch_and_detached_head<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    git_in(&dir, &["checkout", "-q", "-b", "release/1.x"]);
+    assert_eq!(
+        repo.current_branch().unwrap().as_deref(),
+        Some("release/1.x")
+    );
+
+    git_in(&dir, &["checkout", "-q", "--detach"]);
+    assert_eq!(repo.current_branch().unwrap(), None);
+}
+
+fn default_branch_from_origin_head<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    assert_eq!(repo.default_branch().unwrap(), None);
+
+    let head = git_in(&dir, &["rev-parse", "HEAD"]);
+    git_in(&dir, &["update-ref", "refs/remotes/origin/trunk", &head]);
+    git_in(
+        &dir,
+        &[
+            "symbolic-ref",
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/trunk",
+        ],
+    );
+    assert_eq!(repo.default_branch().unwrap().as_deref(), Some("trunk"));
+}
+
+fn all_tags_skips_tags_with_missing_commits<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    git_in(&dir, &["tag", "v1.0.0"]);
+    git_in(&dir, &["tag", "-a", "v1.1.0", "-m", "v1.1.0"]);
+    // A tag whose commit was garbage-collected.
+    std::fs::write(
+        dir.path().join(".git/refs/tags/v1.0.5"),
+        format!("{}\n", "1".repeat(40)),
+    )
+    .unwrap();
+
+    let names: Vec<String> = repo
+        .all_tags(&["v"])
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, ["v1.0.0", "v1.1.0"]);
+}
+
+fn dirty_paths_reports_tracked_changes<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    for f in ["a.txt", "b.txt", "c.txt"] {
+        std::fs::write(dir.path().join(f), f).unwrap();
+    }
+    git_in(&dir, &["add", "."]);
+    git_in(&dir, &["commit", "-m", "chore: files"]);
+    assert!(repo.dirty_paths().unwrap().is_empty());
+
+    std::fs::write(dir.path().join("a.txt"), "changed").unwrap();
+    std::fs::write(dir.path().join("b.txt"), "staged").unwrap();
+    git_in(&dir, &["add", "b.txt"]);
+    git_in(&dir, &["rm", "-q", "--cached", "c.txt"]);
+    std::fs::write(dir.path().join("untracked.txt"), "new").unwrap();
+
+    let mut dirty = repo.dirty_paths().unwrap();
+    dirty.sort();
+    assert_eq!(dirty, vec!["a.txt", "b.txt", "c.txt"]);
+}
+
+fn workdir_is_the_repository_root<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    assert_eq!(
+        repo.workdir().unwrap().canonicalize().unwrap(),
+        dir.path().canonicalize().unwrap()
+    );
+}
+
+// --- Signing ---
+//
+// Uses a throwaway SSH key so no GPG agent or keyring is involved. Each backend
+// gets its own test because `with_signing` is not part of the trait.
+
+fn ssh_signing(dir: &TempDir, tags: bool, commits: bool) -> SigningConfig {
+    let key = dir.path().join("signing_key");
+    let out = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", "sr-test", "-f"])
+        .arg(&key)
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    SigningConfig {
+        tags,
+        commits,
+        key: Some(key.display().to_string()),
+    }
+}
+
+fn assert_signed_release<R: GitRepository>(dir: &TempDir, repo: &R) {
+    std::fs::write(dir.path().join("CHANGELOG.md"), "# Changelog\n").unwrap();
+    assert!(
+        repo.stage_and_commit(&["CHANGELOG.md"], "chore(release): v1.0.0")
+            .unwrap()
+    );
+    repo.create_tag("v1.0.0", "v1.0.0", false).unwrap();
+    repo.force_create_tag("v1").unwrap();
+
+    let commit = git_in(dir, &["cat-file", "commit", "HEAD"]);
+    assert!(commit.contains("-----BEGIN SSH SIGNATURE-----"), "{commit}");
+    for tag in ["v1.0.0", "v1"] {
+        let object = git_in(dir, &["cat-file", "tag", tag]);
+        assert!(object.contains("-----BEGIN SSH SIGNATURE-----"), "{object}");
+    }
+}
+
+#[test]
+fn native_signs_commits_and_tags_with_ssh_key() {
+    let (dir, repo) = init_repo(sr_git::NativeGitRepository::open);
+    let keys = TempDir::new().unwrap();
+    let repo = repo.with_signing(ssh_signing(&keys, true, true));
+    assert_signed_release(&dir, &repo);
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn gix_signs_commits_and_tags_with_ssh_key() {
+    let (dir, repo) = init_repo(sr_git::GixRepository::open);
+    let keys = TempDir::new().unwrap();
+    let repo = repo.with_signing(ssh_signing(&keys, true, true));
+    assert_signed_release(&dir, &repo);
+}
+
+#[test]
+fn signing_with_missing_key_surfaces_git_error() {
+    let (dir, repo) = init_repo(sr_git::NativeGitRepository::open);
+    let missing = dir.path().join("no-such-key");
+    let repo = repo.with_signing(SigningConfig {
+        tags: true,
+        commits: false,
+        key: Some(missing.display().to_string()),
+    });
+
+    let err = repo
+        .create_tag("v1.0.0", "v1.0.0", false)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("failed to sign tag v1.0.0"), "{err}");
+    // The underlying git stderr is preserved
+    assert!(err.contains("no-such-key"), "{err}");
+    // Depending on the git version, the tag is either never created or rolled back
+    assert!(!repo.tag_exists("v1.0.0").unwrap());
+}
+
+// --- Committer identity ---
+
+fn sr_bot() -> GitConfig {
+    GitConfig {
+        user_name: Some("sr-bot".into()),
+        user_email: Some("sr-bot@users.noreply.github.com".into()),
+        ..Default::default()
+    }
+}
+
+fn assert_release_identity<R: GitRepository>(dir: &TempDir, repo: &R) {
+    std::fs::write(dir.path().join("CHANGELOG.md"), "# Changelog\n").unwrap();
+    assert!(
+        repo.stage_and_commit(&["CHANGELOG.md"], "chore(release): v1.0.0")
+            .unwrap()
+    );
+    repo.create_tag("v1.0.0", "v1.0.0", false).unwrap();
+
+    assert_eq!(
+        git_in(dir, &["log", "-1", "--format=%an <%ae>|%cn <%ce>"]),
+        "sr-bot <sr-bot@users.noreply.github.com>|sr-bot <sr-bot@users.noreply.github.com>"
+    );
+    let tag = git_in(dir, &["cat-file", "tag", "v1.0.0"]);
+    assert!(
+        tag.contains("tagger sr-bot <sr-bot@users.noreply.github.com>"),
+        "{tag}"
+    );
+    // The repository's own config is left untouched
+    assert_eq!(git_in(dir, &["config", "user.name"]), "Test");
+}
+
+#[test]
+fn native_commits_and_tags_as_configured_identity() {
+    let (dir, repo) = init_repo(sr_git::NativeGitRepository::open);
+    let repo = repo.with_identity(sr_bot());
+    assert_release_identity(&dir, &repo);
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn gix_commits_and_tags_as_configured_identity() {
+    let (dir, repo) = init_repo(sr_git::GixRepository::open);
+    let repo = repo.with_identity(sr_bot());
+    assert_release_identity(&dir, &repo);
+}
+
+// --- Lightweight tags ---
+
+fn assert_lightweight_release_tag<R: GitRepository>(dir: &TempDir, lightweight: &R, annotated: &R) {
+    lightweight
+        .create_tag("v1.0.0", "v1.0.0\n\nnotes", false)
+        .unwrap();
+    assert_eq!(git_in(dir, &["cat-file", "-t", "v1.0.0"]), "commit");
+    assert_eq!(
+        git_in(dir, &["rev-parse", "v1.0.0"]),
+        git_in(dir, &["rev-parse", "HEAD"])
+    );
+
+    git_in(dir, &["commit", "--allow-empty", "-m", "feat: next"]);
+    annotated.create_tag("v1.1.0", "v1.1.0", false).unwrap();
+    assert_eq!(git_in(dir, &["cat-file", "-t", "v1.1.0"]), "tag");
+
+    let names: Vec<String> = annotated
+        .all_tags(&["v"])
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, ["v1.0.0", "v1.1.0"]);
+    assert_eq!(
+        lightweight.latest_tag(&["v"]).unwrap().unwrap().name,
+        "v1.1.0"
+    );
+}
+
+fn lightweight() -> TagsConfig {
+    TagsConfig { annotated: false }
+}
+
+#[test]
+fn native_creates_lightweight_tags_when_not_annotated() {
+    let (dir, repo) = init_repo(sr_git::NativeGitRepository::open);
+    let annotated = sr_git::NativeGitRepository::open(dir.path()).unwrap();
+    assert_lightweight_release_tag(&dir, &repo.with_tags(lightweight()), &annotated);
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn gix_creates_lightweight_tags_when_not_annotated() {
+    let (dir, repo) = init_repo(sr_git::GixRepository::open);
+    let annotated = sr_git::GixRepository::open(dir.path()).unwrap();
+    assert_lightweight_release_tag(&dir, &repo.with_tags(lightweight()), &annotated);
+}
+
+fn assert_history_capped_at_three<R: GitRepository>(dir: &TempDir, repo: &R) {
+    for i in 0..4 {
+        git_in(
+            dir,
+            &["commit", "--allow-empty", "-m", &format!("fix: {i}")],
+        );
+    }
+    let commits = repo.commits_since(None).unwrap();
+    let messages: Vec<&str> = commits.iter().map(|c| c.message.as_str()).collect();
+    assert_eq!(messages, vec!["fix: 3", "fix: 2", "fix: 1"]);
+
+    let first = git_in(dir, &["rev-parse", "HEAD~2"]);
+    assert_eq!(repo.commits_since(Some(&first)).unwrap().len(), 2);
+}
+
+#[test]
+fn native_caps_history_at_max_commits() {
+    let (dir, repo) = init_repo(sr_git::NativeGitRepository::open);
+    let repo = repo.with_max_commits(3);
+    assert_history_capped_at_three(&dir, &repo);
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn gix_caps_history_at_max_commits() {
+    let (dir, repo) = init_repo(sr_git::GixRepository::open);
+    let repo = repo.with_max_commits(3);
+    assert_history_capped_at_three(&dir, &repo);
+}
+
+fn assert_changed_paths_read<R: GitRepository>(dir: &TempDir, repo: &R) {
+    let write = |file: &str| {
+        let path = dir.path().join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, file).unwrap();
+    };
+    write("src/a.rs");
+    write("README.md");
+    git_in(dir, &["add", "."]);
+    git_in(dir, &["commit", "-m", "feat: a"]);
+    std::fs::create_dir(dir.path().join("lib")).unwrap();
+    git_in(dir, &["mv", "src/a.rs", "lib/a.rs"]);
+    git_in(dir, &["commit", "-m", "refactor: move a"]);
+    git_in(dir, &["checkout", "-q", "-b", "topic"]);
+    write("docs/guide.md");
+    git_in(dir, &["add", "."]);
+    git_in(dir, &["commit", "-m", "docs: guide"]);
+    git_in(dir, &["checkout", "-q", "-"]);
+    git_in(
+        dir,
+        &[
+            "merge",
+            "--no-ff",
+            "-q",
+            "-m",
+            "chore: merge topic",
+            "topic",
+        ],
+    );
+
+    // Same-second commits come back in either order; compare by message
+    let commits = repo.commits_since(None).unwrap();
+    let mut paths: Vec<(&str, Vec<&str>)> = commits
+        .iter()
+        .map(|c| {
+            let mut paths: Vec<&str> = c.paths.iter().map(String::as_str).collect();
+            paths.sort();
+            (c.message.as_str(), paths)
+        })
+        .collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![
+            ("chore: merge topic", vec!["docs/guide.md"]),
+            ("docs: guide", vec!["docs/guide.md"]),
+            ("feat: a", vec!["README.md", "src/a.rs"]),
+            ("feat: initial", vec![]),
+            ("refactor: move a", vec!["lib/a.rs", "src/a.rs"]),
+        ]
+    );
+}
+
+#[test]
+fn native_reads_changed_paths() {
+    let (dir, repo) = init_repo(sr_git::NativeGitRepository::open);
+    assert_changed_paths_read(&dir, &repo.with_changed_paths(true));
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn gix_reads_changed_paths() {
+    let (dir, repo) = init_repo(sr_git::GixRepository::open);
+    assert_changed_paths_read(&dir, &repo.with_changed_paths(true));
+}
+
+fn assert_lenient_tags_read<R: GitRepository>(dir: &TempDir, repo: &R) {
+    git_in(dir, &["tag", "v1.2"]);
+    git_in(dir, &["tag", "v1.1.0"]);
+    git_in(dir, &["tag", "vdemo-build"]);
+    let tags: Vec<(String, String)> = repo
+        .all_tags(&["v"])
+        .unwrap()
+        .into_iter()
+        .map(|t| (t.name, t.version.to_string()))
+        .collect();
+    assert_eq!(
+        tags,
+        [
+            ("v1.1.0".to_string(), "1.1.0".to_string()),
+            ("v1.2".to_string(), "1.2.0".to_string())
+        ]
+    );
+}
+
+#[test]
+fn native_reads_lenient_tags() {
+    let (dir, repo) = init_repo(sr_git::NativeGitRepository::open);
+    assert_lenient_tags_read(&dir, &repo.with_lenient_tags(true));
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn gix_reads_lenient_tags() {
+    let (dir, repo) = init_repo(sr_git::GixRepository::open);
+    assert_lenient_tags_read(&dir, &repo.with_lenient_tags(true));
+}
+
+/// Point `origin` at a transport that never answers, so remote commands hang.
+#[cfg(unix)]
+fn add_stalled_remote(dir: &TempDir) {
+    git_in(dir, &["config", "protocol.ext.allow", "always"]);
+    git_in(dir, &["remote", "add", "origin", "ext::sh -c sleep% 10"]);
+}
+
+#[cfg(unix)]
+fn assert_push_times_out<R: GitRepository>(repo: &R) {
+    let started = std::time::Instant::now();
+    let err = repo.push_tag("v1.0.0").unwrap_err();
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert!(
+        err.to_string()
+            .contains("git push origin v1.0.0 timed out after 1s"),
+        "{err}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn native_kills_git_after_timeout() {
+    let (dir, repo) = init_repo(sr_git::NativeGitRepository::open);
+    add_stalled_remote(&dir);
+    git_in(&dir, &["tag", "v1.0.0"]);
+    let repo = repo.with_timeout(Some(std::time::Duration::from_secs(1)));
+    assert_push_times_out(&repo);
+}
+
+#[cfg(all(unix, feature = "gix"))]
+#[test]
+fn gix_kills_git_after_timeout() {
+    let (dir, repo) = init_repo(sr_git::GixRepository::open);
+    add_stalled_remote(&dir);
+    git_in(&dir, &["tag", "v1.0.0"]);
+    let repo = repo.with_timeout(Some(std::time::Duration::from_secs(1)));
+    assert_push_times_out(&repo);
+}
+
+fn delete_tag_local_and_remote<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let remote = TempDir::new().unwrap();
+    git_in(&remote, &["init", "--bare", "-q"]);
+    git_in(
+        &dir,
+        &["remote", "add", "origin", remote.path().to_str().unwrap()],
+    );
+    git_in(&dir, &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+    git_in(&dir, &["push", "-q", "origin", "v1.0.0"]);
+
+    assert!(repo.delete_remote_tag("v1.0.0").unwrap());
+    assert!(!repo.remote_tag_exists("v1.0.0").unwrap());
+    assert!(!repo.delete_remote_tag("v1.0.0").unwrap());
+
+    assert!(repo.delete_tag("v1.0.0").unwrap());
+    assert!(!repo.tag_exists("v1.0.0").unwrap());
+    assert!(!repo.delete_tag("v1.0.0").unwrap());
+}
+
+fn push_detached_head_to_branch<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let remote = TempDir::new().unwrap();
+    git_in(&remote, &["init", "--bare", "-q"]);
+    git_in(
+        &dir,
+        &["remote", "add", "origin", remote.path().to_str().unwrap()],
+    );
+    git_in(&dir, &["push", "-q", "origin", "HEAD:refs/heads/main"]);
+    git_in(&dir, &["checkout", "-q", "--detach"]);
+    git_in(
+        &dir,
+        &["commit", "--allow-empty", "-m", "chore(release): v1.0.0"],
+    );
+    assert_eq!(repo.current_branch().unwrap(), None);
+
+    repo.push_to_branch("main").unwrap();
+    assert_eq!(
+        git_in(&remote, &["rev-parse", "refs/heads/main"]),
+        git_in(&dir, &["rev-parse", "HEAD"])
+    );
+}
+
+fn force_create_tag_at_revision<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let first = git_in(&dir, &["rev-parse", "HEAD"]);
+    git_in(&dir, &["commit", "--allow-empty", "-m", "fix: second"]);
+    repo.force_create_tag("v1").unwrap();
+
+    repo.force_create_tag_at("v1", &first).unwrap();
+    assert_eq!(git_in(&dir, &["rev-parse", "v1^{commit}"]), first);
+}
+
+fn revert_commit_creates_commit<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    std::fs::write(dir.path().join("CHANGELOG.md"), "## v1.0.0\n").unwrap();
+    git_in(&dir, &["add", "CHANGELOG.md"]);
+    git_in(
+        &dir,
+        &["commit", "-q", "-m", "chore(release): v1.0.0 [skip ci]"],
+    );
+    let release = git_in(&dir, &["rev-parse", "HEAD"]);
+
+    repo.revert_commit(&release).unwrap();
+    assert!(!dir.path().join("CHANGELOG.md").exists());
+    let subject = git_in(&dir, &["log", "-1", "--format=%s"]);
+    assert!(subject.starts_with("Revert"), "{subject}");
+}
+
+fn remote_ref_create_read_delete<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let remote = TempDir::new().unwrap();
+    git_in(&remote, &["init", "--bare", "-q"]);
+    git_in(
+        &dir,
+        &["remote", "add", "origin", remote.path().to_str().unwrap()],
+    );
+    let refname = "refs/sr-lock/release";
+
+    assert!(repo.remote_ref(refname).unwrap().is_none());
+    let sha = repo
+        .create_remote_ref(refname, "sr-lock: v1.0.0\n\nexpires: 42")
+        .unwrap()
+        .expect("ref should be created");
+    assert!(repo.create_remote_ref(refname, "second").unwrap().is_none());
+
+    let (held_sha, message) = repo.remote_ref(refname).unwrap().unwrap();
+    assert_eq!(held_sha, sha);
+    assert!(message.contains("expires: 42"), "{message}");
+
+    // A stale expectation must not delete someone else's ref
+    assert!(repo.delete_remote_ref(refname, &"0".repeat(40)).is_err());
+    repo.delete_remote_ref(refname, &sha).unwrap();
+    assert!(repo.remote_ref(refname).unwrap().is_none());
+}
+
+fn add_and_push_release_notes<R: GitRepository>(open: Opener<R>) {
+    let (dir, repo) = init_repo(open);
+    let remote = TempDir::new().unwrap();
+    git_in(&remote, &["init", "--bare", "-q"]);
+    git_in(
+        &dir,
+        &["remote", "add", "origin", remote.path().to_str().unwrap()],
+    );
+    let notes_ref = "refs/notes/releases";
+    let first = git_in(&dir, &["rev-parse", "HEAD"]);
+
+    repo.add_note(notes_ref, &first, "version: 0.9.0\n")
+        .unwrap();
+    repo.add_note(notes_ref, &first, "version: 1.0.0\n")
+        .unwrap();
+    repo.push_notes(notes_ref).unwrap();
+    let show = ["notes", "--ref=releases", "show", first.as_str()];
+    assert_eq!(git_in(&remote, &show), "version: 1.0.0");
+
+    // A clone that never fetched the notes still adds to the remote's history
+    git_in(&dir, &["update-ref", "-d", notes_ref]);
+    git_in(&dir, &["commit", "--allow-empty", "-m", "feat: second"]);
+    let second = git_in(&dir, &["rev-parse", "HEAD"]);
+    repo.add_note(notes_ref, &second, "version: 1.1.0\n")
+        .unwrap();
+    repo.push_notes(notes_ref).unwrap();
+    assert_eq!(git_in(&remote, &show), "version: 1.0.0");
+    assert_eq!(
+        git_in(&remote, &["notes", "--ref=releases", "show", &second]),
+        "version: 1.1.0"
+    );
 }
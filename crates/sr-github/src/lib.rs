@@ -1,5 +1,166 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::Instant;
+
+use sr_core::config::{GitHubConfig, is_loopback_http};
 use sr_core::error::ReleaseError;
-use sr_core::release::VcsProvider;
+use sr_core::redact::redact;
+use sr_core::release::{RemoteAsset, RemoteRelease, VcsProvider};
+use tracing::{debug, info, warn};
+
+/// HTTPS-only agent shared by every outbound request. Proxies are taken from the
+/// standard `HTTPS_PROXY`/`ALL_PROXY`/`NO_PROXY` environment variables.
+pub fn http_agent() -> ureq::Agent {
+    agent(LogRequests { redact: false }, true)
+}
+
+fn agent(log: LogRequests, https_only: bool) -> ureq::Agent {
+    ureq::Agent::new_with_config(
+        ureq::config::Config::builder()
+            .https_only(https_only)
+            .middleware(log)
+            .build(),
+    )
+}
+
+/// Logs method, URL, status, and duration of every request at debug level.
+/// Credentials travel in headers, which are never logged; `redact` also reduces
+/// the URL to its host for secret-bearing webhook URLs.
+struct LogRequests {
+    redact: bool,
+}
+
+impl ureq::middleware::Middleware for LogRequests {
+    fn handle(
+        &self,
+        request: ureq::http::Request<ureq::SendBody>,
+        next: ureq::middleware::MiddlewareNext,
+    ) -> Result<ureq::http::Response<ureq::Body>, ureq::Error> {
+        let method = request.method().clone();
+        let url = request.uri().to_string();
+        let url = if self.redact {
+            sr_core::notify::redact_url(&url)
+        } else {
+            // Query strings may carry tokens (e.g. signed `extends` URLs)
+            url.split('?').next().unwrap_or_default().to_string()
+        };
+        sr_core::offline::ensure_online(&format!("{method} {url}"))
+            .map_err(|e| ureq::Error::Other(Box::new(e)))?;
+        let started = Instant::now();
+        let result = next.handle(request);
+        let duration_ms = started.elapsed().as_millis() as u64;
+        match &result {
+            Ok(resp) => debug!(%method, %url, status = resp.status().as_u16(), duration_ms, "http"),
+            Err(ureq::Error::StatusCode(status)) => {
+                debug!(%method, %url, status, duration_ms, "http")
+            }
+            Err(e) => debug!(%method, %url, error = %e, duration_ms, "http"),
+        }
+        result
+    }
+}
+
+/// A failed provider request. Only the status code is kept from the ureq error;
+/// its message and the URL are passed through [`redact`] with `secrets`, and
+/// request headers are never part of either.
+fn http_err(method: &str, url: &str, e: ureq::Error, secrets: &[&str]) -> ReleaseError {
+    let status = match e {
+        ureq::Error::StatusCode(status) => Some(status),
+        _ => None,
+    };
+    ReleaseError::Http {
+        method: method.to_string(),
+        url: redact(url, secrets),
+        status,
+        source: redact(&e.to_string(), secrets).into(),
+    }
+}
+
+/// GET `url` and return the body as text (used to fetch remote `extends` configs).
+pub fn fetch_text(url: &str) -> Result<String, ReleaseError> {
+    http_agent()
+        .get(url)
+        .header("User-Agent", "sr-github")
+        .call()
+        .map_err(|e| ReleaseError::Config(redact(&format!("failed to fetch {url}: {e}"), &[])))?
+        .into_body()
+        .read_to_string()
+        .map_err(|e| ReleaseError::Config(redact(&format!("failed to read {url}: {e}"), &[])))
+}
+
+/// GET `url` as bytes, with `token` as a bearer token when given (used to
+/// download URL release artifacts). Release binaries often exceed ureq's
+/// default 10 MB body limit, so none is applied.
+pub fn download(url: &str, token: Option<&str>) -> Result<Vec<u8>, ReleaseError> {
+    let mut request = http_agent().get(url).header("User-Agent", "sr-github");
+    if let Some(token) = token {
+        request = request.header("Authorization", &format!("Bearer {token}"));
+    }
+    request
+        .call()
+        .map_err(|e| http_err("GET", url, e, &[token.unwrap_or_default()]))?
+        .into_body()
+        .with_config()
+        .limit(u64::MAX)
+        .read_to_vec()
+        .map_err(|e| {
+            ReleaseError::Vcs(redact(
+                &format!("failed to read {url}: {e}"),
+                &[token.unwrap_or_default()],
+            ))
+        })
+}
+
+/// Send a JSON `body` to a notification webhook with `method` (POST, PUT, or PATCH).
+///
+/// Errors never include the URL, which usually embeds a secret.
+pub fn send_webhook(method: &str, url: &str, body: &str) -> Result<(), ReleaseError> {
+    let agent = agent(LogRequests { redact: true }, true);
+    let request = match method {
+        "PUT" => agent.put(url),
+        "PATCH" => agent.patch(url),
+        _ => agent.post(url),
+    };
+    request
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "sr-github")
+        .send(body)
+        .map_err(|e| ReleaseError::Notify(redact(&format!("{method}: {e}"), &[url])))?;
+    Ok(())
+}
+
+/// REST API base URL for the provider on `hostname`, or `None` to let
+/// [`GitHubProvider`] derive it from the hostname.
+///
+/// For the origin host, `github.api_url` from the config wins. After that
+/// `GITHUB_API_URL` is used when `GITHUB_SERVER_URL` names `hostname`, or,
+/// without `GITHUB_SERVER_URL`, when `hostname` is the origin host.
+pub fn resolve_api_url(
+    config: &GitHubConfig,
+    hostname: &str,
+    origin_host: &str,
+    github_api_url: Option<&str>,
+    github_server_url: Option<&str>,
+) -> Option<String> {
+    let is_origin = hostname == origin_host;
+    if is_origin && let Some(ref url) = config.api_url {
+        return Some(url.clone());
+    }
+    let env_applies = match github_server_url.filter(|url| !url.is_empty()) {
+        Some(server) => server_url_host(server).as_deref() == Some(hostname),
+        None => is_origin,
+    };
+    github_api_url
+        .filter(|url| env_applies && !url.is_empty())
+        .map(String::from)
+}
+
+/// Host (and port) of a server URL such as `https://ghes.example.com/`.
+pub fn server_url_host(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = rest.split('/').next().unwrap_or_default();
+    (!host.is_empty()).then(|| host.to_string())
+}
 
 /// GitHub implementation of the VcsProvider trait using the GitHub REST API.
 pub struct GitHubProvider {
@@ -7,6 +168,55 @@ pub struct GitHubProvider {
     repo: String,
     hostname: String,
     token: String,
+    api_url: Option<String>,
+    upload_url: Option<String>,
+    capabilities: OnceLock<Capabilities>,
+    repository: OnceLock<RepoResponse>,
+}
+
+/// Optional REST API features of a GitHub host. github.com has all of them;
+/// GitHub Enterprise Server (GHES) gains them with its version, so sr checks
+/// before relying on one and falls back when it is missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// GHES version from `/meta` (`installed_version`), when the server says.
+    pub version: Option<String>,
+    /// `make_latest` in release payloads (GHES 3.8+).
+    pub make_latest: bool,
+}
+
+impl Capabilities {
+    /// Every feature: github.com, or a server that does not report its version.
+    pub fn all() -> Self {
+        Self {
+            version: None,
+            make_latest: true,
+        }
+    }
+
+    /// What GHES `version` (e.g. `3.7.2`) supports. A version that does not
+    /// parse is taken to be current.
+    pub fn for_ghes_version(version: &str) -> Self {
+        let mut parts = version.split('.').map(|p| p.parse::<u32>().ok());
+        let release = match (parts.next().flatten(), parts.next().flatten()) {
+            (Some(major), Some(minor)) => (major, minor),
+            _ => (u32::MAX, u32::MAX),
+        };
+        Self {
+            version: Some(version.to_string()),
+            make_latest: release >= (3, 8),
+        }
+    }
+}
+
+/// Capabilities probed so far, by API URL, so each host is asked once per process.
+static PROBED: LazyLock<Mutex<HashMap<String, Capabilities>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(serde::Deserialize)]
+struct MetaResponse {
+    #[serde(default)]
+    installed_version: Option<String>,
 }
 
 #[derive(serde::Deserialize)]
@@ -18,13 +228,45 @@ struct ReleaseResponse {
     assets: Vec<ReleaseAsset>,
 }
 
+#[derive(serde::Deserialize)]
+struct IssueComment {
+    id: u64,
+    html_url: String,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+#[derive(Clone, serde::Deserialize)]
+struct RepoResponse {
+    permissions: Option<RepoPermissions>,
+    #[serde(default)]
+    default_branch: Option<String>,
+}
+
+#[derive(Clone, serde::Deserialize)]
+struct RepoPermissions {
+    push: bool,
+}
+
 #[derive(serde::Deserialize)]
 struct ReleaseAsset {
     id: u64,
     name: String,
+    #[serde(default)]
+    size: u64,
     browser_download_url: String,
 }
 
+impl ReleaseResponse {
+    fn remote(self, tag: &str) -> RemoteRelease {
+        RemoteRelease {
+            tag: tag.to_string(),
+            id: Some(self.id),
+            url: self.html_url,
+        }
+    }
+}
+
 impl GitHubProvider {
     pub fn new(owner: String, repo: String, hostname: String, token: String) -> Self {
         Self {
@@ -32,6 +274,119 @@ impl GitHubProvider {
             repo,
             hostname,
             token,
+            api_url: None,
+            upload_url: None,
+            capabilities: OnceLock::new(),
+            repository: OnceLock::new(),
+        }
+    }
+
+    /// Use `capabilities` instead of probing the host for them.
+    pub fn with_capabilities(self, capabilities: Capabilities) -> Self {
+        let _ = self.capabilities.set(capabilities);
+        self
+    }
+
+    /// What the host supports. GHES hosts are probed on first use (once per
+    /// process and API URL); github.com is never asked.
+    pub fn capabilities(&self) -> &Capabilities {
+        self.capabilities.get_or_init(|| {
+            if self.hostname == "github.com" {
+                return Capabilities::all();
+            }
+            let key = self.api_url();
+            if let Some(known) = PROBED.lock().unwrap().get(&key) {
+                return known.clone();
+            }
+            let probed = self.probe();
+            PROBED.lock().unwrap().insert(key, probed.clone());
+            probed
+        })
+    }
+
+    /// Read the server version from `/meta`. A failed probe is logged and
+    /// treated as a current server, so it never fails a release by itself.
+    fn probe(&self) -> Capabilities {
+        let url = format!("{}/meta", self.api_url());
+        let meta = self
+            .agent()
+            .get(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "sr-github")
+            .call()
+            .map_err(|e| http_err("GET", &url, e, &[&self.token]))
+            .and_then(|resp| {
+                resp.into_body()
+                    .read_json::<MetaResponse>()
+                    .map_err(|e| ReleaseError::Vcs(format!("failed to parse meta response: {e}")))
+            });
+        match meta {
+            Ok(MetaResponse {
+                installed_version: Some(version),
+            }) => {
+                debug!("{} runs GitHub Enterprise Server {version}", self.hostname);
+                Capabilities::for_ghes_version(&version)
+            }
+            Ok(_) => Capabilities::all(),
+            Err(e) => {
+                debug!("capability probe failed, assuming a current server: {e}");
+                Capabilities::all()
+            }
+        }
+    }
+
+    /// Release fields for the floating release: it must never become the
+    /// latest release, which older GHES cannot be told.
+    fn floating_payload(&self, floating_tag: &str, versioned_tag: &str) -> serde_json::Value {
+        let mut payload = serde_json::json!({
+            "tag_name": floating_tag,
+            "name": floating_tag,
+            "body": format!("Points to {versioned_tag}. Use this tag for GitHub Actions."),
+        });
+        let capabilities = self.capabilities();
+        if capabilities.make_latest {
+            payload["make_latest"] = "false".into();
+        } else {
+            warn!(
+                "GitHub Enterprise Server {} does not support make_latest; \
+                 {floating_tag} may be shown as the latest release",
+                capabilities
+                    .version
+                    .as_deref()
+                    .unwrap_or("(unknown version)")
+            );
+        }
+        payload
+    }
+
+    /// Use an explicit REST API base URL (e.g. `GITHUB_API_URL` on GHES) instead of
+    /// deriving it from the hostname.
+    pub fn with_api_url(mut self, api_url: String) -> Self {
+        self.api_url = Some(api_url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Upload assets under `upload_url` (e.g. `https://ghes.example.com/api/uploads`)
+    /// instead of the host and path the release's own `upload_url` names.
+    pub fn with_upload_url(mut self, upload_url: String) -> Self {
+        self.upload_url = Some(upload_url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Where to upload assets of `release`. The `upload_url` from the API looks like
+    ///   https://uploads.github.com/repos/owner/repo/releases/123/assets{?name,label}
+    /// and loses its `{?name,label}` template suffix, plus everything before
+    /// `/repos/` when an upload URL is configured.
+    fn upload_base(&self, release: &ReleaseResponse) -> String {
+        let url = release
+            .upload_url
+            .split('{')
+            .next()
+            .unwrap_or(&release.upload_url);
+        match (&self.upload_url, url.find("/repos/")) {
+            (Some(base), Some(i)) => format!("{base}{}", &url[i..]),
+            _ => url.to_string(),
         }
     }
 
@@ -40,15 +395,133 @@ impl GitHubProvider {
     }
 
     fn api_url(&self) -> String {
-        if self.hostname == "github.com" {
+        if let Some(ref url) = self.api_url {
+            url.clone()
+        } else if self.hostname == "github.com" {
             "https://api.github.com".to_string()
         } else {
             format!("https://{}/api/v3", self.hostname)
         }
     }
 
+    /// Plain HTTP is only allowed for an API or upload URL on the loopback
+    /// interface (a local mock or proxy); everything else must be HTTPS.
     fn agent(&self) -> ureq::Agent {
-        ureq::Agent::new_with_config(ureq::config::Config::builder().https_only(true).build())
+        let loopback = [&self.api_url, &self.upload_url]
+            .into_iter()
+            .flatten()
+            .any(|url| is_loopback_http(url));
+        agent(LogRequests { redact: false }, !loopback)
+    }
+
+    /// Post `body` as a comment on issue or pull request `number`, or edit the
+    /// earlier comment containing `marker` (typically a hidden `<!-- ... -->`
+    /// HTML comment) so repeated runs keep a single sticky comment. The marker
+    /// is prepended to `body` when missing. Returns the comment's URL.
+    pub fn upsert_issue_comment(
+        &self,
+        number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<String, ReleaseError> {
+        let body = if body.contains(marker) {
+            body.to_string()
+        } else {
+            format!("{marker}\n{body}")
+        };
+        let payload = serde_json::json!({ "body": body });
+        let (method, url) = match self.find_issue_comment(number, marker)? {
+            Some(id) => (
+                "PATCH",
+                format!(
+                    "{}/repos/{}/{}/issues/comments/{id}",
+                    self.api_url(),
+                    self.owner,
+                    self.repo
+                ),
+            ),
+            None => (
+                "POST",
+                format!(
+                    "{}/repos/{}/{}/issues/{number}/comments",
+                    self.api_url(),
+                    self.owner,
+                    self.repo
+                ),
+            ),
+        };
+        let agent = self.agent();
+        let request = if method == "PATCH" {
+            agent.patch(&url)
+        } else {
+            agent.post(&url)
+        };
+        let comment: IssueComment = request
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "sr-github")
+            .send_json(&payload)
+            .map_err(|e| http_err(method, &url, e, &[&self.token]))?
+            .into_body()
+            .read_json()
+            .map_err(|e| ReleaseError::Vcs(format!("failed to parse comment response: {e}")))?;
+        Ok(comment.html_url)
+    }
+
+    /// ID of the first comment on issue `number` whose body contains `marker`,
+    /// following the `Link` header through every page.
+    fn find_issue_comment(&self, number: u64, marker: &str) -> Result<Option<u64>, ReleaseError> {
+        let mut next = Some(format!(
+            "{}/repos/{}/{}/issues/{number}/comments?per_page=100",
+            self.api_url(),
+            self.owner,
+            self.repo
+        ));
+        while let Some(url) = next {
+            let resp = self
+                .agent()
+                .get(&url)
+                .header("Authorization", &format!("Bearer {}", self.token))
+                .header("Accept", "application/vnd.github+json")
+                .header("X-GitHub-Api-Version", "2022-11-28")
+                .header("User-Agent", "sr-github")
+                .call()
+                .map_err(|e| http_err("GET", &url, e, &[&self.token]))?;
+            next = resp
+                .headers()
+                .get("link")
+                .and_then(|v| v.to_str().ok())
+                .and_then(next_page_url);
+            let comments: Vec<IssueComment> = resp.into_body().read_json().map_err(|e| {
+                ReleaseError::Vcs(format!("failed to parse comments response: {e}"))
+            })?;
+            if let Some(comment) = comments
+                .iter()
+                .find(|c| c.body.as_deref().is_some_and(|b| b.contains(marker)))
+            {
+                return Ok(Some(comment.id));
+            }
+        }
+        Ok(None)
+    }
+
+    fn delete_asset(&self, id: u64) -> Result<(), ReleaseError> {
+        let url = format!(
+            "{}/repos/{}/{}/releases/assets/{id}",
+            self.api_url(),
+            self.owner,
+            self.repo
+        );
+        self.agent()
+            .delete(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "sr-github")
+            .call()
+            .map_err(|e| http_err("DELETE", &url, e, &[&self.token]))?;
+        Ok(())
     }
 
     fn get_release_by_tag(&self, tag: &str) -> Result<ReleaseResponse, ReleaseError> {
@@ -66,13 +539,94 @@ impl GitHubProvider {
             .header("X-GitHub-Api-Version", "2022-11-28")
             .header("User-Agent", "sr-github")
             .call()
-            .map_err(|e| ReleaseError::Vcs(format!("GitHub API GET {url}: {e}")))?;
+            .map_err(|e| http_err("GET", &url, e, &[&self.token]))?;
         let release: ReleaseResponse = resp
             .into_body()
             .read_json()
             .map_err(|e| ReleaseError::Vcs(format!("failed to parse release response: {e}")))?;
         Ok(release)
     }
+
+    /// `release` by ID when known (drafts are not found by tag), else by tag.
+    fn get_release(&self, release: &RemoteRelease) -> Result<ReleaseResponse, ReleaseError> {
+        let Some(id) = release.id else {
+            return self.get_release_by_tag(&release.tag);
+        };
+        let url = self.release_url(id);
+        let resp = self
+            .agent()
+            .get(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "sr-github")
+            .call()
+            .map_err(|e| http_err("GET", &url, e, &[&self.token]))?;
+        resp.into_body()
+            .read_json()
+            .map_err(|e| ReleaseError::Vcs(format!("failed to parse release response: {e}")))
+    }
+
+    fn release_url(&self, id: u64) -> String {
+        format!(
+            "{}/repos/{}/{}/releases/{id}",
+            self.api_url(),
+            self.owner,
+            self.repo
+        )
+    }
+
+    /// Upload `files` to `release`, replacing assets of the same name.
+    fn upload_to(&self, release: &ReleaseResponse, files: &[&str]) -> Result<(), ReleaseError> {
+        let upload_base = self.upload_base(release);
+
+        for file_path in files {
+            let path = std::path::Path::new(file_path);
+            let file_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or_else(|| ReleaseError::Vcs(format!("invalid file path: {file_path}")))?;
+
+            let data = std::fs::read(path)
+                .map_err(|e| ReleaseError::Vcs(format!("failed to read asset {file_path}: {e}")))?;
+
+            // GitHub rejects duplicate asset names, so re-uploads replace the old asset
+            if let Some(existing) = release.assets.iter().find(|a| a.name == file_name) {
+                info!("Replacing existing asset {file_name}");
+                self.delete_asset(existing.id)?;
+            }
+
+            let content_type = mime_from_extension(file_name);
+            let url = format!("{upload_base}?name={file_name}");
+
+            // Try up to 3 times, retrying only transient failures (no response,
+            // 5xx, rate limiting); a 4xx will not succeed on retry
+            for attempt in 1..=3 {
+                let result = self
+                    .agent()
+                    .post(&url)
+                    .header("Authorization", &format!("Bearer {}", self.token))
+                    .header("Accept", "application/vnd.github+json")
+                    .header("X-GitHub-Api-Version", "2022-11-28")
+                    .header("User-Agent", "sr-github")
+                    .header("Content-Type", content_type)
+                    .send(&data[..]);
+                match result.map_err(|e| http_err("POST", &url, e, &[&self.token])) {
+                    Ok(_) => break,
+                    Err(e) if attempt < 3 && e.is_transient() => {
+                        warn!(
+                            "upload of {file_name} failed ({e}); retrying (attempt {}/3)...",
+                            attempt + 1
+                        );
+                        std::thread::sleep(std::time::Duration::from_secs(1 << attempt));
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl VcsProvider for GitHubProvider {
@@ -83,7 +637,7 @@ impl VcsProvider for GitHubProvider {
         body: &str,
         prerelease: bool,
         draft: bool,
-    ) -> Result<String, ReleaseError> {
+    ) -> Result<RemoteRelease, ReleaseError> {
         let url = format!(
             "{}/repos/{}/{}/releases",
             self.api_url(),
@@ -106,14 +660,14 @@ impl VcsProvider for GitHubProvider {
             .header("X-GitHub-Api-Version", "2022-11-28")
             .header("User-Agent", "sr-github")
             .send_json(&payload)
-            .map_err(|e| ReleaseError::Vcs(format!("GitHub API POST {url}: {e}")))?;
+            .map_err(|e| http_err("POST", &url, e, &[&self.token]))?;
 
         let release: ReleaseResponse = resp
             .into_body()
             .read_json()
             .map_err(|e| ReleaseError::Vcs(format!("failed to parse release response: {e}")))?;
 
-        Ok(release.html_url)
+        Ok(release.remote(tag))
     }
 
     fn compare_url(&self, base: &str, head: &str) -> Result<String, ReleaseError> {
@@ -138,7 +692,7 @@ impl VcsProvider for GitHubProvider {
         {
             Ok(_) => Ok(true),
             Err(ureq::Error::StatusCode(404)) => Ok(false),
-            Err(e) => Err(ReleaseError::Vcs(format!("GitHub API GET {url}: {e}"))),
+            Err(e) => Err(http_err("GET", &url, e, &[&self.token])),
         }
     }
 
@@ -162,7 +716,7 @@ impl VcsProvider for GitHubProvider {
             .header("X-GitHub-Api-Version", "2022-11-28")
             .header("User-Agent", "sr-github")
             .call()
-            .map_err(|e| ReleaseError::Vcs(format!("GitHub API DELETE {url}: {e}")))?;
+            .map_err(|e| http_err("DELETE", &url, e, &[&self.token]))?;
         Ok(())
     }
 
@@ -173,7 +727,7 @@ impl VcsProvider for GitHubProvider {
         body: &str,
         prerelease: bool,
         draft: bool,
-    ) -> Result<String, ReleaseError> {
+    ) -> Result<RemoteRelease, ReleaseError> {
         let release = self.get_release_by_tag(tag)?;
         let url = format!(
             "{}/repos/{}/{}/releases/{}",
@@ -196,12 +750,99 @@ impl VcsProvider for GitHubProvider {
             .header("X-GitHub-Api-Version", "2022-11-28")
             .header("User-Agent", "sr-github")
             .send_json(&payload)
-            .map_err(|e| ReleaseError::Vcs(format!("GitHub API PATCH {url}: {e}")))?;
+            .map_err(|e| http_err("PATCH", &url, e, &[&self.token]))?;
         let updated: ReleaseResponse = resp
             .into_body()
             .read_json()
             .map_err(|e| ReleaseError::Vcs(format!("failed to parse release response: {e}")))?;
-        Ok(updated.html_url)
+        Ok(updated.remote(tag))
+    }
+
+    fn supports_drafts(&self) -> bool {
+        true
+    }
+
+    fn upload_release_assets(
+        &self,
+        release: &RemoteRelease,
+        files: &[&str],
+    ) -> Result<(), ReleaseError> {
+        self.upload_to(&self.get_release(release)?, files)
+    }
+
+    fn release_assets(
+        &self,
+        release: &RemoteRelease,
+    ) -> Result<Option<Vec<RemoteAsset>>, ReleaseError> {
+        let id = match release.id {
+            Some(id) => id,
+            None => self.get_release_by_tag(&release.tag)?.id,
+        };
+        let mut next = Some(format!("{}/assets?per_page=100", self.release_url(id)));
+        let mut assets = Vec::new();
+        while let Some(url) = next {
+            let resp = self
+                .agent()
+                .get(&url)
+                .header("Authorization", &format!("Bearer {}", self.token))
+                .header("Accept", "application/vnd.github+json")
+                .header("X-GitHub-Api-Version", "2022-11-28")
+                .header("User-Agent", "sr-github")
+                .call()
+                .map_err(|e| http_err("GET", &url, e, &[&self.token]))?;
+            next = resp
+                .headers()
+                .get("link")
+                .and_then(|v| v.to_str().ok())
+                .and_then(next_page_url);
+            let page: Vec<ReleaseAsset> = resp.into_body().read_json().map_err(|e| {
+                ReleaseError::Vcs(format!("failed to parse release assets response: {e}"))
+            })?;
+            assets.extend(page.into_iter().map(|a| RemoteAsset {
+                name: a.name,
+                size: a.size,
+                url: a.browser_download_url,
+            }));
+        }
+        Ok(Some(assets))
+    }
+
+    fn publish_release(&self, release: &RemoteRelease) -> Result<RemoteRelease, ReleaseError> {
+        let id = match release.id {
+            Some(id) => id,
+            None => self.get_release_by_tag(&release.tag)?.id,
+        };
+        let url = self.release_url(id);
+        let resp = self
+            .agent()
+            .patch(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "sr-github")
+            .send_json(serde_json::json!({ "draft": false }))
+            .map_err(|e| http_err("PATCH", &url, e, &[&self.token]))?;
+        let published: ReleaseResponse = resp
+            .into_body()
+            .read_json()
+            .map_err(|e| ReleaseError::Vcs(format!("failed to parse release response: {e}")))?;
+        Ok(published.remote(&release.tag))
+    }
+
+    fn discard_release(&self, release: &RemoteRelease) -> Result<(), ReleaseError> {
+        let Some(id) = release.id else {
+            return self.delete_release(&release.tag);
+        };
+        let url = self.release_url(id);
+        self.agent()
+            .delete(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "sr-github")
+            .call()
+            .map_err(|e| http_err("DELETE", &url, e, &[&self.token]))?;
+        Ok(())
     }
 
     fn sync_floating_release(
@@ -217,21 +858,7 @@ impl VcsProvider for GitHubProvider {
             let existing = self.get_release_by_tag(floating_tag)?;
             // Delete existing assets first
             for asset in &existing.assets {
-                let url = format!(
-                    "{}/repos/{}/{}/releases/assets/{}",
-                    self.api_url(),
-                    self.owner,
-                    self.repo,
-                    asset.id
-                );
-                let _ = self
-                    .agent()
-                    .delete(&url)
-                    .header("Authorization", &format!("Bearer {}", self.token))
-                    .header("Accept", "application/vnd.github+json")
-                    .header("X-GitHub-Api-Version", "2022-11-28")
-                    .header("User-Agent", "sr-github")
-                    .call();
+                let _ = self.delete_asset(asset.id);
             }
             // Update the release metadata and ensure it's not marked as latest
             let url = format!(
@@ -241,12 +868,7 @@ impl VcsProvider for GitHubProvider {
                 self.repo,
                 existing.id
             );
-            let payload = serde_json::json!({
-                "tag_name": floating_tag,
-                "name": floating_tag,
-                "body": format!("Points to {versioned_tag}. Use this tag for GitHub Actions."),
-                "make_latest": "false",
-            });
+            let payload = self.floating_payload(floating_tag, versioned_tag);
             self.agent()
                 .patch(&url)
                 .header("Authorization", &format!("Bearer {}", self.token))
@@ -254,9 +876,7 @@ impl VcsProvider for GitHubProvider {
                 .header("X-GitHub-Api-Version", "2022-11-28")
                 .header("User-Agent", "sr-github")
                 .send_json(&payload)
-                .map_err(|e| {
-                    ReleaseError::Vcs(format!("GitHub API PATCH floating release: {e}"))
-                })?;
+                .map_err(|e| http_err("PATCH", &url, e, &[&self.token]))?;
             self.get_release_by_tag(floating_tag)?
         } else {
             let url = format!(
@@ -265,12 +885,7 @@ impl VcsProvider for GitHubProvider {
                 self.owner,
                 self.repo
             );
-            let payload = serde_json::json!({
-                "tag_name": floating_tag,
-                "name": floating_tag,
-                "body": format!("Points to {versioned_tag}. Use this tag for GitHub Actions."),
-                "make_latest": "false",
-            });
+            let payload = self.floating_payload(floating_tag, versioned_tag);
             let resp = self
                 .agent()
                 .post(&url)
@@ -279,7 +894,7 @@ impl VcsProvider for GitHubProvider {
                 .header("X-GitHub-Api-Version", "2022-11-28")
                 .header("User-Agent", "sr-github")
                 .send_json(&payload)
-                .map_err(|e| ReleaseError::Vcs(format!("GitHub API POST floating release: {e}")))?;
+                .map_err(|e| http_err("POST", &url, e, &[&self.token]))?;
             resp.into_body()
                 .read_json()
                 .map_err(|e| ReleaseError::Vcs(format!("failed to parse release response: {e}")))?
@@ -287,11 +902,7 @@ impl VcsProvider for GitHubProvider {
 
         // Copy assets from the versioned release to the floating release
         if !versioned.assets.is_empty() {
-            let upload_base = floating_release
-                .upload_url
-                .split('{')
-                .next()
-                .unwrap_or(&floating_release.upload_url);
+            let upload_base = self.upload_base(&floating_release);
 
             for asset in &versioned.assets {
                 // Download the asset from the versioned release
@@ -302,7 +913,7 @@ impl VcsProvider for GitHubProvider {
                     .header("Accept", "application/octet-stream")
                     .header("User-Agent", "sr-github")
                     .call()
-                    .map_err(|e| ReleaseError::Vcs(format!("download asset {}: {e}", asset.name)))?
+                    .map_err(|e| http_err("GET", &asset.browser_download_url, e, &[&self.token]))?
                     .into_body()
                     .read_to_vec()
                     .map_err(|e| {
@@ -320,13 +931,11 @@ impl VcsProvider for GitHubProvider {
                     .header("User-Agent", "sr-github")
                     .header("Content-Type", content_type)
                     .send(&data[..])
-                    .map_err(|e| {
-                        ReleaseError::Vcs(format!("upload asset {} to floating: {e}", asset.name))
-                    })?;
+                    .map_err(|e| http_err("POST", &url, e, &[&self.token]))?;
             }
         }
 
-        eprintln!(
+        info!(
             "Synced floating release {floating_tag} with {} ({} asset(s))",
             versioned_tag,
             versioned.assets.len()
@@ -335,64 +944,7 @@ impl VcsProvider for GitHubProvider {
     }
 
     fn upload_assets(&self, tag: &str, files: &[&str]) -> Result<(), ReleaseError> {
-        let release = self.get_release_by_tag(tag)?;
-        // The upload_url from the API looks like:
-        //   https://uploads.github.com/repos/owner/repo/releases/123/assets{?name,label}
-        // Strip the {?name,label} template suffix.
-        let upload_base = release
-            .upload_url
-            .split('{')
-            .next()
-            .unwrap_or(&release.upload_url);
-
-        for file_path in files {
-            let path = std::path::Path::new(file_path);
-            let file_name = path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .ok_or_else(|| ReleaseError::Vcs(format!("invalid file path: {file_path}")))?;
-
-            let data = std::fs::read(path)
-                .map_err(|e| ReleaseError::Vcs(format!("failed to read asset {file_path}: {e}")))?;
-
-            let content_type = mime_from_extension(file_name);
-            let url = format!("{upload_base}?name={file_name}");
-
-            // Retry up to 3 times for transient upload failures
-            let mut last_err = None;
-            for attempt in 0..3 {
-                if attempt > 0 {
-                    std::thread::sleep(std::time::Duration::from_secs(1 << attempt));
-                    eprintln!(
-                        "Retrying upload of {file_name} (attempt {}/3)...",
-                        attempt + 1
-                    );
-                }
-                match self
-                    .agent()
-                    .post(&url)
-                    .header("Authorization", &format!("Bearer {}", self.token))
-                    .header("Accept", "application/vnd.github+json")
-                    .header("X-GitHub-Api-Version", "2022-11-28")
-                    .header("User-Agent", "sr-github")
-                    .header("Content-Type", content_type)
-                    .send(&data[..])
-                {
-                    Ok(_) => {
-                        last_err = None;
-                        break;
-                    }
-                    Err(e) => {
-                        last_err = Some(format!("GitHub API upload asset {file_name}: {e}"));
-                    }
-                }
-            }
-            if let Some(err_msg) = last_err {
-                return Err(ReleaseError::Vcs(err_msg));
-            }
-        }
-
-        Ok(())
+        self.upload_to(&self.get_release_by_tag(tag)?, files)
     }
 
     fn verify_release(&self, tag: &str) -> Result<(), ReleaseError> {
@@ -400,6 +952,65 @@ impl VcsProvider for GitHubProvider {
         self.get_release_by_tag(tag)?;
         Ok(())
     }
+
+    fn can_push(&self) -> Result<Option<bool>, ReleaseError> {
+        // Installation tokens (e.g. GITHUB_TOKEN in Actions) get no `permissions` block
+        Ok(self.repository()?.permissions.as_ref().map(|p| p.push))
+    }
+
+    fn default_branch(&self) -> Result<Option<String>, ReleaseError> {
+        Ok(self.repository()?.default_branch.clone())
+    }
+}
+
+impl GitHubProvider {
+    /// `GET /repos/{owner}/{repo}`, fetched once and kept for later calls.
+    fn repository(&self) -> Result<&RepoResponse, ReleaseError> {
+        if let Some(repo) = self.repository.get() {
+            return Ok(repo);
+        }
+        let url = format!("{}/repos/{}/{}", self.api_url(), self.owner, self.repo);
+        let resp = self
+            .agent()
+            .get(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "sr-github")
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::StatusCode(401) => {
+                    ReleaseError::Vcs("token rejected by GitHub (invalid or expired)".into())
+                }
+                ureq::Error::StatusCode(404) => ReleaseError::Vcs(format!(
+                    "{}/{} not found or not visible to the token",
+                    self.owner, self.repo
+                )),
+                e => http_err("GET", &url, e, &[&self.token]),
+            })?;
+        let repo: RepoResponse = resp
+            .into_body()
+            .read_json()
+            .map_err(|e| ReleaseError::Vcs(format!("failed to parse repository response: {e}")))?;
+        Ok(self.repository.get_or_init(|| repo))
+    }
+}
+
+/// The `rel="next"` target of a GitHub `Link` pagination header.
+fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == r#"rel="next""#)
+            .then(|| {
+                target
+                    .trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
 }
 
 /// Map file extension to MIME type for GitHub asset uploads.
@@ -446,6 +1057,316 @@ mod tests {
         )
     }
 
+    /// A request the mock server received: method, path with query, body.
+    type Recorded = (String, String, String);
+
+    /// Serve the responses (status, extra headers, body) built from the
+    /// server's base URL in order, one per connection, on a loopback port.
+    /// Returns the base URL and a handle yielding the recorded requests.
+    fn mock_api(
+        responses: impl FnOnce(&str) -> Vec<(u16, Vec<String>, String)>,
+    ) -> (String, std::thread::JoinHandle<Vec<Recorded>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let responses = responses(&base);
+        let handle = std::thread::spawn(move || {
+            let mut recorded = Vec::new();
+            for (status, headers, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+                let mut parts = request_line.split_whitespace();
+                recorded.push((
+                    parts.next().unwrap().to_string(),
+                    parts.next().unwrap().to_string(),
+                    String::from_utf8(request_body).unwrap(),
+                ));
+
+                let mut response = format!(
+                    "HTTP/1.1 {status} OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n",
+                    body.len()
+                );
+                for header in headers {
+                    response.push_str(&format!("{header}\r\n"));
+                }
+                response.push_str(&format!("\r\n{body}"));
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            recorded
+        });
+        (base, handle)
+    }
+
+    const MARKER: &str = "<!-- sr:release-preview -->";
+
+    #[test]
+    fn upsert_issue_comment_edits_the_marked_comment_on_a_later_page() {
+        let (base, server) = mock_api(|base| {
+            let next = format!(
+                "Link: <{base}/repos/urmzd/sr/issues/7/comments?per_page=100&page=2>; \
+                 rel=\"next\", <{base}/repos/urmzd/sr/issues/7/comments?page=2>; rel=\"last\""
+            );
+            vec![
+                (
+                    200,
+                    vec![next],
+                    r#"[{"id":1,"html_url":"https://x/1","body":"lgtm"}]"#.into(),
+                ),
+                (
+                    200,
+                    vec![],
+                    format!(
+                        r#"[{{"id":2,"html_url":"https://x/2","body":null}},
+                            {{"id":3,"html_url":"https://x/3","body":"{MARKER}\nold"}}]"#
+                    ),
+                ),
+                (200, vec![], r#"{"id":3,"html_url":"https://x/3"}"#.into()),
+            ]
+        });
+        let provider = github_com_provider().with_api_url(base);
+
+        let url = provider
+            .upsert_issue_comment(7, MARKER, "new plan")
+            .unwrap();
+        assert_eq!(url, "https://x/3");
+
+        let requests = server.join().unwrap();
+        let calls: Vec<_> = requests
+            .iter()
+            .map(|(method, path, _)| format!("{method} {path}"))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                "GET /repos/urmzd/sr/issues/7/comments?per_page=100",
+                "GET /repos/urmzd/sr/issues/7/comments?per_page=100&page=2",
+                "PATCH /repos/urmzd/sr/issues/comments/3",
+            ]
+        );
+        let sent: serde_json::Value = serde_json::from_str(&requests[2].2).unwrap();
+        assert_eq!(sent["body"], format!("{MARKER}\nnew plan"));
+    }
+
+    #[test]
+    fn upsert_issue_comment_posts_when_no_comment_is_marked() {
+        let (base, server) = mock_api(|_| {
+            vec![
+                (
+                    200,
+                    vec![],
+                    r#"[{"id":1,"html_url":"https://x/1","body":"lgtm"}]"#.into(),
+                ),
+                (201, vec![], r#"{"id":9,"html_url":"https://x/9"}"#.into()),
+            ]
+        });
+        let provider = github_com_provider().with_api_url(base);
+
+        let body = format!("{MARKER}\n### Release preview");
+        let url = provider.upsert_issue_comment(7, MARKER, &body).unwrap();
+        assert_eq!(url, "https://x/9");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[1].0, "POST");
+        assert_eq!(requests[1].1, "/repos/urmzd/sr/issues/7/comments");
+        let sent: serde_json::Value = serde_json::from_str(&requests[1].2).unwrap();
+        assert_eq!(sent["body"], body);
+    }
+
+    #[test]
+    fn draft_releases_are_addressed_by_id_until_published() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset = dir.path().join("app.tar.gz");
+        std::fs::write(&asset, "tarball").unwrap();
+        let (base, server) = mock_api(|base| {
+            let draft = format!(
+                r#"{{"id":5,"html_url":"https://x/untagged-1","upload_url":"{base}/uploads/5/assets{{?name,label}}"}}"#
+            );
+            vec![
+                (201, vec![], draft.clone()),
+                (200, vec![], draft),
+                (201, vec![], "{}".into()),
+                (
+                    200,
+                    vec![],
+                    r#"[{"id":9,"name":"app.tar.gz","size":7,"browser_download_url":"https://x/a"}]"#
+                        .into(),
+                ),
+                (
+                    200,
+                    vec![],
+                    r#"{"id":5,"html_url":"https://x/v1.0.0","upload_url":"https://x/u"}"#.into(),
+                ),
+            ]
+        });
+        let provider = github_com_provider().with_api_url(base);
+
+        let release = provider
+            .create_release("v1.0.0", "v1.0.0", "notes", false, true)
+            .unwrap();
+        assert_eq!(release.id, Some(5));
+        provider
+            .upload_release_assets(&release, &[asset.to_str().unwrap()])
+            .unwrap();
+        let assets = provider.release_assets(&release).unwrap().unwrap();
+        assert_eq!(
+            assets,
+            [RemoteAsset {
+                name: "app.tar.gz".into(),
+                size: 7,
+                url: "https://x/a".into(),
+            }]
+        );
+        let published = provider.publish_release(&release).unwrap();
+        assert_eq!(published.url, "https://x/v1.0.0");
+
+        let requests = server.join().unwrap();
+        let calls: Vec<_> = requests
+            .iter()
+            .map(|(method, path, _)| format!("{method} {path}"))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                "POST /repos/urmzd/sr/releases",
+                "GET /repos/urmzd/sr/releases/5",
+                "POST /uploads/5/assets?name=app.tar.gz",
+                "GET /repos/urmzd/sr/releases/5/assets?per_page=100",
+                "PATCH /repos/urmzd/sr/releases/5",
+            ]
+        );
+        let created: serde_json::Value = serde_json::from_str(&requests[0].2).unwrap();
+        assert_eq!(created["draft"], true);
+        let sent: serde_json::Value = serde_json::from_str(&requests[4].2).unwrap();
+        assert_eq!(sent, serde_json::json!({ "draft": false }));
+    }
+
+    #[test]
+    fn next_page_url_reads_the_link_header() {
+        let link = r#"<https://api.github.com/x?page=2>; rel="next", <https://api.github.com/x?page=5>; rel="last""#;
+        assert_eq!(
+            next_page_url(link).as_deref(),
+            Some("https://api.github.com/x?page=2")
+        );
+        assert_eq!(next_page_url(r#"<https://a/x?page=1>; rel="prev""#), None);
+    }
+
+    #[test]
+    fn resolve_api_url_reads_the_actions_env_for_its_host() {
+        let config = GitHubConfig::default();
+        let api = Some("https://api.ghes.example.com");
+        let server = Some("https://ghes.example.com");
+        assert_eq!(
+            resolve_api_url(&config, "ghes.example.com", "ghes.example.com", api, server)
+                .as_deref(),
+            Some("https://api.ghes.example.com")
+        );
+        // Another provider's host keeps its derived URL
+        assert_eq!(
+            resolve_api_url(&config, "github.com", "ghes.example.com", api, server),
+            None
+        );
+        // Without GITHUB_SERVER_URL the env var belongs to the origin host
+        assert_eq!(
+            resolve_api_url(&config, "ghes.example.com", "ghes.example.com", api, None).as_deref(),
+            Some("https://api.ghes.example.com")
+        );
+        assert_eq!(
+            resolve_api_url(&config, "github.com", "ghes.example.com", api, None),
+            None
+        );
+        assert_eq!(
+            resolve_api_url(&config, "github.com", "github.com", None, None),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_api_url_config_takes_precedence_over_the_env() {
+        let config = GitHubConfig {
+            api_url: Some("https://proxy.example.com/github".into()),
+            ..Default::default()
+        };
+        let api = Some("https://api.ghes.example.com");
+        let server = Some("https://ghes.example.com");
+        assert_eq!(
+            resolve_api_url(&config, "ghes.example.com", "ghes.example.com", api, server)
+                .as_deref(),
+            Some("https://proxy.example.com/github")
+        );
+        assert_eq!(
+            resolve_api_url(&config, "ghes.example.com", "ghes.example.com", None, None).as_deref(),
+            Some("https://proxy.example.com/github")
+        );
+        // The override is for the origin host only
+        assert_eq!(
+            resolve_api_url(&config, "github.com", "ghes.example.com", None, None),
+            None
+        );
+    }
+
+    #[test]
+    fn server_url_host_keeps_the_port() {
+        assert_eq!(
+            server_url_host("https://ghes.example.com/").as_deref(),
+            Some("ghes.example.com")
+        );
+        assert_eq!(
+            server_url_host("http://localhost:8080").as_deref(),
+            Some("localhost:8080")
+        );
+        assert_eq!(server_url_host("https://"), None);
+    }
+
+    #[test]
+    fn plain_http_is_only_allowed_on_loopback() {
+        let err = github_com_provider()
+            .with_api_url("http://example.com".into())
+            .upsert_issue_comment(1, MARKER, "x")
+            .unwrap_err();
+        assert!(
+            matches!(err, ReleaseError::Http { status: None, .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn http_errors_redact_the_token() {
+        let url = "https://api.github.com/repos/o/r?access_token=test-token";
+        let err = http_err("GET", url, ureq::Error::BadUri(url.into()), &["test-token"]);
+        let message = err.to_string();
+        assert!(!message.contains("test-token"), "{message}");
+        assert!(message.contains("access_token=***"), "{message}");
+
+        let err = http_err("GET", url, ureq::Error::StatusCode(401), &["test-token"]);
+        assert!(matches!(
+            err,
+            ReleaseError::Http {
+                status: Some(401),
+                ..
+            }
+        ));
+        assert!(!err.to_string().contains("Bearer"), "{err}");
+    }
+
     #[test]
     fn test_api_url_github_com() {
         assert_eq!(github_com_provider().api_url(), "https://api.github.com");
@@ -456,6 +1377,96 @@ mod tests {
         assert_eq!(ghes_provider().api_url(), "https://ghes.example.com/api/v3");
     }
 
+    #[test]
+    fn upload_url_override_keeps_the_release_path() {
+        let release = ReleaseResponse {
+            id: 5,
+            html_url: String::new(),
+            upload_url: "https://uploads.github.com/repos/o/r/releases/5/assets{?name,label}"
+                .into(),
+            assets: vec![],
+        };
+        assert_eq!(
+            github_com_provider().upload_base(&release),
+            "https://uploads.github.com/repos/o/r/releases/5/assets"
+        );
+        assert_eq!(
+            github_com_provider()
+                .with_upload_url("https://ghes.example.com/api/uploads/".into())
+                .upload_base(&release),
+            "https://ghes.example.com/api/uploads/repos/o/r/releases/5/assets"
+        );
+    }
+
+    #[test]
+    fn repository_is_fetched_once_for_default_branch_and_push_access() {
+        let (base, server) = mock_api(|_| {
+            vec![(
+                200,
+                vec![],
+                r#"{"default_branch":"trunk","permissions":{"push":true}}"#.into(),
+            )]
+        });
+        let provider = github_com_provider().with_api_url(base);
+
+        assert_eq!(provider.default_branch().unwrap().as_deref(), Some("trunk"));
+        assert_eq!(provider.can_push().unwrap(), Some(true));
+        assert_eq!(provider.default_branch().unwrap().as_deref(), Some("trunk"));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1, "fetched once");
+        assert_eq!(requests[0].1, "/repos/urmzd/sr");
+    }
+
+    #[test]
+    fn ghes_versions_map_to_capabilities() {
+        assert!(!Capabilities::for_ghes_version("3.7.12").make_latest);
+        assert!(Capabilities::for_ghes_version("3.8.0").make_latest);
+        assert!(Capabilities::for_ghes_version("4.0").make_latest);
+        let unknown = Capabilities::for_ghes_version("next");
+        assert!(unknown.make_latest);
+        assert_eq!(unknown.version.as_deref(), Some("next"));
+    }
+
+    #[test]
+    fn old_ghes_floating_release_omits_make_latest() {
+        let (base, server) = mock_api(|_| {
+            vec![(
+                200,
+                vec![],
+                r#"{"verifiable_password_authentication":false,"installed_version":"3.7.0"}"#
+                    .into(),
+            )]
+        });
+        let provider = ghes_provider().with_api_url(base);
+
+        let payload = provider.floating_payload("v1", "v1.2.0");
+        assert!(payload.get("make_latest").is_none(), "{payload}");
+        assert_eq!(provider.capabilities().version.as_deref(), Some("3.7.0"));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1, "probed once");
+        assert_eq!(requests[0].1, "/meta");
+    }
+
+    #[test]
+    fn injected_capabilities_skip_the_probe() {
+        // Nothing listens on this port: a probe would fail and assume all.
+        let provider = ghes_provider()
+            .with_api_url("http://127.0.0.1:9".into())
+            .with_capabilities(Capabilities::for_ghes_version("3.6.0"));
+        assert!(!provider.capabilities().make_latest);
+
+        let payload = github_com_provider().floating_payload("v1", "v1.2.0");
+        assert_eq!(payload["make_latest"], "false");
+    }
+
+    #[test]
+    fn test_api_url_override() {
+        let provider = ghes_provider().with_api_url("https://api.ghes.example.com/v3/".into());
+        assert_eq!(provider.api_url(), "https://api.ghes.example.com/v3");
+    }
+
     #[test]
     fn test_base_url() {
         assert_eq!(
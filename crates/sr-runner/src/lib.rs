@@ -0,0 +1,377 @@
+//! Run sr releases from Rust without shelling out to the CLI.
+//!
+//! [`Release::builder`] wires a repository, a config, and the remote release
+//! providers into a [`Strategy`] exactly the way the `sr` binary does:
+//!
+//! ```no_run
+//! use sr_core::config::ReleaseConfig;
+//! use sr_runner::Release;
+//!
+//! # fn main() -> Result<(), sr_core::error::ReleaseError> {
+//! let release = Release::builder()
+//!     .repo_path("path/to/repo")
+//!     .config(ReleaseConfig::load("path/to/repo/sr.yaml".as_ref())?)
+//!     .github_token(std::env::var("GH_TOKEN").unwrap_or_default())
+//!     .dry_run(true)
+//!     .build()?;
+//! let plan = release.plan()?;
+//! release.execute(&plan)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! File paths in the config (version files, changelog, artifacts) are relative
+//! to the current directory; like the CLI, anchor version files, changelogs and
+//! archive contents at the config file's directory with
+//! [`ReleaseConfig::resolve_paths_relative_to`].
+
+use std::path::PathBuf;
+
+use sr_core::changelog::DefaultChangelogFormatter;
+use sr_core::commit::DefaultCommitParser;
+use sr_core::config::{ProviderMode, ReleaseConfig};
+use sr_core::dry_run::DryRunReport;
+use sr_core::error::ReleaseError;
+use sr_core::release::{
+    ExecuteOptions, ReleasePlan, ReleaseStrategy, TrunkReleaseStrategy, VcsProvider,
+};
+use sr_core::report::ReleaseReport;
+use sr_git::{AnyGitRepository, GitBackend};
+use sr_github::GitHubProvider;
+use tracing::warn;
+
+/// The release strategy the builder assembles: runtime-selected git backend,
+/// the default commit parser and changelog formatter, boxed providers.
+pub type Strategy =
+    TrunkReleaseStrategy<AnyGitRepository, DefaultCommitParser, DefaultChangelogFormatter>;
+
+/// Remote release targets handed to the strategy.
+pub type Providers = Vec<Box<dyn VcsProvider>>;
+
+/// A ready-to-run release for one repository and config.
+pub struct Release {
+    strategy: Strategy,
+    dry_run: bool,
+}
+
+impl Release {
+    pub fn builder() -> ReleaseBuilder {
+        ReleaseBuilder::default()
+    }
+
+    /// Compute the next release without changing anything.
+    pub fn plan(&self) -> Result<ReleasePlan, ReleaseError> {
+        self.strategy.plan()
+    }
+
+    /// Carry out `plan`, or only log what would happen when built with `dry_run(true)`.
+    pub fn execute(&self, plan: &ReleasePlan) -> Result<(), ReleaseError> {
+        self.strategy.execute(plan, self.dry_run)
+    }
+
+    /// Carry out `plan` for real, whatever `dry_run` says, and return a record
+    /// of each step (see `summary` in the config).
+    pub fn execute_report(&self, plan: &ReleasePlan) -> Result<ReleaseReport, ReleaseError> {
+        self.strategy.execute_report(plan)
+    }
+
+    /// Structured description of what `execute` would do for `plan`.
+    pub fn dry_run_report(&self, plan: &ReleasePlan) -> Result<DryRunReport, ReleaseError> {
+        self.strategy.dry_run_report(plan)
+    }
+
+    /// The underlying strategy, for notes, rollbacks, and changelog entries.
+    pub fn strategy(&self) -> &Strategy {
+        &self.strategy
+    }
+
+    /// Adjust pre-flight switches (`allow_dirty`, `options`, ...) before executing.
+    pub fn strategy_mut(&mut self) -> &mut Strategy {
+        &mut self.strategy
+    }
+
+    pub fn into_strategy(self) -> Strategy {
+        self.strategy
+    }
+}
+
+/// Builder for [`Release`]. Everything is optional: the defaults release the
+/// repository in the current directory with a default config.
+#[derive(Clone, Default)]
+pub struct ReleaseBuilder {
+    repo_path: Option<PathBuf>,
+    config: ReleaseConfig,
+    github_token: Option<String>,
+    git_backend: Option<GitBackend>,
+    dry_run: bool,
+    force: bool,
+    remote: Option<bool>,
+    changed_paths: bool,
+}
+
+impl ReleaseBuilder {
+    /// Repository to release. Default: the current directory.
+    pub fn repo_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.repo_path = Some(path.into());
+        self
+    }
+
+    pub fn config(mut self, config: ReleaseConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Token for providers without a `token_env`, and for pushing to origin.
+    /// Default: `GH_TOKEN`, then `GITHUB_TOKEN`.
+    pub fn github_token(mut self, token: impl Into<String>) -> Self {
+        self.github_token = Some(token.into()).filter(|t| !t.is_empty());
+        self
+    }
+
+    /// Git implementation to use. Default: native `git` when it is on `PATH`.
+    pub fn git_backend(mut self, backend: GitBackend) -> Self {
+        self.git_backend = Some(backend);
+        self
+    }
+
+    /// Make [`Release::execute`] a dry run. Providers that cannot be set up
+    /// (e.g. no token) are then skipped with a warning instead of failing the build.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Re-release the current tag (`sr release --force`).
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Whether to set up remote release providers. Default: unless the config
+    /// says `provider: none`. Without providers a release only tags and pushes.
+    pub fn remote(mut self, remote: bool) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
+    /// Read the files each commit changed, so plans report
+    /// [`touched_paths`](ReleasePlan::touched_paths). Slower on long histories.
+    pub fn changed_paths(mut self, changed_paths: bool) -> Self {
+        self.changed_paths = changed_paths;
+        self
+    }
+
+    pub fn build(self) -> Result<Release, ReleaseError> {
+        let config = self.config;
+        let repo_path = self.repo_path.unwrap_or_else(|| PathBuf::from("."));
+        let mut git = AnyGitRepository::open(&repo_path, self.git_backend)?
+            .with_signing(config.effective_signing())
+            .with_tags(config.tags.clone())
+            .with_identity(config.git.clone())
+            .with_max_commits(config.max_commits)
+            .with_changed_paths(self.changed_paths)
+            .with_lenient_tags(config.lenient_tags)
+            .with_timeout(config.git.timeout());
+
+        let mut vcs = Vec::new();
+        if self.remote.unwrap_or(config.provider != ProviderMode::None) {
+            match connect(&config, &git, self.github_token.as_deref()) {
+                Ok((providers, push_auth)) => {
+                    vcs = providers;
+                    if let Some((hostname, token)) = push_auth {
+                        git = git.with_http_auth(hostname, token);
+                    }
+                }
+                Err(e) if self.dry_run => warn!("{e} (continuing dry-run without GitHub)"),
+                Err(e) => return Err(e),
+            }
+        }
+
+        let formatter = config.changelog_formatter()?;
+        let strategy = TrunkReleaseStrategy {
+            git,
+            vcs,
+            parser: DefaultCommitParser::new(&config.commit_pattern)?,
+            formatter,
+            config,
+            force: self.force,
+            allow_any_branch: false,
+            allow_dirty: false,
+            ignore_head_drift: false,
+            offline: sr_core::offline::is_offline(),
+            options: ExecuteOptions::default(),
+            target_ref: None,
+            override_policy: false,
+            download: Some(Box::new(sr_github::download)),
+            push_branch: None,
+        };
+        Ok(Release {
+            strategy,
+            dry_run: self.dry_run,
+        })
+    }
+}
+
+/// Build the providers for `config`, plus the (hostname, token) to
+/// authenticate pushes to origin with.
+fn connect(
+    config: &ReleaseConfig,
+    git: &AnyGitRepository,
+    token: Option<&str>,
+) -> Result<(Providers, Option<(String, String)>), ReleaseError> {
+    if sr_core::offline::is_offline() {
+        return Err(ReleaseError::Vcs(
+            "offline: not contacting the remote provider".into(),
+        ));
+    }
+    let origin = resolve_origin(config, git)?;
+    let (vcs, push_token) = build_providers(config, &origin, token)?;
+    Ok((vcs, push_token.map(|token| (origin.0, token))))
+}
+
+/// Read the default API token from `GH_TOKEN`, falling back to `GITHUB_TOKEN`.
+pub fn default_token() -> Option<String> {
+    std::env::var("GH_TOKEN")
+        .or_else(|_| std::env::var("GITHUB_TOKEN"))
+        .ok()
+}
+
+/// Resolve (hostname, owner, repo) from config overrides, the `origin` remote,
+/// or the GitHub Actions environment, in that order.
+pub fn resolve_origin(
+    config: &ReleaseConfig,
+    git: &AnyGitRepository,
+) -> Result<(String, String, String), ReleaseError> {
+    sr_core::release::resolve_origin(
+        config,
+        git.parse_remote_full(),
+        std::env::var("GITHUB_REPOSITORY").ok().as_deref(),
+        std::env::var("GITHUB_SERVER_URL")
+            .ok()
+            .as_deref()
+            .and_then(sr_github::server_url_host)
+            .as_deref(),
+    )
+}
+
+/// A provider for `hostname`, with the API and upload URLs from `github` in
+/// the config (for the origin host) or `GITHUB_API_URL`.
+fn github_provider(
+    config: &ReleaseConfig,
+    origin_host: &str,
+    owner: String,
+    repo: String,
+    hostname: String,
+    token: String,
+) -> GitHubProvider {
+    let api_url = sr_github::resolve_api_url(
+        &config.github,
+        &hostname,
+        origin_host,
+        std::env::var("GITHUB_API_URL").ok().as_deref(),
+        std::env::var("GITHUB_SERVER_URL").ok().as_deref(),
+    );
+    let upload_url = config
+        .github
+        .upload_url
+        .clone()
+        .filter(|_| hostname == origin_host);
+    let mut provider = GitHubProvider::new(owner, repo, hostname, token);
+    if let Some(url) = api_url {
+        provider = provider.with_api_url(url);
+    }
+    if let Some(url) = upload_url {
+        provider = provider.with_upload_url(url);
+    }
+    provider
+}
+
+/// A GitHub API client for the origin repository, for calls outside a release
+/// such as commenting on pull requests. Uses the `token_env` of the provider
+/// configured for the origin host, else `token`, else [`default_token`].
+pub fn github_client(
+    config: &ReleaseConfig,
+    git: &AnyGitRepository,
+    token: Option<&str>,
+) -> Result<GitHubProvider, ReleaseError> {
+    let (hostname, owner, repo) = resolve_origin(config, git)?;
+    let configured = config.providers.iter().find(|p| p.hostname == hostname);
+    let token = match configured.and_then(|p| p.token_env.as_deref()) {
+        Some(var) => std::env::var(var).map_err(|_| {
+            ReleaseError::Vcs(format!(
+                "{var} is not set (token_env for provider {hostname})"
+            ))
+        })?,
+        None => token
+            .map(String::from)
+            .or_else(default_token)
+            .ok_or_else(|| ReleaseError::Vcs("neither GH_TOKEN nor GITHUB_TOKEN is set".into()))?,
+    };
+    Ok(github_provider(
+        config,
+        &hostname,
+        configured.and_then(|p| p.owner.clone()).unwrap_or(owner),
+        configured.and_then(|p| p.repo.clone()).unwrap_or(repo),
+        hostname.clone(),
+        token,
+    ))
+}
+
+/// Construct one `VcsProvider` per configured provider. When none are configured,
+/// a single GitHub provider is derived from the `origin` remote. `token` is used
+/// for providers without a `token_env`; default: [`default_token`].
+///
+/// Returns the providers and the token to use for authenticating git pushes to `origin`.
+pub fn build_providers(
+    config: &ReleaseConfig,
+    origin: &(String, String, String),
+    token: Option<&str>,
+) -> Result<(Providers, Option<String>), ReleaseError> {
+    let (hostname, owner, repo) = origin;
+    let default_token = token.map(String::from).or_else(default_token);
+
+    if config.providers.is_empty() {
+        let token = default_token
+            .ok_or_else(|| ReleaseError::Vcs("neither GH_TOKEN nor GITHUB_TOKEN is set".into()))?;
+        let vcs = github_provider(
+            config,
+            hostname,
+            owner.clone(),
+            repo.clone(),
+            hostname.clone(),
+            token.clone(),
+        );
+        return Ok((vec![Box::new(vcs)], Some(token)));
+    }
+
+    let mut providers: Providers = Vec::new();
+    let mut push_token = None;
+    for p in &config.providers {
+        let token = match &p.token_env {
+            Some(var) => std::env::var(var).map_err(|_| {
+                ReleaseError::Vcs(format!(
+                    "{var} is not set (token_env for provider {})",
+                    p.hostname
+                ))
+            })?,
+            None => default_token.clone().ok_or_else(|| {
+                ReleaseError::Vcs(format!(
+                    "neither GH_TOKEN nor GITHUB_TOKEN is set (provider {})",
+                    p.hostname
+                ))
+            })?,
+        };
+        if push_token.is_none() && &p.hostname == hostname {
+            push_token = Some(token.clone());
+        }
+        providers.push(Box::new(github_provider(
+            config,
+            hostname,
+            p.owner.clone().unwrap_or_else(|| owner.clone()),
+            p.repo.clone().unwrap_or_else(|| repo.clone()),
+            p.hostname.clone(),
+            token,
+        )));
+    }
+    Ok((providers, push_token.or(default_token)))
+}
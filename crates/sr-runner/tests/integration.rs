@@ -0,0 +1,120 @@
+//! End-to-end runs of the `Release` facade against throwaway repositories.
+
+use std::path::Path;
+use std::process::Command;
+
+use sr_core::config::{ProviderConfig, ProviderMode, ReleaseConfig};
+use sr_core::error::ReleaseError;
+use sr_runner::Release;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&out.stderr)
+    );
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+/// A repository with one `feat` commit on `main`, pushed to a bare `origin`.
+fn repo_with_origin() -> (TempDir, TempDir) {
+    let origin = TempDir::new().unwrap();
+    git(origin.path(), &["init", "--bare", "-b", "main"]);
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    git(path, &["init", "-b", "main"]);
+    git(path, &["config", "user.email", "test@test.com"]);
+    git(path, &["config", "user.name", "Test"]);
+    git(path, &["commit", "--allow-empty", "-m", "feat: initial"]);
+    git(
+        path,
+        &["remote", "add", "origin", origin.path().to_str().unwrap()],
+    );
+    git(path, &["push", "-u", "origin", "main"]);
+    (dir, origin)
+}
+
+fn tag_only_config() -> ReleaseConfig {
+    ReleaseConfig {
+        provider: ProviderMode::None,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn plans_and_releases_a_repository() {
+    let (dir, origin) = repo_with_origin();
+
+    let dry = Release::builder()
+        .repo_path(dir.path())
+        .config(tag_only_config())
+        .dry_run(true)
+        .build()
+        .unwrap();
+    let plan = dry.plan().unwrap();
+    assert_eq!(plan.tag_name, "v0.1.0");
+    assert_eq!(plan.commits.len(), 1);
+    dry.execute(&plan).unwrap();
+    assert_eq!(git(dir.path(), &["tag", "--list"]), "");
+
+    let release = Release::builder()
+        .repo_path(dir.path())
+        .config(tag_only_config())
+        .build()
+        .unwrap();
+    let plan = release.plan().unwrap();
+    release.execute(&plan).unwrap();
+    assert_eq!(git(dir.path(), &["tag", "--list"]), "v0.1.0");
+    assert_eq!(git(origin.path(), &["tag", "--list"]), "v0.1.0");
+
+    let err = release.plan().unwrap_err();
+    assert!(matches!(err, ReleaseError::NoCommits { .. }), "{err}");
+}
+
+#[test]
+fn dry_run_continues_without_a_provider() {
+    let (dir, _origin) = repo_with_origin();
+    let config = ReleaseConfig {
+        repository: Some("acme/app".into()),
+        providers: vec![ProviderConfig {
+            hostname: "github.com".into(),
+            owner: None,
+            repo: None,
+            token_env: Some("SR_RUNNER_TEST_UNSET_TOKEN".into()),
+        }],
+        ..Default::default()
+    };
+
+    let release = Release::builder()
+        .repo_path(dir.path())
+        .config(config.clone())
+        .dry_run(true)
+        .build()
+        .unwrap();
+    assert!(release.strategy().vcs.is_empty());
+    let plan = release.plan().unwrap();
+    let report = release.dry_run_report(&plan).unwrap();
+    assert!(report.release.is_none());
+
+    let err = Release::builder()
+        .repo_path(dir.path())
+        .config(config)
+        .build()
+        .err()
+        .expect("a real release needs its provider token");
+    assert!(matches!(err, ReleaseError::Vcs(_)), "{err}");
+    assert!(
+        err.to_string()
+            .contains("SR_RUNNER_TEST_UNSET_TOKEN is not set"),
+        "{err}"
+    );
+}